@@ -0,0 +1,395 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::gmp::GmpBackend;
+use super::{gvm_backends, nmap, openvas};
+use crate::config;
+use crate::models::nmap_scan::{ScanRequest, ScanResult};
+use crate::models::openvas::{
+    AlertSpec, AttachedCredential, CleanupReport, ConfigChanges, ConfigList, CreatedObject,
+    CredentialKind, CredentialList, CredentialSpec, DeletedObject, EmptiedTrashcan, FeedList,
+    FeedType, HostAssetList, ImportedReport, ModifiedConfig, ModifiedTask, NvtDetails, PortListList,
+    Report, ReportFilter, ReportFormat, ReportList, RestoredObject, ResultPage, ResumedTask,
+    ScannerList, ScheduleList, ScheduleSpec, StartedTask, StoppedTask, SyncedFeed, TargetList,
+    TaskChanges, TaskList, TaskStatus, Ticket, TicketChanges, TicketList, TrashList, Version,
+};
+
+/// What the nmap tools need from the scan backend.
+#[async_trait]
+pub trait NmapBackend: Send + Sync {
+    async fn advanced_scan(&self, request: &ScanRequest) -> Result<ScanResult>;
+}
+
+/// What the OpenVAS tools need from the scan backend. Mirrors the functions
+/// in `api::openvas`; see there for the Go endpoints behind each, and
+/// `api::gmp` for the GMP commands.
+#[async_trait]
+pub trait OpenvasBackend: Send + Sync {
+    async fn get_version(&self) -> Result<Version>;
+    async fn list_configs(&self) -> Result<ConfigList>;
+    async fn clone_config(&self, config_id: &str, name: &str, comment: Option<&str>) -> Result<CreatedObject>;
+    async fn modify_config(&self, config_id: &str, changes: &ConfigChanges) -> Result<ModifiedConfig>;
+    async fn get_nvt(&self, oid: &str) -> Result<NvtDetails>;
+    async fn list_feeds(&self) -> Result<FeedList>;
+    async fn sync_feed(&self, feed: FeedType) -> Result<SyncedFeed>;
+    async fn list_host_assets(&self) -> Result<HostAssetList>;
+    async fn create_ticket(&self, result_id: &str, assignee: &str, note: &str) -> Result<CreatedObject>;
+    async fn list_tickets(&self) -> Result<TicketList>;
+    async fn modify_ticket(&self, ticket_id: &str, changes: &TicketChanges) -> Result<Ticket>;
+    async fn list_scanners(&self) -> Result<ScannerList>;
+    async fn create_target(
+        &self,
+        name: &str,
+        hosts: &str,
+        port_range: Option<&str>,
+        port_list_id: Option<&str>,
+    ) -> Result<CreatedObject>;
+    async fn list_targets(&self) -> Result<TargetList>;
+    async fn delete_target(&self, target_id: &str, ultimate: bool) -> Result<DeletedObject>;
+    async fn create_port_list(&self, name: &str, port_range: &str, comment: Option<&str>) -> Result<CreatedObject>;
+    async fn list_port_lists(&self) -> Result<PortListList>;
+    async fn create_credential(&self, spec: &CredentialSpec) -> Result<CreatedObject>;
+    async fn list_credentials(&self) -> Result<CredentialList>;
+    async fn attach_credential(
+        &self,
+        target_id: &str,
+        credential_id: &str,
+        kind: CredentialKind,
+        port: Option<u16>,
+    ) -> Result<AttachedCredential>;
+    async fn create_schedule(&self, spec: &ScheduleSpec) -> Result<CreatedObject>;
+    async fn list_schedules(&self) -> Result<ScheduleList>;
+    async fn delete_schedule(&self, schedule_id: &str, ultimate: bool) -> Result<DeletedObject>;
+    async fn create_alert(&self, spec: &AlertSpec) -> Result<CreatedObject>;
+    async fn create_task(
+        &self,
+        name: &str,
+        config_id: &str,
+        target_id: &str,
+        schedule_id: Option<&str>,
+        scanner_id: Option<&str>,
+    ) -> Result<CreatedObject>;
+    async fn create_container_task(&self, name: &str) -> Result<CreatedObject>;
+    async fn list_tasks(&self) -> Result<TaskList>;
+    async fn delete_task(&self, task_id: &str, ultimate: bool) -> Result<DeletedObject>;
+    async fn modify_task(&self, task_id: &str, changes: &TaskChanges) -> Result<ModifiedTask>;
+    async fn start_task(&self, task_id: &str) -> Result<StartedTask>;
+    async fn stop_task(&self, task_id: &str) -> Result<StoppedTask>;
+    async fn resume_task(&self, task_id: &str) -> Result<ResumedTask>;
+    async fn get_task_status(&self, task_id: &str) -> Result<TaskStatus>;
+    async fn get_report(&self, report_id: &str, format: ReportFormat, filter: &ReportFilter) -> Result<Report>;
+    async fn list_reports(&self, task_id: Option<&str>) -> Result<ReportList>;
+    async fn delete_report(&self, report_id: &str) -> Result<DeletedObject>;
+    async fn import_report(&self, task_id: &str, report_xml: &str) -> Result<ImportedReport>;
+    async fn get_results(&self, filter: &str, first: u64, rows: u64) -> Result<ResultPage>;
+    async fn cleanup(
+        &self,
+        name_pattern: &str,
+        retention_days: u64,
+        empty_trashcan: bool,
+        apply: bool,
+    ) -> Result<CleanupReport>;
+    async fn list_trash(&self) -> Result<TrashList>;
+    async fn empty_trashcan(&self) -> Result<EmptiedTrashcan>;
+    async fn restore(&self, id: &str) -> Result<RestoredObject>;
+}
+
+/// A backend serving both tool families.
+pub trait Backend: NmapBackend + OpenvasBackend {}
+
+impl<T: NmapBackend + OpenvasBackend> Backend for T {}
+
+/// The Go backend over HTTP, through `api::nmap` and `api::openvas` (and so
+/// through fixtures, dry runs and offline checks).
+pub struct HttpBackend;
+
+#[async_trait]
+impl NmapBackend for HttpBackend {
+    async fn advanced_scan(&self, request: &ScanRequest) -> Result<ScanResult> {
+        nmap::advanced_scan(request).await
+    }
+}
+
+#[async_trait]
+impl OpenvasBackend for HttpBackend {
+    async fn get_version(&self) -> Result<Version> {
+        openvas::get_version().await
+    }
+
+    async fn list_configs(&self) -> Result<ConfigList> {
+        openvas::list_configs().await
+    }
+
+    async fn clone_config(&self, config_id: &str, name: &str, comment: Option<&str>) -> Result<CreatedObject> {
+        openvas::clone_config(config_id, name, comment).await
+    }
+
+    async fn modify_config(&self, config_id: &str, changes: &ConfigChanges) -> Result<ModifiedConfig> {
+        openvas::modify_config(config_id, changes).await
+    }
+
+    async fn get_nvt(&self, oid: &str) -> Result<NvtDetails> {
+        openvas::get_nvt(oid).await
+    }
+
+    async fn list_feeds(&self) -> Result<FeedList> {
+        openvas::list_feeds().await
+    }
+
+    async fn sync_feed(&self, feed: FeedType) -> Result<SyncedFeed> {
+        openvas::sync_feed(feed).await
+    }
+
+    async fn list_host_assets(&self) -> Result<HostAssetList> {
+        openvas::list_host_assets().await
+    }
+
+    async fn create_ticket(&self, result_id: &str, assignee: &str, note: &str) -> Result<CreatedObject> {
+        openvas::create_ticket(result_id, assignee, note).await
+    }
+
+    async fn list_tickets(&self) -> Result<TicketList> {
+        openvas::list_tickets().await
+    }
+
+    async fn modify_ticket(&self, ticket_id: &str, changes: &TicketChanges) -> Result<Ticket> {
+        openvas::modify_ticket(ticket_id, changes).await
+    }
+
+    async fn list_scanners(&self) -> Result<ScannerList> {
+        openvas::list_scanners().await
+    }
+
+    async fn create_target(
+        &self,
+        name: &str,
+        hosts: &str,
+        port_range: Option<&str>,
+        port_list_id: Option<&str>,
+    ) -> Result<CreatedObject> {
+        openvas::create_target(name, hosts, port_range, port_list_id).await
+    }
+
+    async fn list_targets(&self) -> Result<TargetList> {
+        openvas::list_targets().await
+    }
+
+    async fn delete_target(&self, target_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        openvas::delete_target(target_id, ultimate).await
+    }
+
+    async fn create_port_list(&self, name: &str, port_range: &str, comment: Option<&str>) -> Result<CreatedObject> {
+        openvas::create_port_list(name, port_range, comment).await
+    }
+
+    async fn list_port_lists(&self) -> Result<PortListList> {
+        openvas::list_port_lists().await
+    }
+
+    async fn create_credential(&self, spec: &CredentialSpec) -> Result<CreatedObject> {
+        openvas::create_credential(spec).await
+    }
+
+    async fn list_credentials(&self) -> Result<CredentialList> {
+        openvas::list_credentials().await
+    }
+
+    async fn attach_credential(
+        &self,
+        target_id: &str,
+        credential_id: &str,
+        kind: CredentialKind,
+        port: Option<u16>,
+    ) -> Result<AttachedCredential> {
+        openvas::attach_credential(target_id, credential_id, kind, port).await
+    }
+
+    async fn create_schedule(&self, spec: &ScheduleSpec) -> Result<CreatedObject> {
+        openvas::create_schedule(spec).await
+    }
+
+    async fn list_schedules(&self) -> Result<ScheduleList> {
+        openvas::list_schedules().await
+    }
+
+    async fn delete_schedule(&self, schedule_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        openvas::delete_schedule(schedule_id, ultimate).await
+    }
+
+    async fn create_alert(&self, spec: &AlertSpec) -> Result<CreatedObject> {
+        openvas::create_alert(spec).await
+    }
+
+    async fn create_task(
+        &self,
+        name: &str,
+        config_id: &str,
+        target_id: &str,
+        schedule_id: Option<&str>,
+        scanner_id: Option<&str>,
+    ) -> Result<CreatedObject> {
+        openvas::create_task(name, config_id, target_id, schedule_id, scanner_id).await
+    }
+
+    async fn create_container_task(&self, name: &str) -> Result<CreatedObject> {
+        openvas::create_container_task(name).await
+    }
+
+    async fn list_tasks(&self) -> Result<TaskList> {
+        openvas::list_tasks().await
+    }
+
+    async fn delete_task(&self, task_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        openvas::delete_task(task_id, ultimate).await
+    }
+
+    async fn modify_task(&self, task_id: &str, changes: &TaskChanges) -> Result<ModifiedTask> {
+        openvas::modify_task(task_id, changes).await
+    }
+
+    async fn start_task(&self, task_id: &str) -> Result<StartedTask> {
+        openvas::start_task(task_id).await
+    }
+
+    async fn stop_task(&self, task_id: &str) -> Result<StoppedTask> {
+        openvas::stop_task(task_id).await
+    }
+
+    async fn resume_task(&self, task_id: &str) -> Result<ResumedTask> {
+        openvas::resume_task(task_id).await
+    }
+
+    async fn get_task_status(&self, task_id: &str) -> Result<TaskStatus> {
+        openvas::get_task_status(task_id).await
+    }
+
+    async fn get_report(&self, report_id: &str, format: ReportFormat, filter: &ReportFilter) -> Result<Report> {
+        openvas::get_report(report_id, format, filter).await
+    }
+
+    async fn list_reports(&self, task_id: Option<&str>) -> Result<ReportList> {
+        openvas::list_reports(task_id).await
+    }
+
+    async fn delete_report(&self, report_id: &str) -> Result<DeletedObject> {
+        openvas::delete_report(report_id).await
+    }
+
+    async fn import_report(&self, task_id: &str, report_xml: &str) -> Result<ImportedReport> {
+        openvas::import_report(task_id, report_xml).await
+    }
+
+    async fn get_results(&self, filter: &str, first: u64, rows: u64) -> Result<ResultPage> {
+        openvas::get_results(filter, first, rows).await
+    }
+
+    async fn cleanup(
+        &self,
+        name_pattern: &str,
+        retention_days: u64,
+        empty_trashcan: bool,
+        apply: bool,
+    ) -> Result<CleanupReport> {
+        openvas::cleanup(name_pattern, retention_days, empty_trashcan, apply).await
+    }
+
+    async fn list_trash(&self) -> Result<TrashList> {
+        openvas::list_trash().await
+    }
+
+    async fn empty_trashcan(&self) -> Result<EmptiedTrashcan> {
+        openvas::empty_trashcan().await
+    }
+
+    async fn restore(&self, id: &str) -> Result<RestoredObject> {
+        openvas::restore(id).await
+    }
+}
+
+#[cfg(test)]
+tokio::task_local! {
+    static OVERRIDE: Arc<dyn Backend>;
+}
+
+/// The backend services talk to: the named GVM backend the tool call was
+/// routed to (see `gvm_backends`), gvmd itself with `--gmp`, the Go backend
+/// otherwise, or in tests whatever `with_backend` installed for the
+/// running task. A named backend reached over HTTP is the Go backend, with
+/// `api::backend_url` pointing its OpenVAS calls at the backend's URL.
+pub fn current() -> Arc<dyn Backend> {
+    #[cfg(test)]
+    if let Ok(backend) = OVERRIDE.try_with(Arc::clone) {
+        return backend;
+    }
+    if let Some(selected) = gvm_backends::selected() {
+        return match selected.gmp() {
+            Some(gmp) => Arc::new(gmp),
+            None => Arc::new(HttpBackend),
+        };
+    }
+    match GmpBackend::from_config(&config::current()) {
+        Some(gmp) => Arc::new(gmp),
+        None => Arc::new(HttpBackend),
+    }
+}
+
+/// Run `fut` with every service call going to `backend` instead of the Go
+/// backend.
+#[cfg(test)]
+pub async fn with_backend<F: std::future::Future>(backend: Arc<dyn Backend>, fut: F) -> F::Output {
+    OVERRIDE.scope(backend, fut).await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn registered_tools_reach_the_installed_backend() {
+        let mut registry = crate::ToolRegistry::new();
+        crate::tools::register_all_tools(&mut registry);
+        let mock = Arc::new(MockBackend::default());
+
+        let output = with_backend(mock.clone(), async {
+            let target = registry
+                .call("openvas_create_target", json!({ "name": "web", "hosts": "10.0.0.5" }), false)
+                .await
+                .unwrap();
+            let again = registry
+                .call("openvas_create_target", json!({ "name": "web", "hosts": "10.0.0.5" }), false)
+                .await
+                .unwrap();
+            assert_eq!(again["existed"], true);
+            registry.call("openvas_task_status", json!({ "task_id": "missing" }), false).await.unwrap_err();
+            target
+        })
+        .await;
+        assert_eq!(output["existed"], false);
+        assert_eq!(mock.calls(), ["create_target web", "create_target web", "get_task_status missing"]);
+    }
+
+    #[tokio::test]
+    async fn nmap_failures_from_the_mock_are_backend_errors() {
+        let mut registry = crate::ToolRegistry::new();
+        crate::tools::register_all_tools(&mut registry);
+        let mock = Arc::new(
+            MockBackend::default()
+                .with_scan("10.0.0.9", "You requested a scan type which requires root privileges.\nQUITTING!\n"),
+        );
+        let (up, failed) = with_backend(mock, async {
+            let up = registry.call("nmap.scan", json!({ "target": "10.0.0.5" }), false).await;
+            let failed = registry.call("nmap.scan", json!({ "target": "10.0.0.9" }), false).await;
+            (up, failed)
+        })
+        .await;
+        assert!(up.unwrap()["raw_output"].as_str().unwrap().contains("Host is up"));
+        let err = failed.unwrap_err();
+        let backend = err.downcast_ref::<crate::api::errors::BackendError>().unwrap();
+        assert_eq!(backend.kind, crate::api::errors::BackendErrorKind::ScanFailed);
+    }
+}
@@ -0,0 +1,360 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Longest piece of backend output quoted in an error message.
+const MAX_DETAIL: usize = 300;
+
+/// Lines of nmap output before `QUITTING!` kept as the reason it gave up.
+const NMAP_REASON_LINES: usize = 2;
+
+/// What kind of failure a backend call ran into, independent of whether the
+/// Go backend reported it as a status code, a JSON body, plain text or
+/// gvmd's XML `status_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendErrorKind {
+    /// The Go backend, docker or gvmd could not be reached.
+    Unavailable,
+    /// gvmd rejected the backend's credentials.
+    AuthenticationFailed,
+    /// The referenced object (task, target, report, config) does not exist.
+    NotFound,
+    /// The backend rejected the request itself.
+    InvalidRequest,
+    /// The backend did not answer in time.
+    Timeout,
+    /// The scanner ran but failed.
+    ScanFailed,
+    /// Anything else the backend reported as an error.
+    Failed,
+}
+
+impl BackendErrorKind {
+    fn summary(self) -> &'static str {
+        match self {
+            BackendErrorKind::Unavailable => "scan backend unavailable",
+            BackendErrorKind::AuthenticationFailed => "authentication failed",
+            BackendErrorKind::NotFound => "object not found",
+            BackendErrorKind::InvalidRequest => "request rejected by the backend",
+            BackendErrorKind::Timeout => "scan backend timed out",
+            BackendErrorKind::ScanFailed => "scan failed",
+            BackendErrorKind::Failed => "backend error",
+        }
+    }
+}
+
+/// A backend failure normalized into the error taxonomy, with a hint on
+/// what to do about it where one is known.
+#[derive(Debug, Clone)]
+pub struct BackendError {
+    pub kind: BackendErrorKind,
+    /// HTTP status of the backend response, or gvmd's GMP status.
+    pub status: Option<u16>,
+    pub hint: Option<&'static str>,
+    /// What the backend actually said, trimmed.
+    pub detail: String,
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.kind.summary())?;
+        if let Some(hint) = self.hint {
+            write!(f, " — {hint}")?;
+        }
+        if !self.detail.is_empty() {
+            write!(f, " (backend said: {})", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl BackendError {
+    fn new(kind: BackendErrorKind, status: Option<u16>, detail: &str) -> Self {
+        let detail = detail.trim();
+        let detail = match detail.char_indices().nth(MAX_DETAIL) {
+            Some((end, _)) => format!("{}...", &detail[..end]),
+            None => detail.to_string(),
+        };
+        Self {
+            kind,
+            status,
+            hint: hint_for(kind, &detail),
+            detail,
+        }
+    }
+
+    /// Machine-readable form for JSON-RPC `error.data`.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind,
+            "status": self.status,
+            "hint": self.hint,
+            "detail": self.detail,
+        })
+    }
+
+    /// Classify a failed HTTP call that never got a response.
+    pub fn from_transport(err: &reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            BackendErrorKind::Timeout
+        } else if err.is_connect() {
+            BackendErrorKind::Unavailable
+        } else {
+            BackendErrorKind::Failed
+        };
+        Self::new(kind, None, &err.to_string())
+    }
+
+    /// A call refused without contacting the backend because its circuit is
+    /// open (see `api::health`).
+    pub fn circuit_open(detail: &str) -> Self {
+        Self::new(BackendErrorKind::Unavailable, None, detail)
+    }
+
+    /// Classify a failure of the native GMP client (see `api::gmp`) before
+    /// gvmd answered: an unreachable socket, a broken connection, missing
+    /// credentials.
+    pub fn from_io(err: &std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        let detail = err.to_string();
+        let kind = classify_text(&detail).unwrap_or(match err.kind() {
+            ErrorKind::TimedOut => BackendErrorKind::Timeout,
+            ErrorKind::NotFound
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::BrokenPipe
+            | ErrorKind::UnexpectedEof => BackendErrorKind::Unavailable,
+            _ => BackendErrorKind::Failed,
+        });
+        Self::new(kind, None, &detail)
+    }
+
+    /// Classify a non-2xx backend response from its status and body, which
+    /// may be plain text (`http.Error`), a JSON error object or gvmd XML.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let detail = extract_message(body);
+        let kind = classify_text(&detail).unwrap_or(match status {
+            400 | 405 | 409 | 422 => BackendErrorKind::InvalidRequest,
+            401 | 403 => BackendErrorKind::AuthenticationFailed,
+            404 => BackendErrorKind::NotFound,
+            408 | 504 => BackendErrorKind::Timeout,
+            502 | 503 => BackendErrorKind::Unavailable,
+            _ => BackendErrorKind::Failed,
+        });
+        Self::new(kind, Some(status), &detail)
+    }
+
+    /// gvmd answers some failures with a 200 from the backend whose XML
+    /// carries an error `status` (e.g. `<start_task_response status="404"
+    /// status_text="Failed to find task ..."/>`). Returns the error if the
+    /// root element of `xml` reports one.
+    pub fn from_gmp_xml(xml: &str) -> Option<Self> {
+        let status: u16 = attribute(xml, "status")?.parse().ok()?;
+        if (200..300).contains(&status) {
+            return None;
+        }
+        let text = attribute(xml, "status_text").unwrap_or_default();
+        let kind = classify_text(&text).unwrap_or(match status {
+            400 => BackendErrorKind::InvalidRequest,
+            404 => BackendErrorKind::NotFound,
+            503 => BackendErrorKind::Unavailable,
+            _ => BackendErrorKind::Failed,
+        });
+        Some(Self::new(kind, Some(status), &text))
+    }
+
+    /// nmap reports fatal errors in its output (the backend still answers
+    /// 200 with it). Returns the error if `output` shows nmap gave up, or
+    /// that no target resolved so nothing was scanned.
+    pub fn from_nmap_output(output: &str) -> Option<Self> {
+        let line = |needle: &str| output.lines().find(|l| l.contains(needle));
+        let lines: Vec<&str> = output
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("Starting Nmap"))
+            .collect();
+        if let Some(at) = lines.iter().position(|l| l.contains("QUITTING!")) {
+            // nmap explains why on the lines before `QUITTING!`.
+            let fatal = lines[at.saturating_sub(NMAP_REASON_LINES)..=at].join(" ");
+            return Some(Self::new(BackendErrorKind::ScanFailed, None, &fatal));
+        }
+        if output.contains("0 IP addresses")
+            && let Some(unresolved) = line("Failed to resolve")
+        {
+            return Some(Self::new(BackendErrorKind::InvalidRequest, None, unresolved));
+        }
+        None
+    }
+}
+
+/// Recognise well-known failures by what the backend said, whatever the
+/// status code.
+fn classify_text(text: &str) -> Option<BackendErrorKind> {
+    let text = text.to_ascii_lowercase();
+    let any = |needles: &[&str]| needles.iter().any(|n| text.contains(n));
+    if any(&["authentication failed", "gvm_password is not set", "bad credentials"]) {
+        Some(BackendErrorKind::AuthenticationFailed)
+    } else if any(&["failed to find", "no such object"]) {
+        Some(BackendErrorKind::NotFound)
+    } else if any(&[
+        "no such container",
+        "cannot connect to the docker daemon",
+        "connection refused",
+        "service unavailable",
+    ])
+        // Docker's "Container openvas is not running", not gvmd's answer to
+        // stopping a task that is not running.
+        || (text.contains("container") && text.contains("is not running"))
+    {
+        Some(BackendErrorKind::Unavailable)
+    } else if any(&["timed out", "deadline exceeded"]) {
+        Some(BackendErrorKind::Timeout)
+    } else {
+        None
+    }
+}
+
+fn hint_for(kind: BackendErrorKind, detail: &str) -> Option<&'static str> {
+    let detail = detail.to_ascii_lowercase();
+    match kind {
+        // The Go backend's own check (BACKEND_TOKEN / BACKEND_BASIC_AUTH),
+        // not gvmd's.
+        BackendErrorKind::AuthenticationFailed if detail.contains("backend credentials") => {
+            Some("set --backend-token or --backend-basic-auth to match BACKEND_TOKEN / BACKEND_BASIC_AUTH on the backend")
+        }
+        BackendErrorKind::AuthenticationFailed => {
+            Some("check backend credentials (GVM_USERNAME / GVM_PASSWORD on the Go backend, or here with --gmp or --gvm-backends)")
+        }
+        BackendErrorKind::NotFound => {
+            Some("the referenced ID does not exist in gvmd; list tasks, targets or configs to find a valid one")
+        }
+        BackendErrorKind::Unavailable if detail.contains("container") || detail.contains("docker") => {
+            Some("check that the OpenVAS container is running (OPENVAS_CONTAINER_NAME on the Go backend)")
+        }
+        BackendErrorKind::Unavailable => {
+            Some("check that the Go backend (or gvmd itself, with --gmp) is running and reachable; retry once it is up")
+        }
+        BackendErrorKind::Timeout => Some("the backend is overloaded or a scan is stuck; retry later"),
+        BackendErrorKind::InvalidRequest if detail.contains("failed to resolve") => {
+            Some("the target host name does not resolve; check for typos or scan by IP")
+        }
+        BackendErrorKind::ScanFailed if detail.contains("root privileges") => {
+            Some("this scan type needs root; run the Go backend as root or use a connect scan")
+        }
+        _ => None,
+    }
+}
+
+/// The human-readable part of an error body: `error`/`message`/`detail`
+/// from JSON objects, gvmd's `status_text` from XML, or the text itself.
+fn extract_message(body: &str) -> String {
+    let body = body.trim();
+    if let Ok(value) = serde_json::from_str::<Value>(body) {
+        let message = ["error", "message", "detail"].iter().find_map(|k| match value.get(*k) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Object(o)) => o.get("message").and_then(|m| m.as_str()).map(str::to_string),
+            _ => None,
+        });
+        if let Some(message) = message {
+            return message;
+        }
+    }
+    match attribute(body, "status_text") {
+        Some(text) if body.contains('<') => {
+            // Keep the Go-side context before the XML ("failed to ...: ").
+            let prefix = body[..body.find('<').unwrap_or(0)].trim();
+            if prefix.is_empty() { text } else { format!("{prefix} {text}") }
+        }
+        _ => body.to_string(),
+    }
+}
+
+/// Value of the first `name="..."` (or `name='...'`) attribute in `xml`.
+fn attribute(xml: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let needle = format!(" {name}={quote}");
+        if let Some(start) = xml.find(&needle).map(|i| i + needle.len()) {
+            let end = xml[start..].find(quote)? + start;
+            return Some(xml[start..end].to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_responses_by_text_before_status() {
+        let cases = [
+            (400, "invalid target", BackendErrorKind::InvalidRequest),
+            (401, "", BackendErrorKind::AuthenticationFailed),
+            (404, "not here", BackendErrorKind::NotFound),
+            (409, "target exists with different hosts or ports", BackendErrorKind::InvalidRequest),
+            (502, "", BackendErrorKind::Unavailable),
+            (504, "", BackendErrorKind::Timeout),
+            (500, "boom", BackendErrorKind::Failed),
+            (500, r#"{"error":"GVM_PASSWORD is not set"}"#, BackendErrorKind::AuthenticationFailed),
+            (500, "Error: No such container: openvas", BackendErrorKind::Unavailable),
+            (500, "Error response from daemon: Container 4f2a is not running", BackendErrorKind::Unavailable),
+            (400, "Task is not running", BackendErrorKind::InvalidRequest),
+            (500, "context deadline exceeded", BackendErrorKind::Timeout),
+            (400, r#"failed to start task: <start_task_response status="404" status_text="Failed to find task"/>"#, BackendErrorKind::NotFound),
+        ];
+        for (status, body, kind) in cases {
+            assert_eq!(BackendError::from_response(status, body).kind, kind, "{status} {body}");
+        }
+    }
+
+    #[test]
+    fn rejected_backend_credentials_point_at_the_flags() {
+        let err = BackendError::from_response(401, "missing or wrong backend credentials\n");
+        assert_eq!(err.kind, BackendErrorKind::AuthenticationFailed);
+        assert!(err.hint.is_some_and(|h| h.contains("--backend-token")));
+        let err = BackendError::from_response(500, r#"{"error":"GVM_PASSWORD is not set"}"#);
+        assert!(err.hint.is_some_and(|h| h.contains("GVM_PASSWORD")));
+    }
+
+    #[test]
+    fn extracts_messages_from_json_and_gmp_xml() {
+        assert_eq!(extract_message(r#"{"error":{"message":"nope"}}"#), "nope");
+        assert_eq!(extract_message(r#"{"detail":"bad field"}"#), "bad field");
+        assert_eq!(
+            extract_message(r#"failed to get report: <get_reports_response status="400" status_text="Bogus"/>"#),
+            "failed to get report: Bogus"
+        );
+        assert_eq!(extract_message("  plain text \n"), "plain text");
+
+        assert!(BackendError::from_gmp_xml(r#"<get_tasks_response status="200" status_text="OK"/>"#).is_none());
+        let err = BackendError::from_gmp_xml(r#"<create_target_response status='400' status_text='Missing hosts'/>"#).unwrap();
+        assert_eq!((err.kind, err.status, err.detail.as_str()), (BackendErrorKind::InvalidRequest, Some(400), "Missing hosts"));
+    }
+
+    #[test]
+    fn nmap_failures_keep_the_reason_and_get_hints() {
+        let output = "Starting Nmap 7.94\nYou requested a scan type which requires root privileges.\nQUITTING!\n";
+        let err = BackendError::from_nmap_output(output).unwrap();
+        assert_eq!(err.kind, BackendErrorKind::ScanFailed);
+        assert!(err.detail.contains("requires root privileges") && err.detail.contains("QUITTING!"));
+        assert!(err.hint.is_some_and(|h| h.contains("needs root")));
+
+        let output = "Failed to resolve \"no-such.example\".\nWARNING: No targets were specified, so 0 hosts scanned.\nNmap done: 0 IP addresses (0 hosts up)";
+        let err = BackendError::from_nmap_output(output).unwrap();
+        assert_eq!(err.kind, BackendErrorKind::InvalidRequest);
+        assert!(err.hint.is_some_and(|h| h.contains("does not resolve")));
+
+        assert!(BackendError::from_nmap_output("Nmap done: 1 IP address (1 host up)").is_none());
+    }
+
+    #[test]
+    fn details_are_trimmed_and_capped() {
+        let err = BackendError::from_response(500, &format!("  {}  ", "x".repeat(MAX_DETAIL + 50)));
+        assert_eq!(err.detail.chars().count(), MAX_DETAIL + 3);
+        assert!(err.to_string().starts_with("backend error (backend said: xxx"));
+        assert_eq!(err.to_json()["kind"], "failed");
+    }
+}
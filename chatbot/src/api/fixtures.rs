@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Whether backend calls are recorded to, or replayed from, the fixtures dir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Perform real backend calls and save anonymized copies of each exchange.
+    Record,
+    /// Never touch the network; answer every call from a saved fixture.
+    Replay,
+}
+
+impl FixtureMode {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "record" => Ok(FixtureMode::Record),
+            "replay" => Ok(FixtureMode::Replay),
+            other => anyhow::bail!("unknown fixture mode `{other}` (expected record or replay)"),
+        }
+    }
+}
+
+/// One saved backend exchange. Files live at
+/// `<dir>/<endpoint-slug>/<request-hash>.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub endpoint: String,
+    pub request: Value,
+    pub response: Value,
+}
+
+/// Save an anonymized copy of a backend exchange. The file is named after
+/// the anonymized request, so `replay` finds it from the real one.
+pub fn record(dir: &Path, method: &str, url: &str, request: &Value, response: &Value) -> Result<()> {
+    // One mapping per fixture so the same address or host is replaced
+    // consistently in the request and the response. The request is mapped
+    // first, so its anonymized form only depends on the request itself.
+    let mut anonymizer = Anonymizer::default();
+    let request = anonymizer.anonymize(request);
+    let response = anonymizer.anonymize(response);
+
+    let endpoint = endpoint_of(url);
+    let path = fixture_path(dir, method, &endpoint, &request);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let fixture = Fixture {
+        method: method.to_string(),
+        endpoint,
+        request,
+        response,
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&fixture)?)
+        .with_context(|| format!("failed to write fixture {}", path.display()))
+}
+
+/// Look up the saved response for a backend call.
+pub fn replay(dir: &Path, method: &str, url: &str, request: &Value) -> Result<Value> {
+    let request = Anonymizer::default().anonymize(request);
+    let path = fixture_path(dir, method, &endpoint_of(url), &request);
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("no fixture recorded for {method} {url} ({})", path.display()))?;
+    let fixture: Fixture = serde_json::from_str(&text)?;
+    Ok(fixture.response)
+}
+
+/// Load every fixture recorded for `endpoint` (e.g. `/scan-open-ports`).
+/// Parsers use this to run regression tests over real-world payloads.
+#[cfg(test)]
+pub fn load_all(dir: &Path, endpoint: &str) -> Result<Vec<Fixture>> {
+    let sub = dir.join(slug(endpoint));
+    let mut out = Vec::new();
+    if !sub.exists() {
+        return Ok(out);
+    }
+    let mut entries: Vec<_> = std::fs::read_dir(&sub)?.collect::<std::io::Result<_>>()?;
+    entries.sort_by_key(|e| e.path());
+    for entry in entries {
+        let text = std::fs::read_to_string(entry.path())?;
+        out.push(serde_json::from_str(&text)?);
+    }
+    Ok(out)
+}
+
+/// Fixtures checked in under `chatbot/fixtures/` for `endpoint`, for parser
+/// tests.
+#[cfg(test)]
+pub fn checked_in(endpoint: &str) -> Vec<Fixture> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let fixtures = load_all(&dir, endpoint).unwrap();
+    assert!(!fixtures.is_empty(), "expected fixtures for {endpoint} under {}", dir.display());
+    fixtures
+}
+
+/// Strip scheme and host so fixtures don't depend on where the backend runs.
+fn endpoint_of(url: &str) -> String {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    match rest.find('/') {
+        Some(i) => rest[i..].to_string(),
+        None => "/".to_string(),
+    }
+}
+
+fn slug(endpoint: &str) -> String {
+    let s: String = endpoint
+        .trim_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if s.is_empty() { "root".to_string() } else { s }
+}
+
+fn fixture_path(dir: &Path, method: &str, endpoint: &str, request: &Value) -> PathBuf {
+    let key = format!("{method} {}", serde_json::to_string(request).unwrap_or_default());
+    dir.join(slug(endpoint)).join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+}
+
+/// FNV-1a: stable across Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Object keys whose values are always redacted.
+const SECRET_KEYS: &[&str] = &["password", "private_key", "username", "token", "secret", "authorization"];
+
+/// Replaces IP addresses with reserved addresses (the documentation ranges
+/// 192.0.2.0/24, 198.51.100.0/24, 203.0.113.0/24, then 240.0.0.0/4; and
+/// 2001:db8::/32) and host names with `hostN.example`, and redacts
+/// credential-looking fields. Host names are learned from target fields and
+/// nmap's `Nmap scan report for <name> (<ip>)` lines.
+#[derive(Default)]
+struct Anonymizer {
+    ips: HashMap<IpAddr, String>,
+    hosts: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    fn anonymize(&mut self, value: &Value) -> Value {
+        self.learn_hosts(value);
+        self.rewrite(value)
+    }
+
+    fn learn_hosts(&mut self, value: &Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, v) in map {
+                    if let (true, Some(list)) = (crate::targets::TARGET_FIELDS.contains(&key.as_str()), v.as_str()) {
+                        list.split(|c: char| c == ',' || c.is_whitespace())
+                            .for_each(|t| self.learn_host(t));
+                    }
+                    self.learn_hosts(v);
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|v| self.learn_hosts(v)),
+            Value::String(s) => {
+                for line in s.lines() {
+                    let named = ["Nmap scan report for ", "Other addresses for "]
+                        .iter()
+                        .find_map(|prefix| line.strip_prefix(prefix));
+                    if let Some((name, _)) = named.and_then(|rest| rest.split_once(" (")) {
+                        self.learn_host(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn learn_host(&mut self, token: &str) {
+        let name = token.split('/').next().unwrap_or_default().to_ascii_lowercase();
+        let is_name = name.contains('.')
+            && name.chars().any(|c| c.is_ascii_alphabetic())
+            && name.parse::<IpAddr>().is_err()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'));
+        if is_name && !name.ends_with(".example") && !self.hosts.contains_key(&name) {
+            let replacement = format!("host{}.example", self.hosts.len() + 1);
+            self.hosts.insert(name, replacement);
+        }
+    }
+
+    fn rewrite(&mut self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.rewrite_str(s)),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.rewrite(v)).collect()),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| {
+                        let v = if SECRET_KEYS.contains(&k.to_ascii_lowercase().as_str()) {
+                            Value::String("REDACTED".into())
+                        } else {
+                            self.rewrite(v)
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    fn rewrite_str(&mut self, s: &str) -> String {
+        let s = self.rewrite_hosts(s);
+        let is_ip_char = |c: char| c.is_ascii_hexdigit() || c == '.' || c == ':';
+        let mut out = String::with_capacity(s.len());
+        let mut token = String::new();
+        for c in s.chars() {
+            if is_ip_char(c) {
+                token.push(c);
+            } else {
+                self.flush_ip(&mut token, &mut out);
+                out.push(c);
+            }
+        }
+        self.flush_ip(&mut token, &mut out);
+        out
+    }
+
+    /// Replace known host names, longest first so `www.example.org` is not
+    /// half-replaced by a mapping for `example.org`.
+    fn rewrite_hosts(&self, s: &str) -> String {
+        let mut names: Vec<_> = self.hosts.iter().collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        let mut out = s.to_string();
+        for (name, replacement) in names {
+            let lower = out.to_ascii_lowercase();
+            if !lower.contains(name.as_str()) {
+                continue;
+            }
+            let mut rewritten = String::with_capacity(out.len());
+            let mut rest = 0;
+            for (i, _) in lower.match_indices(name.as_str()) {
+                rewritten.push_str(&out[rest..i]);
+                rewritten.push_str(replacement);
+                rest = i + name.len();
+            }
+            rewritten.push_str(&out[rest..]);
+            out = rewritten;
+        }
+        out
+    }
+
+    fn flush_ip(&mut self, token: &mut String, out: &mut String) {
+        // Allow a trailing sentence dot/colon without losing the address.
+        let trimmed = token.trim_end_matches(['.', ':']);
+        match trimmed.parse::<IpAddr>() {
+            Ok(ip) => {
+                let next = self.ips.len() as u32 + 1;
+                let replacement = self.ips.entry(ip).or_insert_with(|| match ip {
+                    IpAddr::V4(_) => documentation_v4(next).to_string(),
+                    IpAddr::V6(_) => format!("2001:db8::{next:x}"),
+                });
+                out.push_str(replacement);
+                out.push_str(&token[trimmed.len()..]);
+            }
+            Err(_) => out.push_str(token),
+        }
+        token.clear();
+    }
+}
+
+/// The `n`th (1-based) replacement IPv4 address: the three documentation
+/// ranges first, then the reserved 240.0.0.0/4, so addresses never repeat.
+fn documentation_v4(n: u32) -> std::net::Ipv4Addr {
+    const NETS: [[u8; 3]; 3] = [[192, 0, 2], [198, 51, 100], [203, 0, 113]];
+    let (net, host) = ((n - 1) / 254, (n - 1) % 254 + 1);
+    match NETS.get(net as usize) {
+        Some([a, b, c]) => std::net::Ipv4Addr::new(*a, *b, *c, host as u8),
+        None => std::net::Ipv4Addr::from(0xF000_0000 + (n - 3 * 254)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Every checked-in fixture is found again from its own request.
+    #[test]
+    fn recorded_fixtures_replay() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        for endpoint in ["/scan-open-ports", "/openvas/tasks/status", "/openvas/reports"] {
+            for f in checked_in(endpoint) {
+                let url = format!("http://backend{}", f.endpoint);
+                let replayed = replay(&dir, &f.method, &url, &f.request).unwrap();
+                assert_eq!(replayed, f.response);
+            }
+        }
+    }
+
+    /// A recording made from real addresses and names replays from the same
+    /// real request, and none of them end up on disk.
+    #[test]
+    fn record_then_replay_with_real_targets() {
+        let dir = std::env::temp_dir().join(format!("hacker_agent-fixtures-{}", std::process::id()));
+        let url = &crate::api::backend_url("/scan-open-ports");
+        let request = json!({ "target": "10.1.2.3,db.corp.internal" });
+        let response = json!({
+            "target": "10.1.2.3,db.corp.internal",
+            "raw_output": "Nmap scan report for db.corp.internal (10.1.2.4)\nNmap scan report for gw.corp.internal (10.1.2.3)\n",
+        });
+        record(&dir, "POST", url, &request, &response).unwrap();
+
+        let replayed = replay(&dir, "POST", url, &request).unwrap();
+        let text = replayed.to_string();
+        std::fs::remove_dir_all(&dir).unwrap();
+        for secret in ["10.1.2", "corp.internal"] {
+            assert!(!text.contains(secret), "{secret} leaked into {text}");
+        }
+        assert_eq!(replayed["target"], "192.0.2.1,host1.example");
+        assert_eq!(
+            replayed["raw_output"],
+            "Nmap scan report for host1.example (192.0.2.2)\nNmap scan report for host2.example (192.0.2.1)\n"
+        );
+    }
+
+    #[test]
+    fn anonymize_replaces_addresses_consistently() {
+        let mut anonymizer = Anonymizer::default();
+        let out = anonymizer.rewrite_str("scanme (45.33.32.156) and 45.33.32.156. at 14:27");
+        assert_eq!(out, "scanme (192.0.2.1) and 192.0.2.1. at 14:27");
+    }
+
+    #[test]
+    fn replacement_addresses_never_repeat() {
+        let addrs: std::collections::HashSet<_> = (1..=2000).map(documentation_v4).collect();
+        assert_eq!(addrs.len(), 2000);
+        assert_eq!(documentation_v4(254).to_string(), "192.0.2.254");
+        assert_eq!(documentation_v4(255).to_string(), "198.51.100.1");
+        assert_eq!(documentation_v4(763).to_string(), "240.0.0.1");
+    }
+
+    #[test]
+    fn credentials_are_redacted() {
+        let out = Anonymizer::default().anonymize(&json!({ "Password": "hunter2", "name": "x" }));
+        assert_eq!(out, json!({ "Password": "REDACTED", "name": "x" }));
+    }
+}
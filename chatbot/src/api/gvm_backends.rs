@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::gmp::{GmpBackend, GmpEndpoint};
+use crate::config;
+
+/// The `--gvm-backends` file: named GVM accounts, e.g. one per customer,
+/// and which one each engagement uses unless a call names another.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BackendsFile {
+    /// Backend used when a call names none and its workspace has none;
+    /// without it such calls use `--openvas-backend-url` or `--gmp`.
+    #[serde(default)]
+    default: Option<String>,
+    /// Workspace (`--workspace`) → the backend its calls use by default.
+    #[serde(default)]
+    workspaces: BTreeMap<String, String>,
+    backends: BTreeMap<String, BackendSpec>,
+}
+
+/// One GVM account, reached through its own Go backend (`url`) or over
+/// GMP (`gmp`). Secrets are named by environment variable, never written
+/// in the file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BackendSpec {
+    /// Go backend serving this account's `/openvas/` endpoints.
+    url: Option<String>,
+    /// Environment variable holding the bearer token for `url`.
+    token_env: Option<String>,
+    /// Environment variable holding `user:password` for basic auth on `url`.
+    basic_auth_env: Option<String>,
+    /// gvmd endpoint, as for `--gmp`.
+    gmp: Option<String>,
+    /// CA certificate for a TLS `gmp` endpoint.
+    gmp_ca_cert: Option<String>,
+    /// GMP user name. Default: admin
+    username: Option<String>,
+    /// Environment variable holding the GMP password.
+    password_env: Option<String>,
+}
+
+/// How calls reach a named backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// A Go backend base URL, and the environment variables with its
+    /// credentials.
+    Http {
+        url: String,
+        token_env: Option<String>,
+        basic_auth_env: Option<String>,
+    },
+    /// gvmd over GMP, logging in as `username` with the password in
+    /// `password_env`.
+    Gmp {
+        endpoint: GmpEndpoint,
+        ca_cert: Option<PathBuf>,
+        username: String,
+        password_env: Option<String>,
+    },
+}
+
+/// A named GVM backend a call was routed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GvmBackend {
+    pub name: String,
+    pub route: Route,
+}
+
+impl GvmBackend {
+    /// The `Route::Http` base URL, if calls to this backend go over HTTP.
+    pub fn url(&self) -> Option<&str> {
+        match &self.route {
+            Route::Http { url, .. } => Some(url),
+            Route::Gmp { .. } => None,
+        }
+    }
+
+    /// The GMP client for a `Route::Gmp` backend.
+    pub fn gmp(&self) -> Option<GmpBackend> {
+        let Route::Gmp { endpoint, ca_cert, username, password_env } = &self.route else {
+            return None;
+        };
+        let password = password_env.as_deref().and_then(env);
+        Some(GmpBackend::new(endpoint.clone(), ca_cert.clone(), username.clone(), password))
+    }
+
+    /// Attach this backend's Go backend credentials to a request for it.
+    pub fn with_credentials(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        let Route::Http { token_env, basic_auth_env, .. } = &self.route else {
+            return Ok(builder);
+        };
+        let secret = |key: &str| {
+            env(key).with_context(|| format!("{key}, with the credentials of GVM backend `{}`, is not set", self.name))
+        };
+        if let Some(key) = token_env {
+            return Ok(builder.bearer_auth(secret(key)?));
+        }
+        if let Some(key) = basic_auth_env {
+            let credentials = secret(key)?;
+            let (user, password) = credentials
+                .split_once(':')
+                .with_context(|| format!("{key} expects user:password"))?;
+            return Ok(builder.basic_auth(user, Some(password)));
+        }
+        Ok(builder)
+    }
+}
+
+fn env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Parsed `--gvm-backends` file.
+#[derive(Debug, Default)]
+struct Backends {
+    default: Option<String>,
+    workspaces: BTreeMap<String, String>,
+    backends: BTreeMap<String, Arc<GvmBackend>>,
+}
+
+static BACKENDS: OnceLock<Backends> = OnceLock::new();
+
+/// Load the named GVM backends, if a file is configured. Call once at
+/// startup, before the tools are registered; a malformed file fails
+/// startup.
+pub fn install(path: Option<&str>) -> Result<()> {
+    let backends = match path {
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .with_context(|| format!("reading GVM backends from {path}"))?;
+            parse(&raw).with_context(|| format!("parsing GVM backends in {path}"))?
+        }
+        None => Backends::default(),
+    };
+    let _ = BACKENDS.set(backends);
+    Ok(())
+}
+
+fn parse(raw: &str) -> Result<Backends> {
+    let file: BackendsFile = serde_yaml::from_str(raw)?;
+    let mut backends = BTreeMap::new();
+    for (name, spec) in file.backends {
+        let route = route(spec).with_context(|| format!("GVM backend `{name}`"))?;
+        backends.insert(name.clone(), Arc::new(GvmBackend { name, route }));
+    }
+    let named = file.default.iter().map(|d| ("default", d)).chain(file.workspaces.iter().map(|(w, b)| (w.as_str(), b)));
+    for (whose, name) in named {
+        anyhow::ensure!(backends.contains_key(name), "{whose}: no GVM backend named `{name}`");
+    }
+    Ok(Backends { default: file.default, workspaces: file.workspaces, backends })
+}
+
+fn route(spec: BackendSpec) -> Result<Route> {
+    match (spec.url, spec.gmp) {
+        (Some(url), None) => {
+            anyhow::ensure!(
+                spec.gmp_ca_cert.is_none() && spec.username.is_none() && spec.password_env.is_none(),
+                "gmp_ca_cert, username and password_env need gmp, not url"
+            );
+            anyhow::ensure!(
+                spec.token_env.is_none() || spec.basic_auth_env.is_none(),
+                "token_env and basic_auth_env are mutually exclusive"
+            );
+            Ok(Route::Http {
+                url: config::parse_backend_url("url", &url)?,
+                token_env: spec.token_env,
+                basic_auth_env: spec.basic_auth_env,
+            })
+        }
+        (None, Some(gmp)) => {
+            anyhow::ensure!(
+                spec.token_env.is_none() && spec.basic_auth_env.is_none(),
+                "token_env and basic_auth_env need url, not gmp"
+            );
+            let endpoint = GmpEndpoint::parse(&gmp)?;
+            if spec.gmp_ca_cert.is_some() && !matches!(endpoint, GmpEndpoint::Tls { .. }) {
+                anyhow::bail!("gmp_ca_cert needs a TLS gmp endpoint");
+            }
+            Ok(Route::Gmp {
+                endpoint,
+                ca_cert: spec.gmp_ca_cert.map(PathBuf::from),
+                username: spec.username.unwrap_or_else(|| "admin".to_string()),
+                password_env: spec.password_env,
+            })
+        }
+        _ => anyhow::bail!("give exactly one of url and gmp"),
+    }
+}
+
+fn backends() -> &'static Backends {
+    static NONE: OnceLock<Backends> = OnceLock::new();
+    BACKENDS.get().unwrap_or_else(|| NONE.get_or_init(Backends::default))
+}
+
+impl Backends {
+    /// The backend a call naming `requested` (or none) makes in
+    /// `workspace`: the one named, else the workspace's, else the default.
+    /// `None` leaves the call on `--openvas-backend-url` or `--gmp`.
+    fn resolve(&self, requested: Option<&str>, workspace: &str) -> Result<Option<Arc<GvmBackend>>> {
+        let Some(name) = requested.or(self.workspaces.get(workspace).or(self.default.as_ref()).map(String::as_str))
+        else {
+            return Ok(None);
+        };
+        match self.backends.get(name) {
+            Some(backend) => Ok(Some(backend.clone())),
+            None if self.backends.is_empty() => anyhow::bail!("no GVM backends are configured (--gvm-backends)"),
+            None => anyhow::bail!(
+                "unknown GVM backend `{name}` (expected one of {})",
+                self.backends.keys().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+}
+
+/// Names of the configured backends, for the `backend` argument of the
+/// OpenVAS tools; empty without `--gvm-backends`.
+pub fn names() -> Vec<String> {
+    backends().backends.keys().cloned().collect()
+}
+
+/// The backend a call naming `requested` (or none) is routed to in the
+/// current workspace; see `Backends::resolve`.
+pub fn resolve(requested: Option<&str>) -> Result<Option<Arc<GvmBackend>>> {
+    backends().resolve(requested, &config::current().workspace)
+}
+
+/// Base URLs of the backends reached over HTTP, which offline mode lets
+/// calls go to and the health probe checks.
+pub fn urls<'a>() -> impl Iterator<Item = &'a str> {
+    backends().backends.values().filter_map(|b| b.url())
+}
+
+tokio::task_local! {
+    static SELECTED: Option<Arc<GvmBackend>>;
+}
+
+/// Run `fut` (a tool call) with its OpenVAS calls routed to `backend`.
+pub async fn scope<F: Future>(backend: Option<Arc<GvmBackend>>, fut: F) -> F::Output {
+    SELECTED.scope(backend, fut).await
+}
+
+/// The backend the running tool call was routed to, if any.
+pub fn selected() -> Option<Arc<GvmBackend>> {
+    SELECTED.try_with(Clone::clone).ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{backend_url, http_client};
+
+    const FILE: &str = r#"
+default: lab
+workspaces:
+  acme-2026q3: acme
+backends:
+  lab:
+    gmp: /run/gvmd/gvmd.sock
+  acme:
+    url: https://gvm-acme.internal:8443/
+    token_env: ACME_BACKEND_TOKEN
+  globex:
+    gmp: gvm.globex.internal
+    gmp_ca_cert: /etc/hacker_agent/globex-ca.pem
+    username: hacker_agent
+    password_env: GLOBEX_GVM_PASSWORD
+"#;
+
+    #[test]
+    fn calls_use_the_named_then_the_workspace_then_the_default_backend() {
+        let backends = parse(FILE).unwrap();
+        let name = |requested, workspace| backends.resolve(requested, workspace).unwrap().unwrap().name.clone();
+        assert_eq!(name(Some("globex"), "acme-2026q3"), "globex");
+        assert_eq!(name(None, "acme-2026q3"), "acme");
+        assert_eq!(name(None, "default"), "lab");
+        let err = backends.resolve(Some("initech"), "default").unwrap_err().to_string();
+        assert_eq!(err, "unknown GVM backend `initech` (expected one of acme, globex, lab)");
+
+        let acme = &backends.backends["acme"];
+        assert_eq!(acme.url(), Some("https://gvm-acme.internal:8443"));
+        let Route::Gmp { endpoint, username, .. } = &backends.backends["globex"].route else {
+            panic!("globex is reached over GMP");
+        };
+        assert_eq!(endpoint.to_string(), "tls://gvm.globex.internal:9390");
+        assert_eq!(username, "hacker_agent");
+
+        let none = Backends::default();
+        assert!(none.resolve(None, "default").unwrap().is_none());
+        assert!(none.resolve(Some("acme"), "default").is_err());
+    }
+
+    #[test]
+    fn malformed_backends_are_refused() {
+        let refused = |yaml: &str| format!("{:#}", parse(yaml).unwrap_err());
+        assert!(refused("backends:\n  a:\n    username: admin\n").contains("exactly one of url and gmp"));
+        assert!(refused("backends:\n  a:\n    url: http://gvm:8080\n    password_env: P\n").contains("need gmp"));
+        assert!(refused("default: b\nbackends:\n  a:\n    gmp: /run/gvmd.sock\n").contains("no GVM backend named `b`"));
+        assert!(refused("backends:\n  a:\n    gmp: /run/gvmd.sock\n    gmp_ca_cert: ca.pem\n").contains("TLS"));
+    }
+
+    #[tokio::test]
+    async fn routed_calls_go_to_the_backend_with_its_credentials() {
+        let acme = parse(FILE).unwrap().backends["acme"].clone();
+        let (url, routed) = scope(Some(acme.clone()), async { (backend_url("/openvas/tasks"), selected()) }).await;
+        assert_eq!(url, "https://gvm-acme.internal:8443/openvas/tasks");
+        assert_eq!(routed.as_deref(), Some(&*acme));
+        assert!(selected().is_none());
+        assert_eq!(backend_url("/scan/quick"), "http://127.0.0.1:8080/scan/quick");
+
+        // The token is read when the call is made, and must be there.
+        let err = acme.with_credentials(http_client().get(&url)).unwrap_err().to_string();
+        assert_eq!(err, "ACME_BACKEND_TOKEN, with the credentials of GVM backend `acme`, is not set");
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::errors::{BackendError, BackendErrorKind};
+use super::fixtures::FixtureMode;
+use crate::{config, idle};
+
+/// Consecutive unreachable or timed-out calls that open a backend's circuit.
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open circuit fails calls without contacting the backend.
+/// The first call after that is let through; if it fails too, the circuit
+/// opens again straight away.
+const OPEN_FOR: Duration = Duration::from_secs(30);
+
+/// Longest the health probe waits for the backend to answer.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Recent health of one backend, keyed by its origin.
+#[derive(Debug, Default)]
+struct Circuit {
+    /// Consecutive calls that found the backend unreachable or slow.
+    failures: u32,
+    /// While set and in the future, calls fail without a request.
+    open_until: Option<Instant>,
+    /// What the last failed call ran into, quoted when calls fail fast.
+    last_error: String,
+}
+
+impl Circuit {
+    /// How much longer calls must fail fast, if the circuit is open at `now`.
+    fn open_for(&self, now: Instant) -> Option<Duration> {
+        self.open_until.filter(|until| *until > now).map(|until| until - now)
+    }
+
+    /// Record a call that reached the backend. Returns whether this closed
+    /// an open circuit.
+    fn succeed(&mut self) -> bool {
+        let was_open = self.open_until.is_some();
+        *self = Circuit::default();
+        was_open
+    }
+
+    /// Record a call that found the backend down; `probe` failures open the
+    /// circuit at once. Returns whether this opened a closed circuit.
+    fn fail(&mut self, now: Instant, error: &BackendError, probe: bool) -> bool {
+        self.failures += 1;
+        self.last_error = error.detail.clone();
+        if !probe && self.failures < FAILURE_THRESHOLD {
+            return false;
+        }
+        let was_open = self.open_until.is_some();
+        self.open_until = Some(now + OPEN_FOR);
+        !was_open
+    }
+}
+
+static CIRCUITS: Mutex<Option<HashMap<String, Circuit>>> = Mutex::new(None);
+
+fn with_circuit<T>(url: &str, f: impl FnOnce(&mut Circuit) -> T) -> T {
+    let mut guard = CIRCUITS.lock().unwrap();
+    f(guard.get_or_insert_with(HashMap::new).entry(origin(url)).or_default())
+}
+
+/// The part of `url` a circuit is kept for: one per backend, whatever the
+/// endpoint.
+fn origin(url: &str) -> String {
+    reqwest::Url::parse(url).map_or_else(|_| url.to_string(), |u| u.origin().ascii_serialization())
+}
+
+/// Whether `err` means the backend itself is down or hung, as opposed to
+/// having answered with an error.
+fn is_outage(err: &BackendError) -> bool {
+    matches!(err.kind, BackendErrorKind::Unavailable | BackendErrorKind::Timeout)
+}
+
+/// Refuse a call to `url` without contacting it while its backend's
+/// circuit is open, so tools fail in milliseconds with `backend_unavailable`
+/// instead of each waiting out a connect timeout.
+pub fn check(url: &str) -> Result<(), BackendError> {
+    with_circuit(url, |circuit| match circuit.open_for(Instant::now()) {
+        Some(left) => Err(BackendError::circuit_open(&format!(
+            "not contacted: {} failed {} times in a row, retrying in {}s; last error: {}",
+            origin(url),
+            circuit.failures,
+            left.as_secs().max(1),
+            circuit.last_error
+        ))),
+        None => Ok(()),
+    })
+}
+
+/// Record how a call to `url` went. Any answer, even an error status,
+/// counts as the backend being up; only outages count against it.
+pub fn record(url: &str, outcome: Result<(), &BackendError>) {
+    record_outcome(url, outcome, false);
+}
+
+fn record_outcome(url: &str, outcome: Result<(), &BackendError>, probe: bool) {
+    match outcome {
+        Err(err) if is_outage(err) => {
+            if with_circuit(url, |circuit| circuit.fail(Instant::now(), err, probe)) {
+                eprintln!(
+                    "backend {} unavailable ({}); failing calls to it for {}s",
+                    origin(url),
+                    err.detail,
+                    OPEN_FOR.as_secs()
+                );
+            }
+        }
+        _ => {
+            if with_circuit(url, Circuit::succeed) {
+                eprintln!("backend {} is reachable again", origin(url));
+            }
+        }
+    }
+}
+
+/// The Go backend base URLs calls actually go to: the nmap one unless
+/// `--local-nmap` runs nmap here, the OpenVAS one unless `--gmp` talks to
+/// gvmd directly, and those of the named GVM backends (see `gvm_backends`).
+fn probed_urls(cfg: &config::AppConfig) -> Vec<String> {
+    let mut urls = Vec::new();
+    if !cfg.local_nmap {
+        urls.push(cfg.nmap_backend_url.as_deref().unwrap_or(&cfg.backend_url).to_string());
+    }
+    if cfg.gmp.is_none() {
+        urls.push(cfg.openvas_backend_url.as_deref().unwrap_or(&cfg.backend_url).to_string());
+    }
+    urls.extend(super::gvm_backends::urls().map(str::to_string));
+    urls.dedup_by(|a, b| origin(a) == origin(b));
+    urls
+}
+
+/// GET `<base>/health` and record the outcome. Backends that predate the
+/// endpoint answer 404, which still shows they are up.
+async fn probe(base: &str) {
+    let cfg = config::current();
+    let url = format!("{base}/health");
+    if super::ensure_allowed(&url).is_err() {
+        return;
+    }
+    let request = super::with_credentials(super::http_client().get(&url), &cfg).timeout(PROBE_TIMEOUT);
+    let outcome = match request.send().await {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) => {
+            let status = resp.status().as_u16();
+            Err(BackendError::from_response(status, &resp.text().await.unwrap_or_default()))
+        }
+        Err(err) => Err(BackendError::from_transport(&err)),
+    };
+    record_outcome(&url, outcome.as_ref().map(|_| ()), true);
+}
+
+/// Probe the Go backend now and then every `interval`, so an outage opens
+/// the circuit before the first tool call runs into it and a recovery
+/// closes it without waiting for `OPEN_FOR`. Nothing is probed when
+/// replaying fixtures, and rounds are skipped while the server is idle.
+pub fn spawn_probe(interval: Duration) {
+    let cfg = config::current();
+    if interval.is_zero() || cfg.fixtures == Some(FixtureMode::Replay) {
+        return;
+    }
+    let urls = probed_urls(&cfg);
+    if urls.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if idle::is_suspended() {
+                continue;
+            }
+            for url in &urls {
+                probe(url).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refused() -> BackendError {
+        BackendError::from_response(503, "connection refused")
+    }
+
+    #[test]
+    fn repeated_outages_open_the_circuit_until_it_cools_down() {
+        let now = Instant::now();
+        let mut circuit = Circuit::default();
+        assert!(!circuit.fail(now, &refused(), false));
+        assert!(!circuit.fail(now, &refused(), false));
+        assert_eq!(circuit.open_for(now), None);
+        assert!(circuit.fail(now, &refused(), false));
+        assert_eq!(circuit.open_for(now), Some(OPEN_FOR));
+        // Once it cools down one call is let through; failing again reopens.
+        let later = now + OPEN_FOR;
+        assert_eq!(circuit.open_for(later), None);
+        assert!(!circuit.fail(later, &refused(), false));
+        assert_eq!(circuit.open_for(later), Some(OPEN_FOR));
+        assert!(circuit.succeed());
+        assert_eq!(circuit.failures, 0);
+        assert_eq!(circuit.open_for(later), None);
+    }
+
+    #[test]
+    fn a_failed_probe_opens_the_circuit_at_once() {
+        let now = Instant::now();
+        let mut circuit = Circuit::default();
+        assert!(circuit.fail(now, &refused(), true));
+        assert!(circuit.open_for(now).is_some());
+    }
+
+    #[test]
+    fn open_circuits_fail_calls_fast_as_unavailable() {
+        let url = "http://breaker-test.invalid:8080/openvas/tasks";
+        let timeout = BackendError::from_response(504, "gateway timeout");
+        for _ in 0..FAILURE_THRESHOLD {
+            check(url).unwrap();
+            record(url, Err(&timeout));
+        }
+        let err = check("http://breaker-test.invalid:8080/scan/quick").unwrap_err();
+        assert_eq!(err.kind, BackendErrorKind::Unavailable);
+        assert!(err.detail.contains("http://breaker-test.invalid:8080 failed 3 times"), "{}", err.detail);
+        assert!(err.detail.ends_with("gateway timeout"), "{}", err.detail);
+        // Answers other than outages mean the backend is up.
+        record(url, Err(&BackendError::from_response(404, "failed to find task")));
+        check(url).unwrap();
+    }
+
+    #[test]
+    fn probes_skip_the_openvas_backend_when_gmp_is_native() {
+        let cfg = config::AppConfig::default();
+        assert_eq!(probed_urls(&cfg), vec!["http://127.0.0.1:8080".to_string()]);
+        let cfg = config::AppConfig {
+            openvas_backend_url: Some("http://gvm.internal:8080".into()),
+            ..config::AppConfig::default()
+        };
+        assert_eq!(probed_urls(&cfg).len(), 2);
+        let gmp = Some(crate::api::gmp::GmpEndpoint::parse("unix:/run/gvmd/gvmd.sock").unwrap());
+        let cfg = config::AppConfig { gmp, ..cfg };
+        assert_eq!(probed_urls(&cfg), vec!["http://127.0.0.1:8080".to_string()]);
+        // Standalone: nmap runs here and gvmd is spoken to directly.
+        let cfg = config::AppConfig { local_nmap: true, ..cfg };
+        assert!(probed_urls(&cfg).is_empty());
+    }
+}
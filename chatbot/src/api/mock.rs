@@ -0,0 +1,1169 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::backend::{NmapBackend, OpenvasBackend};
+use super::errors::BackendError;
+use super::nmap_xml;
+use crate::models::nmap_scan::{ScanRequest, ScanResult};
+use crate::models::openvas::{
+    AlertSpec, AttachedCredential, CleanupReport, ConfigChanges, ConfigFamily, ConfigList,
+    CreatedObject, CredentialKind, CredentialList, CredentialSpec, CredentialSummary, DeletedObject,
+    EmptiedTrashcan, FeedList, FeedSummary, FeedType, HostAsset, HostAssetList, ImportedReport,
+    ModifiedConfig, ModifiedTask, NvtDetails, NvtReference, PortCount, PortListList,
+    PortListSummary, Report, ReportFilter, ReportFormat, ReportList, ReportResult, ReportSummary,
+    RestoredObject, ResultPage, RESUMABLE_TASK_STATUSES, ResumedTask, ScanConfig, ScannerList,
+    ScannerSummary, ScheduleList, ScheduleSpec, ScheduleSummary, SeverityCounts, StartedTask,
+    StoppedTask, SyncedFeed, TargetList, TargetSummary, TaskChanges, TaskList, TaskStatus,
+    TaskSummary, Ticket, TicketChanges, TicketList, TicketStatus, TrashedObject, TrashList, Version,
+};
+
+/// In-memory stand-in for the Go backend and gvmd, for testing tools and
+/// services (see `backend::with_backend`). Targets and tasks behave like
+/// gvmd's behind the Go backend: identical targets and tasks are reused,
+/// unknown IDs are `not_found`.
+/// Tasks and targets deleted without `ultimate` go to a trashcan they can be
+/// restored from.
+/// Started tasks finish at once, with a report holding `findings`, unless
+/// `with_running_tasks` keeps them running. Reports keep the findings they
+/// were created with, so `set_findings` between runs gives reports to diff.
+#[derive(Default)]
+pub struct MockBackend {
+    state: Mutex<MockState>,
+}
+
+#[derive(Default)]
+struct MockState {
+    /// Every call, as `method first-argument`.
+    calls: Vec<String>,
+    /// Errors returned, in order, by the next calls instead of answering.
+    failures: VecDeque<BackendError>,
+    /// Per scan target: the nmap output to return.
+    scans: BTreeMap<String, String>,
+    /// Config ID → the config, for those cloned through the mock.
+    configs: BTreeMap<String, MockConfig>,
+    /// `(name, hosts)` → target ID.
+    targets: BTreeMap<(String, String), String>,
+    /// Port list ID → the port list, for those created through the mock.
+    port_lists: BTreeMap<String, PortListSummary>,
+    /// Target ID → the port list ID it was created with.
+    target_port_lists: BTreeMap<String, String>,
+    tasks: BTreeMap<String, MockTask>,
+    /// Trashed targets, as in `targets`, and trashed tasks.
+    trashed_targets: BTreeMap<(String, String), String>,
+    trashed_tasks: BTreeMap<String, MockTask>,
+    schedules: BTreeMap<String, ScheduleSpec>,
+    alerts: BTreeMap<String, AlertSpec>,
+    /// Credential ID → the credential, and whether a target uses it.
+    credentials: BTreeMap<String, (CredentialSpec, bool)>,
+    /// Findings put in the report of every task that runs.
+    findings: Vec<ReportResult>,
+    /// Report ID → the findings it was created with.
+    reports: BTreeMap<String, Vec<ReportResult>>,
+    tickets: BTreeMap<String, Ticket>,
+    /// Host assets listed ahead of those derived from `reports`.
+    assets: Vec<HostAsset>,
+    /// Version of every feed; empty until `with_feed_version`, the time of
+    /// the last `sync_feed` after one.
+    feed_version: String,
+    /// Started tasks stay `Running` instead of finishing at once.
+    hold_running: bool,
+    next_id: u64,
+}
+
+struct MockConfig {
+    name: String,
+    comment: String,
+    /// Family → the OIDs selected in it, and whether it is growing.
+    families: BTreeMap<String, (BTreeSet<String>, bool)>,
+}
+
+impl MockConfig {
+    fn families(&self) -> Vec<ConfigFamily> {
+        self.families
+            .iter()
+            .map(|(name, (oids, growing))| ConfigFamily {
+                name: name.clone(),
+                nvt_count: oids.len() as u64,
+                max_nvt_count: nvt_family(name).map_or(0, |oids| oids.len() as u64),
+                growing: *growing,
+            })
+            .collect()
+    }
+}
+
+struct MockTask {
+    name: String,
+    config_id: String,
+    target_id: String,
+    schedule_id: Option<String>,
+    scanner_id: Option<String>,
+    status: &'static str,
+    report_id: Option<String>,
+}
+
+impl MockBackend {
+    /// Answer scans of `target` with `raw_output`.
+    pub fn with_scan(self, target: &str, raw_output: &str) -> Self {
+        self.state.lock().unwrap().scans.insert(target.to_string(), raw_output.to_string());
+        self
+    }
+
+    /// Put `findings` in the report of every task that runs.
+    pub fn with_findings(self, findings: Vec<ReportResult>) -> Self {
+        self.state.lock().unwrap().findings = findings;
+        self
+    }
+
+    /// Put `findings` in the reports of tasks started from now on.
+    pub fn set_findings(&self, findings: Vec<ReportResult>) {
+        self.state.lock().unwrap().findings = findings;
+    }
+
+    /// List `hosts` as host assets, ahead of the hosts found in reports.
+    pub fn with_host_assets(self, hosts: Vec<HostAsset>) -> Self {
+        self.state.lock().unwrap().assets = hosts;
+        self
+    }
+
+    /// Report every feed at `version`, e.g. `202401250530`, until synced.
+    pub fn with_feed_version(self, version: &str) -> Self {
+        self.state.lock().unwrap().feed_version = version.to_string();
+        self
+    }
+
+    /// Keep started tasks `Running`.
+    pub fn with_running_tasks(self) -> Self {
+        self.state.lock().unwrap().hold_running = true;
+        self
+    }
+
+    /// Mark every running task `Interrupted`, as a scanner restart does.
+    pub fn interrupt_running_tasks(&self) {
+        let mut state = self.state.lock().unwrap();
+        for task in state.tasks.values_mut().filter(|t| t.status == "Running") {
+            task.status = "Interrupted";
+        }
+    }
+
+    /// Fail the next call with `err`; queued failures are used in order.
+    pub fn fail_next(&self, err: BackendError) {
+        self.state.lock().unwrap().failures.push_back(err);
+    }
+
+    /// The calls made so far, as `method first-argument`.
+    pub fn calls(&self) -> Vec<String> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    /// Record a call and take the state, or the queued failure.
+    fn call(&self, method: &str, arg: &str) -> Result<std::sync::MutexGuard<'_, MockState>> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(format!("{method} {arg}").trim_end().to_string());
+        match state.failures.pop_front() {
+            Some(err) => Err(err.into()),
+            None => Ok(state),
+        }
+    }
+}
+
+impl MockState {
+    fn new_id(&mut self, kind: &str) -> String {
+        self.next_id += 1;
+        format!("{kind}-{}", self.next_id)
+    }
+}
+
+/// gvmd's predefined scanners: the default OpenVAS scanner, then the CVE
+/// scanner.
+fn predefined_scanners() -> [ScannerSummary; 2] {
+    let scanner = |id: &str, name: &str, scanner_type: &str| ScannerSummary {
+        id: id.to_string(),
+        name: name.to_string(),
+        scanner_type: scanner_type.to_string(),
+        host: String::new(),
+        port: String::new(),
+        in_use: false,
+        created: None,
+    };
+    [
+        scanner("08b69003-5fc2-4037-a479-93b440211c73", "OpenVAS Default", "openvas"),
+        scanner("6acd0832-df90-11e4-b9d5-28d24461215b", "CVE", "cve"),
+    ]
+}
+
+/// gvmd's predefined "Full and fast" scan config, which always exists.
+const FULL_AND_FAST: &str = "daba56c8-73ec-11df-a475-002264764cea";
+
+/// The NVT feed: a few families and the OIDs in each.
+const NVT_FEED: [(&str, [&str; 2]); 3] = [
+    ("Denial of Service", ["1.3.6.1.4.1.25623.1.0.10001", "1.3.6.1.4.1.25623.1.0.10002"]),
+    ("General", ["1.3.6.1.4.1.25623.1.0.10101", "1.3.6.1.4.1.25623.1.0.10102"]),
+    ("Web application abuses", ["1.3.6.1.4.1.25623.1.0.10201", "1.3.6.1.4.1.25623.1.0.10202"]),
+];
+
+/// The OIDs of `family` in the feed.
+fn nvt_family(family: &str) -> Option<&'static [&'static str; 2]> {
+    NVT_FEED.iter().find(|(name, _)| *name == family).map(|(_, oids)| oids)
+}
+
+/// The family of the NVT `oid` in the feed.
+fn family_of(oid: &str) -> Option<&'static str> {
+    NVT_FEED.iter().find(|(_, oids)| oids.contains(&oid)).map(|(name, _)| *name)
+}
+
+/// "Full and fast": every family of the feed, growing.
+fn full_and_fast() -> MockConfig {
+    MockConfig {
+        name: "Full and fast".to_string(),
+        comment: String::new(),
+        families: NVT_FEED
+            .iter()
+            .map(|(name, oids)| (name.to_string(), (oids.iter().map(|o| o.to_string()).collect(), true)))
+            .collect(),
+    }
+}
+
+/// The gvmd users tickets can be assigned to.
+const USERS: [&str; 2] = ["admin", "ops"];
+
+fn check_user(name: &str) -> Result<String> {
+    let name = name.trim();
+    if USERS.contains(&name) { Ok(name.to_string()) } else { Err(not_found("user", name)) }
+}
+
+/// gvmd's predefined "All IANA assigned TCP" port list, which always exists.
+fn predefined_port_list() -> PortListSummary {
+    PortListSummary {
+        id: "33d0cd82-57c6-11e1-8ed1-406186ea4fc5".to_string(),
+        name: "All IANA assigned TCP".to_string(),
+        comment: String::new(),
+        port_count: PortCount { all: 5836, tcp: 5836, udp: 0 },
+        in_use: false,
+        predefined: true,
+    }
+}
+
+/// Whether the result `finding` of `report_id` passes the few gvmd filter
+/// keywords the mock understands: `severity>`/`severity<`, `host=`,
+/// `report_id=`, `task_id=` and `name~`. Others, such as sorting, are
+/// ignored here, as are `and` between terms.
+fn filter_keeps(filter: &str, report_id: &str, task_id: Option<&str>, finding: &ReportResult) -> Result<bool> {
+    for term in filter.split_whitespace().filter(|t| *t != "and") {
+        let keeps = if let Some(min) = term.strip_prefix("severity>") {
+            finding.severity > min.parse::<f64>()?
+        } else if let Some(max) = term.strip_prefix("severity<") {
+            finding.severity < max.parse::<f64>()?
+        } else if let Some(host) = term.strip_prefix("host=") {
+            finding.host == host
+        } else if let Some(id) = term.strip_prefix("report_id=") {
+            report_id == id
+        } else if let Some(id) = term.strip_prefix("task_id=") {
+            task_id == Some(id)
+        } else if let Some(name) = term.strip_prefix("name~") {
+            finding.name.to_lowercase().contains(&name.to_lowercase())
+        } else {
+            true
+        };
+        if !keeps {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The results of a GVM-format `<report>`, whether they sit directly under
+/// it or under an inner `<report>` as in `get_reports` output.
+fn imported_results(report_xml: &str) -> Result<Vec<ReportResult>> {
+    fn child<'a, 'i>(node: roxmltree::Node<'a, 'i>, name: &str) -> Option<roxmltree::Node<'a, 'i>> {
+        node.children().find(|c| c.has_tag_name(name))
+    }
+    fn text(node: roxmltree::Node, name: &str) -> String {
+        child(node, name).and_then(|c| c.text()).unwrap_or_default().trim().to_string()
+    }
+    let doc = roxmltree::Document::parse(report_xml)?;
+    let root = doc.root_element();
+    let report = child(root, "report").unwrap_or(root);
+    Ok(child(report, "results")
+        .into_iter()
+        .flat_map(|results| results.children().filter(|c| c.has_tag_name("result")))
+        .map(|r| ReportResult {
+            id: r.attribute("id").unwrap_or_default().to_string(),
+            name: text(r, "name"),
+            host: text(r, "host"),
+            port: text(r, "port"),
+            nvt_oid: child(r, "nvt").and_then(|n| n.attribute("oid")).unwrap_or_default().to_string(),
+            severity: text(r, "severity").parse().unwrap_or_default(),
+            threat: text(r, "threat"),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        })
+        .collect())
+}
+
+fn not_found(what: &str, id: &str) -> anyhow::Error {
+    BackendError::from_response(404, &format!("Failed to find {what} '{id}'")).into()
+}
+
+#[async_trait]
+impl NmapBackend for MockBackend {
+    async fn advanced_scan(&self, request: &ScanRequest) -> Result<ScanResult> {
+        let state = self.call("advanced_scan", &request.target)?;
+        let raw_output = state.scans.get(&request.target).cloned().unwrap_or_else(|| {
+            format!("Nmap scan report for {}\nHost is up.\nNmap done: 1 IP address (1 host up)\n", request.target)
+        });
+        if let Some(err) = BackendError::from_nmap_output(&raw_output) {
+            return Err(err.into());
+        }
+        Ok(ScanResult {
+            target: request.target.clone(),
+            parsed: nmap_xml::parse_output(&raw_output),
+            raw_output,
+        })
+    }
+}
+
+#[async_trait]
+impl OpenvasBackend for MockBackend {
+    async fn get_version(&self) -> Result<Version> {
+        drop(self.call("get_version", "")?);
+        Ok(Version {
+            version_raw: r#"<get_version_response status="200" status_text="OK"><version>22.7</version></get_version_response>"#
+                .to_string(),
+        })
+    }
+
+    async fn list_configs(&self) -> Result<ConfigList> {
+        let state = self.call("list_configs", "")?;
+        let configs = std::iter::once((FULL_AND_FAST, &full_and_fast()))
+            .chain(state.configs.iter().map(|(id, c)| (id.as_str(), c)))
+            .map(|(id, c)| ScanConfig { id: id.to_string(), name: c.name.clone(), comment: c.comment.clone() })
+            .collect();
+        Ok(ConfigList { configs })
+    }
+
+    async fn clone_config(&self, config_id: &str, name: &str, comment: Option<&str>) -> Result<CreatedObject> {
+        let mut state = self.call("clone_config", config_id)?;
+        let families = match state.configs.get(config_id) {
+            Some(config) => config.families.clone(),
+            None if config_id == FULL_AND_FAST => full_and_fast().families,
+            None => return Err(not_found("config", config_id)),
+        };
+        let id = state.new_id("config");
+        let config = MockConfig {
+            name: name.to_string(),
+            comment: comment.unwrap_or_default().to_string(),
+            families,
+        };
+        state.configs.insert(id.clone(), config);
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn modify_config(&self, config_id: &str, changes: &ConfigChanges) -> Result<ModifiedConfig> {
+        let mut state = self.call("modify_config", config_id)?;
+        if config_id == FULL_AND_FAST {
+            return Err(BackendError::from_gmp_xml(
+                r#"<modify_config_response status="403" status_text="Permission denied"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        let config = state.configs.get_mut(config_id).ok_or_else(|| not_found("config", config_id))?;
+        let nvts = changes.enable_nvts.iter().map(|oid| (oid, true));
+        let nvts: Vec<(&str, &str, bool)> = nvts
+            .chain(changes.disable_nvts.iter().map(|oid| (oid, false)))
+            .map(|(oid, enable)| Ok((oid.as_str(), family_of(oid).ok_or_else(|| not_found("NVT", oid))?, enable)))
+            .collect::<Result<_>>()?;
+
+        // Like gvmd, families not in the feed select nothing.
+        for family in &changes.disable_families {
+            config.families.remove(family.as_str());
+        }
+        for family in &changes.enable_families {
+            if let Some(oids) = nvt_family(family) {
+                config.families.insert(family.clone(), (oids.iter().map(|o| o.to_string()).collect(), true));
+            }
+        }
+        for (oid, family, enable) in nvts {
+            let (oids, growing) = config.families.entry(family.to_string()).or_default();
+            if enable {
+                oids.insert(oid.to_string());
+            } else {
+                oids.remove(oid);
+                *growing = false;
+            }
+        }
+        config.families.retain(|_, (oids, _)| !oids.is_empty());
+        Ok(ModifiedConfig { config_id: config_id.to_string(), families: config.families(), notes: Vec::new() })
+    }
+
+    async fn get_nvt(&self, oid: &str) -> Result<NvtDetails> {
+        let state = self.call("get_nvt", oid)?;
+        // The NVTs of the feed, and those behind the findings.
+        let finding = state.findings.iter().chain(state.reports.values().flatten()).find(|r| r.nvt_oid == oid);
+        let family = family_of(oid);
+        if finding.is_none() && family.is_none() {
+            return Err(not_found("NVT", oid));
+        }
+        Ok(NvtDetails {
+            oid: oid.to_string(),
+            name: finding.map_or_else(|| format!("NVT {oid}"), |r| r.name.clone()),
+            family: family.unwrap_or("General").to_string(),
+            cvss_base: finding.map(|r| r.severity),
+            solution: finding.and_then(|r| r.solution.clone()),
+            references: finding
+                .into_iter()
+                .flat_map(|r| &r.cves)
+                .map(|cve| NvtReference { ref_type: "cve".to_string(), id: cve.clone() })
+                .collect(),
+            ..NvtDetails::default()
+        })
+    }
+
+    async fn list_feeds(&self) -> Result<FeedList> {
+        let state = self.call("list_feeds", "")?;
+        let feed = |feed_type: &str, name: &str| FeedSummary {
+            feed_type: feed_type.to_string(),
+            name: name.to_string(),
+            version: state.feed_version.clone(),
+            description: String::new(),
+            currently_syncing: false,
+            last_update: None,
+            age_hours: None,
+            stale: false,
+        };
+        let feeds = vec![
+            feed("NVT", "Greenbone Community Feed"),
+            feed("SCAP", "Greenbone Community SCAP Feed"),
+            feed("CERT", "Greenbone Community CERT Feed"),
+            feed("GVMD_DATA", "Greenbone Community gvmd Data Feed"),
+        ];
+        Ok(FeedList { feeds, warnings: Vec::new() })
+    }
+
+    async fn sync_feed(&self, feed: FeedType) -> Result<SyncedFeed> {
+        let mut state = self.call("sync_feed", serde_json::to_value(feed)?.as_str().unwrap_or_default())?;
+        state.feed_version = chrono::Utc::now().format("%Y%m%d%H%M").to_string();
+        Ok(SyncedFeed { feed, output: "Feed sync complete.\n".to_string() })
+    }
+
+    async fn list_host_assets(&self) -> Result<HostAssetList> {
+        let state = self.call("list_host_assets", "")?;
+        let mut hosts = state.assets.clone();
+        // Like gvmd: every host a report has results for, with the highest
+        // severity of the latest such report.
+        for (report_id, findings) in &state.reports {
+            let report_hosts: BTreeSet<&str> = findings.iter().map(|r| r.host.as_str()).collect();
+            for host in report_hosts {
+                let severity = findings.iter().filter(|r| r.host == host).map(|r| r.severity).fold(0.0, f64::max);
+                let index = match hosts.iter().position(|h| h.ip == host) {
+                    Some(index) => index,
+                    None => {
+                        let id = format!("asset-{host}");
+                        hosts.push(HostAsset { id, ip: host.to_string(), ..HostAsset::default() });
+                        hosts.len() - 1
+                    }
+                };
+                hosts[index].severity = Some(severity);
+                hosts[index].report_ids.push(report_id.clone());
+            }
+        }
+        Ok(HostAssetList { hosts })
+    }
+
+    async fn create_ticket(&self, result_id: &str, assignee: &str, note: &str) -> Result<CreatedObject> {
+        let mut state = self.call("create_ticket", result_id)?;
+        let assignee = check_user(assignee)?;
+        let (report_id, finding) = state
+            .reports
+            .iter()
+            .find_map(|(report_id, findings)| findings.iter().find(|r| r.id == result_id).map(|r| (report_id, r)))
+            .ok_or_else(|| not_found("result", result_id))?;
+        let task_id = state.tasks.iter().find(|(_, t)| t.report_id.as_ref() == Some(report_id)).map(|(id, _)| id);
+        let mut ticket = Ticket {
+            name: finding.name.clone(),
+            status: "Open".to_string(),
+            assignee: Some(assignee),
+            host: Some(finding.host.clone()),
+            location: Some(finding.port.clone()),
+            severity: Some(finding.severity),
+            result_id: Some(result_id.to_string()),
+            task_id: task_id.cloned(),
+            report_id: Some(report_id.clone()),
+            open_time: Some(chrono::Utc::now().to_rfc3339()),
+            open_note: Some(note.to_string()),
+            ..Ticket::default()
+        };
+        ticket.id = state.new_id("ticket");
+        let id = ticket.id.clone();
+        state.tickets.insert(id.clone(), ticket);
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_tickets(&self) -> Result<TicketList> {
+        let state = self.call("list_tickets", "")?;
+        Ok(TicketList { tickets: state.tickets.values().cloned().collect() })
+    }
+
+    async fn modify_ticket(&self, ticket_id: &str, changes: &TicketChanges) -> Result<Ticket> {
+        let mut state = self.call("modify_ticket", ticket_id)?;
+        let assignee = changes.assignee.as_deref().map(check_user).transpose()?;
+        let ticket = state.tickets.get_mut(ticket_id).ok_or_else(|| not_found("ticket", ticket_id))?;
+        if let Some(assignee) = assignee {
+            ticket.assignee = Some(assignee);
+        }
+        if let Some(status) = changes.status {
+            let (time, note) = match status {
+                TicketStatus::Open => (&mut ticket.open_time, &mut ticket.open_note),
+                TicketStatus::Fixed => (&mut ticket.fixed_time, &mut ticket.fixed_note),
+                TicketStatus::Closed => (&mut ticket.closed_time, &mut ticket.closed_note),
+            };
+            *time = Some(chrono::Utc::now().to_rfc3339());
+            *note = changes.note.clone();
+            ticket.status = status.as_str().to_string();
+        }
+        Ok(ticket.clone())
+    }
+
+    async fn list_scanners(&self) -> Result<ScannerList> {
+        let state = self.call("list_scanners", "")?;
+        let scanners = predefined_scanners()
+            .into_iter()
+            .map(|mut s| {
+                s.in_use = state.tasks.values().any(|t| t.scanner_id.as_ref() == Some(&s.id));
+                s
+            })
+            .collect();
+        Ok(ScannerList { scanners })
+    }
+
+    async fn create_target(
+        &self,
+        name: &str,
+        hosts: &str,
+        _port_range: Option<&str>,
+        port_list_id: Option<&str>,
+    ) -> Result<CreatedObject> {
+        let mut state = self.call("create_target", name)?;
+        if let Some(port_list_id) = port_list_id
+            && port_list_id != predefined_port_list().id
+            && !state.port_lists.contains_key(port_list_id)
+        {
+            return Err(not_found("port_list", port_list_id));
+        }
+        let key = (name.to_string(), hosts.to_string());
+        if let Some(id) = state.targets.get(&key) {
+            return Ok(CreatedObject { id: id.clone(), existed: true });
+        }
+        let id = state.new_id("target");
+        state.targets.insert(key, id.clone());
+        if let Some(port_list_id) = port_list_id {
+            state.target_port_lists.insert(id.clone(), port_list_id.to_string());
+        }
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_targets(&self) -> Result<TargetList> {
+        let state = self.call("list_targets", "")?;
+        let targets = state
+            .targets
+            .iter()
+            .map(|((name, hosts), id)| TargetSummary {
+                id: id.clone(),
+                name: name.clone(),
+                hosts: hosts.clone(),
+                port_list: state
+                    .target_port_lists
+                    .get(id)
+                    .and_then(|p| state.port_lists.get(p).map(|p| p.name.clone()))
+                    .or_else(|| state.target_port_lists.contains_key(id).then(|| predefined_port_list().name)),
+                in_use: state.tasks.values().any(|t| &t.target_id == id),
+                created: None,
+            })
+            .collect();
+        Ok(TargetList { targets })
+    }
+
+    async fn delete_target(&self, target_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        let mut state = self.call("delete_target", target_id)?;
+        if state.tasks.values().any(|t| t.target_id == target_id) {
+            return Err(BackendError::from_gmp_xml(
+                r#"<delete_target_response status="400" status_text="Target is in use"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        let Some(key) = state.targets.iter().find(|(_, id)| *id == target_id).map(|(key, _)| key.clone()) else {
+            return Err(not_found("target", target_id));
+        };
+        state.targets.remove(&key);
+        if !ultimate {
+            state.trashed_targets.insert(key, target_id.to_string());
+        }
+        Ok(DeletedObject {
+            id: target_id.to_string(),
+            ultimate,
+            response_raw: r#"<delete_target_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn create_port_list(&self, name: &str, port_range: &str, comment: Option<&str>) -> Result<CreatedObject> {
+        let mut state = self.call("create_port_list", name)?;
+        let Some(port_count) = PortCount::of_range(port_range) else {
+            return Err(BackendError::from_gmp_xml(
+                r#"<create_port_list_response status="400" status_text="Invalid port range"/>"#,
+            )
+            .unwrap()
+            .into());
+        };
+        let id = state.new_id("port_list");
+        let port_list = PortListSummary {
+            id: id.clone(),
+            name: name.to_string(),
+            comment: comment.unwrap_or_default().to_string(),
+            port_count,
+            in_use: false,
+            predefined: false,
+        };
+        state.port_lists.insert(id.clone(), port_list);
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_port_lists(&self) -> Result<PortListList> {
+        let state = self.call("list_port_lists", "")?;
+        let port_lists = std::iter::once(predefined_port_list())
+            .chain(state.port_lists.values().cloned())
+            .map(|mut p| {
+                p.in_use = state.target_port_lists.values().any(|id| *id == p.id);
+                p
+            })
+            .collect();
+        Ok(PortListList { port_lists })
+    }
+
+    async fn create_credential(&self, spec: &CredentialSpec) -> Result<CreatedObject> {
+        let mut state = self.call("create_credential", &spec.name)?;
+        let id = state.new_id("credential");
+        state.credentials.insert(id.clone(), (spec.clone(), false));
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_credentials(&self) -> Result<CredentialList> {
+        let state = self.call("list_credentials", "")?;
+        let credentials = state
+            .credentials
+            .iter()
+            .map(|(id, (spec, in_use))| CredentialSummary {
+                id: id.clone(),
+                name: spec.name.clone(),
+                credential_type: spec.credential_type.clone(),
+                login: spec.login.clone(),
+                in_use: *in_use,
+                created: None,
+            })
+            .collect();
+        Ok(CredentialList { credentials })
+    }
+
+    async fn attach_credential(
+        &self,
+        target_id: &str,
+        credential_id: &str,
+        kind: CredentialKind,
+        _port: Option<u16>,
+    ) -> Result<AttachedCredential> {
+        let mut state = self.call("attach_credential", target_id)?;
+        if !state.targets.values().any(|id| id == target_id) {
+            return Err(not_found("target", target_id));
+        }
+        if state.tasks.values().any(|t| t.target_id == target_id) {
+            return Err(BackendError::from_gmp_xml(
+                r#"<modify_target_response status="400" status_text="Target is in use"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        let (_, in_use) = state
+            .credentials
+            .get_mut(credential_id)
+            .ok_or_else(|| not_found("credential", credential_id))?;
+        *in_use = true;
+        Ok(AttachedCredential {
+            target_id: target_id.to_string(),
+            credential_id: credential_id.to_string(),
+            kind,
+            response_raw: r#"<modify_target_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn create_schedule(&self, spec: &ScheduleSpec) -> Result<CreatedObject> {
+        let mut state = self.call("create_schedule", &spec.name)?;
+        let id = state.new_id("schedule");
+        state.schedules.insert(id.clone(), spec.clone());
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_schedules(&self) -> Result<ScheduleList> {
+        let state = self.call("list_schedules", "")?;
+        let schedules = state
+            .schedules
+            .iter()
+            .map(|(id, spec)| {
+                let task_ids: Vec<String> = state
+                    .tasks
+                    .iter()
+                    .filter(|(_, task)| task.schedule_id.as_deref() == Some(id.as_str()))
+                    .map(|(task_id, _)| task_id.clone())
+                    .collect();
+                ScheduleSummary {
+                    id: id.clone(),
+                    name: spec.name.clone(),
+                    icalendar: spec.icalendar.clone(),
+                    timezone: spec.timezone.clone().unwrap_or_else(|| "UTC".to_string()),
+                    in_use: !task_ids.is_empty(),
+                    task_ids,
+                    created: None,
+                }
+            })
+            .collect();
+        Ok(ScheduleList { schedules })
+    }
+
+    async fn delete_schedule(&self, schedule_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        let mut state = self.call("delete_schedule", schedule_id)?;
+        if !state.schedules.contains_key(schedule_id) {
+            return Err(not_found("schedule", schedule_id));
+        }
+        if state.tasks.values().any(|t| t.schedule_id.as_deref() == Some(schedule_id)) {
+            return Err(BackendError::from_gmp_xml(
+                r#"<delete_schedule_response status="400" status_text="Schedule is in use"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        state.schedules.remove(schedule_id);
+        Ok(DeletedObject {
+            id: schedule_id.to_string(),
+            ultimate,
+            response_raw: r#"<delete_schedule_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn create_alert(&self, spec: &AlertSpec) -> Result<CreatedObject> {
+        let mut state = self.call("create_alert", &spec.name)?;
+        let id = state.new_id("alert");
+        state.alerts.insert(id.clone(), spec.clone());
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn create_task(
+        &self,
+        name: &str,
+        config_id: &str,
+        target_id: &str,
+        schedule_id: Option<&str>,
+        scanner_id: Option<&str>,
+    ) -> Result<CreatedObject> {
+        let mut state = self.call("create_task", name)?;
+        if !state.targets.values().any(|id| id == target_id) {
+            return Err(not_found("target", target_id));
+        }
+        if let Some(schedule_id) = schedule_id
+            && !state.schedules.contains_key(schedule_id)
+        {
+            return Err(not_found("schedule", schedule_id));
+        }
+        if let Some(scanner_id) = scanner_id
+            && !predefined_scanners().iter().any(|s| s.id == scanner_id)
+        {
+            return Err(not_found("scanner", scanner_id));
+        }
+        let existing = state.tasks.iter().find(|(_, t)| {
+            t.name == name
+                && t.config_id == config_id
+                && t.target_id == target_id
+                && schedule_id.is_none_or(|s| t.schedule_id.as_deref() == Some(s))
+                && scanner_id.is_none_or(|s| t.scanner_id.as_deref() == Some(s))
+        });
+        if let Some((id, _)) = existing {
+            return Ok(CreatedObject { id: id.clone(), existed: true });
+        }
+        let id = state.new_id("task");
+        state.tasks.insert(
+            id.clone(),
+            MockTask {
+                name: name.to_string(),
+                config_id: config_id.to_string(),
+                target_id: target_id.to_string(),
+                schedule_id: schedule_id.map(str::to_string),
+                scanner_id: scanner_id.map(str::to_string),
+                status: "New",
+                report_id: None,
+            },
+        );
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn create_container_task(&self, name: &str) -> Result<CreatedObject> {
+        let mut state = self.call("create_container_task", name)?;
+        if let Some((id, _)) = state.tasks.iter().find(|(_, t)| t.name == name && t.target_id.is_empty()) {
+            return Ok(CreatedObject { id: id.clone(), existed: true });
+        }
+        let id = state.new_id("task");
+        state.tasks.insert(
+            id.clone(),
+            MockTask {
+                name: name.to_string(),
+                config_id: String::new(),
+                target_id: String::new(),
+                schedule_id: None,
+                scanner_id: None,
+                status: "Done",
+                report_id: None,
+            },
+        );
+        Ok(CreatedObject { id, existed: false })
+    }
+
+    async fn list_tasks(&self) -> Result<TaskList> {
+        let state = self.call("list_tasks", "")?;
+        let tasks = state
+            .tasks
+            .iter()
+            .map(|(id, task)| TaskSummary {
+                id: id.clone(),
+                name: task.name.clone(),
+                status: task.status.to_string(),
+                progress: if task.status == "Done" { -1 } else { 0 },
+                config_id: Some(task.config_id.clone()).filter(|id| !id.is_empty()),
+                config_name: None,
+                target_id: Some(task.target_id.clone()).filter(|id| !id.is_empty()),
+                target_name: None,
+                last_report_id: task.report_id.clone(),
+                last_report_time: None,
+                created: None,
+            })
+            .collect();
+        Ok(TaskList { tasks })
+    }
+
+    async fn delete_task(&self, task_id: &str, ultimate: bool) -> Result<DeletedObject> {
+        let mut state = self.call("delete_task", task_id)?;
+        let task = state.tasks.remove(task_id).ok_or_else(|| not_found("task", task_id))?;
+        if !ultimate {
+            state.trashed_tasks.insert(task_id.to_string(), task);
+        }
+        Ok(DeletedObject {
+            id: task_id.to_string(),
+            ultimate,
+            response_raw: r#"<delete_task_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn modify_task(&self, task_id: &str, changes: &TaskChanges) -> Result<ModifiedTask> {
+        let mut state = self.call("modify_task", task_id)?;
+        if let Some(target_id) = &changes.target_id
+            && !state.targets.values().any(|id| id == target_id)
+        {
+            return Err(not_found("target", target_id));
+        }
+        if let Some(schedule_id) = &changes.schedule_id
+            && !state.schedules.contains_key(schedule_id)
+        {
+            return Err(not_found("schedule", schedule_id));
+        }
+        if let Some(alert_id) = changes.alert_ids.iter().flatten().find(|id| !state.alerts.contains_key(*id)) {
+            return Err(not_found("alert", alert_id));
+        }
+        let task = state.tasks.get_mut(task_id).ok_or_else(|| not_found("task", task_id))?;
+        if (changes.config_id.is_some() || changes.target_id.is_some()) && task.status != "New" {
+            return Err(BackendError::from_gmp_xml(
+                r#"<modify_task_response status="400" status_text="Status must be New to edit scanner"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        if let Some(name) = &changes.name {
+            task.name = name.clone();
+        }
+        if let Some(config_id) = &changes.config_id {
+            task.config_id = config_id.clone();
+        }
+        if let Some(target_id) = &changes.target_id {
+            task.target_id = target_id.clone();
+        }
+        if let Some(schedule_id) = &changes.schedule_id {
+            task.schedule_id = Some(schedule_id.clone());
+        }
+        Ok(ModifiedTask {
+            task_id: task_id.to_string(),
+            response_raw: r#"<modify_task_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn start_task(&self, task_id: &str) -> Result<StartedTask> {
+        let mut state = self.call("start_task", task_id)?;
+        let report_id = state.new_id("report");
+        let status = if state.hold_running { "Running" } else { "Done" };
+        let task = state.tasks.get_mut(task_id).ok_or_else(|| not_found("task", task_id))?;
+        task.status = status;
+        task.report_id = Some(report_id.clone());
+        let findings = state.findings.clone();
+        state.reports.insert(report_id, findings);
+        Ok(StartedTask {
+            task_id: task_id.to_string(),
+            response_raw: r#"<start_task_response status="202" status_text="OK, request submitted"/>"#.to_string(),
+        })
+    }
+
+    async fn stop_task(&self, task_id: &str) -> Result<StoppedTask> {
+        let mut state = self.call("stop_task", task_id)?;
+        let task = state.tasks.get_mut(task_id).ok_or_else(|| not_found("task", task_id))?;
+        if task.status != "Running" {
+            return Err(BackendError::from_gmp_xml(
+                r#"<stop_task_response status="400" status_text="Task is not running"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        task.status = "Stopped";
+        Ok(StoppedTask {
+            task_id: task_id.to_string(),
+            response_raw: r#"<stop_task_response status="202" status_text="OK, request submitted"/>"#.to_string(),
+        })
+    }
+
+    async fn resume_task(&self, task_id: &str) -> Result<ResumedTask> {
+        let mut state = self.call("resume_task", task_id)?;
+        let status = if state.hold_running { "Running" } else { "Done" };
+        let task = state.tasks.get_mut(task_id).ok_or_else(|| not_found("task", task_id))?;
+        if !RESUMABLE_TASK_STATUSES.contains(&task.status) {
+            return Err(BackendError::from_gmp_xml(
+                r#"<resume_task_response status="400" status_text="Task must be in Stopped or Interrupted state"/>"#,
+            )
+            .unwrap()
+            .into());
+        }
+        task.status = status;
+        Ok(ResumedTask {
+            task_id: task_id.to_string(),
+            response_raw: r#"<resume_task_response status="202" status_text="OK, request submitted"/>"#.to_string(),
+        })
+    }
+
+    async fn get_task_status(&self, task_id: &str) -> Result<TaskStatus> {
+        let state = self.call("get_task_status", task_id)?;
+        let task = state.tasks.get(task_id).ok_or_else(|| not_found("task", task_id))?;
+        Ok(TaskStatus {
+            task_id: task_id.to_string(),
+            response_raw: String::new(),
+            status: Some(task.status.to_string()),
+            progress: Some(if task.status == "Done" { -1 } else { 0 }),
+            last_report_id: task.report_id.clone(),
+            ..TaskStatus::default()
+        })
+    }
+
+    async fn get_report(&self, report_id: &str, format: ReportFormat, filter: &ReportFilter) -> Result<Report> {
+        let state = self.call("get_report", report_id)?;
+        let Some(findings) = state.reports.get(report_id) else {
+            return Err(not_found("report", report_id));
+        };
+        let mut report = Report {
+            report_id: report_id.to_string(),
+            response_raw: String::new(),
+            results: Vec::new(),
+            content_type: None,
+            extension: None,
+            content: None,
+            content_base64: None,
+            resource_uri: None,
+        };
+        let findings: Vec<ReportResult> = findings.iter().filter(|f| filter.keeps(f)).cloned().collect();
+        match format {
+            ReportFormat::Xml | ReportFormat::AnonymousXml => {
+                report.results = findings;
+                report.extension = Some("xml".into());
+            }
+            ReportFormat::Csv | ReportFormat::Txt => {
+                let lines = findings
+                    .iter()
+                    .map(|f| format!("{},{},{},{},{}\n", f.host, f.port, f.severity, f.threat, f.name));
+                report.content = Some(lines.collect());
+                report.content_type = Some(if format == ReportFormat::Csv { "text/csv" } else { "text/plain" }.into());
+                report.extension = Some(format.name().into());
+            }
+            ReportFormat::Pdf => {
+                // "%PDF-1.4\n"
+                report.content_base64 = Some("JVBERi0xLjQK".into());
+                report.content_type = Some("application/pdf".into());
+                report.extension = Some("pdf".into());
+            }
+        }
+        Ok(report)
+    }
+
+    async fn list_reports(&self, task_id: Option<&str>) -> Result<ReportList> {
+        let state = self.call("list_reports", task_id.unwrap_or_default())?;
+        let mut counts = SeverityCounts::default();
+        for finding in &state.findings {
+            match finding.threat.as_str() {
+                "High" => counts.high += 1,
+                "Medium" => counts.medium += 1,
+                "Low" => counts.low += 1,
+                "Log" => counts.log += 1,
+                _ => counts.false_positive += 1,
+            }
+        }
+        let severity = state.findings.iter().map(|f| f.severity).fold(0.0, f64::max);
+        let reports = state
+            .tasks
+            .iter()
+            .filter(|(id, _)| task_id.is_none_or(|t| t == id.as_str()))
+            .filter_map(|(id, task)| {
+                Some(ReportSummary {
+                    id: task.report_id.clone()?,
+                    task_id: id.clone(),
+                    task_name: Some(task.name.clone()),
+                    status: task.status.to_string(),
+                    started: None,
+                    finished: None,
+                    severity,
+                    counts: counts.clone(),
+                })
+            })
+            .collect();
+        Ok(ReportList { reports })
+    }
+
+    async fn delete_report(&self, report_id: &str) -> Result<DeletedObject> {
+        let mut state = self.call("delete_report", report_id)?;
+        if state.reports.remove(report_id).is_none() {
+            return Err(not_found("report", report_id));
+        }
+        for task in state.tasks.values_mut().filter(|t| t.report_id.as_deref() == Some(report_id)) {
+            task.report_id = None;
+        }
+        Ok(DeletedObject {
+            id: report_id.to_string(),
+            ultimate: true,
+            response_raw: r#"<delete_report_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn import_report(&self, task_id: &str, report_xml: &str) -> Result<ImportedReport> {
+        let mut state = self.call("import_report", task_id)?;
+        match state.tasks.get(task_id) {
+            None => return Err(not_found("task", task_id)),
+            Some(task) if !task.target_id.is_empty() => {
+                return Err(BackendError::from_gmp_xml(
+                    r#"<create_report_response status="400" status_text="Task must be a container"/>"#,
+                )
+                .unwrap()
+                .into());
+            }
+            Some(_) => {}
+        }
+        let findings = imported_results(report_xml)?;
+        let report_id = state.new_id("report");
+        state.reports.insert(report_id.clone(), findings);
+        state.tasks.get_mut(task_id).unwrap().report_id = Some(report_id.clone());
+        Ok(ImportedReport {
+            report_id,
+            response_raw: r#"<create_report_response status="201" status_text="OK, resource created"/>"#.to_string(),
+        })
+    }
+
+    async fn get_results(&self, filter: &str, first: u64, rows: u64) -> Result<ResultPage> {
+        let state = self.call("get_results", filter)?;
+        let mut matching: Vec<&ReportResult> = Vec::new();
+        for (report_id, findings) in &state.reports {
+            let task_id = state.tasks.iter().find(|(_, t)| t.report_id.as_ref() == Some(report_id)).map(|(id, _)| id);
+            for finding in findings {
+                if filter_keeps(filter, report_id, task_id.map(String::as_str), finding)? {
+                    matching.push(finding);
+                }
+            }
+        }
+        if filter.split_whitespace().any(|t| t == "sort-reverse=severity") {
+            matching.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+        }
+        Ok(ResultPage {
+            results: matching.iter().skip(first as usize - 1).take(rows as usize).map(|r| (*r).clone()).collect(),
+            first,
+            rows,
+            filtered: matching.len() as u64,
+            next_first: None,
+        })
+    }
+
+    async fn cleanup(
+        &self,
+        name_pattern: &str,
+        _retention_days: u64,
+        empty_trashcan: bool,
+        apply: bool,
+    ) -> Result<CleanupReport> {
+        drop(self.call("cleanup", name_pattern)?);
+        Ok(CleanupReport {
+            applied: apply,
+            objects: Vec::new(),
+            deleted_tasks: 0,
+            deleted_targets: 0,
+            trash_tasks: 0,
+            trash_targets: 0,
+            trashcan_emptied: apply && empty_trashcan,
+        })
+    }
+
+    async fn list_trash(&self) -> Result<TrashList> {
+        let state = self.call("list_trash", "")?;
+        let tasks = state.trashed_tasks.iter().map(|(id, task)| TrashedObject {
+            kind: "task".into(),
+            id: id.clone(),
+            name: task.name.clone(),
+        });
+        let targets = state.trashed_targets.iter().map(|((name, _), id)| TrashedObject {
+            kind: "target".into(),
+            id: id.clone(),
+            name: name.clone(),
+        });
+        Ok(TrashList { objects: tasks.chain(targets).collect() })
+    }
+
+    async fn empty_trashcan(&self) -> Result<EmptiedTrashcan> {
+        let mut state = self.call("empty_trashcan", "")?;
+        state.trashed_tasks.clear();
+        state.trashed_targets.clear();
+        Ok(EmptiedTrashcan {
+            response_raw: r#"<empty_trashcan_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+
+    async fn restore(&self, id: &str) -> Result<RestoredObject> {
+        let mut state = self.call("restore", id)?;
+        if let Some(task) = state.trashed_tasks.get(id) {
+            if state.trashed_targets.values().any(|t| *t == task.target_id) {
+                return Err(BackendError::from_gmp_xml(
+                    r#"<restore_response status="409" status_text="Target must be restored first"/>"#,
+                )
+                .unwrap()
+                .into());
+            }
+            let task = state.trashed_tasks.remove(id).unwrap();
+            state.tasks.insert(id.to_string(), task);
+        } else if let Some(key) = state.trashed_targets.iter().find(|(_, t)| *t == id).map(|(key, _)| key.clone()) {
+            state.trashed_targets.remove(&key);
+            state.targets.insert(key, id.to_string());
+        } else {
+            return Err(not_found("resource", id));
+        }
+        Ok(RestoredObject {
+            id: id.to_string(),
+            response_raw: r#"<restore_response status="200" status_text="OK"/>"#.to_string(),
+        })
+    }
+}
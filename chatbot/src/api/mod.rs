@@ -1,4 +1,203 @@
+pub mod backend;
+pub mod errors;
+pub mod fixtures;
+pub mod gmp;
+pub mod gvm_backends;
+pub mod health;
+#[cfg(test)]
+pub mod mock;
 pub mod nmap;
+pub mod nmap_xml;
 pub mod openvas;
 
+use std::path::Path;
+use std::sync::Mutex;
 
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::{config, dry_run, trace};
+use errors::BackendError;
+use fixtures::FixtureMode;
+
+/// URL of a backend endpoint, e.g. `backend_url("/openvas/version")`:
+/// `/openvas/` endpoints on the GVM backend the tool call was routed to
+/// (see `gvm_backends`) or `--openvas-backend-url`, the rest on
+/// `--nmap-backend-url`, both defaulting to `--backend-url`.
+pub fn backend_url(path: &str) -> String {
+    let cfg = config::current();
+    if path.starts_with("/openvas/")
+        && let Some(url) = gvm_backends::selected().as_deref().and_then(|b| b.url().map(str::to_string))
+    {
+        return format!("{url}{path}");
+    }
+    let base = if path.starts_with("/openvas/") { &cfg.openvas_backend_url } else { &cfg.nmap_backend_url };
+    format!("{}{path}", base.as_deref().unwrap_or(&cfg.backend_url))
+}
+
+/// Every configured backend base URL, named GVM backends included; the
+/// only destinations HTTP calls may reach in offline mode.
+fn backend_urls(cfg: &config::AppConfig) -> impl Iterator<Item = &str> {
+    std::iter::once(cfg.backend_url.as_str())
+        .chain(cfg.nmap_backend_url.as_deref())
+        .chain(cfg.openvas_backend_url.as_deref())
+        .chain(gvm_backends::urls())
+}
+
+/// Longest a backend call waits to connect, so an unreachable backend host
+/// is noticed (and counted by `health`) without waiting on the OS.
+const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Shared HTTP client for all backend calls. Kept in a slot (rather than
+/// created per call) so connections are pooled while the server is busy and
+/// can be dropped wholesale when it goes idle.
+static CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+
+/// Return the shared backend HTTP client, creating it on first use (or on the
+/// first use after `release_client`).
+pub fn http_client() -> reqwest::Client {
+    CLIENT
+        .lock()
+        .unwrap()
+        .get_or_insert_with(build_client)
+        .clone()
+}
+
+fn build_client() -> reqwest::Client {
+    // Redirects are checked too, so an allowed backend cannot bounce a call
+    // out to the internet in offline mode.
+    let redirects = reqwest::redirect::Policy::custom(|attempt| {
+        match ensure_allowed(attempt.url().as_str()) {
+            Ok(()) if attempt.previous().len() < 10 => attempt.follow(),
+            Ok(()) => attempt.error("too many redirects"),
+            Err(err) => attempt.error(err.to_string()),
+        }
+    });
+    reqwest::Client::builder()
+        .redirect(redirects)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// In `--offline` mode, refuse any HTTP destination other than the scan
+/// backends. Everything that makes HTTP calls goes through this, so no
+/// enrichment source, webhook or download is contacted in air-gapped runs.
+pub fn ensure_allowed(url: &str) -> Result<()> {
+    let cfg = config::current();
+    if !cfg.offline {
+        return Ok(());
+    }
+    let origin = |u: &str| reqwest::Url::parse(u).ok().map(|u| u.origin());
+    if origin(url).is_some_and(|o| backend_urls(&cfg).any(|b| origin(b) == Some(o.clone()))) {
+        return Ok(());
+    }
+    eprintln!("offline mode: blocked outbound call to {url}");
+    anyhow::bail!("outbound call to {url} blocked: only the scan backend may be contacted in offline mode")
+}
+
+/// Attach the Go backend credentials in `cfg` (`--backend-token` or
+/// `--backend-basic-auth`), if any, to a backend request.
+fn with_credentials(builder: reqwest::RequestBuilder, cfg: &config::AppConfig) -> reqwest::RequestBuilder {
+    if let Some(token) = &cfg.backend_token {
+        builder.bearer_auth(token)
+    } else if let Some((user, password)) = cfg.backend_basic_auth.as_deref().and_then(|c| c.split_once(':')) {
+        builder.basic_auth(user, Some(password))
+    } else {
+        builder
+    }
+}
+
+/// Drop the shared client and with it every pooled backend connection.
+/// The next `http_client` call transparently builds a fresh one.
+pub fn release_client() {
+    CLIENT.lock().unwrap().take();
+}
+
+/// GET `url` from a backend and decode its JSON body.
+pub async fn get_json(url: &str) -> Result<Value> {
+    send("GET", url, None).await
+}
+
+/// POST `body` as JSON to a backend and decode its JSON response.
+pub async fn post_json(url: &str, body: &Value) -> Result<Value> {
+    send("POST", url, Some(body)).await
+}
+
+/// Single choke point for backend HTTP calls, so fixture record/replay (and
+/// anything else that must apply to every call) lives in one place.
+async fn send(method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
+    let cfg = config::current();
+    let fixture_dir = Path::new(&cfg.fixtures_dir);
+    let request = body.cloned().unwrap_or(Value::Null);
+
+    if let Some(stop) = dry_run::intercept(method, url, body) {
+        return Err(stop);
+    }
+
+    if cfg.fixtures == Some(FixtureMode::Replay) {
+        return fixtures::replay(fixture_dir, method, url, &request);
+    }
+
+    ensure_allowed(url)?;
+    health::check(url)?;
+    let client = http_client();
+    let mut builder = match body {
+        Some(b) => client.post(url).json(b),
+        None => client.get(url),
+    };
+    // Calls routed to a named GVM backend carry its credentials instead.
+    builder = match gvm_backends::selected().filter(|b| b.url().is_some_and(|base| url.starts_with(base))) {
+        Some(backend) => backend.with_credentials(builder)?,
+        None => with_credentials(builder, &cfg),
+    };
+    if let Some(trace_id) = trace::current() {
+        builder = builder.header(trace::HEADER, trace_id);
+    }
+    let resp = match builder.send().await {
+        Ok(resp) => resp,
+        Err(err) => {
+            let err = BackendError::from_transport(&err);
+            health::record(url, Err(&err));
+            return Err(err.into());
+        }
+    };
+    let status = resp.status();
+    if !status.is_success() {
+        // Classify the failure instead of surfacing reqwest's bare status line.
+        let body = resp.text().await.unwrap_or_default();
+        let err = BackendError::from_response(status.as_u16(), &body);
+        health::record(url, Err(&err));
+        return Err(err.into());
+    }
+    health::record(url, Ok(()));
+    let response: Value = resp.json().await?;
+
+    if cfg.fixtures == Some(FixtureMode::Record)
+        && let Err(err) = fixtures::record(fixture_dir, method, url, &request, &response)
+    {
+        eprintln!("failed to record fixture: {err}");
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backend_calls_carry_the_configured_credentials() {
+        let authorization = |cfg: config::AppConfig| {
+            let request = with_credentials(http_client().get("http://127.0.0.1:8080/openvas/version"), &cfg)
+                .build()
+                .unwrap();
+            request.headers().get(reqwest::header::AUTHORIZATION).map(|v| v.to_str().unwrap().to_string())
+        };
+        let token = config::AppConfig { backend_token: Some("t0k3n".into()), ..config::AppConfig::default() };
+        assert_eq!(authorization(token).as_deref(), Some("Bearer t0k3n"));
+        let basic = config::AppConfig { backend_basic_auth: Some("agent:pa:ss".into()), ..config::AppConfig::default() };
+        assert_eq!(authorization(basic).as_deref(), Some("Basic YWdlbnQ6cGE6c3M="));
+        assert_eq!(authorization(config::AppConfig::default()), None);
+    }
+}
@@ -1,31 +1,341 @@
 
-use anyhow::Result;
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
 use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+use super::errors::BackendError;
+use super::nmap_xml;
+use crate::models::nmap_scan::{OutputFormat, ScanRequest, ScanResult};
+use crate::tool_error::ToolError;
+use crate::{config, dry_run, partial, progress};
+
+/// Advanced Nmap scan with comprehensive options. The backend answers 200
+/// even when nmap gave up, so fatal errors in `raw_output` are normalized
+/// into errors here. With `--local-nmap` the scan runs here instead (see
+/// `local_scan`).
+pub async fn advanced_scan(request: &ScanRequest) -> Result<ScanResult> {
+    if config::current().local_nmap {
+        return local_scan(request).await;
+    }
+    let body = serde_json::to_value(request)?;
+    dry_run::note_command(&request.nmap_command());
+    let response = super::post_json(&super::backend_url("/scan-open-ports"), &body).await?;
+    parse_scan(response)
+}
+
+/// Run nmap on this host, with the arguments the Go backend would give it,
+/// for running without one (e.g. on a Kali box). Its stdout and stderr make
+/// up `raw_output`, as with the backend. nmap is killed if the call is
+/// cancelled or times out.
+async fn local_scan(request: &ScanRequest) -> Result<ScanResult> {
+    let argv = local_command(request)?;
+    dry_run::note_command(&argv);
+    if let Some(stop) = dry_run::intercept("EXEC", "local nmap", None) {
+        return Err(stop);
+    }
+    let raw_output = run_streaming(&argv, &request.target)
+        .await
+        .map_err(|err| BackendError::from_io(&err))
+        .context("running nmap locally (--local-nmap); is it installed? see doctor")?;
+    parse_scan(json!({ "target": request.target, "raw_output": raw_output }))
+}
+
+/// Run `argv` and return its stdout followed by its stderr. Each open port
+/// or host stdout reports as it is found goes to the client as a
+/// `notifications/progress`, and the output so far is kept as the call's
+/// partial output, returned if the call times out.
+async fn run_streaming(argv: &[String], target: &str) -> std::io::Result<String> {
+    let mut child = Command::new(&argv[0])
+        .args(&argv[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+
+    let mut live = LiveOutput::default();
+    let read_stdout = async {
+        let mut lines = BufReader::new(stdout).split(b'\n');
+        while let Some(line) = lines.next_segment().await? {
+            if let Some(found) = live.push(&String::from_utf8_lossy(&line)) {
+                progress::report(live.found.len() as f64, None, &found);
+                partial::report(json!({
+                    "target": target,
+                    "raw_output": live.raw,
+                    "found": live.found,
+                    "complete": false,
+                }));
+            }
+        }
+        Ok::<_, std::io::Error>(())
+    };
+    let mut errors = Vec::new();
+    let (read, _) = tokio::join!(read_stdout, stderr.read_to_end(&mut errors));
+    read?;
+    child.wait().await?;
+    Ok(format!("{}{}", live.raw, String::from_utf8_lossy(&errors)))
+}
+
+/// nmap's stdout as it arrives, and what it reported found so far.
+#[derive(Debug, Default)]
+struct LiveOutput {
+    raw: String,
+    found: Vec<String>,
+    /// Where the `<host>` element being read starts in `raw`, in XML output.
+    host_start: Option<usize>,
+}
+
+impl LiveOutput {
+    /// Append a line of output; what it reports found, if anything. With
+    /// `-v`, nmap reports each open port as it finds it; otherwise hosts
+    /// are reported as they are done, in normal or XML output.
+    fn push(&mut self, line: &str) -> Option<String> {
+        static DISCOVERED: OnceLock<Regex> = OnceLock::new();
+        let discovered = DISCOVERED
+            .get_or_init(|| Regex::new(r"^Discovered open port (\S+) on (\S+)").expect("valid discovery regex"));
+
+        let start = self.raw.len();
+        self.raw.push_str(line);
+        self.raw.push('\n');
+        let trimmed = line.trim_start();
+        let found = if let Some(caps) = discovered.captures(line) {
+            Some(format!("{} open on {}", &caps[1], &caps[2]))
+        } else if let Some(host) = line.strip_prefix("Nmap scan report for ") {
+            (!host.ends_with("[host down]")).then(|| format!("{host} is up"))
+        } else if trimmed.starts_with("<host ") || trimmed.starts_with("<host>") {
+            self.host_start = Some(start);
+            None
+        } else if trimmed.starts_with("</host>") {
+            let host = self.host_start.take().and_then(|start| nmap_xml::parse_host(&self.raw[start..]))?;
+            let open: Vec<String> =
+                host.ports.iter().filter(|p| p.state == "open").map(|p| format!("{}/{}", p.port, p.protocol)).collect();
+            (host.status == "up").then(|| match open.len() {
+                0 => format!("{} is up, no open port", host.name().unwrap_or_default()),
+                n => format!("{} is up, {n} open port(s): {}", host.name().unwrap_or_default(), open.join(", ")),
+            })
+        } else {
+            None
+        };
+        self.found.extend(found.clone());
+        found
+    }
+}
+
+/// `ScanRequest::nmap_command` for running nmap here, with `-v` for normal
+/// output so open ports are reported as they are found (see
+/// `run_streaming`). XML and greppable output go to stdout, as with the
+/// backend; nmap has no JSON output, and `all` needs files. Ports `all`
+/// become `-p-`. Only the scripts nmap knows by name or category are run:
+/// a path would run any NSE file on this host.
+fn local_command(request: &ScanRequest) -> Result<Vec<String>> {
+    if request.target.trim().starts_with('-') {
+        return Err(ToolError::invalid_input(format!("target `{}` looks like an nmap option", request.target)).into());
+    }
+    if let Some(scripts) = &request.scripts
+        && let Some(script) = scripts.split(',').map(str::trim).find(|s| !s.is_empty() && !is_script_name(s))
+    {
+        let message = format!("script `{script}` is not an nmap script name or category; paths are not run");
+        return Err(ToolError::invalid_input(message).into());
+    }
+    let mut argv = request.nmap_command();
+    if let Some(at) = argv.iter().position(|arg| arg == "-p") {
+        let ports = argv[at + 1].clone();
+        if ports.eq_ignore_ascii_case("all") {
+            argv.splice(at..at + 2, ["-p-".to_string()]);
+        } else if !is_port_spec(&ports) {
+            return Err(ToolError::invalid_input(format!("ports `{ports}` is not an nmap port specification")).into());
+        }
+    }
+    match request.output_format {
+        Some(format @ (OutputFormat::Json | OutputFormat::All)) => {
+            let format = serde_json::to_value(format)?;
+            let message = format!("output_format {format} is not supported with --local-nmap");
+            return Err(ToolError::invalid_input(message).into());
+        }
+        Some(OutputFormat::Normal) | None => argv.insert(1, "-v".to_string()),
+        Some(OutputFormat::Xml | OutputFormat::Greppable) => {}
+    }
+    Ok(argv)
+}
+
+/// A script name (`http-title`) or category (`vuln`), rather than a path,
+/// a file or an option.
+fn is_script_name(script: &str) -> bool {
+    !script.starts_with('-')
+        && script.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
 
-/// Advanced Nmap scan with comprehensive options
-pub async fn advanced_scan(request_body: &Value) -> Result<Value> {
-    let client = reqwest::Client::new();
-    
-    let resp = client
-        .post("http://127.0.0.1:8080/scan-open-ports")
-        .json(request_body)
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let response_body: Value = resp.json().await?;
-    Ok(response_body)
-}
-
-/// Legacy simple scan for backward compatibility
-pub async fn scan_open_ports(target: &str, timing: Option<&str>) -> Result<Value> {
-    let mut body = json!({
-        "target": target
+/// Ports as nmap takes them: `22,80`, `1-1000`, `-1024`, `U:53,T:80-443`.
+fn is_port_spec(ports: &str) -> bool {
+    static SPEC: OnceLock<Regex> = OnceLock::new();
+    let spec = SPEC.get_or_init(|| {
+        Regex::new(r"^(?:[TUSP]:)?(?:\d{1,5}|\d{0,5}-\d{0,5})(?:,(?:[TUSP]:)?(?:\d{1,5}|\d{0,5}-\d{0,5}))*$")
+            .expect("valid port spec regex")
     });
-    
-    if let Some(t) = timing {
-        body["timing"] = json!(t);
+    spec.is_match(ports.trim())
+}
+
+/// Decode a `/scan-open-ports` response, turning nmap's fatal errors into
+/// `BackendError`s and parsing XML output into `parsed`.
+fn parse_scan(response: Value) -> Result<ScanResult> {
+    let mut result: ScanResult = serde_json::from_value(response)
+        .map_err(|err| anyhow::anyhow!("unexpected response from the backend: {err}"))?;
+    if let Some(err) = BackendError::from_nmap_output(&result.raw_output) {
+        return Err(err.into());
+    }
+    result.parsed = nmap_xml::parse_output(&result.raw_output);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::errors::BackendErrorKind;
+    use crate::api::fixtures;
+
+    #[test]
+    fn recorded_scans_parse() {
+        let mut scans = 0;
+        for f in fixtures::checked_in("/scan-open-ports") {
+            let Ok(result) = parse_scan(f.response.clone()) else {
+                continue;
+            };
+            scans += 1;
+            assert_eq!(result.target, f.request["target"]);
+            assert!(result.raw_output.contains("22/tcp    open     ssh"));
+            assert!(result.raw_output.contains("Nmap done: 1 IP address (1 host up)"));
+        }
+        assert!(scans >= 2);
+    }
+
+    #[test]
+    fn recorded_nmap_failure_is_a_backend_error() {
+        let failed = fixtures::checked_in("/scan-open-ports")
+            .into_iter()
+            .find(|f| f.response["raw_output"].as_str().is_some_and(|o| o.contains("QUITTING!")))
+            .expect("fixture of a failed scan");
+        let err = parse_scan(failed.response).unwrap_err();
+        let backend = err.downcast_ref::<BackendError>().unwrap();
+        assert_eq!(backend.kind, BackendErrorKind::ScanFailed);
+        assert!(backend.detail.contains("QUITTING!"));
+        assert!(backend.hint.is_some_and(|h| h.contains("needs root")), "{backend}");
+    }
+
+    #[tokio::test]
+    async fn dry_run_plans_the_backend_request_and_nmap_command() {
+        let request = ScanRequest {
+            target: "dry-run.example".to_string(),
+            timing: Some(crate::models::nmap_scan::Timing::T4),
+            scan_type: Some(crate::models::nmap_scan::ScanType::TcpConnect),
+            ports: Some("1-1000".to_string()),
+            service_detection: true,
+            scripts: Some("default,vuln".to_string()),
+            aggressive: true,
+            traceroute: true,
+            ..Default::default()
+        };
+        let (result, planned) = dry_run::scope(advanced_scan(&request)).await;
+        assert!(result.is_err());
+        let planned = planned.expect("a planned request");
+        assert_eq!(planned.method, "POST");
+        assert!(planned.url.ends_with("/scan-open-ports"));
+        assert_eq!(planned.body["target"], "dry-run.example");
+        assert_eq!(
+            planned.command.as_deref(),
+            Some("nmap -T4 -sT -p 1-1000 -sV --script default,vuln -A dry-run.example")
+        );
+    }
+
+    #[test]
+    fn local_scans_send_machine_readable_output_to_stdout() {
+        let request = |target: &str, output_format| ScanRequest {
+            target: target.to_string(),
+            ports: Some("22,80".to_string()),
+            output_format,
+            ..Default::default()
+        };
+        let argv = local_command(&request("10.0.0.5", Some(OutputFormat::Xml))).unwrap();
+        assert_eq!(argv, ["nmap", "-T2", "-p", "22,80", "-oX", "-", "10.0.0.5"]);
+        let argv = local_command(&request("10.0.0.5", None)).unwrap();
+        assert_eq!((argv[1].as_str(), argv.last().unwrap().as_str()), ("-v", "10.0.0.5"));
+
+        let refused = |request| ToolError::classify(local_command(&request).unwrap_err()).to_string();
+        assert!(refused(request("10.0.0.5", Some(OutputFormat::Json))).contains("\"json\" is not supported"));
+        assert!(refused(request("-iL/etc/shadow", None)).contains("looks like an nmap option"));
+    }
+
+    #[test]
+    fn local_scans_only_run_named_scripts_and_valid_ports() {
+        let request = |ports: &str, scripts: &str| ScanRequest {
+            target: "10.0.0.5".to_string(),
+            ports: Some(ports.to_string()),
+            scripts: Some(scripts.to_string()),
+            output_format: Some(OutputFormat::Xml),
+            ..Default::default()
+        };
+        let argv = local_command(&request("all", "default, http-title")).unwrap();
+        assert_eq!(argv, ["nmap", "-T2", "-p-", "--script", "default, http-title", "-oX", "-", "10.0.0.5"]);
+        assert!(local_command(&request("U:53,T:1-1024,8080-", "vuln")).is_ok());
+
+        let refused = |request| ToolError::classify(local_command(&request).unwrap_err()).to_string();
+        for script in ["/tmp/x.nse", "../x", "x.lua", "C:\\x", "--script-args=a", "default,/tmp/x.nse"] {
+            assert!(refused(request("22", script)).contains("not an nmap script name"), "{script}");
+        }
+        for ports in ["everything", "22;id", "80,", "-p"] {
+            assert!(refused(request(ports, "default")).contains("not an nmap port specification"), "{ports}");
+        }
+    }
+
+    #[test]
+    fn live_output_reports_ports_and_hosts_as_they_are_found() {
+        let mut live = LiveOutput::default();
+        let found: Vec<Option<String>> = [
+            "Initiating Connect Scan at 10:00",
+            "Discovered open port 22/tcp on 10.0.0.5",
+            "Nmap scan report for 10.0.0.4 [host down]",
+            "Nmap scan report for web.example (10.0.0.5)",
+            "<host starttime=\"1\" endtime=\"2\"><status state=\"up\" reason=\"syn-ack\"/>",
+            "<address addr=\"10.0.0.6\" addrtype=\"ipv4\"/>",
+            "<hostnames/>",
+            "<ports><port protocol=\"tcp\" portid=\"443\"><state state=\"open\" reason=\"syn-ack\"/></port>",
+            "</ports>",
+            "</host>",
+        ]
+        .into_iter()
+        .map(|line| live.push(line))
+        .collect();
+        assert_eq!(
+            found.into_iter().flatten().collect::<Vec<_>>(),
+            ["22/tcp open on 10.0.0.5", "web.example (10.0.0.5) is up", "10.0.0.6 is up, 1 open port(s): 443/tcp"]
+        );
+        assert_eq!(live.found.len(), 3);
+        assert!(live.raw.starts_with("Initiating Connect Scan at 10:00\nDiscovered open port"));
+    }
+
+    #[tokio::test]
+    async fn streamed_output_is_kept_as_partial_output() {
+        let script = "printf 'Discovered open port 80/tcp on 10.0.0.7\\nscanning\\n'; echo warning >&2";
+        let argv: Vec<String> = ["sh", "-c", script].map(String::from).into();
+        let slot = partial::Slot::default();
+        let raw = partial::scope(slot.clone(), run_streaming(&argv, "10.0.0.7")).await.unwrap();
+        assert_eq!(raw, "Discovered open port 80/tcp on 10.0.0.7\nscanning\nwarning\n");
+
+        let partial = slot.lock().unwrap().clone().expect("partial output");
+        assert_eq!(partial["found"], json!(["80/tcp open on 10.0.0.7"]));
+        assert_eq!(partial["raw_output"], "Discovered open port 80/tcp on 10.0.0.7\n");
+        assert_eq!((&partial["target"], &partial["complete"]), (&json!("10.0.0.7"), &json!(false)));
+    }
+
+    #[test]
+    fn malformed_response_is_an_error() {
+        let err = parse_scan(serde_json::json!({ "target": "x" })).unwrap_err();
+        assert!(err.to_string().starts_with("unexpected response from the backend"));
     }
-    
-    advanced_scan(&body).await
 }
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use roxmltree::{Document, Node, ParsingOptions};
+
+use crate::models::nmap_scan::{
+    NmapAddress, NmapHost, NmapHostname, NmapOsMatch, NmapPort, NmapScanResult, NmapScript, NmapService,
+};
+
+/// Parse nmap's XML output. nmap's document declares a DOCTYPE, which
+/// roxmltree refuses unless told otherwise; it has no entities to expand.
+pub fn parse(xml: &str) -> Result<NmapScanResult> {
+    let options = ParsingOptions { allow_dtd: true, ..Default::default() };
+    let doc = Document::parse_with_options(xml, options).context("nmap's XML output is not well-formed")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("nmaprun") {
+        anyhow::bail!("expected nmap's <nmaprun> XML output, got <{}>", root.tag_name().name());
+    }
+    Ok(NmapScanResult {
+        args: attr(root, "args").unwrap_or_default(),
+        hosts: children(root, "host").map(host).collect(),
+        summary: child(root, "runstats").and_then(|r| child(r, "finished")).and_then(|f| attr(f, "summary")),
+    })
+}
+
+/// One `<host>` element on its own, as nmap writes it to XML output when
+/// it is done with the host; `None` if it is not one.
+pub fn parse_host(xml: &str) -> Option<NmapHost> {
+    let doc = Document::parse(xml.trim()).ok()?;
+    let root = doc.root_element();
+    root.has_tag_name("host").then(|| host(root))
+}
+
+/// The `<nmaprun>` document in a scan's `raw_output`, parsed; `None` when
+/// there is none, or when nmap was cut short before closing it. The
+/// backend mixes nmap's stderr into the output, so warnings may surround
+/// the document.
+pub fn parse_output(raw: &str) -> Option<NmapScanResult> {
+    let start = raw.find("<?xml").or_else(|| raw.find("<nmaprun"))?;
+    let end = raw.rfind("</nmaprun>")? + "</nmaprun>".len();
+    parse(raw.get(start..end)?).ok()
+}
+
+fn host(node: Node) -> NmapHost {
+    let ports = child(node, "ports").into_iter().flat_map(|p| children(p, "port")).filter_map(port).collect();
+    NmapHost {
+        status: child(node, "status").and_then(|s| attr(s, "state")).unwrap_or_default(),
+        addresses: children(node, "address")
+            .map(|a| NmapAddress {
+                addr: attr(a, "addr").unwrap_or_default(),
+                addr_type: attr(a, "addrtype").unwrap_or_default(),
+                vendor: attr(a, "vendor"),
+            })
+            .collect(),
+        hostnames: child(node, "hostnames")
+            .into_iter()
+            .flat_map(|h| children(h, "hostname"))
+            .map(|h| NmapHostname { name: attr(h, "name").unwrap_or_default(), kind: attr(h, "type") })
+            .collect(),
+        ports,
+        os_matches: child(node, "os")
+            .into_iter()
+            .flat_map(|os| children(os, "osmatch"))
+            .map(|m| NmapOsMatch {
+                name: attr(m, "name").unwrap_or_default(),
+                accuracy: attr(m, "accuracy").and_then(|a| a.parse().ok()).unwrap_or_default(),
+            })
+            .collect(),
+        scripts: child(node, "hostscript").map(scripts).unwrap_or_default(),
+    }
+}
+
+/// A `<port>`; `None` for a port number nmap could not have listed.
+fn port(node: Node) -> Option<NmapPort> {
+    let state = child(node, "state");
+    Some(NmapPort {
+        protocol: attr(node, "protocol").unwrap_or_default(),
+        port: attr(node, "portid")?.parse().ok()?,
+        state: state.and_then(|s| attr(s, "state")).unwrap_or_default(),
+        reason: state.and_then(|s| attr(s, "reason")),
+        service: child(node, "service").map(|s| NmapService {
+            name: attr(s, "name").unwrap_or_default(),
+            product: attr(s, "product"),
+            version: attr(s, "version"),
+            extra_info: attr(s, "extrainfo"),
+            tunnel: attr(s, "tunnel"),
+            cpes: children(s, "cpe").filter_map(|c| c.text()).map(|c| c.trim().to_string()).collect(),
+        }),
+        scripts: scripts(node),
+    })
+}
+
+/// The `<script>` children of `node`, with nmap's output text.
+fn scripts(node: Node) -> Vec<NmapScript> {
+    children(node, "script")
+        .map(|s| NmapScript {
+            id: attr(s, "id").unwrap_or_default(),
+            output: attr(s, "output").map(|o| o.trim().to_string()).unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, name: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|c| c.has_tag_name(name))
+}
+
+fn children<'a, 'i>(node: Node<'a, 'i>, name: &'static str) -> impl Iterator<Item = Node<'a, 'i>> {
+    node.children().filter(move |c| c.has_tag_name(name))
+}
+
+/// Non-empty attribute `name` of `node`.
+fn attr(node: Node, name: &str) -> Option<String> {
+    node.attribute(name).map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCAN: &str = r#"Warning: 10.0.0.9 giving up on port because retransmission cap hit (6).
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nmaprun>
+<?xml-stylesheet href="file:///usr/bin/../share/nmap/nmap.xsl" type="text/xsl"?>
+<nmaprun scanner="nmap" args="nmap -T4 -sV -O --script default -oX - 10.0.0.5 10.0.0.9" start="1760600000" version="7.94">
+<host starttime="1760600001" endtime="1760600042"><status state="up" reason="arp-response" reason_ttl="0"/>
+<address addr="10.0.0.5" addrtype="ipv4"/>
+<address addr="52:54:00:12:34:56" addrtype="mac" vendor="QEMU virtual NIC"/>
+<hostnames>
+<hostname name="web.corp.internal" type="PTR"/>
+</hostnames>
+<ports><extraports state="closed" count="997"><extrareasons reason="reset" count="997" proto="tcp" ports="1,3-4"/></extraports>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack" reason_ttl="64"/><service name="ssh" product="OpenSSH" version="8.9p1 Ubuntu 3ubuntu0.10" extrainfo="Ubuntu Linux; protocol 2.0" ostype="Linux" method="probed" conf="10"><cpe>cpe:/a:openbsd:openssh:8.9p1</cpe><cpe>cpe:/o:linux:linux_kernel</cpe></service><script id="ssh-hostkey" output="&#xa;  256 aa:bb:cc (ECDSA)&#xa;  256 dd:ee:ff (ED25519)"/></port>
+<port protocol="tcp" portid="443"><state state="open" reason="syn-ack" reason_ttl="64"/><service name="http" product="nginx" version="1.18.0" tunnel="ssl" method="probed" conf="10"><cpe>cpe:/a:igor_sysoev:nginx:1.18.0</cpe></service></port>
+<port protocol="tcp" portid="3306"><state state="filtered" reason="no-response" reason_ttl="0"/><service name="mysql" method="table" conf="3"/></port>
+</ports>
+<os><portused state="open" proto="tcp" portid="22"/>
+<osmatch name="Linux 5.0 - 5.14" accuracy="98" line="67636"><osclass type="general purpose" vendor="Linux" osfamily="Linux" osgen="5.X" accuracy="98"/></osmatch>
+<osmatch name="Linux 4.15 - 5.8" accuracy="94" line="66455"/>
+</os>
+<hostscript><script id="clock-skew" output="0s"/></hostscript>
+</host>
+<host starttime="1760600001" endtime="1760600042"><status state="down" reason="no-response" reason_ttl="0"/>
+<address addr="10.0.0.9" addrtype="ipv4"/>
+</host>
+<runstats><finished time="1760600042" timestr="Thu Oct 16 07:34:02 2025" summary="Nmap done at Thu Oct 16 07:34:02 2025; 2 IP addresses (1 host up) scanned in 42.10 seconds" elapsed="42.10" exit="success"/><hosts up="1" down="1" total="2"/>
+</runstats>
+</nmaprun>
+"#;
+
+    #[test]
+    fn xml_output_parses_into_hosts_ports_and_services() {
+        let scan = parse_output(SCAN).expect("the nmaprun document");
+        assert!(scan.args.ends_with("-oX - 10.0.0.5 10.0.0.9"));
+        assert!(scan.summary.unwrap().contains("(1 host up)"));
+        assert_eq!(scan.hosts.len(), 2);
+
+        let web = &scan.hosts[0];
+        assert_eq!(web.status, "up");
+        assert_eq!(web.address(), Some("10.0.0.5"));
+        assert_eq!(web.addresses[1].vendor.as_deref(), Some("QEMU virtual NIC"));
+        assert_eq!(web.hostnames[0].name, "web.corp.internal");
+        assert_eq!(web.hostnames[0].kind.as_deref(), Some("PTR"));
+
+        let ports: Vec<(u16, &str)> = web.ports.iter().map(|p| (p.port, p.state.as_str())).collect();
+        assert_eq!(ports, [(22, "open"), (443, "open"), (3306, "filtered")]);
+        let ssh = web.ports[0].service.as_ref().unwrap();
+        assert_eq!(ssh.product.as_deref(), Some("OpenSSH"));
+        assert_eq!(ssh.version.as_deref(), Some("8.9p1 Ubuntu 3ubuntu0.10"));
+        assert_eq!(ssh.cpes, ["cpe:/a:openbsd:openssh:8.9p1", "cpe:/o:linux:linux_kernel"]);
+        assert_eq!(web.ports[0].reason.as_deref(), Some("syn-ack"));
+        assert!(web.ports[0].scripts[0].output.starts_with("256 aa:bb:cc (ECDSA)"));
+        assert_eq!(web.ports[1].service.as_ref().unwrap().tunnel.as_deref(), Some("ssl"));
+        assert_eq!(web.os_matches[0], NmapOsMatch { name: "Linux 5.0 - 5.14".to_string(), accuracy: 98 });
+        assert_eq!(web.scripts[0].id, "clock-skew");
+
+        assert_eq!(scan.hosts[1].status, "down");
+        assert!(scan.hosts[1].ports.is_empty());
+    }
+
+    #[test]
+    fn output_without_a_complete_document_is_left_unparsed() {
+        assert!(parse_output("22/tcp open ssh\n").is_none());
+        let cut_short = &SCAN[..SCAN.find("</ports>").unwrap()];
+        assert!(parse_output(cut_short).is_none());
+        assert!(parse("<report/>").unwrap_err().to_string().contains("<nmaprun>"));
+    }
+}
@@ -1,25 +1,67 @@
 use anyhow::Result;
+use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
 
+use super::errors::BackendError;
+use crate::models::openvas::{
+    AlertSpec, AttachedCredential, CleanupReport, ConfigChanges, ConfigList, CreatedObject,
+    CredentialKind, CredentialList, CredentialSpec, DeletedObject, EmptiedTrashcan, FeedList,
+    FeedType, HostAssetList, ImportedReport, ModifiedConfig, ModifiedTask, NvtDetails, PortListList,
+    Report, ReportFilter, ReportFormat, ReportList, RestoredObject, ResultPage, ResumedTask,
+    ScannerList, ScheduleList, ScheduleSpec, StartedTask, StoppedTask, SyncedFeed, TargetList,
+    TaskChanges, TaskList, TaskStatus, Ticket, TicketChanges, TicketList, TrashList, Version,
+};
+
 /// Low-level HTTP client for talking to the Go OpenVAS backend.
 /// Currently exposes:
 ///  - "get version"
 ///  - "list configs"
+///  - "clone config"
+///  - "modify config"
+///  - "get NVT"
+///  - "list feeds"
+///  - "sync feed"
+///  - "list host assets"
+///  - "create ticket"
+///  - "list tickets"
+///  - "modify ticket"
+///  - "list scanners"
 ///  - "create target"
+///  - "list targets"
+///  - "delete target"
+///  - "create port list"
+///  - "list port lists"
+///  - "create credential"
+///  - "list credentials"
+///  - "attach credential"
+///  - "create schedule"
+///  - "list schedules"
+///  - "delete schedule"
+///  - "create alert"
 ///  - "create task"
+///  - "create container task"
+///  - "list tasks"
+///  - "delete task"
+///  - "modify task"
 ///  - "start task"
+///  - "stop task"
+///  - "resume task"
 ///  - "get task status"
 ///  - "get report"
-pub async fn get_version() -> Result<Value> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .get("http://127.0.0.1:8080/openvas/version")
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let body: Value = resp.json().await?;
-    Ok(body)
+///  - "list reports"
+///  - "delete report"
+///  - "import report"
+///  - "get results"
+///  - "cleanup"
+///  - "list trash"
+///  - "empty trashcan"
+///  - "restore"
+///
+/// Responses are decoded into the types in `models::openvas`.
+pub async fn get_version() -> Result<Version> {
+    super::get_json(&super::backend_url("/openvas/version"))
+        .await
+        .and_then(decode)
 }
 
 /// Fetch all available OpenVAS scan configurations (profiles) from the Go backend.
@@ -30,78 +72,448 @@ pub async fn get_version() -> Result<Value> {
 ///     ...
 ///   ]
 /// }
-pub async fn list_configs() -> Result<Value> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .get("http://127.0.0.1:8080/openvas/configs")
-        .send()
-        .await?
-        .error_for_status()?;
+pub async fn list_configs() -> Result<ConfigList> {
+    super::get_json(&super::backend_url("/openvas/configs"))
+        .await
+        .and_then(decode)
+}
+
+/// Copy an OpenVAS scan config under a new name via the Go backend.
+/// The Go API:
+///   POST /openvas/configs/clone
+///   body: { "config_id": "...", "name": "...", "comment": "..."? }
+/// returns:
+///   { "id": "<config-id>" }
+pub async fn clone_config(config_id: &str, name: &str, comment: Option<&str>) -> Result<CreatedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("config_id".into(), Value::String(config_id.to_string()));
+    body_map.insert("name".into(), Value::String(name.to_string()));
+    if let Some(comment) = comment {
+        body_map.insert("comment".into(), Value::String(comment.to_string()));
+    }
+
+    super::post_json(&super::backend_url("/openvas/configs/clone"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Enable or disable NVT families and single NVTs in a scan config via the
+/// Go backend.
+/// The Go API:
+///   POST /openvas/configs/modify
+///   body: { "config_id": "...", "enable_families": [...]?, "disable_families": [...]?,
+///           "enable_nvts": [...]?, "disable_nvts": [...]? }
+/// returns:
+///   { "config_id": "...", "families": [ { "name", "nvt_count", "max_nvt_count", "growing" }, ... ] }
+pub async fn modify_config(config_id: &str, changes: &ConfigChanges) -> Result<ModifiedConfig> {
+    let mut body = serde_json::to_value(changes)?;
+    body["config_id"] = Value::String(config_id.to_string());
+
+    super::post_json(&super::backend_url("/openvas/configs/modify"), &body)
+        .await
+        .and_then(decode)
+}
+
+/// Fetch the details of one NVT by OID via the Go backend.
+/// The Go API:
+///   POST /openvas/nvts
+///   body: { "oid": "..." }
+/// returns:
+///   { "oid", "name", "family"?, "cvss_base"?, "cvss_vector"?, "summary"?, "insight"?, "affected"?,
+///     "impact"?, "detection"?, "solution"?, "solution_type"?, "references": [ { "type", "id" }, ... ] }
+pub async fn get_nvt(oid: &str) -> Result<NvtDetails> {
+    let mut body_map = Map::new();
+    body_map.insert("oid".into(), Value::String(oid.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/nvts"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Fetch the version and sync state of every GVM feed via the Go backend.
+/// The Go API:
+///   GET /openvas/feeds
+/// returns:
+///   { "feeds": [ { "type", "name", "version", "description"?, "currently_syncing" }, ... ] }
+pub async fn list_feeds() -> Result<FeedList> {
+    super::get_json(&super::backend_url("/openvas/feeds"))
+        .await
+        .and_then(decode)
+}
+
+/// Sync one GVM feed, or all of them, via the Go backend, which runs
+/// greenbone-feed-sync in the gvmd container.
+/// The Go API:
+///   POST /openvas/feeds/sync
+///   body: { "feed": "nvt"|"scap"|"cert"|"gvmd_data"|"all" }
+/// returns:
+///   { "feed": "...", "output": "<greenbone-feed-sync output>" }
+pub async fn sync_feed(feed: FeedType) -> Result<SyncedFeed> {
+    let mut body_map = Map::new();
+    body_map.insert("feed".into(), serde_json::to_value(feed)?);
+
+    super::post_json(&super::backend_url("/openvas/feeds/sync"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Fetch every host in gvmd's asset database from the Go backend.
+/// The Go API:
+///   GET /openvas/assets/hosts
+/// returns:
+///   { "hosts": [ { "id", "ip", "hostnames"?, "os"?, "os_cpe"?, "severity"?, "first_seen"?, "last_seen"?,
+///                  "report_ids"?, "details"?: { "<name>": "<value>", ... } }, ... ] }
+pub async fn list_host_assets() -> Result<HostAssetList> {
+    super::get_json(&super::backend_url("/openvas/assets/hosts"))
+        .await
+        .and_then(decode)
+}
+
+/// Open a remediation ticket for a result via the Go backend.
+/// The Go API:
+///   POST /openvas/tickets
+///   body: { "result_id": "...", "assignee": "<gvmd user name>", "note": "..." }
+/// returns:
+///   { "id": "<ticket id>" }
+pub async fn create_ticket(result_id: &str, assignee: &str, note: &str) -> Result<CreatedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("result_id".into(), Value::String(result_id.to_string()));
+    body_map.insert("assignee".into(), Value::String(assignee.to_string()));
+    body_map.insert("note".into(), Value::String(note.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/tickets"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Fetch every remediation ticket from the Go backend.
+/// The Go API:
+///   GET /openvas/tickets/list
+/// returns:
+///   { "tickets": [ { "id", "name", "status", "assignee"?, "host"?, "location"?, "severity"?, "solution_type"?,
+///                    "result_id"?, "task_id"?, "report_id"?, "open_time"?, "fixed_time"?,
+///                    "fix_verified_time"?, "closed_time"?, "open_note"?, "fixed_note"?, "closed_note"? }, ... ] }
+pub async fn list_tickets() -> Result<TicketList> {
+    super::get_json(&super::backend_url("/openvas/tickets/list"))
+        .await
+        .and_then(decode)
+}
+
+/// Change a ticket's status, with a note, or its assignee via the Go
+/// backend.
+/// The Go API:
+///   POST /openvas/tickets/modify
+///   body: { "ticket_id": "...", "status"?: "Open"|"Fixed"|"Closed", "note"?: "...", "assignee"?: "..." }
+/// returns:
+///   the ticket afterwards, as in GET /openvas/tickets/list
+pub async fn modify_ticket(ticket_id: &str, changes: &TicketChanges) -> Result<Ticket> {
+    let mut body = serde_json::to_value(changes)?;
+    body["ticket_id"] = Value::String(ticket_id.to_string());
 
-    let body: Value = resp.json().await?;
-    Ok(body)
+    super::post_json(&super::backend_url("/openvas/tickets/modify"), &body)
+        .await
+        .and_then(decode)
+}
+
+/// Fetch every scanner tasks can run on from the Go backend.
+/// The Go API:
+///   GET /openvas/scanners
+/// returns:
+///   { "scanners": [ { "id", "name", "type", "host"?, "port"?, "in_use", "created"? }, ... ] }
+pub async fn list_scanners() -> Result<ScannerList> {
+    super::get_json(&super::backend_url("/openvas/scanners"))
+        .await
+        .and_then(decode)
 }
 
 /// Create (or reuse) an OpenVAS target via the Go backend.
 /// The Go API:
 ///   POST /openvas/targets
-///   body: { "name": "...", "hosts": "...", "port_range": "..."? }
+///   body: { "name": "...", "hosts": "...", "port_range": "..."?, "port_list_id": "..."? }
 /// returns:
 ///   { "id": "<target-id>", "existed": true|false }
 pub async fn create_target(
     name: &str,
     hosts: &str,
     port_range: Option<&str>,
-) -> Result<Value> {
-    let client = reqwest::Client::new();
-
+    port_list_id: Option<&str>,
+) -> Result<CreatedObject> {
     let mut body_map = Map::new();
     body_map.insert("name".into(), Value::String(name.to_string()));
     body_map.insert("hosts".into(), Value::String(hosts.to_string()));
-    if let Some(pr) = port_range {
-        if !pr.trim().is_empty() {
-            body_map.insert("port_range".into(), Value::String(pr.to_string()));
-        }
+    if let Some(pr) = port_range
+        && !pr.trim().is_empty()
+    {
+        body_map.insert("port_range".into(), Value::String(pr.to_string()));
     }
+    if let Some(id) = port_list_id
+        && !id.trim().is_empty()
+    {
+        body_map.insert("port_list_id".into(), Value::String(id.to_string()));
+    }
+
+    super::post_json(&super::backend_url("/openvas/targets"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// List every existing OpenVAS target via the Go backend.
+/// The Go API:
+///   GET /openvas/targets/list
+/// returns:
+///   { "targets": [ { "id", "name", "hosts", "port_list"?, "in_use", "created"? }, ... ] }
+pub async fn list_targets() -> Result<TargetList> {
+    super::get_json(&super::backend_url("/openvas/targets/list"))
+        .await
+        .and_then(decode)
+}
+
+/// Delete an OpenVAS target via the Go backend, into the trashcan unless
+/// `ultimate` is set.
+/// The Go API:
+///   POST /openvas/targets/delete
+///   body: { "target_id": "...", "ultimate": bool }
+/// returns:
+///   { "id": "...", "ultimate": bool, "response_raw": "<delete_target_response XML>" }
+pub async fn delete_target(target_id: &str, ultimate: bool) -> Result<DeletedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("target_id".into(), Value::String(target_id.to_string()));
+    body_map.insert("ultimate".into(), Value::Bool(ultimate));
+
+    super::post_json(&super::backend_url("/openvas/targets/delete"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Create an OpenVAS port list via the Go backend.
+/// The Go API:
+///   POST /openvas/port-lists
+///   body: { "name": "...", "port_range": "T:1-1024,U:53", "comment": "..."? }
+/// returns:
+///   { "id": "<port-list-id>" }
+pub async fn create_port_list(name: &str, port_range: &str, comment: Option<&str>) -> Result<CreatedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("name".into(), Value::String(name.to_string()));
+    body_map.insert("port_range".into(), Value::String(port_range.to_string()));
+    if let Some(comment) = comment {
+        body_map.insert("comment".into(), Value::String(comment.to_string()));
+    }
+
+    super::post_json(&super::backend_url("/openvas/port-lists"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// List every existing OpenVAS port list, predefined ones included, via the
+/// Go backend.
+/// The Go API:
+///   GET /openvas/port-lists/list
+/// returns:
+///   { "port_lists": [ { "id", "name", "comment"?, "port_count": { "all", "tcp", "udp" },
+///                       "in_use", "predefined" }, ... ] }
+pub async fn list_port_lists() -> Result<PortListList> {
+    super::get_json(&super::backend_url("/openvas/port-lists/list"))
+        .await
+        .and_then(decode)
+}
+
+/// Create an OpenVAS credential via the Go backend. The backend never
+/// echoes the secrets back.
+/// The Go API:
+///   POST /openvas/credentials
+///   body: { "name": "...", "type": "up"|"usk", "login": "...", "password": "..."?,
+///           "private_key": "..."?, "comment": "..."? }
+/// returns:
+///   { "id": "<credential-id>" }
+pub async fn create_credential(spec: &CredentialSpec) -> Result<CreatedObject> {
+    super::post_json(&super::backend_url("/openvas/credentials"), &serde_json::to_value(spec)?)
+        .await
+        .and_then(decode)
+}
+
+/// List every existing OpenVAS credential, without secrets, via the Go
+/// backend.
+/// The Go API:
+///   GET /openvas/credentials/list
+/// returns:
+///   { "credentials": [ { "id", "name", "type", "login", "in_use", "created"? }, ... ] }
+pub async fn list_credentials() -> Result<CredentialList> {
+    super::get_json(&super::backend_url("/openvas/credentials/list"))
+        .await
+        .and_then(decode)
+}
+
+/// Set the SSH or SMB credential a target's scans log in with, via the Go
+/// backend.
+/// The Go API:
+///   POST /openvas/targets/credential
+///   body: { "target_id": "...", "credential_id": "...", "kind": "ssh"|"smb", "port": 22? }
+/// returns:
+///   { "target_id", "credential_id", "kind", "response_raw": "<modify_target_response XML>" }
+pub async fn attach_credential(
+    target_id: &str,
+    credential_id: &str,
+    kind: CredentialKind,
+    port: Option<u16>,
+) -> Result<AttachedCredential> {
+    let mut body_map = Map::new();
+    body_map.insert("target_id".into(), Value::String(target_id.to_string()));
+    body_map.insert("credential_id".into(), Value::String(credential_id.to_string()));
+    body_map.insert("kind".into(), serde_json::to_value(kind)?);
+    if let Some(port) = port {
+        body_map.insert("port".into(), Value::from(port));
+    }
+
+    super::post_json(&super::backend_url("/openvas/targets/credential"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Create an OpenVAS schedule via the Go backend.
+/// The Go API:
+///   POST /openvas/schedules
+///   body: { "name": "...", "icalendar": "BEGIN:VCALENDAR...", "timezone": "..."?, "comment": "..."? }
+/// returns:
+///   { "id": "<schedule-id>" }
+pub async fn create_schedule(spec: &ScheduleSpec) -> Result<CreatedObject> {
+    super::post_json(&super::backend_url("/openvas/schedules"), &serde_json::to_value(spec)?)
+        .await
+        .and_then(decode)
+}
+
+/// List every existing OpenVAS schedule via the Go backend.
+/// The Go API:
+///   GET /openvas/schedules/list
+/// returns:
+///   { "schedules": [ { "id", "name", "icalendar", "timezone", "in_use", "task_ids"?, "created"? }, ... ] }
+pub async fn list_schedules() -> Result<ScheduleList> {
+    super::get_json(&super::backend_url("/openvas/schedules/list"))
+        .await
+        .and_then(decode)
+}
 
-    let resp = client
-        .post("http://127.0.0.1:8080/openvas/targets")
-        .json(&Value::Object(body_map))
-        .send()
-        .await?
-        .error_for_status()?;
+/// Delete an OpenVAS schedule via the Go backend, into the trashcan unless
+/// `ultimate` is set.
+/// The Go API:
+///   POST /openvas/schedules/delete
+///   body: { "schedule_id": "...", "ultimate": bool }
+/// returns:
+///   { "id": "...", "ultimate": bool, "response_raw": "<delete_schedule_response XML>" }
+pub async fn delete_schedule(schedule_id: &str, ultimate: bool) -> Result<DeletedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("schedule_id".into(), Value::String(schedule_id.to_string()));
+    body_map.insert("ultimate".into(), Value::Bool(ultimate));
 
-    let body: Value = resp.json().await?;
-    Ok(body)
+    super::post_json(&super::backend_url("/openvas/schedules/delete"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
 }
 
-/// Create (or reuse) an OpenVAS task via the Go backend.
+/// Create an OpenVAS alert via the Go backend. Attach it to tasks with
+/// `modify_task`.
+/// The Go API:
+///   POST /openvas/alerts
+///   body: { "name": "...", "method": "Email"|"HTTP Get", "status": "Done",
+///           "method_data": { "to_address": "...", ... }, "comment": "..."? }
+/// returns:
+///   { "id": "<alert-id>" }
+pub async fn create_alert(spec: &AlertSpec) -> Result<CreatedObject> {
+    super::post_json(&super::backend_url("/openvas/alerts"), &serde_json::to_value(spec)?)
+        .await
+        .and_then(decode)
+}
+
+/// Create (or reuse) an OpenVAS task via the Go backend, attached to a
+/// schedule when `schedule_id` is given and run on `scanner_id` rather than
+/// the default scanner when that is.
 /// The Go API:
 ///   POST /openvas/tasks
-///   body: { "name": "...", "config_id": "...", "target_id": "..." }
+///   body: { "name": "...", "config_id": "...", "target_id": "...", "schedule_id": "..."?,
+///           "scanner_id": "..."? }
 /// returns:
 ///   { "id": "<task-id>", "existed": true|false }
 pub async fn create_task(
     name: &str,
     config_id: &str,
     target_id: &str,
-) -> Result<Value> {
-    let client = reqwest::Client::new();
-
+    schedule_id: Option<&str>,
+    scanner_id: Option<&str>,
+) -> Result<CreatedObject> {
     let mut body_map = Map::new();
     body_map.insert("name".into(), Value::String(name.to_string()));
     body_map.insert("config_id".into(), Value::String(config_id.to_string()));
     body_map.insert("target_id".into(), Value::String(target_id.to_string()));
+    if let Some(schedule_id) = schedule_id {
+        body_map.insert("schedule_id".into(), Value::String(schedule_id.to_string()));
+    }
+    if let Some(scanner_id) = scanner_id {
+        body_map.insert("scanner_id".into(), Value::String(scanner_id.to_string()));
+    }
+
+    super::post_json(&super::backend_url("/openvas/tasks"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Create (or reuse) an OpenVAS container task, which has no target and
+/// only holds imported reports, via the Go backend.
+/// The Go API:
+///   POST /openvas/tasks/container
+///   body: { "name": "..." }
+/// returns:
+///   { "id": "<task-id>", "existed": true|false }
+pub async fn create_container_task(name: &str) -> Result<CreatedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("name".into(), Value::String(name.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/tasks/container"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// List every existing OpenVAS task with its status via the Go backend.
+/// The Go API:
+///   GET /openvas/tasks/list
+/// returns:
+///   { "tasks": [ { "id", "name", "status", "progress", "config_id"?, "config_name"?,
+///       "target_id"?, "target_name"?, "last_report_id"?, "last_report_time"?, "created"? }, ... ] }
+pub async fn list_tasks() -> Result<TaskList> {
+    super::get_json(&super::backend_url("/openvas/tasks/list"))
+        .await
+        .and_then(decode)
+}
+
+/// Delete an OpenVAS task and its reports via the Go backend, into the
+/// trashcan unless `ultimate` is set.
+/// The Go API:
+///   POST /openvas/tasks/delete
+///   body: { "task_id": "...", "ultimate": bool }
+/// returns:
+///   { "id": "...", "ultimate": bool, "response_raw": "<delete_task_response XML>" }
+pub async fn delete_task(task_id: &str, ultimate: bool) -> Result<DeletedObject> {
+    let mut body_map = Map::new();
+    body_map.insert("task_id".into(), Value::String(task_id.to_string()));
+    body_map.insert("ultimate".into(), Value::Bool(ultimate));
 
-    let resp = client
-        .post("http://127.0.0.1:8080/openvas/tasks")
-        .json(&Value::Object(body_map))
-        .send()
-        .await?
-        .error_for_status()?;
+    super::post_json(&super::backend_url("/openvas/tasks/delete"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Change an existing OpenVAS task via the Go backend.
+/// The Go API:
+///   POST /openvas/tasks/modify
+///   body: { "task_id": "...", "name"?, "config_id"?, "target_id"?, "schedule_id"?,
+///           "preferences"?: { "<scanner preference>": "<value>", ... } }
+/// returns:
+///   { "task_id": "...", "response_raw": "<modify_task_response XML>" }
+pub async fn modify_task(task_id: &str, changes: &TaskChanges) -> Result<ModifiedTask> {
+    let mut body = serde_json::to_value(changes)?;
+    body["task_id"] = Value::String(task_id.to_string());
 
-    let body: Value = resp.json().await?;
-    Ok(body)
+    super::post_json(&super::backend_url("/openvas/tasks/modify"), &body)
+        .await
+        .and_then(decode)
 }
 
 /// Start an existing OpenVAS task via the Go backend.
@@ -110,21 +522,44 @@ pub async fn create_task(
 ///   body: { "task_id": "..." }
 /// returns:
 ///   { "task_id": "...", "response_raw": "<start_task_response XML>" }
-pub async fn start_task(task_id: &str) -> Result<Value> {
-    let client = reqwest::Client::new();
+pub async fn start_task(task_id: &str) -> Result<StartedTask> {
+    let mut body_map = Map::new();
+    body_map.insert("task_id".into(), Value::String(task_id.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/tasks/start"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
 
+/// Stop a running OpenVAS task via the Go backend. The partial report of the
+/// stopped scan is kept.
+/// The Go API:
+///   POST /openvas/tasks/stop
+///   body: { "task_id": "..." }
+/// returns:
+///   { "task_id": "...", "response_raw": "<stop_task_response XML>" }
+pub async fn stop_task(task_id: &str) -> Result<StoppedTask> {
     let mut body_map = Map::new();
     body_map.insert("task_id".into(), Value::String(task_id.to_string()));
 
-    let resp = client
-        .post("http://127.0.0.1:8080/openvas/tasks/start")
-        .json(&Value::Object(body_map))
-        .send()
-        .await?
-        .error_for_status()?;
+    super::post_json(&super::backend_url("/openvas/tasks/stop"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Resume a stopped or interrupted OpenVAS task via the Go backend.
+/// The Go API:
+///   POST /openvas/tasks/resume
+///   body: { "task_id": "..." }
+/// returns:
+///   { "task_id": "...", "response_raw": "<resume_task_response XML>" }
+pub async fn resume_task(task_id: &str) -> Result<ResumedTask> {
+    let mut body_map = Map::new();
+    body_map.insert("task_id".into(), Value::String(task_id.to_string()));
 
-    let body: Value = resp.json().await?;
-    Ok(body)
+    super::post_json(&super::backend_url("/openvas/tasks/resume"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
 }
 
 /// Get the current status/details for an existing OpenVAS task via the Go backend.
@@ -132,44 +567,254 @@ pub async fn start_task(task_id: &str) -> Result<Value> {
 ///   POST /openvas/tasks/status
 ///   body: { "task_id": "..." }
 /// returns:
-///   { "task_id": "...", "response_raw": "<get_tasks_response XML>" }
-pub async fn get_task_status(task_id: &str) -> Result<Value> {
-    let client = reqwest::Client::new();
-
+///   { "task_id": "...", "response_raw": "<get_tasks_response XML>",
+///     "status": "Running", "progress": 42, "last_report_id": "..."? }
+/// The parsed fields are missing when gvmd's XML could not be parsed.
+pub async fn get_task_status(task_id: &str) -> Result<TaskStatus> {
     let mut body_map = Map::new();
     body_map.insert("task_id".into(), Value::String(task_id.to_string()));
 
-    let resp = client
-        .post("http://127.0.0.1:8080/openvas/tasks/status")
-        .json(&Value::Object(body_map))
-        .send()
-        .await?
-        .error_for_status()?;
-
-    let body: Value = resp.json().await?;
-    Ok(body)
+    super::post_json(&super::backend_url("/openvas/tasks/status"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
 }
 
-/// Fetch the final OpenVAS report by report ID via the Go backend.
+/// Fetch the final OpenVAS report by report ID via the Go backend, in
+/// `format`. gvmd sends formats other than XML base64-encoded; the backend
+/// decodes text formats and passes binary ones on as they are.
 /// The Go API:
 ///   POST /openvas/reports
-///   body: { "report_id": "..." }
+///   body: { "report_id": "...", "format_id": "..."?, "min_severity": 7.0?, "min_qod": 70? }
 /// returns:
-///   { "report_id": "...", "response_raw": "<get_reports_response XML>" }
-pub async fn get_report(report_id: &str) -> Result<Value> {
-    let client = reqwest::Client::new();
+///   { "report_id": "...", "response_raw": "<get_reports_response XML>",
+///     "results": [ { "id", "name", "host", "port", "nvt_oid", "severity", "threat", "qod" }, ... ]?,
+///     "content_type"?, "extension"?, "content"?, "content_base64"? }
+pub async fn get_report(report_id: &str, format: ReportFormat, filter: &ReportFilter) -> Result<Report> {
+    let mut body = serde_json::to_value(filter)?;
+    body["report_id"] = Value::String(report_id.to_string());
+    if format != ReportFormat::Xml {
+        body["format_id"] = Value::String(format.gvm_id().to_string());
+    }
 
+    super::post_json(&super::backend_url("/openvas/reports"), &body)
+        .await
+        .and_then(decode)
+}
+
+/// List reports, newest first, optionally only those of one task, via the
+/// Go backend.
+/// The Go API:
+///   POST /openvas/reports/list
+///   body: { "task_id": "..."? }
+/// returns:
+///   { "reports": [ { "id", "task_id", "task_name"?, "status", "started"?, "finished"?, "severity",
+///       "counts": { "high", "medium", "low", "log", "false_positive" } }, ... ] }
+pub async fn list_reports(task_id: Option<&str>) -> Result<ReportList> {
+    let mut body_map = Map::new();
+    if let Some(task_id) = task_id {
+        body_map.insert("task_id".into(), Value::String(task_id.to_string()));
+    }
+
+    super::post_json(&super::backend_url("/openvas/reports/list"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Delete an OpenVAS report via the Go backend. Reports have no trashcan,
+/// so this is always for good.
+/// The Go API:
+///   POST /openvas/reports/delete
+///   body: { "report_id": "..." }
+/// returns:
+///   { "id": "...", "ultimate": true, "response_raw": "<delete_report_response XML>" }
+pub async fn delete_report(report_id: &str) -> Result<DeletedObject> {
     let mut body_map = Map::new();
     body_map.insert("report_id".into(), Value::String(report_id.to_string()));
 
-    let resp = client
-        .post("http://127.0.0.1:8080/openvas/reports")
-        .json(&Value::Object(body_map))
-        .send()
-        .await?
-        .error_for_status()?;
+    super::post_json(&super::backend_url("/openvas/reports/delete"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Import a GVM-format report into a container task via the Go backend.
+/// The Go API:
+///   POST /openvas/reports/import
+///   body: { "task_id": "...", "report_xml": "<report>...</report>" }
+/// returns:
+///   { "report_id": "...", "response_raw": "<create_report_response XML>" }
+pub async fn import_report(task_id: &str, report_xml: &str) -> Result<ImportedReport> {
+    let mut body_map = Map::new();
+    body_map.insert("task_id".into(), Value::String(task_id.to_string()));
+    body_map.insert("report_xml".into(), Value::String(report_xml.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/reports/import"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Fetch one page of results across reports, selected by a gvmd filter
+/// string, via the Go backend.
+/// The Go API:
+///   POST /openvas/results
+///   body: { "filter": "severity>6.9 and host=10.0.0.5", "first": 1, "rows": 100 }
+/// returns:
+///   { "results": [ { "id", "name", "host", "port", "nvt_oid", "severity", "threat", "qod" }, ... ],
+///     "first": N, "rows": N, "filtered": N }
+pub async fn get_results(filter: &str, first: u64, rows: u64) -> Result<ResultPage> {
+    let mut body_map = Map::new();
+    body_map.insert("filter".into(), Value::String(filter.to_string()));
+    body_map.insert("first".into(), Value::from(first));
+    body_map.insert("rows".into(), Value::from(rows));
+
+    super::post_json(&super::backend_url("/openvas/results"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Delete agent-created tasks/targets older than a retention window and
+/// optionally empty the trashcan via the Go backend.
+/// The Go API:
+///   POST /openvas/cleanup
+///   body: { "name_pattern": "...", "retention_days": N, "empty_trashcan": bool, "apply": bool }
+/// returns:
+///   { "applied": bool, "objects": [...], "deleted_tasks": N, "deleted_targets": N,
+///     "trash_tasks": N, "trash_targets": N, "trashcan_emptied": bool }
+pub async fn cleanup(
+    name_pattern: &str,
+    retention_days: u64,
+    empty_trashcan: bool,
+    apply: bool,
+) -> Result<CleanupReport> {
+    let mut body_map = Map::new();
+    body_map.insert("name_pattern".into(), Value::String(name_pattern.to_string()));
+    body_map.insert("retention_days".into(), Value::from(retention_days));
+    body_map.insert("empty_trashcan".into(), Value::Bool(empty_trashcan));
+    body_map.insert("apply".into(), Value::Bool(apply));
+
+    super::post_json(&super::backend_url("/openvas/cleanup"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
 
-    let body: Value = resp.json().await?;
-    Ok(body)
+/// List the objects in the GVM trashcan via the Go backend.
+/// The Go API:
+///   GET /openvas/trash
+/// returns:
+///   { "objects": [ { "type": "task" | "target" | ..., "id", "name" }, ... ] }
+pub async fn list_trash() -> Result<TrashList> {
+    super::get_json(&super::backend_url("/openvas/trash"))
+        .await
+        .and_then(decode)
+}
+
+/// Delete everything in the GVM trashcan for good via the Go backend.
+/// The Go API:
+///   POST /openvas/trash/empty
+///   body: {}
+/// returns:
+///   { "response_raw": "<empty_trashcan_response XML>" }
+pub async fn empty_trashcan() -> Result<EmptiedTrashcan> {
+    super::post_json(&super::backend_url("/openvas/trash/empty"), &Value::Object(Map::new()))
+        .await
+        .and_then(decode)
 }
 
+/// Move an object out of the GVM trashcan via the Go backend.
+/// The Go API:
+///   POST /openvas/trash/restore
+///   body: { "id": "..." }
+/// returns:
+///   { "id": "...", "response_raw": "<restore_response XML>" }
+pub async fn restore(id: &str) -> Result<RestoredObject> {
+    let mut body_map = Map::new();
+    body_map.insert("id".into(), Value::String(id.to_string()));
+
+    super::post_json(&super::backend_url("/openvas/trash/restore"), &Value::Object(body_map))
+        .await
+        .and_then(decode)
+}
+
+/// Check a backend response for gvmd errors and decode it into its model.
+fn decode<T: DeserializeOwned>(response: Value) -> Result<T> {
+    let response = check_gmp(response)?;
+    serde_json::from_value(response)
+        .map_err(|err| anyhow::anyhow!("unexpected response from the backend: {err}"))
+}
+
+/// gvmd errors can arrive inside a successful backend response, as an error
+/// `status` on the raw GMP XML; turn those into normalized errors too.
+fn check_gmp(response: Value) -> Result<Value> {
+    for field in ["response_raw", "version_raw"] {
+        if let Some(err) = response
+            .get(field)
+            .and_then(|v| v.as_str())
+            .and_then(BackendError::from_gmp_xml)
+        {
+            return Err(err.into());
+        }
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::errors::BackendErrorKind;
+    use crate::api::fixtures;
+
+    fn recorded<T: DeserializeOwned>(endpoint: &str) -> Result<T> {
+        decode(fixtures::checked_in(endpoint).remove(0).response)
+    }
+
+    #[test]
+    fn recorded_version_parses() {
+        let version: Version = recorded("/openvas/version").unwrap();
+        assert!(version.version_raw.contains("<version>22.7</version>"));
+    }
+
+    #[test]
+    fn recorded_configs_parse() {
+        let list: ConfigList = recorded("/openvas/configs").unwrap();
+        assert_eq!(list.configs.len(), 2);
+        assert_eq!(list.configs[0].name, "Full and fast");
+        assert_eq!(list.configs[0].id, "daba56c8-73ec-11df-a475-002264764cea");
+        assert_eq!(list.configs[1].comment, "");
+    }
+
+    #[test]
+    fn recorded_nvt_parses() {
+        let nvt: NvtDetails = recorded("/openvas/nvts").unwrap();
+        assert_eq!(nvt.cvss_base, Some(7.8));
+        assert_eq!(nvt.solution_type.as_deref(), Some("VendorFix"));
+        assert_eq!(nvt.references.len(), 4);
+        assert_eq!(nvt.references[3].ref_type, "cert-bund");
+    }
+
+    #[test]
+    fn recorded_task_status_parses() {
+        let status: TaskStatus = recorded("/openvas/tasks/status").unwrap();
+        assert_eq!(status.status.as_deref(), Some("Done"));
+        assert_eq!(status.progress, Some(-1));
+        assert_eq!(
+            status.last_report_id.as_deref(),
+            Some("b2d1f6e8-3a4c-4e9f-8b7d-1c2e3f4a5b6c")
+        );
+    }
+
+    #[test]
+    fn recorded_report_parses() {
+        let report: Report = recorded("/openvas/reports").unwrap();
+        assert_eq!(report.results.len(), 2);
+        let high = &report.results[0];
+        assert_eq!((high.port.as_str(), high.severity, high.threat.as_str()), ("22/tcp", 7.8, "High"));
+        assert_eq!(high.nvt_oid, "1.3.6.1.4.1.25623.1.0.811011");
+    }
+
+    #[test]
+    fn recorded_gmp_error_is_a_backend_error() {
+        let err = recorded::<StartedTask>("/openvas/tasks/start").unwrap_err();
+        let backend = err.downcast_ref::<BackendError>().unwrap();
+        assert_eq!(backend.kind, BackendErrorKind::NotFound);
+        assert_eq!(backend.status, Some(404));
+    }
+}
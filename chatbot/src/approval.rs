@@ -0,0 +1,386 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{config, native, session, targets, ToolAnnotations};
+
+/// How intrusive tool calls are confirmed with the end user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalMode {
+    /// Never ask.
+    Off,
+    /// Ask via MCP elicitation when the client supports it; otherwise run.
+    Auto,
+    /// Ask via MCP elicitation; refuse intrusive calls if the client can't be
+    /// asked. `required` is accepted as an alias.
+    Elicit,
+}
+
+impl ApprovalMode {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(ApprovalMode::Off),
+            "auto" => Ok(ApprovalMode::Auto),
+            "elicit" | "required" => Ok(ApprovalMode::Elicit),
+            other => anyhow::bail!("unknown approval mode `{other}` (expected off, auto or elicit)"),
+        }
+    }
+}
+
+/// How long to wait for the user to answer a confirmation prompt.
+const ELICITATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// NSE script categories that make a scan intrusive.
+const INTRUSIVE_SCRIPTS: &[&str] = &["vuln", "exploit", "intrusive", "brute", "dos"];
+
+/// Why a tool call counts as intrusive. Empty means no confirmation needed.
+pub fn intrusive_reasons(tool: &str, annotations: ToolAnnotations, input: &Value) -> Vec<String> {
+    let mut reasons = Vec::new();
+    // Tools that never send traffic to targets can't be intrusive.
+    if !annotations.open_world_hint {
+        return reasons;
+    }
+
+    if let Some(scripts) = input.get("scripts").and_then(|v| v.as_str()) {
+        let hits: Vec<&str> = scripts
+            .split(',')
+            .map(str::trim)
+            .filter(|s| INTRUSIVE_SCRIPTS.contains(&s.to_ascii_lowercase().as_str()))
+            .collect();
+        if !hits.is_empty() {
+            reasons.push(format!("runs intrusive NSE scripts: {}", hits.join(",")));
+        }
+    }
+    if input.get("include_vuln").and_then(|v| v.as_bool()) == Some(true)
+        || input.get("scan_type").and_then(|v| v.as_str()) == Some("vuln_scan")
+    {
+        reasons.push("runs vulnerability scripts".to_string());
+    }
+    if input
+        .get("timing")
+        .and_then(|v| v.as_str())
+        .is_some_and(|t| t.eq_ignore_ascii_case("T5"))
+    {
+        reasons.push("uses T5 (insane) timing".to_string());
+    }
+    if ["aggressive", "flag_a"]
+        .iter()
+        .any(|k| input.get(*k).and_then(|v| v.as_bool()) == Some(true))
+    {
+        reasons.push("enables aggressive mode (-A)".to_string());
+    }
+    // Tools that are intrusive whatever their input: evasion and full
+    // vulnerability scans.
+    if reasons.is_empty() && is_always_intrusive(tool) {
+        reasons.push(format!("{tool} is an intrusive scan"));
+    }
+
+    reasons
+}
+
+/// nmap's default port count when no `ports` are given.
+const DEFAULT_PORT_COUNT: u64 = 1000;
+
+/// Probes nmap's host discovery sends per host (ICMP echo, TCP SYN/443,
+/// TCP ACK/80, ICMP timestamp).
+const DISCOVERY_PROBES: u64 = 4;
+
+/// Wire bytes per probe for impact estimates: a ~60 byte packet out and a
+/// ~60 byte reply.
+const BYTES_PER_PROBE: u64 = 120;
+
+/// Rough sustained probe rate per timing template, for duration estimates.
+fn probes_per_sec(timing: &str) -> f64 {
+    match timing.to_ascii_uppercase().as_str() {
+        "T0" => 1.0 / 300.0,
+        "T1" => 1.0 / 15.0,
+        "T2" => 2.5,
+        "T4" => 1000.0,
+        "T5" => 3000.0,
+        _ => 300.0,
+    }
+}
+
+/// Estimated impact of a call that touches many hosts.
+#[derive(Debug)]
+pub struct ImpactSummary {
+    pub hosts: u64,
+    pub probes_per_host: u64,
+    pub estimated_secs: u64,
+    pub estimated_bytes: u64,
+}
+
+impl ImpactSummary {
+    /// Human-readable one-liner for the confirmation prompt.
+    pub fn describe(&self) -> String {
+        format!(
+            "bulk operation: {} hosts x {} probes, roughly {} and {} of traffic",
+            self.hosts,
+            self.probes_per_host,
+            human_duration(self.estimated_secs),
+            human_bytes(self.estimated_bytes)
+        )
+    }
+}
+
+/// Impact summary for calls touching more hosts than the configured bulk
+/// threshold. `None` when the call is small or sends no traffic to targets.
+pub fn bulk_impact(tool: &str, annotations: ToolAnnotations, input: &Value) -> Option<ImpactSummary> {
+    if !annotations.open_world_hint {
+        return None;
+    }
+    let hosts: u64 = targets::TARGET_FIELDS
+        .iter()
+        .filter_map(|k| input.get(*k).and_then(|v| v.as_str()))
+        .map(targets::host_count)
+        .fold(0, u64::saturating_add);
+    if hosts <= config::current().bulk_threshold {
+        return None;
+    }
+
+    let discovery_only = tool == "network_discovery"
+        || input.get("scan_type").and_then(|v| v.as_str()) == Some("ping");
+    let probes_per_host = if discovery_only {
+        DISCOVERY_PROBES
+    } else {
+        DISCOVERY_PROBES + port_count(input.get("ports").and_then(|v| v.as_str()))
+    };
+    let timing = input.get("timing").and_then(|v| v.as_str()).unwrap_or("T3");
+    let probes = hosts.saturating_mul(probes_per_host);
+
+    Some(ImpactSummary {
+        hosts,
+        probes_per_host,
+        estimated_secs: (probes as f64 / probes_per_sec(timing)).ceil() as u64,
+        estimated_bytes: probes.saturating_mul(BYTES_PER_PROBE),
+    })
+}
+
+/// Ports per host for an nmap port spec (`all`, `U:53,T:80-443`, ...).
+fn port_count(spec: Option<&str>) -> u64 {
+    match spec.map(str::trim) {
+        None | Some("") => DEFAULT_PORT_COUNT,
+        Some(s) if s.eq_ignore_ascii_case("all") || s == "-" => 65535,
+        Some(s) => {
+            let plain = s.replace("U:", "").replace("T:", "").replace("S:", "");
+            native::parse_ports(&plain)
+                .map(|p| p.len() as u64)
+                .unwrap_or(DEFAULT_PORT_COUNT)
+        }
+    }
+}
+
+fn human_duration(secs: u64) -> String {
+    match secs {
+        s if s < 120 => format!("{s}s"),
+        s if s < 7200 => format!("{}min", s / 60),
+        s if s < 172_800 => format!("{:.1}h", s as f64 / 3600.0),
+        s => format!("{:.1} days", s as f64 / 86_400.0),
+    }
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn is_always_intrusive(tool: &str) -> bool {
+    matches!(tool, "stealth_scan" | "openvas_start_task" | "openvas_resume_task" | "openvas_fan_out" | "openvas_full_scan")
+}
+
+/// Ask the end user to approve an intrusive call. Returns `Ok(())` when the
+/// call may proceed, or an error explaining why it must not.
+pub async fn confirm(tool: &str, input: &Value, reasons: &[String]) -> Result<()> {
+    let mode = config::current().approval_mode;
+    if mode == ApprovalMode::Off || reasons.is_empty() {
+        return Ok(());
+    }
+
+    let session = session::current().filter(|s| s.client_supports("elicitation"));
+    let Some(session) = session else {
+        return unattended(mode, tool, reasons);
+    };
+
+    let target = targets::target_of(input).unwrap_or("(no target)");
+    let message = format!(
+        "Approve {tool} against {target}.\nReasons: {}.\nOnly continue if you are authorized to test this target.",
+        reasons.join("; ")
+    );
+    let approved = ask(
+        &session,
+        &message,
+        "Run this scan?",
+        "Confirm you are authorized and want to run this intrusive action.",
+    )
+    .await?;
+    if !approved {
+        anyhow::bail!("user did not approve {tool} ({})", reasons.join("; "));
+    }
+    Ok(())
+}
+
+/// What happens to a call needing approval when the client can't be asked:
+/// only `auto` lets it through; everything else fails closed.
+fn unattended(mode: ApprovalMode, tool: &str, reasons: &[String]) -> Result<()> {
+    if mode == ApprovalMode::Auto {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "{tool} requires user approval ({}), but the client does not support elicitation; \
+         use a client that does, or start the server with --approval auto",
+        reasons.join("; ")
+    )
+}
+
+/// Ask the end user to approve a change to the machine the server runs on,
+/// e.g. installing packages. The model filling in a `confirm` argument is
+/// not consent, so this always asks, whatever `--approval` says, and
+/// refuses when the client cannot be asked.
+pub async fn confirm_host_change(tool: &str, action: &str) -> Result<()> {
+    let message = format!("{tool} wants to {action} on the machine running hacker_agent.");
+    let approved = ask_user(
+        tool,
+        &format!("to {action}"),
+        &message,
+        "Allow this change?",
+        "Confirm you want this command to run on the server's host.",
+    )
+    .await?;
+    if !approved {
+        anyhow::bail!("user did not approve: {action}");
+    }
+    Ok(())
+}
+
+/// Ask the end user to confirm a scope attestation. Only a person can vouch
+/// that testing is authorized, so, like `confirm_host_change`, this always
+/// asks and refuses when the client cannot be asked.
+pub async fn confirm_attestation(workspace: &str, engagement_reference: &str, authorized_by: &str) -> Result<()> {
+    let message = format!(
+        "Attest that testing in workspace `{workspace}` is authorized under engagement {engagement_reference}, \
+         authorized by {authorized_by}.\nThe attestation is recorded in the audit log under your name."
+    );
+    let approved = ask_user(
+        "attest_scope",
+        "for this attestation",
+        &message,
+        "Record this attestation?",
+        "Confirm that you personally vouch for this authorization.",
+    )
+    .await?;
+    if !approved {
+        anyhow::bail!("user did not confirm the attestation for engagement {engagement_reference}");
+    }
+    Ok(())
+}
+
+/// `ask` the current session's user, failing when the client can't elicit.
+async fn ask_user(tool: &str, need: &str, message: &str, title: &str, description: &str) -> Result<bool> {
+    let Some(session) = session::current().filter(|s| s.client_supports("elicitation")) else {
+        anyhow::bail!("{tool} needs the user's approval {need}, but the client does not support elicitation");
+    };
+    ask(&session, message, title, description).await
+}
+
+/// Put a yes/no question to the user through MCP elicitation.
+async fn ask(session: &session::Session, message: &str, title: &str, description: &str) -> Result<bool> {
+    let result = session
+        .request(
+            "elicitation/create",
+            json!({
+                "message": message,
+                "requestedSchema": {
+                    "type": "object",
+                    "properties": {
+                        "approve": {
+                            "type": "boolean",
+                            "title": title,
+                            "description": description
+                        }
+                    },
+                    "required": ["approve"]
+                }
+            }),
+            ELICITATION_TIMEOUT,
+        )
+        .await?;
+
+    Ok(result.get("action").and_then(|v| v.as_str()) == Some("accept")
+        && result.pointer("/content/approve").and_then(|v| v.as_bool()) == Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_modes() {
+        assert_eq!(ApprovalMode::parse("off").unwrap(), ApprovalMode::Off);
+        assert_eq!(ApprovalMode::parse(" Auto ").unwrap(), ApprovalMode::Auto);
+        assert_eq!(ApprovalMode::parse("elicit").unwrap(), ApprovalMode::Elicit);
+        assert_eq!(ApprovalMode::parse("required").unwrap(), ApprovalMode::Elicit);
+        assert!(ApprovalMode::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn flags_intrusive_input() {
+        let reasons = |tool, input| intrusive_reasons(tool, ToolAnnotations::ACTIVE_SCAN, &input);
+        assert!(reasons("quick_scan", json!({ "target": "a", "timing": "T4" })).is_empty());
+        assert_eq!(
+            reasons("advanced_nmap_scan", json!({ "scripts": "default, VULN,brute", "timing": "t5" })),
+            ["runs intrusive NSE scripts: VULN,brute", "uses T5 (insane) timing"]
+        );
+        assert_eq!(reasons("stealth_scan", json!({ "target": "a" })), ["stealth_scan is an intrusive scan"]);
+        assert!(intrusive_reasons("stealth_scan", ToolAnnotations::READ_ONLY, &json!({ "flag_a": true })).is_empty());
+    }
+
+    #[test]
+    fn estimates_bulk_impact_above_the_threshold() {
+        let impact = |tool, input| bulk_impact(tool, ToolAnnotations::ACTIVE_SCAN, &input);
+        assert!(impact("quick_scan", json!({ "target": "10.0.0.0/24" })).is_none());
+        assert!(bulk_impact("quick_scan", ToolAnnotations::READ_ONLY, &json!({ "target": "10.0.0.0/16" })).is_none());
+
+        let summary = impact("quick_scan", json!({ "target": "10.0.0.0/16" })).unwrap();
+        assert_eq!(summary.hosts, targets::host_count("10.0.0.0/16"));
+        assert_eq!(summary.probes_per_host, DISCOVERY_PROBES + DEFAULT_PORT_COUNT);
+        let probes = summary.hosts * summary.probes_per_host;
+        assert_eq!(summary.estimated_secs, (probes as f64 / 300.0).ceil() as u64);
+        assert_eq!(summary.estimated_bytes, probes * BYTES_PER_PROBE);
+
+        let discovery = impact("network_discovery", json!({ "target": "10.0.0.0/16", "timing": "T4" })).unwrap();
+        assert_eq!(discovery.probes_per_host, DISCOVERY_PROBES);
+        assert!(discovery.estimated_secs < summary.estimated_secs);
+        assert!(discovery.describe().starts_with(&format!("bulk operation: {} hosts x 4 probes", discovery.hosts)));
+    }
+
+    #[test]
+    fn counts_ports_and_formats_estimates() {
+        assert_eq!(port_count(None), DEFAULT_PORT_COUNT);
+        assert_eq!(port_count(Some("all")), 65535);
+        assert_eq!(port_count(Some("22,80,443")), 3);
+        assert_eq!(port_count(Some("U:53,T:80-89")), 11);
+        assert_eq!(port_count(Some("not ports")), DEFAULT_PORT_COUNT);
+
+        assert_eq!(human_duration(90), "90s");
+        assert_eq!(human_duration(600), "10min");
+        assert_eq!(human_duration(5400 * 2), "3.0h");
+        assert_eq!(human_duration(86_400 * 3), "3.0 days");
+        assert_eq!(human_bytes(512), "512.0 B");
+        assert_eq!(human_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn calls_fail_closed_without_elicitation_unless_auto() {
+        let reasons = vec!["runs vulnerability scripts".to_string()];
+        assert!(unattended(ApprovalMode::Auto, "quick_scan", &reasons).is_ok());
+        let err = unattended(ApprovalMode::Elicit, "quick_scan", &reasons).unwrap_err();
+        assert!(err.to_string().contains("runs vulnerability scripts"));
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::session;
+
+/// `prev_hash` of the first record.
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Scope attestation for a workspace: who authorized testing, under which
+/// engagement. Active scans in that workspace reference it in the log.
+#[derive(Debug, Clone, Serialize)]
+pub struct Attestation {
+    pub workspace: String,
+    pub engagement_reference: String,
+    pub authorized_by: String,
+    pub scope: Option<String>,
+    pub attested_by: String,
+    pub attested_at: String,
+    /// Hash of the audit record that holds this attestation.
+    pub record_hash: String,
+}
+
+/// Tamper-evident JSONL log. Every record carries the hash of the one before
+/// it, so editing, reordering or deleting any line but the last breaks the
+/// chain and is detected the next time the server starts. Truncating the
+/// tail is not detectable from the log alone.
+struct AuditLog {
+    file: File,
+    seq: u64,
+    last_hash: String,
+    attestations: HashMap<String, Attestation>,
+}
+
+static LOG: OnceLock<Mutex<AuditLog>> = OnceLock::new();
+
+/// Open (or create) the audit log at `path`, verify its hash chain and
+/// restore the attestations recorded in it. Call once at startup.
+pub fn install(path: &str) -> Result<()> {
+    LOG.set(Mutex::new(open(path)?))
+        .map_err(|_| anyhow::anyhow!("audit log already installed"))
+}
+
+fn open(path: &str) -> Result<AuditLog> {
+    let mut log = AuditLog {
+        file: OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("cannot open audit log {path}"))?,
+        seq: 0,
+        last_hash: GENESIS.to_string(),
+        attestations: HashMap::new(),
+    };
+
+    let reader = BufReader::new(File::open(path)?);
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut record: Value = serde_json::from_str(&line)
+            .with_context(|| format!("audit log {path} line {} is not valid JSON", n + 1))?;
+        let hash = record
+            .as_object_mut()
+            .and_then(|r| r.remove("hash"))
+            .and_then(|h| h.as_str().map(str::to_string))
+            .unwrap_or_default();
+        if record["prev_hash"] != json!(log.last_hash) || hash != chain_hash(&log.last_hash, &record) {
+            anyhow::bail!("audit log {path} has been modified: hash chain broken at line {}", n + 1);
+        }
+        if record["kind"] == "attestation" {
+            let attestation = attestation_from(&record, &hash);
+            log.attestations.insert(attestation.workspace.clone(), attestation);
+        }
+        log.seq = record["seq"].as_u64().unwrap_or(log.seq);
+        log.last_hash = hash;
+    }
+    Ok(log)
+}
+
+/// Whether an audit log is being kept.
+pub fn enabled() -> bool {
+    LOG.get().is_some()
+}
+
+/// Append a record of `kind` for `workspace`. Written and flushed before
+/// returning, so nothing guarded by a record runs without it on disk.
+pub fn record(kind: &str, workspace: &str, detail: Value) -> Result<Value> {
+    let log = LOG
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("no audit log configured (--audit-log)"))?;
+    let operator = session::current().map(|s| s.operator());
+    log.lock().unwrap().append(kind, workspace, operator, detail)
+}
+
+impl AuditLog {
+    fn append(&mut self, kind: &str, workspace: &str, operator: Option<String>, detail: Value) -> Result<Value> {
+        let mut record = json!({
+            "seq": self.seq + 1,
+            "at": Utc::now().to_rfc3339(),
+            "kind": kind,
+            "workspace": workspace,
+            "operator": operator,
+            "detail": detail,
+            "prev_hash": self.last_hash,
+        });
+        let hash = chain_hash(&self.last_hash, &record);
+        record["hash"] = json!(hash);
+
+        writeln!(self.file, "{record}")?;
+        self.file.sync_data()?;
+        self.seq += 1;
+        self.last_hash = hash.clone();
+        if kind == "attestation" {
+            let attestation = attestation_from(&record, &hash);
+            self.attestations.insert(attestation.workspace.clone(), attestation);
+        }
+        Ok(record)
+    }
+}
+
+/// Latest scope attestation recorded for `workspace`.
+pub fn attestation(workspace: &str) -> Option<Attestation> {
+    LOG.get()?.lock().unwrap().attestations.get(workspace).cloned()
+}
+
+/// SHA-256 over the previous hash and the record (without its own hash).
+fn chain_hash(prev_hash: &str, record: &Value) -> String {
+    let mut ctx = ring::digest::Context::new(&ring::digest::SHA256);
+    ctx.update(prev_hash.as_bytes());
+    ctx.update(record.to_string().as_bytes());
+    ctx.finish()
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn attestation_from(record: &Value, hash: &str) -> Attestation {
+    let text = |v: &Value| v.as_str().unwrap_or_default().to_string();
+    Attestation {
+        workspace: text(&record["workspace"]),
+        engagement_reference: text(&record["detail"]["engagement_reference"]),
+        authorized_by: text(&record["detail"]["authorized_by"]),
+        scope: record["detail"]["scope"].as_str().map(str::to_string),
+        attested_by: record["operator"].as_str().unwrap_or("unknown").to_string(),
+        attested_at: text(&record["at"]),
+        record_hash: hash.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A log at a fresh temp path with an attestation and two scans.
+    fn sample_log(name: &str) -> String {
+        let path = std::env::temp_dir()
+            .join(format!("hacker_agent-audit-{name}-{}.jsonl", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let _ = std::fs::remove_file(&path);
+        let mut log = open(&path).unwrap();
+        let operator = Some("tester@stdio".to_string());
+        log.append(
+            "attestation",
+            "acme",
+            operator.clone(),
+            json!({ "engagement_reference": "PT-1", "authorized_by": "ACME CISO", "scope": null }),
+        )
+        .unwrap();
+        log.append("scan", "acme", operator.clone(), json!({ "target": "192.0.2.1" })).unwrap();
+        log.append("scan", "acme", operator, json!({ "target": "192.0.2.2" })).unwrap();
+        path
+    }
+
+    fn rewrite_lines(path: &str, edit: impl FnOnce(&mut Vec<String>)) {
+        let mut lines: Vec<String> = std::fs::read_to_string(path).unwrap().lines().map(str::to_string).collect();
+        edit(&mut lines);
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn reopening_verifies_the_chain_and_restores_attestations() {
+        let path = sample_log("intact");
+        let mut log = open(&path).unwrap();
+        assert_eq!(log.seq, 3);
+        let attestation = &log.attestations["acme"];
+        assert_eq!(attestation.engagement_reference, "PT-1");
+        assert_eq!(attestation.attested_by, "tester@stdio");
+
+        let next = log.append("scan", "acme", None, json!({})).unwrap();
+        assert_eq!(next["seq"], 4);
+        assert!(open(&path).is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_edited_records() {
+        let path = sample_log("tampered");
+        rewrite_lines(&path, |lines| lines[1] = lines[1].replace("192.0.2.1", "192.0.2.99"));
+        let err = open(&path).err().unwrap();
+        assert!(err.to_string().contains("hash chain broken at line 2"), "{err}");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn detects_removed_and_reordered_records() {
+        let path = sample_log("removed");
+        rewrite_lines(&path, |lines| {
+            lines.remove(1);
+        });
+        assert!(open(&path).err().unwrap().to_string().contains("line 2"));
+        std::fs::remove_file(path).unwrap();
+
+        let path = sample_log("reordered");
+        rewrite_lines(&path, |lines| lines.swap(1, 2));
+        assert!(open(&path).err().unwrap().to_string().contains("line 2"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
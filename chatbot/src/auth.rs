@@ -0,0 +1,232 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::session::Session;
+use crate::ToolCategory;
+
+/// Who may call which tools, from the `--auth-config` file. Clients present
+/// a token in `initialize` (`_meta.authToken`); the token's role decides
+/// which tools the session can list and call.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Policy {
+    roles: BTreeMap<String, Role>,
+    tokens: Vec<TokenEntry>,
+}
+
+/// A set of tools, by category and by name. `deny` wins over both.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Role {
+    #[serde(default)]
+    categories: BTreeSet<ToolCategory>,
+    /// Tool names; `*` allows every tool.
+    #[serde(default)]
+    tools: BTreeSet<String>,
+    #[serde(default)]
+    deny: BTreeSet<String>,
+}
+
+impl Role {
+    fn allows(&self, tool: &str, category: ToolCategory) -> bool {
+        !self.deny.contains(tool)
+            && (self.tools.contains("*")
+                || self.tools.contains(tool)
+                || self.categories.contains(&category))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TokenEntry {
+    token: String,
+    role: String,
+    /// Who holds the token, for job and audit attribution.
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// What a session proved with its token.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub name: Option<String>,
+    pub role: String,
+}
+
+static POLICY: OnceLock<Option<Policy>> = OnceLock::new();
+
+/// Load the authorization policy, if one is configured. Call once at
+/// startup, after `tool_state::install`, so tool names in roles can be
+/// resolved; a malformed file fails startup.
+pub fn install(path: Option<&str>) -> Result<()> {
+    let policy = match path {
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .with_context(|| format!("reading auth config from {path}"))?;
+            let mut policy = parse_policy(&raw).with_context(|| format!("parsing auth config in {path}"))?;
+            policy.resolve_aliases(crate::tool_state::resolve);
+            Some(policy)
+        }
+        None => None,
+    };
+    let _ = POLICY.set(policy);
+    Ok(())
+}
+
+fn parse_policy(raw: &str) -> Result<Policy> {
+    let policy: Policy = serde_yaml::from_str(raw)?;
+    for entry in &policy.tokens {
+        if !policy.roles.contains_key(&entry.role) {
+            anyhow::bail!("token for role `{}`: no such role", entry.role);
+        }
+        if entry.token.len() < 16 {
+            anyhow::bail!("token for role `{}` is shorter than 16 characters", entry.role);
+        }
+    }
+    Ok(policy)
+}
+
+fn policy() -> Option<&'static Policy> {
+    POLICY.get().and_then(Option::as_ref)
+}
+
+impl Policy {
+    /// Rewrite tool names in `tools` and `deny` to the canonical names
+    /// `allows` is checked with, so an entry naming an alias still applies.
+    /// Names `resolve` does not know are kept as written.
+    fn resolve_aliases(&mut self, resolve: impl Fn(&str) -> Option<String>) {
+        let canonical = |names: &mut BTreeSet<String>| {
+            *names = names.iter().map(|n| resolve(n).unwrap_or_else(|| n.clone())).collect();
+        };
+        for role in self.roles.values_mut() {
+            canonical(&mut role.tools);
+            canonical(&mut role.deny);
+        }
+    }
+
+    fn principal(&self, token: &str) -> Option<Principal> {
+        // Check every entry so timing does not reveal which one matched.
+        let mut found = None;
+        for entry in &self.tokens {
+            if tokens_match(token, &entry.token) && found.is_none() {
+                found = Some(Principal {
+                    name: entry.name.clone(),
+                    role: entry.role.clone(),
+                });
+            }
+        }
+        found
+    }
+
+    fn allows(&self, principal: Option<&Principal>, tool: &str, category: ToolCategory) -> bool {
+        principal
+            .and_then(|p| self.roles.get(&p.role))
+            .is_some_and(|role| role.allows(tool, category))
+    }
+}
+
+/// Resolve the token a client sent in `initialize`. `Ok(None)` when no
+/// policy is configured.
+pub fn authenticate(token: Option<&str>) -> Result<Option<Principal>> {
+    let Some(policy) = policy() else {
+        return Ok(None);
+    };
+    let token = token.ok_or_else(|| anyhow::anyhow!("this server requires _meta.authToken in initialize"))?;
+    policy
+        .principal(token)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("unknown auth token"))
+}
+
+/// Whether the current caller may use `tool`. Calls without a session are
+/// the server's own (scheduled monitor scans, which were authorized when
+/// they were set up) and are always allowed.
+pub fn allows(session: Option<&Session>, tool: &str, category: ToolCategory) -> bool {
+    let (Some(policy), Some(session)) = (policy(), session) else {
+        return true;
+    };
+    policy.allows(session.principal().as_ref(), tool, category)
+}
+
+/// Compare without returning early, so response timing does not reveal how
+/// much of a guessed token was right.
+pub fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+roles:
+  intern:
+    categories: [recon]
+    deny: [stealth_scan]
+  reviewer:
+    tools: [openvas_get_report, asset_tags]
+  lead:
+    tools: ["*"]
+tokens:
+  - { token: intern-token-0123456789, role: intern, name: alice }
+  - { token: lead-token-0123456789ab, role: lead }
+"#;
+
+    #[test]
+    fn roles_gate_tools_by_category_and_name() {
+        let policy = parse_policy(POLICY).unwrap();
+        let intern = policy.principal("intern-token-0123456789").unwrap();
+        assert_eq!(intern.role, "intern");
+        assert_eq!(intern.name.as_deref(), Some("alice"));
+        assert!(policy.allows(Some(&intern), "quick_scan", ToolCategory::Recon));
+        assert!(!policy.allows(Some(&intern), "stealth_scan", ToolCategory::Recon));
+        assert!(!policy.allows(Some(&intern), "openvas_start_task", ToolCategory::VulnScan));
+
+        let reviewer = Principal { name: None, role: "reviewer".into() };
+        assert!(policy.allows(Some(&reviewer), "asset_tags", ToolCategory::Recon));
+        assert!(!policy.allows(Some(&reviewer), "quick_scan", ToolCategory::Recon));
+
+        let lead = policy.principal("lead-token-0123456789ab").unwrap();
+        assert!(policy.allows(Some(&lead), "openvas_start_task", ToolCategory::VulnScan));
+        assert!(!policy.allows(None, "echo", ToolCategory::Admin));
+    }
+
+    #[test]
+    fn unknown_tokens_and_roles_are_refused() {
+        let policy = parse_policy(POLICY).unwrap();
+        assert!(policy.principal("intern-token-012345678").is_none());
+        assert!(policy.principal("").is_none());
+
+        let dangling = POLICY.replace("role: lead", "role: root");
+        assert!(parse_policy(&dangling).unwrap_err().to_string().contains("`root`"));
+        let short = POLICY.replace("lead-token-0123456789ab", "lead");
+        assert!(parse_policy(&short).is_err());
+        let unknown_category = POLICY.replace("[recon]", "[exploit]");
+        assert!(parse_policy(&unknown_category).is_err());
+    }
+
+    #[test]
+    fn role_entries_naming_an_alias_apply_to_the_canonical_tool() {
+        let raw = POLICY.replace("deny: [stealth_scan]", "deny: [nmap_open_ports]");
+        let mut policy = parse_policy(&raw).unwrap();
+        policy.resolve_aliases(|name| (name == "nmap_open_ports").then(|| "nmap.scan".to_string()));
+        let intern = policy.principal("intern-token-0123456789").unwrap();
+        assert!(!policy.allows(Some(&intern), "nmap.scan", ToolCategory::Recon));
+        assert!(policy.allows(Some(&intern), "stealth_scan", ToolCategory::Recon));
+    }
+
+    #[test]
+    fn tokens_match_compares_whole_tokens() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secret", "secreT"));
+        assert!(!tokens_match("secret", "secret2"));
+    }
+}
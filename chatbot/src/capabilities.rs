@@ -0,0 +1,173 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{config, ToolAnnotations};
+
+/// MCP capability groups that can be switched off at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Tools,
+    Prompts,
+    Resources,
+    Logging,
+}
+
+impl Capability {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "tools" => Ok(Capability::Tools),
+            "prompts" => Ok(Capability::Prompts),
+            "resources" => Ok(Capability::Resources),
+            "logging" => Ok(Capability::Logging),
+            other => anyhow::bail!(
+                "unknown capability `{other}` (expected tools, prompts, resources or logging)"
+            ),
+        }
+    }
+
+    /// The capability group a JSON-RPC method belongs to, if any.
+    pub fn of_method(method: &str) -> Option<Self> {
+        match method.split_once('/').map(|(group, _)| group) {
+            Some("tools") => Some(Capability::Tools),
+            Some("prompts") => Some(Capability::Prompts),
+            Some("resources") => Some(Capability::Resources),
+            Some("logging") => Some(Capability::Logging),
+            _ => None,
+        }
+    }
+}
+
+/// Launch profile: which kinds of tools the server registers at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// Every tool.
+    #[default]
+    Full,
+    /// Read-only analyst: only tools annotated read-only and non-destructive
+    /// are registered, so nothing sends traffic to targets, schedules scans
+    /// or changes backend or host state.
+    Analyst,
+}
+
+impl Profile {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(Profile::Full),
+            "analyst" => Ok(Profile::Analyst),
+            other => anyhow::bail!("unknown profile `{other}` (expected full or analyst)"),
+        }
+    }
+}
+
+/// Whether the launch profile lets the tool be registered.
+pub fn profile_allows(annotations: ToolAnnotations) -> bool {
+    config::current().profile.allows(annotations)
+}
+
+impl Profile {
+    fn allows(self, annotations: ToolAnnotations) -> bool {
+        match self {
+            Profile::Full => true,
+            Profile::Analyst => {
+                annotations.read_only_hint && !annotations.destructive_hint && !annotations.open_world_hint
+            }
+        }
+    }
+}
+
+/// Tool families that can be disabled individually; see `tools::register_all_tools`.
+pub const TOOL_FAMILIES: [&str; 6] = [
+    "core", "nmap", "openvas", "native", "plugins", "commands",
+];
+
+pub fn parse_tool_family(raw: &str) -> Result<String> {
+    let family = raw.trim().to_ascii_lowercase();
+    if !TOOL_FAMILIES.contains(&family.as_str()) {
+        anyhow::bail!(
+            "unknown tool family `{family}` (expected one of {})",
+            TOOL_FAMILIES.join(", ")
+        );
+    }
+    Ok(family)
+}
+
+pub fn enabled(capability: Capability) -> bool {
+    !config::current().disabled_capabilities.contains(&capability)
+}
+
+pub fn tool_family_enabled(family: &str) -> bool {
+    !config::current().disabled_tool_families.iter().any(|f| f == family)
+}
+
+/// The `capabilities` object for the `initialize` result, listing only the
+/// groups that are enabled.
+pub fn server_capabilities() -> Value {
+    let mut caps = serde_json::Map::new();
+    if enabled(Capability::Tools) {
+        caps.insert("tools".into(), json!({ "listChanged": true }));
+    }
+    if enabled(Capability::Prompts) {
+        caps.insert("prompts".into(), json!({ "listChanged": true }));
+    }
+    if enabled(Capability::Resources) {
+        caps.insert("resources".into(), json!({ "listChanged": false }));
+    }
+    if enabled(Capability::Logging) {
+        caps.insert("logging".into(), json!({}));
+    }
+    Value::Object(caps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyst_profile_keeps_only_read_only_tools() {
+        let mut registry = crate::ToolRegistry::new();
+        crate::tools::register_all_tools(&mut registry);
+        let mut analyst: Vec<String> = registry
+            .tools
+            .values()
+            .filter(|t| Profile::Analyst.allows(t.annotations()))
+            .map(|t| t.name().to_string())
+            .collect();
+        analyst.sort();
+        assert_eq!(
+            analyst,
+            [
+                "asset_tags",
+                "echo",
+                "get_agent_metrics",
+                "jobs_export",
+                "list_tools_by_category",
+                "nmap_scan_result",
+                "nmap_scan_status",
+                "openvas_correlate_nmap",
+                "openvas_diff_reports",
+                "openvas_export_sarif",
+                "openvas_feed_status",
+                "openvas_get_host",
+                "openvas_get_nvt",
+                "openvas_get_report",
+                "openvas_get_results",
+                "openvas_get_version",
+                "openvas_list_credentials",
+                "openvas_list_hosts",
+                "openvas_list_port_lists",
+                "openvas_list_reports",
+                "openvas_list_scan_configs",
+                "openvas_list_scanners",
+                "openvas_list_schedules",
+                "openvas_list_targets",
+                "openvas_list_tasks",
+                "openvas_list_tickets",
+                "openvas_list_trash",
+                "openvas_prioritize_findings",
+                "openvas_task_status",
+                "openvas_wait_for_task",
+                "scan_history",
+            ]
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::AsyncReadExt;
+
+use crate::tool_error::ToolError;
+use crate::{Tool, ToolAnnotations, ToolCategory};
+
+/// Most bytes of stdout and of stderr kept from one run.
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Timeout for command tools that do not set `timeout_secs`.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Only these variables reach the subprocess.
+const PASSED_ENV: [&str; 3] = ["PATH", "LANG", "LC_ALL"];
+
+/// Address space a command tool's process may map.
+#[cfg(unix)]
+const MAX_MEMORY_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Largest file a command tool's process may write.
+#[cfg(unix)]
+const MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Most files a command tool's process may hold open.
+#[cfg(unix)]
+const MAX_OPEN_FILES: u64 = 256;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommandToolFile {
+    tools: Vec<CommandToolSpec>,
+}
+
+/// One tool as the `--command-tools` file defines it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommandToolSpec {
+    name: String,
+    description: String,
+    category: ToolCategory,
+    /// JSON Schema of the tool's input; every placeholder in `command` must
+    /// be one of its properties.
+    #[serde(default = "empty_object")]
+    input_schema: Value,
+    /// Program and arguments. `{field}` is replaced with that input field;
+    /// an argument that is only `{field}` is dropped when the field is absent,
+    /// and so is a group (`["-h", "{server}"]`) containing one.
+    command: Vec<CommandArg>,
+    /// Missing hints default to the most cautious ones.
+    #[serde(default)]
+    annotations: ToolAnnotations,
+    /// Input field naming the host(s) the command reaches; required for
+    /// open-world tools, so the engagement scope applies to them.
+    #[serde(default)]
+    target_field: Option<String>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+/// One entry of `command`: an argument, or arguments that are passed or
+/// dropped together, such as an option and its value.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum CommandArg {
+    One(String),
+    Group(Vec<String>),
+}
+
+impl CommandArg {
+    fn args(&self) -> &[String] {
+        match self {
+            CommandArg::One(arg) => std::slice::from_ref(arg),
+            CommandArg::Group(args) => args,
+        }
+    }
+}
+
+fn empty_object() -> Value {
+    json!({ "type": "object", "properties": {}, "additionalProperties": false })
+}
+
+static SPECS: OnceLock<Vec<CommandToolSpec>> = OnceLock::new();
+
+/// Load the command tool definitions from `path`, if one is configured.
+/// Call once at startup; a malformed file fails startup.
+pub fn install(path: Option<&str>) -> Result<()> {
+    let specs = match path {
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .with_context(|| format!("reading command tools from {path}"))?;
+            parse_specs(&raw).with_context(|| format!("parsing command tools in {path}"))?
+        }
+        None => Vec::new(),
+    };
+    let _ = SPECS.set(specs);
+    Ok(())
+}
+
+/// The tools defined by the installed file.
+pub fn tools() -> Vec<CommandTool> {
+    SPECS
+        .get()
+        .into_iter()
+        .flatten()
+        .cloned()
+        .map(CommandTool::new)
+        .collect()
+}
+
+fn placeholder() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap())
+}
+
+/// The field `arg` consists of, if it is nothing but one placeholder.
+fn whole_placeholder(arg: &str) -> Option<&str> {
+    placeholder()
+        .captures(arg)
+        .filter(|c| &c[0] == arg)
+        .map(|c| c.get(1).unwrap().as_str())
+}
+
+fn parse_specs(raw: &str) -> Result<Vec<CommandToolSpec>> {
+    let file: CommandToolFile = serde_yaml::from_str(raw)?;
+    for spec in &file.tools {
+        let Some(CommandArg::One(program)) = spec.command.first() else {
+            anyhow::bail!("command tool `{}` must start its command with the program", spec.name);
+        };
+        if placeholder().is_match(program) {
+            anyhow::bail!("command tool `{}`: the program cannot be a placeholder", spec.name);
+        }
+        let properties = spec.input_schema.get("properties").and_then(Value::as_object);
+        match &spec.target_field {
+            None if spec.annotations.open_world_hint => anyhow::bail!(
+                "command tool `{}` is open-world (openWorldHint) and must name its target_field",
+                spec.name
+            ),
+            Some(field) if !properties.is_some_and(|p| p.contains_key(field)) => {
+                anyhow::bail!("command tool `{}`: target_field `{field}` is not in its input_schema", spec.name)
+            }
+            _ => {}
+        }
+        for arg in spec.command.iter().flat_map(CommandArg::args) {
+            for field in placeholder().captures_iter(arg) {
+                if !properties.is_some_and(|p| p.contains_key(&field[1])) {
+                    anyhow::bail!(
+                        "command tool `{}`: placeholder {{{}}} is not in its input_schema",
+                        spec.name,
+                        &field[1]
+                    );
+                }
+            }
+        }
+        // `-h {server}` as two separate arguments would leave `-h` behind
+        // without `server`, and the next argument would become its value.
+        let required = spec.input_schema.get("required").and_then(Value::as_array);
+        for pair in spec.command.windows(2) {
+            if let [CommandArg::One(option), CommandArg::One(value)] = pair
+                && option.starts_with('-')
+                && let Some(field) = whole_placeholder(value)
+                && !required.is_some_and(|r| r.iter().any(|f| f == field))
+            {
+                anyhow::bail!(
+                    "command tool `{}`: optional {{{field}}} follows `{option}`; write [\"{option}\", \"{{{field}}}\"] \
+                     so both are dropped when {field} is absent",
+                    spec.name
+                );
+            }
+        }
+    }
+    Ok(file.tools)
+}
+
+/// Fill `template` from `input`. Each argument stays one argument whatever
+/// the input holds; no shell ever sees it. A group is left out whole when
+/// any of its placeholder-only arguments has no value.
+fn render(template: &[CommandArg], input: &Value) -> Result<Vec<String>> {
+    let mut argv = Vec::with_capacity(template.len());
+    for entry in template {
+        let absent = |arg: &String| whole_placeholder(arg).is_some_and(|f| input.get(f).is_none_or(Value::is_null));
+        if let CommandArg::Group(args) = entry
+            && args.iter().any(absent)
+        {
+            continue;
+        }
+        for arg in entry.args() {
+            if absent(arg) {
+                continue;
+            }
+            argv.push(render_arg(arg, input)?);
+        }
+    }
+    Ok(argv)
+}
+
+fn render_arg(arg: &str, input: &Value) -> Result<String> {
+    let mut missing = None;
+    let rendered = placeholder().replace_all(arg, |c: &regex::Captures| {
+        match input.get(&c[1]).map(scalar) {
+            Some(Some(value)) => value,
+            _ => {
+                missing = Some(c[1].to_string());
+                String::new()
+            }
+        }
+    });
+    if let Some(field) = missing {
+        return Err(ToolError::invalid_input(format!("`{field}` must be given as a string, number or boolean")).into());
+    }
+    // Input becoming an option (`-oN /etc/...`) would change what the
+    // program does, not just what it works on.
+    if rendered.starts_with('-') && !arg.starts_with('-') {
+        return Err(ToolError::invalid_input(format!("argument `{rendered}` may not start with `-`")).into());
+    }
+    Ok(rendered.into_owned())
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// A tool defined in the `--command-tools` file.
+pub struct CommandTool {
+    spec: CommandToolSpec,
+    // Tool names and descriptions are `&'static str`; command tools live for
+    // the rest of the process, so their strings are leaked once at startup.
+    name: &'static str,
+    description: &'static str,
+    target_field: Option<&'static str>,
+}
+
+impl CommandTool {
+    fn new(spec: CommandToolSpec) -> Self {
+        Self {
+            name: Box::leak(spec.name.clone().into_boxed_str()),
+            description: Box::leak(spec.description.clone().into_boxed_str()),
+            target_field: spec.target_field.clone().map(|f| &*Box::leak(f.into_boxed_str())),
+            spec,
+        }
+    }
+}
+
+/// The tools defined in `raw`, as `install` would load them.
+#[cfg(test)]
+pub fn parse_tools(raw: &str) -> Result<Vec<CommandTool>> {
+    Ok(parse_specs(raw)?.into_iter().map(CommandTool::new).collect())
+}
+
+#[async_trait]
+impl Tool for CommandTool {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.spec.category
+    }
+
+    fn input_schema(&self) -> Value {
+        self.spec.input_schema.clone()
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        self.spec.annotations
+    }
+
+    fn target_field(&self) -> Option<&'static str> {
+        self.target_field
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(self.timeout())
+    }
+
+    async fn execute(&self, input: Value) -> Result<Value> {
+        let argv = render(&self.spec.command, &input)?;
+        run(&argv, self.timeout()).await
+    }
+}
+
+impl CommandTool {
+    fn timeout(&self) -> Duration {
+        self.spec.timeout_secs.map_or(DEFAULT_TIMEOUT, Duration::from_secs)
+    }
+}
+
+/// Run `argv` without a shell, with an empty stdin, a scrubbed environment
+/// and the temp directory as working directory. On unix it also runs under
+/// `limit` (see there). The process is killed if the call is cut off by its
+/// timeout, along with anything it started.
+async fn run(argv: &[String], timeout: Duration) -> Result<Value> {
+    let mut command = tokio::process::Command::new(&argv[0]);
+    command
+        .args(&argv[1..])
+        .env_clear()
+        .envs(PASSED_ENV.iter().filter_map(|k| Some((k, std::env::var_os(k)?))))
+        .current_dir(std::env::temp_dir())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    limit(&mut command, timeout);
+    #[cfg(not(unix))]
+    let _ = timeout;
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("starting {}", argv[0]))?;
+    #[cfg(unix)]
+    let _group = child.id().map(ProcessGroup);
+
+    let stdout = read_capped(child.stdout.take());
+    let stderr = read_capped(child.stderr.take());
+    let (stdout, stderr, status) = tokio::join!(stdout, stderr, child.wait());
+    let status = status?;
+    Ok(json!({
+        "command": argv,
+        "exit_code": status.code(),
+        "stdout": stdout?,
+        "stderr": stderr?,
+    }))
+}
+
+/// Put the process in a process group of its own and, between fork and
+/// exec, cap its CPU time at `timeout`, its address space, file sizes and
+/// open files, and disable core dumps. On Linux it also sets no_new_privs,
+/// so setuid programs and file capabilities cannot raise its privileges.
+#[cfg(unix)]
+fn limit(command: &mut tokio::process::Command, timeout: Duration) {
+    let cpu_secs = timeout.as_secs().max(1);
+    command.process_group(0);
+    // SAFETY: the closure runs in the forked child and only makes
+    // async-signal-safe system calls; it does not allocate.
+    unsafe {
+        command.pre_exec(move || {
+            let limits = [
+                (libc::RLIMIT_CPU, cpu_secs),
+                (libc::RLIMIT_AS, MAX_MEMORY_BYTES),
+                (libc::RLIMIT_FSIZE, MAX_FILE_BYTES),
+                (libc::RLIMIT_NOFILE, MAX_OPEN_FILES),
+                (libc::RLIMIT_CORE, 0),
+            ];
+            for (resource, value) in limits {
+                let value = value as libc::rlim_t;
+                if libc::setrlimit(resource, &libc::rlimit { rlim_cur: value, rlim_max: value }) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            #[cfg(target_os = "linux")]
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Kills a command tool's process group when dropped, so children it left
+/// running do not outlive the call.
+#[cfg(unix)]
+struct ProcessGroup(u32);
+
+#[cfg(unix)]
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        // SAFETY: kill has no memory-safety preconditions; a group that has
+        // already exited just makes it fail with ESRCH.
+        unsafe {
+            libc::kill(-(self.0 as libc::pid_t), libc::SIGKILL);
+        }
+    }
+}
+
+/// Read a pipe to the end, keeping at most `MAX_OUTPUT_BYTES` so a chatty
+/// program cannot exhaust memory.
+async fn read_capped(pipe: Option<impl tokio::io::AsyncRead + Unpin>) -> Result<String> {
+    let Some(mut pipe) = pipe else {
+        return Ok(String::new());
+    };
+    let mut kept = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let mut truncated = false;
+    loop {
+        let n = pipe.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        let room = MAX_OUTPUT_BYTES - kept.len();
+        kept.extend_from_slice(&chunk[..n.min(room)]);
+        truncated |= n > room;
+    }
+    let mut text = String::from_utf8_lossy(&kept).into_owned();
+    if truncated {
+        text.push_str("\n[output truncated]");
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPECS: &str = r#"
+tools:
+  - name: whois_lookup
+    description: Looks up who registered a domain.
+    category: osint
+    input_schema:
+      type: object
+      properties:
+        domain: { type: string }
+        server: { type: string }
+      required: [domain]
+      additionalProperties: false
+    command: [whois, ["-h", "{server}"], "{domain}"]
+    annotations: { readOnlyHint: true, destructiveHint: false, idempotentHint: true, openWorldHint: false }
+    timeout_secs: 20
+"#;
+
+    fn argv(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn specs_parse_and_placeholders_are_checked() {
+        let specs = parse_specs(SPECS).unwrap();
+        assert_eq!(specs[0].name, "whois_lookup");
+        assert!(specs[0].annotations.read_only_hint);
+
+        let undeclared = SPECS.replace("\"{domain}\"", "\"{host}\"");
+        let err = parse_specs(&undeclared).unwrap_err();
+        assert!(err.to_string().contains("{host}"), "{err}");
+        let program = SPECS.replace("[whois,", "[\"{domain}\",");
+        assert!(parse_specs(&program).is_err());
+        let open_world = SPECS.replace("openWorldHint: false", "openWorldHint: true");
+        let err = parse_specs(&open_world).unwrap_err();
+        assert!(err.to_string().contains("must name its target_field"), "{err}");
+        let unknown_field = format!("{open_world}    target_field: host\n");
+        assert!(parse_specs(&unknown_field).unwrap_err().to_string().contains("`host` is not in its input_schema"));
+        assert!(parse_specs(&format!("{open_world}    target_field: domain\n")).is_ok());
+        let dangling_option = SPECS.replace(r#"["-h", "{server}"]"#, r#""-h", "{server}""#);
+        let err = parse_specs(&dangling_option).unwrap_err();
+        assert!(err.to_string().contains(r#"["-h", "{server}"]"#), "{err}");
+    }
+
+    #[test]
+    fn render_keeps_arguments_whole_and_drops_absent_ones() {
+        let template = parse_specs(SPECS).unwrap().remove(0).command;
+        let rendered = render(&template, &json!({ "domain": "example.com; rm -rf /" })).unwrap();
+        assert_eq!(rendered, argv(&["whois", "example.com; rm -rf /"]));
+        let rendered = render(&template, &json!({ "domain": "example.com", "server": "whois.iana.org" })).unwrap();
+        assert_eq!(rendered, argv(&["whois", "-h", "whois.iana.org", "example.com"]));
+
+        let template = [
+            CommandArg::One("whois".into()),
+            CommandArg::One("{server}".into()),
+            CommandArg::One("--port={port}".into()),
+        ];
+        let rendered = render(&template, &json!({ "port": 43 })).unwrap();
+        assert_eq!(rendered, argv(&["whois", "--port=43"]));
+        let err = render(&template, &json!({ "server": "whois.iana.org" })).unwrap_err();
+        assert!(err.to_string().contains("`port`"), "{err}");
+    }
+
+    #[test]
+    fn render_refuses_input_that_becomes_an_option() {
+        let template = [CommandArg::One("whois".into()), CommandArg::One("{domain}".into())];
+        assert!(render(&template, &json!({ "domain": "-oN/tmp/x" })).is_err());
+        assert!(render(&template, &json!({ "domain": ["a", "b"] })).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_captures_output_without_a_shell() {
+        let output = run(&argv(&["echo", "$HOME", "a b"]), DEFAULT_TIMEOUT).await.unwrap();
+        assert_eq!(output["exit_code"], 0);
+        assert_eq!(output["stdout"], "$HOME a b\n");
+
+        let output = run(&argv(&["sh", "-c", "echo ${HOME:-unset}; exit 3"]), DEFAULT_TIMEOUT).await.unwrap();
+        assert_eq!(output["exit_code"], 3);
+        assert_eq!(output["stdout"], "unset\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_limits_the_process() {
+        let script = "ulimit -t; ulimit -n; ulimit -c";
+        let output = run(&argv(&["sh", "-c", script]), Duration::from_secs(20)).await.unwrap();
+        assert_eq!(output["stdout"], "20\n256\n0\n");
+    }
+}
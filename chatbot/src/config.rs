@@ -0,0 +1,679 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::api::fixtures::FixtureMode;
+use crate::api::gmp::GmpEndpoint;
+use crate::approval::ApprovalMode;
+use crate::capabilities::{self, Capability, Profile};
+use crate::transport::Framing;
+
+const DEFAULT_BACKEND_URL: &str = "http://127.0.0.1:8080";
+const DEFAULT_FIXTURES_DIR: &str = "fixtures";
+const DEFAULT_PLUGINS_DIR: &str = "plugins";
+const DEFAULT_REPORTS_DIR: &str = "reports";
+const DEFAULT_BULK_THRESHOLD: u64 = 256;
+const DEFAULT_WORKSPACE: &str = "default";
+const DEFAULT_AUDIT_LOG: &str = "audit.jsonl";
+const DEFAULT_TARGET_RATE_LIMIT: u64 = 10;
+const DEFAULT_MAX_OUTPUT_BYTES: usize = 100_000;
+const DEFAULT_BACKEND_HEALTH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Runtime configuration for the server, assembled from command-line flags
+/// and `HACKER_AGENT_*` environment variables (flags win over env).
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// When set, serve JSON-RPC over TCP on this address (e.g. `127.0.0.1:7777`)
+    /// instead of stdin/stdout.
+    pub listen: Option<String>,
+    /// Let `--listen` bind a non-loopback address. The TCP transport has no
+    /// authentication, so anyone who can reach the port can run scans.
+    pub allow_remote: bool,
+    /// When set, serve JSON-RPC on a unix domain socket at this path.
+    pub unix_socket: Option<String>,
+    /// Targets (hostnames, IPs or CIDRs, compared verbatim) that are tagged as
+    /// production. Requests against these are rewritten by `policy`.
+    pub production_targets: Vec<String>,
+    /// Suspend background work and release backend connections after this
+    /// many minutes without a request. `None` disables idle suspend.
+    pub idle_timeout_minutes: Option<u64>,
+    /// Record backend responses as fixtures, or replay them instead of
+    /// calling the backend. `None` talks to the backend normally.
+    pub fixtures: Option<FixtureMode>,
+    /// Directory fixtures are written to / read from.
+    pub fixtures_dir: String,
+    /// Base URL of the Go backend, without a trailing slash.
+    pub backend_url: String,
+    /// Base URL of the backend serving the nmap endpoints, when not
+    /// `backend_url`.
+    pub nmap_backend_url: Option<String>,
+    /// Base URL of the backend serving the `/openvas/` endpoints, when not
+    /// `backend_url`.
+    pub openvas_backend_url: Option<String>,
+    /// Run nmap on this host instead of asking the Go backend to (see
+    /// `api::nmap`).
+    pub local_nmap: bool,
+    /// Bearer token sent with every call to the Go backend, matching its
+    /// `BACKEND_TOKEN`.
+    pub backend_token: Option<String>,
+    /// `user:password` sent as basic auth with every call to the Go backend,
+    /// matching its `BACKEND_BASIC_AUTH`.
+    pub backend_basic_auth: Option<String>,
+    /// How often the Go backend is probed (see `api::health`); zero
+    /// disables the probe, leaving the circuit breaker to failed calls.
+    pub backend_health_interval: Duration,
+    /// How often the task watchdog (see `watchdog`) checks for interrupted
+    /// tasks to resume; zero, the default, leaves it off.
+    pub task_watchdog_interval: Duration,
+    /// Speak GMP to gvmd at this endpoint (see `api::gmp`) instead of
+    /// reaching OpenVAS through the Go backend.
+    pub gmp: Option<GmpEndpoint>,
+    /// PEM file with the CA that signed gvmd's certificate, for a TLS `gmp`
+    /// endpoint.
+    pub gmp_ca_cert: Option<String>,
+    /// YAML file naming further GVM accounts OpenVAS tool calls can be
+    /// routed to (see `api::gvm_backends`).
+    pub gvm_backends: Option<String>,
+    /// Upper bound for handling any single request. `None` means no limit.
+    pub request_timeout: Option<Duration>,
+    /// Per-tool overrides of `request_timeout` for `tools/call`, keyed by tool name.
+    pub tool_timeouts: HashMap<String, Duration>,
+    /// Per-tool overrides of how long results of cacheable tools are reused
+    /// (see `middleware::ResultCache`); `0` disables caching for a tool.
+    pub cache_ttls: HashMap<String, Duration>,
+    /// Per-tool overrides of how many calls may execute at once (see
+    /// `middleware::ConcurrencyLimit`); `0` lifts a tool's limit.
+    pub max_concurrency: HashMap<String, usize>,
+    /// Per-tool overrides of how many attempts a call gets when the backend
+    /// fails transiently (see `middleware::Retry`); `1` disables retries.
+    pub retries: HashMap<String, usize>,
+    /// Per-tool overrides of the wait before the first retry.
+    pub retry_backoffs: HashMap<String, Duration>,
+    /// Largest tool output, in bytes of JSON, returned inline; larger ones
+    /// are spilled to a resource (see `middleware::OutputLimit`). `0` is
+    /// unlimited.
+    pub max_output_bytes: usize,
+    /// Per-tool overrides of `max_output_bytes`.
+    pub max_outputs: HashMap<String, usize>,
+    /// Most calls per minute that may send traffic to any one host or CIDR
+    /// (see `middleware::TargetRateLimit`); `0` disables the limit.
+    pub target_rate_limit: u64,
+    /// How intrusive calls are confirmed with the user (see `approval`).
+    pub approval_mode: ApprovalMode,
+    /// YAML file with host tagging rules (see `tagging`). `None` uses the
+    /// built-in rules.
+    pub tag_rules: Option<String>,
+    /// Message framing on stdio: newline-delimited JSON or `Content-Length` headers.
+    pub framing: Framing,
+    /// Calls touching more hosts than this need confirmation with an impact
+    /// summary (see `approval::bulk_impact`).
+    pub bulk_threshold: u64,
+    /// MCP capability groups switched off; their methods answer -32601 and
+    /// they are left out of the `initialize` capabilities.
+    pub disabled_capabilities: HashSet<Capability>,
+    /// Tool families (see `capabilities::TOOL_FAMILIES`) left unregistered.
+    pub disabled_tool_families: Vec<String>,
+    /// Webhook (e.g. a Slack incoming webhook) told when a monitored asset
+    /// changes between scans (see `monitor`) or the watchdog resumes a task.
+    pub monitor_webhook: Option<String>,
+    /// `schema-export` subcommand: print the OpenAPI bundle of all tool
+    /// schemas to stdout and exit instead of serving.
+    pub schema_export: bool,
+    /// Engagement this server instance works in; attestations and audit
+    /// records are kept per workspace.
+    pub workspace: String,
+    /// Append-only, hash-chained audit log (see `audit`). `None` keeps no log
+    /// unless attestation is required.
+    pub audit_log: Option<String>,
+    /// SQLite database recording every nmap result (see `scan_history`).
+    /// `None` records nothing.
+    pub scan_history: Option<String>,
+    /// Refuse active scanning tools until `attest_scope` has recorded who
+    /// authorized testing for the workspace.
+    pub require_attestation: bool,
+    /// Launch profile; `analyst` leaves every active scanning tool out of
+    /// the registry.
+    pub profile: Profile,
+    /// Air-gapped mode: no HTTP call may leave for anything but the scan
+    /// backend (see `api::ensure_allowed`), and nothing is downloaded.
+    pub offline: bool,
+    /// Shared secret `admin_set_tool_state` calls must present. `None`
+    /// leaves tools impossible to switch off at runtime.
+    pub admin_token: Option<String>,
+    /// Directory tool plugins are loaded from at startup (see `plugins`).
+    pub plugins_dir: String,
+    /// Directory `openvas_render_report` writes its documents to.
+    pub reports_dir: String,
+    /// YAML file defining tools that run a command (see `command_tools`).
+    pub command_tools: Option<String>,
+    /// YAML file mapping client tokens to roles that gate tools (see
+    /// `auth`). `None` lets every client call every tool.
+    pub auth_config: Option<String>,
+    /// YAML file of per-tool settings, re-applied whenever it changes (see
+    /// `config_file`).
+    pub config_file: Option<String>,
+    /// IPs, CIDRs and domains the engagement covers; any call naming a
+    /// target outside them is refused (see `middleware::EngagementScope`).
+    /// Empty disables the check.
+    pub scope: Vec<String>,
+    /// IPs, CIDRs and domains no call may touch, whatever `scope` allows.
+    pub deny_targets: Vec<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            listen: None,
+            allow_remote: false,
+            unix_socket: None,
+            production_targets: Vec::new(),
+            idle_timeout_minutes: None,
+            fixtures: None,
+            fixtures_dir: DEFAULT_FIXTURES_DIR.to_string(),
+            backend_url: DEFAULT_BACKEND_URL.to_string(),
+            nmap_backend_url: None,
+            openvas_backend_url: None,
+            local_nmap: false,
+            backend_token: None,
+            backend_basic_auth: None,
+            backend_health_interval: DEFAULT_BACKEND_HEALTH_INTERVAL,
+            task_watchdog_interval: Duration::ZERO,
+            gmp: None,
+            gmp_ca_cert: None,
+            gvm_backends: None,
+            request_timeout: None,
+            tool_timeouts: HashMap::new(),
+            cache_ttls: HashMap::new(),
+            max_concurrency: HashMap::new(),
+            retries: HashMap::new(),
+            retry_backoffs: HashMap::new(),
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            max_outputs: HashMap::new(),
+            target_rate_limit: DEFAULT_TARGET_RATE_LIMIT,
+            approval_mode: ApprovalMode::Elicit,
+            tag_rules: None,
+            framing: Framing::Lines,
+            bulk_threshold: DEFAULT_BULK_THRESHOLD,
+            disabled_capabilities: HashSet::new(),
+            disabled_tool_families: Vec::new(),
+            monitor_webhook: None,
+            schema_export: false,
+            workspace: DEFAULT_WORKSPACE.to_string(),
+            audit_log: None,
+            scan_history: None,
+            require_attestation: false,
+            profile: Profile::Full,
+            offline: false,
+            admin_token: None,
+            plugins_dir: DEFAULT_PLUGINS_DIR.to_string(),
+            reports_dir: DEFAULT_REPORTS_DIR.to_string(),
+            command_tools: None,
+            auth_config: None,
+            config_file: None,
+            scope: Vec::new(),
+            deny_targets: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Build the configuration from the process arguments and environment.
+    pub fn from_env_and_args() -> Result<Self> {
+        let mut cfg = AppConfig {
+            listen: env_non_empty("HACKER_AGENT_LISTEN"),
+            allow_remote: env_non_empty("HACKER_AGENT_ALLOW_REMOTE")
+                .map(|v| parse_bool("HACKER_AGENT_ALLOW_REMOTE", &v))
+                .transpose()?
+                .unwrap_or(false),
+            unix_socket: env_non_empty("HACKER_AGENT_UNIX_SOCKET"),
+            production_targets: env_non_empty("HACKER_AGENT_PRODUCTION_TARGETS")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+            idle_timeout_minutes: env_non_empty("HACKER_AGENT_IDLE_TIMEOUT_MINUTES")
+                .map(|v| parse_minutes("HACKER_AGENT_IDLE_TIMEOUT_MINUTES", &v))
+                .transpose()?,
+            fixtures: env_non_empty("HACKER_AGENT_FIXTURES")
+                .map(|v| FixtureMode::parse(&v))
+                .transpose()?,
+            fixtures_dir: env_non_empty("HACKER_AGENT_FIXTURES_DIR")
+                .unwrap_or_else(|| DEFAULT_FIXTURES_DIR.to_string()),
+            backend_url: env_non_empty("HACKER_AGENT_BACKEND_URL")
+                .map(|v| parse_backend_url("HACKER_AGENT_BACKEND_URL", &v))
+                .transpose()?
+                .unwrap_or_else(|| DEFAULT_BACKEND_URL.to_string()),
+            nmap_backend_url: env_non_empty("HACKER_AGENT_NMAP_BACKEND_URL")
+                .map(|v| parse_backend_url("HACKER_AGENT_NMAP_BACKEND_URL", &v))
+                .transpose()?,
+            openvas_backend_url: env_non_empty("HACKER_AGENT_OPENVAS_BACKEND_URL")
+                .map(|v| parse_backend_url("HACKER_AGENT_OPENVAS_BACKEND_URL", &v))
+                .transpose()?,
+            local_nmap: env_non_empty("HACKER_AGENT_LOCAL_NMAP")
+                .map(|v| parse_bool("HACKER_AGENT_LOCAL_NMAP", &v))
+                .transpose()?
+                .unwrap_or(false),
+            backend_token: env_non_empty("HACKER_AGENT_BACKEND_TOKEN"),
+            backend_basic_auth: env_non_empty("HACKER_AGENT_BACKEND_BASIC_AUTH"),
+            backend_health_interval: env_non_empty("HACKER_AGENT_BACKEND_HEALTH_INTERVAL_SECS")
+                .map(|v| parse_secs("HACKER_AGENT_BACKEND_HEALTH_INTERVAL_SECS", &v))
+                .transpose()?
+                .unwrap_or(DEFAULT_BACKEND_HEALTH_INTERVAL),
+            task_watchdog_interval: env_non_empty("HACKER_AGENT_TASK_WATCHDOG_SECS")
+                .map(|v| parse_secs("HACKER_AGENT_TASK_WATCHDOG_SECS", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            gmp: env_non_empty("HACKER_AGENT_GMP")
+                .map(|v| GmpEndpoint::parse(&v))
+                .transpose()?,
+            gmp_ca_cert: env_non_empty("HACKER_AGENT_GMP_CA_CERT"),
+            gvm_backends: env_non_empty("HACKER_AGENT_GVM_BACKENDS"),
+            request_timeout: env_non_empty("HACKER_AGENT_REQUEST_TIMEOUT_SECS")
+                .map(|v| parse_secs("HACKER_AGENT_REQUEST_TIMEOUT_SECS", &v))
+                .transpose()?,
+            tool_timeouts: env_non_empty("HACKER_AGENT_TOOL_TIMEOUTS")
+                .map(|v| parse_tool_durations("HACKER_AGENT_TOOL_TIMEOUTS", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            cache_ttls: env_non_empty("HACKER_AGENT_CACHE_TTLS")
+                .map(|v| parse_tool_durations("HACKER_AGENT_CACHE_TTLS", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            max_concurrency: env_non_empty("HACKER_AGENT_MAX_CONCURRENCY")
+                .map(|v| parse_tool_counts("HACKER_AGENT_MAX_CONCURRENCY", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            retries: env_non_empty("HACKER_AGENT_RETRIES")
+                .map(|v| parse_tool_counts("HACKER_AGENT_RETRIES", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            retry_backoffs: env_non_empty("HACKER_AGENT_RETRY_BACKOFF_MS")
+                .map(|v| parse_tool_millis("HACKER_AGENT_RETRY_BACKOFF_MS", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            max_output_bytes: env_non_empty("HACKER_AGENT_MAX_OUTPUT_BYTES")
+                .map(|v| parse_count("HACKER_AGENT_MAX_OUTPUT_BYTES", &v))
+                .transpose()?
+                .map_or(DEFAULT_MAX_OUTPUT_BYTES, |n| n as usize),
+            max_outputs: env_non_empty("HACKER_AGENT_MAX_OUTPUTS")
+                .map(|v| parse_tool_counts("HACKER_AGENT_MAX_OUTPUTS", &v))
+                .transpose()?
+                .unwrap_or_default(),
+            target_rate_limit: env_non_empty("HACKER_AGENT_TARGET_RATE_LIMIT")
+                .map(|v| parse_count("HACKER_AGENT_TARGET_RATE_LIMIT", &v))
+                .transpose()?
+                .unwrap_or(DEFAULT_TARGET_RATE_LIMIT),
+            approval_mode: env_non_empty("HACKER_AGENT_APPROVAL")
+                .map(|v| ApprovalMode::parse(&v))
+                .transpose()?
+                .unwrap_or(ApprovalMode::Elicit),
+            tag_rules: env_non_empty("HACKER_AGENT_TAG_RULES"),
+            framing: env_non_empty("HACKER_AGENT_FRAMING")
+                .map(|v| Framing::parse(&v))
+                .transpose()?
+                .unwrap_or_default(),
+            bulk_threshold: env_non_empty("HACKER_AGENT_BULK_THRESHOLD")
+                .map(|v| parse_count("HACKER_AGENT_BULK_THRESHOLD", &v))
+                .transpose()?
+                .unwrap_or(DEFAULT_BULK_THRESHOLD),
+            disabled_capabilities: env_non_empty("HACKER_AGENT_DISABLE_CAPABILITIES")
+                .map(|v| split_list(&v).iter().map(|c| Capability::parse(c)).collect())
+                .transpose()?
+                .unwrap_or_default(),
+            disabled_tool_families: env_non_empty("HACKER_AGENT_DISABLE_TOOL_FAMILIES")
+                .map(|v| split_list(&v).iter().map(|f| capabilities::parse_tool_family(f)).collect())
+                .transpose()?
+                .unwrap_or_default(),
+            monitor_webhook: env_non_empty("HACKER_AGENT_MONITOR_WEBHOOK"),
+            schema_export: false,
+            workspace: env_non_empty("HACKER_AGENT_WORKSPACE")
+                .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string()),
+            audit_log: env_non_empty("HACKER_AGENT_AUDIT_LOG"),
+            scan_history: env_non_empty("HACKER_AGENT_SCAN_HISTORY"),
+            require_attestation: env_non_empty("HACKER_AGENT_REQUIRE_ATTESTATION")
+                .map(|v| parse_bool("HACKER_AGENT_REQUIRE_ATTESTATION", &v))
+                .transpose()?
+                .unwrap_or(false),
+            profile: env_non_empty("HACKER_AGENT_PROFILE")
+                .map(|v| Profile::parse(&v))
+                .transpose()?
+                .unwrap_or_default(),
+            offline: env_non_empty("HACKER_AGENT_OFFLINE")
+                .map(|v| parse_bool("HACKER_AGENT_OFFLINE", &v))
+                .transpose()?
+                .unwrap_or(false),
+            admin_token: env_non_empty("HACKER_AGENT_ADMIN_TOKEN"),
+            plugins_dir: env_non_empty("HACKER_AGENT_PLUGINS_DIR")
+                .unwrap_or_else(|| DEFAULT_PLUGINS_DIR.to_string()),
+            reports_dir: env_non_empty("HACKER_AGENT_REPORTS_DIR")
+                .unwrap_or_else(|| DEFAULT_REPORTS_DIR.to_string()),
+            command_tools: env_non_empty("HACKER_AGENT_COMMAND_TOOLS"),
+            auth_config: env_non_empty("HACKER_AGENT_AUTH_CONFIG"),
+            config_file: env_non_empty("HACKER_AGENT_CONFIG"),
+            scope: env_non_empty("HACKER_AGENT_SCOPE")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+            deny_targets: env_non_empty("HACKER_AGENT_DENY_TARGETS")
+                .map(|v| split_list(&v))
+                .unwrap_or_default(),
+        };
+
+        cfg.apply_args(std::env::args().skip(1))?;
+        Ok(cfg)
+    }
+
+    /// Apply command-line flags on top of the current values.
+    fn apply_args(&mut self, args: impl IntoIterator<Item = String>) -> Result<()> {
+        let cfg = self;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Accept both `--flag value` and `--flag=value`.
+            let (flag, inline) = match arg.split_once('=') {
+                Some((f, v)) => (f.to_string(), Some(v.to_string())),
+                None => (arg.clone(), None),
+            };
+            let mut value = || flag_value(&flag, inline.clone().or_else(|| args.next()));
+
+            match flag.as_str() {
+                "schema-export" => cfg.schema_export = true,
+                "--listen" => cfg.listen = Some(value()?),
+                "--allow-remote" => cfg.allow_remote = switch(&flag, inline.as_deref())?,
+                "--unix-socket" => cfg.unix_socket = Some(value()?),
+                "--production-targets" => cfg.production_targets = split_list(&value()?),
+                "--idle-timeout" => {
+                    cfg.idle_timeout_minutes = Some(parse_minutes(&flag, &value()?)?)
+                }
+                "--fixtures" => cfg.fixtures = Some(FixtureMode::parse(&value()?)?),
+                "--fixtures-dir" => cfg.fixtures_dir = value()?,
+                "--backend-url" => cfg.backend_url = parse_backend_url(&flag, &value()?)?,
+                "--nmap-backend-url" => cfg.nmap_backend_url = Some(parse_backend_url(&flag, &value()?)?),
+                "--openvas-backend-url" => cfg.openvas_backend_url = Some(parse_backend_url(&flag, &value()?)?),
+                "--local-nmap" => cfg.local_nmap = switch(&flag, inline.as_deref())?,
+                "--backend-token" => cfg.backend_token = Some(value()?),
+                "--backend-basic-auth" => cfg.backend_basic_auth = Some(value()?),
+                "--backend-health-interval" => cfg.backend_health_interval = parse_secs(&flag, &value()?)?,
+                "--task-watchdog" => cfg.task_watchdog_interval = parse_secs(&flag, &value()?)?,
+                "--gmp" => cfg.gmp = Some(GmpEndpoint::parse(&value()?)?),
+                "--gmp-ca-cert" => cfg.gmp_ca_cert = Some(value()?),
+                "--gvm-backends" => cfg.gvm_backends = Some(value()?),
+                "--request-timeout" => cfg.request_timeout = Some(parse_secs(&flag, &value()?)?),
+                "--tool-timeout" => cfg.tool_timeouts.extend(parse_tool_durations(&flag, &value()?)?),
+                "--cache-ttl" => cfg.cache_ttls.extend(parse_tool_durations(&flag, &value()?)?),
+                "--target-rate-limit" => cfg.target_rate_limit = parse_count(&flag, &value()?)?,
+                "--max-concurrency" => cfg.max_concurrency.extend(parse_tool_counts(&flag, &value()?)?),
+                "--retries" => cfg.retries.extend(parse_tool_counts(&flag, &value()?)?),
+                "--max-output-bytes" => cfg.max_output_bytes = parse_count(&flag, &value()?)? as usize,
+                "--max-output" => cfg.max_outputs.extend(parse_tool_counts(&flag, &value()?)?),
+                "--retry-backoff" => cfg.retry_backoffs.extend(parse_tool_millis(&flag, &value()?)?),
+                "--approval" => cfg.approval_mode = ApprovalMode::parse(&value()?)?,
+                "--tag-rules" => cfg.tag_rules = Some(value()?),
+                "--framing" => cfg.framing = Framing::parse(&value()?)?,
+                "--bulk-threshold" => cfg.bulk_threshold = parse_count(&flag, &value()?)?,
+                "--disable-capabilities" => {
+                    for c in split_list(&value()?) {
+                        cfg.disabled_capabilities.insert(Capability::parse(&c)?);
+                    }
+                }
+                "--disable-tool-families" => {
+                    for f in split_list(&value()?) {
+                        cfg.disabled_tool_families.push(capabilities::parse_tool_family(&f)?);
+                    }
+                }
+                "--monitor-webhook" => cfg.monitor_webhook = Some(value()?),
+                "--workspace" => cfg.workspace = value()?,
+                "--audit-log" => cfg.audit_log = Some(value()?),
+                "--scan-history" => cfg.scan_history = Some(value()?),
+                "--require-attestation" => cfg.require_attestation = switch(&flag, inline.as_deref())?,
+                "--offline" => cfg.offline = switch(&flag, inline.as_deref())?,
+                "--profile" => cfg.profile = Profile::parse(&value()?)?,
+                "--admin-token" => cfg.admin_token = Some(value()?),
+                "--plugins-dir" => cfg.plugins_dir = value()?,
+                "--reports-dir" => cfg.reports_dir = value()?,
+                "--command-tools" => cfg.command_tools = Some(value()?),
+                "--auth-config" => cfg.auth_config = Some(value()?),
+                "--config" => cfg.config_file = Some(value()?),
+                "--scope" => cfg.scope.extend(split_list(&value()?)),
+                "--deny-targets" => cfg.deny_targets.extend(split_list(&value()?)),
+                other => anyhow::bail!("unknown argument: {other}"),
+            }
+        }
+
+        if cfg.listen.is_some() && cfg.unix_socket.is_some() {
+            anyhow::bail!("--listen and --unix-socket are mutually exclusive");
+        }
+        if cfg.backend_token.is_some() && cfg.backend_basic_auth.is_some() {
+            anyhow::bail!("--backend-token and --backend-basic-auth are mutually exclusive");
+        }
+        if cfg.backend_basic_auth.as_deref().is_some_and(|c| !c.contains(':')) {
+            anyhow::bail!("--backend-basic-auth expects user:password");
+        }
+        // Fixtures record and replay the Go backend's HTTP answers only.
+        if cfg.gmp.is_some() && cfg.fixtures.is_some() {
+            anyhow::bail!("--gmp and --fixtures are mutually exclusive");
+        }
+        if cfg.gmp_ca_cert.is_some() && !matches!(cfg.gmp, Some(GmpEndpoint::Tls { .. })) {
+            anyhow::bail!("--gmp-ca-cert needs a TLS --gmp endpoint");
+        }
+        if cfg.require_attestation && cfg.audit_log.is_none() {
+            cfg.audit_log = Some(DEFAULT_AUDIT_LOG.to_string());
+        }
+        Ok(())
+    }
+}
+
+static CURRENT: OnceLock<RwLock<Arc<AppConfig>>> = OnceLock::new();
+
+/// Install the process-wide configuration. Call once at startup.
+pub fn install(cfg: AppConfig) {
+    let slot = CURRENT.get_or_init(|| RwLock::new(Arc::new(AppConfig::default())));
+    *slot.write().unwrap() = Arc::new(cfg);
+}
+
+/// Snapshot of the active configuration. Falls back to defaults when
+/// `install` has not been called.
+pub fn current() -> Arc<AppConfig> {
+    CURRENT
+        .get()
+        .map(|slot| slot.read().unwrap().clone())
+        .unwrap_or_default()
+}
+
+fn env_non_empty(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn split_list(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_minutes(source: &str, raw: &str) -> Result<u64> {
+    raw.trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{source} expects a whole number of minutes, got `{raw}`"))
+}
+
+fn parse_count(source: &str, raw: &str) -> Result<u64> {
+    raw.trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{source} expects a whole number, got `{raw}`"))
+}
+
+/// An on/off flag: `--flag` alone turns it on, `--flag=false` (or any
+/// `parse_bool` value) sets it explicitly.
+fn switch(flag: &str, inline: Option<&str>) -> Result<bool> {
+    inline.map_or(Ok(true), |raw| parse_bool(flag, raw))
+}
+
+/// An `http(s)://host[:port][/prefix]` backend URL, without a trailing
+/// slash so endpoint paths can be appended.
+pub fn parse_backend_url(source: &str, raw: &str) -> Result<String> {
+    let url = reqwest::Url::parse(raw.trim())
+        .map_err(|err| anyhow::anyhow!("{source} expects a backend URL, got `{raw}`: {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.query().is_some() || url.fragment().is_some() {
+        anyhow::bail!("{source} expects an http(s) URL without query or fragment, got `{raw}`");
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn parse_bool(source: &str, raw: &str) -> Result<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => anyhow::bail!("{source} expects true or false, got `{raw}`"),
+    }
+}
+
+fn parse_secs(source: &str, raw: &str) -> Result<Duration> {
+    raw.trim()
+        .parse()
+        .map(Duration::from_secs)
+        .map_err(|_| anyhow::anyhow!("{source} expects a whole number of seconds, got `{raw}`"))
+}
+
+/// Parse `name=secs[,name=secs...]` into per-tool timeouts.
+fn parse_tool_durations(source: &str, raw: &str) -> Result<HashMap<String, Duration>> {
+    split_list(raw)
+        .into_iter()
+        .map(|entry| {
+            let (name, secs) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("{source} expects name=secs, got `{entry}`"))?;
+            Ok((name.trim().to_string(), parse_secs(source, secs)?))
+        })
+        .collect()
+}
+
+/// Parse `name=n[,name=n...]` into per-tool limits.
+fn parse_tool_counts(source: &str, raw: &str) -> Result<HashMap<String, usize>> {
+    split_list(raw)
+        .into_iter()
+        .map(|entry| {
+            let (name, n) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("{source} expects name=n, got `{entry}`"))?;
+            Ok((name.trim().to_string(), parse_count(source, n)? as usize))
+        })
+        .collect()
+}
+
+/// Parse `name=ms[,name=ms...]` into per-tool durations.
+fn parse_tool_millis(source: &str, raw: &str) -> Result<HashMap<String, Duration>> {
+    Ok(parse_tool_counts(source, raw)?
+        .into_iter()
+        .map(|(name, ms)| (name, Duration::from_millis(ms as u64)))
+        .collect())
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<String> {
+    value.ok_or_else(|| anyhow::anyhow!("missing value for {flag}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<AppConfig> {
+        let mut cfg = AppConfig::default();
+        cfg.apply_args(args.iter().map(|a| a.to_string()))?;
+        Ok(cfg)
+    }
+
+    #[test]
+    fn flags_take_separate_or_inline_values() {
+        let cfg = parse(&["--listen", "127.0.0.1:7777", "--task-watchdog", "60"]).unwrap();
+        assert_eq!(cfg.listen.as_deref(), Some("127.0.0.1:7777"));
+        assert_eq!(parse(&["--listen=[::1]:7777"]).unwrap().listen.as_deref(), Some("[::1]:7777"));
+        assert_eq!(cfg.task_watchdog_interval, Duration::from_secs(60));
+        assert!(parse(&[]).unwrap().task_watchdog_interval.is_zero());
+        assert!(!cfg.allow_remote);
+        assert!(parse(&["--allow-remote"]).unwrap().allow_remote);
+    }
+
+    #[test]
+    fn per_tool_flags_accumulate() {
+        let cfg = parse(&[
+            "--tool-timeout",
+            "echo=5,quick_scan=60",
+            "--tool-timeout=comprehensive_scan=10800",
+            "--max-concurrency",
+            "advanced_nmap_scan=1",
+            "--retries",
+            "openvas_get_report=5,openvas_start_task=2",
+            "--retry-backoff=openvas_get_report=250",
+        ])
+        .unwrap();
+        assert_eq!(cfg.tool_timeouts.len(), 3);
+        assert_eq!(cfg.tool_timeouts["comprehensive_scan"], Duration::from_secs(10800));
+        assert_eq!(cfg.max_concurrency["advanced_nmap_scan"], 1);
+        assert_eq!(cfg.retries["openvas_start_task"], 2);
+        assert_eq!(cfg.retry_backoffs["openvas_get_report"], Duration::from_millis(250));
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["--listen"]).is_err());
+        assert!(parse(&["--listen", "127.0.0.1:1", "--unix-socket", "/tmp/s"]).is_err());
+        assert!(parse(&["--gmp", "/run/gvmd/gvmd.sock", "--fixtures", "replay"]).is_err());
+        assert!(parse(&["--gmp", "/run/gvmd/gvmd.sock", "--gmp-ca-cert", "ca.pem"]).is_err());
+    }
+
+    #[test]
+    fn backend_urls_are_normalized() {
+        let cfg = parse(&[
+            "--backend-url",
+            "https://scanner.internal:8443/api/",
+            "--openvas-backend-url=http://gvm:8081",
+        ])
+        .unwrap();
+        assert_eq!(cfg.backend_url, "https://scanner.internal:8443/api");
+        assert_eq!(cfg.openvas_backend_url.as_deref(), Some("http://gvm:8081"));
+        assert_eq!(parse(&[]).unwrap().backend_url, DEFAULT_BACKEND_URL);
+        assert!(parse(&["--backend-url", "127.0.0.1:8080"]).is_err());
+        assert!(parse(&["--nmap-backend-url", "ftp://scanner"]).is_err());
+    }
+
+    #[test]
+    fn backend_credentials_are_one_kind() {
+        let cfg = parse(&["--backend-basic-auth", "agent:s3cret:with-colon"]).unwrap();
+        assert_eq!(cfg.backend_basic_auth.as_deref(), Some("agent:s3cret:with-colon"));
+        assert!(parse(&["--backend-basic-auth", "agent"]).is_err());
+        assert!(parse(&["--backend-token", "t0k3n", "--backend-basic-auth", "agent:s3cret"]).is_err());
+    }
+
+    #[test]
+    fn gmp_endpoints_select_the_native_backend() {
+        let cfg = parse(&["--gmp=gvm.internal", "--gmp-ca-cert", "/etc/gvm/ca.pem"]).unwrap();
+        assert_eq!(cfg.gmp, Some(GmpEndpoint::Tls { host: "gvm.internal".to_string(), port: 9390 }));
+        assert_eq!(cfg.gmp_ca_cert.as_deref(), Some("/etc/gvm/ca.pem"));
+        assert!(parse(&[]).unwrap().gmp.is_none());
+    }
+
+    #[test]
+    fn switches_take_inline_values() {
+        let cfg = parse(&["--allow-remote=false", "--offline=yes", "--require-attestation=0", "--local-nmap"]).unwrap();
+        assert!(!cfg.allow_remote);
+        assert!(cfg.offline);
+        assert!(cfg.local_nmap);
+        assert!(!cfg.require_attestation);
+        assert!(cfg.audit_log.is_none());
+
+        let mut cfg = AppConfig { offline: true, ..AppConfig::default() };
+        cfg.apply_args(["--offline=false".to_string()]).unwrap();
+        assert!(!cfg.offline);
+
+        assert!(parse(&["--offline=maybe"]).is_err());
+    }
+
+    #[test]
+    fn attestation_implies_an_audit_log() {
+        let cfg = parse(&["--require-attestation"]).unwrap();
+        assert_eq!(cfg.audit_log.as_deref(), Some(DEFAULT_AUDIT_LOG));
+    }
+}
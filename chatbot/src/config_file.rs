@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::config::{self, AppConfig};
+use crate::{session, tool_state};
+
+/// How often the `--config` file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Per-tool settings from the `--config` file. Every field is optional;
+/// what the file sets overrides flags and environment, and is re-applied
+/// whenever the file changes.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// Seconds, per tool.
+    #[serde(default)]
+    tool_timeouts: HashMap<String, u64>,
+    /// Seconds, per tool.
+    #[serde(default)]
+    cache_ttls: HashMap<String, u64>,
+    #[serde(default)]
+    max_concurrency: HashMap<String, usize>,
+    #[serde(default)]
+    retries: HashMap<String, usize>,
+    /// Milliseconds, per tool.
+    #[serde(default)]
+    retry_backoff_ms: HashMap<String, u64>,
+    /// Bytes, per tool.
+    #[serde(default)]
+    max_outputs: HashMap<String, usize>,
+    #[serde(default)]
+    target_rate_limit: Option<u64>,
+    /// Tools switched off, by name or alias.
+    #[serde(default)]
+    disabled_tools: Vec<String>,
+    /// Replaces `--scope` when given.
+    #[serde(default)]
+    scope: Option<Vec<String>>,
+    /// Replaces `--deny-targets` when given.
+    #[serde(default)]
+    deny_targets: Option<Vec<String>>,
+    /// Replaces `--backend-url` when given.
+    #[serde(default)]
+    backend_url: Option<String>,
+    #[serde(default)]
+    nmap_backend_url: Option<String>,
+    #[serde(default)]
+    openvas_backend_url: Option<String>,
+}
+
+fn parse(raw: &str) -> Result<ConfigFile> {
+    let mut file = serde_yaml::from_str::<Option<ConfigFile>>(raw)?.unwrap_or_default();
+    for (key, url) in [
+        ("backend_url", &mut file.backend_url),
+        ("nmap_backend_url", &mut file.nmap_backend_url),
+        ("openvas_backend_url", &mut file.openvas_backend_url),
+    ] {
+        if let Some(raw) = url {
+            *url = Some(config::parse_backend_url(key, raw)?);
+        }
+    }
+    Ok(file)
+}
+
+/// `base` (flags and environment) with the file's settings on top.
+fn overlay(base: &AppConfig, file: &ConfigFile) -> AppConfig {
+    let mut cfg = base.clone();
+    let secs = |m: &HashMap<String, u64>| m.iter().map(|(k, v)| (k.clone(), Duration::from_secs(*v))).collect::<Vec<_>>();
+    cfg.tool_timeouts.extend(secs(&file.tool_timeouts));
+    cfg.cache_ttls.extend(secs(&file.cache_ttls));
+    cfg.max_concurrency.extend(file.max_concurrency.clone());
+    cfg.retries.extend(file.retries.clone());
+    cfg.retry_backoffs.extend(
+        file.retry_backoff_ms
+            .iter()
+            .map(|(k, v)| (k.clone(), Duration::from_millis(*v))),
+    );
+    cfg.max_outputs.extend(file.max_outputs.clone());
+    if let Some(limit) = file.target_rate_limit {
+        cfg.target_rate_limit = limit;
+    }
+    if let Some(scope) = &file.scope {
+        cfg.scope = scope.clone();
+    }
+    if let Some(deny) = &file.deny_targets {
+        cfg.deny_targets = deny.clone();
+    }
+    if let Some(url) = &file.backend_url {
+        cfg.backend_url = url.clone();
+    }
+    if file.nmap_backend_url.is_some() {
+        cfg.nmap_backend_url = file.nmap_backend_url.clone();
+    }
+    if file.openvas_backend_url.is_some() {
+        cfg.openvas_backend_url = file.openvas_backend_url.clone();
+    }
+    cfg
+}
+
+/// Read `path` and apply it over `base`. Returns whether the set of
+/// enabled tools changed.
+fn apply(path: &str, base: &AppConfig) -> Result<bool> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("reading config from {path}"))?;
+    let file = parse(&raw).with_context(|| format!("parsing config in {path}"))?;
+    let changed = tool_state::set_config_disabled(&file.disabled_tools)
+        .with_context(|| format!("disabled_tools in {path}"))?;
+    config::install(overlay(base, &file));
+    Ok(changed)
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Apply the `--config` file, if one is given, and watch it: edits are
+/// picked up within seconds, and clients are sent
+/// `notifications/tools/list_changed` when they enable or disable tools.
+/// Call after `tool_state::install`. A malformed file fails startup; a
+/// malformed edit is reported and the previous settings stay in force.
+pub fn install(path: Option<&str>, base: AppConfig) -> Result<()> {
+    let Some(path) = path else {
+        return Ok(());
+    };
+    apply(path, &base)?;
+    let path = path.to_string();
+    let mut seen = modified(&path);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let now = modified(&path);
+            if now == seen {
+                continue;
+            }
+            seen = now;
+            match apply(&path, &base) {
+                Ok(tools_changed) => {
+                    eprintln!("reloaded config from {path}");
+                    if tools_changed {
+                        session::broadcast("notifications/tools/list_changed");
+                    }
+                }
+                Err(err) => eprintln!("keeping previous config: {err:#}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_settings_override_flags() {
+        let mut base = AppConfig::default();
+        base.tool_timeouts.insert("echo".into(), Duration::from_secs(5));
+        base.tool_timeouts.insert("quick_scan".into(), Duration::from_secs(600));
+        base.scope = vec!["10.0.0.0/8".into()];
+        let file = parse(
+            r#"
+tool_timeouts: { quick_scan: 60 }
+retry_backoff_ms: { openvas_get_report: 250 }
+target_rate_limit: 0
+scope: [192.0.2.0/24]
+openvas_backend_url: https://gvm.internal:8443/
+"#,
+        )
+        .unwrap();
+        let cfg = overlay(&base, &file);
+        assert_eq!(cfg.tool_timeouts["echo"], Duration::from_secs(5));
+        assert_eq!(cfg.tool_timeouts["quick_scan"], Duration::from_secs(60));
+        assert_eq!(cfg.retry_backoffs["openvas_get_report"], Duration::from_millis(250));
+        assert_eq!(cfg.target_rate_limit, 0);
+        assert_eq!(cfg.scope, ["192.0.2.0/24"]);
+        assert_eq!(cfg.openvas_backend_url.as_deref(), Some("https://gvm.internal:8443"));
+        assert_eq!(cfg.backend_url, base.backend_url);
+        // Lists the file leaves out keep their flag values.
+        assert_eq!(cfg.deny_targets, base.deny_targets);
+    }
+
+    #[test]
+    fn empty_and_malformed_files() {
+        assert!(parse("").unwrap().disabled_tools.is_empty());
+        assert!(parse("tool_timeout: { echo: 5 }").is_err());
+        assert!(parse("tool_timeouts: { echo: soon }").is_err());
+        assert!(parse("backend_url: 127.0.0.1:8080").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// The first backend request a tool tried to send during a dry run, in
+/// place of sending it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlannedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub body: Value,
+    /// The nmap command line the backend would run for this request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+/// What a dry run has captured so far.
+#[derive(Default)]
+struct Capture {
+    /// Set by `note_command` just before the request it belongs to.
+    command: Option<String>,
+    request: Option<PlannedRequest>,
+}
+
+tokio::task_local! {
+    static CURRENT: Arc<Mutex<Capture>>;
+    /// Set while `sent` runs lookups that must reach the backend.
+    static SENT: ();
+}
+
+/// Run `fut` (a tool call) as a dry run: backend requests are captured
+/// instead of sent. Returns the tool's result and the first request it
+/// tried to send, if any.
+pub async fn scope<F: Future>(fut: F) -> (F::Output, Option<PlannedRequest>) {
+    let capture = Arc::new(Mutex::new(Capture::default()));
+    let output = CURRENT.scope(capture.clone(), fut).await;
+    let request = capture.lock().unwrap().request.take();
+    (output, request)
+}
+
+/// Run `fut` with its backend requests sent even inside a dry run. For the
+/// read-only lookups middleware makes to decide whether a call may run at
+/// all, which a dry run must answer like a real call.
+pub async fn sent<F: Future>(fut: F) -> F::Output {
+    SENT.scope((), fut).await
+}
+
+/// Apply `f` to the running dry run's capture; `None` outside a dry run
+/// and inside `sent`.
+fn with_capture<R>(f: impl FnOnce(&Arc<Mutex<Capture>>) -> R) -> Option<R> {
+    if SENT.try_with(|_| ()).is_ok() {
+        return None;
+    }
+    CURRENT.try_with(f).ok()
+}
+
+/// Whether the running task is a dry run.
+pub fn is_active() -> bool {
+    with_capture(|_| ()).is_some()
+}
+
+/// Attach the nmap command line to the next captured request. Outside a
+/// dry run it does nothing.
+pub fn note_command(argv: &[String]) {
+    with_capture(|capture| capture.lock().unwrap().command = Some(shell_join(argv)));
+}
+
+/// During a dry run, capture the request instead of sending it and return
+/// the error that stops the tool there; `None` outside a dry run. Tools
+/// that chain several requests stop at the first, since later ones depend
+/// on its response.
+pub fn intercept(method: &str, url: &str, body: Option<&Value>) -> Option<anyhow::Error> {
+    with_capture(|capture| {
+        let mut capture = capture.lock().unwrap();
+        if capture.request.is_none() {
+            let command = capture.command.take();
+            capture.request = Some(PlannedRequest {
+                method: method.to_string(),
+                url: url.to_string(),
+                body: body.cloned().unwrap_or(Value::Null),
+                command,
+            });
+        }
+        anyhow::anyhow!("dry run: {method} {url} was not sent")
+    })
+}
+
+/// `argv` as a command line a POSIX shell would split back into `argv`.
+fn shell_join(argv: &[String]) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_.,:/@%+=".contains(c);
+    argv.iter()
+        .map(|arg| {
+            if !arg.is_empty() && arg.chars().all(safe) {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn captures_the_first_request_only() {
+        let (output, request) = scope(async {
+            note_command(&["nmap".into(), "-T4".into(), "10.0.0.1".into()]);
+            let first = intercept("POST", "http://backend/scan", Some(&json!({ "target": "10.0.0.1" })));
+            let second = intercept("GET", "http://backend/other", None);
+            (first.is_some(), second.is_some())
+        })
+        .await;
+        assert_eq!(output, (true, true));
+        let request = request.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "http://backend/scan");
+        assert_eq!(request.body["target"], "10.0.0.1");
+        assert_eq!(request.command.as_deref(), Some("nmap -T4 10.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn nothing_is_intercepted_outside_a_dry_run() {
+        assert!(intercept("GET", "http://backend/version", None).is_none());
+        let ((), request) = scope(async {}).await;
+        assert!(request.is_none());
+        let (sent, request) = scope(sent(async { intercept("GET", "http://backend/tasks", None).is_none() })).await;
+        assert!(sent && request.is_none());
+    }
+
+    #[test]
+    fn shell_join_quotes_only_what_needs_it() {
+        let argv: Vec<String> = ["nmap", "--script", "default,vuln", "a b", "it's", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(shell_join(&argv), r"nmap --script default,vuln 'a b' 'it'\''s' ''");
+    }
+}
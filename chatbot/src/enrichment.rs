@@ -0,0 +1,346 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::api;
+use crate::config;
+use crate::models::openvas::{CveInfo, PrioritizedFinding, ReportResult};
+
+const NVD_URL: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+const KEV_URL: &str = "https://www.cisa.gov/sites/default/files/feeds/known_exploited_vulnerabilities.json";
+const EPSS_URL: &str = "https://api.first.org/data/v1/epss";
+
+/// How long looked-up CVEs and the KEV catalog are reused. All three
+/// sources update at most daily.
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Per request to a source; the KEV catalog is about a megabyte.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// CVEs per EPSS request.
+const EPSS_BATCH: usize = 100;
+
+/// References kept per CVE; NVD lists dozens for popular ones.
+const MAX_REFERENCES: usize = 10;
+
+/// EPSS score from which a finding is `high` priority whatever its CVSS.
+const EPSS_HIGH: f64 = 0.1;
+
+/// NVD's answers (without KEV and EPSS), per CVE.
+static NVD_CACHE: Mutex<BTreeMap<String, (Instant, CveInfo)>> = Mutex::new(BTreeMap::new());
+
+/// The CVE IDs of the KEV catalog, and when it was fetched.
+static KEV_CACHE: Mutex<Option<(Instant, Arc<BTreeSet<String>>)>> = Mutex::new(None);
+
+/// Look `cves` up in NVD, CISA KEV and FIRST EPSS. A source that cannot be
+/// reached leaves its fields out and adds a note instead of failing the
+/// lookup; in `--offline` mode none is contacted. Send `NVD_API_KEY` from
+/// the server's environment for NVD's higher rate limit.
+pub async fn lookup(cves: &[String]) -> (BTreeMap<String, CveInfo>, Vec<String>) {
+    let mut notes = Vec::new();
+    if cves.is_empty() {
+        return (BTreeMap::new(), notes);
+    }
+    if config::current().offline {
+        notes.push("offline mode: CVEs were not looked up in NVD, KEV or EPSS".to_string());
+        return (BTreeMap::new(), notes);
+    }
+
+    let mut details = BTreeMap::new();
+    let api_key = std::env::var("NVD_API_KEY").ok().filter(|k| !k.trim().is_empty());
+    for (n, cve) in cves.iter().enumerate() {
+        match nvd(cve, api_key.as_deref()).await {
+            Ok(info) => {
+                details.insert(cve.clone(), info);
+            }
+            Err(err) => {
+                // Typically the rate limit; the remaining lookups would fail too.
+                notes.push(format!("NVD: {err:#}; {} CVEs not looked up", cves.len() - n));
+                break;
+            }
+        }
+    }
+    for cve in cves {
+        details.entry(cve.clone()).or_insert_with(|| CveInfo {
+            id: cve.clone(),
+            ..CveInfo::default()
+        });
+    }
+
+    match kev_catalog().await {
+        Ok(kev) => {
+            for info in details.values_mut() {
+                info.kev = kev.contains(&info.id);
+            }
+        }
+        Err(err) => notes.push(format!("CISA KEV: {err:#}")),
+    }
+    for batch in cves.chunks(EPSS_BATCH) {
+        let url = format!("{EPSS_URL}?cve={}", batch.join(","));
+        match get_json(&url, None).await {
+            Ok(body) => {
+                for (cve, epss, percentile) in parse_epss(&body) {
+                    if let Some(info) = details.get_mut(&cve) {
+                        info.epss = Some(epss);
+                        info.epss_percentile = Some(percentile);
+                    }
+                }
+            }
+            Err(err) => {
+                notes.push(format!("FIRST EPSS: {err:#}"));
+                break;
+            }
+        }
+    }
+    (details, notes)
+}
+
+async fn nvd(cve: &str, api_key: Option<&str>) -> Result<CveInfo> {
+    if let Some((at, info)) = NVD_CACHE.lock().unwrap().get(cve)
+        && at.elapsed() < CACHE_TTL
+    {
+        return Ok(info.clone());
+    }
+    let body = get_json(&format!("{NVD_URL}?cveId={cve}"), api_key).await?;
+    let info = parse_nvd(&body)
+        .into_iter()
+        .find(|info| info.id.eq_ignore_ascii_case(cve))
+        .unwrap_or_else(|| CveInfo {
+            id: cve.to_string(),
+            ..CveInfo::default()
+        });
+    NVD_CACHE.lock().unwrap().insert(cve.to_string(), (Instant::now(), info.clone()));
+    Ok(info)
+}
+
+async fn kev_catalog() -> Result<Arc<BTreeSet<String>>> {
+    if let Some((at, kev)) = KEV_CACHE.lock().unwrap().as_ref()
+        && at.elapsed() < CACHE_TTL
+    {
+        return Ok(kev.clone());
+    }
+    let kev = Arc::new(parse_kev(&get_json(KEV_URL, None).await?));
+    *KEV_CACHE.lock().unwrap() = Some((Instant::now(), kev.clone()));
+    Ok(kev)
+}
+
+async fn get_json(url: &str, api_key: Option<&str>) -> Result<Value> {
+    api::ensure_allowed(url)?;
+    let mut request = api::http_client().get(url).timeout(REQUEST_TIMEOUT);
+    if let Some(key) = api_key {
+        request = request.header("apiKey", key);
+    }
+    Ok(request.send().await?.error_for_status()?.json().await?)
+}
+
+/// The CVEs of an NVD API 2.0 `cves` response.
+fn parse_nvd(body: &Value) -> Vec<CveInfo> {
+    let Some(vulnerabilities) = body["vulnerabilities"].as_array() else {
+        return Vec::new();
+    };
+    vulnerabilities
+        .iter()
+        .filter_map(|v| {
+            let cve = &v["cve"];
+            let metrics = &cve["metrics"];
+            // The primary (NVD's own) v3.1 score, else v3.0, else any.
+            let cvss = ["cvssMetricV31", "cvssMetricV30"]
+                .iter()
+                .filter_map(|key| metrics[key].as_array())
+                .flat_map(|list| {
+                    let primary = list.iter().filter(|m| m["type"] == "Primary");
+                    primary.chain(list.iter())
+                })
+                .map(|m| &m["cvssData"])
+                .next();
+            let published = cve["published"].as_str().map(|p| {
+                // NVD's timestamps are UTC without an offset.
+                chrono::NaiveDateTime::parse_from_str(p, "%Y-%m-%dT%H:%M:%S%.f")
+                    .map(|t| t.and_utc().to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                    .unwrap_or_else(|_| p.to_string())
+            });
+            let mut references: Vec<String> = Vec::new();
+            for url in cve["references"].as_array().into_iter().flatten().filter_map(|r| r["url"].as_str()) {
+                if references.len() == MAX_REFERENCES {
+                    break;
+                }
+                if !references.iter().any(|seen| seen == url) {
+                    references.push(url.to_string());
+                }
+            }
+            Some(CveInfo {
+                id: cve["id"].as_str()?.to_string(),
+                cvss_v3_score: cvss.and_then(|c| c["baseScore"].as_f64()),
+                cvss_v3_vector: cvss.and_then(|c| c["vectorString"].as_str()).map(str::to_string),
+                published,
+                references,
+                ..CveInfo::default()
+            })
+        })
+        .collect()
+}
+
+/// The CVE IDs of CISA's KEV catalog.
+fn parse_kev(body: &Value) -> BTreeSet<String> {
+    body["vulnerabilities"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v["cveID"].as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// `(cve, epss, percentile)` of a FIRST EPSS response, which sends the
+/// numbers as strings.
+fn parse_epss(body: &Value) -> Vec<(String, f64, f64)> {
+    let number = |v: &Value| v.as_f64().or_else(|| v.as_str()?.parse().ok());
+    body["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| Some((d["cve"].as_str()?.to_string(), number(&d["epss"])?, number(&d["percentile"])?)))
+        .collect()
+}
+
+/// `urgent` when one of the finding's CVEs is known to be exploited;
+/// `high` when one is likely to be (EPSS) or the finding is critical;
+/// otherwise by severity.
+fn priority(result: &ReportResult, cves: &[CveInfo]) -> &'static str {
+    let epss = cves.iter().filter_map(|c| c.epss).fold(0.0, f64::max);
+    if cves.iter().any(|c| c.kev) {
+        "urgent"
+    } else if epss >= EPSS_HIGH || result.severity >= 9.0 {
+        "high"
+    } else if result.severity >= 4.0 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+fn rank(priority: &str) -> u8 {
+    match priority {
+        "urgent" => 0,
+        "high" => 1,
+        "medium" => 2,
+        _ => 3,
+    }
+}
+
+/// Attach the looked-up `details` to each result and sort the findings
+/// most pressing first: by priority, then EPSS, then severity.
+pub fn prioritize(results: Vec<ReportResult>, details: &BTreeMap<String, CveInfo>) -> Vec<PrioritizedFinding> {
+    let mut findings: Vec<PrioritizedFinding> = results
+        .into_iter()
+        .map(|result| {
+            let cve_details: Vec<CveInfo> = result.cves.iter().filter_map(|c| details.get(c).cloned()).collect();
+            PrioritizedFinding {
+                priority: priority(&result, &cve_details).to_string(),
+                result,
+                cve_details,
+            }
+        })
+        .collect();
+    let epss = |f: &PrioritizedFinding| f.cve_details.iter().filter_map(|c| c.epss).fold(0.0, f64::max);
+    findings.sort_by(|a, b| {
+        rank(&a.priority)
+            .cmp(&rank(&b.priority))
+            .then_with(|| epss(b).partial_cmp(&epss(a)).unwrap_or(Ordering::Equal))
+            .then_with(|| b.result.severity.partial_cmp(&a.result.severity).unwrap_or(Ordering::Equal))
+    });
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn result(severity: f64, cves: &[&str]) -> ReportResult {
+        ReportResult {
+            id: format!("result-{severity}"),
+            name: "finding".into(),
+            host: "10.0.0.5".into(),
+            port: "443/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".into(),
+            severity,
+            threat: "High".into(),
+            qod: Some(80),
+            cves: cves.iter().map(|c| c.to_string()).collect(),
+            solution: None,
+        }
+    }
+
+    #[test]
+    fn sources_parse_into_cve_details() {
+        let nvd = json!({"vulnerabilities": [{"cve": {
+            "id": "CVE-2023-38408",
+            "published": "2023-07-20T03:15:10.170",
+            "references": [
+                {"url": "https://www.openssh.com/txt/release-9.3p2"},
+                {"url": "https://www.openssh.com/txt/release-9.3p2"}
+            ],
+            "metrics": {"cvssMetricV31": [
+                {"type": "Secondary", "cvssData": {"baseScore": 9.0, "vectorString": "CVSS:3.1/AV:N/AC:H"}},
+                {"type": "Primary", "cvssData": {
+                    "baseScore": 9.8,
+                    "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+                }}
+            ]}
+        }}]});
+        let info = &parse_nvd(&nvd)[0];
+        assert_eq!(info.cvss_v3_score, Some(9.8));
+        assert_eq!(info.cvss_v3_vector.as_deref(), Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"));
+        assert_eq!(info.published.as_deref(), Some("2023-07-20T03:15:10Z"));
+        assert_eq!(info.references, ["https://www.openssh.com/txt/release-9.3p2"]);
+        assert!(parse_nvd(&json!({"message": "rate limited"})).is_empty());
+
+        let kev = parse_kev(&json!({"vulnerabilities": [{"cveID": "CVE-2023-38408"}]}));
+        assert!(kev.contains("CVE-2023-38408"));
+        let epss = parse_epss(&json!({"data": [{"cve": "CVE-2023-38408", "epss": "0.58", "percentile": "0.97"}]}));
+        assert_eq!(epss, [("CVE-2023-38408".to_string(), 0.58, 0.97)]);
+    }
+
+    #[test]
+    fn exploited_and_likely_exploited_findings_come_first() {
+        let mut details = BTreeMap::new();
+        details.insert(
+            "CVE-1".to_string(),
+            CveInfo {
+                id: "CVE-1".into(),
+                kev: true,
+                ..CveInfo::default()
+            },
+        );
+        details.insert(
+            "CVE-2".to_string(),
+            CveInfo {
+                id: "CVE-2".into(),
+                epss: Some(0.4),
+                ..CveInfo::default()
+            },
+        );
+        let findings = prioritize(
+            vec![
+                result(9.8, &[]),
+                result(2.6, &[]),
+                result(5.0, &["CVE-2"]),
+                result(6.1, &["CVE-1"]),
+                result(5.0, &[]),
+            ],
+            &details,
+        );
+        let order: Vec<(&str, f64)> = findings.iter().map(|f| (f.priority.as_str(), f.result.severity)).collect();
+        assert_eq!(
+            order,
+            [("urgent", 6.1), ("high", 5.0), ("high", 9.8), ("medium", 5.0), ("low", 2.6)]
+        );
+        assert_eq!(findings[0].cve_details[0].id, "CVE-1");
+    }
+}
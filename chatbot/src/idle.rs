@@ -0,0 +1,96 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::api;
+
+/// Tracks request activity so the server can suspend itself after a period
+/// of inactivity (e.g. when an editor leaves it running all day) and resume
+/// transparently on the next request.
+struct IdleState {
+    last_activity: Instant,
+    suspended: bool,
+    /// Requests still being handled; the server never suspends under a
+    /// running scan.
+    in_flight: usize,
+}
+
+static STATE: Mutex<Option<IdleState>> = Mutex::new(None);
+
+fn with_state<T>(f: impl FnOnce(&mut IdleState) -> T) -> T {
+    let mut guard = STATE.lock().unwrap();
+    let state = guard.get_or_insert_with(|| IdleState {
+        last_activity: Instant::now(),
+        suspended: false,
+        in_flight: 0,
+    });
+    f(state)
+}
+
+/// Record activity; resumes the server if it was suspended.
+pub fn touch() {
+    with_state(|state| {
+        state.last_activity = Instant::now();
+        if state.suspended {
+            state.suspended = false;
+            eprintln!("hacker_agent resumed after idle suspend");
+        }
+    });
+}
+
+/// Marks a client request as in progress until dropped.
+pub struct Busy(());
+
+/// Record an incoming request. The server stays awake until the returned
+/// guard is dropped, and the idle timeout counts from then.
+pub fn begin() -> Busy {
+    touch();
+    with_state(|state| state.in_flight += 1);
+    Busy(())
+}
+
+impl Drop for Busy {
+    fn drop(&mut self) {
+        with_state(|state| {
+            state.in_flight -= 1;
+            state.last_activity = Instant::now();
+        });
+    }
+}
+
+/// Whether the server is suspended. Background work (e.g. the monitor
+/// scheduler) checks this and skips its rounds until a request resumes it.
+pub fn is_suspended() -> bool {
+    STATE.lock().unwrap().as_ref().is_some_and(|state| state.suspended)
+}
+
+/// Spawn the idle watchdog. After `timeout` without requests the server is
+/// suspended: backend connections are released and background work pauses
+/// until the next request arrives.
+pub fn spawn_monitor(timeout: Duration) {
+    touch();
+    let check_every = (timeout / 4).clamp(Duration::from_secs(1), Duration::from_secs(30));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(check_every);
+        loop {
+            ticker.tick().await;
+
+            let should_suspend = with_state(|state| {
+                let idle = state.in_flight == 0 && state.last_activity.elapsed() >= timeout;
+                if !state.suspended && idle {
+                    state.suspended = true;
+                    return true;
+                }
+                false
+            });
+
+            if should_suspend {
+                api::release_client();
+                eprintln!(
+                    "hacker_agent idle for {}s; suspended background work and released backend connections",
+                    timeout.as_secs()
+                );
+            }
+        }
+    });
+}
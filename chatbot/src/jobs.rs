@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{session, targets};
+
+/// Finished jobs kept in memory; the oldest are dropped beyond this.
+const MAX_HISTORY: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Waiting for the user's approval or a free concurrency slot.
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    // The request was abandoned before the tool finished (timeout or
+    // client disconnect).
+    Cancelled,
+}
+
+/// One tool call, from the moment it is queued until it finishes.
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: String,
+    pub tool: String,
+    /// `Tool::version` of the tool that ran.
+    pub tool_version: String,
+    pub target: Option<String>,
+    pub operator: String,
+    pub state: JobState,
+    pub queued_at: DateTime<Utc>,
+    /// When the tool itself started; `None` while queued, or if the call
+    /// never got past approval.
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Time the tool ran, not counting the wait in the queue.
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+}
+
+static JOBS: Mutex<VecDeque<Job>> = Mutex::new(VecDeque::new());
+
+tokio::task_local! {
+    /// The job of the call being handled, so the registry can mark it
+    /// running once the call reaches the tool.
+    static CURRENT: String;
+}
+
+/// Handle for a running job. Finish it with `succeed`/`fail`; if it is
+/// dropped first (e.g. the request timed out) the job is marked cancelled.
+pub struct JobGuard {
+    id: String,
+    done: bool,
+}
+
+/// Record a tool call as queued. Run the rest of the call inside
+/// `JobGuard::scope` so `mark_running` finds it.
+pub fn start(tool: &str, tool_version: &str, input: &Value) -> JobGuard {
+    let job = Job {
+        id: uuid::Uuid::new_v4().to_string(),
+        tool: tool.to_string(),
+        tool_version: tool_version.to_string(),
+        target: targets::target_of(input).map(str::to_string),
+        operator: session::current()
+            .map(|s| s.operator())
+            .unwrap_or_else(|| "internal".to_string()),
+        state: JobState::Queued,
+        queued_at: Utc::now(),
+        started_at: None,
+        finished_at: None,
+        duration_ms: None,
+        error: None,
+    };
+    let id = job.id.clone();
+
+    let mut jobs = JOBS.lock().unwrap();
+    jobs.push_back(job);
+    while jobs.len() > MAX_HISTORY {
+        match jobs.iter().position(|j| !j.is_active()) {
+            Some(i) => {
+                jobs.remove(i);
+            }
+            None => break,
+        }
+    }
+
+    JobGuard { id, done: false }
+}
+
+/// Mark the current call's job as running: it got past approval and
+/// concurrency limits and the tool is about to execute.
+pub fn mark_running() {
+    let Ok(id) = CURRENT.try_with(String::clone) else {
+        return;
+    };
+    if let Some(job) = JOBS.lock().unwrap().iter_mut().find(|j| j.id == id) {
+        job.state = JobState::Running;
+        job.started_at = Some(Utc::now());
+    }
+}
+
+impl Job {
+    fn is_active(&self) -> bool {
+        matches!(self.state, JobState::Queued | JobState::Running)
+    }
+}
+
+impl JobGuard {
+    /// Run `fut` as this job, so `mark_running` inside it applies here.
+    pub async fn scope<F: std::future::Future>(&self, fut: F) -> F::Output {
+        CURRENT.scope(self.id.clone(), fut).await
+    }
+
+    pub fn succeed(mut self) {
+        self.finish(JobState::Succeeded, None);
+    }
+
+    pub fn fail(mut self, error: &str) {
+        self.finish(JobState::Failed, Some(error.to_string()));
+    }
+
+    fn finish(&mut self, state: JobState, error: Option<String>) {
+        self.done = true;
+        let mut jobs = JOBS.lock().unwrap();
+        if let Some(job) = jobs.iter_mut().find(|j| j.id == self.id) {
+            let now = Utc::now();
+            job.state = state;
+            job.finished_at = Some(now);
+            job.duration_ms = job.started_at.map(|started| (now - started).num_milliseconds());
+            job.error = error;
+        }
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if !self.done {
+            self.finish(JobState::Cancelled, Some("request abandoned before completion".into()));
+        }
+    }
+}
+
+/// Snapshot of the queue (queued and running jobs) and history (everything
+/// else), oldest first.
+pub fn snapshot() -> Vec<Job> {
+    JOBS.lock().unwrap().iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn job(id: &str) -> Job {
+        snapshot().into_iter().find(|j| j.id == id).unwrap()
+    }
+
+    #[tokio::test]
+    async fn jobs_are_queued_until_the_tool_runs() {
+        let guard = start("echo", "1.2.0", &json!({ "target": "10.0.0.1" }));
+        let id = guard.id.clone();
+        assert_eq!(job(&id).state, JobState::Queued);
+        assert_eq!(job(&id).target.as_deref(), Some("10.0.0.1"));
+        assert_eq!(job(&id).tool_version, "1.2.0");
+
+        guard.scope(async { mark_running() }).await;
+        assert_eq!(job(&id).state, JobState::Running);
+
+        guard.succeed();
+        let done = job(&id);
+        assert_eq!(done.state, JobState::Succeeded);
+        assert!(done.started_at.is_some() && done.duration_ms.is_some());
+    }
+
+    #[test]
+    fn refused_and_abandoned_jobs_never_started() {
+        let refused = start("stealth_scan", "1.0.0", &json!({}));
+        let id = refused.id.clone();
+        refused.fail("Not approved");
+        assert_eq!(job(&id).state, JobState::Failed);
+        assert_eq!(job(&id).duration_ms, None);
+
+        let abandoned = start("stealth_scan", "1.0.0", &json!({}));
+        let id = abandoned.id.clone();
+        drop(abandoned);
+        assert_eq!(job(&id).state, JobState::Cancelled);
+    }
+}
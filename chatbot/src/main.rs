@@ -1,16 +1,54 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use tool_error::ToolError;
 
 mod api;
+mod approval;
+mod audit;
+mod auth;
+mod capabilities;
+mod command_tools;
+mod config;
+mod config_file;
+mod dry_run;
+mod enrichment;
+mod idle;
+mod jobs;
+mod metrics;
+mod middleware;
+mod models;
+mod monitor;
+mod native;
+mod partial;
+mod plugins;
+mod progress;
+mod policy;
 mod services;
 mod tools;
 mod prompts;
+mod report_render;
+mod resources;
+mod sarif;
+mod scan_history;
+mod scan_jobs;
+mod schema_export;
+mod session;
+mod tagging;
+mod targets;
+mod throttle;
+mod tool_error;
+mod tool_state;
+mod trace;
+mod transport;
+mod validation;
+mod watchdog;
 
 /// Basic JSON-RPC-like request type.
 #[derive(Debug, Deserialize)]
@@ -38,13 +76,114 @@ struct RpcResponse {
 struct RpcError {
     code: i32,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+/// MCP tool annotations, surfaced in `tools/list` so clients can tell safe
+/// read-only tools from intrusive ones and prompt the user accordingly.
+/// Here "open world" means the tool sends traffic to scan targets.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ToolAnnotations {
+    pub read_only_hint: bool,
+    pub destructive_hint: bool,
+    pub idempotent_hint: bool,
+    pub open_world_hint: bool,
+}
+
+impl ToolAnnotations {
+    /// Only reads local or backend state; never touches scan targets.
+    pub const READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+
+    /// Creates or changes backend state (e.g. GVM objects) without touching
+    /// scan targets.
+    pub const BACKEND_WRITE: Self = Self {
+        read_only_hint: false,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+
+    /// Deletes backend state (e.g. GVM objects) without touching scan targets.
+    pub const BACKEND_DELETE: Self = Self {
+        read_only_hint: false,
+        destructive_hint: true,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+
+    /// Sends probes to targets, but only non-intrusive ones.
+    pub const ACTIVE_SCAN: Self = Self {
+        read_only_hint: false,
+        destructive_hint: false,
+        idempotent_hint: false,
+        open_world_hint: true,
+    };
+
+    /// Sends probes that can disrupt targets or trip defenses (vuln scripts,
+    /// evasion, full vulnerability scans).
+    pub const INTRUSIVE_SCAN: Self = Self {
+        read_only_hint: false,
+        destructive_hint: true,
+        idempotent_hint: false,
+        open_world_hint: true,
+    };
 }
 
+impl Default for ToolAnnotations {
+    /// The MCP spec defaults: assume the worst until a tool says otherwise.
+    fn default() -> Self {
+        Self::INTRUSIVE_SCAN
+    }
+}
+
+/// What a tool is for, so agents can narrow the catalog they show the model
+/// (`tools/list` with a `category` filter, or `list_tools_by_category`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolCategory {
+    /// Host and service discovery: port scans, banners, monitoring.
+    Recon,
+    /// Vulnerability scanning through OpenVAS/GVM.
+    VulnScan,
+    /// Intelligence gathered without touching targets.
+    Osint,
+    /// Server housekeeping, diagnostics and governance.
+    Admin,
+}
+
+impl ToolCategory {
+    pub const ALL: [Self; 4] = [
+        ToolCategory::Recon,
+        ToolCategory::VulnScan,
+        ToolCategory::Osint,
+        ToolCategory::Admin,
+    ];
+}
+
+/// Version of tools that do not declare their own.
+pub const DEFAULT_TOOL_VERSION: &str = "1.0.0";
+
 /// Generic tool trait, similar in spirit to a fastmcp tool.
 #[async_trait]
 pub trait Tool: Send + Sync {
     fn name(&self) -> &'static str;
     fn description(&self) -> &'static str;
+    fn category(&self) -> ToolCategory;
+
+    /// Revision of the tool's input schema and behaviour, reported in
+    /// `tools/list`, in every result and in job and audit records so a scan
+    /// can be traced to the tool revision that produced it. Bump it when the
+    /// arguments or the output change.
+    fn version(&self) -> &'static str {
+        DEFAULT_TOOL_VERSION
+    }
 
     /// JSON Schema for this tool's `input` parameter (MCP `inputSchema`).
     /// By default, accept any JSON object. Individual tools can override.
@@ -55,105 +194,456 @@ pub trait Tool: Send + Sync {
         })
     }
 
+    /// Behavioural hints for clients (MCP `annotations`). Defaults to the
+    /// most cautious description; tools override to declare themselves safe.
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    /// How long `execute` may run before the registry gives up on it.
+    /// `--tool-timeout` overrides it; `None` means no per-tool limit.
+    fn default_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Opt-in: how long a successful result may be reused for identical
+    /// input. Only for tools whose output rarely changes and whose calls
+    /// have no side effects.
+    fn cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    /// How many calls of this tool may execute at once; further calls wait
+    /// for a slot. `--max-concurrency` overrides it; `None` means unlimited.
+    fn max_concurrency(&self) -> Option<usize> {
+        None
+    }
+
+    /// How transient backend failures are retried. `--retries` and
+    /// `--retry-backoff` override it. By default only read-only tools retry:
+    /// repeating anything else could, e.g., create a task twice when the
+    /// first attempt reached gvmd but its answer got lost.
+    fn retry_policy(&self) -> middleware::RetryPolicy {
+        if self.annotations().read_only_hint {
+            middleware::RetryPolicy::READ_ONLY
+        } else {
+            middleware::RetryPolicy::NONE
+        }
+    }
+
+    /// Whether the tool can be called as a dry run, returning the backend
+    /// request it would send instead of sending it. Only tools whose every
+    /// side effect goes through the backend API may opt in.
+    fn supports_dry_run(&self) -> bool {
+        false
+    }
+
+    /// Input field naming the host(s) the tool reaches, for tools whose
+    /// target is not in one of `targets::TARGET_FIELDS` (command and plugin
+    /// tools). The engagement scope, the denylist and the per-target rate
+    /// limit check it like those.
+    fn target_field(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the tool talks to gvmd, so a call can name one of the
+    /// `--gvm-backends` in a `backend` argument (see `api::gvm_backends`).
+    fn selects_gvm_backend(&self) -> bool {
+        false
+    }
+
     async fn execute(&self, input: Value) -> Result<Value>;
 }
 
 /// Registry of tools that can be listed and called.
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
+    /// Other names a tool answers to, e.g. its name before a rename.
+    aliases: HashMap<String, ToolAlias>,
+    /// Compiled `input_schema` of each tool, checked before it is called.
+    validators: HashMap<String, jsonschema::Validator>,
+    /// Wraps every call, outermost first (see `middleware`).
+    middleware: Vec<Arc<dyn middleware::ToolMiddleware>>,
+}
+
+/// An alternative name for a registered tool.
+struct ToolAlias {
+    /// Canonical name of the tool the alias resolves to.
+    target: String,
+    /// Listed as deprecated and answered with a deprecation notice, so
+    /// agents move to the canonical name before the alias is removed.
+    deprecated: bool,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            aliases: HashMap::new(),
+            validators: HashMap::new(),
+            middleware: Vec::new(),
         }
     }
 
     pub fn register<T: Tool + 'static>(&mut self, tool: T) {
+        // Tools outside the launch profile are not just hidden but absent.
+        if !capabilities::profile_allows(tool.annotations()) {
+            return;
+        }
+        if let Some(validator) = validation::compile(tool.name(), &tool.input_schema()) {
+            self.validators.insert(tool.name().to_string(), validator);
+        }
         self.tools
             .insert(tool.name().to_string(), Arc::new(tool));
     }
 
-    fn list(&self) -> Vec<Value> {
+    /// Make `alias` call the already registered tool `target`, e.g. to keep
+    /// an old name working after a rename. Ignored when `target` is not
+    /// registered (its family or profile is disabled).
+    pub fn register_alias(&mut self, alias: &str, target: &str, deprecated: bool) {
+        if self.tools.contains_key(target) {
+            self.aliases.insert(
+                alias.to_string(),
+                ToolAlias {
+                    target: target.to_string(),
+                    deprecated,
+                },
+            );
+        }
+    }
+
+    /// Look up a tool by its name or one of its aliases.
+    fn get(&self, name: &str) -> Option<&Arc<dyn Tool>> {
         self.tools
-            .values()
-            .map(|t| {
-                json!({
-                    "name": t.name(),
-                    "description": t.description(),
-                    "inputSchema": t.input_schema(),
-                })
+            .get(name)
+            .or_else(|| self.tools.get(&self.aliases.get(name)?.target))
+    }
+
+    /// Canonical name to use instead of `name`, if `name` is a deprecated alias.
+    fn replacement_for(&self, name: &str) -> Option<&str> {
+        self.aliases
+            .get(name)
+            .filter(|a| a.deprecated)
+            .map(|a| a.target.as_str())
+    }
+
+    /// Every callable name, aliases included, for `tool_state`.
+    fn catalog(&self) -> Vec<tool_state::CatalogEntry> {
+        let canonical = self.tools.values().map(|t| tool_state::CatalogEntry {
+            name: t.name().to_string(),
+            canonical: t.name().to_string(),
+            category: t.category(),
+            description: t.description().to_string(),
+        });
+        let aliases = self.aliases.iter().filter_map(|(alias, a)| {
+            let t = self.tools.get(&a.target)?;
+            Some(tool_state::CatalogEntry {
+                name: alias.clone(),
+                canonical: a.target.clone(),
+                category: t.category(),
+                description: t.description().to_string(),
             })
-            .collect()
+        });
+        let mut entries: Vec<_> = canonical.chain(aliases).collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Tools (and aliases) that are currently enabled, for `tools/list`,
+    /// optionally only those in `category`.
+    fn list(&self, category: Option<ToolCategory>) -> Vec<Value> {
+        let session = session::current();
+        let wanted = |t: &&Arc<dyn Tool>| {
+            tool_state::enabled(t.name())
+                && category.is_none_or(|c| t.category() == c)
+                && auth::allows(session.as_deref(), t.name(), t.category())
+        };
+        let tools = self.tools.values().filter(wanted).map(|t| {
+            json!({
+                "name": t.name(),
+                "description": t.description(),
+                "inputSchema": t.input_schema(),
+                "annotations": t.annotations(),
+                "_meta": { "category": t.category(), "version": t.version() },
+            })
+        });
+        let aliases = self.aliases.iter().filter_map(|(alias, a)| {
+            let t = self.tools.get(&a.target).filter(wanted)?;
+            let description = if a.deprecated {
+                format!("Deprecated: use {} instead. {}", a.target, t.description())
+            } else {
+                t.description().to_string()
+            };
+            Some(json!({
+                "name": alias,
+                "description": description,
+                "inputSchema": t.input_schema(),
+                "annotations": t.annotations(),
+                "_meta": {
+                    "category": t.category(),
+                    "version": t.version(),
+                    "aliasOf": a.target,
+                    "deprecated": a.deprecated,
+                },
+            }))
+        });
+        tools.chain(aliases).collect()
     }
 
-    async fn call(&self, name: &str, input: Value) -> Result<Value> {
+    /// The named tool's input schema for `field`, if it declares one.
+    fn field_schema(&self, name: &str, field: &str) -> Option<Value> {
+        self.get(name)?
+            .input_schema()
+            .pointer(&format!("/properties/{field}"))
+            .cloned()
+    }
+
+    /// Add `middleware` inside those added before it.
+    pub fn add_middleware<M: middleware::ToolMiddleware + 'static>(&mut self, middleware: M) {
+        self.middleware.push(Arc::new(middleware));
+    }
+
+    /// Effective timeout for the named tool: the `--tool-timeout` override,
+    /// else the tool's own default.
+    fn timeout(&self, name: &str) -> Option<Duration> {
+        let tool = self.get(name)?;
+        config::current()
+            .tool_timeouts
+            .get(tool.name())
+            .copied()
+            .or_else(|| tool.default_timeout())
+    }
+
+    /// Check `input` against the named tool's input schema, so malformed
+    /// calls fail with field-level errors before anything runs. Unknown
+    /// tools pass here and are reported by `call`.
+    fn validate(&self, name: &str, input: &Value) -> Result<(), Vec<validation::FieldError>> {
+        let Some(validator) = self.get(name).and_then(|t| self.validators.get(t.name())) else {
+            return Ok(());
+        };
+        // Clients may omit `input` entirely for tools without required fields.
+        if input.is_null() {
+            return validation::check(validator, &json!({}));
+        }
+        validation::check(validator, input)
+    }
+
+    async fn call(&self, name: &str, input: Value, dry_run: bool) -> Result<Value> {
         let tool = self
-            .tools
             .get(name)
             .ok_or_else(|| anyhow::anyhow!(format!("Unknown tool: {name}")))?;
-        tool.execute(input).await
+        let session = session::current();
+        if !auth::allows(session.as_deref(), tool.name(), tool.category()) {
+            let message = match session.and_then(|s| s.principal()) {
+                Some(principal) => format!("Not authorized: role `{}` may not call {}", principal.role, tool.name()),
+                None => "Not authorized: send a valid _meta.authToken in initialize".to_string(),
+            };
+            return Err(middleware::Rejection { code: -32003, message, data: None }.into());
+        }
+        // Middleware and config see the canonical name, whatever alias was used.
+        let call = middleware::ToolCall {
+            name: tool.name().to_string(),
+            version: tool.version(),
+            annotations: tool.annotations(),
+            target_field: tool.target_field(),
+            timeout: self.timeout(name),
+            cache_ttl: config::current()
+                .cache_ttls
+                .get(tool.name())
+                .copied()
+                .or_else(|| tool.cache_ttl())
+                .filter(|ttl| !ttl.is_zero()),
+            max_concurrency: config::current()
+                .max_concurrency
+                .get(tool.name())
+                .copied()
+                .or_else(|| tool.max_concurrency())
+                .filter(|limit| *limit > 0),
+            retry: {
+                let config = config::current();
+                let default = tool.retry_policy();
+                middleware::RetryPolicy {
+                    max_attempts: config.retries.get(tool.name()).copied().unwrap_or(default.max_attempts).max(1),
+                    backoff: config.retry_backoffs.get(tool.name()).copied().unwrap_or(default.backoff),
+                }
+            },
+            max_output_bytes: {
+                let config = config::current();
+                Some(config.max_outputs.get(tool.name()).copied().unwrap_or(config.max_output_bytes))
+                    .filter(|limit| *limit > 0)
+            },
+            dry_run,
+        };
+        let next = middleware::Next {
+            tool: tool.as_ref(),
+            chain: &self.middleware,
+        };
+        next.run(&call, input).await
     }
 }
 
+/// Parameters for tools/list.
+#[derive(Debug, Default, Deserialize)]
+struct ToolListParams {
+    /// Only list tools in this category.
+    #[serde(default)]
+    category: Option<ToolCategory>,
+}
+
 /// Parameters for tools.call.
 #[derive(Debug, Deserialize)]
 struct ToolCallParams {
     name: String,
     #[serde(default)]
     input: Value,
+    /// Return the backend request the tool would send instead of sending
+    /// it; same as `dry_run: true` in `input`.
+    #[serde(default)]
+    dry_run: bool,
+    /// `progressToken` asks for `notifications/progress` while the tool runs.
+    #[serde(default, rename = "_meta")]
+    meta: Value,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let config = config::AppConfig::from_env_and_args()?;
+    config::install(config.clone());
+    tagging::install(config.tag_rules.as_deref())?;
+    command_tools::install(config.command_tools.as_deref())?;
+    api::gvm_backends::install(config.gvm_backends.as_deref())?;
+    if config.offline && config.monitor_webhook.is_some() {
+        eprintln!("warning: --monitor-webhook is ignored in offline mode; change notifications will be dropped");
+    }
+    if let Some(path) = config.audit_log.as_deref() {
+        audit::install(path)?;
+    }
+    if let Some(path) = config.scan_history.as_deref() {
+        scan_history::install(path)?;
+    }
+
+    if let Some(minutes) = config.idle_timeout_minutes.filter(|m| *m > 0) {
+        idle::spawn_monitor(std::time::Duration::from_secs(minutes * 60));
+    }
+
     // 1. Build the tool registry.
     let mut reg = ToolRegistry::new();
     tools::register_all_tools(&mut reg);
+    reg.add_middleware(middleware::DisabledTools);
+    reg.add_middleware(middleware::EngagementScope);
+    reg.add_middleware(middleware::TargetRateLimit::default());
+    reg.add_middleware(middleware::ScopeAttestation);
+    reg.add_middleware(middleware::JobTracking);
+    reg.add_middleware(middleware::Approval);
+    reg.add_middleware(middleware::ConcurrencyLimit);
+    reg.add_middleware(middleware::Metrics);
+    reg.add_middleware(middleware::OutputLimit);
+    reg.add_middleware(middleware::ScanObservers);
+    reg.add_middleware(middleware::ResultCache::default());
+    reg.add_middleware(middleware::Retry);
+    tool_state::install(reg.catalog());
+    auth::install(config.auth_config.as_deref())?;
+    config_file::install(config.config_file.as_deref(), config.clone())?;
     let registry = Arc::new(reg);
 
-    // 2. Set up stdin/stdout JSON loop.
-    let stdin = io::stdin();
-    let stdout = io::stdout();
-    let mut reader = BufReader::new(stdin).lines();
-    let mut writer = io::BufWriter::new(stdout);
-
-    while let Some(line) = reader.next_line().await? {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        // Try to parse a request.
-        let req: RpcRequest = match serde_json::from_str(line) {
-            Ok(r) => r,
-            Err(_err) => {
-                // If we can't parse the incoming JSON at all, just ignore it.
-                // MCP clients can send various notifications; we don't want to
-                // emit malformed error responses that confuse the client.
-                continue;
-            }
-        };
+    if config.schema_export {
+        let bundle = serde_json::to_string_pretty(&schema_export::openapi(&registry))?;
+        // Write rather than println! so a closed pipe (`| head`) is an error, not a panic.
+        std::io::Write::write_all(&mut std::io::stdout(), format!("{bundle}\n").as_bytes())?;
+        return Ok(());
+    }
+    monitor::spawn_scheduler(registry.clone());
+    api::health::spawn_probe(config.backend_health_interval);
+    watchdog::spawn(config.task_watchdog_interval);
 
-        // Notifications in MCP/JSON-RPC do not include an `id` and must not
-        // receive a response. Only handle messages with an ID as requests.
-        let Some(id) = req.id.clone() else {
-            continue;
-        };
+    // 2. Serve JSON-RPC over the selected transport.
+    if let Some(addr) = config.listen.as_deref() {
+        return transport::tcp::serve(addr, config.allow_remote, registry).await;
+    }
+    if let Some(path) = config.unix_socket.as_deref() {
+        #[cfg(unix)]
+        return transport::unix::serve(path, registry).await;
+        #[cfg(not(unix))]
+        anyhow::bail!("--unix-socket {path} is only supported on unix platforms");
+    }
+    transport::stdio::serve(registry, config.framing).await
+}
+
+/// Entry point for every client request: records activity (see `idle`) and
+/// handles the request with `handle_scheduled_request`.
+async fn handle_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest) -> RpcResponse {
+    let _busy = idle::begin();
+    handle_scheduled_request(registry, id, req).await
+}
+
+/// Handle a request without counting it as client activity, for calls the
+/// server schedules itself (monitors), which must not keep it from idling.
+/// Enforces the configured request timeout so a hung backend surfaces as a
+/// -32001 error instead of never answering. Calls to tools with a timeout of
+/// their own are limited by the registry instead (see `ToolRegistry::timeout`).
+async fn handle_scheduled_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest) -> RpcResponse {
+    // Every request gets a trace id; it is sent to the Go backend with each
+    // call and returned to the client so multi-hop failures can be correlated.
+    let trace_id = trace::new_id();
+    let method = req.method.clone();
+    let mut resp = trace::scope(trace_id.clone(), run_with_timeout(registry, id, req)).await;
 
-        // Handle the request and send a response.
-        let resp = handle_request(registry.clone(), id, req).await;
-        let text = serde_json::to_string(&resp)?;
-        writer.write_all(text.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
-        writer.flush().await?;
+    if let Some(result) = resp.result.as_mut().and_then(Value::as_object_mut) {
+        result.insert("_meta".into(), json!({ "traceId": trace_id }));
     }
+    if let Some(error) = resp.error.as_mut() {
+        eprintln!("[trace {trace_id}] {method} failed ({}): {}", error.code, error.message);
+        if let Some(session) = session::current() {
+            session.log(
+                "error",
+                json!({ "method": method, "code": error.code, "message": error.message, "traceId": trace_id }),
+            );
+        }
+        match error.data.as_mut().and_then(Value::as_object_mut) {
+            Some(data) => {
+                data.insert("traceId".into(), json!(trace_id));
+            }
+            None => error.data = Some(json!({ "traceId": trace_id })),
+        }
+    }
+    resp
+}
+
+async fn run_with_timeout(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest) -> RpcResponse {
+    let tool_timeout = (req.method == "tools/call")
+        .then(|| req.params.get("name").and_then(|v| v.as_str()))
+        .flatten()
+        .and_then(|name| registry.timeout(name));
+    let request_timeout = config::current().request_timeout;
+    let Some(limit) = request_timeout.filter(|_| tool_timeout.is_none()) else {
+        return dispatch(registry, id, req).await;
+    };
 
-    Ok(())
+    match tokio::time::timeout(limit, dispatch(registry, id.clone(), req)).await {
+        Ok(resp) => resp,
+        Err(_) => err_resp(
+            id,
+            -32001,
+            format!("Request timed out after {}s", limit.as_secs()),
+        ),
+    }
 }
 
 /// Dispatches methods like `tools/list` and `tools/call`.
-async fn handle_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest) -> RpcResponse {
+async fn dispatch(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest) -> RpcResponse {
+    // Disabled capability groups behave as if the methods did not exist.
+    if let Some(capability) = capabilities::Capability::of_method(&req.method)
+        && !capabilities::enabled(capability)
+    {
+        return err_resp(id, -32601, format!("Method not found: {}", req.method));
+    }
+
     match req.method.as_str() {
         // MCP / JSON-RPC 2.0 initialization handshake.
         // Cursor (and other MCP clients) will generally send an `initialize`
@@ -169,18 +659,21 @@ async fn handle_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest)
                 .and_then(|v| v.as_str())
                 .unwrap_or("2024-11-05");
 
+            let token = req.params.pointer("/_meta/authToken").and_then(|v| v.as_str());
+            let principal = match auth::authenticate(token) {
+                Ok(principal) => principal,
+                Err(err) => return err_resp(id, -32003, format!("Not authorized: {err}")),
+            };
+            if let Some(session) = session::current() {
+                session.set_client_info(&req.params);
+                session.set_principal(principal);
+            }
+
             ok(
                 id,
                 json!({
                     "protocolVersion": protocol_version,
-                    "capabilities": {
-                        "tools": {
-                            "listChanged": true
-                        },
-                        "prompts": {
-                            "listChanged": true
-                        }
-                    },
+                    "capabilities": capabilities::server_capabilities(),
                     "serverInfo": {
                         "name": "hacker_agent",
                         "version": "0.1.0"
@@ -189,26 +682,118 @@ async fn handle_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest)
             )
         }
         "tools/list" => {
-            let tools = registry.list();
+            let params: ToolListParams = if req.params.is_null() {
+                Default::default()
+            } else {
+                match serde_json::from_value(req.params) {
+                    Ok(p) => p,
+                    Err(err) => return err_resp(id, -32602, format!("Invalid params: {err}")),
+                }
+            };
+            let tools = registry.list(params.category);
             ok(id, json!({ "tools": tools }))
         }
+        "tools/schemaExport" => ok(id, schema_export::openapi(&registry)),
         "tools/call" => {
             let parsed: Result<ToolCallParams, _> = serde_json::from_value(req.params);
             let params = match parsed {
                 Ok(p) => p,
-                Err(err) => {
-                    return err_resp(id, -32602, format!("Invalid params: {err}"));
+                Err(err) => return tool_error_resp(id, ToolError::invalid_input(err.to_string())),
+            };
+
+            // Rewrite requests that exceed policy into the nearest compliant
+            // configuration and tell the client what was changed.
+            let mut input = params.input;
+            let mut substitutions = Vec::new();
+            if let Err(err) = targets::normalize_input(&mut input, &mut substitutions) {
+                return tool_error_resp(id, ToolError::invalid_input(err.to_string()));
+            }
+            let (mut input, policy_substitutions) = policy::enforce(input);
+            substitutions.extend(policy_substitutions);
+            // Slow down against hosts that misbehaved on earlier scans.
+            let timing_schema = registry.field_schema(&params.name, "timing");
+            throttle::adjust(&mut input, timing_schema.as_ref(), &mut substitutions);
+
+            if let Err(errors) = registry.validate(&params.name, &input) {
+                let message = validation::summarize(&errors);
+                return tool_error_resp(id, ToolError::InvalidInput { message, errors });
+            }
+
+            // A dry run is requested on the call or in the tool's input; the
+            // field is not passed on, so the tool sees the same input either way.
+            let dry_run = params.dry_run
+                || input
+                    .as_object_mut()
+                    .and_then(|fields| fields.remove("dry_run"))
+                    .is_some_and(|v| v == true);
+            if dry_run && !registry.get(&params.name).is_some_and(|t| t.supports_dry_run()) {
+                return tool_error_resp(
+                    id,
+                    ToolError::invalid_input(format!("{} does not support dry runs", params.name)),
+                );
+            }
+
+            // OpenVAS tools go to the GVM backend the call names, or the
+            // workspace's; like `dry_run`, the field is not passed on.
+            let gvm_backend = if registry.get(&params.name).is_some_and(|t| t.selects_gvm_backend()) {
+                let requested = input.as_object_mut().and_then(|fields| fields.remove("backend"));
+                match api::gvm_backends::resolve(requested.as_ref().and_then(Value::as_str)) {
+                    Ok(backend) => backend,
+                    Err(err) => return tool_error_resp(id, ToolError::invalid_input(err.to_string())),
                 }
+            } else {
+                None
             };
 
-            match registry.call(&params.name, params.input).await {
-                Ok(value) => ok(id, json!({ "output": value })),
-                Err(err) => err_resp(id, -32000, format!("Tool error: {err}")),
+            // Scope checks, approval, job tracking and result observers run
+            // as middleware around the tool (see `middleware`).
+            let result = api::gvm_backends::scope(gvm_backend, async {
+                if dry_run {
+                    match dry_run::scope(registry.call(&params.name, input, true)).await {
+                        (_, Some(planned)) => Ok(json!({ "dryRun": planned })),
+                        (result, None) => result,
+                    }
+                } else {
+                    let token = params.meta.get("progressToken").cloned();
+                    progress::scope(token, registry.call(&params.name, input, false)).await
+                }
+            })
+            .await;
+
+            match result {
+                Ok(value) => {
+                    let mut result = json!({ "output": value });
+                    if let Some(tool) = registry.get(&params.name) {
+                        result["tool"] = json!({ "name": tool.name(), "version": tool.version() });
+                    }
+                    if !substitutions.is_empty() {
+                        result["policy"] = json!({ "substitutions": substitutions });
+                    }
+                    if let Some(replacement) = registry.replacement_for(&params.name) {
+                        result["deprecation"] = json!({
+                            "message": format!("{} is deprecated; use {replacement} instead", params.name),
+                            "replacedBy": replacement,
+                        });
+                    }
+                    ok(id, result)
+                }
+                Err(err) => tool_error_resp(id, ToolError::classify(err)),
             }
         }
         "prompts/list" => {
-            let prompts = prompts::list_prompts();
-            ok(id, json!({ "prompts": prompts }))
+            let params: prompts::PromptListParams = if req.params.is_null() {
+                Default::default()
+            } else {
+                match serde_json::from_value(req.params) {
+                    Ok(p) => p,
+                    Err(err) => return err_resp(id, -32602, format!("Invalid params: {err}")),
+                }
+            };
+
+            match prompts::list_prompts(params.cursor.as_deref()) {
+                Ok(page) => ok(id, json!(page)),
+                Err(err) => err_resp(id, -32602, format!("Invalid params: {err}")),
+            }
         }
         "prompts/get" => {
             let parsed: Result<prompts::PromptGetParams, _> = serde_json::from_value(req.params);
@@ -224,6 +809,38 @@ async fn handle_request(registry: Arc<ToolRegistry>, id: Value, req: RpcRequest)
                 Err(err) => err_resp(id, -32601, format!("Prompt not found: {err}")),
             }
         }
+        "resources/list" => {
+            let resources = resources::list_resources();
+            ok(id, json!({ "resources": resources }))
+        }
+        "resources/templates/list" => {
+            let templates = resources::list_templates();
+            ok(id, json!({ "resourceTemplates": templates }))
+        }
+        "resources/read" => {
+            let parsed: Result<resources::ResourceReadParams, _> = serde_json::from_value(req.params);
+            let params = match parsed {
+                Ok(p) => p,
+                Err(err) => {
+                    return err_resp(id, -32602, format!("Invalid params: {err}"));
+                }
+            };
+
+            match resources::read(&params.uri).await {
+                Ok(result) => ok(id, result),
+                Err(err) => err_resp(id, -32002, format!("Resource not found: {err}")),
+            }
+        }
+        "logging/setLevel" => {
+            let level = req.params.get("level").and_then(|v| v.as_str()).unwrap_or_default();
+            let Some(session) = session::current() else {
+                return err_resp(id, -32603, "No session for logging/setLevel".to_string());
+            };
+            match session.set_log_level(level) {
+                Ok(()) => ok(id, json!({})),
+                Err(err) => err_resp(id, -32602, format!("Invalid params: {err}")),
+            }
+        }
         _ => err_resp(
             id,
             -32601,
@@ -241,11 +858,25 @@ fn ok(id: Value, result: Value) -> RpcResponse {
     }
 }
 
+/// Answer a failed `tools/call` with the error's own code and `data`.
+fn tool_error_resp(id: Value, err: ToolError) -> RpcResponse {
+    RpcResponse {
+        jsonrpc: "2.0",
+        id,
+        result: None,
+        error: Some(RpcError {
+            code: err.code(),
+            message: err.to_string(),
+            data: Some(err.data()),
+        }),
+    }
+}
+
 fn err_resp(id: Value, code: i32, message: String) -> RpcResponse {
     RpcResponse {
         jsonrpc: "2.0",
         id,
         result: None,
-        error: Some(RpcError { code, message }),
+        error: Some(RpcError { code, message, data: None }),
     }
 }
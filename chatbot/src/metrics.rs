@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Upper bounds, in seconds, of the latency histogram buckets: from quick
+/// API lookups up to hour-long full scans. Calls slower than the last land
+/// only in the implicit `+Inf` bucket (the call count).
+const BUCKETS_SECS: [f64; 9] = [0.1, 0.5, 1.0, 5.0, 15.0, 60.0, 300.0, 900.0, 3600.0];
+
+/// Counters for one tool since the server started.
+#[derive(Debug, Default, Clone)]
+struct Counters {
+    calls: u64,
+    errors: u64,
+    total: Duration,
+    max: Duration,
+    /// Calls per bucket of `BUCKETS_SECS`, not cumulative.
+    buckets: [u64; BUCKETS_SECS.len()],
+}
+
+impl Counters {
+    fn add(&mut self, elapsed: Duration, ok: bool) {
+        self.calls += 1;
+        self.errors += u64::from(!ok);
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        if let Some(bucket) = BUCKETS_SECS.iter().position(|le| elapsed.as_secs_f64() <= *le) {
+            self.buckets[bucket] += 1;
+        }
+    }
+}
+
+static METRICS: Mutex<Option<HashMap<String, Counters>>> = Mutex::new(None);
+
+/// Record one finished call of `tool`.
+pub fn record(tool: &str, elapsed: Duration, ok: bool) {
+    let mut metrics = METRICS.lock().unwrap();
+    metrics
+        .get_or_insert_with(HashMap::new)
+        .entry(tool.to_string())
+        .or_default()
+        .add(elapsed, ok);
+}
+
+/// One histogram bucket: calls that took at most `le_secs`.
+#[derive(Debug, Serialize)]
+pub struct Bucket {
+    pub le_secs: f64,
+    pub count: u64,
+}
+
+/// A tool's metrics as reported by `get_agent_metrics`.
+#[derive(Debug, Serialize)]
+pub struct ToolMetrics {
+    pub tool: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    pub total_ms: u128,
+    pub mean_ms: u128,
+    pub max_ms: u128,
+    /// Cumulative, as in Prometheus histograms.
+    pub latency_histogram: Vec<Bucket>,
+}
+
+impl ToolMetrics {
+    fn new(tool: &str, counters: &Counters) -> Self {
+        let mut seen = 0;
+        let latency_histogram = BUCKETS_SECS
+            .iter()
+            .zip(counters.buckets)
+            .map(|(le, count)| {
+                seen += count;
+                Bucket { le_secs: *le, count: seen }
+            })
+            .collect();
+        Self {
+            tool: tool.to_string(),
+            calls: counters.calls,
+            errors: counters.errors,
+            error_rate: counters.errors as f64 / counters.calls.max(1) as f64,
+            total_ms: counters.total.as_millis(),
+            mean_ms: counters.total.as_millis() / u128::from(counters.calls.max(1)),
+            max_ms: counters.max.as_millis(),
+            latency_histogram,
+        }
+    }
+}
+
+/// Metrics of every tool called so far, the ones that took the most time
+/// in total first.
+pub fn snapshot() -> Vec<ToolMetrics> {
+    let metrics = METRICS.lock().unwrap();
+    let mut tools: Vec<ToolMetrics> = metrics
+        .iter()
+        .flatten()
+        .map(|(tool, counters)| ToolMetrics::new(tool, counters))
+        .collect();
+    tools.sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then_with(|| a.tool.cmp(&b.tool)));
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_is_cumulative_and_rates_are_per_tool() {
+        let mut counters = Counters::default();
+        for (ms, ok) in [(40, true), (700, true), (2_000, false), (7_200_000, true)] {
+            counters.add(Duration::from_millis(ms), ok);
+        }
+        let metrics = ToolMetrics::new("quick_scan", &counters);
+        assert_eq!(metrics.error_rate, 0.25);
+        assert_eq!(metrics.max_ms, 7_200_000);
+        let counts: Vec<u64> = metrics.latency_histogram.iter().map(|b| b.count).collect();
+        // The two-hour call is only in `+Inf`, i.e. `calls`.
+        assert_eq!(counts, [1, 1, 2, 3, 3, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn snapshot_puts_the_most_expensive_tool_first() {
+        record("metrics_test_cheap", Duration::from_millis(5), true);
+        record("metrics_test_cheap", Duration::from_millis(5), false);
+        record("metrics_test_slow", Duration::from_secs(90), true);
+        let tools: Vec<String> = snapshot()
+            .into_iter()
+            .filter(|m| m.tool.starts_with("metrics_test_"))
+            .map(|m| m.tool)
+            .collect();
+        assert_eq!(tools, ["metrics_test_slow", "metrics_test_cheap"]);
+    }
+}
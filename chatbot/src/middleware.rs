@@ -0,0 +1,1111 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::Semaphore;
+
+use crate::api::{backend, gvm_backends};
+use crate::api::errors::{BackendError, BackendErrorKind};
+use crate::tool_error::ToolError;
+use crate::{
+    approval, audit, config, dry_run, jobs, metrics, partial, resources, scan_history, scan_jobs,
+    tagging, targets, throttle, tool_state,
+    Tool, ToolAnnotations,
+};
+
+/// The call being made, as seen by middleware.
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub name: String,
+    /// `Tool::version` of the tool being called.
+    pub version: &'static str,
+    pub annotations: ToolAnnotations,
+    /// `Tool::target_field`.
+    pub target_field: Option<&'static str>,
+    /// Limit on the tool's own execution (config override or the tool's
+    /// default); time spent in middleware, e.g. waiting for approval, is not
+    /// counted.
+    pub timeout: Option<Duration>,
+    /// How long a successful result may be reused; `None` for tools that
+    /// did not opt in to caching.
+    pub cache_ttl: Option<Duration>,
+    /// How many calls of this tool may execute at once; `None` is unlimited.
+    pub max_concurrency: Option<usize>,
+    /// How often, and how patiently, transient backend failures are retried.
+    pub retry: RetryPolicy,
+    /// Largest output, in bytes of JSON, returned inline; `None` is
+    /// unlimited. Larger outputs are spilled to a resource (see
+    /// `OutputLimit`).
+    pub max_output_bytes: Option<usize>,
+    /// The call only plans backend requests (see `dry_run`); middleware
+    /// that do not opt in with `runs_on_dry_runs` are skipped.
+    pub dry_run: bool,
+}
+
+/// Wraps every `ToolRegistry::call`. Middleware run outermost-first in the
+/// order they were added; each decides whether (and with what input) to
+/// continue down the chain via `next`, and sees the result on the way back
+/// out. Cross-cutting concerns live here instead of in each tool.
+#[async_trait]
+pub trait ToolMiddleware: Send + Sync {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value>;
+
+    /// Whether to run for dry runs too. Most middleware guard or observe
+    /// traffic a dry run never sends, so they stay out of the way.
+    fn runs_on_dry_runs(&self) -> bool {
+        false
+    }
+}
+
+/// The rest of the chain, ending in the tool itself.
+pub struct Next<'a> {
+    pub(crate) tool: &'a dyn Tool,
+    pub(crate) chain: &'a [Arc<dyn ToolMiddleware>],
+}
+
+impl Next<'_> {
+    pub async fn run(self, call: &ToolCall, input: Value) -> Result<Value> {
+        let skipped = self
+            .chain
+            .iter()
+            .take_while(|m| call.dry_run && !m.runs_on_dry_runs())
+            .count();
+        match self.chain[skipped..].split_first() {
+            Some((middleware, rest)) => {
+                middleware
+                    .handle(call, input, Next { tool: self.tool, chain: rest })
+                    .await
+            }
+            None => {
+                jobs::mark_running();
+                let Some(limit) = call.timeout else {
+                    return self.tool.execute(input).await;
+                };
+                let slot = partial::Slot::default();
+                let run = partial::scope(slot.clone(), self.tool.execute(input));
+                match tokio::time::timeout(limit, run).await {
+                    Ok(result) => result,
+                    Err(_) => Err(ToolTimeout {
+                        tool: call.name.clone(),
+                        limit,
+                        partial_output: slot.lock().unwrap().take(),
+                    }
+                    .into()),
+                }
+            }
+        }
+    }
+}
+
+/// A tool ran past its timeout. Carries whatever the tool had reported
+/// through `partial::report` by then.
+#[derive(Debug)]
+pub struct ToolTimeout {
+    pub tool: String,
+    pub limit: Duration,
+    pub partial_output: Option<Value>,
+}
+
+impl std::fmt::Display for ToolTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} timed out after {}s", self.tool, self.limit.as_secs())
+    }
+}
+
+impl std::error::Error for ToolTimeout {}
+
+/// A call stopped by middleware before reaching the tool. Carries the
+/// JSON-RPC error code to answer with instead of the generic tool error.
+#[derive(Debug)]
+pub struct Rejection {
+    pub code: i32,
+    pub message: String,
+    /// Machine-readable details for JSON-RPC `error.data`.
+    pub data: Option<Value>,
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+fn reject(code: i32, message: String) -> anyhow::Error {
+    Rejection { code, message, data: None }.into()
+}
+
+/// Refuses calls to tools an administrator switched off at runtime (see
+/// `admin_set_tool_state`), even from clients with a stale tool list.
+pub struct DisabledTools;
+
+#[async_trait]
+impl ToolMiddleware for DisabledTools {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        if !tool_state::enabled(&call.name) {
+            return Err(reject(
+                -32003,
+                format!("{} has been disabled by an administrator", call.name),
+            ));
+        }
+        next.run(call, input).await
+    }
+
+    fn runs_on_dry_runs(&self) -> bool {
+        true
+    }
+}
+
+/// Refuses calls naming any target on the denylist (`--deny-targets`) or
+/// outside the engagement scope (`--scope`), whatever the tool: scans, but
+/// also calls that set up later scans, such as GVM targets and monitors.
+/// Every target field (and the tool's `target_field`) and every entry of a
+/// target list is checked, and the
+/// denylist wins over the scope. Calls that reach hosts through GVM tasks,
+/// targets or asset tags instead (see `referenced_hosts`) have those looked
+/// up and checked the same way. Refusals answer `-32006` and are logged to
+/// stderr and the audit log. Runs on dry runs too, so a plan is never shown
+/// for a call that would be refused.
+pub struct EngagementScope;
+
+#[async_trait]
+impl ToolMiddleware for EngagementScope {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let Some((allowed, denied)) = scope_rules() else {
+            return next.run(call, input).await;
+        };
+        let workspace = config::current().workspace.clone();
+        if let Some((target, reason)) = refused_target(&input, call.target_field, &allowed, &denied) {
+            return Err(scope_violation(call, &target, reason, &workspace));
+        }
+        let referenced = dry_run::sent(referenced_hosts(&call.name, &input)).await?;
+        if let Some((target, reason)) = refused_reference(&referenced, &allowed, &denied) {
+            return Err(scope_violation(call, &target, reason, &workspace));
+        }
+        next.run(call, input).await
+    }
+
+    fn runs_on_dry_runs(&self) -> bool {
+        true
+    }
+}
+
+/// Tools that start or resume an existing task's scan, or change the task
+/// (e.g. give it a schedule): the hosts of the task's target are checked.
+const TASK_SCAN_TOOLS: [&str; 3] = ["openvas_start_task", "openvas_resume_task", "openvas_modify_task"];
+
+/// Tools that point tasks at existing GVM targets by `target_id` or
+/// `target_ids`.
+const TARGET_ID_TOOLS: [&str; 3] = ["openvas_create_task", "openvas_modify_task", "openvas_fan_out"];
+
+/// Tools that scan, or create targets for, every asset carrying `tag`.
+const TAG_TOOLS: [&str; 2] = ["openvas_bulk_create_targets", "openvas_fan_out"];
+
+/// The configured scope and denylist; `None` when neither is set.
+fn scope_rules() -> Option<(Vec<targets::Scope>, Vec<targets::Scope>)> {
+    let cfg = config::current();
+    if cfg.scope.is_empty() && cfg.deny_targets.is_empty() {
+        return None;
+    }
+    let allowed = cfg.scope.iter().map(|s| targets::Scope::of(s)).collect();
+    let denied = cfg.deny_targets.iter().map(|s| targets::Scope::of(s)).collect();
+    Some((allowed, denied))
+}
+
+/// The hosts a call to `tool` reaches through GVM objects or asset tags
+/// rather than target fields, each with what it was reached through (e.g.
+/// `task 5b2c...`). IDs gvmd does not know are left out: the call fails
+/// there without scanning anything.
+async fn referenced_hosts(tool: &str, input: &Value) -> Result<Vec<(String, String)>> {
+    let mut target_ids = Vec::new();
+    if TASK_SCAN_TOOLS.contains(&tool)
+        && let Some(task_id) = input.get("task_id").and_then(Value::as_str)
+    {
+        let tasks = backend::current().list_tasks().await?.tasks;
+        if let Some(target_id) = tasks.into_iter().find(|t| t.id == task_id).and_then(|t| t.target_id) {
+            target_ids.push((format!("task {task_id}"), target_id));
+        }
+    }
+    if TARGET_ID_TOOLS.contains(&tool) {
+        let listed = input.get("target_ids").and_then(Value::as_array).into_iter().flatten();
+        for id in input.get("target_id").into_iter().chain(listed).filter_map(Value::as_str) {
+            target_ids.push((format!("target {id}"), id.to_string()));
+        }
+    }
+
+    let mut referenced = Vec::new();
+    if !target_ids.is_empty() {
+        let targets = backend::current().list_targets().await?.targets;
+        for (via, id) in target_ids {
+            if let Some(target) = targets.iter().find(|t| t.id == id) {
+                referenced.push((via, target.hosts.clone()));
+            }
+        }
+    }
+    if TAG_TOOLS.contains(&tool)
+        && let Some(tag) = input.get("tag").and_then(Value::as_str)
+    {
+        let hosts: Vec<String> = tagging::assets(Some(tag)).into_iter().map(|a| a.target).collect();
+        referenced.push((format!("tag {tag}"), hosts.join(",")));
+    }
+    Ok(referenced)
+}
+
+/// Why the scope or denylist refuses resuming task `task_id`, if it does;
+/// for resumes outside a tool call (see `watchdog`).
+pub async fn refused_task(task_id: &str) -> Result<Option<(String, &'static str)>> {
+    let Some((allowed, denied)) = scope_rules() else {
+        return Ok(None);
+    };
+    let referenced = referenced_hosts("openvas_resume_task", &json!({ "task_id": task_id })).await?;
+    Ok(refused_reference(&referenced, &allowed, &denied))
+}
+
+/// The first target in `input`, or in its `target_field`, that is denied
+/// or, with a non-empty `allowed`, out of scope, and why.
+fn refused_target(
+    input: &Value,
+    target_field: Option<&str>,
+    allowed: &[targets::Scope],
+    denied: &[targets::Scope],
+) -> Option<(String, &'static str)> {
+    let mut entries = targets::entries_of(input);
+    if let Some(field) = target_field.filter(|f| !targets::TARGET_FIELDS.contains(f))
+        && let Some(target) = input.get(field).and_then(Value::as_str)
+    {
+        entries.extend(targets::entries(target));
+    }
+    refused_entry(entries, allowed, denied)
+}
+
+/// The target a call names: its tool's `target_field`, else the first
+/// target-like field.
+fn target_of<'a>(call: &ToolCall, input: &'a Value) -> Option<&'a str> {
+    call.target_field
+        .and_then(|field| input.get(field).and_then(Value::as_str))
+        .or_else(|| targets::target_of(input))
+}
+
+/// Like `refused_target`, for hosts from `referenced_hosts`; the refused
+/// entry names what it was reached through.
+fn refused_reference(
+    referenced: &[(String, String)],
+    allowed: &[targets::Scope],
+    denied: &[targets::Scope],
+) -> Option<(String, &'static str)> {
+    referenced.iter().find_map(|(via, hosts)| {
+        refused_entry(targets::entries(hosts), allowed, denied).map(|(entry, reason)| (format!("{entry} ({via})"), reason))
+    })
+}
+
+fn refused_entry(
+    entries: Vec<(String, targets::Scope)>,
+    allowed: &[targets::Scope],
+    denied: &[targets::Scope],
+) -> Option<(String, &'static str)> {
+    entries.into_iter().find_map(|(entry, scope)| {
+        // Any overlap is denied: a /16 that includes a denied host would
+        // scan it too.
+        if denied.iter().any(|d| d.overlaps(&scope) || d.contains(&scope)) {
+            return Some((entry, "denied"));
+        }
+        // `example.com/28` scans whatever the name resolves next to.
+        let named_block = matches!(scope, targets::Scope::Name(_)) && entry.contains('/');
+        let in_scope = allowed.is_empty() || (!named_block && allowed.iter().any(|a| a.contains(&scope)));
+        (!in_scope).then_some((entry, "out_of_scope"))
+    })
+}
+
+/// Log and build the refusal of `call` for `target`; `reason` is
+/// `out_of_scope` or `denied`.
+fn scope_violation(call: &ToolCall, target: &str, reason: &'static str, workspace: &str) -> anyhow::Error {
+    let refusal = ToolError::TargetOutOfScope {
+        target: target.to_string(),
+        reason,
+    };
+    eprintln!("refused {}: {refusal}", call.name);
+    if audit::enabled()
+        && let Err(err) = audit::record(
+            "scope_violation",
+            workspace,
+            json!({ "tool": call.name, "target": target, "reason": reason }),
+        )
+    {
+        eprintln!("failed to audit scope violation: {err}");
+    }
+    refusal.into()
+}
+
+/// Caps how often calls that send traffic to targets may hit the same host
+/// or CIDR (`--target-rate-limit` per minute), protecting both the target
+/// and the scanner's reputation from agent loops that rescan one host over
+/// and over. Entries are compared by the addresses they cover, so
+/// `10.0.0.5` counts against `10.0.0.0/24` and vice versa; a call over the
+/// limit for any entry of its target list is refused with `-32004` and the
+/// seconds until a slot frees up. Calls that later middleware reject (no
+/// attestation, approval declined) do not use up the budget.
+#[derive(Default)]
+pub struct TargetRateLimit {
+    book: Mutex<RateBook>,
+}
+
+/// Window `--target-rate-limit` counts calls in.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[async_trait]
+impl ToolMiddleware for TargetRateLimit {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let limit = config::current().target_rate_limit as usize;
+        let target = target_of(call, &input).filter(|_| call.annotations.open_world_hint);
+        let Some(target) = target.filter(|_| limit > 0) else {
+            return next.run(call, input).await;
+        };
+
+        let entries = targets::entries(target);
+        let ticket = self
+            .book
+            .lock()
+            .unwrap()
+            .admit(&entries, limit, Instant::now())
+            .map_err(RateLimited::into_rejection)?;
+
+        let result = next.run(call, input).await;
+        if result.as_ref().is_err_and(|err| err.is::<Rejection>()) {
+            self.book.lock().unwrap().release(ticket);
+        }
+        result
+    }
+}
+
+/// Calls admitted by `TargetRateLimit` within the window.
+#[derive(Default)]
+struct RateBook {
+    next_ticket: u64,
+    hits: Vec<RateHit>,
+}
+
+struct RateHit {
+    ticket: u64,
+    at: Instant,
+    scope: targets::Scope,
+}
+
+/// A call refused because `target` already had `count` calls in the window.
+#[derive(Debug)]
+struct RateLimited {
+    target: String,
+    count: usize,
+    limit: usize,
+    retry_after: Duration,
+}
+
+impl RateBook {
+    /// Admit a call touching `entries` at `now`, or say which entry is over
+    /// `limit` and how long until the oldest call counting against it ages
+    /// out. Admitted calls are recorded under the returned ticket.
+    fn admit(
+        &mut self,
+        entries: &[(String, targets::Scope)],
+        limit: usize,
+        now: Instant,
+    ) -> Result<u64, RateLimited> {
+        self.hits.retain(|h| now.duration_since(h.at) < RATE_WINDOW);
+
+        let mut refusal: Option<RateLimited> = None;
+        for (target, scope) in entries {
+            // One call covering several overlapping entries counts once.
+            let mut calls: Vec<(u64, Instant)> = self
+                .hits
+                .iter()
+                .filter(|h| h.scope.overlaps(scope))
+                .map(|h| (h.ticket, h.at))
+                .collect();
+            calls.sort();
+            calls.dedup_by_key(|(ticket, _)| *ticket);
+            if calls.len() < limit {
+                continue;
+            }
+            calls.sort_by_key(|(_, at)| *at);
+            let blocking = calls[calls.len() - limit].1;
+            let retry_after = RATE_WINDOW.saturating_sub(now.duration_since(blocking));
+            if refusal.as_ref().is_none_or(|r| retry_after > r.retry_after) {
+                refusal = Some(RateLimited {
+                    target: target.clone(),
+                    count: calls.len(),
+                    limit,
+                    retry_after,
+                });
+            }
+        }
+        if let Some(refusal) = refusal {
+            return Err(refusal);
+        }
+
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.hits.extend(entries.iter().map(|(_, scope)| RateHit {
+            ticket,
+            at: now,
+            scope: scope.clone(),
+        }));
+        Ok(ticket)
+    }
+
+    /// Forget an admitted call, e.g. one a later gate refused.
+    fn release(&mut self, ticket: u64) {
+        self.hits.retain(|h| h.ticket != ticket);
+    }
+}
+
+impl RateLimited {
+    fn into_rejection(self) -> anyhow::Error {
+        // Round up so retrying after the hint always succeeds.
+        let retry_after_secs = self.retry_after.as_secs() + u64::from(self.retry_after.subsec_nanos() > 0);
+        Rejection {
+            code: -32004,
+            message: format!(
+                "Rate limited: {} was targeted {} times in the last {}s (limit {}); retry in {retry_after_secs}s",
+                self.target,
+                self.count,
+                RATE_WINDOW.as_secs(),
+                self.limit,
+            ),
+            data: Some(json!({ "target": self.target, "retryAfterSecs": retry_after_secs })),
+        }
+        .into()
+    }
+}
+
+/// Active scans need a recorded scope attestation for the workspace (with
+/// `--require-attestation`), and each one is written to the audit log with
+/// the attestation that covers it.
+pub struct ScopeAttestation;
+
+#[async_trait]
+impl ToolMiddleware for ScopeAttestation {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        if !call.annotations.open_world_hint {
+            return next.run(call, input).await;
+        }
+
+        let workspace = config::current().workspace.clone();
+        let attestation = audit::attestation(&workspace);
+        if attestation.is_none() && config::current().require_attestation {
+            return Err(reject(
+                -32003,
+                format!("Not authorized: no scope attestation for workspace `{workspace}`; call attest_scope first"),
+            ));
+        }
+
+        if audit::enabled() {
+            let detail = json!({
+                "tool": call.name,
+                "toolVersion": call.version,
+                "target": target_of(call, &input),
+                "attestation": attestation.map(|a| a.record_hash),
+            });
+            audit::record("scan", &workspace, detail)
+                .map_err(|err| reject(-32603, format!("Cannot write audit record: {err}")))?;
+        }
+        next.run(call, input).await
+    }
+}
+
+/// Intrusive and bulk calls need explicit user approval (MCP elicitation).
+pub struct Approval;
+
+#[async_trait]
+impl ToolMiddleware for Approval {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let mut reasons = approval::intrusive_reasons(&call.name, call.annotations, &input);
+        if let Some(impact) = approval::bulk_impact(&call.name, call.annotations, &input) {
+            reasons.push(impact.describe());
+        }
+        approval::confirm(&call.name, &input, &reasons)
+            .await
+            .map_err(|err| reject(-32003, format!("Not approved: {err}")))?;
+        next.run(call, input).await
+    }
+}
+
+/// Caps how many calls of a tool execute at once (`Tool::max_concurrency`),
+/// so an agent loop cannot launch dozens of simultaneous scans. Calls over
+/// the limit wait for a running one to finish; the wait does not count
+/// towards the tool's timeout.
+pub struct ConcurrencyLimit;
+
+/// Per tool: the limit its semaphore was built for, and the semaphore.
+static SLOTS: Mutex<BTreeMap<String, (usize, Arc<Semaphore>)>> = Mutex::new(BTreeMap::new());
+
+/// The tool's semaphore, rebuilt when its limit changed (e.g. on config
+/// reload). Calls holding permits of the old one finish undisturbed.
+/// Background scans (see `scan_jobs`) take their permits here too, so they
+/// count against the same limit as foreground calls.
+pub fn slots_for(tool: &str, limit: usize) -> Arc<Semaphore> {
+    let mut slots = SLOTS.lock().unwrap();
+    match slots.get(tool) {
+        Some((built_for, semaphore)) if *built_for == limit => semaphore.clone(),
+        _ => {
+            let semaphore = Arc::new(Semaphore::new(limit));
+            slots.insert(tool.to_string(), (limit, semaphore.clone()));
+            semaphore
+        }
+    }
+}
+
+#[async_trait]
+impl ToolMiddleware for ConcurrencyLimit {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let Some(limit) = call.max_concurrency else {
+            return next.run(call, input).await;
+        };
+        let _permit = slots_for(&call.name, limit).acquire_owned().await?;
+        next.run(call, input).await
+    }
+}
+
+/// Records every call in the job table (see `jobs_export`). Added before
+/// `Approval` and `ConcurrencyLimit`, so calls waiting on either show up as
+/// queued; the job turns running when the call reaches the tool.
+pub struct JobTracking;
+
+#[async_trait]
+impl ToolMiddleware for JobTracking {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let job = jobs::start(&call.name, call.version, &input);
+        let result = job.scope(next.run(call, input)).await;
+        match &result {
+            Ok(_) => job.succeed(),
+            Err(err) => job.fail(&err.to_string()),
+        }
+        result
+    }
+}
+
+/// Counts calls, failures and time spent per tool (see `get_agent_metrics`).
+/// Added after `Approval` and `ConcurrencyLimit`, so the latency is the
+/// tool's own, retries included, not time spent waiting for the user or a
+/// slot; calls refused before then are not counted.
+pub struct Metrics;
+
+#[async_trait]
+impl ToolMiddleware for Metrics {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let started = Instant::now();
+        let result = next.run(call, input).await;
+        metrics::record(&call.name, started.elapsed(), result.is_ok());
+        result
+    }
+}
+
+/// Keeps outputs over the tool's size limit (`--max-output-bytes`,
+/// `--max-output`) out of the model's context: the full output is stored as
+/// an `output://` resource and the call returns a truncated preview with
+/// its URI. Added before `ScanObservers`, which still see the full output.
+pub struct OutputLimit;
+
+#[async_trait]
+impl ToolMiddleware for OutputLimit {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let output = next.run(call, input).await?;
+        let Some(limit) = call.max_output_bytes else {
+            return Ok(output);
+        };
+        let serialized = output.to_string();
+        if serialized.len() <= limit {
+            return Ok(output);
+        }
+        let uri = resources::spill_output(&call.name, output);
+        Ok(json!({
+            "truncated": true,
+            "resourceUri": uri,
+            "totalBytes": serialized.len(),
+            "preview": preview(&serialized, limit),
+            "note": format!("Output exceeded {limit} bytes; read {uri} with resources/read for the full result."),
+        }))
+    }
+}
+
+/// The first `limit` bytes of `text`, cut at a character boundary.
+fn preview(text: &str, limit: usize) -> String {
+    let mut end = limit.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+/// Feeds scan results to the modules that learn from them: throttling,
+/// `nmap://` resources, the scan history and host tagging.
+pub struct ScanObservers;
+
+#[async_trait]
+impl ToolMiddleware for ScanObservers {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let result = next.run(call, input.clone()).await;
+        // Scans started in the background are observed when they finish.
+        if result.as_ref().is_ok_and(scan_jobs::is_handle) {
+            return result;
+        }
+        if call.annotations.open_world_hint {
+            throttle::observe(&input, &result);
+        }
+        if let (Ok(output), Some(target)) = (&result, targets::target_of(&input)) {
+            resources::remember_scan(&call.name, target, output);
+            scan_history::record(&call.name, target, output);
+            if call.annotations.open_world_hint {
+                tagging::apply(target, output);
+            }
+        }
+        result
+    }
+}
+
+/// Reuses results of tools that opted in to caching (`Tool::cache_ttl`) for
+/// identical input, so agents repeating e.g. `openvas_get_version` do not
+/// hit gvmd every time. Keyed by tool name and a hash of the input and the
+/// GVM backend the call was routed to.
+#[derive(Default)]
+pub struct ResultCache {
+    entries: Mutex<HashMap<(String, u64), (Instant, Value)>>,
+}
+
+#[async_trait]
+impl ToolMiddleware for ResultCache {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let Some(ttl) = call.cache_ttl else {
+            return next.run(call, input).await;
+        };
+
+        // serde_json objects are sorted, so equal inputs serialize equally;
+        // an omitted input is the same call as an empty one.
+        let mut hasher = DefaultHasher::new();
+        match &input {
+            Value::Null => "{}".hash(&mut hasher),
+            other => other.to_string().hash(&mut hasher),
+        }
+        gvm_backends::selected().map(|b| b.name.clone()).hash(&mut hasher);
+        let key = (call.name.clone(), hasher.finish());
+
+        if let Some((stored, value)) = self.entries.lock().unwrap().get(&key)
+            && stored.elapsed() < ttl
+        {
+            return Ok(value.clone());
+        }
+
+        let result = next.run(call, input).await;
+        if let Ok(value) = &result {
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|(tool, _), (stored, _)| tool != &call.name || stored.elapsed() < ttl);
+            entries.insert(key, (Instant::now(), value.clone()));
+        }
+        result
+    }
+}
+
+/// How a tool's calls are retried after transient backend failures (see
+/// `Retry`). One attempt means no retries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    /// Wait before the first retry; doubles for each further one, up to
+    /// `MAX_RETRY_BACKOFF`.
+    pub backoff: Duration,
+}
+
+/// Longest wait between two attempts, however many there are.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+impl RetryPolicy {
+    pub const NONE: Self = Self {
+        max_attempts: 1,
+        backoff: Duration::ZERO,
+    };
+
+    /// For read-only calls, which are safe to repeat: a 502 or a gvmd
+    /// restart usually clears within a few seconds.
+    pub const READ_ONLY: Self = Self {
+        max_attempts: 3,
+        backoff: Duration::from_millis(500),
+    };
+
+    /// Wait before attempt `attempt + 1`, after `attempt` failed ones.
+    fn delay(&self, attempt: usize) -> Duration {
+        let factor = 1u32 << (attempt.saturating_sub(1)).min(16);
+        self.backoff.saturating_mul(factor).min(MAX_RETRY_BACKOFF)
+    }
+}
+
+/// Whether `err` is worth another attempt: the backend, docker or gvmd was
+/// briefly unreachable or slow (502/503/504, refused connections). Refusals,
+/// bad input, missing objects and the tool's own timeout are not.
+pub fn is_retryable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<BackendError>()
+        .is_some_and(|err| matches!(err.kind, BackendErrorKind::Unavailable | BackendErrorKind::Timeout))
+}
+
+/// Retries calls that failed transiently, per the tool's `RetryPolicy`
+/// (`Tool::retry_policy`, `--retries`, `--retry-backoff`). Innermost, so
+/// each attempt gets the tool's full timeout and the job, approval and
+/// rate limit count the call once.
+pub struct Retry;
+
+#[async_trait]
+impl ToolMiddleware for Retry {
+    async fn handle(&self, call: &ToolCall, input: Value, next: Next<'_>) -> Result<Value> {
+        let mut attempt = 1;
+        loop {
+            let next = Next { tool: next.tool, chain: next.chain };
+            match next.run(call, input.clone()).await {
+                Err(err) if attempt < call.retry.max_attempts && is_retryable(&err) => {
+                    let delay = call.retry.delay(attempt);
+                    eprintln!(
+                        "{} failed (attempt {attempt} of {}), retrying in {}ms: {err}",
+                        call.name,
+                        call.retry.max_attempts,
+                        delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::targets::Scope;
+
+    fn entries(raw: &str) -> Vec<(String, Scope)> {
+        raw.split(',').map(|t| (t.to_string(), Scope::of(t))).collect()
+    }
+
+    #[test]
+    fn rate_book_refuses_over_limit_within_window() {
+        let mut book = RateBook::default();
+        let start = Instant::now();
+        assert!(book.admit(&entries("10.0.0.5"), 2, start).is_ok());
+        assert!(book.admit(&entries("10.0.0.5"), 2, start + Duration::from_secs(10)).is_ok());
+
+        let refused = book
+            .admit(&entries("10.0.0.5"), 2, start + Duration::from_secs(20))
+            .unwrap_err();
+        assert_eq!(refused.count, 2);
+        // The first call ages out 60s after it was made.
+        assert_eq!(refused.retry_after, Duration::from_secs(40));
+
+        assert!(book.admit(&entries("10.0.0.5"), 2, start + RATE_WINDOW).is_ok());
+    }
+
+    #[test]
+    fn rate_book_matches_cidrs_and_hosts_inside_them() {
+        let mut book = RateBook::default();
+        let now = Instant::now();
+        book.admit(&entries("10.0.0.0/24"), 1, now).unwrap();
+        assert_eq!(book.admit(&entries("10.0.0.5"), 1, now).unwrap_err().target, "10.0.0.5");
+        assert!(book.admit(&entries("10.0.1.5"), 1, now).is_ok());
+        assert!(book.admit(&entries("10.0.1.0/16"), 1, now).is_err());
+        assert!(book.admit(&entries("Scanme.Example"), 1, now).is_ok());
+        assert!(book.admit(&entries("scanme.example"), 1, now).is_err());
+    }
+
+    #[test]
+    fn rate_book_counts_a_call_once_and_releases_it() {
+        let mut book = RateBook::default();
+        let now = Instant::now();
+        let ticket = book.admit(&entries("10.0.0.0/24,10.0.0.5"), 2, now).unwrap();
+        assert!(book.admit(&entries("10.0.0.5"), 2, now).is_ok());
+        assert!(book.admit(&entries("10.0.0.5"), 2, now).is_err());
+
+        book.release(ticket);
+        assert!(book.admit(&entries("10.0.0.5"), 2, now).is_ok());
+    }
+
+    #[test]
+    fn rate_limited_rejection_carries_retry_after() {
+        let err = RateLimited {
+            target: "10.0.0.5".into(),
+            count: 3,
+            limit: 3,
+            retry_after: Duration::from_millis(12_300),
+        }
+        .into_rejection();
+        let rejection = err.downcast_ref::<Rejection>().unwrap();
+        assert_eq!(rejection.code, -32004);
+        assert_eq!(
+            rejection.data,
+            Some(json!({ "target": "10.0.0.5", "retryAfterSecs": 13 }))
+        );
+        assert!(rejection.message.contains("retry in 13s"));
+    }
+
+    #[test]
+    fn concurrency_limit_follows_limit_changes() {
+        let first = slots_for("quick_scan", 2);
+        let held = first.clone().try_acquire_owned().unwrap();
+        assert!(Arc::ptr_eq(&first, &slots_for("quick_scan", 2)));
+        assert_eq!(slots_for("quick_scan", 2).available_permits(), 1);
+
+        let raised = slots_for("quick_scan", 4);
+        assert!(!Arc::ptr_eq(&first, &raised));
+        assert_eq!(raised.available_permits(), 4);
+        assert_eq!(slots_for("quick_scan", 1).available_permits(), 1);
+        drop(held);
+        assert_eq!(first.available_permits(), 2);
+    }
+
+    #[test]
+    fn denylist_wins_over_scope() {
+        let scopes = |raw: &[&str]| raw.iter().map(|s| Scope::of(s)).collect::<Vec<_>>();
+        let allowed = scopes(&["10.0.0.0/16", "example.com"]);
+        let denied = scopes(&["10.0.5.1", "db.example.com"]);
+        let refused = |target: &str| {
+            refused_target(&json!({ "target": target }), None, &allowed, &denied)
+                .map(|(t, reason)| (t, reason.to_string()))
+        };
+
+        assert_eq!(refused("10.0.1.0/24, www.example.com"), None);
+        assert_eq!(refused("10.0.1.1,10.0.5.0/24"), Some(("10.0.5.0/24".into(), "denied".into())));
+        assert_eq!(refused("db.example.com"), Some(("db.example.com".into(), "denied".into())));
+        assert_eq!(refused("replica.db.example.com").unwrap().1, "denied");
+        assert_eq!(refused("10.1.0.1").unwrap().1, "out_of_scope");
+        assert_eq!(refused("example.com/28").unwrap().1, "out_of_scope");
+
+        // Without a scope only the denylist applies.
+        assert!(refused_target(&json!({ "hosts": "192.0.2.1" }), None, &[], &denied).is_none());
+        assert!(refused_target(&json!({ "hosts": "10.0.0.0/8" }), None, &[], &denied).is_some());
+    }
+
+    #[tokio::test]
+    async fn scope_covers_hosts_reached_through_task_and_target_ids() {
+        use crate::api::backend::{with_backend, OpenvasBackend};
+        use crate::api::mock::MockBackend;
+
+        let mock = Arc::new(MockBackend::default());
+        let inside = mock.create_target("inside", "10.0.0.5", None, None).await.unwrap();
+        let outside = mock.create_target("outside", "10.0.0.5, 10.1.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan", "config", &outside.id, None, None).await.unwrap();
+        async fn refused(tool: &str, input: Value) -> Option<String> {
+            let referenced = referenced_hosts(tool, &input).await.unwrap();
+            refused_reference(&referenced, &[Scope::of("10.0.0.0/16")], &[]).map(|(t, _)| t)
+        }
+
+        let (start, fan_out, create, unknown) = with_backend(mock.clone(), async {
+            (
+                refused("openvas_start_task", json!({ "task_id": task.id })).await,
+                refused("openvas_fan_out", json!({ "target_ids": [inside.id, outside.id] })).await,
+                refused("openvas_create_task", json!({ "target_id": inside.id })).await,
+                refused("openvas_resume_task", json!({ "task_id": "no-such-task" })).await,
+            )
+        })
+        .await;
+
+        assert_eq!(start, Some(format!("10.1.0.5 (task {})", task.id)));
+        assert_eq!(fan_out, Some(format!("10.1.0.5 (target {})", outside.id)));
+        assert_eq!(create, None);
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn scope_covers_the_target_field_of_command_tools() {
+        let tool = crate::command_tools::parse_tools(
+            r#"
+tools:
+  - name: http_probe
+    description: Fetches a URL's headers.
+    category: recon
+    input_schema:
+      type: object
+      properties:
+        host: { type: string }
+      required: [host]
+    command: [curl, -I, "{host}"]
+    target_field: host
+"#,
+        )
+        .unwrap()
+        .remove(0);
+        let call = ToolCall {
+            name: tool.name().to_string(),
+            version: tool.version(),
+            annotations: tool.annotations(),
+            target_field: tool.target_field(),
+            timeout: None,
+            cache_ttl: None,
+            max_concurrency: None,
+            retry: RetryPolicy::NONE,
+            max_output_bytes: None,
+            dry_run: false,
+        };
+        let allowed = [Scope::of("10.0.0.0/16")];
+        let denied = [Scope::of("10.0.5.1")];
+        let refused = |host: &str| refused_target(&json!({ "host": host }), call.target_field, &allowed, &denied);
+
+        assert_eq!(refused("10.0.1.1"), None);
+        assert_eq!(refused("10.1.0.5"), Some(("10.1.0.5".into(), "out_of_scope")));
+        assert_eq!(refused("10.0.5.1"), Some(("10.0.5.1".into(), "denied")));
+        // The rate limit and the audit log count the call against the host.
+        assert_eq!(target_of(&call, &json!({ "host": "10.1.0.5" })), Some("10.1.0.5"));
+    }
+
+    struct Reached;
+
+    #[async_trait]
+    impl Tool for Reached {
+        fn name(&self) -> &'static str {
+            "reached"
+        }
+
+        fn description(&self) -> &'static str {
+            "test tool"
+        }
+
+        fn category(&self) -> crate::ToolCategory {
+            crate::ToolCategory::Recon
+        }
+
+        async fn execute(&self, _input: Value) -> Result<Value> {
+            Ok(json!("reached"))
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_runs_skip_approval() {
+        let chain: Vec<Arc<dyn ToolMiddleware>> = vec![Arc::new(Approval)];
+        let mut call = ToolCall {
+            name: "reached".to_string(),
+            version: crate::DEFAULT_TOOL_VERSION,
+            annotations: ToolAnnotations::INTRUSIVE_SCAN,
+            target_field: None,
+            timeout: None,
+            cache_ttl: None,
+            max_concurrency: None,
+            retry: RetryPolicy::NONE,
+            max_output_bytes: None,
+            dry_run: true,
+        };
+        let next = || Next { tool: &Reached, chain: &chain };
+        let input = json!({ "target": "dry-run.example", "timing": "T5" });
+        assert_eq!(next().run(&call, input.clone()).await.unwrap(), json!("reached"));
+
+        // Without a client to ask, a real intrusive call is refused.
+        call.dry_run = false;
+        let err = next().run(&call, input).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<Rejection>().unwrap().code, -32003);
+    }
+
+    /// Fails with the given backend errors, in order, then succeeds.
+    struct Flaky(Mutex<Vec<BackendError>>);
+
+    #[async_trait]
+    impl Tool for Flaky {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn description(&self) -> &'static str {
+            "test tool"
+        }
+
+        fn category(&self) -> crate::ToolCategory {
+            crate::ToolCategory::VulnScan
+        }
+
+        async fn execute(&self, _input: Value) -> Result<Value> {
+            match self.0.lock().unwrap().pop() {
+                Some(err) => Err(err.into()),
+                None => Ok(json!("done")),
+            }
+        }
+    }
+
+    fn flaky(statuses: &[(u16, &str)]) -> Flaky {
+        let errors = statuses
+            .iter()
+            .rev()
+            .map(|(status, body)| BackendError::from_response(*status, body))
+            .collect();
+        Flaky(Mutex::new(errors))
+    }
+
+    fn retried_call(max_attempts: usize) -> ToolCall {
+        ToolCall {
+            name: "flaky".to_string(),
+            version: crate::DEFAULT_TOOL_VERSION,
+            annotations: ToolAnnotations::READ_ONLY,
+            target_field: None,
+            timeout: None,
+            cache_ttl: None,
+            max_concurrency: None,
+            retry: RetryPolicy {
+                max_attempts,
+                backoff: Duration::from_millis(1),
+            },
+            max_output_bytes: None,
+            dry_run: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_recovers_from_transient_failures_only() {
+        let chain: Vec<Arc<dyn ToolMiddleware>> = vec![Arc::new(Retry)];
+        let tool = flaky(&[(502, "bad gateway"), (500, "Failed to connect: connection refused")]);
+        let next = Next { tool: &tool, chain: &chain };
+        assert_eq!(next.run(&retried_call(3), Value::Null).await.unwrap(), json!("done"));
+
+        let tool = flaky(&[(503, ""), (503, ""), (503, "")]);
+        let next = Next { tool: &tool, chain: &chain };
+        let err = next.run(&retried_call(2), Value::Null).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<BackendError>().unwrap().status, Some(503));
+        assert_eq!(tool.0.lock().unwrap().len(), 1);
+
+        // Bad requests and missing objects fail the same way every time.
+        let tool = flaky(&[(404, "Failed to find task"), (400, "")]);
+        let next = Next { tool: &tool, chain: &chain };
+        assert!(next.run(&retried_call(3), Value::Null).await.is_err());
+        assert_eq!(tool.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(500));
+        assert_eq!(policy.delay(3), Duration::from_secs(2));
+        assert_eq!(policy.delay(9), MAX_RETRY_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn large_outputs_spill_to_a_resource() {
+        let chain: Vec<Arc<dyn ToolMiddleware>> = vec![Arc::new(OutputLimit)];
+        let mut call = retried_call(1);
+        call.max_output_bytes = Some(4);
+        let next = || Next { tool: &Reached, chain: &chain };
+
+        let output = next().run(&call, Value::Null).await.unwrap();
+        assert_eq!(output["truncated"], true);
+        assert_eq!(output["totalBytes"], 9);
+        assert_eq!(output["preview"], "\"rea…");
+        let uri = output["resourceUri"].as_str().unwrap();
+        let read = resources::read(uri).await.unwrap();
+        assert_eq!(read["contents"][0]["text"], "\"reached\"");
+
+        call.max_output_bytes = Some(9);
+        assert_eq!(next().run(&call, Value::Null).await.unwrap(), json!("reached"));
+    }
+
+    #[test]
+    fn preview_cuts_at_character_boundaries() {
+        assert_eq!(preview("añb", 2), "a…");
+    }
+}
@@ -0,0 +1,10 @@
+//! Typed inputs and outputs of the scan tools and the backend calls behind
+//! them. Tool inputs derive `JsonSchema`, so each tool's MCP `inputSchema`
+//! is generated from the same struct its arguments are deserialized into;
+//! backend responses are decoded into these types at the API layer, so a
+//! change in the Go backend's JSON shape fails loudly instead of producing
+//! silently missing fields.
+
+pub mod native;
+pub mod nmap_scan;
+pub mod openvas;
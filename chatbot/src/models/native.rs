@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Input of `native_syn_scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NativeSynScanArgs {
+    /// Target hostname or IPv4 address to scan.
+    pub target: String,
+    /// Port specification, e.g. '22,80,443' or '1-1024'. Default: top 100 TCP ports.
+    pub ports: Option<String>,
+    /// How long to wait for replies after each probe round, in milliseconds. Default: 1500
+    #[serde(default = "default_syn_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Most SYN probes sent per second. Default: 300
+    #[serde(default = "default_syn_max_rate")]
+    pub max_rate: u32,
+    /// Grab banners from open ports and identify services/versions with the built-in signature database. Default: false
+    #[serde(default)]
+    pub service_detection: bool,
+}
+
+fn default_syn_timeout_ms() -> u64 {
+    1500
+}
+
+fn default_syn_max_rate() -> u32 {
+    300
+}
+
+/// Input of `native_banner_grab`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NativeBannerGrabArgs {
+    /// Target hostname or IP address.
+    pub target: String,
+    /// Ports to probe, e.g. '22,80,443' or '8000-8010'.
+    pub ports: String,
+    /// Connect and read timeout per probe, in milliseconds. Default: 3000
+    #[serde(default = "default_banner_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_banner_timeout_ms() -> u64 {
+    3000
+}
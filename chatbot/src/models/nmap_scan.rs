@@ -0,0 +1,478 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Nmap timing template (`-T0` .. `-T5`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum Timing {
+    T0,
+    T1,
+    T2,
+    T3,
+    T4,
+    T5,
+}
+
+/// The faster timing templates, offered where slow scans make no sense.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+pub enum FastTiming {
+    T3,
+    #[default]
+    T4,
+}
+
+impl From<FastTiming> for Timing {
+    fn from(timing: FastTiming) -> Self {
+        match timing {
+            FastTiming::T3 => Timing::T3,
+            FastTiming::T4 => Timing::T4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanType {
+    Ping,
+    TcpSyn,
+    TcpConnect,
+    Udp,
+    TcpAck,
+    TcpFin,
+    TcpNull,
+    TcpXmas,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    Normal,
+    Xml,
+    Json,
+    Greppable,
+    All,
+}
+
+/// Stealth and evasion options. The backend passes them through as given.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StealthOptions {
+    /// Decoy IPs (-D RND:10,ME,8.8.8.8)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decoys: Vec<String>,
+    /// Source port for packets (--source-port 53)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_port: Option<u16>,
+    /// Network interface to use (-e eth0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    /// Time to live for packets (--ttl 64)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+    /// Randomize target host order (--randomize-hosts)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub randomize_hosts: bool,
+    /// Spoof source IP address (-S 192.168.1.1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spoof_ip: Option<String>,
+    /// Spoof MAC address (--spoof-mac 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spoof_mac: Option<String>,
+    /// Fragment packets to evade IDS (-f)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fragment_packets: bool,
+}
+
+/// Body of the backend's `POST /scan-open-ports`, which is also the input
+/// of `advanced_nmap_scan`: every option that tool exposes maps one to one
+/// onto the backend request, except `background`, which stays here. Unset
+/// options are left out of the request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScanRequest {
+    /// Target hostname or IP address to scan.
+    pub target: String,
+    /// Nmap timing template: T0 (Paranoid), T1 (Sneaky), T2 (Polite), T3 (Normal), T4 (Aggressive), T5 (Insane). Default: T2
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    /// Type of scan to perform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_type: Option<ScanType>,
+    /// Port specification: '80,443', '1-1000', 'U:53,T:80-443', or 'all' for all ports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<String>,
+    /// Enable service/version detection (-sV)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub service_detection: bool,
+    /// Enable OS detection (-O)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub os_detection: bool,
+    /// Script names or categories: 'vuln', 'default', 'auth,discovery', or specific script names
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scripts: Option<String>,
+    /// Output format for results. xml also returns the scan parsed into hosts, ports, services, scripts and OS matches (`parsed`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+    /// Enable aggressive scan options (-A): service detection, OS detection, scripts, and traceroute
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub aggressive: bool,
+    /// Enable traceroute (--traceroute)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub traceroute: bool,
+    /// Enable OS detection (-O)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag_o: bool,
+    /// Enable default scripts (-sC)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag_sc: bool,
+    /// Enable service detection (-sV)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag_sv: bool,
+    /// Enable traceroute (--traceroute)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag_traceroute: bool,
+    /// Enable aggressive scan (-A)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag_a: bool,
+    /// Stealth and evasion options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stealth_options: Option<StealthOptions>,
+    /// Run the scan in the background: return a scan_id at once, then poll nmap_scan_status and fetch the output with nmap_scan_result. For scans that take long, e.g. all ports. Default: false
+    #[serde(default, skip_serializing)]
+    pub background: bool,
+}
+
+impl ScanRequest {
+    /// The nmap command line the backend runs for this request, built the
+    /// same way `scanOpenPortsHandler` does (for dry runs). The backend does
+    /// not pass `stealth_options` on to nmap, so they do not appear here.
+    pub fn nmap_command(&self) -> Vec<String> {
+        let mut argv = vec!["nmap".to_string()];
+        let mut flag = |f: &str| argv.push(f.to_string());
+        flag(&format!("-{:?}", self.timing.unwrap_or(Timing::T2)));
+        if let Some(scan_type) = self.scan_type {
+            flag(match scan_type {
+                ScanType::Ping => "-sn",
+                ScanType::TcpSyn => "-sS",
+                ScanType::TcpConnect => "-sT",
+                ScanType::Udp => "-sU",
+                ScanType::TcpAck => "-sA",
+                ScanType::TcpFin => "-sF",
+                ScanType::TcpNull => "-sN",
+                ScanType::TcpXmas => "-sX",
+            });
+        }
+        if let Some(ports) = self.ports.as_deref().filter(|p| !p.is_empty()) {
+            flag("-p");
+            flag(ports);
+        }
+        if self.service_detection {
+            flag("-sV");
+        }
+        if self.os_detection {
+            flag("-O");
+        }
+        if let Some(scripts) = self.scripts.as_deref().filter(|s| !s.is_empty()) {
+            flag("--script");
+            flag(scripts);
+        }
+        match self.output_format {
+            Some(OutputFormat::Xml) => {
+                flag("-oX");
+                flag("-");
+            }
+            Some(OutputFormat::Json) => flag("-oJ"),
+            Some(OutputFormat::Greppable) => {
+                flag("-oG");
+                flag("-");
+            }
+            Some(OutputFormat::All) => flag("-oA"),
+            Some(OutputFormat::Normal) | None => {}
+        }
+        if self.flag_o {
+            flag("-O");
+        }
+        if self.flag_sc {
+            flag("-sC");
+        }
+        if self.flag_sv {
+            flag("-sV");
+        }
+        if self.flag_traceroute {
+            flag("--traceroute");
+        }
+        if self.flag_a {
+            flag("-A");
+        }
+        if self.aggressive && !self.flag_a {
+            flag("-A");
+        }
+        if self.traceroute && !self.flag_traceroute && !self.aggressive && !self.flag_a {
+            flag("--traceroute");
+        }
+        flag(self.target.trim());
+        argv
+    }
+}
+
+/// What the backend returns for every nmap scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub target: String,
+    /// nmap's output in the requested format.
+    pub raw_output: String,
+    /// `raw_output` parsed, for scans with XML output (see `api::nmap_xml`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<NmapScanResult>,
+}
+
+/// nmap's XML output (`-oX`) as structured data: per host, its addresses,
+/// names, ports with the services and script output on them, and OS guesses.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapScanResult {
+    /// The nmap command line, from `<nmaprun args>`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub args: String,
+    #[serde(default)]
+    pub hosts: Vec<NmapHost>,
+    /// nmap's closing summary, e.g. "Nmap done at ...; 1 IP address (1 host up) scanned in 0.05 seconds".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapHost {
+    /// `up` or `down`.
+    pub status: String,
+    #[serde(default)]
+    pub addresses: Vec<NmapAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<NmapHostname>,
+    /// The ports nmap listed; those it folded into "Not shown" are left out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<NmapPort>,
+    /// OS detection guesses, best first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub os_matches: Vec<NmapOsMatch>,
+    /// Host script output (`<hostscript>`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<NmapScript>,
+}
+
+impl NmapHost {
+    /// The host's IP address (else its first address of any kind).
+    pub fn address(&self) -> Option<&str> {
+        self.addresses
+            .iter()
+            .find(|a| a.addr_type.starts_with("ipv"))
+            .or(self.addresses.first())
+            .map(|a| a.addr.as_str())
+    }
+
+    /// The name nmap's normal output reports the host under: its first
+    /// hostname, else its address.
+    pub fn name(&self) -> Option<&str> {
+        self.hostnames.first().map(|h| h.name.as_str()).or_else(|| self.address())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapAddress {
+    pub addr: String,
+    /// `ipv4`, `ipv6` or `mac`.
+    pub addr_type: String,
+    /// NIC vendor, for MAC addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapHostname {
+    pub name: String,
+    /// `user` (given as the target) or `PTR` (reverse DNS).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapPort {
+    /// `tcp`, `udp` or `sctp`.
+    pub protocol: String,
+    pub port: u16,
+    /// `open`, `closed`, `filtered`, `open|filtered`, ...
+    pub state: String,
+    /// Why nmap decided the state, e.g. `syn-ack`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<NmapService>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<NmapScript>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapService {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_info: Option<String>,
+    /// `ssl` when the service was found inside TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tunnel: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpes: Vec<String>,
+}
+
+impl NmapService {
+    /// The service as nmap's normal output names it, e.g. `ssl/http`.
+    pub fn full_name(&self) -> String {
+        match &self.tunnel {
+            Some(tunnel) => format!("{tunnel}/{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapScript {
+    pub id: String,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NmapOsMatch {
+    pub name: String,
+    /// nmap's confidence, 0-100.
+    pub accuracy: u8,
+}
+
+/// Input of `nmap.scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NmapScanArgs {
+    /// Target hostname or IP address to scan.
+    pub target: String,
+    /// Nmap timing template: T0 (Paranoid), T1 (Sneaky), T2 (Polite), T3 (Normal), T4 (Aggressive), T5 (Insane). Default: T2
+    pub timing: Option<Timing>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickScanType {
+    PingSweep,
+    #[default]
+    CommonPorts,
+    ServiceDetection,
+    VulnScan,
+}
+
+/// Input of `quick_scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct QuickScanArgs {
+    /// Target hostname, IP, or CIDR range.
+    pub target: String,
+    /// Quick scan type. Default: common_ports
+    #[serde(default)]
+    pub scan_type: QuickScanType,
+    /// Speed: T3 (Normal) or T4 (Aggressive). Default: T4
+    #[serde(default)]
+    pub timing: FastTiming,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StealthLevel {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Maximum,
+}
+
+/// The scan types that evade simple filters; all need raw sockets.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+pub enum StealthScanType {
+    #[serde(rename = "tcp_fin")]
+    Fin,
+    #[serde(rename = "tcp_null")]
+    Null,
+    #[serde(rename = "tcp_xmas")]
+    Xmas,
+    #[serde(rename = "tcp_ack")]
+    Ack,
+    #[default]
+    #[serde(rename = "tcp_syn")]
+    Syn,
+}
+
+impl From<StealthScanType> for ScanType {
+    fn from(scan_type: StealthScanType) -> Self {
+        match scan_type {
+            StealthScanType::Fin => ScanType::TcpFin,
+            StealthScanType::Null => ScanType::TcpNull,
+            StealthScanType::Xmas => ScanType::TcpXmas,
+            StealthScanType::Ack => ScanType::TcpAck,
+            StealthScanType::Syn => ScanType::TcpSyn,
+        }
+    }
+}
+
+/// Input of `stealth_scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StealthScanArgs {
+    /// Target hostname or IP address.
+    pub target: String,
+    /// Stealth level. Default: medium
+    #[serde(default)]
+    pub stealth_level: StealthLevel,
+    /// Stealth scan type. Default: tcp_syn
+    #[serde(default)]
+    pub scan_type: StealthScanType,
+    /// Use decoy hosts. Default: true
+    #[serde(default = "default_true")]
+    pub use_decoys: bool,
+    /// Fragment packets to evade IDS. Default: false
+    #[serde(default)]
+    pub fragment_packets: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Input of `comprehensive_scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ComprehensiveScanArgs {
+    /// Target hostname or IP address to scan.
+    pub target: String,
+    /// Include vulnerability scripts (vuln category). Default: false
+    #[serde(default)]
+    pub include_vuln: bool,
+    /// Run the scan in the background: return a scan_id at once, then poll nmap_scan_status and fetch the output with nmap_scan_result. Default: false
+    #[serde(default)]
+    pub background: bool,
+}
+
+/// Input of `nmap_scan_status` and `nmap_scan_result`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScanJobArgs {
+    /// scan_id returned by a scan started with background: true.
+    pub scan_id: String,
+}
+
+/// Input of `network_discovery`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkDiscoveryArgs {
+    /// Target subnet in CIDR notation (e.g., '192.168.1.0/24') or IP range.
+    pub subnet: String,
+    /// Timing template: T3 (Normal) or T4 (Aggressive). Default: T4
+    #[serde(default)]
+    pub timing: FastTiming,
+}
@@ -0,0 +1,2023 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::nmap_scan::FastTiming;
+
+/// gvmd's default `max_hosts` setting: larger targets are rejected.
+pub const GVM_MAX_HOSTS: u64 = 4095;
+
+/// gvmd task statuses of tasks that are running or about to; deleting or
+/// modifying such a task pulls it from under the scanner.
+pub const BUSY_TASK_STATUSES: [&str; 5] = ["Running", "Requested", "Queued", "Stop Requested", "Delete Requested"];
+
+/// gvmd object types the agent creates, and so the ones that end up in the
+/// trashcan when deleted without `ultimate`; tasks first.
+pub const TRASH_TYPES: [&str; 7] = ["task", "target", "config", "schedule", "alert", "credential", "port_list"];
+
+/// gvmd task statuses a task never leaves on its own.
+pub const FINISHED_TASK_STATUSES: [&str; 3] = ["Done", "Stopped", "Interrupted"];
+
+/// gvmd task statuses `openvas_resume_task` can continue from.
+pub const RESUMABLE_TASK_STATUSES: [&str; 2] = ["Stopped", "Interrupted"];
+
+/// Input of the tools that take no arguments.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NoArgs {}
+
+/// Input of `openvas_clone_scan_config`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CloneScanConfigArgs {
+    /// Scan config ID to copy (see openvas_list_scan_configs), e.g. the predefined 'Full and fast'.
+    pub config_id: String,
+    /// Name of the new scan config.
+    pub name: String,
+    pub comment: Option<String>,
+}
+
+/// Input of `openvas_modify_scan_config`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModifyScanConfigArgs {
+    /// Scan config ID to change; a clone (see openvas_clone_scan_config), predefined configs cannot be changed.
+    pub config_id: String,
+    /// NVT families to select in full, e.g. ["Web application abuses", "Web Servers"].
+    #[serde(default)]
+    pub enable_families: Vec<String>,
+    /// NVT families to drop, e.g. ["Denial of Service", "Brute force attacks"].
+    #[serde(default)]
+    pub disable_families: Vec<String>,
+    /// OIDs of single NVTs to select, e.g. one check from an otherwise disabled family.
+    #[serde(default)]
+    pub enable_nvts: Vec<String>,
+    /// OIDs of single NVTs to drop.
+    #[serde(default)]
+    pub disable_nvts: Vec<String>,
+}
+
+/// What `openvas_modify_scan_config` changes. Families are gvmd's NVT family
+/// names, NVTs are OIDs; family changes are applied before NVT ones.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigChanges {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub enable_families: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disable_families: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub enable_nvts: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub disable_nvts: Vec<String>,
+}
+
+impl ConfigChanges {
+    pub fn is_empty(&self) -> bool {
+        self.enable_families.is_empty()
+            && self.disable_families.is_empty()
+            && self.enable_nvts.is_empty()
+            && self.disable_nvts.is_empty()
+    }
+}
+
+/// Input of `openvas_get_nvt`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetNvtArgs {
+    /// NVT OID, e.g. the `nvt_oid` of a finding from openvas_get_report ('1.3.6.1.4.1.25623.1.0.811011').
+    pub oid: String,
+}
+
+/// Input of `openvas_feed_status`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FeedStatusArgs {
+    /// Feeds last updated more than this many days ago are reported as stale. Default: 7
+    #[serde(default = "default_feed_max_age_days")]
+    pub max_age_days: u64,
+}
+
+/// A GVM feed `openvas_sync_feed` can sync.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedType {
+    /// The vulnerability tests (NVTs).
+    Nvt,
+    /// CVE, CPE and OVAL data.
+    Scap,
+    /// CERT-Bund and DFN-CERT advisories.
+    Cert,
+    /// gvmd's scan configs, port lists and report formats.
+    GvmdData,
+    /// Every feed, in order.
+    #[default]
+    All,
+}
+
+/// Input of `openvas_sync_feed`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncFeedArgs {
+    /// Feed to sync. Default: all
+    #[serde(default)]
+    pub feed: FeedType,
+}
+
+/// Input of `openvas_list_hosts`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListHostsArgs {
+    /// Only hosts whose IP or a hostname contains this, case-insensitively, e.g. `10.0.1.` or `.corp.example`.
+    pub host_contains: Option<String>,
+    /// Only hosts whose detected OS contains this, case-insensitively, e.g. `windows`.
+    pub os_contains: Option<String>,
+    /// Only hosts whose latest severity is at least this CVSS score.
+    pub min_severity: Option<f64>,
+    /// Only hosts not seen by any scan for at least this many days, i.e. due a rescan.
+    pub not_seen_for_days: Option<u64>,
+    /// Return at most this many hosts, most severe first. Default: 100
+    #[serde(default = "default_host_limit")]
+    pub limit: usize,
+}
+
+/// Input of `openvas_get_host`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetHostArgs {
+    /// Asset ID from `openvas_list_hosts`, or the host's IP or hostname.
+    pub host: String,
+}
+
+/// A status `openvas_modify_ticket` can set. gvmd moves a Fixed ticket to
+/// Fix Verified itself, once a later scan no longer finds the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TicketStatus {
+    #[serde(alias = "open")]
+    Open,
+    #[serde(alias = "fixed")]
+    Fixed,
+    #[serde(alias = "closed")]
+    Closed,
+}
+
+impl TicketStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TicketStatus::Open => "Open",
+            TicketStatus::Fixed => "Fixed",
+            TicketStatus::Closed => "Closed",
+        }
+    }
+}
+
+/// Input of `openvas_create_ticket`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTicketArgs {
+    /// Result (finding) ID from openvas_get_report to track.
+    pub result_id: String,
+    /// gvmd user name to assign the ticket to.
+    pub assignee: String,
+    /// What needs doing, kept as the ticket's open note.
+    pub note: String,
+}
+
+/// Input of `openvas_list_tickets`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListTicketsArgs {
+    /// Only tickets with this status: Open, Fixed, Fix Verified or Closed. Default: every status
+    pub status: Option<String>,
+    /// Only tickets assigned to this gvmd user name.
+    pub assignee: Option<String>,
+    /// Only tickets whose host contains this, e.g. `10.0.1.`.
+    pub host_contains: Option<String>,
+    /// Only tickets for results of this task ID.
+    pub task_id: Option<String>,
+}
+
+/// Input of `openvas_modify_ticket`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModifyTicketArgs {
+    /// Ticket ID from openvas_create_ticket or openvas_list_tickets.
+    pub ticket_id: String,
+    /// New status. Needs a `note`.
+    pub status: Option<TicketStatus>,
+    /// Why the status changes, e.g. the fix applied; kept as the note of the new status.
+    pub note: Option<String>,
+    /// gvmd user name to reassign the ticket to.
+    pub assignee: Option<String>,
+}
+
+/// What `openvas_modify_ticket` changes; fields left out stay as they are.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TicketChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TicketStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+}
+
+impl TicketChanges {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.note.is_none() && self.assignee.is_none()
+    }
+}
+
+/// Input of `openvas_create_target`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTargetArgs {
+    /// Friendly name for the target.
+    pub name: String,
+    /// Hostname/IP or CIDR understood by OpenVAS.
+    pub hosts: String,
+    /// Optional port range string (e.g. '1-65535' or '62078').
+    pub port_range: Option<String>,
+    /// OpenVAS port list ID to scan instead of `port_range` (see `openvas_list_port_lists`).
+    pub port_list_id: Option<String>,
+}
+
+/// Input of `openvas_list_targets`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListTargetsArgs {
+    /// Only targets whose name contains this text (case-insensitive).
+    pub name_contains: Option<String>,
+    /// Only targets whose hosts contain this text (case-insensitive), e.g. an IP or CIDR.
+    pub hosts_contains: Option<String>,
+}
+
+/// Input of `openvas_delete_target`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteTargetArgs {
+    /// OpenVAS target ID to delete. Targets used by a task cannot be deleted.
+    pub target_id: String,
+    /// Delete for good instead of moving the target to the trashcan, where it can still be restored. Default: false
+    #[serde(default)]
+    pub ultimate: bool,
+}
+
+/// Input of `openvas_create_port_list`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePortListArgs {
+    /// Friendly name for the port list.
+    pub name: String,
+    /// Ports in gvmd's syntax: comma-separated ports and ranges, `T:` and `U:` switching between TCP and UDP (e.g. 'T:1-1024,8080,U:53,161').
+    pub port_range: String,
+    pub comment: Option<String>,
+}
+
+/// Input of `openvas_list_port_lists`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListPortListsArgs {
+    /// Only port lists whose name contains this text (case-insensitive).
+    pub name_contains: Option<String>,
+}
+
+/// What a credential holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialType {
+    /// Login and password.
+    UsernamePassword,
+    /// Login and SSH private key, with an optional passphrase.
+    SshKey,
+}
+
+impl CredentialType {
+    /// gvmd's name for the type.
+    pub fn gvm_type(self) -> &'static str {
+        match self {
+            CredentialType::UsernamePassword => "up",
+            CredentialType::SshKey => "usk",
+        }
+    }
+}
+
+/// Input of `openvas_create_credential`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateCredentialArgs {
+    /// Friendly name for the credential.
+    pub name: String,
+    #[serde(rename = "type")]
+    pub credential_type: CredentialType,
+    /// User name the scanner logs in as.
+    pub login: String,
+    /// Password for username_password; the key's passphrase, if any, for ssh_key.
+    pub password: Option<String>,
+    /// PEM-encoded SSH private key, for ssh_key.
+    pub private_key: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// What `openvas_create_credential` sends to the backend. Deliberately not
+/// `Debug`: it holds the secrets.
+#[derive(Clone, Serialize)]
+pub struct CredentialSpec {
+    pub name: String,
+    /// gvmd's type: `up` or `usk`.
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub login: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Input of `openvas_list_credentials`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListCredentialsArgs {
+    /// Only credentials whose name contains this text (case-insensitive).
+    pub name_contains: Option<String>,
+}
+
+/// Which login of a target a credential is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialKind {
+    /// Local security checks over SSH (Linux, BSD, network devices).
+    Ssh,
+    /// Local security checks over SMB (Windows).
+    Smb,
+}
+
+/// Input of `openvas_attach_credential`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AttachCredentialArgs {
+    /// OpenVAS target ID whose scans should log in. Targets used by a task cannot be changed.
+    pub target_id: String,
+    /// OpenVAS credential ID (see `openvas_create_credential`).
+    pub credential_id: String,
+    pub kind: CredentialKind,
+    /// SSH port, for ssh. Default: 22
+    pub port: Option<u16>,
+}
+
+/// Input of `openvas_create_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTaskArgs {
+    /// Friendly name for the task.
+    pub name: String,
+    /// OpenVAS scan configuration ID to use for the task.
+    pub config_id: String,
+    /// OpenVAS target ID that this task will scan.
+    pub target_id: String,
+    /// OpenVAS schedule ID (see `openvas_create_schedule`); gvmd then starts the task at the scheduled times by itself.
+    pub schedule_id: Option<String>,
+    /// OpenVAS scanner ID to run the task on (see `openvas_list_scanners`). Default: gvmd's default OpenVAS scanner
+    pub scanner_id: Option<String>,
+}
+
+/// Input of `openvas_import_results`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImportResultsArgs {
+    /// Name of the container task holding the imported reports; an existing container task of that name is reused. Default: 'hacker_agent-imported'
+    #[serde(default = "default_import_task_name")]
+    pub task_name: String,
+    /// A GVM-format report to import: a <report> element as returned by gvmd's get_reports or produced by a converter. Give exactly one of report_xml and nmap_output.
+    pub report_xml: Option<String>,
+    /// nmap's normal output (the raw_output of the nmap tools); each open port becomes a Log-level result. Give exactly one of report_xml and nmap_output.
+    pub nmap_output: Option<String>,
+}
+
+fn default_import_task_name() -> String {
+    "hacker_agent-imported".to_string()
+}
+
+/// How an alert notifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMethod {
+    /// Mail `to_address`, with the report attached if `attach_report`.
+    Email,
+    /// Send an HTTP GET to `url`.
+    HttpGet,
+}
+
+impl AlertMethod {
+    /// gvmd's name for the method.
+    pub fn gvm_method(self) -> &'static str {
+        match self {
+            AlertMethod::Email => "Email",
+            AlertMethod::HttpGet => "HTTP Get",
+        }
+    }
+}
+
+/// The task status that sets an alert off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+pub enum AlertTrigger {
+    /// The scan finished.
+    #[default]
+    Done,
+    /// Someone stopped the scan.
+    Stopped,
+    /// The scan broke off, e.g. because the scanner died.
+    Interrupted,
+}
+
+impl AlertTrigger {
+    /// gvmd's task status.
+    pub fn gvm_status(self) -> &'static str {
+        match self {
+            AlertTrigger::Done => "Done",
+            AlertTrigger::Stopped => "Stopped",
+            AlertTrigger::Interrupted => "Interrupted",
+        }
+    }
+}
+
+/// Input of `openvas_create_alert`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateAlertArgs {
+    /// Friendly name for the alert.
+    pub name: String,
+    pub method: AlertMethod,
+    /// Task status that sets the alert off. Default: Done
+    #[serde(default)]
+    pub on_status: AlertTrigger,
+    /// Recipient, for email.
+    pub to_address: Option<String>,
+    /// Sender, for email. Default: gvmd's configured sender
+    pub from_address: Option<String>,
+    /// Attach the report as text instead of sending a short notice, for email. Default: false
+    #[serde(default)]
+    pub attach_report: bool,
+    /// http:// or https:// URL to GET, for http_get. gvmd replaces $n with the task name, $e with the event and $c with the condition.
+    pub url: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// What `openvas_create_alert` sends to the backend.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertSpec {
+    pub name: String,
+    /// gvmd's method name, e.g. `Email` or `HTTP Get`.
+    pub method: String,
+    /// Task status that sets the alert off.
+    pub status: String,
+    /// The method's settings by gvmd's names, e.g. `to_address` or `URL`.
+    pub method_data: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// How often a schedule repeats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Run once, at `first_run`.
+    #[default]
+    Once,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Input of `openvas_create_schedule`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateScheduleArgs {
+    /// Friendly name for the schedule.
+    pub name: String,
+    /// First run, RFC 3339, e.g. `2026-11-02T02:00:00Z`; later runs repeat from it.
+    pub first_run: String,
+    /// How often to repeat. Default: once
+    #[serde(default)]
+    pub recurrence: Recurrence,
+    /// IANA timezone gvmd shows and repeats the schedule in, e.g. `Europe/Berlin`. Default: UTC
+    pub timezone: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// What `openvas_create_schedule` sends to the backend: the schedule as an
+/// iCalendar event.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleSpec {
+    pub name: String,
+    pub icalendar: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Input of `openvas_list_schedules`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListSchedulesArgs {
+    /// Only schedules whose name contains this text (case-insensitive).
+    pub name_contains: Option<String>,
+}
+
+/// Input of `openvas_delete_schedule`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteScheduleArgs {
+    /// OpenVAS schedule ID to delete. Schedules used by a task cannot be deleted.
+    pub schedule_id: String,
+    /// Delete for good instead of moving the schedule to the trashcan. Default: false
+    #[serde(default)]
+    pub ultimate: bool,
+}
+
+/// Input of `openvas_list_tasks`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListTasksArgs {
+    /// Only tasks in this gvmd status (case-insensitive), e.g. 'Running', 'Done' or 'Stopped'.
+    pub status: Option<String>,
+    /// Only tasks whose name contains this text (case-insensitive).
+    pub name_contains: Option<String>,
+    /// Only tasks scanning this target ID.
+    pub target_id: Option<String>,
+}
+
+/// Input of `openvas_modify_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModifyTaskArgs {
+    /// OpenVAS task ID to change. Running or queued tasks cannot be changed.
+    pub task_id: String,
+    /// New task name.
+    pub name: Option<String>,
+    /// New scan config ID (see openvas_list_scan_configs). Only for tasks that have never run.
+    pub config_id: Option<String>,
+    /// New target ID (see openvas_list_targets). Only for tasks that have never run.
+    pub target_id: Option<String>,
+    /// Schedule ID to run the task on.
+    pub schedule_id: Option<String>,
+    /// Alert IDs (see `openvas_create_alert`) the task should set off, replacing its current alerts; [] removes them all.
+    pub alert_ids: Option<Vec<String>>,
+    /// Scanner preferences by name, e.g. {"max_checks": "4", "max_hosts": "10"}.
+    #[serde(default)]
+    pub preferences: BTreeMap<String, String>,
+}
+
+/// What `openvas_modify_task` changes; fields left out stay as they are.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TaskChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub preferences: BTreeMap<String, String>,
+}
+
+impl TaskChanges {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.config_id.is_none()
+            && self.target_id.is_none()
+            && self.schedule_id.is_none()
+            && self.alert_ids.is_none()
+            && self.preferences.is_empty()
+    }
+}
+
+/// Input of `openvas_start_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StartTaskArgs {
+    /// OpenVAS task ID to start.
+    pub task_id: String,
+}
+
+/// Input of `openvas_delete_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteTaskArgs {
+    /// OpenVAS task ID to delete, along with its reports.
+    pub task_id: String,
+    /// Delete for good instead of moving the task to the trashcan, where it can still be restored. Default: false
+    #[serde(default)]
+    pub ultimate: bool,
+    /// Also delete a task that is running or queued, stopping its scan. Default: false
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Input of `openvas_stop_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StopTaskArgs {
+    /// OpenVAS task ID to stop.
+    pub task_id: String,
+}
+
+/// Input of `openvas_resume_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ResumeTaskArgs {
+    /// OpenVAS task ID to resume; it must be Stopped or Interrupted.
+    pub task_id: String,
+}
+
+/// Input of `openvas_task_status`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TaskStatusArgs {
+    /// OpenVAS task ID whose status should be fetched.
+    pub task_id: String,
+}
+
+/// Input of `openvas_wait_for_task`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WaitForTaskArgs {
+    /// OpenVAS task ID to wait for.
+    pub task_id: String,
+    /// Longest wait in seconds; the call then returns the latest status with finished: false. At most 86400. Default: 3600
+    #[serde(default = "default_max_wait_secs")]
+    pub max_wait_secs: u64,
+    /// Seconds between task status polls. Default: 30
+    #[serde(default = "default_wait_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+/// Input of `openvas_get_report`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetReportArgs {
+    /// OpenVAS report ID whose contents should be fetched.
+    pub report_id: String,
+    /// Report format. Default: xml
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Save the report as an `openvas://report/{report_id}/{format}` resource and return its URI instead of the contents. Default: false
+    #[serde(default)]
+    pub as_resource: bool,
+    /// Only results with at least this CVSS score, 0.0 to 10.0, e.g. 7.0 for High and Critical or 9.0 for Critical only.
+    pub min_severity: Option<f64>,
+    /// Only results with at least this quality of detection, 0 to 100, e.g. 70 for gvmd's default or 90 for high-confidence results.
+    pub min_qod: Option<u8>,
+}
+
+/// Which results of a report `openvas_get_report` keeps; gvmd applies it
+/// to every format, and fields left out keep everything.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ReportFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_qod: Option<u8>,
+}
+
+impl ReportFilter {
+    pub fn keeps(&self, result: &ReportResult) -> bool {
+        self.min_severity.is_none_or(|min| result.severity >= min)
+            && self.min_qod.is_none_or(|min| result.qod.is_none_or(|qod| qod >= min))
+    }
+}
+
+/// gvmd's predefined report formats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    /// The raw `<get_reports_response/>`, with parsed findings.
+    #[default]
+    Xml,
+    Csv,
+    /// Base64-encoded.
+    Pdf,
+    Txt,
+    /// XML with host names and addresses replaced.
+    AnonymousXml,
+}
+
+impl ReportFormat {
+    /// The ID gvmd knows the format by.
+    pub fn gvm_id(self) -> &'static str {
+        match self {
+            ReportFormat::Xml => "a994b278-1f62-11e1-96ac-406186ea4fc5",
+            ReportFormat::Csv => "c1645568-627a-11e3-a660-406186ea4fc5",
+            ReportFormat::Pdf => "c402cc3e-b531-11e1-9163-406186ea4fc5",
+            ReportFormat::Txt => "a3810a62-1f62-11e1-9219-406186ea4fc5",
+            ReportFormat::AnonymousXml => "5057e5cc-b825-11e4-9d0e-28d24461215b",
+        }
+    }
+
+    /// Name used in resource URIs.
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportFormat::Xml => "xml",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Txt => "txt",
+            ReportFormat::AnonymousXml => "anonymous_xml",
+        }
+    }
+}
+
+/// Input of `openvas_get_results`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetResultsArgs {
+    /// gvmd filter string selecting results, e.g. `severity>6.9 and host=10.0.0.5` or `name~OpenSSH`. Paging is set with `first` and `rows`, not here. Default: every result
+    pub filter: Option<String>,
+    /// Only results of this report ID.
+    pub report_id: Option<String>,
+    /// Only results of this task ID's reports.
+    pub task_id: Option<String>,
+    /// 1-based index of the first result to return; pass the previous page's `next_first` to continue. Default: 1
+    #[serde(default = "default_first")]
+    pub first: u64,
+    /// Results per page, at most 1000. Default: 100
+    #[serde(default = "default_rows")]
+    pub rows: u64,
+}
+
+/// Input of `openvas_list_reports`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListReportsArgs {
+    /// Only reports of this task ID. Default: reports of every task
+    pub task_id: Option<String>,
+    /// Return at most this many reports, newest first; 1 gives a task's latest report.
+    pub limit: Option<usize>,
+}
+
+/// Input of `openvas_delete_report`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteReportArgs {
+    /// OpenVAS report ID to delete (see openvas_list_reports).
+    pub report_id: String,
+}
+
+/// Input of `openvas_diff_reports`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DiffReportsArgs {
+    /// Report ID of the earlier scan, e.g. from before remediation.
+    pub base_report_id: String,
+    /// Report ID of the later scan to compare against it.
+    pub report_id: String,
+    /// Only compare results with at least this CVSS score, 0.0 to 10.0, e.g. 4.0 to ignore Low and Log results.
+    pub min_severity: Option<f64>,
+    /// Only compare results with at least this quality of detection, 0 to 100.
+    pub min_qod: Option<u8>,
+    /// List the unchanged findings too, not just their count. Default: true
+    #[serde(default = "default_true")]
+    pub include_unchanged: bool,
+}
+
+/// Input of `openvas_prioritize_findings`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PrioritizeFindingsArgs {
+    /// OpenVAS report ID whose findings should be prioritized.
+    pub report_id: String,
+    /// Only results with at least this CVSS score, 0.0 to 10.0.
+    pub min_severity: Option<f64>,
+    /// Only results with at least this quality of detection, 0 to 100.
+    pub min_qod: Option<u8>,
+    /// Look the findings' CVEs up in NVD, CISA KEV and FIRST EPSS. Without it, findings are ranked by CVSS alone. Default: true
+    #[serde(default = "default_true")]
+    pub enrich: bool,
+    /// Look up at most this many CVEs, most severe findings first; NVD rate-limits clients without an API key. Default: 25
+    #[serde(default = "default_max_cves")]
+    pub max_cves: usize,
+}
+
+/// Input of `openvas_export_sarif`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExportSarifArgs {
+    /// OpenVAS report ID whose findings should be exported.
+    pub report_id: String,
+    /// Only results with at least this CVSS score, 0.0 to 10.0, e.g. 0.1 to leave out Log results.
+    pub min_severity: Option<f64>,
+    /// Only results with at least this quality of detection, 0 to 100.
+    pub min_qod: Option<u8>,
+    /// Save the log as an `openvas://report/{report_id}/sarif` resource and return its URI instead of the log. Default: false
+    #[serde(default)]
+    pub as_resource: bool,
+}
+
+/// Document formats `openvas_render_report` writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    #[default]
+    Markdown,
+    /// A standalone page with inline styles.
+    Html,
+}
+
+impl RenderFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            RenderFormat::Markdown => "md",
+            RenderFormat::Html => "html",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            RenderFormat::Markdown => "text/markdown",
+            RenderFormat::Html => "text/html",
+        }
+    }
+
+    /// Name used in resource URIs.
+    pub fn name(self) -> &'static str {
+        match self {
+            RenderFormat::Markdown => "markdown",
+            RenderFormat::Html => "html",
+        }
+    }
+}
+
+/// Input of `openvas_render_report`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RenderReportArgs {
+    /// OpenVAS report ID to render.
+    pub report_id: String,
+    /// Document format. Default: markdown
+    #[serde(default)]
+    pub format: RenderFormat,
+    /// Only results with at least this CVSS score, 0.0 to 10.0, e.g. 0.1 to leave out Log results.
+    pub min_severity: Option<f64>,
+    /// Only results with at least this quality of detection, 0 to 100.
+    pub min_qod: Option<u8>,
+    /// Targets of earlier nmap scans (as passed to the scan tool) whose latest open ports and services are added to their host's section. Default: none
+    #[serde(default)]
+    pub nmap_targets: Vec<String>,
+}
+
+/// Input of `openvas_correlate_nmap`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CorrelateNmapArgs {
+    /// OpenVAS report ID whose findings to check.
+    pub report_id: String,
+    /// Only results with at least this CVSS score, 0.0 to 10.0. Default: 0.1, leaving out Log results
+    pub min_severity: Option<f64>,
+    /// Only results with at least this quality of detection, 0 to 100.
+    pub min_qod: Option<u8>,
+    /// Targets of earlier nmap scans (as passed to the scan tool) to check the findings against, e.g. a subnet scanned as a whole. Default: the latest scan of each host with findings
+    #[serde(default)]
+    pub nmap_targets: Vec<String>,
+}
+
+/// Input of `openvas_bulk_create_targets`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BulkCreateTargetsArgs {
+    /// Hosts, IPs, ranges or CIDRs: one string separated by commas or whitespace, or an array of them. Give either hosts or tag.
+    pub hosts: Option<HostList>,
+    /// Use every asset the tagging rules gave this tag (see asset_tags), e.g. 'web-server'. Give either hosts or tag.
+    pub tag: Option<String>,
+    /// Most hosts per GVM target; larger CIDRs are split. Default: 4095 (gvmd's max_hosts)
+    #[serde(default = "default_max_hosts")]
+    pub max_hosts_per_target: u64,
+    /// Target name template; {n}, {total} and {date} (YYYYMMDD) are filled in. Default: 'hacker_agent-{date}-{n}of{total}'
+    #[serde(default = "default_name_template")]
+    pub name_template: String,
+    /// Optional port range for every target (e.g. '1-65535'). Give at most one of port_range and port_list_id.
+    pub port_range: Option<String>,
+    /// OpenVAS port list ID for every target (see openvas_list_port_lists).
+    pub port_list_id: Option<String>,
+}
+
+/// A host list given as one comma/whitespace-separated string or as an
+/// array of entries.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum HostList {
+    Joined(String),
+    Entries(Vec<String>),
+}
+
+impl HostList {
+    /// The list as one comma-separated string.
+    pub fn joined(self) -> String {
+        match self {
+            HostList::Joined(hosts) => hosts,
+            HostList::Entries(entries) => entries.join(","),
+        }
+    }
+}
+
+/// Input of `openvas_full_scan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FullScanArgs {
+    /// Hostname/IP or CIDR understood by OpenVAS, comma-separated for several.
+    pub hosts: String,
+    /// Scan config name (case-insensitive, see openvas_list_scan_configs). Default: 'Full and fast'
+    #[serde(default = "default_config_name")]
+    pub config_name: String,
+    /// Target and task name; calls with the same name, hosts and config reuse the target and task. Default: 'hacker_agent {hosts}'
+    pub name: Option<String>,
+    /// Optional port range for the target (e.g. '1-65535'). Give at most one of port_range and port_list_id.
+    pub port_range: Option<String>,
+    /// OpenVAS port list ID for the target (see openvas_list_port_lists).
+    pub port_list_id: Option<String>,
+    /// OpenVAS scanner ID to run the task on (see openvas_list_scanners). Default: gvmd's default OpenVAS scanner
+    pub scanner_id: Option<String>,
+}
+
+/// Input of `openvas_recommend_config`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RecommendConfigArgs {
+    /// Hostname/IP or CIDR to plan an OpenVAS scan of; nmap scans its top 1000 TCP ports with service detection first.
+    pub target: String,
+    /// Open TCP ports already known, e.g. from an earlier nmap scan; skips the nmap scan.
+    pub open_ports: Option<Vec<u16>>,
+    /// Speed of the nmap scan: T3 (Normal) or T4 (Aggressive). Default: T4
+    #[serde(default)]
+    pub timing: FastTiming,
+}
+
+/// Input of `openvas_fan_out`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FanOutArgs {
+    /// OpenVAS scan config ID for every task (see openvas_list_scan_configs).
+    pub config_id: String,
+    /// Hosts, IPs, ranges or CIDRs, as one string separated by commas or whitespace or an array; targets are created for them. Give exactly one of hosts, tag or target_ids.
+    pub hosts: Option<HostList>,
+    /// Scan every asset the tagging rules gave this tag (see asset_tags). Give exactly one of hosts, tag or target_ids.
+    pub tag: Option<String>,
+    /// Existing target IDs, e.g. from openvas_bulk_create_targets. Give exactly one of hosts, tag or target_ids.
+    pub target_ids: Option<Vec<String>>,
+    /// Most hosts per target when splitting hosts or tag. Default: 4095 (gvmd's max_hosts)
+    #[serde(default = "default_max_hosts")]
+    pub max_hosts_per_target: u64,
+    /// Target and task name template; {n}, {total} and {date} (YYYYMMDD) are filled in. Default: 'hacker_agent-{date}-{n}of{total}'
+    #[serde(default = "default_name_template")]
+    pub name_template: String,
+    /// Optional port range for created targets (e.g. '1-65535'). Give at most one of port_range and port_list_id.
+    pub port_range: Option<String>,
+    /// OpenVAS port list ID for created targets (see openvas_list_port_lists).
+    pub port_list_id: Option<String>,
+    /// Seconds between task status polls. Default: 60
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+/// Input of `openvas_cleanup`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CleanupArgs {
+    /// Glob matched against task/target names, e.g. 'hacker_agent-*'. Only matching objects are considered.
+    pub name_pattern: String,
+    /// Only delete objects created more than this many days ago. Default: 30
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u64,
+    /// Delete objects outright and empty the GVM trashcan afterwards. Default: true
+    #[serde(default = "default_true")]
+    pub empty_trashcan: bool,
+    /// Actually delete. Without it the matching objects are only listed for review. Default: false
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Input of `openvas_empty_trashcan`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EmptyTrashcanArgs {
+    /// Actually empty the trashcan. Without it its contents are only listed for review. Default: false
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Input of `openvas_restore`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RestoreArgs {
+    /// ID of the trashed task, target, scan config, schedule, alert, credential or port list to restore (see openvas_list_trash).
+    pub id: String,
+}
+
+fn default_config_name() -> String {
+    "Full and fast".to_string()
+}
+
+fn default_max_wait_secs() -> u64 {
+    60 * 60
+}
+
+fn default_wait_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_max_hosts() -> u64 {
+    GVM_MAX_HOSTS
+}
+
+fn default_name_template() -> String {
+    "hacker_agent-{date}-{n}of{total}".to_string()
+}
+
+fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_retention_days() -> u64 {
+    30
+}
+
+fn default_max_cves() -> usize {
+    25
+}
+
+fn default_feed_max_age_days() -> u64 {
+    7
+}
+
+fn default_host_limit() -> usize {
+    100
+}
+
+fn default_first() -> u64 {
+    1
+}
+
+fn default_rows() -> u64 {
+    100
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `GET /openvas/version`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Version {
+    /// gvmd's raw `<get_version_response/>`.
+    pub version_raw: String,
+}
+
+/// One scan configuration (profile).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanConfig {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub comment: String,
+}
+
+/// `GET /openvas/configs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigList {
+    pub configs: Vec<ScanConfig>,
+}
+
+/// One NVT family selected in a scan config: `nvt_count` of its
+/// `max_nvt_count` NVTs, and whether NVTs added to it by feed updates join.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigFamily {
+    pub name: String,
+    pub nvt_count: u64,
+    pub max_nvt_count: u64,
+    pub growing: bool,
+}
+
+/// `POST /openvas/configs/modify`: the families selected after the change.
+/// `notes` is filled by `openvas_modify_scan_config`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedConfig {
+    pub config_id: String,
+    pub families: Vec<ConfigFamily>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// One reference of an NVT: a CVE, CERT advisory or URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NvtReference {
+    /// gvmd's reference type, lowercased: `cve`, `url`, `cert-bund`, `dfn-cert`, ...
+    #[serde(rename = "type")]
+    pub ref_type: String,
+    pub id: String,
+}
+
+/// `POST /openvas/nvts`: what gvmd knows about one NVT. The description
+/// fields come from the NVT's tags.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NvtDetails {
+    pub oid: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub family: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_base: Option<f64>,
+    /// e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_vector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insight: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affected: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact: Option<String>,
+    /// How the NVT detects the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detection: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+    /// `VendorFix`, `Workaround`, `Mitigation`, `NoneAvailable` or `WillNotFix`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution_type: Option<String>,
+    #[serde(default)]
+    pub references: Vec<NvtReference>,
+}
+
+/// One GVM feed: NVT, SCAP, CERT or GVMD_DATA. `version` is the feed's
+/// timestamp, e.g. `202401250530`; `openvas_feed_status` fills
+/// `last_update`, `age_hours` and `stale` from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedSummary {
+    #[serde(rename = "type")]
+    pub feed_type: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default)]
+    pub currently_syncing: bool,
+    /// RFC 3339, UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_hours: Option<u64>,
+    #[serde(default)]
+    pub stale: bool,
+}
+
+/// `GET /openvas/feeds`. `warnings` is filled by `openvas_feed_status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedList {
+    pub feeds: Vec<FeedSummary>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// `POST /openvas/feeds/sync`: greenbone-feed-sync's output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedFeed {
+    pub feed: FeedType,
+    pub output: String,
+}
+
+/// One host in gvmd's asset database, which gvmd fills from the reports
+/// that saw it. `severity` is the highest of the host's latest report;
+/// `report_ids` are every report the host appears in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostAsset {
+    pub id: String,
+    pub ip: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+    /// gvmd's best guess, e.g. `Ubuntu 22.04`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// e.g. `cpe:/o:canonical:ubuntu_linux:22.04`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_cpe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+    #[serde(default)]
+    pub report_ids: Vec<String>,
+    /// Every host detail gvmd recorded, e.g. `ports`, `traceroute`, `best_os_txt`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+}
+
+/// `GET /openvas/assets/hosts`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostAssetList {
+    pub hosts: Vec<HostAsset>,
+}
+
+/// Output of `openvas_list_hosts`: the hosts that matched, without their
+/// details, and how many matched before `limit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostInventory {
+    pub total: usize,
+    pub hosts: Vec<HostAsset>,
+}
+
+/// One GVM remediation ticket: a result assigned to a user, with its
+/// status (`Open`, `Fixed`, `Fix Verified` or `Closed`), when it reached
+/// each, and the note left at each change.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ticket {
+    pub id: String,
+    /// The result's NVT name.
+    pub name: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The result's port, e.g. `22/tcp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix_verified_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_note: Option<String>,
+}
+
+/// `GET /openvas/tickets/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketList {
+    pub tickets: Vec<Ticket>,
+}
+
+/// `POST /openvas/targets` and `POST /openvas/tasks`: the object's ID, and
+/// whether an identical object was reused instead of created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedObject {
+    pub id: String,
+    #[serde(default)]
+    pub existed: bool,
+}
+
+/// `POST /openvas/reports/import`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedReport {
+    pub report_id: String,
+    /// gvmd's raw `<create_report_response/>`.
+    pub response_raw: String,
+}
+
+/// Output of `openvas_import_results`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedResults {
+    pub task_id: String,
+    pub task_name: String,
+    /// The container task already existed and got one more report.
+    pub task_existed: bool,
+    pub report_id: String,
+    /// Distinct hosts and results in the imported report.
+    pub hosts: usize,
+    pub results: usize,
+}
+
+/// One existing target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetSummary {
+    pub id: String,
+    pub name: String,
+    /// gvmd's host list, comma-separated.
+    pub hosts: String,
+    /// Name of the target's port list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_list: Option<String>,
+    /// Whether a task uses the target; targets in use cannot be deleted.
+    #[serde(default)]
+    pub in_use: bool,
+    /// Creation time, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+}
+
+/// `GET /openvas/targets/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetList {
+    pub targets: Vec<TargetSummary>,
+}
+
+/// How many ports a port list holds, per protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortCount {
+    pub all: u32,
+    pub tcp: u32,
+    pub udp: u32,
+}
+
+impl PortCount {
+    /// Count the ports of `range`, in gvmd's port range syntax: ports and
+    /// `first-last` ranges separated by commas, TCP unless a `U:` prefix
+    /// switches to UDP (and `T:` back). `None` if gvmd would reject it.
+    pub fn of_range(range: &str) -> Option<PortCount> {
+        let mut count = PortCount::default();
+        let mut udp = false;
+        for item in range.split(',').map(str::trim) {
+            let item = match item.split_once(':') {
+                Some((proto, rest)) => {
+                    udp = match proto.trim() {
+                        "T" | "t" => false,
+                        "U" | "u" => true,
+                        _ => return None,
+                    };
+                    rest.trim()
+                }
+                None => item,
+            };
+            let (first, last) = item.split_once('-').unwrap_or((item, item));
+            let first: u16 = first.trim().parse().ok()?;
+            let last: u16 = last.trim().parse().ok()?;
+            if first == 0 || last < first {
+                return None;
+            }
+            let n = u32::from(last - first) + 1;
+            if udp {
+                count.udp += n;
+            } else {
+                count.tcp += n;
+            }
+        }
+        count.all = count.tcp + count.udp;
+        Some(count)
+    }
+}
+
+/// One existing port list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortListSummary {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub comment: String,
+    pub port_count: PortCount,
+    pub in_use: bool,
+    /// Shipped with gvmd, e.g. "All IANA assigned TCP"; these cannot be changed.
+    pub predefined: bool,
+}
+
+/// `GET /openvas/port-lists/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortListList {
+    pub port_lists: Vec<PortListSummary>,
+}
+
+/// One scanner tasks can run on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerSummary {
+    pub id: String,
+    pub name: String,
+    /// gvmd's scanner type: `openvas`, `cve`, `osp_sensor`, ...
+    #[serde(rename = "type")]
+    pub scanner_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub host: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub port: String,
+    pub in_use: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+}
+
+/// `GET /openvas/scanners`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannerList {
+    pub scanners: Vec<ScannerSummary>,
+}
+
+/// One existing credential, without its secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialSummary {
+    pub id: String,
+    pub name: String,
+    /// gvmd's type, e.g. `up` or `usk`.
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub login: String,
+    pub in_use: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+}
+
+/// `GET /openvas/credentials/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialList {
+    pub credentials: Vec<CredentialSummary>,
+}
+
+/// `POST /openvas/targets/credential`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachedCredential {
+    pub target_id: String,
+    pub credential_id: String,
+    pub kind: CredentialKind,
+    /// gvmd's raw `<modify_target_response/>`.
+    pub response_raw: String,
+}
+
+/// One existing schedule and the tasks that use it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleSummary {
+    pub id: String,
+    pub name: String,
+    /// The schedule as an iCalendar event: DTSTART is the first run, RRULE the recurrence.
+    pub icalendar: String,
+    pub timezone: String,
+    pub in_use: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub task_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+}
+
+/// `GET /openvas/schedules/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleList {
+    pub schedules: Vec<ScheduleSummary>,
+}
+
+/// One existing task and where it stands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSummary {
+    pub id: String,
+    pub name: String,
+    /// gvmd's task status, e.g. `Running` or `Done`.
+    pub status: String,
+    /// Percent complete; gvmd reports -1 for tasks that are not running.
+    pub progress: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_report_id: Option<String>,
+    /// When the last report was started, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_report_time: Option<String>,
+    /// Creation time, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+}
+
+/// `GET /openvas/tasks/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskList {
+    pub tasks: Vec<TaskSummary>,
+}
+
+/// `POST /openvas/targets/delete` and `POST /openvas/tasks/delete`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedObject {
+    pub id: String,
+    /// Deleted for good rather than moved to the trashcan.
+    pub ultimate: bool,
+    /// gvmd's raw `<delete_*_response/>`.
+    pub response_raw: String,
+}
+
+/// `POST /openvas/tasks/start`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartedTask {
+    pub task_id: String,
+    /// gvmd's raw `<start_task_response/>`.
+    pub response_raw: String,
+}
+
+/// `POST /openvas/tasks/modify`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedTask {
+    pub task_id: String,
+    /// gvmd's raw `<modify_task_response/>`.
+    pub response_raw: String,
+}
+
+/// `POST /openvas/tasks/resume`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumedTask {
+    pub task_id: String,
+    /// gvmd's raw `<resume_task_response/>`.
+    pub response_raw: String,
+}
+
+/// `POST /openvas/tasks/stop`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoppedTask {
+    pub task_id: String,
+    /// gvmd's raw `<stop_task_response/>`.
+    pub response_raw: String,
+}
+
+/// `POST /openvas/tasks/status`. The parsed fields are missing when the
+/// backend could not parse gvmd's XML; `openvas_task_status` fills them,
+/// and the name and run timestamps, from `response_raw`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub task_id: String,
+    /// gvmd's raw `<get_tasks_response/>`.
+    pub response_raw: String,
+    /// gvmd's task status, e.g. `Running` or `Done`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Percent complete; gvmd reports -1 for tasks that are not running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_report_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Report of the scan that is running, if one is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_report_id: Option<String>,
+    /// When the running scan, or else the last one, started; RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_start: Option<String>,
+    /// When the last scan ended; missing while a scan runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_end: Option<String>,
+}
+
+/// Output of `openvas_wait_for_task`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitedTask {
+    pub task_id: String,
+    /// Whether the task reached Done, Stopped or Interrupted within the wait.
+    pub finished: bool,
+    /// gvmd's task status when the wait ended.
+    pub status: String,
+    /// Percent complete when the wait ended; -1 once the task is not running.
+    pub progress: i64,
+    /// The task's latest report, for openvas_get_report.
+    pub report_id: Option<String>,
+    pub waited_secs: u64,
+}
+
+/// One finding of a report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub port: String,
+    pub nvt_oid: String,
+    /// CVSS score, 0.0 to 10.0.
+    pub severity: f64,
+    /// gvmd's threat level: High, Medium, Low, Log or False Positive.
+    pub threat: String,
+    /// Quality of detection, 0 to 100: how sure the scanner is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qod: Option<u8>,
+    /// CVEs the NVT references, e.g. `CVE-2023-38408`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cves: Vec<String>,
+    /// How to fix it, from the NVT, e.g. `Update to version 9.3p2 or later.`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+}
+
+/// `POST /openvas/reports`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub report_id: String,
+    /// gvmd's raw `<get_reports_response/>`; empty for formats other than XML.
+    #[serde(default)]
+    pub response_raw: String,
+    /// Parsed findings; empty when the backend could not parse the report.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub results: Vec<ReportResult>,
+    /// MIME type of the report format, e.g. `text/csv`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+    /// Output of text formats (CSV, TXT).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Output of binary formats (PDF), base64-encoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_base64: Option<String>,
+    /// Where the report was saved instead, with `as_resource`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_uri: Option<String>,
+}
+
+/// `POST /openvas/results`: one page of results across reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultPage {
+    pub results: Vec<ReportResult>,
+    /// 1-based index of the first result on this page.
+    pub first: u64,
+    /// Page size asked for; the last page holds fewer.
+    pub rows: u64,
+    /// Results matching the filter across every page.
+    pub filtered: u64,
+    /// `first` of the next page; missing on the last one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_first: Option<u64>,
+}
+
+/// A report's results counted by threat level.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    pub high: u64,
+    pub medium: u64,
+    pub low: u64,
+    pub log: u64,
+    pub false_positive: u64,
+}
+
+/// One report, without its results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSummary {
+    pub id: String,
+    pub task_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_name: Option<String>,
+    /// gvmd's scan run status, e.g. `Running`, `Done` or `Stopped`.
+    pub status: String,
+    /// Scan start, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    /// Scan end, RFC 3339; missing while the scan runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished: Option<String>,
+    /// Highest CVSS score among the results.
+    pub severity: f64,
+    pub counts: SeverityCounts,
+}
+
+/// `POST /openvas/reports/list`, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportList {
+    pub reports: Vec<ReportSummary>,
+}
+
+/// What NVD, CISA KEV and FIRST EPSS know about a CVE. Fields a source
+/// did not answer for are missing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CveInfo {
+    pub id: String,
+    /// NVD's CVSS v3 base score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_v3_score: Option<f64>,
+    /// e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_v3_vector: Option<String>,
+    /// When NVD published the CVE, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// Listed in CISA's Known Exploited Vulnerabilities catalog.
+    #[serde(default)]
+    pub kev: bool,
+    /// Probability of exploitation in the next 30 days, 0.0 to 1.0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss_percentile: Option<f64>,
+}
+
+/// One finding of `openvas_prioritize_findings`, with its CVEs looked up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritizedFinding {
+    /// `urgent` (a CVE is known to be exploited), `high`, `medium` or `low`.
+    pub priority: String,
+    #[serde(flatten)]
+    pub result: ReportResult,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cve_details: Vec<CveInfo>,
+}
+
+/// Output of `openvas_prioritize_findings`, most pressing first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritizedFindings {
+    pub report_id: String,
+    pub findings: Vec<PrioritizedFinding>,
+    /// Sources that could not be reached, CVEs left out by `max_cves`,
+    /// and the like.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Output of `openvas_export_sarif`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifExport {
+    pub report_id: String,
+    pub result_count: usize,
+    /// The SARIF 2.1.0 log; missing with `as_resource`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sarif: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_uri: Option<String>,
+}
+
+/// Output of `openvas_render_report`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedReport {
+    pub report_id: String,
+    pub format: RenderFormat,
+    /// Where the document was written, under `--reports-dir`.
+    pub path: String,
+    /// `openvas://report/{report_id}/{format}`, to read the document back.
+    pub resource_uri: String,
+    pub findings: usize,
+    pub hosts: usize,
+    /// `nmap_targets` without a remembered scan.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_scans: Vec<String>,
+}
+
+/// How reachable a finding's port was in the latest nmap scan of its host,
+/// most exposed first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Exposure {
+    /// nmap found the port open.
+    Reachable,
+    /// nmap got no answer on the port: a firewall drops the probes.
+    Filtered,
+    /// nmap found the port closed; the service may be gone since the scan.
+    Closed,
+    /// The finding is about the host, not a port (`general/tcp`).
+    HostLevel,
+    /// The host's scan did not list the port, e.g. outside the ports nmap scanned.
+    NotScanned,
+    /// No nmap scan of the host is remembered.
+    NoScan,
+}
+
+/// One finding of `openvas_correlate_nmap`, with what nmap saw on its port.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelatedFinding {
+    #[serde(flatten)]
+    pub finding: ReportResult,
+    pub exposure: Exposure,
+    /// nmap's state for the port, e.g. `open|filtered`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nmap_state: Option<String>,
+    /// nmap's service name for the port.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nmap_service: Option<String>,
+}
+
+/// One nmap scan `openvas_correlate_nmap` used.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelatedScan {
+    pub target: String,
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Output of `openvas_correlate_nmap`: the findings, most exposed and most
+/// severe first.
+#[derive(Debug, Clone, Serialize)]
+pub struct NmapCorrelation {
+    pub report_id: String,
+    pub counts: BTreeMap<Exposure, usize>,
+    pub findings: Vec<CorrelatedFinding>,
+    pub scans: Vec<CorrelatedScan>,
+    /// Hosts with findings, and `nmap_targets`, without a remembered scan.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_scans: Vec<String>,
+}
+
+/// How many findings of each kind a report diff found.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffCounts {
+    pub new: usize,
+    pub fixed: usize,
+    pub unchanged: usize,
+}
+
+/// Output of `openvas_diff_reports`. Findings are matched by host, port
+/// and NVT; `new` and `unchanged` come from the later report, `fixed` from
+/// the base report. Each list is sorted by severity, highest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDiff {
+    pub base_report_id: String,
+    pub report_id: String,
+    pub counts: DiffCounts,
+    pub new: Vec<ReportResult>,
+    pub fixed: Vec<ReportResult>,
+    /// Left out with `include_unchanged: false`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unchanged: Option<Vec<ReportResult>>,
+}
+
+/// One task or target selected by cleanup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupObject {
+    /// `task` or `target`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+    pub name: String,
+    pub created: String,
+    pub deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `POST /openvas/cleanup`: what was removed, or would be when not applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub applied: bool,
+    pub objects: Vec<CleanupObject>,
+    pub deleted_tasks: u64,
+    pub deleted_targets: u64,
+    pub trash_tasks: u64,
+    pub trash_targets: u64,
+    pub trashcan_emptied: bool,
+}
+
+/// One object in the GVM trashcan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedObject {
+    /// gvmd's object type: task, target, config, schedule, alert, credential or port_list.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+    pub name: String,
+}
+
+/// `GET /openvas/trash`, tasks first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashList {
+    pub objects: Vec<TrashedObject>,
+}
+
+/// `POST /openvas/trash/empty`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmptiedTrashcan {
+    /// gvmd's raw `<empty_trashcan_response/>`.
+    pub response_raw: String,
+}
+
+/// Output of `openvas_empty_trashcan`: what the trashcan held, and without
+/// `confirm`, how to empty it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmptyTrashcanResult {
+    pub emptied: bool,
+    pub count: usize,
+    pub objects: Vec<TrashedObject>,
+    /// `pending_confirmation` when nothing was deleted yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// `POST /openvas/trash/restore`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredObject {
+    pub id: String,
+    /// gvmd's raw `<restore_response/>`.
+    pub response_raw: String,
+}
+
+/// Output of `openvas_cleanup`: the backend's report, and without
+/// `confirm`, how to apply it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupResult {
+    #[serde(flatten)]
+    pub report: CleanupReport,
+    /// `pending_confirmation` when nothing was deleted yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// One target created by `openvas_bulk_create_targets`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTarget {
+    pub id: String,
+    pub existed: bool,
+    pub name: String,
+    /// The chunk of hosts this target covers, comma-separated.
+    pub hosts: String,
+    pub host_count: u64,
+}
+
+/// Output of `openvas_bulk_create_targets`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTargets {
+    pub count: usize,
+    pub target_ids: Vec<String>,
+    pub targets: Vec<BulkTarget>,
+}
+
+/// One task of a fan-out and what polling has learned about it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutTask {
+    pub task_id: String,
+    pub target_id: String,
+    pub name: String,
+    pub status: String,
+    pub progress: i64,
+    pub report_id: Option<String>,
+    /// Findings in the task's report, once it has been fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutSummary {
+    pub findings: usize,
+    /// Finding count per threat level.
+    pub by_threat: BTreeMap<String, u64>,
+}
+
+/// Output of `openvas_full_scan`: what was created or reused, and where
+/// the scan stands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullScanStarted {
+    pub task_id: String,
+    pub target_id: String,
+    pub config_id: String,
+    /// The target already existed and was reused.
+    pub target_existed: bool,
+    /// The task already existed and was reused.
+    pub task_existed: bool,
+    /// Whether this call started the task; false when a reused task was already running.
+    pub started: bool,
+    /// gvmd's task status after the call, e.g. `Requested` or `Running`.
+    pub status: String,
+    /// How to follow the scan.
+    pub next: String,
+}
+
+/// Output of `openvas_recommend_config`: the scan config and port range to
+/// create the task with, and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigRecommendation {
+    pub target: String,
+    /// Open TCP ports nmap found, or those given.
+    pub open_ports: Vec<u16>,
+    /// Services nmap identified on them, e.g. `ssh` or `http`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<String>,
+    pub config_name: String,
+    /// ID of `config_name` in gvmd; missing when gvmd has no such config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_id: Option<String>,
+    /// Port range for the target, e.g. `T:22,80,443`.
+    pub port_range: String,
+    /// Why, and what else would improve the scan (e.g. credentials).
+    pub rationale: Vec<String>,
+    /// How to act on it.
+    pub next: String,
+}
+
+/// Output of `openvas_fan_out`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutResult {
+    /// Whether every task ran to `Done`.
+    pub complete: bool,
+    pub tasks: Vec<FanOutTask>,
+    pub summary: FanOutSummary,
+    /// Findings of all tasks, deduplicated and sorted by severity.
+    pub findings: Vec<ReportResult>,
+}
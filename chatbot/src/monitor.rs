@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{api, config, handle_scheduled_request, idle, tagging, RpcRequest, ToolRegistry};
+
+/// Tag carried by every asset under continuous monitoring.
+pub const MONITORED_TAG: &str = "monitored";
+
+/// Scan tools a monitor may run: repeatable and non-intrusive, so running
+/// them unattended needs no approval.
+pub const MONITOR_TOOLS: [&str; 3] = ["nmap.scan", "native_syn_scan", "native_banner_grab"];
+
+/// How often the scheduler looks for monitors that are due.
+const TICK: Duration = Duration::from_secs(30);
+
+/// Open ports and services seen on the previous scan, diffed against the next.
+#[derive(Debug, Clone, Default, Serialize)]
+struct Baseline {
+    open_ports: BTreeSet<u16>,
+    services: BTreeSet<String>,
+    scanned_at: Option<DateTime<Utc>>,
+}
+
+/// What changed between two scans of a monitored asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct Delta {
+    pub opened_ports: Vec<u16>,
+    pub closed_ports: Vec<u16>,
+    pub new_services: Vec<String>,
+    pub gone_services: Vec<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// One monitored asset and its schedule.
+#[derive(Debug, Clone, Serialize)]
+pub struct Monitor {
+    pub target: String,
+    pub tool: String,
+    pub arguments: Map<String, Value>,
+    pub interval_minutes: u64,
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    baseline: Option<Baseline>,
+    pub last_change: Option<Delta>,
+}
+
+/// Monitors by target. In memory only: they are lost when the server restarts.
+static MONITORS: Mutex<BTreeMap<String, Monitor>> = Mutex::new(BTreeMap::new());
+
+/// Start (or reschedule) monitoring of `target`. The first scan establishes
+/// the baseline; later ones only notify when something changed.
+pub fn add(target: &str, tool: &str, arguments: Map<String, Value>, interval_minutes: u64) -> Result<Monitor> {
+    if !MONITOR_TOOLS.contains(&tool) {
+        anyhow::bail!("{tool} cannot be monitored (expected one of {})", MONITOR_TOOLS.join(", "));
+    }
+    if interval_minutes == 0 {
+        anyhow::bail!("interval_minutes must be at least 1");
+    }
+
+    let monitor = Monitor {
+        target: target.to_string(),
+        tool: tool.to_string(),
+        arguments,
+        interval_minutes,
+        last_run: None,
+        last_error: None,
+        baseline: None,
+        last_change: None,
+    };
+    MONITORS.lock().unwrap().insert(target.to_string(), monitor.clone());
+    tagging::add_tags(target, [MONITORED_TAG.to_string()]);
+    Ok(monitor)
+}
+
+/// Stop monitoring `target`. Returns whether it was monitored.
+pub fn remove(target: &str) -> bool {
+    tagging::remove_tag(target, MONITORED_TAG);
+    MONITORS.lock().unwrap().remove(target).is_some()
+}
+
+pub fn list() -> Vec<Monitor> {
+    MONITORS.lock().unwrap().values().cloned().collect()
+}
+
+/// Spawn the scheduler that runs due monitors through the normal
+/// `tools/call` pipeline (policy, throttling, jobs, tagging all apply).
+/// Scheduled scans do not count as activity, so monitors alone never keep
+/// the server awake. Nothing runs while the server is idle-suspended;
+/// monitors that came due meanwhile run on the first tick after it resumes.
+pub fn spawn_scheduler(registry: Arc<ToolRegistry>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(TICK);
+        loop {
+            ticker.tick().await;
+            if idle::is_suspended() {
+                continue;
+            }
+            for monitor in take_due() {
+                tokio::spawn(run_once(registry.clone(), monitor));
+            }
+        }
+    });
+}
+
+/// Monitors whose interval has elapsed, marked as run so the next tick does
+/// not start them again while they are still scanning.
+fn take_due() -> Vec<Monitor> {
+    let now = Utc::now();
+    let mut monitors = MONITORS.lock().unwrap();
+    monitors
+        .values_mut()
+        .filter(|m| {
+            m.last_run
+                .is_none_or(|t| (now - t).num_minutes() >= m.interval_minutes as i64)
+        })
+        .map(|m| {
+            m.last_run = Some(now);
+            m.clone()
+        })
+        .collect()
+}
+
+async fn run_once(registry: Arc<ToolRegistry>, monitor: Monitor) {
+    let mut input = monitor.arguments.clone();
+    input.insert("target".into(), json!(monitor.target));
+    let req = RpcRequest {
+        id: Some(json!("monitor")),
+        method: "tools/call".to_string(),
+        params: json!({ "name": monitor.tool, "input": input }),
+    };
+
+    let resp = handle_scheduled_request(registry, json!("monitor"), req).await;
+    let outcome = match (resp.result, resp.error) {
+        (Some(result), _) => Ok(result.get("output").cloned().unwrap_or(Value::Null)),
+        (None, Some(err)) => Err(err.message),
+        (None, None) => Err("empty response".to_string()),
+    };
+
+    let delta = {
+        let mut monitors = MONITORS.lock().unwrap();
+        // Removed while scanning: nothing to record.
+        let Some(current) = monitors.get_mut(&monitor.target) else {
+            return;
+        };
+        match outcome {
+            Err(err) => {
+                eprintln!("monitor scan of {} failed: {err}", monitor.target);
+                current.last_error = Some(err);
+                None
+            }
+            Ok(output) => {
+                current.last_error = None;
+                let observed = tagging::observe(&output);
+                let next = Baseline {
+                    open_ports: observed.open_ports,
+                    services: observed.services,
+                    scanned_at: Some(Utc::now()),
+                };
+                let delta = current.baseline.as_ref().and_then(|prev| diff(prev, &next));
+                current.baseline = Some(next);
+                if delta.is_some() {
+                    current.last_change = delta.clone();
+                }
+                delta
+            }
+        }
+    };
+
+    if let Some(delta) = delta
+        && let Err(err) = notify(&monitor, &delta).await
+    {
+        eprintln!("failed to deliver change notification for {}: {err}", monitor.target);
+    }
+}
+
+fn diff(prev: &Baseline, next: &Baseline) -> Option<Delta> {
+    let delta = Delta {
+        opened_ports: next.open_ports.difference(&prev.open_ports).copied().collect(),
+        closed_ports: prev.open_ports.difference(&next.open_ports).copied().collect(),
+        new_services: next.services.difference(&prev.services).cloned().collect(),
+        gone_services: prev.services.difference(&next.services).cloned().collect(),
+        detected_at: Utc::now(),
+    };
+    let unchanged = delta.opened_ports.is_empty()
+        && delta.closed_ports.is_empty()
+        && delta.new_services.is_empty()
+        && delta.gone_services.is_empty();
+    (!unchanged).then_some(delta)
+}
+
+/// POST the change to the configured webhook. The payload's `text` field
+/// makes it a valid Slack incoming-webhook message; the structured fields
+/// are there for generic receivers.
+async fn notify(monitor: &Monitor, delta: &Delta) -> Result<()> {
+    let Some(url) = config::current().monitor_webhook.clone() else {
+        eprintln!("change detected on {} but no --monitor-webhook is configured", monitor.target);
+        return Ok(());
+    };
+
+    let mut parts = Vec::new();
+    let ports = |ps: &[u16]| ps.iter().map(u16::to_string).collect::<Vec<_>>().join(", ");
+    if !delta.opened_ports.is_empty() {
+        parts.push(format!("opened ports {}", ports(&delta.opened_ports)));
+    }
+    if !delta.closed_ports.is_empty() {
+        parts.push(format!("closed ports {}", ports(&delta.closed_ports)));
+    }
+    if !delta.new_services.is_empty() {
+        parts.push(format!("new services {}", delta.new_services.join(", ")));
+    }
+    if !delta.gone_services.is_empty() {
+        parts.push(format!("gone services {}", delta.gone_services.join(", ")));
+    }
+    let text = format!(
+        "hacker_agent: {} changed ({}): {}",
+        monitor.target,
+        monitor.tool,
+        parts.join("; ")
+    );
+
+    api::ensure_allowed(&url)?;
+    api::http_client()
+        .post(&url)
+        .json(&json!({
+            "text": text,
+            "target": monitor.target,
+            "tool": monitor.tool,
+            "delta": delta,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+
+use super::signatures::{self, Probe, ServiceMatch};
+use crate::partial;
+
+/// Longest banner kept in results.
+const MAX_BANNER: usize = 256;
+
+/// Most ports probed at once; keeps a long port list from opening thousands
+/// of sockets against the target (and exhausting our file descriptors).
+const MAX_PARALLEL_GRABS: usize = 32;
+
+/// What the banner grabber learned about one open port.
+#[derive(Debug, Clone, Serialize)]
+pub struct BannerResult {
+    pub port: u16,
+    pub banner: Option<String>,
+    #[serde(flatten)]
+    pub service: Option<ServiceMatch>,
+}
+
+/// Grab banners from up to `MAX_PARALLEL_GRABS` ports at a time, sorted by
+/// port. Results collected so far are reported as partial output in case the
+/// call times out; dropping the future aborts the probes still running.
+pub async fn grab_all(ip: IpAddr, ports: &[u16], timeout: Duration) -> Vec<BannerResult> {
+    let mut pending = ports.iter().copied();
+    let mut tasks = JoinSet::new();
+    for port in pending.by_ref().take(MAX_PARALLEL_GRABS) {
+        tasks.spawn(grab(SocketAddr::new(ip, port), timeout));
+    }
+
+    let mut results = Vec::with_capacity(ports.len());
+    while let Some(joined) = tasks.join_next().await {
+        if let Some(port) = pending.next() {
+            tasks.spawn(grab(SocketAddr::new(ip, port), timeout));
+        }
+        if let Ok(result) = joined {
+            let at = results.partition_point(|r: &BannerResult| r.port < result.port);
+            results.insert(at, result);
+            partial::report(json!({ "services": results }));
+        }
+    }
+    results
+}
+
+/// Connect to `addr` and run each probe in turn (fresh connection per
+/// probe) until the signature engine recognises the reply. Falls back to the
+/// first non-empty reply as an unidentified banner.
+pub async fn grab(addr: SocketAddr, timeout: Duration) -> BannerResult {
+    let mut first_banner = None;
+
+    for probe in Probe::ALL {
+        let Some(reply) = probe_once(addr, probe, timeout).await else {
+            continue;
+        };
+        if let Some(service) = signatures::identify(probe, &reply) {
+            return BannerResult {
+                port: addr.port(),
+                banner: Some(printable(&reply)),
+                service: Some(service),
+            };
+        }
+        first_banner.get_or_insert_with(|| printable(&reply));
+    }
+
+    BannerResult {
+        port: addr.port(),
+        banner: first_banner,
+        service: None,
+    }
+}
+
+async fn probe_once(addr: SocketAddr, probe: Probe, timeout: Duration) -> Option<Vec<u8>> {
+    let mut stream = tokio::time::timeout(timeout, TcpStream::connect(addr)).await.ok()?.ok()?;
+    if !probe.payload().is_empty() {
+        stream.write_all(probe.payload()).await.ok()?;
+    }
+
+    let mut buf = vec![0u8; 4096];
+    let n = tokio::time::timeout(timeout, stream.read(&mut buf)).await.ok()?.ok()?;
+    (n > 0).then(|| {
+        buf.truncate(n);
+        buf
+    })
+}
+
+/// Banner as text, control characters escaped and length capped.
+fn printable(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_BANNER)])
+        .escape_debug()
+        .to_string()
+}
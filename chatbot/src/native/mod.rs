@@ -0,0 +1,47 @@
+pub mod banner;
+pub mod signatures;
+pub mod syn_scan;
+
+use anyhow::Result;
+
+/// Roughly nmap's top 100 TCP ports, used when no port list is given.
+pub const TOP_PORTS: &[u16] = &[
+    7, 9, 13, 21, 22, 23, 25, 26, 37, 53, 79, 80, 81, 88, 106, 110, 111, 113, 119, 135, 139, 143,
+    144, 179, 199, 389, 427, 443, 444, 445, 465, 513, 514, 515, 543, 544, 548, 554, 587, 631, 646,
+    873, 990, 993, 995, 1025, 1026, 1027, 1028, 1029, 1110, 1433, 1720, 1723, 1755, 1900, 2000,
+    2001, 2049, 2121, 2717, 3000, 3128, 3306, 3389, 3986, 4899, 5000, 5009, 5051, 5060, 5101,
+    5190, 5357, 5432, 5631, 5666, 5800, 5900, 6000, 6001, 6646, 7070, 8000, 8008, 8009, 8080,
+    8081, 8443, 8888, 9100, 9999, 10000, 32768, 49152, 49153, 49154, 49155, 49156, 49157,
+];
+
+/// Parse an nmap-style TCP port list (`22,80,8000-8100`) into sorted,
+/// de-duplicated port numbers.
+pub fn parse_ports(spec: &str) -> Result<Vec<u16>> {
+    let mut ports = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (lo, hi) = match part.split_once('-') {
+            Some((lo, hi)) => (parse_port(lo)?, parse_port(hi)?),
+            None => {
+                let p = parse_port(part)?;
+                (p, p)
+            }
+        };
+        if lo > hi {
+            anyhow::bail!("invalid port range `{part}`");
+        }
+        ports.extend(lo..=hi);
+    }
+    ports.sort_unstable();
+    ports.dedup();
+    if ports.is_empty() {
+        anyhow::bail!("port list `{spec}` is empty");
+    }
+    Ok(ports)
+}
+
+fn parse_port(raw: &str) -> Result<u16> {
+    match raw.trim().parse::<u16>() {
+        Ok(p) if p > 0 => Ok(p),
+        _ => anyhow::bail!("invalid port `{raw}`"),
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::OnceLock;
+
+use regex::bytes::Regex;
+use serde::Serialize;
+
+/// Probe sent to a port before matching its reply, mirroring the probes in
+/// nmap-service-probes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Probe {
+    /// Send nothing; wait for the server to speak first (SSH, FTP, SMTP, ...).
+    Null,
+    /// `GET / HTTP/1.0`.
+    HttpGet,
+}
+
+impl Probe {
+    /// Probes in the order they are tried.
+    pub const ALL: [Probe; 2] = [Probe::Null, Probe::HttpGet];
+
+    pub fn payload(self) -> &'static [u8] {
+        match self {
+            Probe::Null => b"",
+            Probe::HttpGet => b"GET / HTTP/1.0\r\n\r\n",
+        }
+    }
+}
+
+/// One signature: a probe reply matching `pattern` identifies `service` /
+/// `product`, with the version taken from capture group `version_group`.
+struct Signature {
+    probe: Probe,
+    service: &'static str,
+    product: &'static str,
+    pattern: &'static str,
+    version_group: Option<usize>,
+}
+
+/// Signature database, most specific first within each service. Patterns
+/// are byte regexes (`(?-u)` lets them match binary greetings like MySQL's).
+const SIGNATURES: &[Signature] = &[
+    // --- NULL probe -------------------------------------------------------
+    Signature { probe: Probe::Null, service: "ssh", product: "OpenSSH", pattern: r"^SSH-[\d.]+-OpenSSH[_-]([\w.]+)", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "ssh", product: "Dropbear sshd", pattern: r"^SSH-[\d.]+-dropbear[_-]([\w.]+)", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "ssh", product: "", pattern: r"^SSH-([\d.]+)-", version_group: None },
+    Signature { probe: Probe::Null, service: "ftp", product: "vsftpd", pattern: r"^220[- ][^\r\n]*\(vsFTPd ([\w.]+)\)", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "ftp", product: "ProFTPD", pattern: r"^220[- ][^\r\n]*ProFTPD ([\w.]+)", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "ftp", product: "Pure-FTPd", pattern: r"^220[- ][^\r\n]*Pure-FTPd", version_group: None },
+    Signature { probe: Probe::Null, service: "smtp", product: "Postfix smtpd", pattern: r"^220[- ][^\r\n]*ESMTP Postfix", version_group: None },
+    Signature { probe: Probe::Null, service: "smtp", product: "Exim smtpd", pattern: r"^220[- ][^\r\n]*ESMTP Exim ([\w.]+)", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "smtp", product: "", pattern: r"^220[- ][^\r\n]*E?SMTP", version_group: None },
+    Signature { probe: Probe::Null, service: "ftp", product: "", pattern: r"^220[- ][^\r\n]*FTP", version_group: None },
+    Signature { probe: Probe::Null, service: "pop3", product: "Dovecot pop3d", pattern: r"^\+OK[^\r\n]*Dovecot", version_group: None },
+    Signature { probe: Probe::Null, service: "pop3", product: "", pattern: r"^\+OK", version_group: None },
+    Signature { probe: Probe::Null, service: "imap", product: "Dovecot imapd", pattern: r"^\* OK[^\r\n]*Dovecot", version_group: None },
+    Signature { probe: Probe::Null, service: "imap", product: "", pattern: r"^\* OK", version_group: None },
+    Signature { probe: Probe::Null, service: "mysql", product: "MariaDB", pattern: r"(?s-u)^.\x00\x00\x00\x0a(?:5\.5\.5-)?([\d.]+)-MariaDB", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "mysql", product: "MySQL", pattern: r"(?s-u)^.\x00\x00\x00\x0a([\d.]+[\w.-]*)\x00", version_group: Some(1) },
+    Signature { probe: Probe::Null, service: "vnc", product: "", pattern: r"^RFB (\d{3}\.\d{3})\n", version_group: Some(1) },
+    // --- HTTP GET probe ---------------------------------------------------
+    Signature { probe: Probe::HttpGet, service: "http", product: "Apache httpd", pattern: r"(?is)^HTTP/1\.[01] \d{3}.*?\r\nServer: Apache/([\d.]+)", version_group: Some(1) },
+    Signature { probe: Probe::HttpGet, service: "http", product: "nginx", pattern: r"(?is)^HTTP/1\.[01] \d{3}.*?\r\nServer: nginx/([\d.]+)", version_group: Some(1) },
+    Signature { probe: Probe::HttpGet, service: "http", product: "Microsoft IIS httpd", pattern: r"(?is)^HTTP/1\.[01] \d{3}.*?\r\nServer: Microsoft-IIS/([\d.]+)", version_group: Some(1) },
+    Signature { probe: Probe::HttpGet, service: "http", product: "lighttpd", pattern: r"(?is)^HTTP/1\.[01] \d{3}.*?\r\nServer: lighttpd/([\d.]+)", version_group: Some(1) },
+    Signature { probe: Probe::HttpGet, service: "http", product: "SimpleHTTPServer", pattern: r"(?is)^HTTP/1\.[01] \d{3}.*?\r\nServer: SimpleHTTP/([\d.]+)", version_group: Some(1) },
+    Signature { probe: Probe::HttpGet, service: "http", product: "", pattern: r"^HTTP/1\.[01] \d{3}", version_group: None },
+];
+
+/// A service identified from a probe reply.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceMatch {
+    pub service: String,
+    pub product: Option<String>,
+    pub version: Option<String>,
+    pub probe: Probe,
+}
+
+fn compiled() -> &'static [(usize, Regex)] {
+    static COMPILED: OnceLock<Vec<(usize, Regex)>> = OnceLock::new();
+    COMPILED.get_or_init(|| {
+        SIGNATURES
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i, Regex::new(s.pattern).expect("built-in signature compiles")))
+            .collect()
+    })
+}
+
+/// Identify the service behind `reply`, the bytes received after sending `probe`.
+pub fn identify(probe: Probe, reply: &[u8]) -> Option<ServiceMatch> {
+    compiled()
+        .iter()
+        .filter(|(i, _)| SIGNATURES[*i].probe == probe)
+        .find_map(|(i, re)| {
+            let caps = re.captures(reply)?;
+            let sig = &SIGNATURES[*i];
+            let version = sig
+                .version_group
+                .and_then(|g| caps.get(g))
+                .map(|m| String::from_utf8_lossy(m.as_bytes()).into_owned());
+            Some(ServiceMatch {
+                service: sig.service.to_string(),
+                product: (!sig.product.is_empty()).then(|| sig.product.to_string()),
+                version,
+                probe,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Probe, reply, and the service, product and version it identifies.
+    type Sample = (Probe, &'static [u8], &'static str, &'static str, Option<&'static str>);
+
+    /// One sample reply per signature, in table order.
+    const SAMPLES: &[Sample] = &[
+        (Probe::Null, b"SSH-2.0-OpenSSH_9.2p1 Debian-2\r\n", "ssh", "OpenSSH", Some("9.2p1")),
+        (Probe::Null, b"SSH-2.0-dropbear_2022.83\r\n", "ssh", "Dropbear sshd", Some("2022.83")),
+        (Probe::Null, b"SSH-2.0-Cisco-1.25\r\n", "ssh", "", None),
+        (Probe::Null, b"220 (vsFTPd 3.0.5)\r\n", "ftp", "vsftpd", Some("3.0.5")),
+        (Probe::Null, b"220 ProFTPD 1.3.8 Server (Debian)\r\n", "ftp", "ProFTPD", Some("1.3.8")),
+        (Probe::Null, b"220---------- Welcome to Pure-FTPd [privsep] ----------\r\n", "ftp", "Pure-FTPd", None),
+        (Probe::Null, b"220 mail.example.com ESMTP Postfix (Debian/GNU)\r\n", "smtp", "Postfix smtpd", None),
+        (Probe::Null, b"220 mx.example.com ESMTP Exim 4.96 Mon, 02 Oct 2023\r\n", "smtp", "Exim smtpd", Some("4.96")),
+        (Probe::Null, b"220 relay.example.com ESMTP Sendmail 8.17.1\r\n", "smtp", "", None),
+        (Probe::Null, b"220 FileZilla FTP server ready\r\n", "ftp", "", None),
+        (Probe::Null, b"+OK Dovecot (Ubuntu) ready.\r\n", "pop3", "Dovecot pop3d", None),
+        (Probe::Null, b"+OK POP3 server ready\r\n", "pop3", "", None),
+        (Probe::Null, b"* OK [CAPABILITY IMAP4rev1] Dovecot (Ubuntu) ready.\r\n", "imap", "Dovecot imapd", None),
+        (Probe::Null, b"* OK IMAP4 ready\r\n", "imap", "", None),
+        (Probe::Null, b"\x59\x00\x00\x00\x0a5.5.5-10.6.12-MariaDB-0ubuntu0.22.04.1\x00", "mysql", "MariaDB", Some("10.6.12")),
+        (Probe::Null, b"\x4a\x00\x00\x00\x0a8.0.32\x00\x08\x00\x00\x00", "mysql", "MySQL", Some("8.0.32")),
+        (Probe::Null, b"RFB 003.008\n", "vnc", "", Some("003.008")),
+        (Probe::HttpGet, b"HTTP/1.1 200 OK\r\nDate: Mon, 02 Oct 2023\r\nServer: Apache/2.4.57 (Debian)\r\n\r\n", "http", "Apache httpd", Some("2.4.57")),
+        (Probe::HttpGet, b"HTTP/1.1 301 Moved Permanently\r\nServer: nginx/1.24.0\r\n\r\n", "http", "nginx", Some("1.24.0")),
+        (Probe::HttpGet, b"HTTP/1.1 200 OK\r\nServer: Microsoft-IIS/10.0\r\n\r\n", "http", "Microsoft IIS httpd", Some("10.0")),
+        (Probe::HttpGet, b"HTTP/1.0 200 OK\r\nServer: lighttpd/1.4.69\r\n\r\n", "http", "lighttpd", Some("1.4.69")),
+        (Probe::HttpGet, b"HTTP/1.0 200 OK\r\nServer: SimpleHTTP/0.6 Python/3.11.4\r\n\r\n", "http", "SimpleHTTPServer", Some("0.6")),
+        (Probe::HttpGet, b"HTTP/1.0 404 Not Found\r\nServer: Caddy\r\n\r\n", "http", "", None),
+    ];
+
+    #[test]
+    fn every_signature_identifies_its_sample() {
+        assert_eq!(SAMPLES.len(), SIGNATURES.len(), "one sample per signature");
+        for (sig, (probe, reply, service, product, version)) in SIGNATURES.iter().zip(SAMPLES) {
+            assert_eq!((sig.service, sig.product), (*service, *product), "samples follow the table order");
+            let found = identify(*probe, reply).unwrap_or_else(|| panic!("no match for {}", sig.pattern));
+            assert_eq!(found.service, *service, "{}", sig.pattern);
+            assert_eq!(found.product.as_deref().unwrap_or(""), *product, "{}", sig.pattern);
+            assert_eq!(found.version.as_deref(), *version, "{}", sig.pattern);
+        }
+    }
+
+    #[test]
+    fn replies_only_match_their_probe() {
+        assert!(identify(Probe::HttpGet, b"SSH-2.0-OpenSSH_9.2p1\r\n").is_none());
+        assert!(identify(Probe::Null, b"HTTP/1.1 200 OK\r\n\r\n").is_none());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use pnet_packet::ip::IpNextHeaderProtocols;
+use pnet_packet::tcp::{self, MutableTcpPacket, TcpFlags};
+use pnet_packet::Packet;
+use pnet_transport::TransportChannelType::Layer4;
+use pnet_transport::TransportProtocol::Ipv4;
+use pnet_transport::{tcp_packet_iter, transport_channel};
+use serde::Serialize;
+
+/// Result for one probed port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortState {
+    /// Answered with SYN/ACK.
+    Open,
+    /// Answered with RST.
+    Closed,
+    /// No answer before the timeout.
+    Filtered,
+}
+
+const TCP_HEADER_LEN: usize = 20;
+
+/// Longest the receive loop blocks before checking for cancellation.
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+/// Sets the shared cancellation flag when dropped. The async caller holds
+/// one while the blocking scan runs, so a timed-out or abandoned call stops
+/// sending probes instead of scanning on in the background.
+pub struct CancelOnDrop(pub Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Gap between two probes to stay at `max_rate` packets per second.
+fn probe_interval(max_rate: u32) -> Duration {
+    Duration::from_secs(1) / max_rate.max(1)
+}
+
+/// Whether this process may open raw sockets (root or CAP_NET_RAW).
+pub fn has_raw_socket_privileges() -> bool {
+    transport_channel(64, Layer4(Ipv4(IpNextHeaderProtocols::Tcp))).is_ok()
+}
+
+/// Half-open (SYN) scan of `ports` on an IPv4 `target` using raw sockets.
+/// Every port gets one SYN; unanswered ports get one retry. Probes are sent
+/// at most `max_rate` per second. Blocking — run it on a blocking thread;
+/// it gives up as soon as `cancel` is set.
+pub fn scan(
+    target: Ipv4Addr,
+    ports: &[u16],
+    timeout: Duration,
+    max_rate: u32,
+    cancel: &AtomicBool,
+) -> Result<BTreeMap<u16, PortState>> {
+    let (mut tx, mut rx) = transport_channel(64 * 1024, Layer4(Ipv4(IpNextHeaderProtocols::Tcp)))
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::PermissionDenied => anyhow::anyhow!(
+                "native SYN scan needs raw socket privileges (run as root or grant CAP_NET_RAW)"
+            ),
+            _ => anyhow::anyhow!("failed to open raw socket: {err}"),
+        })?;
+
+    let source = source_addr_for(target)?;
+    // One source port per scan keeps matching replies cheap and unambiguous.
+    let source_port: u16 = 40_000 + (std::process::id() % 20_000) as u16;
+    let mut results: BTreeMap<u16, PortState> =
+        ports.iter().map(|p| (*p, PortState::Filtered)).collect();
+    let interval = probe_interval(max_rate);
+    let cancelled = || -> Result<()> {
+        if cancel.load(Ordering::Relaxed) {
+            anyhow::bail!("native SYN scan of {target} cancelled");
+        }
+        Ok(())
+    };
+
+    for attempt in 0..2 {
+        let pending: Vec<u16> = results
+            .iter()
+            .filter(|(_, s)| **s == PortState::Filtered)
+            .map(|(p, _)| *p)
+            .collect();
+        if pending.is_empty() {
+            break;
+        }
+
+        let mut sequences = HashMap::new();
+        let mut next_send = Instant::now();
+        for port in &pending {
+            cancelled()?;
+            if let Some(wait) = next_send.checked_duration_since(Instant::now()) {
+                std::thread::sleep(wait);
+            }
+            next_send += interval;
+
+            let seq = rand_seq(*port, attempt);
+            sequences.insert(*port, seq);
+            let mut buf = [0u8; TCP_HEADER_LEN];
+            let packet = build_syn(&mut buf, source, target, source_port, *port, seq);
+            tx.send_to(packet, IpAddr::V4(target))?;
+        }
+
+        let deadline = Instant::now() + timeout;
+        let mut iter = tcp_packet_iter(&mut rx);
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            cancelled()?;
+            let Some((reply, from)) = iter.next_with_timeout(remaining.min(CANCEL_POLL))? else {
+                continue;
+            };
+            if from != IpAddr::V4(target) || reply.get_destination() != source_port {
+                continue;
+            }
+            let port = reply.get_source();
+            let Some(seq) = sequences.get(&port) else {
+                continue;
+            };
+            if reply.get_acknowledgement() != seq.wrapping_add(1) {
+                continue;
+            }
+            let flags = reply.get_flags();
+            let state = if flags & (TcpFlags::SYN | TcpFlags::ACK) == TcpFlags::SYN | TcpFlags::ACK {
+                PortState::Open
+            } else if flags & TcpFlags::RST != 0 {
+                PortState::Closed
+            } else {
+                continue;
+            };
+            results.insert(port, state);
+            if results.values().all(|s| *s != PortState::Filtered) {
+                break;
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+fn build_syn<'a>(
+    buf: &'a mut [u8],
+    source: Ipv4Addr,
+    target: Ipv4Addr,
+    source_port: u16,
+    port: u16,
+    seq: u32,
+) -> MutableTcpPacket<'a> {
+    let mut packet = MutableTcpPacket::new(buf).expect("buffer holds a TCP header");
+    packet.set_source(source_port);
+    packet.set_destination(port);
+    packet.set_sequence(seq);
+    packet.set_acknowledgement(0);
+    packet.set_data_offset((TCP_HEADER_LEN / 4) as u8);
+    packet.set_flags(TcpFlags::SYN);
+    packet.set_window(1024);
+    packet.set_urgent_ptr(0);
+    let checksum = tcp::ipv4_checksum(&packet.to_immutable(), &source, &target);
+    packet.set_checksum(checksum);
+    debug_assert_eq!(packet.packet().len(), TCP_HEADER_LEN);
+    packet
+}
+
+/// Local address the kernel would use to reach `target` (needed for the TCP
+/// checksum pseudo-header). A connected UDP socket sends nothing.
+fn source_addr_for(target: Ipv4Addr) -> Result<Ipv4Addr> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.connect((target, 9))?;
+    match socket.local_addr()?.ip() {
+        IpAddr::V4(v4) => Ok(v4),
+        IpAddr::V6(_) => anyhow::bail!("no IPv4 route to {target}"),
+    }
+}
+
+/// Cheap per-probe sequence number; only needs to be unpredictable enough to
+/// match replies, not cryptographically random.
+fn rand_seq(port: u16, attempt: u32) -> u32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    nanos ^ ((port as u32) << 16) ^ attempt.wrapping_mul(0x9e37_79b9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probes_are_spaced_to_the_rate() {
+        assert_eq!(probe_interval(1000), Duration::from_millis(1));
+        assert_eq!(probe_interval(4), Duration::from_millis(250));
+        assert_eq!(probe_interval(0), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn dropping_the_guard_cancels() {
+        let flag = Arc::new(AtomicBool::new(false));
+        drop(CancelOnDrop(flag.clone()));
+        assert!(flag.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,23 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+/// Where a running tool leaves its best result so far. If the tool is cut
+/// off by its timeout, the registry returns this instead of nothing.
+pub type Slot = Arc<Mutex<Option<Value>>>;
+
+tokio::task_local! {
+    static CURRENT: Slot;
+}
+
+/// Run `fut` (a tool's `execute`) with `slot` as its partial-output slot.
+pub async fn scope<F: Future>(slot: Slot, fut: F) -> F::Output {
+    CURRENT.scope(slot, fut).await
+}
+
+/// Replace the current tool's partial output. Tools that build their result
+/// incrementally call this as they go; outside a tool call it does nothing.
+pub fn report(value: Value) {
+    let _ = CURRENT.try_with(|slot| *slot.lock().unwrap() = Some(value));
+}
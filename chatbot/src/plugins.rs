@@ -0,0 +1,296 @@
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{Tool, ToolAnnotations, ToolCategory};
+
+/// Version of the plugin ABI below; plugins built against another version
+/// are refused.
+pub const ABI_VERSION: u32 = 1;
+
+/// Entry points a plugin library exports (`extern "C"`, unmangled):
+///
+/// - `hacker_agent_plugin_abi_version() -> u32`, returning `ABI_VERSION`;
+/// - `hacker_agent_plugin_manifest() -> *mut c_char`, a JSON string
+///   `{ "tools": [ToolManifest, ...] }`;
+/// - `hacker_agent_plugin_call(name, input) -> *mut c_char`, running the
+///   named tool on a JSON input and returning `{ "output": ... }` or
+///   `{ "error": "..." }`. It may be called from several threads at once;
+/// - `hacker_agent_plugin_free(*mut c_char)`, releasing a string returned
+///   by the other two.
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type ManifestFn = unsafe extern "C" fn() -> *mut c_char;
+type CallFn = unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// One tool as a plugin describes it.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct ToolManifest {
+    name: String,
+    description: String,
+    category: ToolCategory,
+    #[serde(default = "any_object")]
+    input_schema: Value,
+    /// Missing hints default to the most cautious ones.
+    #[serde(default)]
+    annotations: ToolAnnotations,
+    /// Input field naming the host(s) the tool reaches; required for
+    /// open-world tools, so the engagement scope applies to them.
+    #[serde(default)]
+    target_field: Option<String>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginManifest {
+    tools: Vec<ToolManifest>,
+}
+
+fn any_object() -> Value {
+    json!({ "type": "object" })
+}
+
+fn parse_manifest(raw: &str) -> Result<Vec<ToolManifest>> {
+    let manifest: PluginManifest = serde_json::from_str(raw)?;
+    if let Some(tool) = manifest.tools.iter().find(|t| t.annotations.open_world_hint && t.target_field.is_none()) {
+        anyhow::bail!("tool `{}` is open-world (openWorldHint) and must name its targetField", tool.name);
+    }
+    Ok(manifest.tools)
+}
+
+/// A loaded plugin library. Never unloaded: its tools stay registered for
+/// the life of the process.
+struct Plugin {
+    path: String,
+    call: CallFn,
+    free: FreeFn,
+    library: libloading::Library,
+}
+
+impl Plugin {
+    fn open(path: &Path) -> Result<Self> {
+        // SAFETY: loading a library runs its initializers; plugins are
+        // trusted code the operator placed in the plugins directory.
+        let library = unsafe { libloading::Library::new(path) }?;
+        // SAFETY: the symbol types are those of the ABI documented above,
+        // and the pointers are only used while `library` is alive.
+        let (abi_version, call, free) = unsafe {
+            let abi_version: AbiVersionFn = *library.get(b"hacker_agent_plugin_abi_version\0")?;
+            let call: CallFn = *library.get(b"hacker_agent_plugin_call\0")?;
+            let free: FreeFn = *library.get(b"hacker_agent_plugin_free\0")?;
+            (abi_version(), call, free)
+        };
+        if abi_version != ABI_VERSION {
+            anyhow::bail!("plugin ABI version {abi_version}, expected {ABI_VERSION}");
+        }
+        Ok(Self {
+            path: path.display().to_string(),
+            call,
+            free,
+            library,
+        })
+    }
+
+    fn manifest(&self) -> Result<Vec<ToolManifest>> {
+        // SAFETY: as in `open`; the library is still loaded.
+        let raw = unsafe {
+            let manifest: ManifestFn = *self.library.get(b"hacker_agent_plugin_manifest\0")?;
+            self.take_string(manifest())?
+        };
+        parse_manifest(&raw).context("invalid plugin manifest")
+    }
+
+    fn call(&self, tool: &str, input: &Value) -> Result<Value> {
+        let name = CString::new(tool)?;
+        let input = CString::new(input.to_string())?;
+        // SAFETY: both arguments are valid NUL-terminated strings that
+        // outlive the call.
+        let raw = unsafe { self.take_string((self.call)(name.as_ptr(), input.as_ptr()))? };
+        let reply: Value = serde_json::from_str(&raw).context("plugin returned invalid JSON")?;
+        if let Some(error) = reply.get("error") {
+            anyhow::bail!("{}", error.as_str().map_or_else(|| error.to_string(), str::to_string));
+        }
+        reply
+            .get("output")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("plugin reply has neither `output` nor `error`"))
+    }
+
+    /// Copy a string the plugin returned and hand it back to be freed.
+    ///
+    /// # Safety
+    /// `ptr` must be null or a NUL-terminated string allocated by the plugin.
+    unsafe fn take_string(&self, ptr: *mut c_char) -> Result<String> {
+        if ptr.is_null() {
+            anyhow::bail!("plugin returned no data");
+        }
+        // SAFETY: guaranteed by the caller.
+        let text = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+        // SAFETY: `ptr` came from this plugin and is freed exactly once.
+        unsafe { (self.free)(ptr) };
+        Ok(text)
+    }
+}
+
+/// A tool implemented by a plugin.
+pub struct PluginTool {
+    plugin: Arc<Plugin>,
+    // Tool names and descriptions are `&'static str`; plugin tools live for
+    // the rest of the process, so their strings are leaked once at load.
+    name: &'static str,
+    description: &'static str,
+    category: ToolCategory,
+    input_schema: Value,
+    annotations: ToolAnnotations,
+    target_field: Option<&'static str>,
+    timeout: Option<Duration>,
+}
+
+#[async_trait]
+impl Tool for PluginTool {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn category(&self) -> ToolCategory {
+        self.category
+    }
+
+    fn input_schema(&self) -> Value {
+        self.input_schema.clone()
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        self.annotations
+    }
+
+    fn target_field(&self) -> Option<&'static str> {
+        self.target_field
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    async fn execute(&self, input: Value) -> Result<Value> {
+        let plugin = self.plugin.clone();
+        let name = self.name;
+        // Plugin code is synchronous; keep it off the async workers.
+        tokio::task::spawn_blocking(move || plugin.call(name, &input))
+            .await?
+            .with_context(|| format!("plugin {}", self.plugin.path))
+    }
+}
+
+/// Load every plugin library in `dir`. A missing directory means no
+/// plugins; a plugin that fails to load is reported and skipped so one bad
+/// library does not take the server down.
+pub fn load_dir(dir: &Path) -> Vec<PluginTool> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries.filter_map(|e| Some(e.ok()?.path())).collect();
+    paths.sort();
+
+    let mut tools = Vec::new();
+    for path in paths {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext == std::env::consts::DLL_EXTENSION => {}
+            Some("wasm") => {
+                eprintln!("skipping plugin {}: WASM plugins are not supported", path.display());
+                continue;
+            }
+            _ => continue,
+        }
+        match load(&path) {
+            Ok(loaded) => tools.extend(loaded),
+            Err(err) => eprintln!("skipping plugin {}: {err:#}", path.display()),
+        }
+    }
+    tools
+}
+
+fn load(path: &Path) -> Result<Vec<PluginTool>> {
+    let plugin = Arc::new(Plugin::open(path)?);
+    let tools = plugin
+        .manifest()?
+        .into_iter()
+        .map(|t| PluginTool {
+            plugin: plugin.clone(),
+            name: Box::leak(t.name.into_boxed_str()),
+            description: Box::leak(t.description.into_boxed_str()),
+            category: t.category,
+            input_schema: t.input_schema,
+            annotations: t.annotations,
+            target_field: t.target_field.map(|f| &*Box::leak(f.into_boxed_str())),
+            timeout: t.timeout_secs.map(Duration::from_secs),
+        })
+        .collect();
+    Ok(tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_defaults_to_cautious_annotations() {
+        let tools = parse_manifest(
+            r#"{ "tools": [
+                { "name": "whois_lookup", "description": "Looks up a domain.", "category": "osint",
+                  "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false },
+                  "timeoutSecs": 30 },
+                { "name": "legacy_probe", "description": "Probes a host.", "category": "recon", "targetField": "host" }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(tools.len(), 2);
+        assert!(tools[0].annotations.read_only_hint);
+        assert_eq!(tools[0].timeout_secs, Some(30));
+        assert_eq!(tools[1].input_schema, any_object());
+        assert!(tools[1].annotations.destructive_hint && tools[1].annotations.open_world_hint);
+        assert_eq!(tools[1].target_field.as_deref(), Some("host"));
+    }
+
+    #[test]
+    fn manifest_requires_open_world_tools_to_name_their_target() {
+        let untargeted = r#"{ "tools": [{ "name": "probe", "description": "Probes a host.", "category": "recon" }] }"#;
+        let err = parse_manifest(untargeted).unwrap_err();
+        assert!(err.to_string().contains("must name its targetField"), "{err}");
+    }
+
+    #[test]
+    fn manifest_rejects_unknown_categories_and_fields() {
+        let unknown_category = r#"{ "tools": [{ "name": "x", "description": "x", "category": "exploit" }] }"#;
+        assert!(parse_manifest(unknown_category).is_err());
+        let unknown_field = r#"{ "tools": [{ "name": "x", "description": "x", "category": "recon", "sudo": true }] }"#;
+        assert!(parse_manifest(unknown_field).is_err());
+    }
+
+    #[test]
+    fn broken_and_foreign_files_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("hacker_agent_plugins_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("broken.{}", std::env::consts::DLL_EXTENSION)), b"not a library").unwrap();
+        std::fs::write(dir.join("tool.wasm"), b"\0asm").unwrap();
+        std::fs::write(dir.join("README.txt"), b"notes").unwrap();
+        assert!(load_dir(&dir).is_empty());
+        assert!(load(&dir.join(format!("broken.{}", std::env::consts::DLL_EXTENSION))).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(load_dir(&dir).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{config, targets};
+
+/// Slowest-first ordering of nmap timing templates.
+pub const TIMINGS: [&str; 6] = ["T0", "T1", "T2", "T3", "T4", "T5"];
+
+/// Fastest timing template allowed against production-tagged targets.
+const PRODUCTION_MAX_TIMING: &str = "T3";
+
+/// NSE script categories that are never run, regardless of target.
+const BLOCKED_SCRIPT_CATEGORIES: &[&str] = &["dos"];
+
+/// NSE script categories that are stripped when scanning production targets.
+const PRODUCTION_BLOCKED_SCRIPT_CATEGORIES: &[&str] = &["exploit", "intrusive", "brute"];
+
+/// A single rewrite applied to a tool call to bring it within policy.
+#[derive(Debug, Serialize)]
+pub struct Substitution {
+    pub field: String,
+    pub requested: Value,
+    pub applied: Value,
+    pub reason: String,
+}
+
+/// Rewrite a `tools/call` input that exceeds policy into the nearest compliant
+/// configuration. Returns the (possibly modified) input together with every
+/// substitution made, so the caller can report them back to the client
+/// instead of failing or silently obeying.
+pub fn enforce(input: Value) -> (Value, Vec<Substitution>) {
+    enforce_for(input, &config::current().production_targets)
+}
+
+/// `enforce` against an explicit list of production targets.
+fn enforce_for(input: Value, production_targets: &[String]) -> (Value, Vec<Substitution>) {
+    let mut input = input;
+    let mut substitutions = Vec::new();
+
+    let Some(obj) = input.as_object_mut() else {
+        return (input, substitutions);
+    };
+
+    let production = targets::TARGET_FIELDS
+        .iter()
+        .filter_map(|k| obj.get(*k).and_then(|v| v.as_str()))
+        .any(|t| is_production_target(t, production_targets));
+
+    // 1. Cap the timing template on production targets.
+    if production
+        && let Some(requested) = obj.get("timing").and_then(|v| v.as_str())
+        && timing_rank(requested) > timing_rank(PRODUCTION_MAX_TIMING)
+    {
+        substitutions.push(Substitution {
+            field: "timing".into(),
+            requested: json!(requested),
+            applied: json!(PRODUCTION_MAX_TIMING),
+            reason: format!(
+                "timing above {PRODUCTION_MAX_TIMING} is not allowed against production-tagged targets"
+            ),
+        });
+        obj.insert("timing".into(), json!(PRODUCTION_MAX_TIMING));
+    }
+
+    // 2. Strip disallowed NSE script categories.
+    if let Some(requested) = obj.get("scripts").and_then(|v| v.as_str()).map(str::to_string) {
+        let (kept, dropped): (Vec<&str>, Vec<&str>) = requested
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .partition(|s| !is_blocked_script(s, production));
+
+        if !dropped.is_empty() {
+            let applied = if kept.is_empty() {
+                "default".to_string()
+            } else {
+                kept.join(",")
+            };
+            substitutions.push(Substitution {
+                field: "scripts".into(),
+                requested: json!(requested),
+                applied: json!(applied),
+                reason: format!(
+                    "script categories not permitted{}: {}",
+                    if production { " against production-tagged targets" } else { "" },
+                    dropped.join(",")
+                ),
+            });
+            obj.insert("scripts".into(), json!(applied));
+        }
+    }
+
+    (input, substitutions)
+}
+
+/// Whether any entry of `target` touches a production target: overlaps its
+/// addresses (`10.0.0.0/24` covers `10.0.0.1`) or is it or a subdomain of it,
+/// as `EngagementScope` matches the denylist. Names compare in their ASCII
+/// form, so `bücher.example` matches `xn--bcher-kva.example`.
+fn is_production_target(target: &str, production_targets: &[String]) -> bool {
+    let production: Vec<targets::Scope> =
+        production_targets.iter().flat_map(|p| targets::entries(p)).map(|(_, scope)| scope).collect();
+    targets::entries(target)
+        .iter()
+        .any(|(_, scope)| production.iter().any(|p| p.overlaps(scope) || p.contains(scope)))
+}
+
+fn is_blocked_script(script: &str, production: bool) -> bool {
+    let script = script.to_ascii_lowercase();
+    BLOCKED_SCRIPT_CATEGORIES.contains(&script.as_str())
+        || (production && PRODUCTION_BLOCKED_SCRIPT_CATEGORIES.contains(&script.as_str()))
+}
+
+pub fn timing_rank(timing: &str) -> usize {
+    TIMINGS
+        .iter()
+        .position(|t| t.eq_ignore_ascii_case(timing))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn production() -> Vec<String> {
+        vec!["prod.example".to_string(), "10.0.0.1".to_string()]
+    }
+
+    #[test]
+    fn production_targets_get_capped_timing_and_no_intrusive_scripts() {
+        let input = json!({ "target": "PROD.example", "timing": "T5", "scripts": "vuln,brute,default" });
+        let (out, subs) = enforce_for(input, &production());
+        assert_eq!(out["timing"], "T3");
+        assert_eq!(out["scripts"], "vuln,default");
+        let fields: Vec<_> = subs.iter().map(|s| s.field.as_str()).collect();
+        assert_eq!(fields, ["timing", "scripts"]);
+    }
+
+    #[test]
+    fn other_targets_keep_timing_but_never_run_dos() {
+        let input = json!({ "target": "10.0.0.2", "timing": "T5", "scripts": "DoS,brute" });
+        let (out, subs) = enforce_for(input, &production());
+        assert_eq!(out["timing"], "T5");
+        assert_eq!(out["scripts"], "brute");
+        assert_eq!(subs.len(), 1);
+    }
+
+    #[test]
+    fn dropping_every_script_falls_back_to_default() {
+        let (out, _) = enforce_for(json!({ "subnet": "10.0.0.1", "scripts": "exploit" }), &production());
+        assert_eq!(out["scripts"], "default");
+    }
+
+    #[test]
+    fn ranges_and_lists_covering_a_production_host_are_production() {
+        for target in ["10.0.0.0/24", "a.example,prod.example", "a.example 10.0.0.1", "www.prod.example"] {
+            let input = json!({ "target": target, "timing": "T5", "scripts": "exploit,vuln" });
+            let (out, _) = enforce_for(input, &production());
+            assert_eq!((out["timing"].as_str(), out["scripts"].as_str()), (Some("T3"), Some("vuln")), "{target}");
+        }
+        assert!(!is_production_target("10.0.1.0/24,a.example", &production()));
+    }
+
+    #[test]
+    fn compliant_input_is_untouched() {
+        let input = json!({ "target": "prod.example", "timing": "T2" });
+        let (out, subs) = enforce_for(input.clone(), &production());
+        assert_eq!(out, input);
+        assert!(subs.is_empty());
+    }
+
+    #[test]
+    fn timings_rank_slowest_first() {
+        assert!(timing_rank("t1") < timing_rank("T4"));
+        assert_eq!(timing_rank("bogus"), 0);
+    }
+}
@@ -0,0 +1,60 @@
+use std::future::Future;
+
+use serde_json::{json, Value};
+
+use crate::session;
+
+tokio::task_local! {
+    static TOKEN: Option<Value>;
+}
+
+/// Run `fut` (a `tools/call`) with the client's `_meta.progressToken`, if it
+/// sent one.
+pub async fn scope<F: Future>(token: Option<Value>, fut: F) -> F::Output {
+    TOKEN.scope(token, fut).await
+}
+
+/// Tell the client how far the current tool call has got, as a
+/// `notifications/progress`. Only clients that sent a progress token get
+/// one; otherwise, and outside a tool call, it does nothing.
+pub fn report(progress: f64, total: Option<f64>, message: &str) {
+    let Some(token) = TOKEN.try_with(|t| t.clone()).ok().flatten() else {
+        return;
+    };
+    let Some(session) = session::current() else {
+        return;
+    };
+    let mut params = json!({ "progressToken": token, "progress": progress, "message": message });
+    if let Some(total) = total {
+        params["total"] = json!(total);
+    }
+    // Progress is best effort; a closed session has nobody to tell.
+    let _ = session.notify("notifications/progress", params);
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::session::Session;
+
+    #[tokio::test]
+    async fn only_calls_with_a_token_get_notifications() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let session = Session::new("test", tx);
+        session::scope(session, async {
+            scope(None, async { report(10.0, Some(100.0), "quiet") }).await;
+            scope(Some(json!("tok-1")), async { report(42.0, Some(100.0), "Running") }).await;
+        })
+        .await;
+
+        let sent: Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(sent["method"], "notifications/progress");
+        assert_eq!(
+            sent["params"],
+            json!({ "progressToken": "tok-1", "progress": 42.0, "total": 100.0, "message": "Running" })
+        );
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -28,9 +28,54 @@ pub struct PromptGetParams {
     pub arguments: Value,
 }
 
-/// Return all built-in prompts exposed by this MCP server.
-pub fn list_prompts() -> Vec<PromptDef> {
-    vec![PromptDef {
+/// Parameters for `prompts/list`.
+#[derive(Debug, Default, Deserialize)]
+pub struct PromptListParams {
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// One page of `prompts/list` results.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptPage {
+    pub prompts: Vec<PromptDef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Prompts returned per `prompts/list` page.
+const PAGE_SIZE: usize = 20;
+
+/// Builds one prompt definition on demand.
+type PromptBuilder = fn() -> PromptDef;
+
+/// Built-in prompts by name. Definitions are only constructed for the page
+/// (or the single prompt) actually requested.
+const PROMPTS: &[(&str, PromptBuilder)] =
+    &[("explain_openvas_scan_configs", explain_openvas_scan_configs)];
+
+/// Return one page of the built-in prompts exposed by this MCP server. The
+/// cursor is opaque to clients; it encodes the offset of the next page.
+pub fn list_prompts(cursor: Option<&str>) -> Result<PromptPage> {
+    let start = match cursor {
+        None => 0,
+        Some(c) => c
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n <= PROMPTS.len())
+            .ok_or_else(|| anyhow::anyhow!("invalid cursor `{c}`"))?,
+    };
+    let end = (start + PAGE_SIZE).min(PROMPTS.len());
+
+    Ok(PromptPage {
+        prompts: PROMPTS[start..end].iter().map(|(_, build)| build()).collect(),
+        next_cursor: (end < PROMPTS.len()).then(|| end.to_string()),
+    })
+}
+
+fn explain_openvas_scan_configs() -> PromptDef {
+    PromptDef {
         name: "explain_openvas_scan_configs".to_string(),
         description:
             "Explain OpenVAS scan configurations returned by openvas_list_scan_configs in a structured, human-readable way."
@@ -53,15 +98,15 @@ pub fn list_prompts() -> Vec<PromptDef> {
                 schema: json!({ "type": "string" }),
             },
         ],
-    }]
+    }
 }
 
 /// Look up a prompt by name and return a full prompt object including messages.
 pub fn get_prompt(name: &str, _arguments: Value) -> Result<Value> {
-    let prompts = list_prompts();
-    let def = prompts
-        .into_iter()
-        .find(|p| p.name == name)
+    let def = PROMPTS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, build)| build())
         .ok_or_else(|| anyhow::anyhow!(format!("Unknown prompt: {name}")))?;
 
     // Messages follow the MCP prompt message shape: role + content array.
@@ -0,0 +1,386 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::openvas::{ReportResult, SeverityCounts};
+
+/// What an earlier nmap scan saw on a host, shown next to its findings.
+#[derive(Debug, Clone)]
+pub struct HostScan {
+    pub target: String,
+    pub scanned_at: DateTime<Utc>,
+    pub open_ports: BTreeSet<u16>,
+    pub services: BTreeSet<String>,
+}
+
+/// One fix and the findings it addresses.
+struct Remediation<'a> {
+    solution: &'a str,
+    name: &'a str,
+    severity: f64,
+    hosts: BTreeSet<&'a str>,
+}
+
+/// A report laid out once and written as Markdown or HTML.
+struct Document<'a> {
+    report_id: &'a str,
+    generated_at: DateTime<Utc>,
+    counts: SeverityCounts,
+    /// Host → its findings, most severe first, and what nmap saw.
+    hosts: BTreeMap<&'a str, (Vec<&'a ReportResult>, Option<&'a HostScan>)>,
+    /// Most severe first.
+    remediations: Vec<Remediation<'a>>,
+}
+
+fn count(counts: &mut SeverityCounts, threat: &str) {
+    match threat {
+        "High" => counts.high += 1,
+        "Medium" => counts.medium += 1,
+        "Low" => counts.low += 1,
+        "Log" => counts.log += 1,
+        _ => counts.false_positive += 1,
+    }
+}
+
+fn by_severity(a: f64, b: f64) -> std::cmp::Ordering {
+    b.total_cmp(&a)
+}
+
+fn layout<'a>(
+    report_id: &'a str,
+    results: &'a [ReportResult],
+    scans: &'a [HostScan],
+    generated_at: DateTime<Utc>,
+) -> Document<'a> {
+    let mut counts = SeverityCounts::default();
+    let mut hosts: BTreeMap<&str, (Vec<&ReportResult>, Option<&HostScan>)> = BTreeMap::new();
+    let mut remediations: BTreeMap<&str, Remediation> = BTreeMap::new();
+    for r in results {
+        count(&mut counts, &r.threat);
+        hosts.entry(&r.host).or_default().0.push(r);
+        if let Some(solution) = r.solution.as_deref() {
+            let fix = remediations.entry(&r.nvt_oid).or_insert_with(|| Remediation {
+                solution,
+                name: &r.name,
+                severity: r.severity,
+                hosts: BTreeSet::new(),
+            });
+            fix.severity = fix.severity.max(r.severity);
+            fix.hosts.insert(&r.host);
+        }
+    }
+    for scan in scans {
+        hosts.entry(&scan.target).or_default().1 = Some(scan);
+    }
+    for (findings, _) in hosts.values_mut() {
+        findings.sort_by(|a, b| by_severity(a.severity, b.severity));
+    }
+    let mut remediations: Vec<Remediation> = remediations.into_values().collect();
+    remediations.sort_by(|a, b| by_severity(a.severity, b.severity));
+    Document {
+        report_id,
+        generated_at,
+        counts,
+        hosts,
+        remediations,
+    }
+}
+
+fn host_counts(findings: &[&ReportResult]) -> SeverityCounts {
+    let mut counts = SeverityCounts::default();
+    for r in findings {
+        count(&mut counts, &r.threat);
+    }
+    counts
+}
+
+fn highest(findings: &[&ReportResult]) -> f64 {
+    findings.iter().map(|r| r.severity).fold(0.0, f64::max)
+}
+
+fn ports(scan: &HostScan) -> String {
+    scan.open_ports.iter().map(u16::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn services(scan: &HostScan) -> String {
+    scan.services.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+/// Text safe inside a Markdown table cell or heading.
+fn md(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// `results` (and what `scans` saw) as a Markdown document: a summary
+/// table, a section per host and the remediation notes.
+pub fn markdown(report_id: &str, results: &[ReportResult], scans: &[HostScan], generated_at: DateTime<Utc>) -> String {
+    let doc = layout(report_id, results, scans, generated_at);
+    let mut out = String::new();
+    let c = &doc.counts;
+    // Writing to a String cannot fail.
+    let _ = writeln!(out, "# Vulnerability report {}\n", md(doc.report_id));
+    let _ = writeln!(out, "Generated {} from OpenVAS report `{}`.\n", doc.generated_at.to_rfc3339(), md(doc.report_id));
+    let _ = writeln!(out, "## Summary\n");
+    let _ = writeln!(out, "| Hosts | High | Medium | Low | Log | False positive |");
+    let _ = writeln!(out, "|---:|---:|---:|---:|---:|---:|");
+    let _ = writeln!(
+        out,
+        "| {} | {} | {} | {} | {} | {} |\n",
+        doc.hosts.len(),
+        c.high,
+        c.medium,
+        c.low,
+        c.log,
+        c.false_positive
+    );
+    if !doc.hosts.is_empty() {
+        let _ = writeln!(out, "| Host | Highest severity | High | Medium | Low | Open ports |");
+        let _ = writeln!(out, "|---|---:|---:|---:|---:|---|");
+        for (host, (findings, scan)) in &doc.hosts {
+            let hc = host_counts(findings);
+            let open = scan.map(ports).unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "| {} | {:.1} | {} | {} | {} | {} |",
+                md(host),
+                highest(findings),
+                hc.high,
+                hc.medium,
+                hc.low,
+                open
+            );
+        }
+        out.push('\n');
+    }
+
+    for (host, (findings, scan)) in &doc.hosts {
+        let _ = writeln!(out, "## {}\n", md(host));
+        if let Some(scan) = scan {
+            let _ = writeln!(out, "nmap, {}: open ports {}.", scan.scanned_at.to_rfc3339(), ports(scan));
+            if !scan.services.is_empty() {
+                let _ = writeln!(out, "Services: {}.", md(&services(scan)));
+            }
+            out.push('\n');
+        }
+        if findings.is_empty() {
+            let _ = writeln!(out, "No findings.\n");
+        }
+        for r in findings {
+            let _ = writeln!(out, "### {} ({:.1}, {})\n", md(&r.name), r.severity, md(&r.threat));
+            let _ = writeln!(out, "- Port: {}", md(&r.port));
+            let _ = writeln!(out, "- NVT: `{}`", r.nvt_oid);
+            if let Some(qod) = r.qod {
+                let _ = writeln!(out, "- Quality of detection: {qod}%");
+            }
+            if !r.cves.is_empty() {
+                let _ = writeln!(out, "- CVEs: {}", r.cves.join(", "));
+            }
+            if let Some(solution) = &r.solution {
+                let _ = writeln!(out, "- Remediation: {}", md(solution));
+            }
+            out.push('\n');
+        }
+    }
+
+    if !doc.remediations.is_empty() {
+        let _ = writeln!(out, "## Remediation notes\n");
+        for fix in &doc.remediations {
+            let hosts = fix.hosts.iter().copied().collect::<Vec<_>>().join(", ");
+            let _ = writeln!(out, "- **{}** ({:.1}; {}): {}", md(fix.name), fix.severity, md(&hosts), md(fix.solution));
+        }
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn threat_class(threat: &str) -> &'static str {
+    match threat {
+        "High" => "high",
+        "Medium" => "medium",
+        "Low" => "low",
+        _ => "log",
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#222}\
+table{border-collapse:collapse;margin:1rem 0}th,td{border:1px solid #ccc;padding:.3rem .6rem;text-align:left}\
+.high{color:#b00020}.medium{color:#c77700}.low{color:#1565c0}.log{color:#666}";
+
+/// The same document as `markdown`, as a standalone HTML page.
+pub fn html(report_id: &str, results: &[ReportResult], scans: &[HostScan], generated_at: DateTime<Utc>) -> String {
+    let doc = layout(report_id, results, scans, generated_at);
+    let id = html_escape(doc.report_id);
+    let c = &doc.counts;
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Vulnerability report {id}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n"
+    );
+    let _ = writeln!(out, "<h1>Vulnerability report {id}</h1>");
+    let _ = writeln!(
+        out,
+        "<p>Generated {} from OpenVAS report <code>{id}</code>.</p>",
+        doc.generated_at.to_rfc3339()
+    );
+    let _ = writeln!(out, "<h2>Summary</h2>");
+    let _ = writeln!(
+        out,
+        "<table><tr><th>Hosts</th><th>High</th><th>Medium</th><th>Low</th><th>Log</th><th>False positive</th></tr>\
+         <tr><td>{}</td><td class=\"high\">{}</td><td class=\"medium\">{}</td><td class=\"low\">{}</td>\
+         <td>{}</td><td>{}</td></tr></table>",
+        doc.hosts.len(),
+        c.high,
+        c.medium,
+        c.low,
+        c.log,
+        c.false_positive
+    );
+    if !doc.hosts.is_empty() {
+        out.push_str(
+            "<table><tr><th>Host</th><th>Highest severity</th><th>High</th><th>Medium</th><th>Low</th>\
+             <th>Open ports</th></tr>\n",
+        );
+        for (host, (findings, scan)) in &doc.hosts {
+            let hc = host_counts(findings);
+            let _ = writeln!(
+                out,
+                "<tr><td><a href=\"#host-{0}\">{0}</a></td><td>{1:.1}</td><td>{2}</td><td>{3}</td><td>{4}</td>\
+                 <td>{5}</td></tr>",
+                html_escape(host),
+                highest(findings),
+                hc.high,
+                hc.medium,
+                hc.low,
+                scan.map(ports).unwrap_or_default()
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    for (host, (findings, scan)) in &doc.hosts {
+        let host = html_escape(host);
+        let _ = writeln!(out, "<h2 id=\"host-{host}\">{host}</h2>");
+        if let Some(scan) = scan {
+            let _ = write!(out, "<p>nmap, {}: open ports {}.", scan.scanned_at.to_rfc3339(), ports(scan));
+            if !scan.services.is_empty() {
+                let _ = write!(out, " Services: {}.", html_escape(&services(scan)));
+            }
+            out.push_str("</p>\n");
+        }
+        if findings.is_empty() {
+            out.push_str("<p>No findings.</p>\n");
+        }
+        for r in findings {
+            let _ = writeln!(
+                out,
+                "<h3 class=\"{}\">{} ({:.1}, {})</h3>\n<ul>\n<li>Port: {}</li>\n<li>NVT: <code>{}</code></li>",
+                threat_class(&r.threat),
+                html_escape(&r.name),
+                r.severity,
+                html_escape(&r.threat),
+                html_escape(&r.port),
+                html_escape(&r.nvt_oid)
+            );
+            if let Some(qod) = r.qod {
+                let _ = writeln!(out, "<li>Quality of detection: {qod}%</li>");
+            }
+            if !r.cves.is_empty() {
+                let _ = writeln!(out, "<li>CVEs: {}</li>", html_escape(&r.cves.join(", ")));
+            }
+            if let Some(solution) = &r.solution {
+                let _ = writeln!(out, "<li>Remediation: {}</li>", html_escape(solution));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+
+    if !doc.remediations.is_empty() {
+        out.push_str("<h2>Remediation notes</h2>\n<ul>\n");
+        for fix in &doc.remediations {
+            let hosts = fix.hosts.iter().copied().collect::<Vec<_>>().join(", ");
+            let _ = writeln!(
+                out,
+                "<li><strong>{}</strong> ({:.1}; {}): {}</li>",
+                html_escape(fix.name),
+                fix.severity,
+                html_escape(&hosts),
+                html_escape(fix.solution)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(host: &str, oid: &str, severity: f64, threat: &str, solution: Option<&str>) -> ReportResult {
+        ReportResult {
+            id: format!("result-{host}-{oid}"),
+            name: format!("Finding <{oid}> | x"),
+            host: host.into(),
+            port: "22/tcp".into(),
+            nvt_oid: oid.into(),
+            severity,
+            threat: threat.into(),
+            qod: Some(80),
+            cves: vec!["CVE-2016-6210".into()],
+            solution: solution.map(str::to_string),
+        }
+    }
+
+    fn sample() -> (Vec<ReportResult>, Vec<HostScan>) {
+        let results = vec![
+            result("10.0.0.5", "1.2", 2.6, "Low", None),
+            result("10.0.0.5", "1.1", 7.8, "High", Some("Update to version 7.3 or later.")),
+            result("10.0.0.6", "1.1", 7.8, "High", Some("Update to version 7.3 or later.")),
+        ];
+        let scans = vec![HostScan {
+            target: "10.0.0.7".into(),
+            scanned_at: DateTime::from_timestamp(0, 0).unwrap(),
+            open_ports: BTreeSet::from([22, 80]),
+            services: BTreeSet::from(["http".to_string(), "ssh".to_string()]),
+        }];
+        (results, scans)
+    }
+
+    #[test]
+    fn markdown_has_summary_hosts_and_remediation() {
+        let (results, scans) = sample();
+        let doc = markdown("report-1", &results, &scans, DateTime::from_timestamp(0, 0).unwrap());
+        assert!(doc.starts_with("# Vulnerability report report-1\n"));
+        assert!(doc.contains("| 3 | 2 | 0 | 1 | 0 | 0 |"));
+        assert!(doc.contains("| 10.0.0.5 | 7.8 | 1 | 0 | 1 |  |"));
+        assert!(doc.contains("| 10.0.0.7 | 0.0 | 0 | 0 | 0 | 22, 80 |"));
+        // Most severe first within a host.
+        let host = &doc[doc.find("## 10.0.0.5").unwrap()..];
+        assert!(host.find("<1.1>").unwrap() < host.find("<1.2>").unwrap());
+        assert!(doc.contains("### Finding <1.1> \\| x (7.8, High)"));
+        assert!(doc.contains("## 10.0.0.7\n\nnmap, 1970-01-01T00:00:00+00:00: open ports 22, 80.\n"));
+        assert!(doc.contains("Services: http, ssh."));
+        assert!(doc.contains("- **Finding <1.1> \\| x** (7.8; 10.0.0.5, 10.0.0.6): Update to version 7.3 or later."));
+    }
+
+    #[test]
+    fn html_escapes_and_links_hosts() {
+        let (results, scans) = sample();
+        let doc = html("report-1", &results, &scans, DateTime::from_timestamp(0, 0).unwrap());
+        assert!(doc.starts_with("<!DOCTYPE html>"));
+        assert!(doc.contains("<h3 class=\"high\">Finding &lt;1.1&gt; | x (7.8, High)</h3>"));
+        assert!(doc.contains("<a href=\"#host-10.0.0.6\">10.0.0.6</a>"));
+        assert!(doc.contains("<h2>Remediation notes</h2>"));
+        assert!(!doc.contains("<1.1>"));
+        assert!(doc.ends_with("</html>\n"));
+    }
+}
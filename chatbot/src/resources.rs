@@ -0,0 +1,254 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::models::openvas::{Report, ReportFilter, ReportFormat};
+use crate::services::openvas_get_report;
+
+/// Definition of a resource template for MCP `resources/templates/list`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    pub uri_template: String,
+    pub name: String,
+    pub description: String,
+    pub mime_type: String,
+}
+
+/// Parameters for `resources/read`.
+#[derive(Debug, Deserialize)]
+pub struct ResourceReadParams {
+    pub uri: String,
+}
+
+/// Latest nmap result per target, kept so `nmap://{target}/latest` resolves
+/// without rescanning.
+#[derive(Debug, Clone, Serialize)]
+struct StoredScan {
+    tool: String,
+    scanned_at: DateTime<Utc>,
+    output: Value,
+}
+
+static LATEST_SCANS: Mutex<Option<HashMap<String, StoredScan>>> = Mutex::new(None);
+
+/// Full outputs too large to return inline (see `middleware::OutputLimit`);
+/// the oldest are dropped beyond this many.
+const MAX_SPILLED_OUTPUTS: usize = 32;
+
+/// A tool output kept as `output://{id}`.
+#[derive(Debug, Clone, Serialize)]
+struct SpilledOutput {
+    id: String,
+    tool: String,
+    stored_at: DateTime<Utc>,
+    output: Value,
+}
+
+static SPILLED_OUTPUTS: Mutex<VecDeque<SpilledOutput>> = Mutex::new(VecDeque::new());
+
+/// Reports saved by `openvas_get_report` and the export tools with
+/// `as_resource`; the oldest are dropped beyond this many.
+const MAX_SAVED_REPORTS: usize = 16;
+
+/// A report kept as `openvas://report/{report_id}/{format}`, as text or,
+/// for binary formats, base64.
+#[derive(Debug, Clone)]
+struct SavedReport {
+    uri: String,
+    mime_type: String,
+    text: Option<String>,
+    blob: Option<String>,
+}
+
+static SAVED_REPORTS: Mutex<VecDeque<SavedReport>> = Mutex::new(VecDeque::new());
+
+/// Return all resource templates exposed by this MCP server.
+pub fn list_templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate {
+            uri_template: "nmap://{target}/latest".to_string(),
+            name: "Latest nmap scan".to_string(),
+            description: "Most recent nmap scan result for a target (as passed to the scan tool)."
+                .to_string(),
+            mime_type: "application/json".to_string(),
+        },
+        ResourceTemplate {
+            uri_template: "openvas://report/{report_id}".to_string(),
+            name: "OpenVAS report".to_string(),
+            description: "Raw OpenVAS/GVM report for a report ID, fetched via the Go backend."
+                .to_string(),
+            mime_type: "application/json".to_string(),
+        },
+    ]
+}
+
+/// Concrete resources that can be read right now: one per scanned target,
+/// and one per spilled tool output.
+pub fn list_resources() -> Vec<Value> {
+    let guard = LATEST_SCANS.lock().unwrap();
+    let mut targets: Vec<&String> = guard.iter().flat_map(|m| m.keys()).collect();
+    targets.sort();
+    let mut resources: Vec<Value> = targets
+        .into_iter()
+        .map(|t| {
+            json!({
+                "uri": format!("nmap://{t}/latest"),
+                "name": format!("Latest nmap scan of {t}"),
+                "mimeType": "application/json",
+            })
+        })
+        .collect();
+    resources.extend(SPILLED_OUTPUTS.lock().unwrap().iter().map(|spilled| {
+        json!({
+            "uri": format!("output://{}", spilled.id),
+            "name": format!("Full output of {} at {}", spilled.tool, spilled.stored_at.to_rfc3339()),
+            "mimeType": "application/json",
+        })
+    }));
+    resources.extend(SAVED_REPORTS.lock().unwrap().iter().map(|saved| {
+        json!({
+            "uri": saved.uri,
+            "name": format!("Saved OpenVAS report {}", saved.uri),
+            "mimeType": saved.mime_type,
+        })
+    }));
+    resources
+}
+
+/// Keep the output of `report` in `format`, and return the URI it can be
+/// read from. Saving the same report and format again replaces it.
+pub fn save_report(report: &Report, format: ReportFormat) -> String {
+    let uri = format!("openvas://report/{}/{}", report.report_id, format.name());
+    let (text, blob) = match (&report.content, &report.content_base64) {
+        (_, Some(blob)) => (None, Some(blob.clone())),
+        (Some(text), None) => (Some(text.clone()), None),
+        (None, None) => (Some(report.response_raw.clone()), None),
+    };
+    keep_report(SavedReport {
+        uri: uri.clone(),
+        mime_type: report.content_type.clone().unwrap_or_else(|| "application/xml".to_string()),
+        text,
+        blob,
+    });
+    uri
+}
+
+/// Keep a document rendered from a report (e.g. SARIF) as
+/// `openvas://report/{report_id}/{kind}`, and return that URI.
+pub fn save_export(report_id: &str, kind: &str, mime_type: &str, text: String) -> String {
+    let uri = format!("openvas://report/{report_id}/{kind}");
+    keep_report(SavedReport {
+        uri: uri.clone(),
+        mime_type: mime_type.to_string(),
+        text: Some(text),
+        blob: None,
+    });
+    uri
+}
+
+fn keep_report(report: SavedReport) {
+    let mut saved = SAVED_REPORTS.lock().unwrap();
+    saved.retain(|s| s.uri != report.uri);
+    if saved.len() >= MAX_SAVED_REPORTS {
+        saved.pop_front();
+    }
+    saved.push_back(report);
+}
+
+/// Keep a tool output that was too large to return inline, and return the
+/// URI it can be read from.
+pub fn spill_output(tool: &str, output: Value) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    let mut spilled = SPILLED_OUTPUTS.lock().unwrap();
+    if spilled.len() >= MAX_SPILLED_OUTPUTS {
+        spilled.pop_front();
+    }
+    spilled.push_back(SpilledOutput {
+        id: id.clone(),
+        tool: tool.to_string(),
+        stored_at: Utc::now(),
+        output,
+    });
+    format!("output://{id}")
+}
+
+/// Remember a scan result so it is reachable as `nmap://{target}/latest`.
+/// Only results in the nmap backend shape (with `raw_output`) are stored.
+pub fn remember_scan(tool: &str, target: &str, output: &Value) {
+    if output.get("raw_output").is_none() {
+        return;
+    }
+    LATEST_SCANS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(
+            target.to_string(),
+            StoredScan {
+                tool: tool.to_string(),
+                scanned_at: Utc::now(),
+                output: output.clone(),
+            },
+        );
+}
+
+/// The latest scan of `target` remembered by `remember_scan`: when it ran,
+/// and its output.
+pub fn latest_scan(target: &str) -> Option<(DateTime<Utc>, Value)> {
+    let scans = LATEST_SCANS.lock().unwrap();
+    let scan = scans.as_ref()?.get(target)?;
+    Some((scan.scanned_at, scan.output.clone()))
+}
+
+/// Resolve a resource URI and return the MCP `resources/read` result.
+pub async fn read(uri: &str) -> Result<Value> {
+    let saved = SAVED_REPORTS.lock().unwrap().iter().find(|s| s.uri == uri).cloned();
+    if let Some(saved) = saved {
+        let mut content = json!({ "uri": uri, "mimeType": saved.mime_type });
+        match (saved.text, saved.blob) {
+            (_, Some(blob)) => content["blob"] = json!(blob),
+            (text, None) => content["text"] = json!(text.unwrap_or_default()),
+        }
+        return Ok(json!({ "contents": [content] }));
+    }
+
+    let body = if let Some(rest) = uri.strip_prefix("nmap://") {
+        let target = rest
+            .strip_suffix("/latest")
+            .ok_or_else(|| anyhow::anyhow!("unsupported nmap resource: {uri}"))?;
+        let scan = LATEST_SCANS
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|m| m.get(target).cloned())
+            .ok_or_else(|| anyhow::anyhow!("no nmap scan recorded for {target}"))?;
+        json!(scan)
+    } else if let Some(id) = uri.strip_prefix("output://") {
+        let spilled = SPILLED_OUTPUTS.lock().unwrap();
+        let spilled = spilled
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| anyhow::anyhow!("no stored output {id}; only the last {MAX_SPILLED_OUTPUTS} are kept"))?;
+        spilled.output.clone()
+    } else if let Some(report_id) = uri.strip_prefix("openvas://report/") {
+        if report_id.contains('/') {
+            anyhow::bail!("no saved report {uri}; only the last {MAX_SAVED_REPORTS} are kept");
+        }
+        json!(openvas_get_report::openvas_get_report(report_id, ReportFormat::Xml, &ReportFilter::default(), false).await?)
+    } else {
+        anyhow::bail!("unknown resource URI: {uri}");
+    };
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": serde_json::to_string_pretty(&body)?,
+        }]
+    }))
+}
@@ -0,0 +1,145 @@
+use serde_json::{json, Value};
+
+use crate::models::openvas::ReportResult;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF level of a CVSS score, on the same cut-offs as gvmd's threat
+/// levels: High is an `error`, Medium a `warning`, Low a `note`.
+fn level(severity: f64) -> &'static str {
+    if severity >= 7.0 {
+        "error"
+    } else if severity >= 4.0 {
+        "warning"
+    } else if severity > 0.0 {
+        "note"
+    } else {
+        "none"
+    }
+}
+
+/// A SARIF 2.1.0 log of `results`, one rule per NVT, as accepted by GitHub
+/// code scanning and DefectDojo. Findings have no source file, so each is
+/// located at its host, with the port as a logical location; the
+/// fingerprint (host, port, NVT) keeps findings stable across re-scans.
+pub fn from_report(report_id: &str, results: &[ReportResult]) -> Value {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_ids: Vec<&str> = Vec::new();
+    let sarif_results: Vec<Value> = results
+        .iter()
+        .map(|r| {
+            let index = match rule_ids.iter().position(|id| *id == r.nvt_oid) {
+                Some(index) => index,
+                None => {
+                    let mut tags = vec!["security".to_string()];
+                    tags.extend(r.cves.iter().cloned());
+                    rules.push(json!({
+                        "id": r.nvt_oid,
+                        "name": r.name,
+                        "shortDescription": { "text": r.name },
+                        "properties": {
+                            // GitHub ranks code scanning alerts by this.
+                            "security-severity": format!("{:.1}", r.severity),
+                            "tags": tags,
+                        },
+                    }));
+                    rule_ids.push(&r.nvt_oid);
+                    rule_ids.len() - 1
+                }
+            };
+            let mut properties = json!({
+                "host": r.host,
+                "port": r.port,
+                "severity": r.severity,
+                "threat": r.threat,
+            });
+            if let Some(qod) = r.qod {
+                properties["qod"] = json!(qod);
+            }
+            if !r.cves.is_empty() {
+                properties["cves"] = json!(r.cves);
+            }
+            json!({
+                "ruleId": r.nvt_oid,
+                "ruleIndex": index,
+                "level": level(r.severity),
+                "message": { "text": format!("{} on {} ({})", r.name, r.host, r.port) },
+                "locations": [{
+                    "physicalLocation": { "artifactLocation": { "uri": r.host } },
+                    "logicalLocations": [{
+                        "name": r.port,
+                        "fullyQualifiedName": format!("{}:{}", r.host, r.port),
+                        "kind": "module",
+                    }],
+                }],
+                "partialFingerprints": {
+                    "openvasFinding/v1": format!("{}|{}|{}", r.host, r.port, r.nvt_oid),
+                },
+                "properties": properties,
+            })
+        })
+        .collect();
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "OpenVAS",
+                    "informationUri": "https://www.openvas.org/",
+                    "semanticVersion": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "automationDetails": { "id": format!("openvas/{report_id}") },
+            "results": sarif_results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(host: &str, oid: &str, severity: f64, cves: &[&str]) -> ReportResult {
+        ReportResult {
+            id: format!("result-{host}-{oid}"),
+            name: format!("finding {oid}"),
+            host: host.into(),
+            port: "22/tcp".into(),
+            nvt_oid: oid.into(),
+            severity,
+            threat: "High".into(),
+            qod: Some(80),
+            cves: cves.iter().map(|c| c.to_string()).collect(),
+            solution: None,
+        }
+    }
+
+    #[test]
+    fn findings_become_rules_and_located_results() {
+        let sarif = from_report(
+            "report-1",
+            &[
+                result("10.0.0.5", "1.1", 7.8, &["CVE-2016-6210"]),
+                result("10.0.0.6", "1.1", 7.8, &["CVE-2016-6210"]),
+                result("10.0.0.6", "1.2", 2.6, &[]),
+            ],
+        );
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["properties"]["security-severity"], "7.8");
+        assert_eq!(rules[0]["properties"]["tags"], json!(["security", "CVE-2016-6210"]));
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[2]["ruleIndex"], 1);
+        assert_eq!(results[2]["level"], "note");
+        assert_eq!(results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "10.0.0.6");
+        assert_eq!(results[1]["partialFingerprints"]["openvasFinding/v1"], "10.0.0.6|22/tcp|1.1");
+        assert_eq!(run["automationDetails"]["id"], "openvas/report-1");
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::tagging;
+use crate::tool_error::ToolError;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS scans (
+        id INTEGER PRIMARY KEY,
+        tool TEXT NOT NULL,
+        target TEXT NOT NULL,
+        scanned_at TEXT NOT NULL,
+        output TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS scan_hosts (
+        scan_id INTEGER NOT NULL REFERENCES scans(id),
+        host TEXT NOT NULL,
+        open_ports TEXT NOT NULL,
+        services TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS scans_by_target ON scans(target);
+    CREATE INDEX IF NOT EXISTS scan_hosts_by_host ON scan_hosts(host);
+    CREATE INDEX IF NOT EXISTS scan_hosts_by_scan ON scan_hosts(scan_id);
+";
+
+/// One host in one recorded scan, as `scan_history` lists it.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    pub scan_id: i64,
+    pub tool: String,
+    /// The target as scanned, e.g. a subnet.
+    pub target: String,
+    /// The host as nmap reported it; `None` when the scan reported no host up.
+    pub host: Option<String>,
+    pub scanned_at: DateTime<Utc>,
+    pub open_ports: Vec<u16>,
+    pub services: Vec<String>,
+    /// Ports open now but not in the previous listed scan of the host;
+    /// `None` for its oldest listed scan.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opened_ports: Option<Vec<u16>>,
+    /// Ports open in the previous listed scan of the host but not now.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_ports: Option<Vec<u16>>,
+    /// The scan's full output, when asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+}
+
+/// nmap results by target and time, in SQLite. Each scan is stored once
+/// with its output, parsed XML included, and once per host it reported
+/// with the host's open ports and services, so a host can be looked up
+/// whether it was scanned alone or as part of a subnet.
+pub struct ScanHistory {
+    db: Connection,
+}
+
+static HISTORY: OnceLock<Mutex<ScanHistory>> = OnceLock::new();
+
+/// Open (or create) the scan history database at `path`. Call once at
+/// startup; without it, scans are not recorded.
+pub fn install(path: &str) -> Result<()> {
+    HISTORY
+        .set(Mutex::new(ScanHistory::open(path)?))
+        .map_err(|_| anyhow::anyhow!("scan history already installed"))
+}
+
+/// Record a scan result of `target`, if the history is on. Only results in
+/// the nmap backend shape (with `raw_output`) are recorded; a failure to
+/// record is logged, not passed on to the call.
+pub fn record(tool: &str, target: &str, output: &Value) {
+    let Some(history) = HISTORY.get() else {
+        return;
+    };
+    if output.get("raw_output").is_none() {
+        return;
+    }
+    if let Err(err) = history.lock().unwrap().record(tool, target, output, Utc::now()) {
+        eprintln!("failed to record the {tool} scan of {target} in the scan history: {err:#}");
+    }
+}
+
+/// The latest `limit` scans of `target`, a host or a target as scanned,
+/// newest first.
+pub fn list(target: &str, limit: usize, include_output: bool) -> Result<Vec<HistoryEntry>> {
+    let history = HISTORY.get().ok_or_else(|| {
+        ToolError::invalid_input("scan history is off; start the server with --scan-history <path> to record scans")
+    })?;
+    history.lock().unwrap().list(target, limit, include_output)
+}
+
+/// Hosts and targets are matched as typed, ignoring case and surrounding space.
+fn key(target: &str) -> String {
+    target.trim().to_ascii_lowercase()
+}
+
+impl ScanHistory {
+    pub fn open(path: &str) -> Result<Self> {
+        let db = Connection::open(path).with_context(|| format!("cannot open scan history {path}"))?;
+        db.execute_batch(SCHEMA).with_context(|| format!("cannot set up scan history {path}"))?;
+        Ok(ScanHistory { db })
+    }
+
+    fn record(&mut self, tool: &str, target: &str, output: &Value, at: DateTime<Utc>) -> Result<()> {
+        let tx = self.db.transaction()?;
+        tx.execute(
+            "INSERT INTO scans (tool, target, scanned_at, output) VALUES (?1, ?2, ?3, ?4)",
+            params![tool, key(target), at.to_rfc3339(), output.to_string()],
+        )?;
+        let scan_id = tx.last_insert_rowid();
+        for (host, observed) in tagging::hosts(target, output) {
+            tx.execute(
+                "INSERT INTO scan_hosts (scan_id, host, open_ports, services) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    scan_id,
+                    key(&host),
+                    serde_json::to_string(&observed.open_ports)?,
+                    serde_json::to_string(&observed.services)?
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn list(&self, target: &str, limit: usize, include_output: bool) -> Result<Vec<HistoryEntry>> {
+        let mut query = self.db.prepare(
+            "SELECT s.id, s.tool, s.target, s.scanned_at, h.host, h.open_ports, h.services, s.output
+             FROM scans s LEFT JOIN scan_hosts h ON h.scan_id = s.id
+             WHERE h.host = ?1 OR s.target = ?1
+             ORDER BY s.id DESC, h.host
+             LIMIT ?2",
+        )?;
+        let rows = query.query_map(params![key(target), limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, String>(7)?,
+            ))
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (scan_id, tool, target, scanned_at, host, open_ports, services, output) = row?;
+            entries.push(HistoryEntry {
+                scan_id,
+                tool,
+                target,
+                host,
+                scanned_at: DateTime::parse_from_rfc3339(&scanned_at)?.with_timezone(&Utc),
+                open_ports: open_ports.map(|p| serde_json::from_str(&p)).transpose()?.unwrap_or_default(),
+                services: services.map(|s| serde_json::from_str(&s)).transpose()?.unwrap_or_default(),
+                opened_ports: None,
+                closed_ports: None,
+                output: if include_output { Some(serde_json::from_str(&output)?) } else { None },
+            });
+        }
+
+        // Diff each host's scans against the one before, oldest first.
+        let mut previous: BTreeMap<String, BTreeSet<u16>> = BTreeMap::new();
+        for entry in entries.iter_mut().rev() {
+            let host = entry.host.clone().unwrap_or_else(|| entry.target.clone());
+            let open: BTreeSet<u16> = entry.open_ports.iter().copied().collect();
+            if let Some(before) = previous.insert(host, open.clone()) {
+                entry.opened_ports = Some(open.difference(&before).copied().collect());
+                entry.closed_ports = Some(before.difference(&open).copied().collect());
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn hosts_are_listed_newest_first_with_their_port_changes() {
+        let mut history = ScanHistory::open(":memory:").unwrap();
+        let start = Utc::now() - Duration::hours(2);
+        let sweep = "Nmap scan report for db.history.example (192.0.2.20)\n22/tcp open ssh\n3306/tcp open mysql\n\n\
+                     Nmap scan report for 192.0.2.21\n80/tcp open http\n";
+        history.record("quick_scan", "192.0.2.0/24", &json!({ "raw_output": sweep }), start).unwrap();
+        let rescan = "Nmap scan report for db.history.example (192.0.2.20)\n22/tcp open ssh\n443/tcp open https\n";
+        let output = json!({ "raw_output": rescan });
+        history.record("advanced_nmap_scan", "DB.history.example", &output, start + Duration::hours(1)).unwrap();
+        history.record("nmap.scan", "192.0.2.99", &json!({ "raw_output": "Note: Host seems down.\n" }), start).unwrap();
+
+        let db = history.list("db.history.example", 10, false).unwrap();
+        assert_eq!(db.len(), 2);
+        assert_eq!((db[0].tool.as_str(), db[0].target.as_str()), ("advanced_nmap_scan", "db.history.example"));
+        assert_eq!(db[0].open_ports, [22, 443]);
+        assert_eq!(db[0].services, ["https", "ssh"]);
+        assert_eq!(db[0].opened_ports.as_deref(), Some(&[443][..]));
+        assert_eq!(db[0].closed_ports.as_deref(), Some(&[3306][..]));
+        assert_eq!(db[1].target, "192.0.2.0/24");
+        assert!(db[1].opened_ports.is_none() && db[0].output.is_none());
+
+        let sweep = history.list("192.0.2.0/24", 10, true).unwrap();
+        let hosts: Vec<Option<&str>> = sweep.iter().map(|e| e.host.as_deref()).collect();
+        assert_eq!(hosts, [Some("192.0.2.21"), Some("db.history.example")]);
+        assert!(sweep[0].output.as_ref().is_some_and(|o| o["raw_output"].as_str().unwrap().contains("3306/tcp")));
+
+        let down = history.list("192.0.2.99", 10, false).unwrap();
+        assert_eq!((down.len(), down[0].host.as_deref()), (1, None));
+        assert_eq!(history.list("db.history.example", 1, false).unwrap().len(), 1);
+    }
+}
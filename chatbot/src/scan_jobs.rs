@@ -0,0 +1,365 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::api::backend;
+use crate::api::errors::BackendError;
+use crate::jobs::JobState;
+use crate::middleware::{self, Rejection, ToolTimeout};
+use crate::models::nmap_scan::{ScanRequest, ScanResult};
+use crate::tool_error::ToolError;
+use crate::{config, partial, resources, scan_history, session, tagging, throttle, trace};
+
+/// Finished background scans kept with their results; the oldest are
+/// dropped beyond this.
+const MAX_FINISHED: usize = 100;
+
+/// Background scans of a tool queued or running at once; more are refused
+/// until one finishes.
+const MAX_PENDING: usize = 20;
+
+/// Background scans of a tool run at once when neither `--max-concurrency`
+/// nor the tool sets a limit: each call would start another nmap otherwise.
+const DEFAULT_LIMIT: usize = 2;
+
+/// A scan started with `background: true`, as `nmap_scan_status` reports it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanJob {
+    pub scan_id: String,
+    pub tool: String,
+    pub target: String,
+    /// Who started the scan, as in the job table.
+    pub operator: String,
+    /// `queued` while waiting for a slot; never `cancelled`.
+    pub state: JobState,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Time the scan ran, not counting the wait for a slot.
+    pub duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// While the scan runs, what it found so far, for scans that report it
+    /// as they go (`--local-nmap`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_output: Option<Value>,
+    /// What to call next.
+    pub next: String,
+}
+
+/// Why a background scan failed, kept so that `nmap_scan_result` fails the
+/// way the scan would have in the foreground.
+enum Failure {
+    Backend(BackendError),
+    /// With what the scan found before it was cut off, if it said.
+    Timeout(Duration, Option<Value>),
+    InvalidInput(String),
+    Other(String),
+}
+
+struct Entry {
+    job: ScanJob,
+    partial: partial::Slot,
+    outcome: Option<Result<ScanResult, Failure>>,
+}
+
+/// Background scans, oldest first. In memory only: they are lost when the
+/// server restarts.
+static SCANS: Mutex<VecDeque<Entry>> = Mutex::new(VecDeque::new());
+
+/// Start `request` as a background scan of `tool` and return at once, or
+/// refuse it when `MAX_PENDING` scans of the tool have not finished yet. The scan goes
+/// to the nmap backend of the calling task and runs in its session and
+/// trace, waits for one of the tool's `--max-concurrency` slots (shared
+/// with foreground calls; `DEFAULT_LIMIT` without a limit), and is cut off
+/// after the tool's timeout. A finished scan is observed like one in the
+/// foreground: remembered as `nmap://{target}/latest` and in the scan
+/// history, tagged, and counted by the throttle.
+pub fn start(
+    tool: &str,
+    request: ScanRequest,
+    default_timeout: Option<Duration>,
+    default_limit: Option<usize>,
+) -> Result<ScanJob> {
+    let (timeout, limit) = {
+        let config = config::current();
+        (
+            config.tool_timeouts.get(tool).copied().or(default_timeout),
+            config
+                .max_concurrency
+                .get(tool)
+                .copied()
+                .or(default_limit)
+                .filter(|limit| *limit > 0)
+                .unwrap_or(DEFAULT_LIMIT),
+        )
+    };
+    let scan_id = uuid::Uuid::new_v4().to_string();
+    let job = ScanJob {
+        next: next(&scan_id, JobState::Queued),
+        scan_id: scan_id.clone(),
+        tool: tool.to_string(),
+        target: request.target.clone(),
+        operator: session::current().map(|s| s.operator()).unwrap_or_else(|| "internal".to_string()),
+        state: JobState::Queued,
+        queued_at: Utc::now(),
+        started_at: None,
+        finished_at: None,
+        duration_ms: None,
+        error: None,
+        partial_output: None,
+    };
+    let partial = partial::Slot::default();
+    {
+        let mut scans = SCANS.lock().unwrap();
+        if scans.iter().filter(|e| e.outcome.is_none() && e.job.tool == tool).count() >= MAX_PENDING {
+            return Err(Rejection {
+                code: -32004,
+                message: format!(
+                    "Too many background scans: {MAX_PENDING} of {tool} have not finished; wait for one (nmap_scan_status) \
+                     and start this one again"
+                ),
+                data: Some(json!({ "pending": MAX_PENDING })),
+            }
+            .into());
+        }
+        scans.push_back(Entry { job: job.clone(), partial: partial.clone(), outcome: None });
+        while scans.iter().filter(|e| e.outcome.is_some()).count() > MAX_FINISHED {
+            let oldest = scans.iter().position(|e| e.outcome.is_some()).expect("a finished scan");
+            scans.remove(oldest);
+        }
+    }
+
+    let slots = middleware::slots_for(tool, limit);
+    let backend = backend::current();
+    let (session, trace_id) = (session::current(), trace::current());
+    let tool = tool.to_string();
+    let run = async move {
+        let _permit = slots.acquire_owned().await.ok();
+        update(&scan_id, |job| {
+            job.state = JobState::Running;
+            job.started_at = Some(Utc::now());
+        });
+        let scan = partial::scope(partial.clone(), backend.advanced_scan(&request));
+        let outcome = match timeout {
+            Some(limit) => tokio::time::timeout(limit, scan).await.unwrap_or_else(|_| {
+                let partial_output = partial.lock().unwrap().take();
+                Err(ToolTimeout { tool: tool.clone(), limit, partial_output }.into())
+            }),
+            None => scan.await,
+        };
+        if let Ok(scan) = &outcome {
+            observe(&tool, &request.target, scan);
+        }
+        finish(&scan_id, outcome);
+    };
+    // Backend calls carry the caller's trace id, and the audit log and
+    // progress notifications see the caller's session.
+    let run = async move {
+        match trace_id {
+            Some(id) => trace::scope(id, run).await,
+            None => run.await,
+        }
+    };
+    tokio::spawn(async move {
+        match session {
+            Some(session) => session::scope(session, run).await,
+            None => run.await,
+        }
+    });
+    Ok(job)
+}
+
+/// What `ScanObservers` does for a foreground scan.
+fn observe(tool: &str, target: &str, scan: &ScanResult) {
+    let Ok(output) = serde_json::to_value(scan) else {
+        return;
+    };
+    throttle::observe(&json!({ "target": target }), &Ok(output.clone()));
+    resources::remember_scan(tool, target, &output);
+    scan_history::record(tool, target, &output);
+    tagging::apply(target, &output);
+}
+
+fn update(scan_id: &str, change: impl FnOnce(&mut ScanJob)) {
+    if let Some(entry) = SCANS.lock().unwrap().iter_mut().find(|e| e.job.scan_id == scan_id) {
+        change(&mut entry.job);
+        entry.job.next = next(scan_id, entry.job.state);
+    }
+}
+
+fn finish(scan_id: &str, outcome: Result<ScanResult>) {
+    let mut scans = SCANS.lock().unwrap();
+    let Some(entry) = scans.iter_mut().find(|e| e.job.scan_id == scan_id) else {
+        return;
+    };
+    let now = Utc::now();
+    let job = &mut entry.job;
+    job.finished_at = Some(now);
+    job.duration_ms = job.started_at.map(|started| (now - started).num_milliseconds());
+    entry.outcome = Some(outcome.map_err(|err| {
+        job.error = Some(format!("{err:#}"));
+        match ToolError::classify(err) {
+            ToolError::Backend(backend) | ToolError::BackendUnavailable(backend) => Failure::Backend(backend),
+            ToolError::Timeout(timeout) => Failure::Timeout(timeout.limit, timeout.partial_output),
+            ToolError::InvalidInput { message, .. } => Failure::InvalidInput(message),
+            _ => Failure::Other(job.error.clone().unwrap_or_default()),
+        }
+    }));
+    job.state = if job.error.is_some() { JobState::Failed } else { JobState::Succeeded };
+    job.next = next(scan_id, job.state);
+}
+
+fn next(scan_id: &str, state: JobState) -> String {
+    match state {
+        JobState::Queued | JobState::Running => {
+            format!("check again later with nmap_scan_status {{\"scan_id\": \"{scan_id}\"}}")
+        }
+        JobState::Succeeded => format!("fetch the result with nmap_scan_result {{\"scan_id\": \"{scan_id}\"}}"),
+        JobState::Failed | JobState::Cancelled => "see error; the scan can be run again".to_string(),
+    }
+}
+
+fn unknown(scan_id: &str) -> anyhow::Error {
+    ToolError::invalid_input(format!(
+        "no background scan {scan_id}; scans are kept in memory, the latest {MAX_FINISHED} finished ones, \
+         and lost when the server restarts"
+    ))
+    .into()
+}
+
+/// The background scan `scan_id`.
+pub fn status(scan_id: &str) -> Result<ScanJob> {
+    let scans = SCANS.lock().unwrap();
+    let entry = scans.iter().find(|e| e.job.scan_id == scan_id.trim()).ok_or_else(|| unknown(scan_id))?;
+    let mut job = entry.job.clone();
+    if entry.outcome.is_none() {
+        job.partial_output = entry.partial.lock().unwrap().clone();
+    }
+    Ok(job)
+}
+
+/// The result of the background scan `scan_id`, or the error it failed
+/// with; an error too while the scan has not finished.
+pub fn result(scan_id: &str) -> Result<ScanResult> {
+    let scans = SCANS.lock().unwrap();
+    let entry = scans.iter().find(|e| e.job.scan_id == scan_id.trim()).ok_or_else(|| unknown(scan_id))?;
+    match &entry.outcome {
+        Some(Ok(scan)) => Ok(scan.clone()),
+        Some(Err(failure)) => Err(match failure {
+            Failure::Backend(backend) => backend.clone().into(),
+            Failure::Timeout(limit, partial_output) => ToolTimeout {
+                tool: entry.job.tool.clone(),
+                limit: *limit,
+                partial_output: partial_output.clone(),
+            }
+            .into(),
+            Failure::InvalidInput(message) => ToolError::invalid_input(message.clone()).into(),
+            Failure::Other(message) => anyhow::anyhow!("{message}"),
+        }),
+        None => {
+            let state = serde_json::to_value(entry.job.state)?;
+            Err(ToolError::invalid_input(format!("scan {scan_id} is {state}, not finished; {}", entry.job.next)).into())
+        }
+    }
+}
+
+/// Whether a tool's output is the `ScanJob` of a scan started in the
+/// background rather than a scan result.
+pub fn is_handle(output: &Value) -> bool {
+    output.get("scan_id").is_some() && output.get("raw_output").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    async fn finished(scan_id: &str) -> ScanJob {
+        loop {
+            let job = status(scan_id).unwrap();
+            if !matches!(job.state, JobState::Queued | JobState::Running) {
+                return job;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    fn request(target: &str) -> ScanRequest {
+        ScanRequest { target: target.to_string(), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn background_scans_run_detached_and_are_observed() {
+        let raw = "Nmap scan report for 10.88.0.5\nHost is up.\n22/tcp open ssh\nNmap done: 1 IP address (1 host up)\n";
+        let mock = Arc::new(MockBackend::default().with_scan("10.88.0.5", raw));
+        let job = with_backend(mock.clone(), async {
+            start("comprehensive_scan", request("10.88.0.5"), Some(Duration::from_secs(60)), Some(1)).unwrap()
+        })
+        .await;
+        assert_eq!(job.state, JobState::Queued);
+        assert!(is_handle(&serde_json::to_value(&job).unwrap()));
+
+        let done = finished(&job.scan_id).await;
+        assert_eq!(done.state, JobState::Succeeded);
+        assert!(done.next.starts_with("fetch the result with nmap_scan_result"));
+        let scan = result(&job.scan_id).unwrap();
+        assert!(scan.raw_output.contains("22/tcp open ssh"));
+        assert!(!is_handle(&serde_json::to_value(&scan).unwrap()));
+        assert!(resources::latest_scan("10.88.0.5").is_some());
+        assert_eq!(mock.calls(), ["advanced_scan 10.88.0.5"]);
+    }
+
+    #[tokio::test]
+    async fn failed_scans_fail_their_result_like_the_scan_would() {
+        let mock = Arc::new(MockBackend::default());
+        mock.fail_next(BackendError::from_response(503, "connection refused"));
+        let job = with_backend(mock, async { start("advanced_nmap_scan", request("10.88.0.6"), None, None).unwrap() }).await;
+
+        let done = finished(&job.scan_id).await;
+        assert_eq!(done.state, JobState::Failed);
+        assert!(done.error.is_some());
+        let err = ToolError::classify(result(&job.scan_id).unwrap_err());
+        assert_eq!(err.kind(), "backend_unavailable");
+
+        let unknown = ToolError::classify(result("no-such-scan").unwrap_err());
+        assert!(unknown.to_string().contains("no background scan no-such-scan"));
+    }
+
+    #[tokio::test]
+    async fn background_scans_share_the_foreground_slots_and_are_capped() {
+        async fn queued_until_released(tool: &str, limit: Option<usize>, slots: usize) {
+            let held = middleware::slots_for(tool, slots).acquire_many_owned(slots as u32).await.unwrap();
+            let jobs: Vec<ScanJob> =
+                (0..MAX_PENDING).map(|_| start(tool, request("10.88.0.7"), None, limit).unwrap()).collect();
+            let refused = start(tool, request("10.88.0.7"), None, limit).unwrap_err();
+            assert_eq!(refused.downcast_ref::<Rejection>().unwrap().code, -32004);
+            // Foreground calls hold every slot: nothing runs until they end.
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+            assert!(jobs.iter().all(|job| status(&job.scan_id).unwrap().state == JobState::Queued));
+            drop(held);
+            for job in &jobs {
+                finished(&job.scan_id).await;
+            }
+            assert!(start(tool, request("10.88.0.7"), None, limit).is_ok());
+        }
+
+        let mock = Arc::new(MockBackend::default());
+        with_backend(mock, async {
+            queued_until_released("shared_scan", Some(1), 1).await;
+            // Without a limit, background scans still take DEFAULT_LIMIT slots.
+            queued_until_released("unlimited_scan", None, DEFAULT_LIMIT).await;
+        })
+        .await;
+    }
+}
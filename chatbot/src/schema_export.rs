@@ -0,0 +1,60 @@
+use serde_json::{json, Map, Value};
+
+use crate::ToolRegistry;
+
+/// Render every registered tool's input schema as an OpenAPI 3.1 document,
+/// so typed clients can be generated (or agent calls validated) without an
+/// MCP client. Each tool becomes `POST /tools/{name}` whose request body is
+/// the tool's `input`; over MCP the same call is `tools/call` with that name.
+/// Schemas live under `components/schemas/{name}`, so the document doubles as
+/// a plain JSON Schema bundle.
+pub fn openapi(registry: &ToolRegistry) -> Value {
+    let mut tools = registry.list(None);
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    for tool in tools {
+        let Some(name) = tool["name"].as_str().map(str::to_string) else {
+            continue;
+        };
+        schemas.insert(name.clone(), tool["inputSchema"].clone());
+        paths.insert(
+            format!("/tools/{name}"),
+            json!({
+                "post": {
+                    "operationId": name,
+                    "summary": tool["description"],
+                    "tags": [tool["_meta"]["category"]],
+                    "x-mcp-annotations": tool["annotations"],
+                    "x-tool-version": tool["_meta"]["version"],
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": format!("#/components/schemas/{name}") }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "Tool output",
+                            "content": { "application/json": { "schema": {} } }
+                        }
+                    }
+                }
+            }),
+        );
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "hacker_agent tools",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Input schemas of the hacker_agent MCP tools. Each operation corresponds to an MCP `tools/call` with the same tool name and the request body as `input`."
+        },
+        "paths": paths,
+        "components": { "schemas": schemas }
+    })
+}
@@ -1,188 +1,112 @@
 use anyhow::Result;
-use serde_json::{json, Value};
 
-use crate::api::nmap;
+use crate::api::backend;
+use crate::models::nmap_scan::{
+    FastTiming, QuickScanType, ScanRequest, ScanResult, ScanType, StealthLevel, StealthOptions,
+    StealthScanType, Timing,
+};
 
 /// Comprehensive Nmap scan with all options
-pub async fn advanced_nmap_scan(
-    target: &str,
-    timing: Option<&str>,
-    scan_type: Option<&str>,
-    ports: Option<&str>,
-    service_detection: bool,
-    os_detection: bool,
-    scripts: Option<&str>,
-    output_format: Option<&str>,
-    aggressive: bool,
-    traceroute: bool,
-    flag_o: bool,
-    flag_sc: bool,
-    flag_sv: bool,
-    flag_traceroute: bool,
-    flag_a: bool,
-    stealth_options: Option<&Value>,
-) -> Result<Value> {
-    let mut body = json!({
-        "target": target
-    });
-
-    // Add optional parameters
-    if let Some(t) = timing {
-        body["timing"] = json!(t);
-    }
-    if let Some(st) = scan_type {
-        body["scan_type"] = json!(st);
-    }
-    if let Some(p) = ports {
-        body["ports"] = json!(p);
-    }
-    if service_detection {
-        body["service_detection"] = json!(true);
-    }
-    if os_detection {
-        body["os_detection"] = json!(true);
-    }
-    if let Some(s) = scripts {
-        body["scripts"] = json!(s);
-    }
-    if let Some(of) = output_format {
-        body["output_format"] = json!(of);
-    }
-    if let Some(so) = stealth_options {
-        body["stealth_options"] = so.clone();
-    }
-    if aggressive {
-        body["aggressive"] = json!(true);
-    }
-    if traceroute {
-        body["traceroute"] = json!(true);
-    }
-    if flag_o {
-        body["flag_o"] = json!(true);
-    }
-    if flag_sc {
-        body["flag_sc"] = json!(true);
-    }
-    if flag_sv {
-        body["flag_sv"] = json!(true);
-    }
-    if flag_traceroute {
-        body["flag_traceroute"] = json!(true);
-    }
-    if flag_a {
-        body["flag_a"] = json!(true);
-    }
-
-    nmap::advanced_scan(&body).await
+pub async fn advanced_nmap_scan(request: &ScanRequest) -> Result<ScanResult> {
+    backend::current().advanced_scan(request).await
 }
 
 /// Quick scan presets for common scenarios
 /// Note: Uses tcp_connect instead of tcp_syn to avoid requiring root privileges
-pub async fn quick_scan(target: &str, scan_type: &str, timing: &str) -> Result<Value> {
-    let body = match scan_type {
-        "ping_sweep" => json!({
-            "target": target,
-            "timing": timing,
-            "scan_type": "ping"
-        }),
-        "common_ports" => json!({
-            "target": target,
-            "timing": timing,
-            "scan_type": "tcp_connect",
-            "ports": "1-1000",
-            "service_detection": true
-        }),
-        "service_detection" => json!({
-            "target": target,
-            "timing": timing,
-            "scan_type": "tcp_connect",
-            "service_detection": true
-        }),
-        "vuln_scan" => json!({
-            "target": target,
-            "timing": timing,
-            "scan_type": "tcp_connect",
-            "service_detection": true,
-            "scripts": "vuln"
-        }),
-        _ => json!({
-            "target": target,
-            "timing": timing
-        })
+pub async fn quick_scan(target: &str, scan_type: QuickScanType, timing: FastTiming) -> Result<ScanResult> {
+    let base = ScanRequest {
+        target: target.to_string(),
+        timing: Some(timing.into()),
+        ..Default::default()
+    };
+    let request = match scan_type {
+        QuickScanType::PingSweep => ScanRequest {
+            scan_type: Some(ScanType::Ping),
+            ..base
+        },
+        QuickScanType::CommonPorts => ScanRequest {
+            scan_type: Some(ScanType::TcpConnect),
+            ports: Some("1-1000".to_string()),
+            service_detection: true,
+            ..base
+        },
+        QuickScanType::ServiceDetection => ScanRequest {
+            scan_type: Some(ScanType::TcpConnect),
+            service_detection: true,
+            ..base
+        },
+        QuickScanType::VulnScan => ScanRequest {
+            scan_type: Some(ScanType::TcpConnect),
+            service_detection: true,
+            scripts: Some("vuln".to_string()),
+            ..base
+        },
     };
 
-    nmap::advanced_scan(&body).await
+    backend::current().advanced_scan(&request).await
 }
 
 /// Stealth scan with evasion techniques
 pub async fn stealth_scan(
     target: &str,
-    stealth_level: &str,
-    scan_type: &str,
+    stealth_level: StealthLevel,
+    scan_type: StealthScanType,
     use_decoys: bool,
     fragment_packets: bool,
-) -> Result<Value> {
-    let (timing, decoys, ttl) = match stealth_level {
-        "low" => ("T3", None, None),
-        "medium" => ("T2", 
-            if use_decoys { Some(json!(["RND:5", "ME"])) } else { None },
-            Some(64)
-        ),
-        "high" => ("T1",
-            if use_decoys { Some(json!(["RND:10", "8.8.8.8", "ME"])) } else { None },
-            Some(128)
-        ),
-        "maximum" => ("T0",
-            if use_decoys { Some(json!(["RND:15", "8.8.8.8", "1.1.1.1", "ME"])) } else { None },
-            Some(255)
-        ),
-        _ => ("T2", None, None)
+) -> Result<ScanResult> {
+    let (timing, decoys, ttl): (Timing, &[&str], Option<u8>) = match stealth_level {
+        StealthLevel::Low => (Timing::T3, &[], None),
+        StealthLevel::Medium => (Timing::T2, &["RND:5", "ME"], Some(64)),
+        StealthLevel::High => (Timing::T1, &["RND:10", "8.8.8.8", "ME"], Some(128)),
+        StealthLevel::Maximum => (Timing::T0, &["RND:15", "8.8.8.8", "1.1.1.1", "ME"], Some(255)),
     };
 
-    let mut stealth_opts = json!({});
-    if let Some(d) = decoys {
-        stealth_opts["decoys"] = d;
-    }
-    if let Some(t) = ttl {
-        stealth_opts["ttl"] = json!(t);
-    }
-    if fragment_packets {
-        stealth_opts["fragment_packets"] = json!(true);
-    }
+    let stealth_options = StealthOptions {
+        decoys: if use_decoys { decoys.iter().map(|d| d.to_string()).collect() } else { Vec::new() },
+        ttl,
+        fragment_packets,
+        ..Default::default()
+    };
 
-    let body = json!({
-        "target": target,
-        "timing": timing,
-        "scan_type": scan_type,
-        "stealth_options": stealth_opts
-    });
+    let request = ScanRequest {
+        target: target.to_string(),
+        timing: Some(timing),
+        scan_type: Some(scan_type.into()),
+        stealth_options: Some(stealth_options),
+        ..Default::default()
+    };
 
-    nmap::advanced_scan(&body).await
+    backend::current().advanced_scan(&request).await
 }
 
 /// Comprehensive scan with multiple techniques - scans all 65535 ports
 /// Note: OS detection (-O) requires root privileges, so it's disabled by default
-pub async fn comprehensive_scan(target: &str, include_vuln: bool) -> Result<Value> {
-    let body = json!({
-        "target": target,
-        "timing": "T3",
-        "scan_type": "tcp_connect",
-        "ports": "1-65535",
-        "service_detection": true,
-        "scripts": if include_vuln { "default,vuln" } else { "default" }
-    });
+pub async fn comprehensive_scan(target: &str, include_vuln: bool) -> Result<ScanResult> {
+    backend::current().advanced_scan(&comprehensive_request(target, include_vuln)).await
+}
 
-    nmap::advanced_scan(&body).await
+/// The request `comprehensive_scan` sends, also for running it in the background.
+pub fn comprehensive_request(target: &str, include_vuln: bool) -> ScanRequest {
+    ScanRequest {
+        target: target.to_string(),
+        timing: Some(Timing::T3),
+        scan_type: Some(ScanType::TcpConnect),
+        ports: Some("1-65535".to_string()),
+        service_detection: true,
+        scripts: Some(if include_vuln { "default,vuln" } else { "default" }.to_string()),
+        ..Default::default()
+    }
 }
 
 /// Network discovery scan for subnet enumeration
 /// Uses ping scan to find live hosts (no port specification allowed with -sn)
-pub async fn network_discovery(subnet: &str, timing: &str) -> Result<Value> {
-    let body = json!({
-        "target": subnet,
-        "timing": timing,
-        "scan_type": "ping"
-    });
+pub async fn network_discovery(subnet: &str, timing: FastTiming) -> Result<ScanResult> {
+    let request = ScanRequest {
+        target: subnet.to_string(),
+        timing: Some(timing.into()),
+        scan_type: Some(ScanType::Ping),
+        ..Default::default()
+    };
 
-    nmap::advanced_scan(&body).await
+    backend::current().advanced_scan(&request).await
 }
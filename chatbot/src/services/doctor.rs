@@ -0,0 +1,216 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use tokio::process::Command;
+
+use crate::{approval, config};
+
+/// One external dependency that hacker_agent (or its Go backend) shells out to.
+struct Dependency {
+    /// Binary name looked up on PATH.
+    binary: &'static str,
+    /// Arguments that make the binary print its version.
+    version_args: &'static [&'static str],
+    /// Oldest version known to produce output our parsers understand.
+    min_version: &'static str,
+    /// Versions (prefix match) with known result-parsing incompatibilities,
+    /// paired with the warning to show. Extend as incompatibilities surface.
+    known_bad: &'static [(&'static str, &'static str)],
+    /// Package name per package manager, used when installing.
+    packages: &'static [(&'static str, &'static str)],
+    /// Why the server needs this dependency.
+    purpose: &'static str,
+}
+
+/// Known-good version matrix for external scanner dependencies.
+const MATRIX: &[Dependency] = &[
+    Dependency {
+        binary: "nmap",
+        version_args: &["--version"],
+        min_version: "7.80",
+        known_bad: &[],
+        packages: &[("apt-get", "nmap"), ("dnf", "nmap"), ("yum", "nmap"), ("pacman", "nmap"), ("apk", "nmap"), ("brew", "nmap")],
+        purpose: "port scanning (run by the Go backend)",
+    },
+    Dependency {
+        binary: "docker",
+        version_args: &["--version"],
+        min_version: "20.10",
+        known_bad: &[],
+        packages: &[("apt-get", "docker.io"), ("dnf", "docker"), ("yum", "docker"), ("pacman", "docker"), ("apk", "docker"), ("brew", "docker")],
+        purpose: "reaching gvm-cli inside the OpenVAS container",
+    },
+    Dependency {
+        binary: "go",
+        version_args: &["version"],
+        min_version: "1.22",
+        known_bad: &[],
+        packages: &[("apt-get", "golang-go"), ("dnf", "golang"), ("yum", "golang"), ("pacman", "go"), ("apk", "go"), ("brew", "go")],
+        purpose: "building the Go backend (golangBackendServices)",
+    },
+];
+
+/// Package managers we know how to drive, in preference order.
+const PACKAGE_MANAGERS: &[(&str, &[&str])] = &[
+    ("apt-get", &["install", "-y"]),
+    ("dnf", &["install", "-y"]),
+    ("yum", &["install", "-y"]),
+    ("pacman", &["-S", "--noconfirm"]),
+    ("apk", &["add"]),
+    ("brew", &["install"]),
+];
+
+/// Inspect every external dependency and report its installed version against
+/// the known-good matrix. When `install_missing` is set, missing tools are
+/// installed via the system package manager — but only if `confirm` is also
+/// set and the user approves each command when asked (MCP elicitation);
+/// otherwise the commands that would run are returned for review.
+pub async fn doctor(install_missing: bool, confirm: bool) -> Result<Value> {
+    let package_manager = detect_package_manager().await;
+    let mut reports = Vec::new();
+    let mut healthy = true;
+
+    for dep in MATRIX {
+        let installed = installed_version(dep).await;
+        let mut warnings = Vec::new();
+
+        let status = match installed.as_deref() {
+            None => "missing",
+            Some(v) => {
+                if let Some((_, why)) = dep.known_bad.iter().find(|(bad, _)| v.starts_with(bad)) {
+                    warnings.push(why.to_string());
+                }
+                if compare_versions(v, dep.min_version) == std::cmp::Ordering::Less {
+                    warnings.push(format!(
+                        "{} {v} is older than the known-good minimum {}",
+                        dep.binary, dep.min_version
+                    ));
+                }
+                if warnings.is_empty() { "ok" } else { "warning" }
+            }
+        };
+        if status != "ok" {
+            healthy = false;
+        }
+
+        let mut report = json!({
+            "tool": dep.binary,
+            "purpose": dep.purpose,
+            "installed_version": installed,
+            "min_version": dep.min_version,
+            "status": status,
+            "warnings": warnings,
+        });
+
+        if status == "missing" && install_missing {
+            report["install"] = install(dep, package_manager, confirm).await;
+        }
+
+        reports.push(report);
+    }
+
+    Ok(json!({
+        "healthy": healthy,
+        "package_manager": package_manager,
+        "dependencies": reports,
+    }))
+}
+
+async fn installed_version(dep: &Dependency) -> Option<String> {
+    let output = Command::new(dep.binary)
+        .args(dep.version_args)
+        .output()
+        .await
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    extract_version(&text)
+}
+
+/// Pull the first dotted version number (e.g. `7.94` or `1.22.3`) out of a
+/// tool's `--version` banner.
+fn extract_version(text: &str) -> Option<String> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|s| s.trim_matches('.'))
+        .find(|s| s.contains('.') && s.split('.').all(|p| !p.is_empty()))
+        .map(str::to_string)
+}
+
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parse(a).cmp(&parse(b))
+}
+
+async fn detect_package_manager() -> Option<&'static str> {
+    for (pm, _) in PACKAGE_MANAGERS {
+        if Command::new("which").arg(pm).output().await.is_ok_and(|o| o.status.success()) {
+            return Some(pm);
+        }
+    }
+    None
+}
+
+async fn install(dep: &Dependency, package_manager: Option<&'static str>, confirm: bool) -> Value {
+    let Some(pm) = package_manager else {
+        return json!({ "status": "skipped", "reason": "no supported package manager found" });
+    };
+    let Some((_, package)) = dep.packages.iter().find(|(p, _)| *p == pm) else {
+        return json!({ "status": "skipped", "reason": format!("no known {pm} package for {}", dep.binary) });
+    };
+    let install_args = PACKAGE_MANAGERS
+        .iter()
+        .find(|(p, _)| *p == pm)
+        .map(|(_, args)| *args)
+        .unwrap_or(&[]);
+
+    // Non-root users go through `sudo -n` so we fail fast instead of hanging
+    // on a password prompt nobody can answer.
+    let is_root = Command::new("id")
+        .arg("-u")
+        .output()
+        .await
+        .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).trim() == "0");
+    let mut argv: Vec<String> = Vec::new();
+    if !is_root && pm != "brew" {
+        argv.extend(["sudo".to_string(), "-n".to_string()]);
+    }
+    argv.push(pm.to_string());
+    argv.extend(install_args.iter().map(|a| a.to_string()));
+    argv.push(package.to_string());
+
+    if config::current().offline {
+        return json!({
+            "status": "skipped",
+            "command": argv.join(" "),
+            "reason": "package downloads are disabled in offline mode",
+        });
+    }
+
+    if !confirm {
+        return json!({
+            "status": "pending_confirmation",
+            "command": argv.join(" "),
+            "hint": "re-run with confirm: true to ask the user to approve this command",
+        });
+    }
+
+    let command = argv.join(" ");
+    if let Err(err) = approval::confirm_host_change("doctor", &format!("run `{command}`")).await {
+        return json!({ "status": "not_approved", "command": command, "reason": err.to_string() });
+    }
+
+    match Command::new(&argv[0]).args(&argv[1..]).output().await {
+        Ok(out) => json!({
+            "status": if out.status.success() { "installed" } else { "failed" },
+            "command": argv.join(" "),
+            "output": format!(
+                "{}{}",
+                String::from_utf8_lossy(&out.stdout),
+                String::from_utf8_lossy(&out.stderr)
+            ),
+        }),
+        Err(err) => json!({
+            "status": "failed",
+            "command": argv.join(" "),
+            "output": err.to_string(),
+        }),
+    }
+}
@@ -1,10 +1,58 @@
 pub mod nmap_normal_scan;
 pub mod advanced_nmap_scan;
+pub mod doctor;
+pub mod native_syn_scan;
+pub mod native_banner_grab;
 pub mod openvas_get_version;
+pub mod openvas_feed_status;
+pub mod openvas_sync_feed;
 pub mod openvas_list_configs;
+pub mod openvas_clone_scan_config;
+pub mod openvas_modify_scan_config;
+pub mod openvas_list_scanners;
 pub mod openvas_create_target;
+pub mod openvas_list_targets;
+pub mod openvas_delete_target;
+pub mod openvas_create_port_list;
+pub mod openvas_list_port_lists;
+pub mod openvas_create_credential;
+pub mod openvas_list_credentials;
+pub mod openvas_attach_credential;
+pub mod openvas_create_schedule;
+pub mod openvas_list_schedules;
+pub mod openvas_delete_schedule;
+pub mod openvas_create_alert;
 pub mod openvas_create_task;
+pub mod openvas_list_tasks;
+pub mod openvas_delete_task;
+pub mod openvas_modify_task;
 pub mod openvas_start_task;
+pub mod openvas_stop_task;
+pub mod openvas_resume_task;
 pub mod openvas_task_status;
+pub mod openvas_wait_for_task;
 pub mod openvas_get_report;
+pub mod openvas_get_results;
+pub mod openvas_get_nvt;
+pub mod openvas_list_hosts;
+pub mod openvas_get_host;
+pub mod openvas_create_ticket;
+pub mod openvas_list_tickets;
+pub mod openvas_modify_ticket;
+pub mod openvas_list_reports;
+pub mod openvas_delete_report;
+pub mod openvas_import_results;
+pub mod openvas_diff_reports;
+pub mod openvas_prioritize_findings;
+pub mod openvas_export_sarif;
+pub mod openvas_render_report;
+pub mod openvas_correlate_nmap;
+pub mod openvas_cleanup;
+pub mod openvas_list_trash;
+pub mod openvas_empty_trashcan;
+pub mod openvas_restore;
 
+pub mod openvas_bulk_create_targets;
+pub mod openvas_fan_out;
+pub mod openvas_full_scan;
+pub mod openvas_recommend_config;
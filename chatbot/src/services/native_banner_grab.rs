@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::native::{self, banner};
+
+/// Business-logic layer for the native banner grabber. Uses plain TCP
+/// connects, so unlike the SYN scanner it needs no special privileges and
+/// works over IPv6.
+pub async fn native_banner_grab(target: &str, ports: &str, timeout_ms: u64) -> Result<Value> {
+    let ports = native::parse_ports(ports)?;
+
+    let addr = tokio::net::lookup_host((target, 0))
+        .await?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("{target} did not resolve to an address"))?
+        .ip();
+
+    let services = banner::grab_all(addr, &ports, Duration::from_millis(timeout_ms)).await;
+    let identified = services.iter().filter(|s| s.service.is_some()).count();
+
+    Ok(json!({
+        "target": target,
+        "address": addr.to_string(),
+        "scanner": "native_banner",
+        "summary": format!("identified {identified} of {} ports", services.len()),
+        "services": services,
+    }))
+}
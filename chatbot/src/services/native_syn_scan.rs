@@ -0,0 +1,77 @@
+use std::net::IpAddr;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::native::{self, banner, syn_scan};
+
+/// Business-logic layer for the experimental pure-Rust SYN scanner. Resolves
+/// the target to IPv4, checks raw socket privileges up front, and shapes the
+/// per-port results like the other scan tools (open ports first). With
+/// `service_detection`, open ports are followed up by the banner grabber so
+/// services are identified without nmap's `-sV`. If the call is dropped
+/// (timeout, cancellation) the blocking scan stops sending probes.
+pub async fn native_syn_scan(
+    target: &str,
+    ports: Option<&str>,
+    timeout_ms: u64,
+    max_rate: u32,
+    service_detection: bool,
+) -> Result<Value> {
+    if max_rate == 0 {
+        anyhow::bail!("max_rate must be at least 1 packet per second");
+    }
+
+    let ports = match ports {
+        Some(spec) => native::parse_ports(spec)?,
+        None => native::TOP_PORTS.to_vec(),
+    };
+
+    let addr = tokio::net::lookup_host((target, 0))
+        .await?
+        .find_map(|a| match a.ip() {
+            IpAddr::V4(v4) => Some(v4),
+            IpAddr::V6(_) => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("{target} did not resolve to an IPv4 address"))?;
+
+    if !syn_scan::has_raw_socket_privileges() {
+        anyhow::bail!(
+            "native SYN scan needs raw socket privileges (run as root or grant CAP_NET_RAW); use nmap.scan instead"
+        );
+    }
+
+    let timeout = Duration::from_millis(timeout_ms);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let _cancel_on_drop = syn_scan::CancelOnDrop(cancel.clone());
+    let results =
+        tokio::task::spawn_blocking(move || syn_scan::scan(addr, &ports, timeout, max_rate, &cancel)).await??;
+
+    let open: Vec<u16> = results
+        .iter()
+        .filter(|(_, s)| **s == syn_scan::PortState::Open)
+        .map(|(p, _)| *p)
+        .collect();
+    let ports: Vec<Value> = results
+        .iter()
+        .map(|(p, s)| json!({ "port": p, "protocol": "tcp", "state": s }))
+        .collect();
+
+    let mut result = json!({
+        "target": target,
+        "address": addr.to_string(),
+        "scanner": "native_syn",
+        "summary": format!("{} open of {} scanned ports", open.len(), ports.len()),
+        "open_ports": open,
+        "ports": ports,
+    });
+
+    if service_detection {
+        result["services"] = json!(banner::grab_all(IpAddr::V4(addr), &open, timeout).await);
+    }
+
+    Ok(result)
+}
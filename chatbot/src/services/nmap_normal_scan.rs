@@ -1,11 +1,15 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::nmap;
+use crate::api::backend;
+use crate::models::nmap_scan::{ScanRequest, ScanResult, Timing};
 
 /// Business-logic layer for a "normal" Nmap scan using the Go backend.
-pub async fn nmap_normal_scan(target: &str, timing: Option<&str>) -> Result<Value> {
+pub async fn nmap_normal_scan(target: &str, timing: Option<Timing>) -> Result<ScanResult> {
     // In the future we can add validation, logging, or result shaping here.
-    nmap::scan_open_ports(target, timing).await
+    let request = ScanRequest {
+        target: target.to_string(),
+        timing,
+        ..Default::default()
+    };
+    backend::current().advanced_scan(&request).await
 }
-
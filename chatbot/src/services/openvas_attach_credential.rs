@@ -0,0 +1,20 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{AttachedCredential, CredentialKind};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS attach credential" using the Go
+/// backend. gvmd itself refuses to change a target a task already uses, and
+/// that refusal comes back as a backend error.
+pub async fn openvas_attach_credential(
+    target_id: &str,
+    credential_id: &str,
+    kind: CredentialKind,
+    port: Option<u16>,
+) -> Result<AttachedCredential> {
+    if kind != CredentialKind::Ssh && port.is_some() {
+        return Err(ToolError::invalid_input("port only applies to ssh credentials").into());
+    }
+    backend::current().attach_credential(target_id, credential_id, kind, port).await
+}
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use super::openvas_create_target::openvas_create_target;
+use crate::models::openvas::{BulkTarget, BulkTargets, GVM_MAX_HOSTS};
+use crate::targets;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for bulk target creation: splits `hosts` into
+/// chunks of at most `max_hosts` and creates one GVM target per chunk, named
+/// from `name_template` (`{n}`, `{total}` and `{date}` are filled in).
+/// Every target gets the same `port_range` or `port_list_id`.
+pub async fn openvas_bulk_create_targets(
+    hosts: &str,
+    max_hosts: u64,
+    name_template: &str,
+    port_range: Option<&str>,
+    port_list_id: Option<&str>,
+) -> Result<BulkTargets> {
+    if !(1..=GVM_MAX_HOSTS).contains(&max_hosts) {
+        return Err(ToolError::invalid_input(format!(
+            "max_hosts_per_target must be between 1 and gvmd's limit of {GVM_MAX_HOSTS}"
+        ))
+        .into());
+    }
+    if port_range.is_some_and(|p| !p.trim().is_empty()) && port_list_id.is_some_and(|p| !p.trim().is_empty()) {
+        return Err(ToolError::invalid_input("give either port_range or port_list_id, not both").into());
+    }
+    let chunks = targets::chunk_hosts(hosts, max_hosts).map_err(|err| ToolError::invalid_input(format!("{err:#}")))?;
+    if chunks.is_empty() {
+        return Err(ToolError::invalid_input("no hosts to create targets for").into());
+    }
+
+    let date = chrono::Utc::now().format("%Y%m%d").to_string();
+    let total = chunks.len();
+    let mut created = Vec::with_capacity(total);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let name = fill_name_template(name_template, i + 1, total, &date);
+        let hosts = chunk.join(",");
+
+        // On failure, report what already exists so the caller can use or
+        // clean it up; the context keeps the backend's error kind.
+        let result = openvas_create_target(&name, &hosts, port_range, port_list_id).await.with_context(|| {
+            let ids: Vec<&str> = created.iter().map(|t: &BulkTarget| t.id.as_str()).collect();
+            format!("creating target {} of {total} ({name}) failed; already created: {}", i + 1, json!(ids))
+        })?;
+        created.push(BulkTarget {
+            id: result.id,
+            existed: result.existed,
+            name,
+            host_count: targets::host_count(&hosts),
+            hosts,
+        });
+    }
+
+    Ok(BulkTargets {
+        count: created.len(),
+        target_ids: created.iter().map(|t| t.id.clone()).collect(),
+        targets: created,
+    })
+}
+
+/// Fill `{n}`, `{total}` and `{date}` into an object name template.
+pub fn fill_name_template(template: &str, n: usize, total: usize, date: &str) -> String {
+    template
+        .replace("{n}", &n.to_string())
+        .replace("{total}", &total.to_string())
+        .replace("{date}", date)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn splits_a_subnet_into_targets_and_reuses_them() {
+        let mock = Arc::new(MockBackend::default());
+        let (first, again) = with_backend(mock.clone(), async {
+            let create = || openvas_bulk_create_targets("10.1.0.0/24, 10.2.0.9", 64, "estate {n}/{total}", None, None);
+            (create().await.unwrap(), create().await.unwrap())
+        })
+        .await;
+        assert_eq!(first.count, 5);
+        assert_eq!(first.targets[0].name, "estate 1/5");
+        assert_eq!(first.targets[0].hosts, "10.1.0.0/26");
+        assert_eq!(first.targets.iter().map(|t| t.host_count).sum::<u64>(), 257);
+        assert_eq!(first.target_ids.len(), 5);
+        assert!(again.targets.iter().all(|t| t.existed));
+        assert_eq!(again.target_ids, first.target_ids);
+    }
+
+    #[tokio::test]
+    async fn refuses_bad_input_before_creating_anything() {
+        let mock = Arc::new(MockBackend::default());
+        let errors = with_backend(mock.clone(), async {
+            [
+                openvas_bulk_create_targets("10.0.0.1", 0, "t{n}", None, None).await,
+                openvas_bulk_create_targets("10.0.0.1", GVM_MAX_HOSTS + 1, "t{n}", None, None).await,
+                openvas_bulk_create_targets(" , ", 16, "t{n}", None, None).await,
+                openvas_bulk_create_targets("10.0.0.1", 16, "t{n}", Some("T:22"), Some("pl-1")).await,
+            ]
+        })
+        .await;
+        for result in errors {
+            let err = ToolError::classify(result.unwrap_err());
+            assert_eq!(err.kind(), "invalid_input", "{err:?}");
+        }
+        assert!(mock.calls().is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::CleanupResult;
+
+/// Business-logic layer for "OpenVAS cleanup" using the Go backend. Without
+/// `confirm` the backend only lists what would be deleted; the result then
+/// carries a hint on how to apply it, mirroring `doctor`'s install flow.
+pub async fn openvas_cleanup(
+    name_pattern: &str,
+    retention_days: u64,
+    empty_trashcan: bool,
+    confirm: bool,
+) -> Result<CleanupResult> {
+    let report = backend::current().cleanup(name_pattern, retention_days, empty_trashcan, confirm).await?;
+    let pending = !confirm;
+    Ok(CleanupResult {
+        report,
+        status: pending.then(|| "pending_confirmation".to_string()),
+        hint: pending.then(|| "re-run with confirm: true to delete the listed objects".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn unconfirmed_cleanup_asks_for_confirmation() {
+        let (listed, applied) = with_backend(Arc::new(MockBackend::default()), async {
+            (
+                openvas_cleanup("agent-*", 30, true, false).await.unwrap(),
+                openvas_cleanup("agent-*", 30, true, true).await.unwrap(),
+            )
+        })
+        .await;
+        let listed = serde_json::to_value(listed).unwrap();
+        assert_eq!(listed["status"], "pending_confirmation");
+        assert_eq!(listed["applied"], false);
+        assert_eq!(listed["trashcan_emptied"], false);
+        let applied = serde_json::to_value(applied).unwrap();
+        assert!(applied.get("status").is_none() && applied.get("hint").is_none());
+        assert_eq!(applied["trashcan_emptied"], true);
+    }
+}
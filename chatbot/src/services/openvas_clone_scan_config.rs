@@ -0,0 +1,16 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::CreatedObject;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS clone scan config" using the Go
+/// backend. gvmd's predefined configs cannot be changed, so tailored ones
+/// start as a copy, to be changed with `openvas_modify_scan_config`.
+pub async fn openvas_clone_scan_config(config_id: &str, name: &str, comment: Option<&str>) -> Result<CreatedObject> {
+    let (config_id, name) = (config_id.trim(), name.trim());
+    if config_id.is_empty() || name.is_empty() {
+        return Err(ToolError::invalid_input("config_id and name are required").into());
+    }
+    backend::current().clone_config(config_id, name, comment).await
+}
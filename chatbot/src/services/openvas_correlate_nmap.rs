@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::models::nmap_scan::NmapScanResult;
+use crate::models::openvas::{
+    CorrelatedFinding, CorrelatedScan, Exposure, NmapCorrelation, ReportFilter, ReportFormat,
+};
+use crate::resources;
+use crate::services::openvas_get_report::openvas_get_report;
+
+/// Port → nmap's state and service name for it, e.g. `443/tcp` →
+/// (`filtered`, `https`).
+type PortStates = BTreeMap<String, (String, String)>;
+
+/// The port states nmap's normal output lists per host, keyed by both the
+/// name and the address nmap reports the host under. Ports nmap folds into
+/// "Not shown" are left out.
+fn port_states(raw: &str) -> BTreeMap<String, PortStates> {
+    static LINES: OnceLock<(Regex, Regex)> = OnceLock::new();
+    let (host_line, port_line) = LINES.get_or_init(|| {
+        (
+            Regex::new(r"^Nmap scan report for (\S+)(?: \(([^)]+)\))?").expect("valid host line regex"),
+            Regex::new(r"^(\d+/(?:tcp|udp|sctp))\s+(\S+)\s+(\S+)").expect("valid port line regex"),
+        )
+    });
+
+    let mut hosts: BTreeMap<String, PortStates> = BTreeMap::new();
+    let mut names: Vec<String> = Vec::new();
+    for line in raw.lines() {
+        if let Some(caps) = host_line.captures(line) {
+            names = caps.iter().skip(1).flatten().map(|m| m.as_str().to_string()).collect();
+            for name in &names {
+                hosts.entry(name.clone()).or_default();
+            }
+        } else if let Some(caps) = port_line.captures(line) {
+            for name in &names {
+                let states = hosts.entry(name.clone()).or_default();
+                states.insert(caps[1].to_string(), (caps[2].to_string(), caps[3].to_string()));
+            }
+        }
+    }
+    hosts
+}
+
+/// `port_states` for a scan with XML output, from its parsed hosts.
+fn parsed_port_states(scan: &NmapScanResult) -> BTreeMap<String, PortStates> {
+    let mut hosts: BTreeMap<String, PortStates> = BTreeMap::new();
+    for host in &scan.hosts {
+        let states: PortStates = host
+            .ports
+            .iter()
+            .map(|p| {
+                let service = p.service.as_ref().map_or_else(|| "unknown".to_string(), |s| s.full_name());
+                (format!("{}/{}", p.port, p.protocol), (p.state.clone(), service))
+            })
+            .collect();
+        let names = host.hostnames.iter().map(|h| h.name.as_str()).chain(host.address());
+        for name in names {
+            hosts.entry(name.to_string()).or_default().extend(states.clone());
+        }
+    }
+    hosts
+}
+
+/// How exposed a port in nmap's `state` is.
+fn exposure(state: &str) -> Exposure {
+    match state {
+        "open" => Exposure::Reachable,
+        "closed" => Exposure::Closed,
+        // open|filtered, closed|filtered: no answer either way.
+        s if s.contains("filtered") && s != "unfiltered" => Exposure::Filtered,
+        _ => Exposure::NotScanned,
+    }
+}
+
+/// Business-logic layer for "OpenVAS correlate nmap": checks each finding
+/// of a report against the latest nmap scan of its host (or of
+/// `nmap_targets`), remembered from the nmap tools, to tell vulnerable
+/// services nmap could reach from those behind a firewall. Nothing is
+/// scanned; hosts without a scan are listed in `missing_scans`.
+pub async fn openvas_correlate_nmap(
+    report_id: &str,
+    filter: &ReportFilter,
+    nmap_targets: &[String],
+) -> Result<NmapCorrelation> {
+    let report = openvas_get_report(report_id, ReportFormat::Xml, filter, false).await?;
+    let finding_hosts: BTreeSet<String> = report.results.iter().map(|r| r.host.clone()).collect();
+    let targets: Vec<String> =
+        if nmap_targets.is_empty() { finding_hosts.iter().cloned().collect() } else { nmap_targets.to_vec() };
+
+    let mut hosts: BTreeMap<String, PortStates> = BTreeMap::new();
+    let mut scans = Vec::new();
+    let mut missing_scans = BTreeSet::new();
+    for target in targets {
+        match resources::latest_scan(&target) {
+            Some((scanned_at, output)) => {
+                let states = match output.get("parsed").and_then(|p| NmapScanResult::deserialize(p).ok()) {
+                    Some(parsed) => parsed_port_states(&parsed),
+                    None => port_states(output.get("raw_output").and_then(|v| v.as_str()).unwrap_or_default()),
+                };
+                for (host, states) in states {
+                    hosts.entry(host).or_default().extend(states);
+                }
+                scans.push(CorrelatedScan { target, scanned_at });
+            }
+            None => {
+                missing_scans.insert(target);
+            }
+        }
+    }
+
+    let mut findings: Vec<CorrelatedFinding> = report
+        .results
+        .into_iter()
+        .map(|finding| {
+            let Some(states) = hosts.get(&finding.host) else {
+                missing_scans.insert(finding.host.clone());
+                return CorrelatedFinding { finding, exposure: Exposure::NoScan, nmap_state: None, nmap_service: None };
+            };
+            if !finding.port.starts_with(|c: char| c.is_ascii_digit()) {
+                return CorrelatedFinding {
+                    finding,
+                    exposure: Exposure::HostLevel,
+                    nmap_state: None,
+                    nmap_service: None,
+                };
+            }
+            match states.get(&finding.port) {
+                Some((state, service)) => CorrelatedFinding {
+                    exposure: exposure(state),
+                    nmap_state: Some(state.clone()),
+                    nmap_service: Some(service.clone()),
+                    finding,
+                },
+                None => CorrelatedFinding {
+                    finding,
+                    exposure: Exposure::NotScanned,
+                    nmap_state: None,
+                    nmap_service: None,
+                },
+            }
+        })
+        .collect();
+    findings.sort_by(|a, b| a.exposure.cmp(&b.exposure).then(b.finding.severity.total_cmp(&a.finding.severity)));
+
+    let mut counts = BTreeMap::new();
+    for finding in &findings {
+        *counts.entry(finding.exposure).or_insert(0) += 1;
+    }
+    Ok(NmapCorrelation {
+        report_id: report.report_id,
+        counts,
+        findings,
+        scans,
+        missing_scans: missing_scans.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    fn finding(host: &str, port: &str, severity: f64) -> ReportResult {
+        ReportResult {
+            id: format!("{host}-{port}"),
+            name: format!("issue on {port}"),
+            host: host.to_string(),
+            port: port.to_string(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".to_string(),
+            severity,
+            threat: "High".to_string(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        }
+    }
+
+    #[test]
+    fn port_states_are_kept_per_host_name_and_address() {
+        let raw = "Nmap scan report for db.internal (10.77.0.5)\nPORT     STATE    SERVICE\n\
+                   22/tcp   open     ssh\n5432/tcp filtered postgresql\n\n\
+                   Nmap scan report for 10.77.0.6\n53/udp   open|filtered domain\n";
+        let hosts = port_states(raw);
+        assert_eq!(hosts["db.internal"], hosts["10.77.0.5"]);
+        assert_eq!(hosts["10.77.0.5"]["5432/tcp"], ("filtered".to_string(), "postgresql".to_string()));
+        assert_eq!(exposure(&hosts["10.77.0.6"]["53/udp"].0), Exposure::Filtered);
+        assert_eq!(exposure("unfiltered"), Exposure::NotScanned);
+
+        let parsed: NmapScanResult = serde_json::from_value(json!({ "hosts": [{
+            "status": "up",
+            "addresses": [{ "addr": "10.77.0.5", "addr_type": "ipv4" }],
+            "hostnames": [{ "name": "db.internal", "kind": "PTR" }],
+            "ports": [{ "protocol": "tcp", "port": 5432, "state": "filtered", "service": { "name": "postgresql" } }],
+        }] }))
+        .unwrap();
+        let from_xml = parsed_port_states(&parsed);
+        assert_eq!(from_xml["db.internal"], from_xml["10.77.0.5"]);
+        assert_eq!(from_xml["10.77.0.5"]["5432/tcp"], hosts["10.77.0.5"]["5432/tcp"]);
+    }
+
+    #[tokio::test]
+    async fn findings_are_ranked_by_what_nmap_could_reach() {
+        let raw = "Nmap scan report for 10.77.1.5\nPORT     STATE    SERVICE\n443/tcp  open     https\n\
+                   5432/tcp filtered postgresql\n8080/tcp closed   http-proxy\n";
+        resources::remember_scan("quick_tcp_scan", "10.77.1.5", &json!({ "target": "10.77.1.5", "raw_output": raw }));
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("10.77.1.5", "5432/tcp", 9.8),
+            finding("10.77.1.5", "443/tcp", 5.0),
+            finding("10.77.1.5", "8080/tcp", 7.5),
+            finding("10.77.1.5", "general/tcp", 2.6),
+            finding("10.77.1.5", "3389/tcp", 6.1),
+            finding("10.77.1.6", "22/tcp", 4.3),
+        ]));
+        let target = mock.create_target("web", "10.77.1.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let report_id = mock.list_reports(None).await.unwrap().reports[0].id.clone();
+
+        let correlation =
+            with_backend(mock, openvas_correlate_nmap(&report_id, &ReportFilter::default(), &[])).await.unwrap();
+        let ranked: Vec<(Exposure, &str)> =
+            correlation.findings.iter().map(|f| (f.exposure, f.finding.port.as_str())).collect();
+        assert_eq!(
+            ranked,
+            [
+                (Exposure::Reachable, "443/tcp"),
+                (Exposure::Filtered, "5432/tcp"),
+                (Exposure::Closed, "8080/tcp"),
+                (Exposure::HostLevel, "general/tcp"),
+                (Exposure::NotScanned, "3389/tcp"),
+                (Exposure::NoScan, "22/tcp"),
+            ]
+        );
+        assert_eq!(correlation.findings[0].nmap_service.as_deref(), Some("https"));
+        assert_eq!(correlation.counts[&Exposure::Reachable], 1);
+        assert_eq!(correlation.missing_scans, ["10.77.1.6"]);
+        assert_eq!(correlation.scans.len(), 1);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{AlertMethod, AlertSpec, CreateAlertArgs, CreatedObject, ReportFormat};
+use crate::tool_error::ToolError;
+
+/// gvmd's email `notice` values: a short notice, or the report attached.
+const EMAIL_SIMPLE_NOTICE: &str = "1";
+const EMAIL_ATTACH_REPORT: &str = "2";
+
+/// Business-logic layer for "OpenVAS create alert" using the Go backend.
+/// Translates the method's settings into gvmd's alert method data,
+/// refusing settings that belong to the other method so a misdirected
+/// alert fails here instead of silently never firing.
+pub async fn openvas_create_alert(args: &CreateAlertArgs) -> Result<CreatedObject> {
+    let mut method_data = BTreeMap::new();
+    match args.method {
+        AlertMethod::Email => {
+            if args.url.is_some() {
+                return Err(ToolError::invalid_input("url is for http_get alerts").into());
+            }
+            let Some(to_address) = args.to_address.as_deref().map(str::trim).filter(|a| a.contains('@')) else {
+                return Err(ToolError::invalid_input("email alerts need a to_address").into());
+            };
+            method_data.insert("to_address".to_string(), to_address.to_string());
+            if let Some(from_address) = &args.from_address {
+                method_data.insert("from_address".to_string(), from_address.trim().to_string());
+            }
+            if args.attach_report {
+                method_data.insert("notice".to_string(), EMAIL_ATTACH_REPORT.to_string());
+                method_data.insert("notice_attach_format".to_string(), ReportFormat::Txt.gvm_id().to_string());
+            } else {
+                method_data.insert("notice".to_string(), EMAIL_SIMPLE_NOTICE.to_string());
+            }
+        }
+        AlertMethod::HttpGet => {
+            if args.to_address.is_some() || args.from_address.is_some() || args.attach_report {
+                return Err(ToolError::invalid_input(
+                    "to_address, from_address and attach_report are for email alerts",
+                )
+                .into());
+            }
+            let Some(url) = args
+                .url
+                .as_deref()
+                .map(str::trim)
+                .filter(|u| u.starts_with("http://") || u.starts_with("https://"))
+            else {
+                return Err(ToolError::invalid_input("http_get alerts need an http:// or https:// url").into());
+            };
+            method_data.insert("URL".to_string(), url.to_string());
+        }
+    }
+    let spec = AlertSpec {
+        name: args.name.trim().to_string(),
+        method: args.method.gvm_method().to_string(),
+        status: args.on_status.gvm_status().to_string(),
+        method_data,
+        comment: args.comment.clone(),
+    };
+    backend::current().create_alert(&spec).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::TaskChanges;
+    use crate::services::openvas_modify_task::openvas_modify_task;
+
+    fn args(value: serde_json::Value) -> CreateAlertArgs {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn alerts_are_created_and_attached_to_tasks() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("nightly web", "config", &target.id, None, None).await.unwrap();
+
+        let missing = with_backend(mock.clone(), async {
+            let mail = args(json!({
+                "name": "mail", "method": "email", "to_address": "soc@example.com", "attach_report": true
+            }));
+            let hook = args(json!({
+                "name": "hook", "method": "http_get", "on_status": "Interrupted",
+                "url": "https://hooks.example.com/scan?task=$n"
+            }));
+            let mail = openvas_create_alert(&mail).await.unwrap();
+            let hook = openvas_create_alert(&hook).await.unwrap();
+            let changes = TaskChanges {
+                alert_ids: Some(vec![mail.id, hook.id]),
+                ..Default::default()
+            };
+            openvas_modify_task(&task.id, &changes).await.unwrap();
+
+            let changes = TaskChanges {
+                alert_ids: Some(vec!["alert-9".to_string()]),
+                ..Default::default()
+            };
+            openvas_modify_task(&task.id, &changes).await
+        })
+        .await;
+        assert!(missing.unwrap_err().to_string().contains("alert-9"));
+        assert_eq!(mock.calls()[2..4], ["create_alert mail", "create_alert hook"]);
+    }
+
+    #[tokio::test]
+    async fn settings_of_the_other_method_are_refused() {
+        let mock = Arc::new(MockBackend::default());
+        let refused = [
+            json!({ "name": "a", "method": "email" }),
+            json!({ "name": "b", "method": "email", "to_address": "soc" }),
+            json!({ "name": "c", "method": "email", "to_address": "soc@example.com", "url": "https://x" }),
+            json!({ "name": "d", "method": "http_get", "url": "ftp://x" }),
+            json!({ "name": "e", "method": "http_get", "url": "https://x", "attach_report": true }),
+        ];
+        with_backend(mock.clone(), async {
+            for value in refused {
+                let err = openvas_create_alert(&args(value.clone())).await.unwrap_err();
+                assert_eq!(ToolError::classify(err).kind(), "invalid_input", "{value}");
+            }
+        })
+        .await;
+        assert!(mock.calls().is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{CreatedObject, PortCount};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS create port list" using the Go
+/// backend. Checks `port_range` against gvmd's syntax first, so a typo is
+/// reported with the accepted form instead of gvmd's bare refusal.
+pub async fn openvas_create_port_list(name: &str, port_range: &str, comment: Option<&str>) -> Result<CreatedObject> {
+    let port_range = port_range.trim();
+    if PortCount::of_range(port_range).is_none() {
+        return Err(ToolError::invalid_input(format!(
+            "port_range '{port_range}' is not valid: use comma-separated ports (1-65535) and first-last ranges, \
+             with T: or U: before TCP or UDP ones, e.g. 'T:1-1024,8080,U:53,161'"
+        ))
+        .into());
+    }
+    backend::current().create_port_list(name.trim(), port_range, comment).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::services::openvas_create_target::openvas_create_target;
+    use crate::services::openvas_list_port_lists::openvas_list_port_lists;
+
+    #[tokio::test]
+    async fn created_port_lists_are_counted_and_usable_by_targets() {
+        let mock = Arc::new(MockBackend::default());
+        with_backend(mock.clone(), async {
+            let created = openvas_create_port_list("web", "T:80,443,8000-8009,U:53", None).await.unwrap();
+            let target = openvas_create_target("web", "10.0.0.5", None, Some(&created.id)).await.unwrap();
+
+            let lists = openvas_list_port_lists(Some("WEB")).await.unwrap().port_lists;
+            assert_eq!(lists.len(), 1);
+            assert_eq!(lists[0].port_count, PortCount { all: 13, tcp: 12, udp: 1 });
+            assert!(lists[0].in_use);
+            let targets = mock.list_targets().await.unwrap().targets;
+            assert_eq!(targets[0].id, target.id);
+            assert_eq!(targets[0].port_list.as_deref(), Some("web"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn bad_ranges_and_conflicting_ports_are_refused_before_the_backend() {
+        let mock = Arc::new(MockBackend::default());
+        with_backend(mock.clone(), async {
+            for range in ["", "T:0-10", "80-22", "X:80", "http"] {
+                let err = openvas_create_port_list("bad", range, None).await.unwrap_err();
+                assert!(err.to_string().contains("is not valid"), "{range}: {err}");
+            }
+            let err = openvas_create_target("web", "10.0.0.5", Some("1-1024"), Some("port_list-1")).await.unwrap_err();
+            assert!(err.to_string().contains("not both"), "{err}");
+            let err = openvas_create_target("web", "10.0.0.5", None, Some("port_list-9")).await.unwrap_err();
+            assert!(err.to_string().contains("port_list-9"), "{err}");
+        })
+        .await;
+        assert_eq!(mock.calls(), ["create_target web"]);
+    }
+}
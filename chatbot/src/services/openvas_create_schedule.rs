@@ -0,0 +1,115 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::api::backend;
+use crate::models::openvas::{CreatedObject, Recurrence, ScheduleSpec};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS create schedule" using the Go backend.
+/// Turns a first run and a recurrence into the iCalendar event gvmd stores
+/// and returns the new schedule's ID, to pass to `openvas_create_task`.
+pub async fn openvas_create_schedule(
+    name: &str,
+    first_run: &str,
+    recurrence: Recurrence,
+    timezone: Option<&str>,
+    comment: Option<&str>,
+) -> Result<CreatedObject> {
+    let first_run = DateTime::parse_from_rfc3339(first_run.trim())
+        .map_err(|err| ToolError::invalid_input(format!("first_run must be an RFC 3339 time: {err}")))?
+        .with_timezone(&Utc);
+    if recurrence == Recurrence::Once && first_run <= Utc::now() {
+        return Err(ToolError::invalid_input(format!(
+            "first_run {} is in the past, so a one-off schedule would never run",
+            first_run.to_rfc3339()
+        ))
+        .into());
+    }
+    let spec = ScheduleSpec {
+        name: name.trim().to_string(),
+        icalendar: icalendar(first_run, recurrence, Utc::now()),
+        timezone: timezone.map(str::trim).filter(|tz| !tz.is_empty()).map(str::to_string),
+        comment: comment.map(str::to_string),
+    };
+    backend::current().create_schedule(&spec).await
+}
+
+/// A VCALENDAR with one VEVENT starting at `first_run` and repeating at
+/// `recurrence`, as gvmd's `<icalendar>` expects.
+fn icalendar(first_run: DateTime<Utc>, recurrence: Recurrence, now: DateTime<Utc>) -> String {
+    const FORMAT: &str = "%Y%m%dT%H%M%SZ";
+    let rrule = match recurrence {
+        Recurrence::Once => None,
+        Recurrence::Hourly => Some("HOURLY"),
+        Recurrence::Daily => Some("DAILY"),
+        Recurrence::Weekly => Some("WEEKLY"),
+        Recurrence::Monthly => Some("MONTHLY"),
+    };
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//hacker_agent//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uuid::Uuid::new_v4()),
+        format!("DTSTAMP:{}", now.format(FORMAT)),
+        format!("DTSTART:{}", first_run.format(FORMAT)),
+    ];
+    if let Some(freq) = rrule {
+        lines.push(format!("RRULE:FREQ={freq}"));
+    }
+    lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".to_string()]);
+    lines.join("\r\n") + "\r\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::{BackendError, BackendErrorKind};
+    use crate::api::mock::MockBackend;
+    use crate::services::openvas_delete_schedule::openvas_delete_schedule;
+
+    #[test]
+    fn weekly_schedules_repeat_from_the_first_run() {
+        let first_run = DateTime::parse_from_rfc3339("2026-11-02T03:00:00+01:00").unwrap().with_timezone(&Utc);
+        let ical = icalendar(first_run, Recurrence::Weekly, first_run);
+        assert!(ical.contains("\r\nDTSTART:20261102T020000Z\r\nRRULE:FREQ=WEEKLY\r\n"));
+        assert!(!icalendar(first_run, Recurrence::Once, first_run).contains("RRULE"));
+    }
+
+    #[tokio::test]
+    async fn scheduled_tasks_keep_their_schedule() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+
+        let (schedule, past, unused) = with_backend(mock.clone(), async {
+            (
+                openvas_create_schedule("weekly", "2026-01-05T02:00:00Z", Recurrence::Weekly, None, None).await,
+                openvas_create_schedule("once", "2020-01-01T00:00:00Z", Recurrence::Once, None, None).await,
+                openvas_create_schedule("unused", "2020-01-01T00:00:00Z", Recurrence::Daily, None, None).await,
+            )
+        })
+        .await;
+        let schedule = schedule.unwrap();
+        assert_eq!(ToolError::classify(past.unwrap_err()).kind(), "invalid_input");
+        mock.create_task("weekly web", "config", &target.id, Some(&schedule.id), None).await.unwrap();
+
+        let list = mock.list_schedules().await.unwrap();
+        assert_eq!(list.schedules.len(), 2);
+        assert!(list.schedules[0].in_use);
+        assert_eq!(list.schedules[0].timezone, "UTC");
+
+        let (refused, deleted) = with_backend(mock, async {
+            (
+                openvas_delete_schedule(&schedule.id, false).await,
+                openvas_delete_schedule(&unused.unwrap().id, true).await,
+            )
+        })
+        .await;
+        let err = refused.unwrap_err();
+        assert_eq!(err.downcast_ref::<BackendError>().unwrap().kind, BackendErrorKind::InvalidRequest);
+        assert!(deleted.unwrap().ultimate);
+    }
+}
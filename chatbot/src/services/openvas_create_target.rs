@@ -1,17 +1,24 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::CreatedObject;
+use crate::tool_error::ToolError;
 
 /// Business-logic layer for "OpenVAS create target" using the Go backend.
-/// For now this is a thin wrapper around the low-level HTTP client.
-/// It returns the raw JSON from the Go API, which includes the created
-/// target ID under the `id` field.
+/// For now this is a thin wrapper around the low-level HTTP client; it only
+/// refuses a target given both a `port_range` and a `port_list_id`, which
+/// gvmd would otherwise settle by ignoring one of them.
+/// It returns the created target ID under the `id` field.
 pub async fn openvas_create_target(
     name: &str,
     hosts: &str,
     port_range: Option<&str>,
-) -> Result<Value> {
-    openvas::create_target(name, hosts, port_range).await
+    port_list_id: Option<&str>,
+) -> Result<CreatedObject> {
+    let port_range = port_range.map(str::trim).filter(|p| !p.is_empty());
+    let port_list_id = port_list_id.map(str::trim).filter(|p| !p.is_empty());
+    if port_range.is_some() && port_list_id.is_some() {
+        return Err(ToolError::invalid_input("give either port_range or port_list_id, not both").into());
+    }
+    backend::current().create_target(name, hosts, port_range, port_list_id).await
 }
-
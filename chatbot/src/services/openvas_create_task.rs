@@ -1,18 +1,20 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::CreatedObject;
 
 /// Business-logic layer for "OpenVAS create task" using the Go backend.
 /// This is a thin wrapper around the low-level HTTP client and returns
-/// the raw JSON from the Go API, which includes the created task ID
-/// under the `id` field and an `existed` flag when a matching task
-/// already existed.
+/// the created task ID under the `id` field and an `existed` flag that
+/// is set when a matching task already existed. With a `schedule_id` gvmd
+/// starts the task at the scheduled times; with a `scanner_id` it runs on
+/// that scanner instead of the default one.
 pub async fn openvas_create_task(
     name: &str,
     config_id: &str,
     target_id: &str,
-) -> Result<Value> {
-    openvas::create_task(name, config_id, target_id).await
+    schedule_id: Option<&str>,
+    scanner_id: Option<&str>,
+) -> Result<CreatedObject> {
+    backend::current().create_task(name, config_id, target_id, schedule_id, scanner_id).await
 }
-
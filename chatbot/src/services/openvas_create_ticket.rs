@@ -0,0 +1,62 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::CreatedObject;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS create ticket" using the Go backend.
+/// gvmd wants a result, an assignee and an open note for every ticket, so
+/// a missing one is reported before gvmd is asked.
+pub async fn openvas_create_ticket(result_id: &str, assignee: &str, note: &str) -> Result<CreatedObject> {
+    let (result_id, assignee, note) = (result_id.trim(), assignee.trim(), note.trim());
+    if result_id.is_empty() || assignee.is_empty() || note.is_empty() {
+        return Err(ToolError::invalid_input(
+            "result_id (a finding's id from openvas_get_report), assignee (a gvmd user name) and note are required",
+        )
+        .into());
+    }
+    backend::current().create_ticket(result_id, assignee, note).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    #[tokio::test]
+    async fn findings_become_tickets_for_known_users() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![ReportResult {
+            id: "result-1".into(),
+            name: "OpenSSH Multiple Vulnerabilities".into(),
+            host: "10.0.0.5".into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity: 7.8,
+            threat: "High".into(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        }]));
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        with_backend(mock.clone(), async {
+            let created = openvas_create_ticket(" result-1 ", "ops", "Upgrade OpenSSH to 9.x").await.unwrap();
+            let ticket = backend::current().list_tickets().await.unwrap().tickets.remove(0);
+            assert_eq!(ticket.id, created.id);
+            assert_eq!((ticket.status.as_str(), ticket.assignee.as_deref()), ("Open", Some("ops")));
+            assert_eq!(ticket.task_id.as_deref(), Some(task.id.as_str()));
+
+            let err = openvas_create_ticket("result-1", "nobody", "Upgrade").await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "backend_error");
+            let err = openvas_create_ticket("result-1", "ops", " ").await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "invalid_input");
+        })
+        .await;
+    }
+}
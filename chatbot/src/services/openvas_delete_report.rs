@@ -0,0 +1,44 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::DeletedObject;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS delete report" using the Go backend.
+/// Thin wrapper: reports skip the trashcan, and gvmd itself refuses to
+/// delete the report of a task that is still running.
+pub async fn openvas_delete_report(report_id: &str) -> Result<DeletedObject> {
+    let report_id = report_id.trim();
+    if report_id.is_empty() {
+        return Err(ToolError::invalid_input("report_id is empty").into());
+    }
+    backend::current().delete_report(report_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::BackendErrorKind;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn deleted_reports_leave_the_task() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let report_id = mock.list_reports(None).await.unwrap().reports[0].id.clone();
+
+        let (deleted, again) = with_backend(mock.clone(), async {
+            (openvas_delete_report(&report_id).await, openvas_delete_report(&report_id).await)
+        })
+        .await;
+        assert!(deleted.unwrap().ultimate);
+        let again = again.unwrap_err();
+        assert_eq!(again.downcast_ref::<crate::api::errors::BackendError>().unwrap().kind, BackendErrorKind::NotFound);
+        assert!(mock.list_reports(None).await.unwrap().reports.is_empty());
+    }
+}
@@ -0,0 +1,11 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::DeletedObject;
+
+/// Business-logic layer for "OpenVAS delete schedule" using the Go backend.
+/// Thin wrapper: gvmd itself refuses to delete a schedule a task still
+/// uses, and that refusal comes back as a backend error.
+pub async fn openvas_delete_schedule(schedule_id: &str, ultimate: bool) -> Result<DeletedObject> {
+    backend::current().delete_schedule(schedule_id, ultimate).await
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::DeletedObject;
+
+/// Business-logic layer for "OpenVAS delete target" using the Go backend.
+/// Thin wrapper: gvmd itself refuses to delete a target a task still uses,
+/// and that refusal comes back as a backend error.
+pub async fn openvas_delete_target(target_id: &str, ultimate: bool) -> Result<DeletedObject> {
+    backend::current().delete_target(target_id, ultimate).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::{BackendError, BackendErrorKind};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn targets_in_use_are_refused() {
+        let mock = Arc::new(MockBackend::default());
+        let stale = mock.create_target("stale", "10.0.0.7", None, None).await.unwrap();
+        let used = mock.create_target("used", "10.0.0.8", None, None).await.unwrap();
+        mock.create_task("scan used", "config", &used.id, None, None).await.unwrap();
+
+        let (deleted, refused, again) = with_backend(mock.clone(), async {
+            (
+                openvas_delete_target(&stale.id, true).await,
+                openvas_delete_target(&used.id, false).await,
+                openvas_delete_target(&stale.id, false).await,
+            )
+        })
+        .await;
+        assert!(deleted.unwrap().ultimate);
+        let kind = |err: anyhow::Error| err.downcast_ref::<BackendError>().unwrap().kind;
+        assert_eq!(kind(refused.unwrap_err()), BackendErrorKind::InvalidRequest);
+        assert_eq!(kind(again.unwrap_err()), BackendErrorKind::NotFound);
+        assert_eq!(mock.list_targets().await.unwrap().targets.len(), 1);
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{DeletedObject, BUSY_TASK_STATUSES};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS delete task" using the Go backend.
+/// gvmd would stop a running task to delete it, so unless `force` is set
+/// the task's status is checked first and busy tasks are refused.
+pub async fn openvas_delete_task(task_id: &str, ultimate: bool, force: bool) -> Result<DeletedObject> {
+    if !force {
+        let state = backend::current().get_task_status(task_id).await?;
+        if let Some(status) = state.status.filter(|s| BUSY_TASK_STATUSES.contains(&s.as_str())) {
+            return Err(ToolError::invalid_input(format!(
+                "task {task_id} is {status}; stop it first (openvas_stop_task) or pass force: true"
+            ))
+            .into());
+        }
+    }
+    backend::current().delete_task(task_id, ultimate).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn running_tasks_need_force() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let (refused, forced) = with_backend(mock.clone(), async {
+            (
+                openvas_delete_task(&task.id, false, false).await,
+                openvas_delete_task(&task.id, false, true).await,
+            )
+        })
+        .await;
+        let refused = ToolError::classify(refused.unwrap_err());
+        assert_eq!(refused.kind(), "invalid_input");
+        assert!(refused.to_string().contains("is Running"));
+        assert_eq!(forced.unwrap().id, task.id);
+        assert!(mock.list_tasks().await.unwrap().tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn finished_tasks_are_deleted_without_force() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let deleted = with_backend(mock.clone(), openvas_delete_task(&task.id, true, false)).await;
+        assert!(deleted.unwrap().ultimate);
+        assert_eq!(mock.calls()[3..], ["get_task_status task-2", "delete_task task-2"]);
+    }
+}
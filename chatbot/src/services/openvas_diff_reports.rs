@@ -0,0 +1,156 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::models::openvas::{DiffCounts, ReportDiff, ReportFilter, ReportFormat, ReportResult};
+use crate::services::openvas_get_report::openvas_get_report;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS diff reports" using the Go backend.
+/// Fetches both reports' findings and splits them into those only the
+/// later report has (new), those only the base report has (fixed) and
+/// those in both, so a re-scan after remediation shows what it changed.
+/// `filter` applies to both reports alike.
+pub async fn openvas_diff_reports(
+    base_report_id: &str,
+    report_id: &str,
+    filter: &ReportFilter,
+    include_unchanged: bool,
+) -> Result<ReportDiff> {
+    let (base_report_id, report_id) = (base_report_id.trim(), report_id.trim());
+    if base_report_id == report_id {
+        return Err(ToolError::invalid_input("base_report_id and report_id must be different reports").into());
+    }
+    let base = openvas_get_report(base_report_id, ReportFormat::Xml, filter, false).await?;
+    let later = openvas_get_report(report_id, ReportFormat::Xml, filter, false).await?;
+
+    let mut before = by_finding(base.results);
+    let mut new = Vec::new();
+    let mut unchanged = Vec::new();
+    for (key, result) in by_finding(later.results) {
+        match before.remove(&key) {
+            Some(_) => unchanged.push(result),
+            None => new.push(result),
+        }
+    }
+    let mut fixed: Vec<ReportResult> = before.into_values().collect();
+    for list in [&mut new, &mut fixed, &mut unchanged] {
+        list.sort_by(|a, b| b.severity.partial_cmp(&a.severity).unwrap_or(Ordering::Equal));
+    }
+    Ok(ReportDiff {
+        base_report_id: base_report_id.to_string(),
+        report_id: report_id.to_string(),
+        counts: DiffCounts {
+            new: new.len(),
+            fixed: fixed.len(),
+            unchanged: unchanged.len(),
+        },
+        new,
+        fixed,
+        unchanged: include_unchanged.then_some(unchanged),
+    })
+}
+
+/// Results keyed by what identifies a finding across scans: result IDs
+/// differ between reports, host, port and NVT do not. Of duplicates, the
+/// most severe is kept.
+fn by_finding(results: Vec<ReportResult>) -> BTreeMap<(String, String, String), ReportResult> {
+    let mut findings = BTreeMap::new();
+    for result in results {
+        let key = (result.host.clone(), result.port.clone(), result.nvt_oid.clone());
+        if findings.get(&key).is_none_or(|kept: &ReportResult| kept.severity < result.severity) {
+            findings.insert(key, result);
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    fn finding(host: &str, oid: &str, severity: f64) -> ReportResult {
+        ReportResult {
+            id: format!("result-{host}-{oid}-{severity}"),
+            name: format!("finding {oid}"),
+            host: host.into(),
+            port: "443/tcp".into(),
+            nvt_oid: oid.into(),
+            severity,
+            threat: if severity >= 7.0 { "High" } else { "Medium" }.into(),
+            qod: Some(80),
+            cves: Vec::new(),
+            solution: None,
+        }
+    }
+
+    async fn run(mock: &MockBackend, task_id: &str) -> String {
+        mock.start_task(task_id).await.unwrap();
+        mock.get_task_status(task_id).await.unwrap().last_report_id.unwrap()
+    }
+
+    #[tokio::test]
+    async fn rescans_split_into_new_fixed_and_unchanged() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("10.0.0.5", "1.1", 9.8),
+            finding("10.0.0.5", "1.2", 5.0),
+            finding("10.0.0.6", "1.1", 9.8),
+        ]));
+        let target = mock.create_target("web", "10.0.0.5-6", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        let before = run(&mock, &task.id).await;
+        mock.set_findings(vec![
+            finding("10.0.0.5", "1.2", 5.0),
+            finding("10.0.0.6", "1.3", 7.5),
+            finding("10.0.0.6", "1.4", 4.0),
+        ]);
+        let after = run(&mock, &task.id).await;
+
+        let (diff, serious, same) = with_backend(mock, async {
+            (
+                openvas_diff_reports(&before, &after, &ReportFilter::default(), true).await.unwrap(),
+                openvas_diff_reports(
+                    &before,
+                    &after,
+                    &ReportFilter {
+                        min_severity: Some(7.0),
+                        min_qod: None,
+                    },
+                    false,
+                )
+                .await
+                .unwrap(),
+                openvas_diff_reports(&after, &after, &ReportFilter::default(), true).await,
+            )
+        })
+        .await;
+        assert_eq!(
+            diff.counts,
+            DiffCounts {
+                new: 2,
+                fixed: 2,
+                unchanged: 1,
+            }
+        );
+        let oids = |list: &[ReportResult]| list.iter().map(|r| r.nvt_oid.clone()).collect::<Vec<_>>();
+        assert_eq!(oids(&diff.new), ["1.3", "1.4"]);
+        assert_eq!(diff.fixed.iter().map(|r| r.host.as_str()).collect::<Vec<_>>(), ["10.0.0.5", "10.0.0.6"]);
+        assert_eq!(oids(diff.unchanged.as_deref().unwrap()), ["1.2"]);
+
+        assert_eq!((serious.counts.new, serious.counts.fixed, serious.counts.unchanged), (1, 2, 0));
+        assert!(serious.unchanged.is_none());
+        assert!(same.is_err());
+    }
+
+    #[test]
+    fn duplicate_findings_keep_the_most_severe() {
+        let findings = by_finding(vec![finding("10.0.0.5", "1.1", 5.0), finding("10.0.0.5", "1.1", 9.8)]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings.values().next().unwrap().severity, 9.8);
+    }
+}
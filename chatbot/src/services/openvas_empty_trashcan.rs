@@ -0,0 +1,58 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::EmptyTrashcanResult;
+
+/// Business-logic layer for "OpenVAS empty trashcan" using the Go backend.
+/// The trashcan is listed first, so the result shows what was (or, without
+/// `confirm`, would be) deleted for good; an empty trashcan is left alone.
+pub async fn openvas_empty_trashcan(confirm: bool) -> Result<EmptyTrashcanResult> {
+    let objects = backend::current().list_trash().await?.objects;
+    let pending = !confirm && !objects.is_empty();
+    let emptied = confirm && !objects.is_empty();
+    if emptied {
+        backend::current().empty_trashcan().await?;
+    }
+    Ok(EmptyTrashcanResult {
+        emptied,
+        count: objects.len(),
+        objects,
+        status: pending.then(|| "pending_confirmation".to_string()),
+        hint: pending.then(|| "re-run with confirm: true to delete the listed objects for good".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn unconfirmed_emptying_only_lists_the_trashcan() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.delete_task(&task.id, false).await.unwrap();
+        mock.delete_target(&target.id, false).await.unwrap();
+
+        let (listed, emptied, nothing) = with_backend(mock.clone(), async {
+            (
+                openvas_empty_trashcan(false).await.unwrap(),
+                openvas_empty_trashcan(true).await.unwrap(),
+                openvas_empty_trashcan(true).await.unwrap(),
+            )
+        })
+        .await;
+        assert!(!listed.emptied);
+        assert_eq!(listed.status.as_deref(), Some("pending_confirmation"));
+        let kinds: Vec<&str> = listed.objects.iter().map(|o| o.kind.as_str()).collect();
+        assert_eq!(kinds, ["task", "target"]);
+        assert!(emptied.emptied && emptied.status.is_none());
+        assert_eq!(emptied.count, 2);
+        assert!(!nothing.emptied && nothing.status.is_none());
+        assert_eq!(mock.calls().iter().filter(|c| c.starts_with("empty_trashcan")).count(), 1);
+    }
+}
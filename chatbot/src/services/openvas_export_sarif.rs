@@ -0,0 +1,74 @@
+use anyhow::Result;
+
+use crate::models::openvas::{ReportFilter, ReportFormat, SarifExport};
+use crate::services::openvas_get_report::openvas_get_report;
+use crate::{resources, sarif};
+
+/// Business-logic layer for "OpenVAS export SARIF" using the Go backend.
+/// Converts a report's findings into a SARIF 2.1.0 log for GitHub code
+/// scanning or DefectDojo; with `as_resource` the log is saved as
+/// `openvas://report/{report_id}/sarif` and only its URI is returned.
+pub async fn openvas_export_sarif(report_id: &str, filter: &ReportFilter, as_resource: bool) -> Result<SarifExport> {
+    let report = openvas_get_report(report_id, ReportFormat::Xml, filter, false).await?;
+    let log = sarif::from_report(&report.report_id, &report.results);
+    let mut export = SarifExport {
+        report_id: report.report_id,
+        result_count: report.results.len(),
+        sarif: None,
+        resource_uri: None,
+    };
+    if as_resource {
+        let text = serde_json::to_string_pretty(&log)?;
+        export.resource_uri = Some(resources::save_export(&export.report_id, "sarif", "application/sarif+json", text));
+    } else {
+        export.sarif = Some(log);
+    }
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    #[tokio::test]
+    async fn saved_logs_are_readable_resources() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![ReportResult {
+            id: "result-1".into(),
+            name: "OpenSSH Multiple Vulnerabilities".into(),
+            host: "10.0.0.5".into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity: 7.8,
+            threat: "High".into(),
+            qod: Some(80),
+            cves: vec!["CVE-2016-6210".into()],
+            solution: None,
+        }]));
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let report_id = mock.get_task_status(&task.id).await.unwrap().last_report_id.unwrap();
+
+        let (inline, saved) = with_backend(mock, async {
+            (
+                openvas_export_sarif(&report_id, &ReportFilter::default(), false).await.unwrap(),
+                openvas_export_sarif(&report_id, &ReportFilter::default(), true).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(inline.result_count, 1);
+        assert_eq!(inline.sarif.unwrap()["runs"][0]["results"][0]["level"], "error");
+
+        let uri = saved.resource_uri.unwrap();
+        assert_eq!(uri, format!("openvas://report/{report_id}/sarif"));
+        assert!(saved.sarif.is_none());
+        let read = resources::read(&uri).await.unwrap();
+        assert_eq!(read["contents"][0]["mimeType"], "application/sarif+json");
+        assert!(read["contents"][0]["text"].as_str().unwrap().contains("\"version\": \"2.1.0\""));
+    }
+}
@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::api::backend;
+use crate::models::openvas::{
+    FanOutResult, FanOutSummary, FanOutTask, ReportFilter, ReportFormat, ReportResult, FINISHED_TASK_STATUSES,
+};
+use crate::partial;
+
+fn finished(task: &FanOutTask) -> bool {
+    FINISHED_TASK_STATUSES.contains(&task.status.as_str())
+}
+
+/// Business-logic layer for task fan-out: creates and starts one task per
+/// `(target_id, name)` pair, polls them all every `poll_interval` until
+/// every task has finished, then merges the findings of their reports into
+/// one result. Progress so far is reported as partial output.
+pub async fn openvas_fan_out(
+    targets: &[(String, String)],
+    config_id: &str,
+    poll_interval: Duration,
+) -> Result<FanOutResult> {
+    if targets.is_empty() {
+        anyhow::bail!("no targets to scan");
+    }
+
+    let mut tasks: Vec<FanOutTask> = Vec::with_capacity(targets.len());
+    for (i, (target_id, name)) in targets.iter().enumerate() {
+        let started = async {
+            let created = backend::current().create_task(name, config_id, target_id, None, None).await?;
+            backend::current().start_task(&created.id).await?;
+            anyhow::Ok(created.id)
+        };
+        let task_id = match started.await {
+            Ok(task_id) => task_id,
+            Err(err) => {
+                // Tasks already running keep running; name them so the
+                // caller can follow or stop them.
+                let ids: Vec<&str> = tasks.iter().map(|t| t.task_id.as_str()).collect();
+                anyhow::bail!(
+                    "starting task {} of {} ({name}) failed: {err}; already started: {}",
+                    i + 1,
+                    targets.len(),
+                    json!(ids)
+                );
+            }
+        };
+        tasks.push(FanOutTask {
+            task_id,
+            target_id: target_id.clone(),
+            name: name.clone(),
+            status: "Requested".to_string(),
+            progress: 0,
+            report_id: None,
+            result_count: None,
+        });
+    }
+
+    loop {
+        for task in tasks.iter_mut().filter(|t| !finished(t)) {
+            let state = backend::current().get_task_status(&task.task_id).await?;
+            let Some(status) = state.status else {
+                anyhow::bail!("backend returned no parsed status for task {}", task.task_id);
+            };
+            task.status = status;
+            task.progress = state.progress.unwrap_or(0);
+            if state.last_report_id.is_some() {
+                task.report_id = state.last_report_id;
+            }
+        }
+        partial::report(json!({ "complete": false, "tasks": tasks }));
+        if tasks.iter().all(finished) {
+            break;
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+
+    let mut findings = Vec::new();
+    for task in &mut tasks {
+        let results = match &task.report_id {
+            Some(report_id) => {
+                backend::current()
+                    .get_report(report_id, ReportFormat::Xml, &ReportFilter::default())
+                    .await?
+                    .results
+            }
+            None => Vec::new(),
+        };
+        task.result_count = Some(results.len());
+        findings.extend(results);
+    }
+
+    let findings = merge_findings(findings);
+    let mut by_threat: BTreeMap<String, u64> = BTreeMap::new();
+    for finding in &findings {
+        *by_threat.entry(finding.threat.clone()).or_default() += 1;
+    }
+
+    Ok(FanOutResult {
+        complete: tasks.iter().all(|t| t.status == "Done"),
+        tasks,
+        summary: FanOutSummary {
+            findings: findings.len(),
+            by_threat,
+        },
+        findings,
+    })
+}
+
+/// Drop findings reported more than once for the same host, port and NVT
+/// (overlapping targets), keeping the most severe, and sort what is left by
+/// severity, highest first.
+fn merge_findings(findings: Vec<ReportResult>) -> Vec<ReportResult> {
+    let mut merged: BTreeMap<(String, String, String), ReportResult> = BTreeMap::new();
+    for finding in findings {
+        let key = (finding.host.clone(), finding.port.clone(), finding.nvt_oid.clone());
+        match merged.get(&key) {
+            Some(existing) if existing.severity >= finding.severity => {}
+            _ => {
+                merged.insert(key, finding);
+            }
+        }
+    }
+
+    let mut findings: Vec<ReportResult> = merged.into_values().collect();
+    findings.sort_by(|a, b| b.severity.partial_cmp(&a.severity).unwrap_or(Ordering::Equal));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::BackendError;
+    use crate::api::mock::MockBackend;
+
+    fn finding(host: &str, severity: f64, threat: &str) -> ReportResult {
+        ReportResult {
+            id: format!("result-{host}-{severity}"),
+            name: "OpenSSH vulnerability".to_string(),
+            host: host.to_string(),
+            port: "22/tcp".to_string(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".to_string(),
+            severity,
+            threat: threat.to_string(),
+            qod: Some(80),
+            cves: Vec::new(),
+            solution: None,
+        }
+    }
+
+    async fn targets(mock: &MockBackend, hosts: &[&str]) -> Vec<(String, String)> {
+        let mut targets = Vec::new();
+        for host in hosts {
+            let created = mock.create_target(host, host, None, None).await.unwrap();
+            targets.push((created.id, format!("fan-out {host}")));
+        }
+        targets
+    }
+
+    #[tokio::test]
+    async fn fan_out_merges_the_findings_of_every_task() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("10.0.0.5", 5.0, "Medium"),
+            finding("10.0.0.5", 7.8, "High"),
+            finding("10.0.0.6", 7.8, "High"),
+        ]));
+        let targets = targets(&mock, &["10.0.0.5", "10.0.0.6"]).await;
+
+        let result = with_backend(mock.clone(), openvas_fan_out(&targets, "config", Duration::ZERO))
+            .await
+            .unwrap();
+        assert!(result.complete);
+        assert_eq!(result.tasks.len(), 2);
+        // Both reports hold the same findings; each host/port/NVT is kept once, at its highest severity.
+        assert_eq!(result.summary.findings, 2);
+        assert_eq!(result.summary.by_threat["High"], 2);
+        assert!(result.findings.iter().all(|f| f.severity == 7.8));
+    }
+
+    #[tokio::test]
+    async fn fan_out_names_tasks_already_started_when_one_fails() {
+        let mock = Arc::new(MockBackend::default());
+        let targets = targets(&mock, &["10.0.0.5", "10.0.0.6"]).await;
+        let run = async {
+            let first = openvas_fan_out(&targets[..1], "config", Duration::ZERO).await.unwrap();
+            mock.fail_next(BackendError::from_response(503, "service unavailable"));
+            let err = openvas_fan_out(&targets, "config", Duration::ZERO).await.unwrap_err();
+            (first, err)
+        };
+        let (first, err) = with_backend(mock.clone(), run).await;
+        assert!(first.complete);
+        assert!(err.to_string().contains("starting task 1 of 2"), "{err}");
+    }
+}
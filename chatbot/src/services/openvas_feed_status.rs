@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::api::backend;
+use crate::models::openvas::FeedList;
+
+/// Business-logic layer for "OpenVAS feed status" using the Go backend.
+/// Dates each feed from its version and warns about feeds older than
+/// `max_age_days` or still syncing, since scans with stale NVTs miss recent
+/// vulnerabilities.
+pub async fn openvas_feed_status(max_age_days: u64) -> Result<FeedList> {
+    let list = backend::current().list_feeds().await?;
+    Ok(assess(list, max_age_days, Utc::now()))
+}
+
+/// The time a feed version stands for. gvmd reports `YYYYMMDDhhmm`; some
+/// feeds use `YYYYMMDDThhmm`, seconds, or just the date.
+fn feed_time(version: &str) -> Option<DateTime<Utc>> {
+    let digits: String = version.chars().filter(char::is_ascii_digit).collect();
+    let time = match digits.len() {
+        8 => NaiveDate::parse_from_str(&digits, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0)?,
+        12 => NaiveDateTime::parse_from_str(&digits, "%Y%m%d%H%M").ok()?,
+        14 => NaiveDateTime::parse_from_str(&digits, "%Y%m%d%H%M%S").ok()?,
+        _ => return None,
+    };
+    Some(time.and_utc())
+}
+
+/// `list` with each feed's age filled in as of `now`, and warnings about
+/// stale, syncing and missing feeds.
+fn assess(mut list: FeedList, max_age_days: u64, now: DateTime<Utc>) -> FeedList {
+    for feed in &mut list.feeds {
+        if feed.currently_syncing {
+            list.warnings.push(format!(
+                "the {} feed is syncing; scans started before it finishes may use partial data",
+                feed.feed_type
+            ));
+        }
+        let Some(updated) = feed_time(&feed.version) else {
+            list.warnings.push(format!(
+                "the {} feed's version '{}' is not a date, so its age is unknown",
+                feed.feed_type, feed.version
+            ));
+            continue;
+        };
+        let age_hours = (now - updated).num_hours().max(0) as u64;
+        feed.last_update = Some(updated.to_rfc3339_opts(SecondsFormat::Secs, true));
+        feed.age_hours = Some(age_hours);
+        feed.stale = age_hours > max_age_days * 24;
+        if feed.stale {
+            list.warnings.push(format!(
+                "the {} feed was last updated {} days ago; scans may miss recent vulnerabilities until it is synced \
+                 (openvas_sync_feed)",
+                feed.feed_type,
+                age_hours / 24
+            ));
+        }
+    }
+    if !list.feeds.iter().any(|f| f.feed_type == "NVT") {
+        list.warnings.push("gvmd reports no NVT feed: scans have no vulnerability tests until it is synced".to_string());
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::openvas::FeedSummary;
+
+    fn feed(feed_type: &str, version: &str, currently_syncing: bool) -> FeedSummary {
+        FeedSummary {
+            feed_type: feed_type.into(),
+            name: format!("Greenbone Community {feed_type} Feed"),
+            version: version.into(),
+            description: String::new(),
+            currently_syncing,
+            last_update: None,
+            age_hours: None,
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn feed_versions_are_dated() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(feed_time("202401250530"), Some(at("2024-01-25T05:30:00Z")));
+        assert_eq!(feed_time("20240125T0530"), Some(at("2024-01-25T05:30:00Z")));
+        assert_eq!(feed_time("20240125"), Some(at("2024-01-25T00:00:00Z")));
+        assert_eq!(feed_time("22.04"), None);
+        assert_eq!(feed_time("202413250530"), None);
+    }
+
+    #[test]
+    fn old_syncing_and_missing_feeds_are_warned_about() {
+        let now = DateTime::parse_from_rfc3339("2024-02-05T06:00:00Z").unwrap().with_timezone(&Utc);
+        let list = FeedList {
+            feeds: vec![feed("SCAP", "202401250530", false), feed("CERT", "202402041200", true)],
+            warnings: Vec::new(),
+        };
+        let list = assess(list, 7, now);
+        assert_eq!(list.feeds[0].age_hours, Some(264));
+        assert_eq!(list.feeds[0].last_update.as_deref(), Some("2024-01-25T05:30:00Z"));
+        assert!(list.feeds[0].stale);
+        assert!(!list.feeds[1].stale);
+        assert_eq!(list.warnings.len(), 3, "{:?}", list.warnings);
+        assert!(list.warnings[0].contains("SCAP feed was last updated 11 days ago"));
+        assert!(list.warnings[1].contains("CERT feed is syncing"));
+        assert!(list.warnings[2].contains("no NVT feed"));
+    }
+}
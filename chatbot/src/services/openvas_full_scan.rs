@@ -0,0 +1,112 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{FullScanStarted, BUSY_TASK_STATUSES};
+use crate::services::openvas_create_target::openvas_create_target;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for the one-shot full scan: resolves `config_name`
+/// to a scan config, creates (or reuses) the target and the task, and
+/// starts the task unless a reused one is already busy. Returns at once;
+/// the scan runs on in gvmd.
+pub async fn openvas_full_scan(
+    hosts: &str,
+    config_name: &str,
+    name: Option<&str>,
+    port_range: Option<&str>,
+    port_list_id: Option<&str>,
+    scanner_id: Option<&str>,
+) -> Result<FullScanStarted> {
+    let hosts = hosts.trim();
+    if hosts.is_empty() {
+        return Err(ToolError::invalid_input("hosts is empty").into());
+    }
+    let name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => format!("hacker_agent {hosts}"),
+    };
+
+    let configs = backend::current().list_configs().await?.configs;
+    let Some(config) = configs.iter().find(|c| c.name.eq_ignore_ascii_case(config_name.trim())) else {
+        let names: Vec<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+        return Err(ToolError::invalid_input(format!(
+            "no scan config named '{config_name}'; available: {}",
+            names.join(", ")
+        ))
+        .into());
+    };
+
+    let target = openvas_create_target(&name, hosts, port_range, port_list_id).await?;
+    let task = backend::current().create_task(&name, &config.id, &target.id, None, scanner_id).await?;
+
+    let busy = if task.existed {
+        let state = backend::current().get_task_status(&task.id).await?;
+        state.status.filter(|s| BUSY_TASK_STATUSES.contains(&s.as_str()))
+    } else {
+        None
+    };
+    let (started, status) = match busy {
+        Some(status) => (false, status),
+        None => {
+            backend::current().start_task(&task.id).await?;
+            (true, "Requested".to_string())
+        }
+    };
+
+    Ok(FullScanStarted {
+        next: format!(
+            "follow it with openvas_task_status {{\"task_id\": \"{}\"}}; once Done, fetch the last_report_id with openvas_get_report",
+            task.id
+        ),
+        task_id: task.id,
+        target_id: target.id,
+        config_id: config.id.clone(),
+        target_existed: target.existed,
+        task_existed: task.existed,
+        started,
+        status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn one_call_creates_and_starts_and_a_repeat_reuses() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let (first, second, unknown) = with_backend(mock.clone(), async {
+            let first = openvas_full_scan("10.0.0.5", "full AND fast", None, None, None, None).await.unwrap();
+            let second = openvas_full_scan("10.0.0.5", "Full and fast", None, None, None, None).await.unwrap();
+            let unknown = openvas_full_scan("10.0.0.5", "Ultimate", None, None, None, None).await;
+            (first, second, unknown)
+        })
+        .await;
+
+        assert!(first.started && !first.target_existed && !first.task_existed);
+        assert_eq!(first.config_id, "daba56c8-73ec-11df-a475-002264764cea");
+        assert!(first.next.contains(&first.task_id));
+        assert!(!second.started && second.target_existed && second.task_existed);
+        assert_eq!((second.task_id, second.status.as_str()), (first.task_id.clone(), "Running"));
+        let err = unknown.unwrap_err().to_string();
+        assert!(err.contains("available: Full and fast"), "{err}");
+        assert_eq!(
+            mock.calls(),
+            [
+                "list_configs",
+                "create_target hacker_agent 10.0.0.5",
+                "create_task hacker_agent 10.0.0.5",
+                &format!("start_task {}", first.task_id),
+                "list_configs",
+                "create_target hacker_agent 10.0.0.5",
+                "create_task hacker_agent 10.0.0.5",
+                &format!("get_task_status {}", first.task_id),
+                "list_configs",
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::HostAsset;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS get host" using the Go backend. Finds
+/// the host asset by asset ID, IP or hostname, since the agent usually
+/// knows a host by address rather than by gvmd's ID.
+pub async fn openvas_get_host(host: &str) -> Result<HostAsset> {
+    let host = host.trim();
+    if host.is_empty() {
+        return Err(ToolError::invalid_input("host must be an asset ID, IP or hostname").into());
+    }
+    let list = backend::current().list_host_assets().await?;
+    list.hosts
+        .into_iter()
+        .find(|h| h.id == host || h.ip == host || h.hostnames.iter().any(|n| n.eq_ignore_ascii_case(host)))
+        .ok_or_else(|| {
+            ToolError::invalid_input(format!(
+                "GVM has no host asset '{host}': no scan has reported it yet (openvas_list_hosts lists the known hosts)"
+            ))
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn hosts_are_found_by_id_ip_or_hostname() {
+        let asset = HostAsset {
+            id: "a1".into(),
+            ip: "10.0.0.5".into(),
+            hostnames: vec!["web-1.corp.example".into()],
+            details: [("ports".to_string(), "22,80".to_string())].into(),
+            ..HostAsset::default()
+        };
+        let mock = Arc::new(MockBackend::default().with_host_assets(vec![asset]));
+        with_backend(mock, async {
+            for key in ["a1", "10.0.0.5", " WEB-1.corp.example "] {
+                let host = openvas_get_host(key).await.unwrap();
+                assert_eq!(host.id, "a1", "{key}");
+                assert_eq!(host.details["ports"], "22,80");
+            }
+            let err = openvas_get_host("10.0.0.9").await.unwrap_err();
+            assert!(err.to_string().contains("no host asset '10.0.0.9'"), "{err}");
+            assert_eq!(ToolError::classify(err).kind(), "invalid_input");
+        })
+        .await;
+    }
+}
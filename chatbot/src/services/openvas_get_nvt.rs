@@ -0,0 +1,63 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::NvtDetails;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS get NVT" using the Go backend. Checks
+/// that `oid` looks like an OID first, so a finding's name or ID passed by
+/// mistake is explained instead of reported as an unknown NVT.
+pub async fn openvas_get_nvt(oid: &str) -> Result<NvtDetails> {
+    let oid = oid.trim();
+    let is_oid = oid.contains('.')
+        && oid.split('.').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    if !is_oid {
+        return Err(ToolError::invalid_input(format!(
+            "'{oid}' is not an NVT OID: pass a finding's nvt_oid, e.g. '1.3.6.1.4.1.25623.1.0.811011'"
+        ))
+        .into());
+    }
+    backend::current().get_nvt(oid).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    #[tokio::test]
+    async fn findings_are_explained_by_their_nvt() {
+        let finding = ReportResult {
+            id: "r1".into(),
+            name: "OpenSSH Multiple Vulnerabilities".into(),
+            host: "10.0.0.5".into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity: 7.8,
+            threat: "High".into(),
+            qod: None,
+            cves: vec!["CVE-2016-6210".into()],
+            solution: Some("Update to version 7.3 or later.".into()),
+        };
+        let mock = Arc::new(MockBackend::default().with_findings(vec![finding]));
+        with_backend(mock.clone(), async {
+            let nvt = openvas_get_nvt(" 1.3.6.1.4.1.25623.1.0.811011 ").await.unwrap();
+            assert_eq!(nvt.name, "OpenSSH Multiple Vulnerabilities");
+            assert_eq!(nvt.cvss_base, Some(7.8));
+            assert_eq!(nvt.references[0].id, "CVE-2016-6210");
+
+            let err = openvas_get_nvt("1.3.6.1.4.1.25623.1.0.1").await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "backend_error");
+            for bad in ["", "r1", "OpenSSH", "1..2", "811011"] {
+                let err = openvas_get_nvt(bad).await.unwrap_err();
+                assert!(err.to_string().contains("is not an NVT OID"), "{bad}: {err}");
+            }
+        })
+        .await;
+        assert_eq!(mock.calls().len(), 2);
+    }
+}
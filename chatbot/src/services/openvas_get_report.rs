@@ -1,13 +1,120 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::{Report, ReportFilter, ReportFormat};
+use crate::resources;
+use crate::tool_error::ToolError;
 
 /// Business-logic layer for "OpenVAS get report" using the Go backend.
-/// Thin wrapper around the low-level HTTP client. Returns the raw JSON
-/// from the Go API, which includes the `report_id` and `response_raw`
-/// (the XML <get_reports_response/> from gvmd).
-pub async fn openvas_get_report(report_id: &str) -> Result<Value> {
-    openvas::get_report(report_id).await
+/// Returns the `report_id` and, for XML formats, `response_raw` (the XML
+/// <get_reports_response/> from gvmd) and the findings parsed from it; for
+/// the others the output as text or base64. With `as_resource` the output
+/// is saved as a resource instead and only its URI is returned. `filter`
+/// drops low-severity or low-confidence results, which keeps large reports
+/// within context limits.
+pub async fn openvas_get_report(
+    report_id: &str,
+    format: ReportFormat,
+    filter: &ReportFilter,
+    as_resource: bool,
+) -> Result<Report> {
+    if filter.min_severity.is_some_and(|s| !(0.0..=10.0).contains(&s)) {
+        return Err(ToolError::invalid_input("min_severity must be a CVSS score from 0.0 to 10.0").into());
+    }
+    if filter.min_qod.is_some_and(|q| q > 100) {
+        return Err(ToolError::invalid_input("min_qod must be from 0 to 100").into());
+    }
+    let mut report = backend::current().get_report(report_id, format, filter).await?;
+    report.results.retain(|r| filter.keeps(r));
+    if as_resource {
+        report.resource_uri = Some(resources::save_report(&report, format));
+        report.response_raw.clear();
+        report.results.clear();
+        report.content = None;
+        report.content_base64 = None;
+    }
+    Ok(report)
 }
 
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    async fn finished_report(mock: &MockBackend) -> String {
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("nightly web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        mock.get_task_status(&task.id).await.unwrap().last_report_id.unwrap()
+    }
+
+    fn finding(severity: f64, qod: u8) -> ReportResult {
+        ReportResult {
+            id: format!("result-{severity}-{qod}"),
+            name: "finding".into(),
+            host: "10.0.0.5".into(),
+            port: "443/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".into(),
+            severity,
+            threat: "High".into(),
+            qod: Some(qod),
+            cves: Vec::new(),
+            solution: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn filters_keep_severe_confident_results() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding(9.8, 95),
+            finding(9.1, 30),
+            finding(5.0, 99),
+        ]));
+        let report_id = finished_report(&mock).await;
+        let filter = ReportFilter {
+            min_severity: Some(7.0),
+            min_qod: Some(70),
+        };
+        let out_of_range = ReportFilter {
+            min_severity: Some(11.0),
+            min_qod: None,
+        };
+        let (kept, refused) = with_backend(mock, async {
+            (
+                openvas_get_report(&report_id, ReportFormat::Xml, &filter, false).await.unwrap(),
+                openvas_get_report(&report_id, ReportFormat::Xml, &out_of_range, false).await.unwrap_err(),
+            )
+        })
+        .await;
+        let ids: Vec<&str> = kept.results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["result-9.8-95"]);
+        assert_eq!(ToolError::classify(refused).kind(), "invalid_input");
+    }
+
+    #[tokio::test]
+    async fn binary_formats_are_base64_or_saved_as_a_resource() {
+        let mock = Arc::new(MockBackend::default());
+        let report_id = finished_report(&mock).await;
+
+        let (pdf, saved) = with_backend(mock, async {
+            (
+                openvas_get_report(&report_id, ReportFormat::Pdf, &ReportFilter::default(), false).await.unwrap(),
+                openvas_get_report(&report_id, ReportFormat::Pdf, &ReportFilter::default(), true).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(pdf.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(pdf.content_base64.as_deref(), Some("JVBERi0xLjQK"));
+
+        let uri = saved.resource_uri.unwrap();
+        assert_eq!(uri, format!("openvas://report/{report_id}/pdf"));
+        assert_eq!(saved.content_base64, None);
+        let read = resources::read(&uri).await.unwrap();
+        assert_eq!(read["contents"][0]["mimeType"], "application/pdf");
+        assert_eq!(read["contents"][0]["blob"], "JVBERi0xLjQK");
+    }
+}
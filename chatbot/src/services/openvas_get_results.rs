@@ -0,0 +1,122 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{GetResultsArgs, ResultPage};
+use crate::tool_error::ToolError;
+
+/// Most results one page may hold.
+const MAX_ROWS: u64 = 1000;
+
+/// The gvmd filter string for `args`: the caller's filter, narrowed to a
+/// report or task and sorted most severe first unless it sorts itself.
+/// Paging stays with `first` and `rows`, which the backend appends, so a
+/// filter may not set them itself.
+fn gvm_filter(args: &GetResultsArgs) -> Result<String, ToolError> {
+    if args.first == 0 {
+        return Err(ToolError::invalid_input("first is 1-based and must be at least 1"));
+    }
+    if !(1..=MAX_ROWS).contains(&args.rows) {
+        return Err(ToolError::invalid_input(format!("rows must be from 1 to {MAX_ROWS}")));
+    }
+    let filter = args.filter.as_deref().unwrap_or_default().trim();
+    let has = |keyword: &str| filter.split_whitespace().find(|t| t.to_ascii_lowercase().starts_with(keyword));
+    if let Some(term) = has("rows=").or_else(|| has("first=")) {
+        return Err(ToolError::invalid_input(format!(
+            "filter sets `{term}`; page with the first and rows arguments instead"
+        )));
+    }
+    let mut terms = vec![filter.to_string()];
+    for (keyword, id) in [("report_id", &args.report_id), ("task_id", &args.task_id)] {
+        let Some(id) = id.as_deref().map(str::trim) else {
+            continue;
+        };
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(ToolError::invalid_input(format!("{keyword} must be a GVM ID, got `{id}`")));
+        }
+        terms.push(format!("{keyword}={id}"));
+    }
+    if has("sort").is_none() {
+        terms.push("sort-reverse=severity".to_string());
+    }
+    Ok(terms.join(" ").trim().to_string())
+}
+
+/// Business-logic layer for "OpenVAS get results" using the Go backend.
+/// Returns one page of results across reports, most severe first, and the
+/// `first` of the next page while more match, so large result sets are
+/// read a page at a time instead of as one giant report.
+pub async fn openvas_get_results(args: &GetResultsArgs) -> Result<ResultPage> {
+    let filter = gvm_filter(args)?;
+    let mut page = backend::current().get_results(&filter, args.first, args.rows).await?;
+    let end = page.first - 1 + page.results.len() as u64;
+    page.next_first = (!page.results.is_empty() && end < page.filtered).then_some(end + 1);
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    fn args(filter: &str, first: u64, rows: u64) -> GetResultsArgs {
+        GetResultsArgs { filter: Some(filter.into()), report_id: None, task_id: None, first, rows }
+    }
+
+    #[test]
+    fn filters_are_narrowed_to_a_report_and_leave_paging_to_the_arguments() {
+        let narrowed = GetResultsArgs { report_id: Some(" r-1 ".into()), ..args("severity>6.9", 1, 100) };
+        assert_eq!(gvm_filter(&narrowed).unwrap(), "severity>6.9 report_id=r-1 sort-reverse=severity");
+        assert_eq!(gvm_filter(&args("sort=host", 1, 100)).unwrap(), "sort=host");
+        for bad in [
+            args("severity>6.9 rows=5000", 1, 100),
+            args("", 0, 100),
+            args("", 1, 1001),
+            GetResultsArgs { task_id: Some("t-1 rows=-1".into()), ..args("", 1, 100) },
+        ] {
+            assert_eq!(gvm_filter(&bad).unwrap_err().kind(), "invalid_input");
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_continue_until_every_match_is_read() {
+        let finding = |id: &str, host: &str, severity: f64| ReportResult {
+            id: id.into(),
+            name: format!("finding {id}"),
+            host: host.into(),
+            port: "443/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".into(),
+            severity,
+            threat: "High".into(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        };
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("r3", "10.0.0.5", 7.0),
+            finding("r4", "10.0.0.5", 5.0),
+            finding("r1", "10.0.0.5", 9.8),
+            finding("r5", "10.0.0.6", 9.0),
+            finding("r2", "10.0.0.5", 7.5),
+        ]));
+        let target = mock.create_target("web", "10.0.0.0/24", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let (first, last) = with_backend(mock, async {
+            let filter = "severity>6.9 and host=10.0.0.5";
+            let first = openvas_get_results(&args(filter, 1, 2)).await.unwrap();
+            let last = openvas_get_results(&args(filter, first.next_first.unwrap(), 2)).await.unwrap();
+            (first, last)
+        })
+        .await;
+        let ids = |page: &ResultPage| page.results.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&first), ["r1", "r2"]);
+        assert_eq!((first.filtered, first.next_first), (3, Some(3)));
+        assert_eq!(ids(&last), ["r3"]);
+        assert_eq!(last.next_first, None);
+    }
+}
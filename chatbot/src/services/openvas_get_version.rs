@@ -1,12 +1,11 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::Version;
 
 /// Business-logic layer for "OpenVAS get version" using the Go backend.
 /// Right now this is just a thin wrapper, but we can later add parsing
 /// or normalization (e.g. extract only the numeric version).
-pub async fn openvas_get_version() -> Result<Value> {
-    openvas::get_version().await
+pub async fn openvas_get_version() -> Result<Version> {
+    backend::current().get_version().await
 }
-
@@ -0,0 +1,196 @@
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use regex::Regex;
+use roxmltree::{Document, Node};
+
+use crate::api::backend;
+use crate::api::gmp::escape;
+use crate::models::openvas::ImportedResults;
+use crate::tool_error::ToolError;
+
+/// NVT OID of the results converted from nmap output. gvmd keeps whatever
+/// OID an imported result names; this one names no NVT, so the open ports
+/// nmap found stay apart from the scanner's own results.
+const NMAP_OPEN_PORT_OID: &str = "nmap-open-port";
+
+/// A GVM-format report of the open ports in nmap's normal output, one
+/// Log-level result per port, and how many hosts it covers.
+fn nmap_report(raw: &str, now: &str) -> (String, usize) {
+    static LINES: OnceLock<(Regex, Regex)> = OnceLock::new();
+    let (host_line, port_line) = LINES.get_or_init(|| {
+        (
+            Regex::new(r"^Nmap scan report for (\S+)(?: \(([^)]+)\))?").expect("valid host line regex"),
+            Regex::new(r"^(\d+/(?:tcp|udp))\s+open\s+(\S+)(?:\s+(.+))?$").expect("valid port line regex"),
+        )
+    });
+
+    let mut results = String::new();
+    let mut hosts = BTreeSet::new();
+    let mut host = None;
+    for line in raw.lines().map(str::trim_end) {
+        if let Some(caps) = host_line.captures(line) {
+            // "for name (address)" or "for address".
+            host = Some(caps.get(2).map_or(&caps[1], |address| address.as_str()).to_string());
+            continue;
+        }
+        let (Some(host), Some(caps)) = (&host, port_line.captures(line)) else {
+            continue;
+        };
+        hosts.insert(host.clone());
+        let service = &caps[2];
+        let version = caps.get(3).map_or("", |v| v.as_str().trim());
+        let description = if version.is_empty() {
+            format!("nmap found {} open, running {service}.", &caps[1])
+        } else {
+            format!("nmap found {} open, running {service} ({version}).", &caps[1])
+        };
+        results.push_str(&format!(
+            "<result><name>{}</name><host>{}</host><port>{}</port><nvt oid='{NMAP_OPEN_PORT_OID}'><name>Open port \
+             (nmap)</name></nvt><threat>Log</threat><severity>0.0</severity><description>{}</description></result>",
+            escape(&format!("Open port: {service}")),
+            escape(host),
+            escape(&caps[1]),
+            escape(&description),
+        ));
+    }
+    let hosts_xml: String = hosts
+        .iter()
+        .map(|h| format!("<host><ip>{}</ip><start>{now}</start><end>{now}</end></host>", escape(h)))
+        .collect();
+    let report = format!(
+        "<report><scan_start>{now}</scan_start><scan_end>{now}</scan_end><results>{results}</results>{hosts_xml}\
+         </report>"
+    );
+    (report, hosts.len())
+}
+
+/// The hosts and results of a GVM-format report, whose results sit under
+/// `<report>` or, as in `get_reports` output, under an inner `<report>`.
+fn count_report(report_xml: &str) -> Result<(usize, usize)> {
+    let doc = Document::parse(report_xml)
+        .map_err(|err| ToolError::invalid_input(format!("report_xml is not well-formed XML: {err}")))?;
+    let root = doc.root_element();
+    if !root.has_tag_name("report") {
+        return Err(ToolError::invalid_input(format!(
+            "report_xml must be a <report> element, not <{}>",
+            root.tag_name().name()
+        ))
+        .into());
+    }
+    let report = root.children().find(|c| c.has_tag_name("report")).unwrap_or(root);
+    let results: Vec<Node> = report
+        .children()
+        .filter(|c| c.has_tag_name("results"))
+        .flat_map(|r| r.children().filter(|c| c.has_tag_name("result")))
+        .collect();
+    let hosts: BTreeSet<&str> =
+        results.iter().filter_map(|r| r.children().find(|c| c.has_tag_name("host"))?.text()).map(str::trim).collect();
+    Ok((hosts.len(), results.len()))
+}
+
+/// Business-logic layer for "OpenVAS import results": imports `report_xml`,
+/// or the open ports in `nmap_output` converted to a GVM report, into the
+/// container task `task_name` (created unless it exists), so findings from
+/// other scanners sit in gvmd next to OpenVAS's own.
+pub async fn openvas_import_results(
+    task_name: &str,
+    report_xml: Option<&str>,
+    nmap_output: Option<&str>,
+) -> Result<ImportedResults> {
+    let task_name = task_name.trim();
+    if task_name.is_empty() {
+        return Err(ToolError::invalid_input("task_name is empty").into());
+    }
+    let (report, hosts, results) = match (report_xml, nmap_output) {
+        (Some(report_xml), None) => {
+            let (hosts, results) = count_report(report_xml.trim())?;
+            (report_xml.trim().to_string(), hosts, results)
+        }
+        (None, Some(nmap_output)) => {
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+            let (report, hosts) = nmap_report(nmap_output, &now);
+            let (_, results) = count_report(&report)?;
+            (report, hosts, results)
+        }
+        _ => return Err(ToolError::invalid_input("give exactly one of report_xml and nmap_output").into()),
+    };
+    if results == 0 {
+        return Err(ToolError::invalid_input("the report holds no results to import").into());
+    }
+
+    let task = backend::current().create_container_task(task_name).await?;
+    let imported = backend::current().import_report(&task.id, &report).await?;
+    Ok(ImportedResults {
+        task_id: task.id,
+        task_name: task_name.to_string(),
+        task_existed: task.existed,
+        report_id: imported.report_id,
+        hosts,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::{ReportFilter, ReportFormat};
+
+    const NMAP_OUTPUT: &str = "Nmap scan report for web.example.com (10.0.0.5)\nHost is up.\n\
+                               PORT    STATE    SERVICE VERSION\n22/tcp  open     ssh     OpenSSH 8.9p1\n\
+                               25/tcp  filtered smtp\n80/tcp  open     http\n\n\
+                               Nmap scan report for 10.0.0.6\nHost is up.\n443/tcp open  https\n\
+                               Nmap done: 2 IP addresses (2 hosts up)\n";
+
+    #[test]
+    fn open_ports_become_log_results() {
+        let (report, hosts) = nmap_report(NMAP_OUTPUT, "2026-01-01T00:00:00Z");
+        assert_eq!(hosts, 2);
+        assert_eq!(count_report(&report).unwrap(), (2, 3));
+        assert!(report.contains("<host>10.0.0.5</host><port>22/tcp</port>"), "{report}");
+        assert!(report.contains("running ssh (OpenSSH 8.9p1)"), "{report}");
+        assert!(!report.contains("25/tcp"));
+    }
+
+    #[tokio::test]
+    async fn imports_into_one_reused_container_task() {
+        let mock = Arc::new(MockBackend::default());
+        let report_xml = "<report id='r'><report><results><result><name>Weak cipher</name><host>10.0.0.9</host>\
+                          <port>443/tcp</port><threat>Medium</threat><severity>5.0</severity></result></results>\
+                          </report></report>";
+        let (nmap, given) = with_backend(mock.clone(), async {
+            (
+                openvas_import_results("imported", None, Some(NMAP_OUTPUT)).await.unwrap(),
+                openvas_import_results("imported", Some(report_xml), None).await.unwrap(),
+            )
+        })
+        .await;
+        assert!(!nmap.task_existed);
+        assert!(given.task_existed);
+        assert_eq!(given.task_id, nmap.task_id);
+        assert_eq!((given.hosts, given.results), (1, 1));
+        let report = mock.get_report(&given.report_id, ReportFormat::Xml, &ReportFilter::default()).await.unwrap();
+        assert_eq!(report.results[0].threat, "Medium");
+    }
+
+    #[tokio::test]
+    async fn refuses_reports_without_results() {
+        let errors = with_backend(Arc::new(MockBackend::default()), async {
+            [
+                openvas_import_results("imported", None, Some("Nmap done: 1 IP address (0 hosts up)")).await,
+                openvas_import_results("imported", Some("<results/>"), None).await,
+                openvas_import_results("imported", Some("<report>"), None).await,
+                openvas_import_results("imported", None, None).await,
+            ]
+        })
+        .await;
+        for result in errors {
+            assert_eq!(ToolError::classify(result.unwrap_err()).kind(), "invalid_input");
+        }
+    }
+}
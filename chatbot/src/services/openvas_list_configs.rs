@@ -1,11 +1,10 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::ConfigList;
 
 /// Business-logic layer for "OpenVAS list configs" using the Go backend.
-/// Right now this is just a thin wrapper returning the backend JSON as-is.
-pub async fn openvas_list_configs() -> Result<Value> {
-    openvas::list_configs().await
+/// Right now this is just a thin wrapper returning the backend's config list.
+pub async fn openvas_list_configs() -> Result<ConfigList> {
+    backend::current().list_configs().await
 }
-
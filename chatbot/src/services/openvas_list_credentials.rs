@@ -0,0 +1,15 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::CredentialList;
+
+/// Business-logic layer for "OpenVAS list credentials" using the Go
+/// backend. Returns the existing credentials (gvmd never hands out their
+/// secrets), keeping only those whose name contains `name_contains`
+/// (case-insensitive).
+pub async fn openvas_list_credentials(name_contains: Option<&str>) -> Result<CredentialList> {
+    let mut list = backend::current().list_credentials().await?;
+    list.credentials
+        .retain(|c| name_contains.is_none_or(|n| c.name.to_lowercase().contains(&n.trim().to_lowercase())));
+    Ok(list)
+}
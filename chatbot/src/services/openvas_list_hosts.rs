@@ -0,0 +1,156 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::api::backend;
+use crate::models::openvas::{HostAsset, HostAssetList, HostInventory, ListHostsArgs};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS list hosts" using the Go backend.
+/// Filters gvmd's host assets, which gvmd has no filter keywords for on
+/// hostname or OS, and drops their details to keep the list short;
+/// `openvas_get_host` has them.
+pub async fn openvas_list_hosts(args: &ListHostsArgs) -> Result<HostInventory> {
+    if args.limit == 0 {
+        return Err(ToolError::invalid_input("limit must be at least 1").into());
+    }
+    if let Some(min) = args.min_severity
+        && !(0.0..=10.0).contains(&min)
+    {
+        return Err(ToolError::invalid_input(format!("min_severity must be a CVSS score from 0 to 10, not {min}")).into());
+    }
+    let list = backend::current().list_host_assets().await?;
+    Ok(select(list, args, Utc::now()))
+}
+
+/// The hosts of `list` matching `args` as of `now`, most severe first.
+fn select(list: HostAssetList, args: &ListHostsArgs, now: DateTime<Utc>) -> HostInventory {
+    let contains = |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.trim().to_lowercase());
+    let stale_before = args.not_seen_for_days.map(|days| now - Duration::days(days as i64));
+    let mut hosts: Vec<HostAsset> = list
+        .hosts
+        .into_iter()
+        .filter(|h| {
+            args.host_contains
+                .as_deref()
+                .is_none_or(|needle| contains(&h.ip, needle) || h.hostnames.iter().any(|n| contains(n, needle)))
+        })
+        .filter(|h| {
+            args.os_contains.as_deref().is_none_or(|needle| {
+                [&h.os, &h.os_cpe].into_iter().flatten().any(|os| contains(os, needle))
+            })
+        })
+        .filter(|h| args.min_severity.is_none_or(|min| h.severity.is_some_and(|s| s >= min)))
+        // A host without a usable last-seen time may never have been
+        // scanned properly, so it counts as due a rescan.
+        .filter(|h| {
+            stale_before.is_none_or(|before| {
+                h.last_seen
+                    .as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .is_none_or(|seen| seen < before)
+            })
+        })
+        .collect();
+    hosts.sort_by(|a, b| {
+        let severity = |h: &HostAsset| h.severity.unwrap_or(-1.0);
+        severity(b).total_cmp(&severity(a)).then_with(|| a.ip.cmp(&b.ip))
+    });
+    let total = hosts.len();
+    hosts.truncate(args.limit);
+    for host in &mut hosts {
+        host.details.clear();
+    }
+    HostInventory { total, hosts }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    fn args() -> ListHostsArgs {
+        ListHostsArgs { host_contains: None, os_contains: None, min_severity: None, not_seen_for_days: None, limit: 100 }
+    }
+
+    fn host(ip: &str, hostname: &str, os: &str, severity: f64, last_seen: &str) -> HostAsset {
+        HostAsset {
+            id: format!("asset-{ip}"),
+            ip: ip.into(),
+            hostnames: vec![hostname.into()],
+            os: Some(os.into()),
+            severity: Some(severity),
+            last_seen: Some(last_seen.into()),
+            details: [("ports".to_string(), "22".to_string())].into(),
+            ..HostAsset::default()
+        }
+    }
+
+    #[test]
+    fn hosts_are_filtered_and_most_severe_first() {
+        let list = HostAssetList {
+            hosts: vec![
+                host("10.0.0.5", "web-1.corp.example", "Ubuntu 22.04", 5.0, "2024-01-25T10:42:17Z"),
+                host("10.0.0.6", "dc-1.corp.example", "Microsoft Windows Server 2019", 9.8, "2023-11-02T08:31:55Z"),
+                host("10.0.1.7", "build.lab.example", "Debian 12", 0.0, "2023-06-01T00:00:00Z"),
+                HostAsset { id: "asset-10.0.1.8".into(), ip: "10.0.1.8".into(), ..HostAsset::default() },
+            ],
+        };
+        let now = DateTime::parse_from_rfc3339("2024-01-30T00:00:00Z").unwrap().to_utc();
+        let ips = |args: ListHostsArgs| -> Vec<String> {
+            select(list.clone(), &args, now).hosts.into_iter().map(|h| h.ip).collect()
+        };
+
+        let all = select(list.clone(), &args(), now);
+        assert_eq!(all.total, 4);
+        assert_eq!(
+            all.hosts.iter().map(|h| h.ip.as_str()).collect::<Vec<_>>(),
+            ["10.0.0.6", "10.0.0.5", "10.0.1.7", "10.0.1.8"]
+        );
+        assert!(all.hosts[0].details.is_empty());
+
+        assert_eq!(ips(ListHostsArgs { host_contains: Some("CORP.example".into()), ..args() }), ["10.0.0.6", "10.0.0.5"]);
+        assert_eq!(ips(ListHostsArgs { host_contains: Some("10.0.1.".into()), ..args() }), ["10.0.1.7", "10.0.1.8"]);
+        assert_eq!(ips(ListHostsArgs { os_contains: Some("windows".into()), ..args() }), ["10.0.0.6"]);
+        assert_eq!(ips(ListHostsArgs { min_severity: Some(5.0), ..args() }), ["10.0.0.6", "10.0.0.5"]);
+        assert_eq!(ips(ListHostsArgs { not_seen_for_days: Some(30), ..args() }), ["10.0.0.6", "10.0.1.7", "10.0.1.8"]);
+
+        let limited = select(list, &ListHostsArgs { limit: 1, ..args() }, now);
+        assert_eq!((limited.total, limited.hosts.len()), (4, 1));
+    }
+
+    #[tokio::test]
+    async fn scanned_hosts_become_assets() {
+        let finding = |host: &str, severity: f64| ReportResult {
+            id: format!("r-{host}"),
+            name: "OpenSSH Multiple Vulnerabilities".into(),
+            host: host.into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity,
+            threat: "High".into(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        };
+        let mock = Arc::new(MockBackend::default().with_findings(vec![finding("10.0.0.5", 7.8), finding("10.0.0.6", 2.6)]));
+        let target = mock.create_target("web", "10.0.0.5, 10.0.0.6", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        with_backend(mock, async {
+            let inventory = openvas_list_hosts(&ListHostsArgs { min_severity: Some(7.0), ..args() }).await.unwrap();
+            assert_eq!(inventory.total, 1);
+            assert_eq!(inventory.hosts[0].ip, "10.0.0.5");
+            assert_eq!(inventory.hosts[0].severity, Some(7.8));
+            assert_eq!(inventory.hosts[0].report_ids.len(), 1);
+
+            let err = openvas_list_hosts(&ListHostsArgs { min_severity: Some(11.0), ..args() }).await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "invalid_input");
+        })
+        .await;
+    }
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::PortListList;
+
+/// Business-logic layer for "OpenVAS list port lists" using the Go backend.
+/// Returns the existing port lists, predefined ones included, keeping only
+/// those whose name contains `name_contains` (case-insensitive).
+pub async fn openvas_list_port_lists(name_contains: Option<&str>) -> Result<PortListList> {
+    let mut list = backend::current().list_port_lists().await?;
+    list.port_lists
+        .retain(|p| name_contains.is_none_or(|n| p.name.to_lowercase().contains(&n.trim().to_lowercase())));
+    Ok(list)
+}
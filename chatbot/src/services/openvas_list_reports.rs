@@ -0,0 +1,70 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::ReportList;
+
+/// Business-logic layer for "OpenVAS list reports" using the Go backend.
+/// Returns reports newest first, with their timestamps and result counts
+/// per threat level, optionally only those of one task and at most `limit`
+/// of them, so the agent can find a task's latest report ID.
+pub async fn openvas_list_reports(task_id: Option<&str>, limit: Option<usize>) -> Result<ReportList> {
+    let task_id = task_id.map(str::trim).filter(|id| !id.is_empty());
+    let mut list = backend::current().list_reports(task_id).await?;
+    if let Some(limit) = limit {
+        list.reports.truncate(limit);
+    }
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    fn finding(threat: &str, severity: f64) -> ReportResult {
+        ReportResult {
+            id: format!("result-{threat}"),
+            name: "finding".into(),
+            host: "10.0.0.5".into(),
+            port: "443/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".into(),
+            severity,
+            threat: threat.into(),
+            qod: Some(80),
+            cves: Vec::new(),
+            solution: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_of_a_task_come_with_severity_counts() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("High", 9.8),
+            finding("Medium", 5.0),
+            finding("Log", 0.0),
+        ]));
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let ran = mock.create_task("nightly web", "config", &target.id, None, None).await.unwrap();
+        let other = mock.create_task("weekly web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&ran.id).await.unwrap();
+        mock.start_task(&other.id).await.unwrap();
+
+        let (for_task, limited) = with_backend(mock, async {
+            (
+                openvas_list_reports(Some(&ran.id), None).await.unwrap(),
+                openvas_list_reports(None, Some(1)).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(for_task.reports.len(), 1);
+        let report = &for_task.reports[0];
+        assert_eq!(report.task_id, ran.id);
+        assert_eq!(report.severity, 9.8);
+        assert_eq!((report.counts.high, report.counts.medium, report.counts.low, report.counts.log), (1, 1, 0, 1));
+        assert_eq!(limited.reports.len(), 1);
+    }
+}
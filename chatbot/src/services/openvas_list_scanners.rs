@@ -0,0 +1,38 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::ScannerList;
+
+/// Business-logic layer for "OpenVAS list scanners" using the Go backend.
+/// A thin wrapper returning every scanner tasks can be created against.
+pub async fn openvas_list_scanners() -> Result<ScannerList> {
+    backend::current().list_scanners().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::services::openvas_create_task::openvas_create_task;
+
+    #[tokio::test]
+    async fn tasks_run_on_a_listed_scanner() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+
+        let (scanners, missing) = with_backend(mock.clone(), async {
+            let scanners = openvas_list_scanners().await.unwrap().scanners;
+            let cve = scanners.iter().find(|s| s.scanner_type == "cve").unwrap();
+            openvas_create_task("cve web", "config", &target.id, None, Some(&cve.id)).await.unwrap();
+            let missing = openvas_create_task("web", "config", &target.id, None, Some("scanner-9")).await;
+            (openvas_list_scanners().await.unwrap().scanners, missing)
+        })
+        .await;
+        let in_use: Vec<_> = scanners.iter().map(|s| (s.scanner_type.as_str(), s.in_use)).collect();
+        assert_eq!(in_use, [("openvas", false), ("cve", true)]);
+        assert!(missing.unwrap_err().to_string().contains("scanner-9"));
+    }
+}
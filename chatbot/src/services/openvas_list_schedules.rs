@@ -0,0 +1,15 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::ScheduleList;
+
+/// Business-logic layer for "OpenVAS list schedules" using the Go backend.
+/// Returns the existing schedules with their iCalendar event and the tasks
+/// that use them, keeping only those whose name contains `name_contains`
+/// (case-insensitive).
+pub async fn openvas_list_schedules(name_contains: Option<&str>) -> Result<ScheduleList> {
+    let mut list = backend::current().list_schedules().await?;
+    list.schedules
+        .retain(|s| name_contains.is_none_or(|n| s.name.to_lowercase().contains(&n.trim().to_lowercase())));
+    Ok(list)
+}
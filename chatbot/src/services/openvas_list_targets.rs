@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::TargetList;
+
+/// Business-logic layer for "OpenVAS list targets" using the Go backend.
+/// Returns the existing targets, keeping only those whose name and hosts
+/// contain the given filters (case-insensitive), so the agent can reuse a
+/// target instead of creating a duplicate.
+pub async fn openvas_list_targets(name_contains: Option<&str>, hosts_contains: Option<&str>) -> Result<TargetList> {
+    let mut list = backend::current().list_targets().await?;
+    let matches = |field: &str, filter: Option<&str>| {
+        filter.is_none_or(|f| field.to_lowercase().contains(&f.trim().to_lowercase()))
+    };
+    list.targets
+        .retain(|t| matches(&t.name, name_contains) && matches(&t.hosts, hosts_contains));
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn filters_match_name_and_hosts_case_insensitively() {
+        let mock = Arc::new(MockBackend::default());
+        let web = mock.create_target("Web DMZ", "10.0.0.5,10.0.0.6", None, None).await.unwrap();
+        mock.create_target("mail", "10.0.1.25", None, None).await.unwrap();
+        mock.create_task("weekly", "config", &web.id, None, None).await.unwrap();
+
+        let (by_name, by_host, all) = with_backend(mock, async {
+            (
+                openvas_list_targets(Some("web"), None).await.unwrap(),
+                openvas_list_targets(None, Some(" 10.0.1.25 ")).await.unwrap(),
+                openvas_list_targets(None, None).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(by_name.targets.len(), 1);
+        assert_eq!(by_name.targets[0].id, web.id);
+        assert!(by_name.targets[0].in_use);
+        assert_eq!(by_host.targets[0].name, "mail");
+        assert!(!by_host.targets[0].in_use);
+        assert_eq!(all.targets.len(), 2);
+    }
+}
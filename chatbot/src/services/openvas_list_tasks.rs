@@ -0,0 +1,55 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::TaskList;
+
+/// Business-logic layer for "OpenVAS list tasks" using the Go backend.
+/// Returns every task with its status, progress, target and last report,
+/// keeping only those matching the given filters, so the agent can pick up
+/// scans it started before a restart.
+pub async fn openvas_list_tasks(
+    status: Option<&str>,
+    name_contains: Option<&str>,
+    target_id: Option<&str>,
+) -> Result<TaskList> {
+    let mut list = backend::current().list_tasks().await?;
+    list.tasks.retain(|t| {
+        status.is_none_or(|s| t.status.eq_ignore_ascii_case(s.trim()))
+            && name_contains.is_none_or(|n| t.name.to_lowercase().contains(&n.trim().to_lowercase()))
+            && target_id.is_none_or(|id| t.target_id.as_deref() == Some(id.trim()))
+    });
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn finished_tasks_carry_their_last_report() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let done = mock.create_task("nightly web", "config", &target.id, None, None).await.unwrap();
+        mock.create_task("weekly web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&done.id).await.unwrap();
+
+        let (finished, weekly, for_target) = with_backend(mock, async {
+            (
+                openvas_list_tasks(Some("done"), None, None).await.unwrap(),
+                openvas_list_tasks(None, Some("Weekly"), None).await.unwrap(),
+                openvas_list_tasks(None, None, Some(&target.id)).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(finished.tasks.len(), 1);
+        assert_eq!(finished.tasks[0].id, done.id);
+        assert!(finished.tasks[0].last_report_id.is_some());
+        assert_eq!(weekly.tasks[0].status, "New");
+        assert_eq!(weekly.tasks[0].last_report_id, None);
+        assert_eq!(for_target.tasks.len(), 2);
+    }
+}
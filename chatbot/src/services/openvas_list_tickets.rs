@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{ListTicketsArgs, TicketList};
+
+/// Business-logic layer for "OpenVAS list tickets" using the Go backend.
+/// Keeps the tickets matching every given filter, most severe first, so
+/// open work on the worst findings leads.
+pub async fn openvas_list_tickets(args: &ListTicketsArgs) -> Result<TicketList> {
+    let mut list = backend::current().list_tickets().await?;
+    let equals = |field: Option<&str>, filter: Option<&str>| {
+        filter.is_none_or(|f| field.is_some_and(|v| v.eq_ignore_ascii_case(f.trim())))
+    };
+    list.tickets.retain(|t| {
+        equals(Some(&t.status), args.status.as_deref())
+            && equals(t.assignee.as_deref(), args.assignee.as_deref())
+            && equals(t.task_id.as_deref(), args.task_id.as_deref())
+            && args.host_contains.as_deref().is_none_or(|f| t.host.as_deref().is_some_and(|h| h.contains(f.trim())))
+    });
+    list.tickets.sort_by(|a, b| b.severity.unwrap_or(0.0).total_cmp(&a.severity.unwrap_or(0.0)));
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::{ReportResult, TicketChanges, TicketStatus};
+
+    fn args() -> ListTicketsArgs {
+        ListTicketsArgs { status: None, assignee: None, host_contains: None, task_id: None }
+    }
+
+    #[tokio::test]
+    async fn tickets_are_filtered_and_most_severe_first() {
+        let finding = |id: &str, host: &str, severity: f64| ReportResult {
+            id: id.into(),
+            name: format!("finding {id}"),
+            host: host.into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity,
+            threat: "High".into(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        };
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding("r1", "10.0.0.5", 5.0),
+            finding("r2", "10.0.0.6", 9.8),
+            finding("r3", "10.0.1.7", 7.5),
+        ]));
+        let target = mock.create_target("web", "10.0.0.0/16", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let mut ids = Vec::new();
+        for (result, assignee) in [("r1", "ops"), ("r2", "admin"), ("r3", "ops")] {
+            ids.push(mock.create_ticket(result, assignee, "fix it").await.unwrap().id);
+        }
+        let fixed = TicketChanges { status: Some(TicketStatus::Fixed), note: Some("patched".into()), assignee: None };
+        mock.modify_ticket(&ids[2], &fixed).await.unwrap();
+
+        with_backend(mock, async {
+            let hosts = |args: ListTicketsArgs| async move {
+                let list = openvas_list_tickets(&args).await.unwrap();
+                list.tickets.into_iter().map(|t| t.host.unwrap()).collect::<Vec<_>>()
+            };
+            assert_eq!(hosts(args()).await, ["10.0.0.6", "10.0.1.7", "10.0.0.5"]);
+            assert_eq!(hosts(ListTicketsArgs { status: Some("open".into()), ..args() }).await, ["10.0.0.6", "10.0.0.5"]);
+            assert_eq!(hosts(ListTicketsArgs { assignee: Some("ops".into()), ..args() }).await, ["10.0.1.7", "10.0.0.5"]);
+            assert_eq!(hosts(ListTicketsArgs { host_contains: Some("10.0.0.".into()), ..args() }).await.len(), 2);
+            assert_eq!(hosts(ListTicketsArgs { task_id: Some("task-0".into()), ..args() }).await.len(), 0);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,10 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::TrashList;
+
+/// Business-logic layer for "OpenVAS list trash" using the Go backend.
+/// For now this is a thin wrapper around the low-level HTTP client.
+pub async fn openvas_list_trash() -> Result<TrashList> {
+    backend::current().list_trash().await
+}
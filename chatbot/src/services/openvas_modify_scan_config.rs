@@ -0,0 +1,96 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{ConfigChanges, ModifiedConfig};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS modify scan config" using the Go
+/// backend. Refuses empty changes and families or NVTs both enabled and
+/// disabled. gvmd silently selects nothing for a family name it does not
+/// know, so enabled families missing from the result are noted.
+pub async fn openvas_modify_scan_config(config_id: &str, changes: &ConfigChanges) -> Result<ModifiedConfig> {
+    if changes.is_empty() {
+        return Err(ToolError::invalid_input(
+            "nothing to change: give enable_families, disable_families, enable_nvts or disable_nvts",
+        )
+        .into());
+    }
+    let both = |enable: &[String], disable: &[String]| {
+        enable.iter().map(|e| e.trim()).find(|e| disable.iter().any(|d| d.trim() == *e)).map(str::to_string)
+    };
+    if let Some(name) = both(&changes.enable_families, &changes.disable_families)
+        .or_else(|| both(&changes.enable_nvts, &changes.disable_nvts))
+    {
+        return Err(ToolError::invalid_input(format!("'{name}' is both enabled and disabled")).into());
+    }
+
+    let mut modified = backend::current().modify_config(config_id.trim(), changes).await?;
+    for family in &changes.enable_families {
+        let family = family.trim();
+        if !modified.families.iter().any(|f| f.name == family) {
+            modified.notes.push(format!(
+                "no NVT family is named '{family}', so nothing was enabled for it; family names are case-sensitive"
+            ));
+        }
+    }
+    Ok(modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+    use crate::services::openvas_clone_scan_config::openvas_clone_scan_config;
+    use crate::services::openvas_list_configs::openvas_list_configs;
+
+    const FULL_AND_FAST: &str = "daba56c8-73ec-11df-a475-002264764cea";
+
+    #[tokio::test]
+    async fn clones_can_drop_families_and_single_nvts() {
+        let mock = Arc::new(MockBackend::default());
+        with_backend(mock.clone(), async {
+            let clone = openvas_clone_scan_config(FULL_AND_FAST, "web only, no DoS", None).await.unwrap();
+            let configs = openvas_list_configs().await.unwrap().configs;
+            assert_eq!(configs[1].name, "web only, no DoS");
+
+            let changes = ConfigChanges {
+                enable_families: vec!["Web application abuses".into(), "web application abuses".into()],
+                disable_families: vec!["Denial of Service".into()],
+                disable_nvts: vec!["1.3.6.1.4.1.25623.1.0.10102".into()],
+                ..Default::default()
+            };
+            let modified = openvas_modify_scan_config(&clone.id, &changes).await.unwrap();
+            let families: Vec<_> = modified.families.iter().map(|f| (f.name.as_str(), f.nvt_count, f.growing)).collect();
+            assert_eq!(families, [("General", 1, false), ("Web application abuses", 2, true)]);
+            assert_eq!(modified.notes.len(), 1);
+            assert!(modified.notes[0].contains("'web application abuses'"), "{}", modified.notes[0]);
+
+            let err = openvas_modify_scan_config(FULL_AND_FAST, &changes).await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "backend_error");
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn empty_and_contradictory_changes_are_refused_before_the_backend() {
+        let mock = Arc::new(MockBackend::default());
+        with_backend(mock.clone(), async {
+            let err = openvas_modify_scan_config("config-1", &ConfigChanges::default()).await.unwrap_err();
+            assert!(err.to_string().contains("nothing to change"), "{err}");
+            let changes = ConfigChanges {
+                enable_nvts: vec!["1.2.3".into()],
+                disable_nvts: vec![" 1.2.3 ".into()],
+                ..Default::default()
+            };
+            let err = openvas_modify_scan_config("config-1", &changes).await.unwrap_err();
+            assert!(err.to_string().contains("'1.2.3' is both"), "{err}");
+            let err = openvas_clone_scan_config(FULL_AND_FAST, " ", None).await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "invalid_input");
+        })
+        .await;
+        assert!(mock.calls().is_empty());
+    }
+}
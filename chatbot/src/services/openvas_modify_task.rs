@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{ModifiedTask, TaskChanges, BUSY_TASK_STATUSES};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS modify task" using the Go backend.
+/// Refuses empty changes and tasks that are running or queued; gvmd itself
+/// refuses config or target changes once a task has run.
+pub async fn openvas_modify_task(task_id: &str, changes: &TaskChanges) -> Result<ModifiedTask> {
+    if changes.is_empty() {
+        return Err(ToolError::invalid_input(
+            "nothing to change: give name, config_id, target_id, schedule_id, alert_ids or preferences",
+        )
+        .into());
+    }
+    let state = backend::current().get_task_status(task_id).await?;
+    if let Some(status) = state.status.filter(|s| BUSY_TASK_STATUSES.contains(&s.as_str())) {
+        return Err(ToolError::invalid_input(format!(
+            "task {task_id} is {status}; stop it first (openvas_stop_task)"
+        ))
+        .into());
+    }
+    backend::current().modify_task(task_id, changes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::{BackendError, BackendErrorKind};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn new_tasks_can_be_pointed_at_another_target() {
+        let mock = Arc::new(MockBackend::default());
+        let wrong = mock.create_target("wrong", "10.0.0.5", None, None).await.unwrap();
+        let right = mock.create_target("right", "10.0.0.6", None, None).await.unwrap();
+        let task = mock.create_task("scan", "config", &wrong.id, None, None).await.unwrap();
+
+        let changes = TaskChanges {
+            name: Some("scan right".into()),
+            target_id: Some(right.id.clone()),
+            ..Default::default()
+        };
+        let (modified, empty) = with_backend(mock.clone(), async {
+            (
+                openvas_modify_task(&task.id, &changes).await,
+                openvas_modify_task(&task.id, &TaskChanges::default()).await,
+            )
+        })
+        .await;
+        modified.unwrap();
+        assert_eq!(ToolError::classify(empty.unwrap_err()).kind(), "invalid_input");
+        let listed = mock.list_tasks().await.unwrap().tasks;
+        assert_eq!(listed[0].name, "scan right");
+        assert_eq!(listed[0].target_id.as_deref(), Some(right.id.as_str()));
+    }
+
+    #[tokio::test]
+    async fn running_and_finished_tasks_keep_their_target() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let changes = TaskChanges {
+            target_id: Some(target.id.clone()),
+            ..Default::default()
+        };
+
+        let running = with_backend(mock.clone(), openvas_modify_task(&task.id, &changes)).await;
+        assert!(ToolError::classify(running.unwrap_err()).to_string().contains("is Running"));
+
+        mock.stop_task(&task.id).await.unwrap();
+        let stopped = with_backend(mock.clone(), openvas_modify_task(&task.id, &changes)).await;
+        let err = stopped.unwrap_err();
+        assert_eq!(err.downcast_ref::<BackendError>().unwrap().kind, BackendErrorKind::InvalidRequest);
+    }
+}
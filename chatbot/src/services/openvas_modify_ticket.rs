@@ -0,0 +1,89 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{Ticket, TicketChanges};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS modify ticket" using the Go backend.
+/// gvmd keeps one note per status, so a status change needs a note and a
+/// note needs a status to belong to.
+pub async fn openvas_modify_ticket(ticket_id: &str, changes: &TicketChanges) -> Result<Ticket> {
+    if changes.is_empty() {
+        return Err(ToolError::invalid_input("nothing to change: give status with a note, or assignee").into());
+    }
+    let note = changes.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    match (changes.status, note) {
+        (Some(status), None) => {
+            return Err(ToolError::invalid_input(format!(
+                "a note is required to set a ticket {}, e.g. the fix applied or why it is closed",
+                status.as_str()
+            ))
+            .into());
+        }
+        (None, Some(_)) => {
+            return Err(ToolError::invalid_input("a note is kept with a status change: give status too").into());
+        }
+        _ => {}
+    }
+    let changes = TicketChanges { note: note.map(str::to_string), ..changes.clone() };
+    backend::current().modify_ticket(ticket_id.trim(), &changes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::{ReportResult, TicketStatus};
+
+    #[tokio::test]
+    async fn tickets_move_through_their_statuses() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![ReportResult {
+            id: "result-1".into(),
+            name: "OpenSSH Multiple Vulnerabilities".into(),
+            host: "10.0.0.5".into(),
+            port: "22/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.811011".into(),
+            severity: 7.8,
+            threat: "High".into(),
+            qod: None,
+            cves: Vec::new(),
+            solution: None,
+        }]));
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let ticket_id = mock.create_ticket("result-1", "ops", "Upgrade OpenSSH").await.unwrap().id;
+
+        with_backend(mock.clone(), async {
+            let change = |status: Option<TicketStatus>, note: Option<&str>, assignee: Option<&str>| TicketChanges {
+                status,
+                note: note.map(str::to_string),
+                assignee: assignee.map(str::to_string),
+            };
+            let ticket = openvas_modify_ticket(&ticket_id, &change(None, None, Some("admin"))).await.unwrap();
+            assert_eq!((ticket.status.as_str(), ticket.assignee.as_deref()), ("Open", Some("admin")));
+
+            let fixed = change(Some(TicketStatus::Fixed), Some(" Upgraded to 9.6 "), None);
+            let ticket = openvas_modify_ticket(&ticket_id, &fixed).await.unwrap();
+            assert_eq!(ticket.status, "Fixed");
+            assert_eq!(ticket.fixed_note.as_deref(), Some("Upgraded to 9.6"));
+            assert!(ticket.fixed_time.is_some());
+
+            for bad in [
+                change(None, None, None),
+                change(Some(TicketStatus::Closed), Some("  "), None),
+                change(None, Some("just a note"), None),
+            ] {
+                let err = openvas_modify_ticket(&ticket_id, &bad).await.unwrap_err();
+                assert_eq!(ToolError::classify(err).kind(), "invalid_input");
+            }
+            let err = openvas_modify_ticket("ticket-0", &change(None, None, Some("ops"))).await.unwrap_err();
+            assert_eq!(ToolError::classify(err).kind(), "backend_error");
+        })
+        .await;
+        assert_eq!(mock.calls().iter().filter(|c| c.starts_with("modify_ticket")).count(), 3);
+    }
+}
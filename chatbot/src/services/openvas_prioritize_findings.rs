@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::enrichment;
+use crate::models::openvas::{PrioritizedFindings, ReportFilter, ReportFormat};
+use crate::services::openvas_get_report::openvas_get_report;
+
+/// Business-logic layer for "OpenVAS prioritize findings" using the Go
+/// backend. Fetches a report's findings and, with `enrich`, looks their
+/// CVEs up in NVD (CVSS v3 vector, publication date, references), CISA KEV
+/// and FIRST EPSS, then returns them most pressing first. Lookups go to the
+/// most severe findings' CVEs first, up to `max_cves`.
+pub async fn openvas_prioritize_findings(
+    report_id: &str,
+    filter: &ReportFilter,
+    enrich: bool,
+    max_cves: usize,
+) -> Result<PrioritizedFindings> {
+    let mut results = openvas_get_report(report_id, ReportFormat::Xml, filter, false).await?.results;
+    results.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+
+    let mut notes = Vec::new();
+    let details = if enrich {
+        let mut cves: Vec<String> = Vec::new();
+        for cve in results.iter().flat_map(|r| &r.cves) {
+            if !cves.contains(cve) {
+                cves.push(cve.clone());
+            }
+        }
+        if cves.len() > max_cves {
+            notes.push(format!("{} of {} CVEs not looked up (max_cves)", cves.len() - max_cves, cves.len()));
+            cves.truncate(max_cves);
+        }
+        let (details, lookup_notes) = enrichment::lookup(&cves).await;
+        notes.extend(lookup_notes);
+        details
+    } else {
+        BTreeMap::new()
+    };
+    Ok(PrioritizedFindings {
+        report_id: report_id.to_string(),
+        findings: enrichment::prioritize(results, &details),
+        notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::models::openvas::ReportResult;
+
+    fn finding(severity: f64, cves: &[&str]) -> ReportResult {
+        ReportResult {
+            id: format!("result-{severity}"),
+            name: "finding".into(),
+            host: "10.0.0.5".into(),
+            port: "443/tcp".into(),
+            nvt_oid: "1.3.6.1.4.1.25623.1.0.1".into(),
+            severity,
+            threat: "High".into(),
+            qod: Some(80),
+            cves: cves.iter().map(|c| c.to_string()).collect(),
+            solution: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn without_enrichment_findings_rank_by_severity() {
+        let mock = Arc::new(MockBackend::default().with_findings(vec![
+            finding(5.0, &["CVE-2016-6210"]),
+            finding(9.8, &[]),
+            finding(2.6, &[]),
+        ]));
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+        let report_id = mock.get_task_status(&task.id).await.unwrap().last_report_id.unwrap();
+
+        let filter = ReportFilter {
+            min_severity: Some(4.0),
+            min_qod: None,
+        };
+        let prioritized =
+            with_backend(mock, openvas_prioritize_findings(&report_id, &filter, false, 25)).await.unwrap();
+        let order: Vec<&str> = prioritized.findings.iter().map(|f| f.priority.as_str()).collect();
+        assert_eq!(order, ["high", "medium"]);
+        assert!(prioritized.findings.iter().all(|f| f.cve_details.is_empty()));
+        assert!(prioritized.notes.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::nmap_scan::{FastTiming, ScanRequest, ScanType};
+use crate::models::openvas::ConfigRecommendation;
+use crate::tagging;
+use crate::tool_error::ToolError;
+
+/// gvmd's predefined config for vulnerability scans; the right one for
+/// nearly every target, with the port range doing the narrowing.
+const FULL_AND_FAST: &str = "Full and fast";
+
+/// Database ports that rarely belong on a reachable interface.
+const DATABASE_PORTS: [u16; 7] = [1433, 1521, 3306, 5432, 6379, 9200, 27017];
+
+/// The config, port range and rationale for a target with `open_ports`
+/// running `services` (nmap's names, lowercase). Ports named by nmap's
+/// scan narrow the range, so OpenVAS skips its own discovery over 5000+
+/// ports; with none found, every TCP port is scanned in case services
+/// listen outside nmap's top 1000.
+fn recommend(open_ports: &BTreeSet<u16>, services: &BTreeSet<String>) -> (String, Vec<String>) {
+    let has = |ports: &[u16], names: &[&str]| {
+        ports.iter().any(|p| open_ports.contains(p)) || services.iter().any(|s| names.iter().any(|n| s.contains(n)))
+    };
+    let mut rationale = Vec::new();
+    let port_range = if open_ports.is_empty() {
+        rationale.push(
+            "nmap found no open port among its top 1000, so every TCP port is scanned; this is slow, and a host \
+             that filters probes may still show nothing"
+                .to_string(),
+        );
+        "T:1-65535".to_string()
+    } else {
+        rationale.push(format!(
+            "{FULL_AND_FAST} on the {} open port(s) nmap found: the same checks as a default scan, without \
+             OpenVAS probing thousands of closed ports",
+            open_ports.len()
+        ));
+        let ports: Vec<String> = open_ports.iter().map(u16::to_string).collect();
+        format!("T:{}", ports.join(","))
+    };
+    if has(&[22], &["ssh"]) {
+        rationale.push(
+            "SSH is open: attach an SSH credential (openvas_create_credential, openvas_attach_credential) for \
+             authenticated local security checks, which find far more than a remote scan"
+                .to_string(),
+        );
+    }
+    if has(&[139, 445], &["microsoft-ds", "netbios-ssn"]) {
+        rationale.push(
+            "SMB is open: attach an SMB credential for authenticated Windows checks (patch levels, registry)"
+                .to_string(),
+        );
+    }
+    if has(&[80, 443, 8000, 8080, 8443], &["http"]) {
+        rationale.push("web servers found: expect the web application checks to take most of the scan time".to_string());
+    }
+    if has(&DATABASE_PORTS, &["mysql", "postgresql", "ms-sql", "oracle", "redis", "mongodb", "elasticsearch"]) {
+        rationale.push("database services are reachable; check whether they should be exposed at all".to_string());
+    }
+    (port_range, rationale)
+}
+
+/// Business-logic layer for "OpenVAS recommend config": finds the open
+/// ports of `target` with a quick nmap scan (unless `open_ports` are
+/// given) and recommends the scan config and port range to create the
+/// OpenVAS task with, resolved to a config ID on gvmd.
+pub async fn openvas_recommend_config(
+    target: &str,
+    open_ports: Option<&[u16]>,
+    timing: FastTiming,
+) -> Result<ConfigRecommendation> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err(ToolError::invalid_input("target is empty").into());
+    }
+    let (ports, services) = match open_ports {
+        Some(ports) => (ports.iter().copied().filter(|p| *p != 0).collect(), BTreeSet::new()),
+        None => {
+            let request = ScanRequest {
+                target: target.to_string(),
+                timing: Some(timing.into()),
+                scan_type: Some(ScanType::TcpConnect),
+                service_detection: true,
+                ..Default::default()
+            };
+            let scan = backend::current().advanced_scan(&request).await?;
+            let observed = tagging::observe(&serde_json::to_value(&scan)?);
+            (observed.open_ports, observed.services)
+        }
+    };
+    let (port_range, mut rationale) = recommend(&ports, &services);
+
+    let configs = backend::current().list_configs().await?.configs;
+    let config_id = configs.iter().find(|c| c.name.eq_ignore_ascii_case(FULL_AND_FAST)).map(|c| c.id.clone());
+    if config_id.is_none() {
+        rationale.push(format!(
+            "gvmd has no '{FULL_AND_FAST}' config; pick another with openvas_list_scan_configs"
+        ));
+    }
+
+    Ok(ConfigRecommendation {
+        next: format!(
+            "create and start the scan with openvas_full_scan {{\"hosts\": \"{target}\", \"config_name\": \
+             \"{FULL_AND_FAST}\", \"port_range\": \"{port_range}\"}}"
+        ),
+        target: target.to_string(),
+        open_ports: ports.into_iter().collect(),
+        services: services.into_iter().collect(),
+        config_name: FULL_AND_FAST.to_string(),
+        config_id,
+        port_range,
+        rationale,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+
+    #[test]
+    fn found_ports_narrow_the_range_and_services_add_advice() {
+        let ports = BTreeSet::from([22, 443, 5432]);
+        let services = BTreeSet::from(["ssh".to_string(), "ssl/http".to_string(), "postgresql".to_string()]);
+        let (range, rationale) = recommend(&ports, &services);
+        assert_eq!(range, "T:22,443,5432");
+        assert_eq!(rationale.len(), 4);
+        assert!(rationale[1].starts_with("SSH is open"));
+
+        let (range, rationale) = recommend(&BTreeSet::new(), &BTreeSet::new());
+        assert_eq!(range, "T:1-65535");
+        assert_eq!(rationale.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn the_nmap_scan_decides_the_port_range() {
+        let raw = "Nmap scan report for 10.0.0.5\nHost is up.\nPORT    STATE SERVICE VERSION\n\
+                   22/tcp  open  ssh     OpenSSH 8.9p1\n80/tcp  open  http    nginx 1.18.0\n\
+                   Nmap done: 1 IP address (1 host up)\n";
+        let mock = Arc::new(MockBackend::default().with_scan("10.0.0.5", raw));
+        let (scanned, given) = with_backend(mock.clone(), async {
+            (
+                openvas_recommend_config("10.0.0.5", None, FastTiming::T4).await.unwrap(),
+                openvas_recommend_config("10.0.0.6", Some(&[3389]), FastTiming::T4).await.unwrap(),
+            )
+        })
+        .await;
+        assert_eq!(scanned.open_ports, [22, 80]);
+        assert_eq!(scanned.services, ["http", "ssh"]);
+        assert_eq!(scanned.port_range, "T:22,80");
+        assert_eq!(scanned.config_id.as_deref(), Some("daba56c8-73ec-11df-a475-002264764cea"));
+        assert!(scanned.next.contains("\"port_range\": \"T:22,80\""), "{}", scanned.next);
+        assert_eq!(given.port_range, "T:3389");
+        assert_eq!(mock.calls(), ["advanced_scan 10.0.0.5", "list_configs", "list_configs"]);
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::models::openvas::{RenderFormat, RenderedReport, ReportFilter, ReportFormat};
+use crate::report_render::{self, HostScan};
+use crate::services::openvas_get_report::openvas_get_report;
+use crate::{config, resources, tagging};
+
+/// Business-logic layer for "OpenVAS render report" using the Go backend.
+/// Renders a report's findings, and the latest nmap scans of
+/// `nmap_targets`, as a Markdown or HTML document with a summary table,
+/// per-host sections and remediation notes. The document is written to
+/// `--reports-dir` and kept as `openvas://report/{report_id}/{format}`.
+pub async fn openvas_render_report(
+    report_id: &str,
+    format: RenderFormat,
+    filter: &ReportFilter,
+    nmap_targets: &[String],
+) -> Result<RenderedReport> {
+    let report = openvas_get_report(report_id, ReportFormat::Xml, filter, false).await?;
+
+    let mut scans = Vec::new();
+    let mut missing_scans = Vec::new();
+    for target in nmap_targets {
+        match resources::latest_scan(target) {
+            Some((scanned_at, output)) => {
+                let seen = tagging::observe(&output);
+                scans.push(HostScan {
+                    target: target.clone(),
+                    scanned_at,
+                    open_ports: seen.open_ports,
+                    services: seen.services,
+                });
+            }
+            None => missing_scans.push(target.clone()),
+        }
+    }
+
+    let render = match format {
+        RenderFormat::Markdown => report_render::markdown,
+        RenderFormat::Html => report_render::html,
+    };
+    let document = render(&report.report_id, &report.results, &scans, Utc::now());
+
+    // Report IDs are UUIDs; anything else is kept out of the path.
+    let stem: String = report
+        .report_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    let dir = Path::new(&config::current().reports_dir).to_path_buf();
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let path = dir.join(format!("openvas-{stem}.{}", format.extension()));
+    std::fs::write(&path, &document).with_context(|| format!("writing {}", path.display()))?;
+
+    let hosts: BTreeSet<&str> =
+        report.results.iter().map(|r| r.host.as_str()).chain(scans.iter().map(|s| s.target.as_str())).collect();
+    Ok(RenderedReport {
+        resource_uri: resources::save_export(&report.report_id, format.name(), format.mime_type(), document),
+        path: path.display().to_string(),
+        findings: report.results.len(),
+        hosts: hosts.len(),
+        report_id: report.report_id,
+        format,
+        missing_scans,
+    })
+}
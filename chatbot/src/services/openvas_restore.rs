@@ -0,0 +1,46 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::RestoredObject;
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS restore" using the Go backend. Thin
+/// wrapper: gvmd refuses to restore an object whose dependencies are still
+/// in the trashcan, such as a task whose target was trashed with it, and
+/// that refusal comes back as a backend error.
+pub async fn openvas_restore(id: &str) -> Result<RestoredObject> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err(ToolError::invalid_input("id is empty").into());
+    }
+    backend::current().restore(id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn tasks_come_back_after_their_target() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.delete_task(&task.id, false).await.unwrap();
+        mock.delete_target(&target.id, false).await.unwrap();
+
+        let (early, target_back, task_back) = with_backend(mock.clone(), async {
+            (openvas_restore(&task.id).await, openvas_restore(&target.id).await, openvas_restore(&task.id).await)
+        })
+        .await;
+        let early = ToolError::classify(early.unwrap_err());
+        assert!(early.to_string().contains("Target must be restored first"), "{early}");
+        assert_eq!(target_back.unwrap().id, target.id);
+        assert_eq!(task_back.unwrap().id, task.id);
+        assert_eq!(mock.list_tasks().await.unwrap().tasks.len(), 1);
+        assert!(mock.list_trash().await.unwrap().objects.is_empty());
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::{ResumedTask, RESUMABLE_TASK_STATUSES};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS resume task" using the Go backend.
+/// Checks the task's status first, so the agent is told to start (not
+/// resume) a task that never ran, rather than getting gvmd's bare refusal.
+pub async fn openvas_resume_task(task_id: &str) -> Result<ResumedTask> {
+    let state = backend::current().get_task_status(task_id).await?;
+    if let Some(status) = state.status.filter(|s| !RESUMABLE_TASK_STATUSES.contains(&s.as_str())) {
+        let hint = match status.as_str() {
+            "New" => "start it with openvas_start_task",
+            "Done" => "it finished; start it again with openvas_start_task for a new report",
+            _ => "only Stopped or Interrupted tasks can be resumed",
+        };
+        return Err(ToolError::invalid_input(format!("task {task_id} is {status}; {hint}")).into());
+    }
+    backend::current().resume_task(task_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn stopped_tasks_resume_and_new_ones_are_sent_to_start() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let stopped = mock.create_task("long scan", "config", &target.id, None, None).await.unwrap();
+        let fresh = mock.create_task("never ran", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&stopped.id).await.unwrap();
+        mock.stop_task(&stopped.id).await.unwrap();
+
+        let (resumed, refused) = with_backend(mock.clone(), async {
+            (openvas_resume_task(&stopped.id).await, openvas_resume_task(&fresh.id).await)
+        })
+        .await;
+        assert_eq!(resumed.unwrap().task_id, stopped.id);
+        let status = mock.get_task_status(&stopped.id).await.unwrap();
+        assert_eq!(status.status.as_deref(), Some("Running"));
+        let refused = ToolError::classify(refused.unwrap_err());
+        assert_eq!(refused.kind(), "invalid_input");
+        assert!(refused.to_string().contains("openvas_start_task"));
+    }
+}
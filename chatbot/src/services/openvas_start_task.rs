@@ -1,13 +1,11 @@
 use anyhow::Result;
-use serde_json::Value;
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::StartedTask;
 
 /// Business-logic layer for "OpenVAS start task" using the Go backend.
-/// Thin wrapper around the low-level HTTP client. Returns the raw JSON
-/// from the Go API, which includes the `task_id` and `response_raw`
-/// (the XML <start_task_response/> from gvmd).
-pub async fn openvas_start_task(task_id: &str) -> Result<Value> {
-    openvas::start_task(task_id).await
+/// Thin wrapper around the low-level HTTP client. Returns the `task_id`
+/// and `response_raw` (the XML <start_task_response/> from gvmd).
+pub async fn openvas_start_task(task_id: &str) -> Result<StartedTask> {
+    backend::current().start_task(task_id).await
 }
-
@@ -0,0 +1,39 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::models::openvas::StoppedTask;
+
+/// Business-logic layer for "OpenVAS stop task" using the Go backend.
+/// Thin wrapper around the low-level HTTP client. Returns the `task_id`
+/// and `response_raw` (the XML <stop_task_response/> from gvmd).
+pub async fn openvas_stop_task(task_id: &str) -> Result<StoppedTask> {
+    backend::current().stop_task(task_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::{BackendError, BackendErrorKind};
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn only_running_tasks_stop() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let (stopped, again) = with_backend(mock.clone(), async {
+            (openvas_stop_task(&task.id).await, openvas_stop_task(&task.id).await)
+        })
+        .await;
+        assert_eq!(stopped.unwrap().task_id, task.id);
+        let err = again.unwrap_err();
+        assert_eq!(err.downcast_ref::<BackendError>().unwrap().kind, BackendErrorKind::InvalidRequest);
+        let status = mock.get_task_status(&task.id).await.unwrap();
+        assert_eq!(status.status.as_deref(), Some("Stopped"));
+    }
+}
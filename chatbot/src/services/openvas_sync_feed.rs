@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use crate::api::backend;
+use crate::config;
+use crate::models::openvas::{FeedType, SyncedFeed};
+use crate::tool_error::ToolError;
+
+/// Business-logic layer for "OpenVAS sync feed" using the Go backend.
+/// Syncing downloads from Greenbone's feed servers, so it is refused in
+/// `--offline` mode.
+pub async fn openvas_sync_feed(feed: FeedType) -> Result<SyncedFeed> {
+    if config::current().offline {
+        return Err(ToolError::invalid_input(
+            "feed syncs download from Greenbone's feed servers, which offline mode forbids; \
+             sync the feeds on the GVM host instead",
+        )
+        .into());
+    }
+    backend::current().sync_feed(feed).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::with_backend;
+    use crate::api::mock::MockBackend;
+    use crate::services::openvas_feed_status::openvas_feed_status;
+
+    #[tokio::test]
+    async fn syncing_refreshes_stale_feeds() {
+        let mock = Arc::new(MockBackend::default().with_feed_version("202001010000"));
+        with_backend(mock.clone(), async {
+            let before = openvas_feed_status(7).await.unwrap();
+            assert!(before.feeds.iter().all(|f| f.stale));
+            assert_eq!(before.warnings.len(), 4);
+
+            let synced = openvas_sync_feed(FeedType::default()).await.unwrap();
+            assert_eq!(synced.feed, FeedType::All);
+            let after = openvas_feed_status(7).await.unwrap();
+            assert!(after.feeds.iter().all(|f| !f.stale && f.age_hours == Some(0)), "{:?}", after.feeds);
+            assert!(after.warnings.is_empty(), "{:?}", after.warnings);
+        })
+        .await;
+        assert_eq!(mock.calls(), ["list_feeds", "sync_feed all", "list_feeds"]);
+    }
+}
@@ -1,13 +1,120 @@
 use anyhow::Result;
-use serde_json::Value;
+use roxmltree::{Document, Node};
 
-use crate::api::openvas;
+use crate::api::backend;
+use crate::models::openvas::TaskStatus;
 
 /// Business-logic layer for "OpenVAS task status" using the Go backend.
-/// Thin wrapper around the low-level HTTP client. Returns the raw JSON
-/// from the Go API, which includes the `task_id` and `response_raw`
-/// (the XML <get_tasks_response/> from gvmd).
-pub async fn openvas_task_status(task_id: &str) -> Result<Value> {
-    openvas::get_task_status(task_id).await
+/// Returns the `task_id`, `response_raw` (the XML <get_tasks_response/>
+/// from gvmd) and what is parsed from it: status, progress, the task's
+/// name, its current and last report IDs and when its scan started and
+/// ended, so callers never have to read the XML themselves.
+pub async fn openvas_task_status(task_id: &str) -> Result<TaskStatus> {
+    let mut status = backend::current().get_task_status(task_id).await?;
+    fill_from_xml(&mut status);
+    Ok(status)
 }
 
+/// Trimmed text of the element at `path` below `node`, if not empty.
+fn text(node: Node, path: &[&str]) -> Option<String> {
+    let mut node = node;
+    for name in path {
+        node = node.children().find(|c| c.has_tag_name(*name))?;
+    }
+    node.text().map(str::trim).filter(|t| !t.is_empty()).map(str::to_string)
+}
+
+/// The `id` of the report element below `node`'s `parent` child.
+fn report_id(node: Node, parent: &str) -> Option<String> {
+    let report = node.children().find(|c| c.has_tag_name(parent))?.children().find(|c| c.has_tag_name("report"))?;
+    report.attribute("id").map(str::trim).filter(|id| !id.is_empty()).map(str::to_string)
+}
+
+/// Fill what the backend left out from `response_raw`; what it parsed
+/// itself is kept.
+fn fill_from_xml(status: &mut TaskStatus) {
+    let Ok(doc) = Document::parse(&status.response_raw) else {
+        return;
+    };
+    let Some(task) = doc.root_element().children().find(|c| c.has_tag_name("task")) else {
+        return;
+    };
+    status.status = status.status.take().or_else(|| text(task, &["status"]));
+    status.progress = status.progress.or_else(|| text(task, &["progress"])?.parse().ok());
+    status.last_report_id = status.last_report_id.take().or_else(|| report_id(task, "last_report"));
+    status.name = status.name.take().or_else(|| text(task, &["name"]));
+    status.current_report_id = status.current_report_id.take().or_else(|| report_id(task, "current_report"));
+
+    // A running scan's start; otherwise the last scan's start and end.
+    let current_start = text(task, &["current_report", "report", "scan_start"])
+        .or_else(|| text(task, &["current_report", "report", "timestamp"]));
+    let (start, end) = match current_start {
+        Some(start) => (Some(start), None),
+        None => (
+            text(task, &["last_report", "report", "scan_start"])
+                .or_else(|| text(task, &["last_report", "report", "timestamp"])),
+            text(task, &["last_report", "report", "scan_end"]),
+        ),
+    };
+    status.scan_start = status.scan_start.take().or(start);
+    status.scan_end = status.scan_end.take().or(end);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(raw: &str) -> TaskStatus {
+        let mut status = TaskStatus {
+            task_id: "task-1".into(),
+            response_raw: raw.into(),
+            ..TaskStatus::default()
+        };
+        fill_from_xml(&mut status);
+        status
+    }
+
+    #[test]
+    fn running_and_finished_tasks_are_parsed() {
+        let running = parsed(
+            r#"<get_tasks_response status="200" status_text="OK"><task id="task-1"><name>web nightly</name>
+            <status>Running</status><progress>42</progress>
+            <current_report><report id="report-2"><timestamp>2026-01-26T10:00:00Z</timestamp>
+            <scan_start>2026-01-26T10:00:04Z</scan_start></report></current_report>
+            <last_report><report id="report-1"><scan_start>2026-01-25T10:00:05Z</scan_start>
+            <scan_end>2026-01-25T10:42:17Z</scan_end></report></last_report></task></get_tasks_response>"#,
+        );
+        assert_eq!(running.status.as_deref(), Some("Running"));
+        assert_eq!(running.progress, Some(42));
+        assert_eq!(running.name.as_deref(), Some("web nightly"));
+        assert_eq!(running.current_report_id.as_deref(), Some("report-2"));
+        assert_eq!(running.last_report_id.as_deref(), Some("report-1"));
+        assert_eq!(running.scan_start.as_deref(), Some("2026-01-26T10:00:04Z"));
+        assert_eq!(running.scan_end, None);
+
+        let done = parsed(
+            r#"<get_tasks_response status="200" status_text="OK"><task id="task-1"><status>Done</status>
+            <progress>-1</progress><last_report><report id="report-1"><scan_start>2026-01-25T10:00:05Z</scan_start>
+            <scan_end>2026-01-25T10:42:17Z</scan_end></report></last_report></task></get_tasks_response>"#,
+        );
+        assert_eq!(done.progress, Some(-1));
+        assert_eq!(done.current_report_id, None);
+        assert_eq!(done.scan_start.as_deref(), Some("2026-01-25T10:00:05Z"));
+        assert_eq!(done.scan_end.as_deref(), Some("2026-01-25T10:42:17Z"));
+    }
+
+    #[test]
+    fn backend_fields_win_and_bad_xml_is_ignored() {
+        let mut status = TaskStatus {
+            task_id: "task-1".into(),
+            response_raw: "<get_tasks_response><task><status>Done</status></task></get_tasks_response>".into(),
+            status: Some("Stopped".into()),
+            ..TaskStatus::default()
+        };
+        fill_from_xml(&mut status);
+        assert_eq!(status.status.as_deref(), Some("Stopped"));
+
+        let unparsable = parsed("not xml");
+        assert_eq!(unparsable.status, None);
+    }
+}
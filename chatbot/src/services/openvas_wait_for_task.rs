@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::json;
+use tokio::time::Instant;
+
+use crate::api::backend;
+use crate::models::openvas::{WaitedTask, FINISHED_TASK_STATUSES};
+use crate::{partial, progress};
+
+/// Business-logic layer for waiting on a task: polls its status every
+/// `poll_interval` until it is Done, Stopped or Interrupted, or `max_wait`
+/// would be exceeded, reporting each poll as progress (gvmd's percentage)
+/// and as partial output. Running past `max_wait` is not an error: the
+/// result says `finished: false` with the latest status.
+pub async fn openvas_wait_for_task(task_id: &str, max_wait: Duration, poll_interval: Duration) -> Result<WaitedTask> {
+    let start = Instant::now();
+    loop {
+        let state = backend::current().get_task_status(task_id).await?;
+        let Some(status) = state.status else {
+            anyhow::bail!("backend returned no parsed status for task {task_id}");
+        };
+        let finished = FINISHED_TASK_STATUSES.contains(&status.as_str());
+        let progress = state.progress.unwrap_or(0);
+        let waited = WaitedTask {
+            task_id: task_id.to_string(),
+            finished,
+            status,
+            progress,
+            report_id: state.last_report_id,
+            waited_secs: start.elapsed().as_secs(),
+        };
+
+        let percent = if finished { 100 } else { progress.clamp(0, 100) };
+        progress::report(percent as f64, Some(100.0), &format!("task {task_id} is {}", waited.status));
+        partial::report(json!(waited));
+        if finished || start.elapsed() + poll_interval > max_wait {
+            return Ok(waited);
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::Value;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::mock::MockBackend;
+    use crate::session::{self, Session};
+
+    #[tokio::test]
+    async fn finished_tasks_return_their_report_with_progress_notifications() {
+        let mock = Arc::new(MockBackend::default());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let wait = openvas_wait_for_task(&task.id, Duration::from_secs(60), Duration::from_secs(1));
+        let wait = session::scope(Session::new("test", tx), progress::scope(Some(json!(7)), wait));
+        let waited = with_backend(mock.clone(), wait).await.unwrap();
+
+        assert!(waited.finished);
+        assert_eq!(waited.status, "Done");
+        assert!(waited.report_id.is_some());
+        let sent: Value = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+        assert_eq!(sent["params"]["progressToken"], 7);
+        assert_eq!(sent["params"]["progress"], 100.0);
+    }
+
+    #[tokio::test]
+    async fn running_tasks_are_polled_until_the_wait_runs_out() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let task = mock.create_task("scan web", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&task.id).await.unwrap();
+
+        let wait = openvas_wait_for_task(&task.id, Duration::from_millis(50), Duration::from_millis(10));
+        let waited = with_backend(mock.clone(), wait).await.unwrap();
+
+        assert!(!waited.finished);
+        assert_eq!(waited.status, "Running");
+        let polls = mock.calls().iter().filter(|c| c.starts_with("get_task_status")).count();
+        assert!((3..=5).contains(&polls), "{polls} polls");
+    }
+}
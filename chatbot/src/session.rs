@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::auth::Principal;
+use crate::capabilities::{self, Capability};
+
+/// MCP log levels (syslog severities), least severe first.
+const LOG_LEVELS: [&str; 8] = [
+    "debug", "info", "notice", "warning", "error", "critical", "alert", "emergency",
+];
+
+/// Per-connection context, available to everything that runs on behalf of a
+/// request through `current()`. Besides identifying the client, it lets
+/// server code send notifications and server-initiated requests (such as
+/// elicitation) back over the same connection.
+#[derive(Debug)]
+pub struct Session {
+    /// How the client is connected (`stdio`, a TCP peer address, ...).
+    transport: String,
+    /// `clientInfo.name` from `initialize`, once the client has sent it.
+    client_name: Mutex<Option<String>>,
+    /// `capabilities` object the client declared in `initialize`.
+    client_capabilities: Mutex<Value>,
+    /// Whoever the client's auth token identified (see `auth`).
+    principal: Mutex<Option<Principal>>,
+    /// Serialized messages for the connection's writer task. Taken on close.
+    outgoing: Mutex<Option<mpsc::UnboundedSender<String>>>,
+    /// Server-initiated requests awaiting a client response, by request id.
+    pending: Mutex<HashMap<String, oneshot::Sender<Value>>>,
+    next_request_id: AtomicU64,
+    /// Minimum level for `notifications/message`, as an index into
+    /// `LOG_LEVELS`. `None` until the client calls `logging/setLevel`.
+    log_level: Mutex<Option<usize>>,
+}
+
+/// Every session ever opened; dead ones are pruned on `broadcast`.
+static OPEN: Mutex<Vec<Weak<Session>>> = Mutex::new(Vec::new());
+
+impl Session {
+    pub fn new(transport: impl Into<String>, outgoing: mpsc::UnboundedSender<String>) -> Arc<Self> {
+        let session = Arc::new(Self {
+            transport: transport.into(),
+            client_name: Mutex::new(None),
+            client_capabilities: Mutex::new(Value::Null),
+            principal: Mutex::new(None),
+            outgoing: Mutex::new(Some(outgoing)),
+            pending: Mutex::new(HashMap::new()),
+            next_request_id: AtomicU64::new(1),
+            log_level: Mutex::new(None),
+        });
+        OPEN.lock().unwrap().push(Arc::downgrade(&session));
+        session
+    }
+
+    /// Record what the client told us about itself in `initialize`.
+    pub fn set_client_info(&self, params: &Value) {
+        if let Some(name) = params.pointer("/clientInfo/name").and_then(|v| v.as_str()) {
+            *self.client_name.lock().unwrap() = Some(name.to_string());
+        }
+        if let Some(caps) = params.get("capabilities") {
+            *self.client_capabilities.lock().unwrap() = caps.clone();
+        }
+    }
+
+    /// Record who the client authenticated as in `initialize`.
+    pub fn set_principal(&self, principal: Option<Principal>) {
+        *self.principal.lock().unwrap() = principal;
+    }
+
+    pub fn principal(&self) -> Option<Principal> {
+        self.principal.lock().unwrap().clone()
+    }
+
+    /// Whether the client declared the named capability (e.g. `elicitation`).
+    pub fn client_supports(&self, capability: &str) -> bool {
+        self.client_capabilities
+            .lock()
+            .unwrap()
+            .get(capability)
+            .is_some()
+    }
+
+    /// Human-readable identity of whoever is driving this session, used to
+    /// attribute jobs and audit records.
+    pub fn operator(&self) -> String {
+        let who = match self.client_name.lock().unwrap().as_deref() {
+            Some(name) => format!("{name}@{}", self.transport),
+            None => self.transport.clone(),
+        };
+        match self.principal() {
+            Some(Principal { name: Some(holder), .. }) => format!("{holder} via {who}"),
+            _ => who,
+        }
+    }
+
+    /// Send a server-initiated request and wait for the client's answer.
+    pub async fn request(&self, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+        let id = format!("srv-{}", self.next_request_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id.clone(), tx);
+
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = self.send(&message) {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(err);
+        }
+
+        let response = match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(resp)) => resp,
+            Ok(Err(_)) => anyhow::bail!("session closed before the client answered {method}"),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                anyhow::bail!("client did not answer {method} within {}s", timeout.as_secs());
+            }
+        };
+
+        if let Some(err) = response.get("error") {
+            anyhow::bail!("client rejected {method}: {err}");
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Handle `logging/setLevel`.
+    pub fn set_log_level(&self, level: &str) -> Result<()> {
+        let rank = LOG_LEVELS
+            .iter()
+            .position(|l| *l == level)
+            .ok_or_else(|| anyhow::anyhow!("unknown log level `{level}`"))?;
+        *self.log_level.lock().unwrap() = Some(rank);
+        Ok(())
+    }
+
+    /// Send a `notifications/message` log entry if logging is enabled and the
+    /// client asked for messages at this level.
+    pub fn log(&self, level: &str, data: Value) {
+        let wanted = *self.log_level.lock().unwrap();
+        let rank = LOG_LEVELS.iter().position(|l| *l == level).unwrap_or(0);
+        if !capabilities::enabled(Capability::Logging) || wanted.is_none_or(|w| rank < w) {
+            return;
+        }
+        let message = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": { "level": level, "logger": "hacker_agent", "data": data },
+        });
+        // Logging is best effort; a closed session has nobody to tell.
+        let _ = self.send(&message);
+    }
+
+    /// Send a notification with `params` to this client.
+    pub fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    /// Route a client response to the server-initiated request awaiting it.
+    pub fn complete_request(&self, response: Value) {
+        let id = match response.get("id") {
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => return,
+        };
+        if let Some(tx) = self.pending.lock().unwrap().remove(&id) {
+            let _ = tx.send(response);
+        }
+    }
+
+    /// Stop accepting outgoing messages so the writer can drain and exit.
+    pub fn close(&self) {
+        self.outgoing.lock().unwrap().take();
+        self.pending.lock().unwrap().clear();
+    }
+
+    fn send(&self, message: &Value) -> Result<()> {
+        let text = serde_json::to_string(message)?;
+        self.outgoing
+            .lock()
+            .unwrap()
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("session is closed"))?
+            .send(text)
+            .map_err(|_| anyhow::anyhow!("session is closed"))
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: Arc<Session>;
+}
+
+/// Send a parameterless notification (e.g. `notifications/tools/list_changed`)
+/// to every connected client.
+pub fn broadcast(method: &str) {
+    let message = json!({ "jsonrpc": "2.0", "method": method });
+    let mut open = OPEN.lock().unwrap();
+    // Closed sessions refuse to send; forget them along with dropped ones.
+    open.retain(|weak| weak.upgrade().is_some_and(|s| s.send(&message).is_ok()));
+}
+
+/// Send a `notifications/message` log entry to every connected client that
+/// asked for messages at `level` (see `Session::log`).
+pub fn log_all(level: &str, data: Value) {
+    let mut open = OPEN.lock().unwrap();
+    open.retain(|weak| match weak.upgrade() {
+        Some(session) => {
+            session.log(level, data.clone());
+            true
+        }
+        None => false,
+    });
+}
+
+/// Run `fut` with `session` as the current session.
+pub async fn scope<F: std::future::Future>(session: Arc<Session>, fut: F) -> F::Output {
+    CURRENT.scope(session, fut).await
+}
+
+/// The session the current request belongs to, if any.
+pub fn current() -> Option<Arc<Session>> {
+    CURRENT.try_with(|s| s.clone()).ok()
+}
@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::nmap_scan::{NmapHost, NmapScanResult};
+
+/// Rules used when no `--tag-rules` file is configured. Same format as the
+/// file: a `rules` list of `tag` + `when` conditions.
+const DEFAULT_RULES: &str = r#"
+rules:
+  - tag: database-server
+    when:
+      any_port_open: [1433, 1521, 3306, 5432, 6379, 9042, 27017]
+  - tag: web-server
+    when:
+      any_service: [http, https, http-proxy, https-alt, http-alt]
+  - tag: remote-admin
+    when:
+      any_service: [ssh, telnet, rdp, ms-wbt-server, vnc]
+  - tag: mail-server
+    when:
+      any_service: [smtp, submission, pop3, pop3s, imap, imaps]
+  - tag: windows-host
+    when:
+      all_ports_open: [135, 445]
+"#;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    rules: Vec<Rule>,
+}
+
+/// One tagging rule: when every condition set in `when` holds for a scan
+/// result, the scanned target gets `tag`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    tag: String,
+    when: Condition,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Condition {
+    /// At least one of these ports is open.
+    #[serde(default)]
+    any_port_open: Vec<u16>,
+    /// All of these ports are open.
+    #[serde(default)]
+    all_ports_open: Vec<u16>,
+    /// At least one open port runs one of these services (case-insensitive).
+    #[serde(default)]
+    any_service: Vec<String>,
+    /// The raw scan output contains this text (case-insensitive).
+    #[serde(default)]
+    output_contains: Option<String>,
+}
+
+/// What a scan result says about its target, normalised across nmap text
+/// and XML output and the native scanners' structured output.
+#[derive(Debug, Default)]
+pub struct Observation {
+    pub open_ports: BTreeSet<u16>,
+    pub services: BTreeSet<String>,
+    text: String,
+}
+
+/// Tags accumulated for one target across scans.
+#[derive(Debug, Clone, Serialize)]
+pub struct Asset {
+    pub target: String,
+    pub tags: BTreeSet<String>,
+    pub tagged_at: DateTime<Utc>,
+    /// Tags the rules gave at the last scan, re-evaluated on the next one;
+    /// tags added by hand are left alone.
+    #[serde(skip)]
+    rule_tags: BTreeSet<String>,
+}
+
+static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+static ASSETS: Mutex<BTreeMap<String, Asset>> = Mutex::new(BTreeMap::new());
+
+/// Load tagging rules from `path`, or the built-in rules when `None`. Call
+/// once at startup so a broken rules file fails fast.
+pub fn install(path: Option<&str>) -> Result<()> {
+    let rules = match path {
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .with_context(|| format!("reading tag rules from {path}"))?;
+            parse_rules(&raw).with_context(|| format!("parsing tag rules in {path}"))?
+        }
+        None => parse_rules(DEFAULT_RULES)?,
+    };
+    let _ = RULES.set(rules);
+    Ok(())
+}
+
+fn parse_rules(raw: &str) -> Result<Vec<Rule>> {
+    let file: RuleFile = serde_yaml::from_str(raw)?;
+    for rule in &file.rules {
+        let c = &rule.when;
+        if c.any_port_open.is_empty()
+            && c.all_ports_open.is_empty()
+            && c.any_service.is_empty()
+            && c.output_contains.is_none()
+        {
+            anyhow::bail!("rule for tag `{}` has no conditions", rule.tag);
+        }
+    }
+    Ok(file.rules)
+}
+
+/// Evaluate the rules against a scan result of `target`. Each host the scan
+/// reports is tagged on its own (a /24 sweep tags its hosts, not the range),
+/// and rule tags from earlier scans that no longer match are dropped.
+pub fn apply(target: &str, output: &Value) {
+    if let Some(rules) = RULES.get() {
+        apply_rules(rules, target, output);
+    }
+}
+
+fn apply_rules(rules: &[Rule], target: &str, output: &Value) {
+    for (host, observation) in hosts(target, output) {
+        let matched: BTreeSet<String> = rules
+            .iter()
+            .filter(|r| r.when.matches(&observation))
+            .map(|r| r.tag.clone())
+            .collect();
+
+        let mut assets = ASSETS.lock().unwrap();
+        if matched.is_empty() && !assets.contains_key(&host) {
+            continue;
+        }
+        let asset = asset_entry(&mut assets, &host);
+        for stale in asset.rule_tags.difference(&matched) {
+            asset.tags.remove(stale);
+        }
+        asset.tags.extend(matched.iter().cloned());
+        asset.rule_tags = matched;
+        asset.tagged_at = Utc::now();
+    }
+}
+
+/// Split a scan result into the hosts it reports. nmap text output has one
+/// "Nmap scan report for" section per host that was up, named as nmap
+/// names it, and XML output one parsed host each; other results describe
+/// `target` as a whole.
+pub fn hosts(target: &str, output: &Value) -> Vec<(String, Observation)> {
+    const REPORT: &str = "Nmap scan report for ";
+    if let Some(parsed) = parsed(output) {
+        return parsed
+            .hosts
+            .iter()
+            .filter(|host| host.status == "up")
+            .filter_map(|host| {
+                let mut obs = Observation {
+                    text: serde_json::to_string(host).unwrap_or_default().to_ascii_lowercase(),
+                    ..Default::default()
+                };
+                observe_host(&mut obs, host);
+                Some((host.name()?.to_string(), obs))
+            })
+            .collect();
+    }
+    let Some(raw) = output.get("raw_output").and_then(|v| v.as_str()) else {
+        return vec![(target.to_string(), observe(output))];
+    };
+    raw.split(REPORT)
+        .skip(1)
+        .filter(|section| !section.lines().next().unwrap_or("").contains("[host down]"))
+        .filter_map(|section| {
+            let host = section.split_whitespace().next()?;
+            Some((host.to_string(), observe(&serde_json::json!({ "raw_output": section }))))
+        })
+        .collect()
+}
+
+fn asset_entry<'a>(assets: &'a mut BTreeMap<String, Asset>, target: &str) -> &'a mut Asset {
+    assets.entry(target.to_string()).or_insert_with(|| Asset {
+        target: target.to_string(),
+        tags: BTreeSet::new(),
+        tagged_at: Utc::now(),
+        rule_tags: BTreeSet::new(),
+    })
+}
+
+/// Tag `target` by hand (e.g. `monitored`), outside the rules.
+pub fn add_tags(target: &str, tags: impl IntoIterator<Item = String>) {
+    let mut assets = ASSETS.lock().unwrap();
+    let asset = asset_entry(&mut assets, target);
+    asset.tags.extend(tags);
+    asset.tagged_at = Utc::now();
+}
+
+/// Remove one tag from `target`, if present.
+pub fn remove_tag(target: &str, tag: &str) {
+    if let Some(asset) = ASSETS.lock().unwrap().get_mut(target) {
+        asset.tags.remove(tag);
+    }
+}
+
+/// Tagged assets, optionally only those carrying `tag`.
+pub fn assets(tag: Option<&str>) -> Vec<Asset> {
+    ASSETS
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|a| tag.is_none_or(|t| a.tags.contains(t)))
+        .cloned()
+        .collect()
+}
+
+impl Condition {
+    fn matches(&self, obs: &Observation) -> bool {
+        (self.any_port_open.is_empty() || self.any_port_open.iter().any(|p| obs.open_ports.contains(p)))
+            && self.all_ports_open.iter().all(|p| obs.open_ports.contains(p))
+            && (self.any_service.is_empty()
+                || self.any_service.iter().any(|s| obs.services.contains(&s.to_ascii_lowercase())))
+            && self
+                .output_contains
+                .as_ref()
+                .is_none_or(|needle| obs.text.contains(&needle.to_ascii_lowercase()))
+    }
+}
+
+pub fn observe(output: &Value) -> Observation {
+    static PORT_LINE: OnceLock<Regex> = OnceLock::new();
+    let port_line = PORT_LINE.get_or_init(|| {
+        Regex::new(r"(?m)^(\d+)/(?:tcp|udp)\s+open\s+(\S+)").expect("valid port line regex")
+    });
+
+    let mut obs = Observation::default();
+
+    // nmap backend shape: text in `raw_output`.
+    if let Some(raw) = output.get("raw_output").and_then(|v| v.as_str()) {
+        for caps in port_line.captures_iter(raw) {
+            if let Ok(port) = caps[1].parse() {
+                obs.open_ports.insert(port);
+            }
+            obs.services.insert(caps[2].to_ascii_lowercase());
+        }
+        obs.text = raw.to_ascii_lowercase();
+    } else {
+        obs.text = output.to_string().to_ascii_lowercase();
+    }
+
+    // XML scans: the ports of the parsed hosts.
+    for host in parsed(output).iter().flat_map(|p| &p.hosts) {
+        observe_host(&mut obs, host);
+    }
+
+    // Native scanner shape: `open_ports` plus identified `services`.
+    if let Some(ports) = output.get("open_ports").and_then(|v| v.as_array()) {
+        obs.open_ports.extend(ports.iter().filter_map(|p| p.as_u64()).filter_map(|p| u16::try_from(p).ok()));
+    }
+    if let Some(services) = output.get("services").and_then(|v| v.as_array()) {
+        // The banner grabber lists every probed port; only answered ones are open.
+        for s in services.iter().filter(|s| !s.get("banner").is_some_and(Value::is_null)) {
+            if let Some(port) = s.get("port").and_then(|p| p.as_u64()).and_then(|p| u16::try_from(p).ok()) {
+                obs.open_ports.insert(port);
+            }
+            if let Some(name) = s.get("service").and_then(|v| v.as_str()) {
+                obs.services.insert(name.to_ascii_lowercase());
+            }
+        }
+    }
+
+    obs
+}
+
+/// The parsed XML output of an nmap scan result, if it has one.
+fn parsed(output: &Value) -> Option<NmapScanResult> {
+    output.get("parsed").and_then(|p| NmapScanResult::deserialize(p).ok())
+}
+
+fn observe_host(obs: &mut Observation, host: &NmapHost) {
+    for port in host.ports.iter().filter(|p| p.state == "open") {
+        obs.open_ports.insert(port.port);
+        if let Some(service) = &port.service {
+            obs.services.insert(service.full_name().to_ascii_lowercase());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn tags_of(target: &str) -> Vec<String> {
+        assets(None)
+            .into_iter()
+            .find(|a| a.target == target)
+            .map(|a| a.tags.into_iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn loads_built_in_and_custom_rules() {
+        assert_eq!(parse_rules(DEFAULT_RULES).unwrap().len(), 5);
+
+        let rules = parse_rules("rules:\n  - tag: legacy-ssl\n    when:\n      output_contains: SSLv3\n").unwrap();
+        assert_eq!(rules[0].tag, "legacy-ssl");
+
+        let err = parse_rules("rules:\n  - tag: empty\n    when: {}\n").unwrap_err();
+        assert!(err.to_string().contains("no conditions"));
+        assert!(parse_rules("rules:\n  - tag: x\n    when:\n      any_port: [22]\n").is_err());
+    }
+
+    #[test]
+    fn conditions_match_observations() {
+        let obs = observe(&json!({
+            "raw_output": "135/tcp open msrpc\n445/tcp open microsoft-ds\n443/tcp open https\n| ssl: SSLv3 enabled"
+        }));
+        let condition = |yaml: &str| serde_yaml::from_str::<Condition>(yaml).unwrap();
+        assert!(condition("all_ports_open: [135, 445]").matches(&obs));
+        assert!(!condition("all_ports_open: [135, 3389]").matches(&obs));
+        assert!(condition("any_port_open: [22, 445]").matches(&obs));
+        assert!(condition("any_service: [HTTPS]").matches(&obs));
+        assert!(condition("output_contains: sslv3").matches(&obs));
+        assert!(!condition("{any_service: [https], any_port_open: [22]}").matches(&obs));
+
+        let native = observe(&json!({
+            "open_ports": [22],
+            "services": [
+                { "port": 22, "banner": "SSH-2.0-OpenSSH_9.2", "service": "ssh" },
+                { "port": 3306, "banner": null },
+            ],
+        }));
+        assert_eq!(native.open_ports, BTreeSet::from([22]));
+        assert!(native.services.contains("ssh"));
+    }
+
+    #[test]
+    fn tags_each_reported_host_and_drops_stale_rule_tags() {
+        let rules = parse_rules(DEFAULT_RULES).unwrap();
+        let sweep = "Nmap scan report for db.tagging.example (192.0.2.10)\n3306/tcp open mysql\n\n\
+                     Nmap scan report for 192.0.2.11\n22/tcp open ssh\n";
+        apply_rules(&rules, "192.0.2.0/24", &json!({ "raw_output": sweep }));
+        assert_eq!(tags_of("db.tagging.example"), ["database-server"]);
+        assert_eq!(tags_of("192.0.2.11"), ["remote-admin"]);
+        assert!(tags_of("192.0.2.0/24").is_empty());
+
+        add_tags("db.tagging.example", ["monitored".to_string()]);
+        let rescan = "Nmap scan report for db.tagging.example (192.0.2.10)\n443/tcp open https\n";
+        apply_rules(&rules, "db.tagging.example", &json!({ "raw_output": rescan }));
+        assert_eq!(tags_of("db.tagging.example"), ["monitored", "web-server"]);
+    }
+
+    #[test]
+    fn tags_the_hosts_of_xml_scans() {
+        let rules = parse_rules(DEFAULT_RULES).unwrap();
+        let port = |port: u16, service: &str, tunnel: Option<&str>| {
+            let service = json!({ "name": service, "tunnel": tunnel });
+            json!({ "protocol": "tcp", "port": port, "state": "open", "service": service })
+        };
+        let output = json!({
+            "target": "198.51.100.0/24",
+            "raw_output": "<nmaprun>...</nmaprun>",
+            "parsed": { "hosts": [
+                {
+                    "status": "up",
+                    "addresses": [{ "addr": "198.51.100.7", "addr_type": "ipv4" }],
+                    "ports": [port(5432, "postgresql", None), port(8443, "http", Some("ssl"))],
+                },
+                { "status": "down", "addresses": [{ "addr": "198.51.100.8", "addr_type": "ipv4" }] },
+            ] },
+        });
+        let obs = observe(&output);
+        assert_eq!(obs.open_ports, BTreeSet::from([5432, 8443]));
+        assert_eq!(obs.services, BTreeSet::from(["postgresql".to_string(), "ssl/http".to_string()]));
+        apply_rules(&rules, "198.51.100.0/24", &output);
+        assert_eq!(tags_of("198.51.100.7"), ["database-server"]);
+        assert!(tags_of("198.51.100.8").is_empty());
+    }
+}
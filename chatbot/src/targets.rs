@@ -0,0 +1,437 @@
+use std::net::IpAddr;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::policy::Substitution;
+
+/// Input fields that name the host(s) a tool call will touch.
+pub const TARGET_FIELDS: [&str; 3] = ["target", "subnet", "hosts"];
+
+/// Most hosts (a /8) a target list may cover to be split by `chunk_hosts`.
+const MAX_CHUNKED_HOSTS: u64 = 1 << 24;
+
+/// Most groups `chunk_hosts` produces; each becomes a GVM target.
+const MAX_CHUNKS: u64 = 4096;
+
+/// Return the first target-like field of a tool input, if any.
+pub fn target_of(input: &Value) -> Option<&str> {
+    TARGET_FIELDS
+        .iter()
+        .find_map(|k| input.get(*k).and_then(|v| v.as_str()))
+}
+
+/// Every entry of every target-like field of a tool input, with the scope
+/// it covers.
+pub fn entries_of(input: &Value) -> Vec<(String, Scope)> {
+    TARGET_FIELDS
+        .iter()
+        .filter_map(|k| input.get(*k).and_then(|v| v.as_str()))
+        .flat_map(entries)
+        .collect()
+}
+
+/// The entries of a comma/whitespace separated target list, with the scope
+/// each covers.
+pub fn entries(target: &str) -> Vec<(String, Scope)> {
+    target
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .map(|t| (t.to_string(), Scope::of(t)))
+        .collect()
+}
+
+/// Validate every target-like field of a tool input and convert
+/// internationalized domain names to their ASCII (punycode) form, since nmap
+/// and gvmd only understand A-labels. Each conversion is recorded so the
+/// client can see what was actually scanned. Host lists given as an array
+/// of strings are joined into one comma-separated list first, so scope and
+/// rate limits see every entry.
+pub fn normalize_input(input: &mut Value, substitutions: &mut Vec<Substitution>) -> Result<()> {
+    for field in TARGET_FIELDS {
+        if let Some(list) = input.get(field).and_then(Value::as_array) {
+            let Some(hosts) = list.iter().map(Value::as_str).collect::<Option<Vec<&str>>>() else {
+                anyhow::bail!("{field} must be a string or an array of strings");
+            };
+            input[field] = json!(hosts.join(","));
+        }
+        let Some(raw) = input.get(field).and_then(|v| v.as_str()).map(str::to_string) else {
+            continue;
+        };
+        let normalized = normalize_target_list(&raw)?;
+        if normalized != raw {
+            substitutions.push(Substitution {
+                field: field.to_string(),
+                requested: json!(raw),
+                applied: json!(normalized),
+                reason: "internationalized domain name converted to punycode".into(),
+            });
+            input[field] = json!(normalized);
+        }
+    }
+    Ok(())
+}
+
+/// Normalize a comma/whitespace separated list of targets, preserving the
+/// original separators.
+pub fn normalize_target_list(raw: &str) -> Result<String> {
+    let mut out = String::with_capacity(raw.len());
+    let mut token = String::new();
+    for c in raw.chars() {
+        if c == ',' || c.is_whitespace() {
+            if !token.is_empty() {
+                out.push_str(&normalize_target(&token)?);
+                token.clear();
+            }
+            out.push(c);
+        } else {
+            token.push(c);
+        }
+    }
+    if !token.is_empty() {
+        out.push_str(&normalize_target(&token)?);
+    }
+    if out.trim().is_empty() {
+        anyhow::bail!("target must not be empty");
+    }
+    Ok(out)
+}
+
+/// Number of hosts a comma/whitespace separated target list expands to:
+/// CIDRs count their whole block, octet ranges/wildcards (`10.0.1-3.*`) the
+/// product of each octet, and anything else (IPs, hostnames) one host.
+pub fn host_count(raw: &str) -> u64 {
+    raw.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .map(token_host_count)
+        .fold(0, u64::saturating_add)
+}
+
+/// Split a target list into groups of at most `max_hosts` hosts each, in
+/// order and without duplicates. IPv4 CIDRs larger than the limit are cut
+/// into smaller blocks; any other single entry over the limit is an error.
+/// Lists covering more than `MAX_CHUNKED_HOSTS` hosts, or needing more than
+/// `MAX_CHUNKS` groups, are refused before anything is expanded.
+pub fn chunk_hosts(raw: &str, max_hosts: u64) -> Result<Vec<Vec<String>>> {
+    if max_hosts == 0 {
+        anyhow::bail!("max_hosts must be at least 1");
+    }
+    let total = host_count(raw);
+    if total > MAX_CHUNKED_HOSTS {
+        anyhow::bail!("target list covers {total} hosts, more than the {MAX_CHUNKED_HOSTS} that can be split up");
+    }
+    if total.div_ceil(max_hosts) > MAX_CHUNKS {
+        anyhow::bail!(
+            "splitting {total} hosts into targets of at most {max_hosts} needs over {MAX_CHUNKS} targets; raise max_hosts"
+        );
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut entries = Vec::new();
+    for token in raw.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()) {
+        if !seen.insert(token.to_string()) {
+            continue;
+        }
+        if token_host_count(token) <= max_hosts {
+            entries.push(token.to_string());
+            continue;
+        }
+        let Some(blocks) = split_ipv4_cidr(token, max_hosts) else {
+            anyhow::bail!(
+                "`{token}` covers {} hosts, more than the per-target limit of {max_hosts}; split it into smaller ranges",
+                token_host_count(token)
+            );
+        };
+        entries.extend(blocks);
+        if entries.len() as u64 > MAX_CHUNKS {
+            anyhow::bail!("target list splits into more than {MAX_CHUNKS} targets; raise max_hosts");
+        }
+    }
+
+    let mut chunks: Vec<Vec<String>> = Vec::new();
+    let mut size = 0;
+    for entry in entries {
+        let count = token_host_count(&entry);
+        if chunks.is_empty() || size + count > max_hosts {
+            chunks.push(Vec::new());
+            size = 0;
+        }
+        size += count;
+        chunks.last_mut().expect("pushed above").push(entry);
+    }
+    Ok(chunks)
+}
+
+/// Cut an IPv4 CIDR into the largest sub-blocks that hold at most
+/// `max_hosts` addresses each.
+fn split_ipv4_cidr(token: &str, max_hosts: u64) -> Option<Vec<String>> {
+    let (host, bits) = token.split_once('/')?;
+    let base: std::net::Ipv4Addr = host.parse().ok()?;
+    let bits: u32 = bits.parse().ok().filter(|b| *b <= 32)?;
+    // Smallest prefix whose block fits the limit.
+    let sub_bits = (bits..=32).find(|b| 1u64 << (32 - b) <= max_hosts)?;
+
+    let mask = if bits == 0 { 0 } else { u32::MAX << (32 - bits) };
+    let network = u32::from(base) & mask;
+    let step = 1u64 << (32 - sub_bits);
+    let blocks = 1u64 << (sub_bits - bits);
+    Some(
+        (0..blocks)
+            .map(|i| {
+                let addr = std::net::Ipv4Addr::from((network as u64 + i * step) as u32);
+                format!("{addr}/{sub_bits}")
+            })
+            .collect(),
+    )
+}
+
+fn token_host_count(token: &str) -> u64 {
+    if let Some((host, bits)) = token.split_once('/') {
+        let width = if host.parse::<std::net::Ipv6Addr>().is_ok() { 128 } else { 32 };
+        let free = width - bits.parse::<u32>().unwrap_or(width).min(width);
+        return 1u64.checked_shl(free).unwrap_or(u64::MAX);
+    }
+
+    let octet_range = token.contains('.')
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '*'));
+    if !octet_range {
+        return 1;
+    }
+    token
+        .split('.')
+        .map(|octet| match octet.split_once('-') {
+            _ if octet == "*" => 256,
+            Some((lo, hi)) => {
+                let lo: u64 = lo.parse().unwrap_or(0);
+                let hi: u64 = hi.parse().unwrap_or(255);
+                hi.saturating_sub(lo) + 1
+            }
+            None => 1,
+        })
+        .product()
+}
+
+/// The addresses (or the host name) one target entry covers, so entries
+/// written differently can be compared: `10.0.0.5` falls inside
+/// `10.0.0.0/24`, and `Example.COM` is `example.com`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// Inclusive address range; IPv4 is mapped into `::ffff:0:0/96`.
+    Addresses(u128, u128),
+    /// A host name, normalized to lowercase punycode.
+    Name(String),
+}
+
+impl Scope {
+    /// Scope of one entry of a target list. IPs, CIDRs and octet ranges
+    /// become address ranges (octet ranges by their bounding range), anything
+    /// else a host name.
+    pub fn of(token: &str) -> Scope {
+        let normalized = normalize_target(token)
+            .unwrap_or_else(|_| token.to_string())
+            .to_ascii_lowercase();
+        Self::addresses(&normalized).unwrap_or_else(|| {
+            let host = normalized.split_once('/').map_or(normalized.as_str(), |(h, _)| h);
+            Scope::Name(host.to_string())
+        })
+    }
+
+    fn addresses(token: &str) -> Option<Scope> {
+        let (host, bits) = match token.split_once('/') {
+            Some((h, b)) => (h, Some(b.parse::<u32>().ok()?)),
+            None => (token, None),
+        };
+        let (addr, width) = match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(v4)) => (u128::from(v4.to_ipv6_mapped()), 32),
+            Ok(IpAddr::V6(v6)) => (u128::from(v6), 128),
+            Err(_) if bits.is_none() => return octet_range(host),
+            Err(_) => return None,
+        };
+        let free = width - bits.unwrap_or(width).min(width);
+        let mask = 1u128.checked_shl(free).map_or(u128::MAX, |b| b - 1);
+        Some(Scope::Addresses(addr & !mask, addr | mask))
+    }
+
+    /// Whether everything `other` covers is covered by `self`. A name
+    /// covers itself and its subdomains; names and addresses never cover
+    /// each other, since resolving a name proves nothing about what it will
+    /// resolve to when scanned.
+    pub fn contains(&self, other: &Scope) -> bool {
+        match (self, other) {
+            (Scope::Addresses(lo, hi), Scope::Addresses(other_lo, other_hi)) => {
+                lo <= other_lo && other_hi <= hi
+            }
+            (Scope::Name(a), Scope::Name(b)) => {
+                b == a || b.strip_suffix(a.as_str()).is_some_and(|sub| sub.ends_with('.'))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn overlaps(&self, other: &Scope) -> bool {
+        match (self, other) {
+            (Scope::Addresses(lo, hi), Scope::Addresses(other_lo, other_hi)) => {
+                lo <= other_hi && other_lo <= hi
+            }
+            (Scope::Name(a), Scope::Name(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Bounding range of an nmap octet range like `10.0.1-3.*`.
+fn octet_range(host: &str) -> Option<Scope> {
+    let octets: Vec<&str> = host.split('.').collect();
+    if octets.len() != 4 {
+        return None;
+    }
+    let (mut lo, mut hi) = (0u32, 0u32);
+    for octet in octets {
+        let (a, b) = match octet.split_once('-') {
+            _ if octet == "*" => (0, 255),
+            Some((a, b)) => (a.parse::<u8>().ok()?, b.parse::<u8>().ok()?),
+            None => {
+                let n = octet.parse::<u8>().ok()?;
+                (n, n)
+            }
+        };
+        lo = lo << 8 | u32::from(a);
+        hi = hi << 8 | u32::from(b);
+    }
+    let mapped = |v: u32| u128::from(std::net::Ipv4Addr::from(v).to_ipv6_mapped());
+    Some(Scope::Addresses(mapped(lo), mapped(hi)))
+}
+
+/// Normalize one target: an IP, an nmap-style range (`10.0.0.1-50`), a CIDR
+/// (`10.0.0.0/24`, `example.com/28`) or a hostname, possibly non-ASCII.
+/// Prefix lengths above /32 (IPv4) or /128 (IPv6, host names) are refused.
+pub fn normalize_target(target: &str) -> Result<String> {
+    let (host, suffix) = match target.split_once('/') {
+        Some((h, bits)) => {
+            let max = match h.parse::<IpAddr>() {
+                Ok(IpAddr::V6(_)) => 128,
+                Ok(IpAddr::V4(_)) => 32,
+                Err(_) if h.contains(':') || h.chars().any(|c| c.is_alphabetic()) => 128,
+                Err(_) => 32,
+            };
+            if bits.parse::<u8>().ok().is_none_or(|b| b > max) {
+                anyhow::bail!("invalid target `{target}`: bad prefix length `/{bits}` (at most /{max})");
+            }
+            (h, &target[h.len()..])
+        }
+        None => (target, ""),
+    };
+
+    // IPs and numeric nmap ranges/wildcards pass through untouched.
+    let numeric = host
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '*'));
+    if host.parse::<IpAddr>().is_ok() || (numeric && host.contains('.')) {
+        return Ok(target.to_string());
+    }
+
+    let ascii = idna::domain_to_ascii(host)
+        .map_err(|_| anyhow::anyhow!("invalid target `{target}`: not a valid host name"))?;
+    if ascii.is_empty()
+        || !ascii
+            .split('.')
+            .all(|label| !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+    {
+        anyhow::bail!("invalid target `{target}`: not a valid host name");
+    }
+    Ok(format!("{ascii}{suffix}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_prefixes_longer_than_the_address() {
+        assert!(normalize_target("10.0.0.0/32").is_ok());
+        assert!(normalize_target("10.0.0.0/33").is_err());
+        assert!(normalize_target("10.0.0.0/255").is_err());
+        assert!(normalize_target("2001:db8::/128").is_ok());
+        assert!(normalize_target("2001:db8::/129").is_err());
+        assert!(normalize_target("example.com/28").is_ok());
+        assert!(normalize_target("10.0.0.0/").is_err());
+    }
+
+    #[test]
+    fn chunks_split_cidrs_and_pack_entries() {
+        let chunks = chunk_hosts("10.0.0.0/24", 100).unwrap();
+        assert_eq!(
+            chunks,
+            [["10.0.0.0/26"], ["10.0.0.64/26"], ["10.0.0.128/26"], ["10.0.0.192/26"]]
+        );
+
+        let chunks = chunk_hosts("10.0.0.1, 10.0.0.2,10.0.0.1 host.example 10.0.1.0/30", 4).unwrap();
+        assert_eq!(chunks, [vec!["10.0.0.1", "10.0.0.2", "host.example"], vec!["10.0.1.0/30"]]);
+
+        assert_eq!(chunk_hosts("10.0.0.0/24", 256).unwrap(), [["10.0.0.0/24"]]);
+        assert_eq!(chunk_hosts("10.0.0.5/32", 1).unwrap(), [["10.0.0.5/32"]]);
+    }
+
+    #[test]
+    fn chunking_refuses_what_it_cannot_split_or_would_blow_up() {
+        assert!(chunk_hosts("10.0.0.1", 0).is_err());
+        assert!(chunk_hosts("10.0.0-1.*", 256).is_err(), "octet ranges are not split");
+        assert!(chunk_hosts("2001:db8::/120", 16).is_err(), "IPv6 blocks are not split");
+        assert!(chunk_hosts("0.0.0.0/0", 4095).is_err(), "too many hosts");
+        assert!(chunk_hosts("10.0.0.0/8", 1).is_err(), "too many chunks");
+        assert_eq!(chunk_hosts("10.0.0.0/8", 4096).unwrap().len(), 4096);
+    }
+
+    #[test]
+    fn scopes_compare_by_covered_addresses() {
+        let cidr = Scope::of("10.0.0.0/24");
+        assert!(cidr.overlaps(&Scope::of("10.0.0.255")));
+        assert!(!cidr.overlaps(&Scope::of("10.0.1.0")));
+        assert!(cidr.overlaps(&Scope::of("10.0.0.7/30")));
+        assert!(cidr.overlaps(&Scope::of("10.0.0-3.*")));
+        assert!(!cidr.overlaps(&Scope::of("::ffff:10.0.1.0/120")));
+        assert!(Scope::of("2001:db8::/32").overlaps(&Scope::of("2001:db8::1")));
+        assert!(!Scope::of("10.0.0.1").overlaps(&Scope::of("::1")));
+    }
+
+    #[test]
+    fn scopes_contain_covered_addresses_and_subdomains() {
+        let cidr = Scope::of("10.0.0.0/24");
+        assert!(cidr.contains(&Scope::of("10.0.0.7/30")));
+        assert!(cidr.contains(&Scope::of("10.0.0.1-20")));
+        assert!(!cidr.contains(&Scope::of("10.0.0.0/23")));
+        assert!(!cidr.contains(&Scope::of("10.0.0-3.*")));
+
+        let domain = Scope::of("Example.com");
+        assert!(domain.contains(&Scope::of("example.com")));
+        assert!(domain.contains(&Scope::of("www.example.com")));
+        assert!(!domain.contains(&Scope::of("badexample.com")));
+        assert!(!domain.contains(&Scope::of("example.com.evil.net")));
+        assert!(!domain.contains(&Scope::of("10.0.0.1")));
+        assert!(!cidr.contains(&Scope::of("example.com")));
+    }
+
+    #[test]
+    fn entries_cover_every_target_field() {
+        let input = json!({ "hosts": "10.0.0.1, 10.0.0.2", "target": "example.com", "tag": "web" });
+        let names: Vec<String> = entries_of(&input).into_iter().map(|(t, _)| t).collect();
+        assert_eq!(names, ["example.com", "10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    fn host_arrays_become_one_list() {
+        let mut input = json!({ "hosts": ["10.0.0.1", "10.0.1.0/24"] });
+        normalize_input(&mut input, &mut Vec::new()).unwrap();
+        assert_eq!(input["hosts"], "10.0.0.1,10.0.1.0/24");
+        assert!(normalize_input(&mut json!({ "hosts": ["10.0.0.1", 7] }), &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn scopes_normalize_host_names() {
+        assert_eq!(Scope::of("Example.COM"), Scope::Name("example.com".into()));
+        assert_eq!(Scope::of("bücher.example/28"), Scope::Name("xn--bcher-kva.example".into()));
+        assert!(!Scope::of("example.com").overlaps(&Scope::of("10.0.0.1")));
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+
+use crate::policy::{self, Substitution};
+use crate::targets;
+
+/// Phrases in nmap output that indicate the target (or something in front of
+/// it) is dropping, resetting or rate-limiting our probes. A host that is
+/// simply down is not one of them.
+const DISTRESS_SIGNALS: &[&str] = &[
+    "Increasing send delay",
+    "retransmission cap hit",
+    "RTTVAR has grown",
+    "Connection reset by peer",
+];
+
+/// Slowest timing applied once a host keeps misbehaving.
+const FLOOR_TIMING: &str = "T1";
+
+/// Failure "strikes" per target. Each distressed scan adds one, each clean
+/// scan removes one.
+static STRIKES: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+
+/// If earlier scans of this call's target showed signs of IDS blocking, slow the timing template down (more strikes, gentler timing)
+/// and record the adjustment so it is reported alongside the result.
+/// `timing_schema` is the tool's schema for its `timing` field (`None` if it
+/// has none); the timing is never set below the slowest value it allows.
+pub fn adjust(input: &mut Value, timing_schema: Option<&Value>, substitutions: &mut Vec<Substitution>) {
+    let Some(schema) = timing_schema else {
+        return;
+    };
+    let Some(target) = targets::target_of(input).map(str::to_string) else {
+        return;
+    };
+    let strikes = strikes_for(&target);
+    if strikes == 0 {
+        return;
+    }
+
+    // 1 strike → T3, 2 → T2, 3+ → T1, or the slowest the tool accepts.
+    let cap_rank = 4usize.saturating_sub(strikes as usize).max(policy::timing_rank(FLOOR_TIMING));
+    let allowed: Vec<usize> = match schema.get("enum").and_then(Value::as_array) {
+        Some(values) => values.iter().filter_map(Value::as_str).map(policy::timing_rank).collect(),
+        None => (0..policy::TIMINGS.len()).collect(),
+    };
+    let Some(applied_rank) = allowed
+        .iter()
+        .copied()
+        .filter(|rank| *rank <= cap_rank)
+        .max()
+        .or_else(|| allowed.iter().copied().min())
+    else {
+        return;
+    };
+    let cap = policy::TIMINGS[applied_rank];
+    let requested = input.get("timing").and_then(|v| v.as_str()).map(str::to_string);
+    // Without a timing the tool's default applies; if it declares none,
+    // assume the fastest.
+    let requested_rank = requested
+        .as_deref()
+        .or_else(|| schema.get("default").and_then(Value::as_str))
+        .map(policy::timing_rank)
+        .unwrap_or(4);
+    if requested_rank <= applied_rank {
+        return;
+    }
+
+    substitutions.push(Substitution {
+        field: "timing".into(),
+        requested: json!(requested),
+        applied: json!(cap),
+        reason: format!(
+            "{strikes} earlier scan(s) of {target} triggered rate limiting/IDS blocking; using gentler timing"
+        ),
+    });
+    input["timing"] = json!(cap);
+}
+
+/// Learn from a finished scan: distress signals in the raw output add a
+/// strike for the target, clean runs remove one. Failed calls say nothing
+/// about the target (the backend may be down, the input invalid, the call
+/// timed out) and leave its strikes alone.
+pub fn observe(input: &Value, result: &anyhow::Result<Value>) {
+    let (Some(target), Ok(output)) = (targets::target_of(input), result) else {
+        return;
+    };
+
+    let distressed = output
+        .get("raw_output")
+        .and_then(|v| v.as_str())
+        .is_some_and(|raw| DISTRESS_SIGNALS.iter().any(|s| raw.contains(s)));
+
+    let mut guard = STRIKES.lock().unwrap();
+    let strikes = guard.get_or_insert_with(HashMap::new);
+    let key = target.trim().to_ascii_lowercase();
+    if distressed {
+        *strikes.entry(key).or_insert(0) += 1;
+    } else if let Some(n) = strikes.get_mut(&key) {
+        *n = n.saturating_sub(1);
+        if *n == 0 {
+            strikes.remove(&key);
+        }
+    }
+}
+
+fn strikes_for(target: &str) -> u32 {
+    STRIKES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|m| m.get(&target.trim().to_ascii_lowercase()).copied())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(target: &str, raw_output: &str) {
+        observe(&json!({ "target": target }), &Ok(json!({ "raw_output": raw_output })));
+    }
+
+    #[test]
+    fn distress_adds_strikes_and_clean_scans_remove_them() {
+        scan("strikes.example", "Increasing send delay for 10.0.0.1 from 0 to 5");
+        scan("STRIKES.example", "Warning: 10.0.0.1 giving up on port because retransmission cap hit (6).");
+        assert_eq!(strikes_for("strikes.example"), 2);
+
+        scan("strikes.example", "22/tcp open ssh");
+        assert_eq!(strikes_for("strikes.example"), 1);
+        scan("strikes.example", "22/tcp open ssh");
+        scan("strikes.example", "22/tcp open ssh");
+        assert_eq!(strikes_for("strikes.example"), 0);
+    }
+
+    fn registry() -> crate::ToolRegistry {
+        let mut registry = crate::ToolRegistry::new();
+        crate::tools::register_all_tools(&mut registry);
+        registry
+    }
+
+    #[test]
+    fn slows_down_within_each_tools_timing_choices() {
+        for _ in 0..3 {
+            scan("ids.example", "Increasing send delay for 10.0.0.1 from 0 to 5");
+        }
+        let registry = registry();
+        let cases = [
+            ("nmap.scan", "target", "T1"),
+            ("nmap_open_ports", "target", "T1"),
+            ("advanced_nmap_scan", "target", "T1"),
+            ("quick_scan", "target", "T3"),
+            ("network_discovery", "subnet", "T3"),
+            ("openvas_recommend_config", "target", "T3"),
+        ];
+        let mut with_timing: Vec<String> = registry
+            .list(None)
+            .iter()
+            .filter_map(|t| t["name"].as_str())
+            .filter(|name| registry.field_schema(name, "timing").is_some())
+            .map(str::to_string)
+            .collect();
+        let mut covered: Vec<String> = cases.iter().map(|(tool, ..)| tool.to_string()).collect();
+        with_timing.sort();
+        covered.sort();
+        assert_eq!(with_timing, covered, "every tool with a timing field is covered");
+
+        for (tool, target_field, expected) in cases {
+            let schema = registry.field_schema(tool, "timing");
+            assert!(schema.is_some(), "{tool} has a timing field");
+            let mut input = json!({ target_field: "ids.example", "timing": "T4" });
+            let mut substitutions = Vec::new();
+            adjust(&mut input, schema.as_ref(), &mut substitutions);
+            assert_eq!(input["timing"], expected, "{tool}");
+            assert_eq!(substitutions.len(), 1, "{tool}");
+            assert!(registry.validate(tool, &input).is_ok(), "{tool} accepts the adjusted timing");
+        }
+    }
+
+    #[test]
+    fn leaves_slow_enough_or_unstruck_calls_alone() {
+        scan("slow.example", "RTTVAR has grown to over 2.3 seconds, decreasing to 2.0");
+        let schema = json!({ "enum": ["T3", "T4"], "default": "T4" });
+        let mut substitutions = Vec::new();
+
+        let mut input = json!({ "target": "slow.example", "timing": "T3" });
+        adjust(&mut input, Some(&schema), &mut substitutions);
+        assert_eq!(input["timing"], "T3");
+
+        let mut input = json!({ "target": "slow.example" });
+        adjust(&mut input, Some(&schema), &mut substitutions);
+        assert_eq!(input["timing"], "T3", "the T4 default is slowed down");
+
+        let mut input = json!({ "target": "calm.example", "timing": "T4" });
+        adjust(&mut input, Some(&schema), &mut substitutions);
+        assert_eq!(input["timing"], "T4");
+
+        let mut input = json!({ "target": "slow.example" });
+        adjust(&mut input, None, &mut substitutions);
+        assert!(input.get("timing").is_none());
+        assert_eq!(substitutions.len(), 1);
+    }
+
+    #[test]
+    fn failures_and_down_hosts_are_not_strikes() {
+        let input = json!({ "target": "flaky.example" });
+        observe(&input, &Err(anyhow::anyhow!("scan backend unavailable")));
+        scan("flaky.example", "Note: Host seems down. If it is really up, but blocking our ping probes, try -Pn");
+        assert_eq!(strikes_for("flaky.example"), 0);
+    }
+}
@@ -0,0 +1,195 @@
+use serde_json::{json, Value};
+
+use crate::api::errors::{BackendError, BackendErrorKind};
+use crate::middleware::{Rejection, ToolTimeout};
+use crate::validation::FieldError;
+
+/// How a `tools/call` failed, as the client sees it. Each variant answers
+/// with its own JSON-RPC code, and `error.data.kind` names the variant so
+/// agents can react without parsing messages (retry later, fix the input,
+/// pick another target, ...).
+#[derive(Debug)]
+pub enum ToolError {
+    /// The input was rejected before the tool did anything.
+    InvalidInput {
+        message: String,
+        /// Schema violations, when the input failed the tool's `inputSchema`.
+        errors: Vec<FieldError>,
+    },
+    /// The Go backend, docker or gvmd could not be reached or did not
+    /// answer in time; the same call may well work later.
+    BackendUnavailable(BackendError),
+    /// The backend answered, with a failure: a missing object, a rejected
+    /// request, bad credentials, a failed scan.
+    Backend(BackendError),
+    /// The tool ran past its timeout.
+    Timeout(ToolTimeout),
+    /// A target is outside the engagement scope or on the denylist.
+    TargetOutOfScope { target: String, reason: &'static str },
+    /// Stopped by middleware: not authorized, not approved, rate limited.
+    Refused(Rejection),
+    /// Anything else went wrong in the tool.
+    Internal(String),
+}
+
+impl ToolError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        ToolError::InvalidInput {
+            message: message.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Sort a failure from the tool or its middleware into a variant.
+    pub fn classify(err: anyhow::Error) -> Self {
+        if let Some(backend) = err.downcast_ref::<BackendError>() {
+            return match backend.kind {
+                BackendErrorKind::Unavailable | BackendErrorKind::Timeout => {
+                    ToolError::BackendUnavailable(backend.clone())
+                }
+                _ => ToolError::Backend(backend.clone()),
+            };
+        }
+        let err = match err.downcast::<ToolError>() {
+            Ok(tool_error) => return tool_error,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<Rejection>() {
+            Ok(rejection) => return ToolError::Refused(rejection),
+            Err(err) => err,
+        };
+        match err.downcast::<ToolTimeout>() {
+            Ok(timeout) => ToolError::Timeout(timeout),
+            Err(err) => ToolError::Internal(format!("{err:#}")),
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        match self {
+            ToolError::InvalidInput { .. } => -32602,
+            ToolError::BackendUnavailable(_) => -32005,
+            ToolError::Backend(_) => -32000,
+            ToolError::Timeout(_) => -32001,
+            ToolError::TargetOutOfScope { .. } => -32006,
+            ToolError::Refused(rejection) => rejection.code,
+            ToolError::Internal(_) => -32603,
+        }
+    }
+
+    /// `error.data.kind`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ToolError::InvalidInput { .. } => "invalid_input",
+            ToolError::BackendUnavailable(_) => "backend_unavailable",
+            ToolError::Backend(_) => "backend_error",
+            ToolError::Timeout(_) => "timeout",
+            ToolError::TargetOutOfScope { .. } => "target_out_of_scope",
+            ToolError::Refused(rejection) if rejection.code == -32004 => "rate_limited",
+            ToolError::Refused(_) => "refused",
+            ToolError::Internal(_) => "internal",
+        }
+    }
+
+    /// Machine-readable details for JSON-RPC `error.data`.
+    pub fn data(&self) -> Value {
+        let mut data = match self {
+            ToolError::InvalidInput { errors, .. } if !errors.is_empty() => json!({ "errors": errors }),
+            ToolError::BackendUnavailable(backend) | ToolError::Backend(backend) => {
+                json!({ "backend": backend.to_json() })
+            }
+            ToolError::Timeout(timeout) => json!({ "partialOutput": timeout.partial_output }),
+            ToolError::TargetOutOfScope { target, reason } => json!({ "target": target, "reason": reason }),
+            ToolError::Refused(rejection) => rejection.data.clone().unwrap_or_else(|| json!({})),
+            _ => json!({}),
+        };
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert("kind".into(), json!(self.kind()));
+        }
+        data
+    }
+}
+
+impl std::fmt::Display for ToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToolError::InvalidInput { message, .. } => write!(f, "Invalid params: {message}"),
+            ToolError::BackendUnavailable(backend) | ToolError::Backend(backend) => {
+                write!(f, "Tool error: {backend}")
+            }
+            ToolError::Timeout(timeout) => write!(f, "Tool error: {timeout}"),
+            ToolError::TargetOutOfScope { target, reason } => {
+                let why = match *reason {
+                    "denied" => "is on the target denylist",
+                    _ => "is outside the engagement scope",
+                };
+                write!(f, "Not authorized: {target} {why}")
+            }
+            ToolError::Refused(rejection) => f.write_str(&rejection.message),
+            ToolError::Internal(message) => write!(f, "Tool error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn failures_map_to_distinct_codes_and_kinds() {
+        let unavailable = ToolError::classify(BackendError::from_response(502, "bad gateway").into());
+        assert_eq!((unavailable.code(), unavailable.kind()), (-32005, "backend_unavailable"));
+        assert_eq!(unavailable.data()["backend"]["status"], 502);
+
+        let missing = ToolError::classify(BackendError::from_response(404, "Failed to find task").into());
+        assert_eq!((missing.code(), missing.kind()), (-32000, "backend_error"));
+
+        let timeout = ToolError::classify(
+            ToolTimeout {
+                tool: "quick_scan".into(),
+                limit: Duration::from_secs(5),
+                partial_output: Some(json!({ "ports": [22] })),
+            }
+            .into(),
+        );
+        assert_eq!(timeout.code(), -32001);
+        assert_eq!(timeout.data(), json!({ "kind": "timeout", "partialOutput": { "ports": [22] } }));
+
+        let internal = ToolError::classify(anyhow::anyhow!("raw socket: permission denied"));
+        assert_eq!((internal.code(), internal.kind()), (-32603, "internal"));
+        assert_eq!(internal.to_string(), "Tool error: raw socket: permission denied");
+    }
+
+    #[test]
+    fn typed_errors_survive_the_round_trip_through_anyhow() {
+        let scope = ToolError::classify(
+            ToolError::TargetOutOfScope {
+                target: "10.9.0.1".into(),
+                reason: "out_of_scope",
+            }
+            .into(),
+        );
+        assert_eq!(scope.code(), -32006);
+        assert_eq!(
+            scope.data(),
+            json!({ "kind": "target_out_of_scope", "target": "10.9.0.1", "reason": "out_of_scope" })
+        );
+
+        let limited = ToolError::classify(
+            Rejection {
+                code: -32004,
+                message: "rate limited".into(),
+                data: Some(json!({ "retryAfterSecs": 3 })),
+            }
+            .into(),
+        );
+        assert_eq!(limited.code(), -32004);
+        assert_eq!(limited.data(), json!({ "kind": "rate_limited", "retryAfterSecs": 3 }));
+
+        let invalid = ToolError::classify(ToolError::invalid_input("`domain` must be a string").into());
+        assert_eq!(invalid.code(), -32602);
+        assert_eq!(invalid.data(), json!({ "kind": "invalid_input" }));
+    }
+}
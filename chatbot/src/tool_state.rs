@@ -0,0 +1,99 @@
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+
+use crate::ToolCategory;
+
+/// The admin tool itself, which must stay callable to undo what it did.
+pub const ADMIN_TOOL: &str = "admin_set_tool_state";
+
+/// One callable tool name (canonical or alias).
+#[derive(Debug, Clone)]
+pub struct CatalogEntry {
+    pub name: String,
+    /// Canonical name of the tool `name` calls.
+    pub canonical: String,
+    pub category: ToolCategory,
+    pub description: String,
+}
+
+/// Every callable name, captured once the registry is built.
+static CATALOG: OnceLock<Vec<CatalogEntry>> = OnceLock::new();
+
+/// Canonical names of tools switched off at runtime.
+static DISABLED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Canonical names of tools switched off in the `--config` file. Kept apart
+/// from `DISABLED` so a reload does not undo an administrator's changes.
+static CONFIG_DISABLED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Record the registry's catalog so `set_enabled` can resolve aliases and
+/// reject unknown tools, and tools can list their peers. Call once at
+/// startup.
+pub fn install(catalog: Vec<CatalogEntry>) {
+    let _ = CATALOG.set(catalog);
+}
+
+/// Catalog entries of the tools that are currently enabled.
+pub fn catalog() -> Vec<CatalogEntry> {
+    CATALOG
+        .get()
+        .into_iter()
+        .flatten()
+        .filter(|e| enabled(&e.canonical))
+        .cloned()
+        .collect()
+}
+
+/// Whether the canonical tool `name` may be listed and called.
+pub fn enabled(name: &str) -> bool {
+    !DISABLED.lock().unwrap().contains(name) && !CONFIG_DISABLED.lock().unwrap().contains(name)
+}
+
+/// Canonical names of the tools currently switched off.
+pub fn disabled() -> Vec<String> {
+    let mut disabled = DISABLED.lock().unwrap().clone();
+    disabled.extend(CONFIG_DISABLED.lock().unwrap().iter().cloned());
+    disabled.into_iter().collect()
+}
+
+/// The canonical name of the tool called `name`, which may be an alias.
+/// `None` for names the registry does not know.
+pub fn resolve(name: &str) -> Option<String> {
+    CATALOG
+        .get()
+        .and_then(|catalog| catalog.iter().find(|e| e.name == name))
+        .map(|e| e.canonical.clone())
+}
+
+/// Like `resolve`, but an unknown tool or the admin tool is an error.
+fn canonical(name: &str) -> Result<String> {
+    let canonical = resolve(name).ok_or_else(|| anyhow::anyhow!("unknown tool `{name}`"))?;
+    if canonical == ADMIN_TOOL {
+        anyhow::bail!("{ADMIN_TOOL} cannot be switched off");
+    }
+    Ok(canonical)
+}
+
+/// Switch the tool called `name` (or an alias of it) on or off. Returns its
+/// canonical name and whether its state actually changed.
+pub fn set_enabled(name: &str, enabled: bool) -> Result<(String, bool)> {
+    let canonical = canonical(name)?;
+    let mut disabled = DISABLED.lock().unwrap();
+    let changed = if enabled {
+        disabled.remove(&canonical)
+    } else {
+        disabled.insert(canonical.clone())
+    };
+    Ok((canonical, changed))
+}
+
+/// Replace the tools the `--config` file switches off. Returns whether any
+/// tool changed between enabled and disabled.
+pub fn set_config_disabled(names: &[String]) -> Result<bool> {
+    let names = names.iter().map(|n| canonical(n)).collect::<Result<BTreeSet<_>>>()?;
+    let before = disabled();
+    *CONFIG_DISABLED.lock().unwrap() = names;
+    Ok(disabled() != before)
+}
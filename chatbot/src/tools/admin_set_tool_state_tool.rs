@@ -0,0 +1,65 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::auth::tokens_match;
+use crate::{audit, config, session, tool_state};
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that switches other tools off (or back on) for every session, e.g.
+/// to take `stealth_scan` away during a demo. Guarded by `--admin-token`.
+pub struct AdminSetToolStateTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AdminSetToolStateArgs {
+    /// Tool to switch on or off, by name or alias, e.g. 'stealth_scan'.
+    tool: String,
+    /// false removes the tool from tools/list and refuses calls to it; true restores it.
+    enabled: bool,
+    /// The admin token the server was started with (--admin-token).
+    token: String,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for AdminSetToolStateTool {
+    type Args = AdminSetToolStateArgs;
+
+    const NAME: &'static str = tool_state::ADMIN_TOOL;
+    const DESCRIPTION: &'static str = "Disables or re-enables another tool for all sessions at runtime, e.g. turning off stealth_scan mid-session. Requires the server's admin token. Clients are notified through notifications/tools/list_changed.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    async fn run(&self, args: AdminSetToolStateArgs) -> Result<Value> {
+        let Some(expected) = config::current().admin_token.clone() else {
+            anyhow::bail!("runtime tool administration is off; start the server with --admin-token");
+        };
+        if !tokens_match(&args.token, &expected) {
+            anyhow::bail!("invalid admin token");
+        }
+
+        let (tool, changed) = tool_state::set_enabled(&args.tool, args.enabled)?;
+        if changed {
+            if audit::enabled() {
+                audit::record(
+                    "tool_state",
+                    &config::current().workspace,
+                    json!({ "tool": tool, "enabled": args.enabled }),
+                )?;
+            }
+            session::broadcast("notifications/tools/list_changed");
+        }
+
+        Ok(json!({
+            "tool": tool,
+            "enabled": args.enabled,
+            "changed": changed,
+            "disabled_tools": tool_state::disabled(),
+        }))
+    }
+}
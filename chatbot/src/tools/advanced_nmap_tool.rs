@@ -1,170 +1,50 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::nmap_scan::{
+    ComprehensiveScanArgs, NetworkDiscoveryArgs, QuickScanArgs, ScanRequest, StealthScanArgs,
+};
 use crate::services::advanced_nmap_scan;
-use crate::Tool;
+use crate::{dry_run, scan_jobs, ToolAnnotations, ToolCategory};
 
 /// Advanced Nmap tool with comprehensive options
 pub struct AdvancedNmapTool;
 
 #[async_trait::async_trait]
-impl Tool for AdvancedNmapTool {
-    fn name(&self) -> &'static str {
-        "advanced_nmap_scan"
+impl TypedTool for AdvancedNmapTool {
+    type Args = ScanRequest;
+
+    const NAME: &'static str = "advanced_nmap_scan";
+    const DESCRIPTION: &'static str = "Comprehensive Nmap scan with multiple options: timing, scan types, service detection, OS detection, scripts, and output formats. Long scans can run in the background.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
     }
 
-    fn description(&self) -> &'static str {
-        "Comprehensive Nmap scan with multiple options: timing, scan types, service detection, OS detection, scripts, and output formats."
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "target": {
-                    "type": "string",
-                    "description": "Target hostname or IP address to scan."
-                },
-                "timing": {
-                    "type": "string",
-                    "description": "Nmap timing template: T0 (Paranoid), T1 (Sneaky), T2 (Polite), T3 (Normal), T4 (Aggressive), T5 (Insane). Default: T2",
-                    "enum": ["T0", "T1", "T2", "T3", "T4", "T5"]
-                },
-                "scan_type": {
-                    "type": "string",
-                    "description": "Type of scan to perform",
-                    "enum": ["ping", "tcp_syn", "tcp_connect", "udp", "tcp_ack", "tcp_fin", "tcp_null", "tcp_xmas"]
-                },
-                "ports": {
-                    "type": "string",
-                    "description": "Port specification: '80,443', '1-1000', 'U:53,T:80-443', or 'all' for all ports"
-                },
-                "service_detection": {
-                    "type": "boolean",
-                    "description": "Enable service/version detection (-sV)"
-                },
-                "os_detection": {
-                    "type": "boolean",
-                    "description": "Enable OS detection (-O)"
-                },
-                "scripts": {
-                    "type": "string",
-                    "description": "Script names or categories: 'vuln', 'default', 'auth,discovery', or specific script names"
-                },
-                "output_format": {
-                    "type": "string",
-                    "description": "Output format for results",
-                    "enum": ["normal", "xml", "json", "greppable", "all"]
-                },
-                "aggressive": {
-                    "type": "boolean",
-                    "description": "Enable aggressive scan options (-A): service detection, OS detection, scripts, and traceroute"
-                },
-                "traceroute": {
-                    "type": "boolean", 
-                    "description": "Enable traceroute (--traceroute)"
-                },
-                "flag_o": {
-                    "type": "boolean",
-                    "description": "Enable OS detection (-O)"
-                },
-                "flag_sc": {
-                    "type": "boolean",
-                    "description": "Enable default scripts (-sC)"
-                },
-                "flag_sv": {
-                    "type": "boolean",
-                    "description": "Enable service detection (-sV)"
-                },
-                "flag_traceroute": {
-                    "type": "boolean",
-                    "description": "Enable traceroute (--traceroute)"
-                },
-                "flag_a": {
-                    "type": "boolean",
-                    "description": "Enable aggressive scan (-A)"
-                },
-                "stealth_options": {
-                    "type": "object",
-                    "description": "Stealth and evasion options",
-                    "properties": {
-                        "decoys": {
-                            "type": "array",
-                            "items": {"type": "string"},
-                            "description": "Decoy IPs (-D RND:10,ME,8.8.8.8)"
-                        },
-                        "source_port": {
-                            "type": "integer",
-                            "description": "Source port for packets (--source-port 53)"
-                        },
-                        "interface": {
-                            "type": "string",
-                            "description": "Network interface to use (-e eth0)"
-                        },
-                        "ttl": {
-                            "type": "integer",
-                            "description": "Time to live for packets (--ttl 64)"
-                        },
-                        "randomize_hosts": {
-                            "type": "boolean",
-                            "description": "Randomize target host order (--randomize-hosts)"
-                        },
-                        "spoof_ip": {
-                            "type": "string",
-                            "description": "Spoof source IP address (-S 192.168.1.1)"
-                        },
-                        "spoof_mac": {
-                            "type": "string",
-                            "description": "Spoof MAC address (--spoof-mac 0)"
-                        }
-                    }
-                }
-            },
-            "required": ["target"],
-            "additionalProperties": false
-        })
+    fn max_concurrency(&self) -> Option<usize> {
+        Some(2)
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let target = input
-            .get("target")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target`"))?;
-
-        let timing = input.get("timing").and_then(|v| v.as_str());
-        let scan_type = input.get("scan_type").and_then(|v| v.as_str());
-        let ports = input.get("ports").and_then(|v| v.as_str());
-        let service_detection = input.get("service_detection").and_then(|v| v.as_bool()).unwrap_or(false);
-        let os_detection = input.get("os_detection").and_then(|v| v.as_bool()).unwrap_or(false);
-        let scripts = input.get("scripts").and_then(|v| v.as_str());
-        let output_format = input.get("output_format").and_then(|v| v.as_str());
-        let aggressive = input.get("aggressive").and_then(|v| v.as_bool()).unwrap_or(false);
-        let traceroute = input.get("traceroute").and_then(|v| v.as_bool()).unwrap_or(false);
-        let flag_o = input.get("flag_o").and_then(|v| v.as_bool()).unwrap_or(false);
-        let flag_sc = input.get("flag_sc").and_then(|v| v.as_bool()).unwrap_or(false);
-        let flag_sv = input.get("flag_sv").and_then(|v| v.as_bool()).unwrap_or(false);
-        let flag_traceroute = input.get("flag_traceroute").and_then(|v| v.as_bool()).unwrap_or(false);
-        let flag_a = input.get("flag_a").and_then(|v| v.as_bool()).unwrap_or(false);
-        let stealth_options = input.get("stealth_options");
-
-        advanced_nmap_scan::advanced_nmap_scan(
-            target,
-            timing,
-            scan_type,
-            ports,
-            service_detection,
-            os_detection,
-            scripts,
-            output_format,
-            aggressive,
-            traceroute,
-            flag_o,
-            flag_sc,
-            flag_sv,
-            flag_traceroute,
-            flag_a,
-            stealth_options,
-        ).await
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ScanRequest) -> Result<Value> {
+        // A dry run plans the scan request whether or not it would run in the background.
+        if args.background && !dry_run::is_active() {
+            let limits = (TypedTool::default_timeout(self), TypedTool::max_concurrency(self));
+            return Ok(serde_json::to_value(scan_jobs::start(Self::NAME, args, limits.0, limits.1)?)?);
+        }
+        let result = advanced_nmap_scan::advanced_nmap_scan(&args).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
 
@@ -172,51 +52,28 @@ impl Tool for AdvancedNmapTool {
 pub struct QuickScanTool;
 
 #[async_trait::async_trait]
-impl Tool for QuickScanTool {
-    fn name(&self) -> &'static str {
-        "quick_scan"
-    }
+impl TypedTool for QuickScanTool {
+    type Args = QuickScanArgs;
 
-    fn description(&self) -> &'static str {
-        "Fast network reconnaissance with common scan patterns (ping sweep, port scan, service detection)."
-    }
+    const NAME: &'static str = "quick_scan";
+    const DESCRIPTION: &'static str = "Fast network reconnaissance with common scan patterns (ping sweep, port scan, service detection).";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "target": {
-                    "type": "string",
-                    "description": "Target hostname, IP, or CIDR range."
-                },
-                "scan_type": {
-                    "type": "string",
-                    "description": "Quick scan type",
-                    "enum": ["ping_sweep", "common_ports", "service_detection", "vuln_scan"],
-                    "default": "common_ports"
-                },
-                "timing": {
-                    "type": "string",
-                    "description": "Speed: T3 (Normal) or T4 (Aggressive)",
-                    "enum": ["T3", "T4"],
-                    "default": "T4"
-                }
-            },
-            "required": ["target"],
-            "additionalProperties": false
-        })
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let target = input
-            .get("target")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target`"))?;
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
 
-        let scan_type = input.get("scan_type").and_then(|v| v.as_str()).unwrap_or("common_ports");
-        let timing = input.get("timing").and_then(|v| v.as_str()).unwrap_or("T4");
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        advanced_nmap_scan::quick_scan(target, scan_type, timing).await
+    async fn run(&self, args: QuickScanArgs) -> Result<Value> {
+        let result = advanced_nmap_scan::quick_scan(&args.target, args.scan_type, args.timing).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
 
@@ -224,63 +81,39 @@ impl Tool for QuickScanTool {
 pub struct StealthScanTool;
 
 #[async_trait::async_trait]
-impl Tool for StealthScanTool {
-    fn name(&self) -> &'static str {
-        "stealth_scan"
-    }
+impl TypedTool for StealthScanTool {
+    type Args = StealthScanArgs;
+
+    const NAME: &'static str = "stealth_scan";
+    const DESCRIPTION: &'static str = "Stealthy scans with evasion techniques (slow timing, decoys, fragmentation).";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
 
-    fn description(&self) -> &'static str {
-        "Stealthy scans with evasion techniques (slow timing, decoys, fragmentation)."
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "target": {
-                    "type": "string",
-                    "description": "Target hostname or IP address."
-                },
-                "stealth_level": {
-                    "type": "string",
-                    "description": "Stealth level",
-                    "enum": ["low", "medium", "high", "maximum"],
-                    "default": "medium"
-                },
-                "scan_type": {
-                    "type": "string",
-                    "description": "Stealth scan type",
-                    "enum": ["tcp_fin", "tcp_null", "tcp_xmas", "tcp_ack", "tcp_syn"],
-                    "default": "tcp_syn"
-                },
-                "use_decoys": {
-                    "type": "boolean",
-                    "description": "Use decoy hosts",
-                    "default": true
-                },
-                "fragment_packets": {
-                    "type": "boolean",
-                    "description": "Fragment packets to evade IDS",
-                    "default": false
-                }
-            },
-            "required": ["target"],
-            "additionalProperties": false
-        })
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let target = input
-            .get("target")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target`"))?;
+    fn max_concurrency(&self) -> Option<usize> {
+        Some(2)
+    }
 
-        let stealth_level = input.get("stealth_level").and_then(|v| v.as_str()).unwrap_or("medium");
-        let scan_type = input.get("scan_type").and_then(|v| v.as_str()).unwrap_or("tcp_syn");
-        let use_decoys = input.get("use_decoys").and_then(|v| v.as_bool()).unwrap_or(true);
-        let fragment_packets = input.get("fragment_packets").and_then(|v| v.as_bool()).unwrap_or(false);
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        advanced_nmap_scan::stealth_scan(target, stealth_level, scan_type, use_decoys, fragment_packets).await
+    async fn run(&self, args: StealthScanArgs) -> Result<Value> {
+        let result = advanced_nmap_scan::stealth_scan(
+            &args.target,
+            args.stealth_level,
+            args.scan_type,
+            args.use_decoys,
+            args.fragment_packets,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
 
@@ -288,43 +121,37 @@ impl Tool for StealthScanTool {
 pub struct ComprehensiveScanTool;
 
 #[async_trait::async_trait]
-impl Tool for ComprehensiveScanTool {
-    fn name(&self) -> &'static str {
-        "comprehensive_scan"
-    }
+impl TypedTool for ComprehensiveScanTool {
+    type Args = ComprehensiveScanArgs;
 
-    fn description(&self) -> &'static str {
-        "Full comprehensive scan: all 65535 ports with service detection, OS detection, and scripts. Use for thorough security assessment."
+    const NAME: &'static str = "comprehensive_scan";
+    const DESCRIPTION: &'static str = "Full comprehensive scan: all 65535 ports with service detection, OS detection, and scripts. Use for thorough security assessment; run it in the background to get a scan_id at once.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "target": {
-                    "type": "string",
-                    "description": "Target hostname or IP address to scan."
-                },
-                "include_vuln": {
-                    "type": "boolean",
-                    "description": "Include vulnerability scripts (vuln category). Default: false",
-                    "default": false
-                }
-            },
-            "required": ["target"],
-            "additionalProperties": false
-        })
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60 * 60))
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let target = input
-            .get("target")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target`"))?;
+    fn max_concurrency(&self) -> Option<usize> {
+        Some(1)
+    }
 
-        let include_vuln = input.get("include_vuln").and_then(|v| v.as_bool()).unwrap_or(false);
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        advanced_nmap_scan::comprehensive_scan(target, include_vuln).await
+    async fn run(&self, args: ComprehensiveScanArgs) -> Result<Value> {
+        if args.background && !dry_run::is_active() {
+            let request = advanced_nmap_scan::comprehensive_request(&args.target, args.include_vuln);
+            let limits = (TypedTool::default_timeout(self), TypedTool::max_concurrency(self));
+            return Ok(serde_json::to_value(scan_jobs::start(Self::NAME, request, limits.0, limits.1)?)?);
+        }
+        let result = advanced_nmap_scan::comprehensive_scan(&args.target, args.include_vuln).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
 
@@ -332,43 +159,27 @@ impl Tool for ComprehensiveScanTool {
 pub struct NetworkDiscoveryTool;
 
 #[async_trait::async_trait]
-impl Tool for NetworkDiscoveryTool {
-    fn name(&self) -> &'static str {
-        "network_discovery"
-    }
+impl TypedTool for NetworkDiscoveryTool {
+    type Args = NetworkDiscoveryArgs;
 
-    fn description(&self) -> &'static str {
-        "Network discovery scan for subnet enumeration. Finds live hosts and checks common ports (22, 80, 443, 3389, 8080)."
-    }
+    const NAME: &'static str = "network_discovery";
+    const DESCRIPTION: &'static str = "Network discovery scan for subnet enumeration. Finds live hosts and checks common ports (22, 80, 443, 3389, 8080).";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "subnet": {
-                    "type": "string",
-                    "description": "Target subnet in CIDR notation (e.g., '192.168.1.0/24') or IP range."
-                },
-                "timing": {
-                    "type": "string",
-                    "description": "Timing template: T3 (Normal) or T4 (Aggressive). Default: T4",
-                    "enum": ["T3", "T4"],
-                    "default": "T4"
-                }
-            },
-            "required": ["subnet"],
-            "additionalProperties": false
-        })
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::ACTIVE_SCAN
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let subnet = input
-            .get("subnet")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `subnet`"))?;
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(30 * 60))
+    }
 
-        let timing = input.get("timing").and_then(|v| v.as_str()).unwrap_or("T4");
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        advanced_nmap_scan::network_discovery(subnet, timing).await
+    async fn run(&self, args: NetworkDiscoveryArgs) -> Result<Value> {
+        let result = advanced_nmap_scan::network_discovery(&args.subnet, args.timing).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
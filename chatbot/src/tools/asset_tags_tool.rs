@@ -0,0 +1,42 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::tagging;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists assets tagged by the rules engine after each scan, so
+/// follow-up work can be aimed at e.g. every `database-server`.
+pub struct AssetTagsTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AssetTagsArgs {
+    /// Only list assets carrying this tag.
+    tag: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for AssetTagsTool {
+    type Args = AssetTagsArgs;
+
+    const NAME: &'static str = "asset_tags";
+    const DESCRIPTION: &'static str = "Lists scanned targets and the tags the rules engine assigned from their scan results (e.g. database-server, web-server). Filter by tag to find targets for follow-up scans.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: AssetTagsArgs) -> Result<Value> {
+        let assets = tagging::assets(args.tag.as_deref());
+
+        Ok(json!({
+            "count": assets.len(),
+            "targets": assets.iter().map(|a| a.target.clone()).collect::<Vec<_>>(),
+            "assets": assets,
+        }))
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::{approval, audit, config};
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that records who authorized testing for the current workspace. With
+/// `--require-attestation`, active scanning tools refuse to run until this
+/// has been called. The model filling in the arguments is not an
+/// attestation: a person confirms it through elicitation.
+pub struct AttestScopeTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AttestScopeArgs {
+    /// Engagement or contract reference the testing is carried out under, e.g. 'PT-2026-014'.
+    engagement_reference: String,
+    /// Person or organisation that owns the targets and authorized testing.
+    authorized_by: String,
+    /// Targets or ranges the authorization covers, as written in the engagement.
+    scope: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for AttestScopeTool {
+    type Args = AttestScopeArgs;
+
+    const NAME: &'static str = "attest_scope";
+    const DESCRIPTION: &'static str = "Records an attestation that testing of the current workspace is authorized (engagement reference and authorizing party) in the tamper-evident audit log, after the user confirms it. Required before active scans when the server runs with --require-attestation.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        // Every call appends another record to the audit log.
+        ToolAnnotations {
+            idempotent_hint: false,
+            ..ToolAnnotations::BACKEND_WRITE
+        }
+    }
+
+    async fn run(&self, args: AttestScopeArgs) -> Result<Value> {
+        for (field, value) in [("engagement_reference", &args.engagement_reference), ("authorized_by", &args.authorized_by)] {
+            if value.trim().is_empty() {
+                anyhow::bail!("{field} must not be empty");
+            }
+        }
+
+        let workspace = config::current().workspace.clone();
+        let engagement_reference = args.engagement_reference.trim();
+        let authorized_by = args.authorized_by.trim();
+        approval::confirm_attestation(&workspace, engagement_reference, authorized_by).await?;
+
+        let record = audit::record(
+            "attestation",
+            &workspace,
+            json!({
+                "engagement_reference": engagement_reference,
+                "authorized_by": authorized_by,
+                "scope": args.scope,
+            }),
+        )?;
+
+        Ok(json!({
+            "status": "attested",
+            "workspace": workspace,
+            "record": record,
+        }))
+    }
+}
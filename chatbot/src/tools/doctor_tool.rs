@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::services::doctor;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that checks external scanner dependencies against a known-good
+/// version matrix and can install missing ones on confirmation.
+pub struct DoctorTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DoctorArgs {
+    /// Plan installation of missing dependencies via the system package manager. Default: false
+    #[serde(default)]
+    install_missing: bool,
+    /// Run the install commands planned by install_missing, after the user approves each one when asked. Without it the commands are only returned for review. Default: false
+    #[serde(default)]
+    confirm: bool,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for DoctorTool {
+    type Args = DoctorArgs;
+
+    const NAME: &'static str = "doctor";
+    const DESCRIPTION: &'static str = "Reports installed versions of external scanner dependencies (nmap, docker, go) against a known-good matrix, warns about incompatible versions, and optionally installs missing tools via the system package manager.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    /// Installs packages on the server's host, so clients should treat it
+    /// as destructive even though it never touches scan targets.
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations {
+            destructive_hint: true,
+            ..ToolAnnotations::BACKEND_WRITE
+        }
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(15 * 60))
+    }
+
+    async fn run(&self, args: DoctorArgs) -> Result<Value> {
+        doctor::doctor(args.install_missing, args.confirm).await
+    }
+}
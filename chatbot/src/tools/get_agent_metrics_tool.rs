@@ -0,0 +1,41 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::metrics;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that reports call counts, error rates and latency per tool, so
+/// operators can see which scans dominate runtime.
+pub struct GetAgentMetricsTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAgentMetricsArgs {
+    /// Only report this tool.
+    tool: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for GetAgentMetricsTool {
+    type Args = GetAgentMetricsArgs;
+
+    const NAME: &'static str = "get_agent_metrics";
+    const DESCRIPTION: &'static str =
+        "Reports, per tool since the server started: calls, errors, error rate, total/mean/max latency and a latency histogram. Tools that took the most time in total come first.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: GetAgentMetricsArgs) -> Result<Value> {
+        let tools: Vec<_> = metrics::snapshot()
+            .into_iter()
+            .filter(|m| args.tool.as_ref().is_none_or(|t| &m.tool == t))
+            .collect();
+        Ok(json!({ "tools": tools }))
+    }
+}
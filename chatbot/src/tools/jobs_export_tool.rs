@@ -0,0 +1,89 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::jobs::{self, Job, JobState};
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that exports the job queue and history for capacity planning and
+/// engagement retrospectives.
+pub struct JobsExportTool;
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JobsExportArgs {
+    /// Export format. Default: json
+    #[serde(default)]
+    format: ExportFormat,
+    /// Only export jobs in this state.
+    state: Option<JobState>,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for JobsExportTool {
+    type Args = JobsExportArgs;
+
+    const NAME: &'static str = "jobs_export";
+    const DESCRIPTION: &'static str =
+        "Exports the full job queue (calls waiting for approval or a concurrency slot, and running ones) and history (state, duration, target, operator) as CSV or JSON.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: JobsExportArgs) -> Result<Value> {
+        let jobs: Vec<Job> = jobs::snapshot()
+            .into_iter()
+            .filter(|j| args.state.is_none_or(|s| j.state == s))
+            .collect();
+
+        Ok(match args.format {
+            ExportFormat::Json => json!({ "count": jobs.len(), "jobs": jobs }),
+            ExportFormat::Csv => json!({ "count": jobs.len(), "csv": to_csv(&jobs) }),
+        })
+    }
+}
+
+fn to_csv(jobs: &[Job]) -> String {
+    let mut out =
+        String::from("id,tool,tool_version,target,operator,state,queued_at,started_at,finished_at,duration_ms,error\n");
+    for j in jobs {
+        let state = json!(j.state).as_str().unwrap_or_default().to_string();
+        let row = [
+            j.id.clone(),
+            j.tool.clone(),
+            j.tool_version.clone(),
+            j.target.clone().unwrap_or_default(),
+            j.operator.clone(),
+            state,
+            j.queued_at.to_rfc3339(),
+            j.started_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            j.finished_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            j.duration_ms.map(|d| d.to_string()).unwrap_or_default(),
+            j.error.clone().unwrap_or_default(),
+        ];
+        let cells: Vec<String> = row.iter().map(|c| csv_escape(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::typed::TypedTool;
+use crate::tool_state;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that shows agents the catalog grouped by category, so they can pick
+/// the slice of tools worth presenting to the model.
+pub struct ListToolsByCategoryTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListToolsByCategoryArgs {
+    /// Only list this category. Default: every category
+    category: Option<ToolCategory>,
+}
+
+#[async_trait::async_trait]
+impl TypedTool for ListToolsByCategoryTool {
+    type Args = ListToolsByCategoryArgs;
+
+    const NAME: &'static str = "list_tools_by_category";
+    const DESCRIPTION: &'static str = "Lists the available tools grouped by category (recon, vuln-scan, osint, admin) with their descriptions, optionally for a single category. Use it to narrow the tool catalog to what a task needs.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: ListToolsByCategoryArgs) -> Result<Value> {
+        let catalog = tool_state::catalog();
+        let mut categories = Map::new();
+        for category in ToolCategory::ALL {
+            if args.category.is_some_and(|c| c != category) {
+                continue;
+            }
+            let tools: Vec<Value> = catalog
+                .iter()
+                .filter(|e| e.category == category)
+                .map(|e| {
+                    let mut tool = json!({ "name": e.name, "description": e.description });
+                    if e.name != e.canonical {
+                        tool["aliasOf"] = json!(e.canonical);
+                    }
+                    tool
+                })
+                .collect();
+            let key = serde_json::to_value(category)?.as_str().unwrap_or_default().to_string();
+            categories.insert(key, json!(tools));
+        }
+        Ok(json!({ "categories": categories }))
+    }
+}
@@ -1,31 +1,203 @@
+mod typed;
+
 mod nmap_normal_scan_tool;
 mod advanced_nmap_tool;
+mod nmap_scan_jobs_tool;
 mod openvas_get_version_tool;
+mod openvas_feed_status_tool;
+mod openvas_sync_feed_tool;
 mod openvas_list_configs_tool;
+mod openvas_clone_scan_config_tool;
+mod openvas_modify_scan_config_tool;
+mod openvas_list_scanners_tool;
 mod openvas_create_target_tool;
+mod openvas_list_targets_tool;
+mod openvas_delete_target_tool;
+mod openvas_create_port_list_tool;
+mod openvas_list_port_lists_tool;
+mod openvas_create_credential_tool;
+mod openvas_list_credentials_tool;
+mod openvas_attach_credential_tool;
+mod openvas_create_schedule_tool;
+mod openvas_list_schedules_tool;
+mod openvas_delete_schedule_tool;
+mod openvas_create_alert_tool;
 mod openvas_create_task_tool;
+mod openvas_list_tasks_tool;
+mod openvas_delete_task_tool;
+mod openvas_modify_task_tool;
 mod openvas_start_task_tool;
+mod openvas_stop_task_tool;
+mod openvas_resume_task_tool;
 mod openvas_task_status_tool;
+mod openvas_wait_for_task_tool;
 mod openvas_get_report_tool;
+mod openvas_get_results_tool;
+mod openvas_get_nvt_tool;
+mod openvas_list_hosts_tool;
+mod openvas_get_host_tool;
+mod openvas_create_ticket_tool;
+mod openvas_list_tickets_tool;
+mod openvas_modify_ticket_tool;
+mod openvas_list_reports_tool;
+mod openvas_delete_report_tool;
+mod openvas_import_results_tool;
+mod openvas_diff_reports_tool;
+mod openvas_prioritize_findings_tool;
+mod openvas_export_sarif_tool;
+mod openvas_render_report_tool;
+mod openvas_correlate_nmap_tool;
+mod openvas_cleanup_tool;
+mod openvas_list_trash_tool;
+mod openvas_empty_trashcan_tool;
+mod openvas_restore_tool;
+mod openvas_bulk_create_targets_tool;
+mod openvas_fan_out_tool;
+mod openvas_full_scan_tool;
+mod openvas_recommend_config_tool;
 mod simple_echo_tool;
+mod doctor_tool;
+mod jobs_export_tool;
+mod native_syn_scan_tool;
+mod native_banner_grab_tool;
+mod asset_tags_tool;
+mod scan_history_tool;
+mod monitor_asset_tool;
+mod attest_scope_tool;
+mod admin_set_tool_state_tool;
+mod list_tools_by_category_tool;
+mod get_agent_metrics_tool;
+
+use std::path::Path;
 
-use crate::ToolRegistry;
+use crate::{capabilities, command_tools, config, plugins, Tool, ToolRegistry};
 
-/// Register all tools that this MCP server exposes.
+/// Registers every tool of one family.
+type RegisterFamily = fn(&mut ToolRegistry);
+
+/// Register all tools that this MCP server exposes, skipping families
+/// disabled at startup (`--disable-tool-families`).
 pub fn register_all_tools(registry: &mut ToolRegistry) {
+    let families: [(&str, RegisterFamily); 6] = [
+        ("core", register_core_tools),
+        ("nmap", register_nmap_tools),
+        ("openvas", register_openvas_tools),
+        ("native", register_native_tools),
+        ("plugins", register_plugin_tools),
+        ("commands", register_command_tools),
+    ];
+    for (family, register) in families {
+        if capabilities::tool_family_enabled(family) {
+            register(registry);
+        }
+    }
+}
+
+fn register_core_tools(registry: &mut ToolRegistry) {
     registry.register(simple_echo_tool::EchoTool);
+    registry.register(doctor_tool::DoctorTool);
+    registry.register(jobs_export_tool::JobsExportTool);
+    registry.register(asset_tags_tool::AssetTagsTool);
+    registry.register(scan_history_tool::ScanHistoryTool);
+    registry.register(monitor_asset_tool::MonitorAssetTool);
+    registry.register(attest_scope_tool::AttestScopeTool);
+    registry.register(admin_set_tool_state_tool::AdminSetToolStateTool);
+    registry.register(list_tools_by_category_tool::ListToolsByCategoryTool);
+    registry.register(get_agent_metrics_tool::GetAgentMetricsTool);
+}
+
+fn register_nmap_tools(registry: &mut ToolRegistry) {
     registry.register(nmap_normal_scan_tool::NmapOpenPortsTool);
+    registry.register_alias("nmap_open_ports", "nmap.scan", true);
     registry.register(advanced_nmap_tool::AdvancedNmapTool);
     registry.register(advanced_nmap_tool::QuickScanTool);
     registry.register(advanced_nmap_tool::StealthScanTool);
     registry.register(advanced_nmap_tool::ComprehensiveScanTool);
     registry.register(advanced_nmap_tool::NetworkDiscoveryTool);
+    registry.register(nmap_scan_jobs_tool::NmapScanStatusTool);
+    registry.register(nmap_scan_jobs_tool::NmapScanResultTool);
+}
+
+fn register_openvas_tools(registry: &mut ToolRegistry) {
     registry.register(openvas_get_version_tool::OpenVASGetVersionTool);
+    registry.register(openvas_feed_status_tool::OpenVASFeedStatusTool);
+    registry.register(openvas_sync_feed_tool::OpenVASSyncFeedTool);
     registry.register(openvas_list_configs_tool::OpenVASListConfigsTool);
+    registry.register(openvas_clone_scan_config_tool::OpenVASCloneScanConfigTool);
+    registry.register(openvas_modify_scan_config_tool::OpenVASModifyScanConfigTool);
+    registry.register(openvas_list_scanners_tool::OpenVASListScannersTool);
+    registry.register(openvas_list_targets_tool::OpenVASListTargetsTool);
     registry.register(openvas_create_target_tool::OpenVASCreateTargetTool);
+    registry.register(openvas_delete_target_tool::OpenVASDeleteTargetTool);
+    registry.register(openvas_bulk_create_targets_tool::OpenVASBulkCreateTargetsTool);
+    registry.register(openvas_create_port_list_tool::OpenVASCreatePortListTool);
+    registry.register(openvas_list_port_lists_tool::OpenVASListPortListsTool);
+    registry.register(openvas_create_credential_tool::OpenVASCreateCredentialTool);
+    registry.register(openvas_list_credentials_tool::OpenVASListCredentialsTool);
+    registry.register(openvas_attach_credential_tool::OpenVASAttachCredentialTool);
+    registry.register(openvas_create_schedule_tool::OpenVASCreateScheduleTool);
+    registry.register(openvas_list_schedules_tool::OpenVASListSchedulesTool);
+    registry.register(openvas_delete_schedule_tool::OpenVASDeleteScheduleTool);
+    registry.register(openvas_create_alert_tool::OpenVASCreateAlertTool);
     registry.register(openvas_create_task_tool::OpenVASCreateTaskTool);
+    registry.register(openvas_modify_task_tool::OpenVASModifyTaskTool);
     registry.register(openvas_start_task_tool::OpenVASStartTaskTool);
+    registry.register(openvas_stop_task_tool::OpenVASStopTaskTool);
+    registry.register(openvas_resume_task_tool::OpenVASResumeTaskTool);
     registry.register(openvas_task_status_tool::OpenVASTaskStatusTool);
+    registry.register(openvas_wait_for_task_tool::OpenVASWaitForTaskTool);
+    registry.register(openvas_list_tasks_tool::OpenVASListTasksTool);
+    registry.register(openvas_delete_task_tool::OpenVASDeleteTaskTool);
     registry.register(openvas_get_report_tool::OpenVASGetReportTool);
+    registry.register(openvas_get_results_tool::OpenVASGetResultsTool);
+    registry.register(openvas_get_nvt_tool::OpenVASGetNvtTool);
+    registry.register(openvas_list_hosts_tool::OpenVASListHostsTool);
+    registry.register(openvas_get_host_tool::OpenVASGetHostTool);
+    registry.register(openvas_create_ticket_tool::OpenVASCreateTicketTool);
+    registry.register(openvas_list_tickets_tool::OpenVASListTicketsTool);
+    registry.register(openvas_modify_ticket_tool::OpenVASModifyTicketTool);
+    registry.register(openvas_list_reports_tool::OpenVASListReportsTool);
+    registry.register(openvas_delete_report_tool::OpenVASDeleteReportTool);
+    registry.register(openvas_import_results_tool::OpenVASImportResultsTool);
+    registry.register(openvas_diff_reports_tool::OpenVASDiffReportsTool);
+    registry.register(openvas_prioritize_findings_tool::OpenVASPrioritizeFindingsTool);
+    registry.register(openvas_export_sarif_tool::OpenVASExportSarifTool);
+    registry.register(openvas_render_report_tool::OpenVASRenderReportTool);
+    registry.register(openvas_correlate_nmap_tool::OpenVASCorrelateNmapTool);
+    registry.register(openvas_fan_out_tool::OpenVASFanOutTool);
+    registry.register(openvas_full_scan_tool::OpenVASFullScanTool);
+    registry.register(openvas_recommend_config_tool::OpenVASRecommendConfigTool);
+    registry.register(openvas_cleanup_tool::OpenVASCleanupTool);
+    registry.register(openvas_list_trash_tool::OpenVASListTrashTool);
+    registry.register(openvas_empty_trashcan_tool::OpenVASEmptyTrashcanTool);
+    registry.register(openvas_restore_tool::OpenVASRestoreTool);
+}
+
+fn register_native_tools(registry: &mut ToolRegistry) {
+    registry.register(native_syn_scan_tool::NativeSynScanTool);
+    registry.register(native_banner_grab_tool::NativeBannerGrabTool);
 }
 
+/// Tools from plugin libraries (see `plugins`). Registered last, and never
+/// in place of a built-in tool of the same name.
+fn register_plugin_tools(registry: &mut ToolRegistry) {
+    for tool in plugins::load_dir(Path::new(&config::current().plugins_dir)) {
+        if registry.get(tool.name()).is_some() {
+            eprintln!("skipping plugin tool {}: a tool with that name already exists", tool.name());
+            continue;
+        }
+        registry.register(tool);
+    }
+}
+
+/// Tools defined in the `--command-tools` file (see `command_tools`), which
+/// likewise never replace a tool of the same name.
+fn register_command_tools(registry: &mut ToolRegistry) {
+    for tool in command_tools::tools() {
+        if registry.get(tool.name()).is_some() {
+            eprintln!("skipping command tool {}: a tool with that name already exists", tool.name());
+            continue;
+        }
+        registry.register(tool);
+    }
+}
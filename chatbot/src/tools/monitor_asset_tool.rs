@@ -0,0 +1,84 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use super::typed::TypedTool;
+use crate::monitor;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that puts assets under continuous monitoring: scheduled rescans that
+/// notify the configured webhook only when something changed.
+pub struct MonitorAssetTool;
+
+#[derive(Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorAction {
+    Add,
+    Remove,
+    #[default]
+    List,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorAssetArgs {
+    /// add: start (or reschedule) monitoring; remove: stop; list: show all monitors. Default: list
+    #[serde(default)]
+    action: MonitorAction,
+    /// Asset to monitor (IP or hostname). Required for add and remove.
+    target: Option<String>,
+    /// Scan to repeat: nmap.scan, native_syn_scan or native_banner_grab. Default: nmap.scan
+    #[serde(default = "default_tool")]
+    tool: String,
+    /// Minutes between scans. Default: 1440 (daily)
+    #[serde(default = "default_interval_minutes")]
+    interval_minutes: u64,
+    /// Extra input for the scan tool, e.g. {"ports": "1-1024"}. The target is filled in automatically.
+    #[serde(default)]
+    arguments: Map<String, Value>,
+}
+
+fn default_tool() -> String {
+    "nmap.scan".to_string()
+}
+
+fn default_interval_minutes() -> u64 {
+    1440
+}
+
+#[async_trait::async_trait]
+impl TypedTool for MonitorAssetTool {
+    type Args = MonitorAssetArgs;
+
+    const NAME: &'static str = "monitor_asset";
+    const DESCRIPTION: &'static str = "Manages continuously monitored assets. Monitored assets are rescanned on a schedule; each scan is diffed against the previous one and a webhook/Slack notification is sent only when ports or services changed. Monitors are kept in memory and lost when the server restarts.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    async fn run(&self, args: MonitorAssetArgs) -> Result<Value> {
+        let target = || {
+            args.target
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("target is required for this action"))
+        };
+
+        match args.action {
+            MonitorAction::Add => {
+                let monitor = monitor::add(target()?, &args.tool, args.arguments.clone(), args.interval_minutes)?;
+                Ok(json!({ "status": "monitoring", "monitor": monitor }))
+            }
+            MonitorAction::Remove => {
+                let removed = monitor::remove(target()?);
+                Ok(json!({ "status": if removed { "removed" } else { "not_monitored" } }))
+            }
+            MonitorAction::List => {
+                let monitors = monitor::list();
+                Ok(json!({ "count": monitors.len(), "monitors": monitors }))
+            }
+        }
+    }
+}
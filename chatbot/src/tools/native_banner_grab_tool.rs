@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::native::NativeBannerGrabArgs;
+use crate::services::native_banner_grab;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Pure-Rust banner grabber that identifies services against the built-in
+/// signature database.
+pub struct NativeBannerGrabTool;
+
+#[async_trait::async_trait]
+impl TypedTool for NativeBannerGrabTool {
+    type Args = NativeBannerGrabArgs;
+
+    const NAME: &'static str = "native_banner_grab";
+    const DESCRIPTION: &'static str = "EXPERIMENTAL: connects to the given TCP ports, grabs banners (NULL and HTTP probes) and identifies service/product/version from a built-in nmap-service-probes-style signature database. No nmap, no Go backend, no special privileges.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::ACTIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    async fn run(&self, args: NativeBannerGrabArgs) -> Result<Value> {
+        native_banner_grab::native_banner_grab(&args.target, &args.ports, args.timeout_ms).await
+    }
+}
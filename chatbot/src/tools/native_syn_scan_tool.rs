@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::native::NativeSynScanArgs;
+use crate::services::native_syn_scan;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Experimental pure-Rust SYN scan, for when nmap isn't installed and the Go
+/// backend is unreachable.
+pub struct NativeSynScanTool;
+
+#[async_trait::async_trait]
+impl TypedTool for NativeSynScanTool {
+    type Args = NativeSynScanArgs;
+
+    const NAME: &'static str = "native_syn_scan";
+    const DESCRIPTION: &'static str = "EXPERIMENTAL: fast SYN scan of top TCP ports implemented natively (no nmap, no Go backend). Requires root or CAP_NET_RAW; IPv4 only.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::ACTIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn max_concurrency(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    async fn run(&self, args: NativeSynScanArgs) -> Result<Value> {
+        native_syn_scan::native_syn_scan(
+            &args.target,
+            args.ports.as_deref(),
+            args.timeout_ms,
+            args.max_rate,
+            args.service_detection,
+        )
+        .await
+    }
+}
@@ -1,52 +1,38 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::nmap_scan::NmapScanArgs;
 use crate::services::nmap_normal_scan;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that exposes a "normal" Nmap open-port scan via the Go backend.
 pub struct NmapOpenPortsTool;
 
 #[async_trait::async_trait]
-impl Tool for NmapOpenPortsTool {
-    fn name(&self) -> &'static str {
-        "nmap_open_ports"
+impl TypedTool for NmapOpenPortsTool {
+    type Args = NmapScanArgs;
+
+    const NAME: &'static str = "nmap.scan";
+    const DESCRIPTION: &'static str = "Scans open TCP ports on a given target with optional timing template (T0-T5).";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::ACTIVE_SCAN
     }
 
-    fn description(&self) -> &'static str {
-        "Scans open TCP ports on a given target with optional timing template (T0-T5)."
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(30 * 60))
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "target": {
-                    "type": "string",
-                    "description": "Target hostname or IP address to scan."
-                },
-                "timing": {
-                    "type": "string",
-                    "description": "Nmap timing template: T0 (Paranoid), T1 (Sneaky), T2 (Polite), T3 (Normal), T4 (Aggressive), T5 (Insane). Default: T2",
-                    "enum": ["T0", "T1", "T2", "T3", "T4", "T5"]
-                }
-            },
-            "required": ["target"],
-            "additionalProperties": false
-        })
+    fn supports_dry_run(&self) -> bool {
+        true
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let target = input
-            .get("target")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target`"))?;
-            
-        let timing = input
-            .get("timing")
-            .and_then(|v| v.as_str());
-
-        nmap_normal_scan::nmap_normal_scan(target, timing).await
+    async fn run(&self, args: NmapScanArgs) -> Result<Value> {
+        let result = nmap_normal_scan::nmap_normal_scan(&args.target, args.timing).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::nmap_scan::ScanJobArgs;
+use crate::scan_jobs;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Reports on a scan started with `background: true`.
+pub struct NmapScanStatusTool;
+
+#[async_trait::async_trait]
+impl TypedTool for NmapScanStatusTool {
+    type Args = ScanJobArgs;
+
+    const NAME: &'static str = "nmap_scan_status";
+    const DESCRIPTION: &'static str = "State of a background nmap scan (queued, running, succeeded or failed), when it started and finished, and its error; see background in advanced_nmap_scan and comprehensive_scan.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: ScanJobArgs) -> Result<Value> {
+        Ok(serde_json::to_value(scan_jobs::status(&args.scan_id)?)?)
+    }
+}
+
+/// Returns the output of a finished background scan.
+pub struct NmapScanResultTool;
+
+#[async_trait::async_trait]
+impl TypedTool for NmapScanResultTool {
+    type Args = ScanJobArgs;
+
+    const NAME: &'static str = "nmap_scan_result";
+    const DESCRIPTION: &'static str = "Output of a finished background nmap scan, as the scan tool would have returned it; fails with the scan's error if it failed, and while it is still running.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: ScanJobArgs) -> Result<Value> {
+        Ok(serde_json::to_value(scan_jobs::result(&args.scan_id)?)?)
+    }
+}
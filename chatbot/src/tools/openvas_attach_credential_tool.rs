@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::AttachCredentialArgs;
+use crate::services::openvas_attach_credential;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that sets the SSH or SMB credential of an OpenVAS/GVM target via the
+/// Go backend, turning its scans into authenticated ones.
+pub struct OpenVASAttachCredentialTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASAttachCredentialTool {
+    type Args = AttachCredentialArgs;
+
+    const NAME: &'static str = "openvas_attach_credential";
+    const DESCRIPTION: &'static str = "Attaches an OpenVAS/GVM credential to a target via the Go backend, as its ssh (with optional port) or smb login, so scans of the target run authenticated local security checks. Do it before creating tasks for the target: gvmd refuses to change targets in use.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: AttachCredentialArgs) -> Result<Value> {
+        let result = openvas_attach_credential::openvas_attach_credential(
+            &args.target_id,
+            &args.credential_id,
+            args.kind,
+            args.port,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{BulkCreateTargetsArgs, HostList};
+use crate::services::openvas_bulk_create_targets;
+use crate::tagging;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that turns a large host list (or every asset carrying a tag) into
+/// as many GVM targets as needed, ready for task fan-out.
+pub struct OpenVASBulkCreateTargetsTool;
+
+/// The host list to split: `hosts` as given, or every asset tagged `tag`.
+pub(super) fn resolve_hosts(hosts: Option<HostList>, tag: Option<String>) -> Result<String> {
+    match (hosts, tag) {
+        (Some(hosts), None) => Ok(hosts.joined()),
+        (None, Some(tag)) => {
+            let assets = tagging::assets(Some(&tag));
+            if assets.is_empty() {
+                anyhow::bail!("no assets are tagged `{tag}`");
+            }
+            Ok(assets.into_iter().map(|a| a.target).collect::<Vec<_>>().join(","))
+        }
+        _ => anyhow::bail!("give exactly one of hosts or tag"),
+    }
+}
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASBulkCreateTargetsTool {
+    type Args = BulkCreateTargetsArgs;
+
+    const NAME: &'static str = "openvas_bulk_create_targets";
+    const DESCRIPTION: &'static str = "Creates OpenVAS/GVM targets for a large host list or for all assets with a given tag, split into chunks that respect GVM's per-target host limit, with templated names. hosts is one string or an array of hosts, ranges and CIDRs; CIDRs larger than the limit are cut into smaller blocks. Returns every target ID for creating one task per target; repeating a call reuses the targets it created.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: BulkCreateTargetsArgs) -> Result<Value> {
+        let hosts = resolve_hosts(args.hosts, args.tag)?;
+
+        let created = openvas_bulk_create_targets::openvas_bulk_create_targets(
+            &hosts,
+            args.max_hosts_per_target,
+            &args.name_template,
+            args.port_range.as_deref(),
+            args.port_list_id.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(created)?)
+    }
+}
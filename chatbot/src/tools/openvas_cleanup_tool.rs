@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CleanupArgs;
+use crate::services::openvas_cleanup;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Housekeeping for long-lived GVM instances: removes expired agent-created
+/// tasks and targets and empties the trashcan.
+pub struct OpenVASCleanupTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCleanupTool {
+    type Args = CleanupArgs;
+
+    const NAME: &'static str = "openvas_cleanup";
+    const DESCRIPTION: &'static str = "Deletes OpenVAS/GVM tasks and targets whose names match a naming pattern and that are older than a retention window (running tasks are skipped), optionally empties the trashcan, and reports reclaimed object counts. Lists candidates only unless confirm is true.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CleanupArgs) -> Result<Value> {
+        let result = openvas_cleanup::openvas_cleanup(
+            &args.name_pattern,
+            args.retention_days,
+            args.empty_trashcan,
+            args.confirm,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
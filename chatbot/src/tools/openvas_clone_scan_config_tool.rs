@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CloneScanConfigArgs;
+use crate::services::openvas_clone_scan_config;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that copies an OpenVAS/GVM scan config via the Go backend, as the
+/// starting point of a tailored config.
+pub struct OpenVASCloneScanConfigTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCloneScanConfigTool {
+    type Args = CloneScanConfigArgs;
+
+    const NAME: &'static str = "openvas_clone_scan_config";
+    const DESCRIPTION: &'static str = "Copies an existing OpenVAS/GVM scan config (config_id from openvas_list_scan_configs, e.g. 'Full and fast') under a new name via the Go backend and returns the new config's ID. Predefined configs cannot be changed: clone one, tailor the clone with openvas_modify_scan_config, then pass its ID to openvas_create_task.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CloneScanConfigArgs) -> Result<Value> {
+        let result = openvas_clone_scan_config::openvas_clone_scan_config(
+            &args.config_id,
+            &args.name,
+            args.comment.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{CorrelateNmapArgs, ReportFilter};
+use crate::services::openvas_correlate_nmap;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that checks the findings of an OpenVAS/GVM report against the
+/// latest nmap scans of the same hosts.
+pub struct OpenVASCorrelateNmapTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCorrelateNmapTool {
+    type Args = CorrelateNmapArgs;
+
+    const NAME: &'static str = "openvas_correlate_nmap";
+    const DESCRIPTION: &'static str = "Cross-references the findings of an OpenVAS/GVM report with the latest nmap scan of each affected host (or of nmap_targets), to tell vulnerable services nmap could reach from those it found filtered or closed. Each finding gets an exposure: reachable, filtered, closed, host_level (no port, e.g. general/tcp), not_scanned (port not in the nmap scan) or no_scan (host never scanned); findings are ranked by exposure, then severity. Nothing is scanned: run nmap first from where an attacker would stand, e.g. outside the firewall.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CorrelateNmapArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: Some(args.min_severity.unwrap_or(0.1)),
+            min_qod: args.min_qod,
+        };
+        let result =
+            openvas_correlate_nmap::openvas_correlate_nmap(&args.report_id, &filter, &args.nmap_targets).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
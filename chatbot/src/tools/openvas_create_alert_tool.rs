@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CreateAlertArgs;
+use crate::services::openvas_create_alert;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that creates an OpenVAS/GVM alert via the Go backend, so long scans
+/// can notify someone when they end instead of being polled.
+pub struct OpenVASCreateAlertTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCreateAlertTool {
+    type Args = CreateAlertArgs;
+
+    const NAME: &'static str = "openvas_create_alert";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM alert via the Go backend and returns its ID: email (to_address, optionally attach_report) or http_get (url), set off when a task reaches on_status (Done by default). Attach it to tasks with openvas_modify_task's alert_ids; gvmd then notifies by itself, without polling.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CreateAlertArgs) -> Result<Value> {
+        let result = openvas_create_alert::openvas_create_alert(&args).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CreateCredentialArgs;
+use crate::services::openvas_create_credential;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that creates an OpenVAS/GVM credential via the Go backend, for
+/// authenticated scans.
+pub struct OpenVASCreateCredentialTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCreateCredentialTool {
+    type Args = CreateCredentialArgs;
+
+    const NAME: &'static str = "openvas_create_credential";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM credential via the Go backend and returns its ID: username_password (login, password) or ssh_key (login, PEM private_key, optional passphrase as password). Attach it to a target with openvas_attach_credential; authenticated scans find far more than unauthenticated ones. Secrets are never returned.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CreateCredentialArgs) -> Result<Value> {
+        let result = openvas_create_credential::openvas_create_credential(
+            &args.name,
+            args.credential_type,
+            &args.login,
+            args.password.as_deref(),
+            args.private_key.as_deref(),
+            args.comment.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CreatePortListArgs;
+use crate::services::openvas_create_port_list;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that creates an OpenVAS/GVM port list via the Go backend, for
+/// targets that should scan a fixed set of ports.
+pub struct OpenVASCreatePortListTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCreatePortListTool {
+    type Args = CreatePortListArgs;
+
+    const NAME: &'static str = "openvas_create_port_list";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM port list (name, port_range such as 'T:1-1024,8080,U:53,161') via the Go backend and returns its ID, for openvas_create_target's port_list_id. Check openvas_list_port_lists first: gvmd ships lists for the common cases.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CreatePortListArgs) -> Result<Value> {
+        let result = openvas_create_port_list::openvas_create_port_list(
+            &args.name,
+            &args.port_range,
+            args.comment.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
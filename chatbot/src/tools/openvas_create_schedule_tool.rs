@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CreateScheduleArgs;
+use crate::services::openvas_create_schedule;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that creates an OpenVAS/GVM schedule via the Go backend, so tasks
+/// can be set up to run on their own, e.g. every week.
+pub struct OpenVASCreateScheduleTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCreateScheduleTool {
+    type Args = CreateScheduleArgs;
+
+    const NAME: &'static str = "openvas_create_schedule";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM schedule (name, first_run as RFC 3339, recurrence once/hourly/daily/weekly/monthly, optional timezone) via the Go backend and returns its ID. Pass the ID as schedule_id to openvas_create_task or openvas_modify_task to run a task on that schedule.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CreateScheduleArgs) -> Result<Value> {
+        let result = openvas_create_schedule::openvas_create_schedule(
+            &args.name,
+            &args.first_run,
+            args.recurrence,
+            args.timezone.as_deref(),
+            args.comment.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
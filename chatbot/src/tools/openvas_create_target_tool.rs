@@ -1,62 +1,45 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::CreateTargetArgs;
 use crate::services::openvas_create_target;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that creates a new OpenVAS/GVM target via the Go backend
 /// and returns the created target ID.
 pub struct OpenVASCreateTargetTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASCreateTargetTool {
-    fn name(&self) -> &'static str {
-        "openvas_create_target"
-    }
+impl TypedTool for OpenVASCreateTargetTool {
+    type Args = CreateTargetArgs;
 
-    fn description(&self) -> &'static str {
-        "Creates an OpenVAS/GVM target (name, hosts, optional port_range) via the Go backend and returns its ID."
-    }
+    const NAME: &'static str = "openvas_create_target";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM target (name, hosts, optional port_range or port_list_id, not both) via the Go backend and returns its ID. Reuses a target with the same name, hosts and ports; one with the same name but other hosts or ports is an error.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "name": {
-                    "type": "string",
-                    "description": "Friendly name for the target."
-                },
-                "hosts": {
-                    "type": "string",
-                    "description": "Hostname/IP or CIDR understood by OpenVAS."
-                },
-                "port_range": {
-                    "type": "string",
-                    "description": "Optional port range string (e.g. '1-65535' or '62078')."
-                }
-            },
-            "required": ["name", "hosts"],
-            "additionalProperties": false
-        })
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let name = input
-            .get("name")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `name`"))?;
-
-        let hosts = input
-            .get("hosts")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `hosts`"))?;
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
 
-        let port_range = input
-            .get("port_range")
-            .and_then(|v| v.as_str());
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        let result = openvas_create_target::openvas_create_target(name, hosts, port_range).await?;
-        Ok(result)
+    async fn run(&self, args: CreateTargetArgs) -> Result<Value> {
+        let result = openvas_create_target::openvas_create_target(
+            &args.name,
+            &args.hosts,
+            args.port_range.as_deref(),
+            args.port_list_id.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
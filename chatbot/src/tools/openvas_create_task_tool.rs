@@ -1,63 +1,46 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::CreateTaskArgs;
 use crate::services::openvas_create_task;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that creates a new OpenVAS/GVM task via the Go backend
 /// and returns the created task ID.
 pub struct OpenVASCreateTaskTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASCreateTaskTool {
-    fn name(&self) -> &'static str {
-        "openvas_create_task"
-    }
+impl TypedTool for OpenVASCreateTaskTool {
+    type Args = CreateTaskArgs;
 
-    fn description(&self) -> &'static str {
-        "Creates an OpenVAS/GVM task (name, config_id, target_id) via the Go backend and returns its ID."
-    }
+    const NAME: &'static str = "openvas_create_task";
+    const DESCRIPTION: &'static str = "Creates an OpenVAS/GVM task (name, config_id, target_id) via the Go backend and returns its ID. Pass a schedule_id from openvas_create_schedule to have gvmd run the task at the scheduled times, e.g. weekly, and a scanner_id from openvas_list_scanners to run it on a scanner other than the default OpenVAS one.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "name": {
-                    "type": "string",
-                    "description": "Friendly name for the task."
-                },
-                "config_id": {
-                    "type": "string",
-                    "description": "OpenVAS scan configuration ID to use for the task."
-                },
-                "target_id": {
-                    "type": "string",
-                    "description": "OpenVAS target ID that this task will scan."
-                }
-            },
-            "required": ["name", "config_id", "target_id"],
-            "additionalProperties": false
-        })
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let name = input
-            .get("name")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `name`"))?;
-
-        let config_id = input
-            .get("config_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `config_id`"))?;
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
 
-        let target_id = input
-            .get("target_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `target_id`"))?;
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        let result = openvas_create_task::openvas_create_task(name, config_id, target_id).await?;
-        Ok(result)
+    async fn run(&self, args: CreateTaskArgs) -> Result<Value> {
+        let result = openvas_create_task::openvas_create_task(
+            &args.name,
+            &args.config_id,
+            &args.target_id,
+            args.schedule_id.as_deref(),
+            args.scanner_id.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::CreateTicketArgs;
+use crate::services::openvas_create_ticket;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that opens an OpenVAS/GVM remediation ticket for a finding via the
+/// Go backend.
+pub struct OpenVASCreateTicketTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASCreateTicketTool {
+    type Args = CreateTicketArgs;
+
+    const NAME: &'static str = "openvas_create_ticket";
+    const DESCRIPTION: &'static str = "Turns a finding into a GVM remediation ticket via the Go backend: opens a ticket for the result ID (a finding's id from openvas_get_report), assigned to a gvmd user by name, with a note saying what needs doing. Returns the ticket ID. Track it with openvas_list_tickets and openvas_modify_ticket.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: CreateTicketArgs) -> Result<Value> {
+        let result = openvas_create_ticket::openvas_create_ticket(&args.result_id, &args.assignee, &args.note).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::DeleteReportArgs;
+use crate::services::openvas_delete_report;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that deletes an OpenVAS/GVM report via the Go backend, so reports
+/// of scans the agent no longer needs stop piling up in gvmd.
+pub struct OpenVASDeleteReportTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASDeleteReportTool {
+    type Args = DeleteReportArgs;
+
+    const NAME: &'static str = "openvas_delete_report";
+    const DESCRIPTION: &'static str = "Deletes an OpenVAS/GVM report by ID via the Go backend. Reports do not go to the trashcan, so this cannot be undone; the task itself is kept. gvmd refuses to delete the report of a task that is still running.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: DeleteReportArgs) -> Result<Value> {
+        let result = openvas_delete_report::openvas_delete_report(&args.report_id).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
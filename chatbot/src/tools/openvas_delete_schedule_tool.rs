@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::DeleteScheduleArgs;
+use crate::services::openvas_delete_schedule;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that deletes (or trashes) an OpenVAS/GVM schedule by ID via the Go
+/// backend.
+pub struct OpenVASDeleteScheduleTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASDeleteScheduleTool {
+    type Args = DeleteScheduleArgs;
+
+    const NAME: &'static str = "openvas_delete_schedule";
+    const DESCRIPTION: &'static str = "Deletes an OpenVAS/GVM schedule by ID via the Go backend: into the trashcan by default, for good with ultimate. Schedules still used by a task are refused; delete the task or give it another schedule first.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: DeleteScheduleArgs) -> Result<Value> {
+        let result = openvas_delete_schedule::openvas_delete_schedule(&args.schedule_id, args.ultimate).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
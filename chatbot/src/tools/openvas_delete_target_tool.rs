@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::DeleteTargetArgs;
+use crate::services::openvas_delete_target;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that deletes (or trashes) an OpenVAS/GVM target by ID via the Go
+/// backend, so stale test targets can be cleaned up from an agent session.
+pub struct OpenVASDeleteTargetTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASDeleteTargetTool {
+    type Args = DeleteTargetArgs;
+
+    const NAME: &'static str = "openvas_delete_target";
+    const DESCRIPTION: &'static str = "Deletes an OpenVAS/GVM target by ID via the Go backend: into the trashcan by default, for good with ultimate. Targets still used by a task are refused; delete the task first.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: DeleteTargetArgs) -> Result<Value> {
+        let result = openvas_delete_target::openvas_delete_target(&args.target_id, args.ultimate).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::DeleteTaskArgs;
+use crate::services::openvas_delete_task;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that deletes (or trashes) an OpenVAS/GVM task and its reports via
+/// the Go backend. Running tasks are refused unless `force` is set.
+pub struct OpenVASDeleteTaskTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASDeleteTaskTool {
+    type Args = DeleteTaskArgs;
+
+    const NAME: &'static str = "openvas_delete_task";
+    const DESCRIPTION: &'static str = "Deletes an OpenVAS/GVM task and its reports by ID via the Go backend: into the trashcan by default, for good with ultimate. Tasks that are running or queued are refused unless force is true, which stops their scan.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: DeleteTaskArgs) -> Result<Value> {
+        let result = openvas_delete_task::openvas_delete_task(&args.task_id, args.ultimate, args.force).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
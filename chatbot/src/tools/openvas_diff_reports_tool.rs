@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{DiffReportsArgs, ReportFilter};
+use crate::services::openvas_diff_reports;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that compares two OpenVAS/GVM reports via the Go backend, for
+/// validating a re-scan after remediation.
+pub struct OpenVASDiffReportsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASDiffReportsTool {
+    type Args = DiffReportsArgs;
+
+    const NAME: &'static str = "openvas_diff_reports";
+    const DESCRIPTION: &'static str = "Compares two OpenVAS/GVM reports, e.g. scans of the same target before and after remediation, and returns the findings that are new in report_id, fixed since base_report_id, and unchanged, with counts. Findings are matched by host, port and NVT. Use min_severity and min_qod to compare only serious, high-confidence results.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: DiffReportsArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: args.min_severity,
+            min_qod: args.min_qod,
+        };
+        let result = openvas_diff_reports::openvas_diff_reports(
+            &args.base_report_id,
+            &args.report_id,
+            &filter,
+            args.include_unchanged,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
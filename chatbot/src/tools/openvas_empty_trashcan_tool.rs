@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::EmptyTrashcanArgs;
+use crate::services::openvas_empty_trashcan;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that empties the OpenVAS/GVM trashcan via the Go backend, after
+/// listing what it holds.
+pub struct OpenVASEmptyTrashcanTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASEmptyTrashcanTool {
+    type Args = EmptyTrashcanArgs;
+
+    const NAME: &'static str = "openvas_empty_trashcan";
+    const DESCRIPTION: &'static str = "Empties the OpenVAS/GVM trashcan via the Go backend, deleting every trashed task, target, scan config, schedule, alert, credential and port list for good, and returns what it held. Lists the trashcan only unless confirm is true.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_DELETE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: EmptyTrashcanArgs) -> Result<Value> {
+        let result = openvas_empty_trashcan::openvas_empty_trashcan(args.confirm).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
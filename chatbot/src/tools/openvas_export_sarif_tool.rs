@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{ExportSarifArgs, ReportFilter};
+use crate::services::openvas_export_sarif;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that converts an OpenVAS/GVM report into SARIF 2.1.0, for upload
+/// into GitHub code scanning or DefectDojo.
+pub struct OpenVASExportSarifTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASExportSarifTool {
+    type Args = ExportSarifArgs;
+
+    const NAME: &'static str = "openvas_export_sarif";
+    const DESCRIPTION: &'static str = "Converts an OpenVAS/GVM report's findings into a SARIF 2.1.0 log (one rule per NVT, results located at their host and port, with CVSS as security-severity) that can be uploaded to GitHub code scanning or imported into DefectDojo. Pass as_resource to save the log as an openvas://report/{report_id}/sarif resource and get its URI instead.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ExportSarifArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: args.min_severity,
+            min_qod: args.min_qod,
+        };
+        let result = openvas_export_sarif::openvas_export_sarif(&args.report_id, &filter, args.as_resource).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
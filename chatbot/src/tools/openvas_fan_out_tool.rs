@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::openvas_bulk_create_targets_tool::resolve_hosts;
+use super::typed::TypedTool;
+use crate::models::openvas::FanOutArgs;
+use crate::services::{openvas_bulk_create_targets, openvas_fan_out};
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Orchestration tool for estates too large for one GVM task: one task per
+/// target chunk, all running at once, merged into a single result.
+pub struct OpenVASFanOutTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASFanOutTool {
+    type Args = FanOutArgs;
+
+    const NAME: &'static str = "openvas_fan_out";
+    const DESCRIPTION: &'static str = "Scans a large estate with OpenVAS/GVM by creating and starting one task per target chunk (from a host list, a tag, or existing target IDs), monitoring all tasks until they finish, and merging their findings into one deduplicated result sorted by severity.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+
+    fn max_concurrency(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: FanOutArgs) -> Result<Value> {
+        if args.poll_interval_secs == 0 {
+            anyhow::bail!("poll_interval_secs must be at least 1");
+        }
+
+        let given = [args.hosts.is_some(), args.tag.is_some(), args.target_ids.is_some()];
+        if given.iter().filter(|g| **g).count() != 1 {
+            anyhow::bail!("give exactly one of hosts, tag or target_ids");
+        }
+
+        let targets: Vec<(String, String)> = match args.target_ids {
+            Some(ids) => {
+                let date = chrono::Utc::now().format("%Y%m%d").to_string();
+                ids.iter()
+                    .enumerate()
+                    .map(|(i, id)| {
+                        let name = openvas_bulk_create_targets::fill_name_template(
+                            &args.name_template,
+                            i + 1,
+                            ids.len(),
+                            &date,
+                        );
+                        (id.clone(), name)
+                    })
+                    .collect()
+            }
+            None => {
+                let hosts = resolve_hosts(args.hosts, args.tag)?;
+                let created = openvas_bulk_create_targets::openvas_bulk_create_targets(
+                    &hosts,
+                    args.max_hosts_per_target,
+                    &args.name_template,
+                    args.port_range.as_deref(),
+                    args.port_list_id.as_deref(),
+                )
+                .await?;
+                created.targets.into_iter().map(|t| (t.id, t.name)).collect()
+            }
+        };
+
+        let result = openvas_fan_out::openvas_fan_out(
+            &targets,
+            &args.config_id,
+            Duration::from_secs(args.poll_interval_secs),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
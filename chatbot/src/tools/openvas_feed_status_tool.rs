@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::FeedStatusArgs;
+use crate::services::openvas_feed_status;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that reports the age and sync state of the GVM feeds via the Go
+/// backend, to tell whether scans run on current vulnerability data.
+pub struct OpenVASFeedStatusTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASFeedStatusTool {
+    type Args = FeedStatusArgs;
+
+    const NAME: &'static str = "openvas_feed_status";
+    const DESCRIPTION: &'static str = "Reports the GVM feeds (NVT, SCAP, CERT, GVMD_DATA) via the Go backend: each feed's version, last update, age in hours, whether it is syncing, and whether it is stale (older than max_age_days, default 7). warnings lists stale, syncing and missing feeds. Check it before scanning: scans on a stale NVT feed miss recent vulnerabilities; openvas_sync_feed updates it.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: FeedStatusArgs) -> Result<Value> {
+        let result = openvas_feed_status::openvas_feed_status(args.max_age_days).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::FullScanArgs;
+use crate::services::openvas_full_scan;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Orchestration tool that collapses target creation, task creation and
+/// task start into one call.
+pub struct OpenVASFullScanTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASFullScanTool {
+    type Args = FullScanArgs;
+
+    const NAME: &'static str = "openvas_full_scan";
+    const DESCRIPTION: &'static str = "Starts an OpenVAS/GVM scan of hosts in one call: looks up the scan config by name ('Full and fast' by default), creates or reuses the target and task, starts the task and returns the task ID with how to follow it. Use instead of openvas_create_target, openvas_create_task and openvas_start_task.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: FullScanArgs) -> Result<Value> {
+        let result = openvas_full_scan::openvas_full_scan(
+            &args.hosts,
+            &args.config_name,
+            args.name.as_deref(),
+            args.port_range.as_deref(),
+            args.port_list_id.as_deref(),
+            args.scanner_id.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
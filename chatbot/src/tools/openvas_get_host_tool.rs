@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::GetHostArgs;
+use crate::services::openvas_get_host;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that looks up one host in OpenVAS/GVM's asset database via the Go
+/// backend.
+pub struct OpenVASGetHostTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASGetHostTool {
+    type Args = GetHostArgs;
+
+    const NAME: &'static str = "openvas_get_host";
+    const DESCRIPTION: &'static str = "Looks up one host GVM knows from past scans, by asset id, IP or hostname, via the Go backend. Returns what openvas_list_hosts does plus every host detail gvmd recorded (open ports, traceroute, OS detection, ...). Pass a report id from report_ids to openvas_get_report for the host's findings.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: GetHostArgs) -> Result<Value> {
+        let result = openvas_get_host::openvas_get_host(&args.host).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
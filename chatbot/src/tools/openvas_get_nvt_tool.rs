@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::GetNvtArgs;
+use crate::services::openvas_get_nvt;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that looks up one OpenVAS NVT (vulnerability test) via the Go
+/// backend, to explain a finding in depth.
+pub struct OpenVASGetNvtTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASGetNvtTool {
+    type Args = GetNvtArgs;
+
+    const NAME: &'static str = "openvas_get_nvt";
+    const DESCRIPTION: &'static str = "Looks up an OpenVAS NVT (vulnerability test) by OID, e.g. a finding's nvt_oid from openvas_get_report, via the Go backend. Returns its name, family, CVSS score and vector, summary, insight, affected versions, impact, detection method, solution and solution type (VendorFix, Workaround, Mitigation, NoneAvailable, WillNotFix), and references (CVEs, advisories, URLs). Use it to explain a finding in depth.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: GetNvtArgs) -> Result<Value> {
+        let result = openvas_get_nvt::openvas_get_nvt(&args.oid).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
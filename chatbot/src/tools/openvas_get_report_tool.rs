@@ -1,45 +1,44 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::{GetReportArgs, ReportFilter};
 use crate::services::openvas_get_report;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that fetches the final OpenVAS/GVM report by report ID via the Go
-/// backend and returns the raw get_reports_response XML.
+/// backend, as the raw get_reports_response XML or in another report format.
 pub struct OpenVASGetReportTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASGetReportTool {
-    fn name(&self) -> &'static str {
-        "openvas_get_report"
-    }
+impl TypedTool for OpenVASGetReportTool {
+    type Args = GetReportArgs;
+
+    const NAME: &'static str = "openvas_get_report";
+    const DESCRIPTION: &'static str = "Fetches the final OpenVAS/GVM report by report ID via the Go backend, as XML with parsed findings (default), CSV, PDF, TXT or anonymous XML. PDF comes back base64-encoded; pass as_resource to save the report as a resource and get its URI instead of the contents. Use min_severity (e.g. 7.0) and min_qod (e.g. 70) to keep large reports to the serious, high-confidence results.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
 
-    fn description(&self) -> &'static str {
-        "Fetches the final OpenVAS/GVM report by report ID via the Go backend."
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "report_id": {
-                    "type": "string",
-                    "description": "OpenVAS report ID whose contents should be fetched."
-                }
-            },
-            "required": ["report_id"],
-            "additionalProperties": false
-        })
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let report_id = input
-            .get("report_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `report_id`"))?;
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        let result = openvas_get_report::openvas_get_report(report_id).await?;
-        Ok(result)
+    async fn run(&self, args: GetReportArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: args.min_severity,
+            min_qod: args.min_qod,
+        };
+        let result =
+            openvas_get_report::openvas_get_report(&args.report_id, args.format, &filter, args.as_resource).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
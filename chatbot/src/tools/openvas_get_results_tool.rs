@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::GetResultsArgs;
+use crate::services::openvas_get_results;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that pages through OpenVAS/GVM results via the Go backend.
+pub struct OpenVASGetResultsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASGetResultsTool {
+    type Args = GetResultsArgs;
+
+    const NAME: &'static str = "openvas_get_results";
+    const DESCRIPTION: &'static str = "Fetches one page of GVM results (findings) across reports via the Go backend: id, name, host, port, NVT OID, severity, threat, QoD, CVEs and solution, plus how many results match in total and the `first` of the next page. Select results with a gvmd filter string, e.g. `severity>6.9 and host=10.0.0.5`, `name~OpenSSH` or `cve~CVE-2023`, optionally within one report or task. Use it instead of openvas_get_report for large scans: read the worst findings first and continue with `next_first` only as far as needed.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: GetResultsArgs) -> Result<Value> {
+        let result = openvas_get_results::openvas_get_results(&args).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
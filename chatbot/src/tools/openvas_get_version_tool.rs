@@ -1,31 +1,42 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::NoArgs;
 use crate::services::openvas_get_version;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that fetches the OpenVAS/GVM version via the Go backend.
 pub struct OpenVASGetVersionTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASGetVersionTool {
-    fn name(&self) -> &'static str {
-        "openvas_get_version"
+impl TypedTool for OpenVASGetVersionTool {
+    type Args = NoArgs;
+
+    const NAME: &'static str = "openvas_get_version";
+    const DESCRIPTION: &'static str = "Fetches the OpenVAS/GVM version via the Go backend.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
     }
 
-    fn description(&self) -> &'static str {
-        "Fetches the OpenVAS/GVM version via the Go backend."
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "description": "No input fields required."
-        })
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
     }
 
-    async fn execute(&self, _input: Value) -> Result<Value> {
-        openvas_get_version::openvas_get_version().await
+    fn supports_dry_run(&self) -> bool {
+        true
     }
-}
 
+    async fn run(&self, _args: NoArgs) -> Result<Value> {
+        let result = openvas_get_version::openvas_get_version().await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
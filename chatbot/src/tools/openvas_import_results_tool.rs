@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ImportResultsArgs;
+use crate::services::openvas_import_results;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that imports results from outside OpenVAS into a GVM container
+/// task via the Go backend, so every finding lives in one store.
+pub struct OpenVASImportResultsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASImportResultsTool {
+    type Args = ImportResultsArgs;
+
+    const NAME: &'static str = "openvas_import_results";
+    const DESCRIPTION: &'static str = "Imports external results into OpenVAS/GVM via the Go backend: creates (or reuses) a container task named task_name and adds a report to it, either a GVM-format report given as report_xml or nmap's normal output, whose open ports become Log-level results. The imported report can then be read, diffed and exported like any other.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ImportResultsArgs) -> Result<Value> {
+        let result = openvas_import_results::openvas_import_results(
+            &args.task_name,
+            args.report_xml.as_deref(),
+            args.nmap_output.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListCredentialsArgs;
+use crate::services::openvas_list_credentials;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists existing OpenVAS/GVM credentials, without secrets, via
+/// the Go backend, so the agent can reuse one instead of asking again.
+pub struct OpenVASListCredentialsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListCredentialsTool {
+    type Args = ListCredentialsArgs;
+
+    const NAME: &'static str = "openvas_list_credentials";
+    const DESCRIPTION: &'static str = "Lists existing OpenVAS/GVM credentials (ID, name, type, login, whether a target uses them) via the Go backend, optionally filtered by name. Secrets are never returned.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListCredentialsArgs) -> Result<Value> {
+        let result = openvas_list_credentials::openvas_list_credentials(args.name_contains.as_deref()).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
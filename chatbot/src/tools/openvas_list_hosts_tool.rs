@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListHostsArgs;
+use crate::services::openvas_list_hosts;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists the hosts in OpenVAS/GVM's asset database via the Go
+/// backend, so the agent can see what is already known before rescanning.
+pub struct OpenVASListHostsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListHostsTool {
+    type Args = ListHostsArgs;
+
+    const NAME: &'static str = "openvas_list_hosts";
+    const DESCRIPTION: &'static str = "Lists the hosts GVM already knows from past scans (its host asset inventory) via the Go backend: asset id, IP, hostnames, detected OS and CPE, latest severity, first and last seen, and the reports each host appears in. Filter by IP/hostname, OS, minimum severity, or hosts not seen for N days. Most severe first. Use it before deciding what to rescan; openvas_get_host has one host's full details.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListHostsArgs) -> Result<Value> {
+        let result = openvas_list_hosts::openvas_list_hosts(&args).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
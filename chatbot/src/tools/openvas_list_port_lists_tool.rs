@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListPortListsArgs;
+use crate::services::openvas_list_port_lists;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists existing OpenVAS/GVM port lists via the Go backend, so
+/// the agent can pick one for a target instead of spelling out ports.
+pub struct OpenVASListPortListsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListPortListsTool {
+    type Args = ListPortListsArgs;
+
+    const NAME: &'static str = "openvas_list_port_lists";
+    const DESCRIPTION: &'static str = "Lists existing OpenVAS/GVM port lists (ID, name, TCP/UDP port counts, whether predefined or used by a target) via the Go backend, optionally filtered by name. Pass an ID as openvas_create_target's port_list_id.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListPortListsArgs) -> Result<Value> {
+        let result = openvas_list_port_lists::openvas_list_port_lists(args.name_contains.as_deref()).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListReportsArgs;
+use crate::services::openvas_list_reports;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists OpenVAS/GVM reports with their timestamps and severity
+/// counts via the Go backend, so the agent can find a task's latest report.
+pub struct OpenVASListReportsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListReportsTool {
+    type Args = ListReportsArgs;
+
+    const NAME: &'static str = "openvas_list_reports";
+    const DESCRIPTION: &'static str = "Lists OpenVAS/GVM reports newest first, with their task, scan status, start and end times, highest severity and result counts per threat level, via the Go backend. Pass task_id and limit 1 to get the latest report ID of a task for openvas_get_report.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListReportsArgs) -> Result<Value> {
+        let result = openvas_list_reports::openvas_list_reports(args.task_id.as_deref(), args.limit).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
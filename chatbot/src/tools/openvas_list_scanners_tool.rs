@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::NoArgs;
+use crate::services::openvas_list_scanners;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists the OpenVAS/GVM scanners tasks can run on via the Go backend.
+pub struct OpenVASListScannersTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListScannersTool {
+    type Args = NoArgs;
+
+    const NAME: &'static str = "openvas_list_scanners";
+    const DESCRIPTION: &'static str = "Lists the OpenVAS/GVM scanners (ID, name, type such as openvas or cve, host and port of remote sensors, whether a task uses them) via the Go backend. Pass an ID as openvas_create_task's scanner_id to scan with that scanner.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, _args: NoArgs) -> Result<Value> {
+        let result = openvas_list_scanners::openvas_list_scanners().await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListSchedulesArgs;
+use crate::services::openvas_list_schedules;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists existing OpenVAS/GVM schedules via the Go backend, so
+/// the agent can reuse a schedule instead of creating a duplicate.
+pub struct OpenVASListSchedulesTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListSchedulesTool {
+    type Args = ListSchedulesArgs;
+
+    const NAME: &'static str = "openvas_list_schedules";
+    const DESCRIPTION: &'static str = "Lists existing OpenVAS/GVM schedules with their iCalendar event (first run and recurrence), timezone and the IDs of the tasks using them, via the Go backend, optionally filtered by name.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListSchedulesArgs) -> Result<Value> {
+        let result = openvas_list_schedules::openvas_list_schedules(args.name_contains.as_deref()).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListTargetsArgs;
+use crate::services::openvas_list_targets;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists existing OpenVAS/GVM targets via the Go backend, so the
+/// agent can reuse one instead of creating a duplicate.
+pub struct OpenVASListTargetsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListTargetsTool {
+    type Args = ListTargetsArgs;
+
+    const NAME: &'static str = "openvas_list_targets";
+    const DESCRIPTION: &'static str = "Lists existing OpenVAS/GVM targets (id, name, hosts, port list, whether a task uses them) via the Go backend, optionally filtered by name or hosts. Check here before openvas_create_target to reuse a target.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListTargetsArgs) -> Result<Value> {
+        let result = openvas_list_targets::openvas_list_targets(
+            args.name_contains.as_deref(),
+            args.hosts_contains.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListTasksArgs;
+use crate::services::openvas_list_tasks;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists existing OpenVAS/GVM tasks and where they stand via the
+/// Go backend, so the agent can resume monitoring scans it started earlier.
+pub struct OpenVASListTasksTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListTasksTool {
+    type Args = ListTasksArgs;
+
+    const NAME: &'static str = "openvas_list_tasks";
+    const DESCRIPTION: &'static str = "Lists existing OpenVAS/GVM tasks with their status, progress, scan config, target and last report ID via the Go backend, optionally filtered by status, name or target. Use it to pick up monitoring of scans started earlier.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListTasksArgs) -> Result<Value> {
+        let result = openvas_list_tasks::openvas_list_tasks(
+            args.status.as_deref(),
+            args.name_contains.as_deref(),
+            args.target_id.as_deref(),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ListTicketsArgs;
+use crate::services::openvas_list_tickets;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists OpenVAS/GVM remediation tickets via the Go backend.
+pub struct OpenVASListTicketsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListTicketsTool {
+    type Args = ListTicketsArgs;
+
+    const NAME: &'static str = "openvas_list_tickets";
+    const DESCRIPTION: &'static str = "Lists GVM remediation tickets via the Go backend, most severe first: id, finding name, status (Open, Fixed, Fix Verified, Closed), assignee, host, port, severity, the result, task and report behind it, when it reached each status and the note left at each. Filter by status, assignee, host or task. gvmd sets Fix Verified itself once a later scan no longer finds the result.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ListTicketsArgs) -> Result<Value> {
+        let result = openvas_list_tickets::openvas_list_tickets(&args).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::NoArgs;
+use crate::services::openvas_list_trash;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists what sits in the OpenVAS/GVM trashcan via the Go
+/// backend, to find the IDs `openvas_restore` takes.
+pub struct OpenVASListTrashTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASListTrashTool {
+    type Args = NoArgs;
+
+    const NAME: &'static str = "openvas_list_trash";
+    const DESCRIPTION: &'static str = "Lists the tasks, targets, scan configs, schedules, alerts, credentials and port lists in the OpenVAS/GVM trashcan, with their type, ID and name, via the Go backend. Objects deleted without ultimate end up here and can be brought back with openvas_restore.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, _args: NoArgs) -> Result<Value> {
+        let result = openvas_list_trash::openvas_list_trash().await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
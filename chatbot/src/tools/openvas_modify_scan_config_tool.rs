@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{ConfigChanges, ModifyScanConfigArgs};
+use crate::services::openvas_modify_scan_config;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that enables and disables NVT families and single NVTs in an
+/// OpenVAS/GVM scan config via the Go backend.
+pub struct OpenVASModifyScanConfigTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASModifyScanConfigTool {
+    type Args = ModifyScanConfigArgs;
+
+    const NAME: &'static str = "openvas_modify_scan_config";
+    const DESCRIPTION: &'static str = "Enables or disables NVT families (by gvmd family name, e.g. 'Denial of Service', 'Web application abuses') and single NVTs (by OID) in a scan config cloned with openvas_clone_scan_config, via the Go backend, and returns the families selected afterwards with their NVT counts. Enabled families take every NVT, including ones added by later feed updates. Predefined configs cannot be changed.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ModifyScanConfigArgs) -> Result<Value> {
+        let changes = ConfigChanges {
+            enable_families: args.enable_families,
+            disable_families: args.disable_families,
+            enable_nvts: args.enable_nvts,
+            disable_nvts: args.disable_nvts,
+        };
+        let result = openvas_modify_scan_config::openvas_modify_scan_config(&args.config_id, &changes).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{ModifyTaskArgs, TaskChanges};
+use crate::services::openvas_modify_task;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that changes an existing OpenVAS/GVM task via the Go backend, so a
+/// mistake can be fixed without deleting and recreating the task.
+pub struct OpenVASModifyTaskTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASModifyTaskTool {
+    type Args = ModifyTaskArgs;
+
+    const NAME: &'static str = "openvas_modify_task";
+    const DESCRIPTION: &'static str = "Changes an existing OpenVAS/GVM task's name, scan config, target, schedule, alerts or scanner preferences via the Go backend. The config and target can only be changed before the task first runs; running or queued tasks cannot be changed.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ModifyTaskArgs) -> Result<Value> {
+        let changes = TaskChanges {
+            name: args.name,
+            config_id: args.config_id,
+            target_id: args.target_id,
+            schedule_id: args.schedule_id,
+            alert_ids: args.alert_ids,
+            preferences: args.preferences,
+        };
+        let result = openvas_modify_task::openvas_modify_task(&args.task_id, &changes).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{ModifyTicketArgs, TicketChanges};
+use crate::services::openvas_modify_ticket;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that changes the status or assignee of an OpenVAS/GVM remediation
+/// ticket via the Go backend.
+pub struct OpenVASModifyTicketTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASModifyTicketTool {
+    type Args = ModifyTicketArgs;
+
+    const NAME: &'static str = "openvas_modify_ticket";
+    const DESCRIPTION: &'static str = "Updates a GVM remediation ticket via the Go backend: sets its status to Open, Fixed or Closed with a note (the fix applied, or why it is closed), and/or reassigns it to another gvmd user. Returns the ticket afterwards. Mark a ticket Fixed once remediated; gvmd verifies the fix on the task's next scan.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ModifyTicketArgs) -> Result<Value> {
+        let changes = TicketChanges { status: args.status, note: args.note, assignee: args.assignee };
+        let result = openvas_modify_ticket::openvas_modify_ticket(&args.ticket_id, &changes).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
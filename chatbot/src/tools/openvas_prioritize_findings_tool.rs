@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{PrioritizeFindingsArgs, ReportFilter};
+use crate::services::openvas_prioritize_findings;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that ranks an OpenVAS/GVM report's findings by exploitability, with
+/// their CVEs looked up in NVD, CISA KEV and FIRST EPSS.
+pub struct OpenVASPrioritizeFindingsTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASPrioritizeFindingsTool {
+    type Args = PrioritizeFindingsArgs;
+
+    const NAME: &'static str = "openvas_prioritize_findings";
+    const DESCRIPTION: &'static str = "Returns an OpenVAS/GVM report's findings most pressing first, each with its CVEs looked up in NVD (CVSS v3 vector, publication date, references), CISA's Known Exploited Vulnerabilities catalog and FIRST EPSS. Priority is urgent for known-exploited CVEs, high for likely-exploited (EPSS) or critical findings, then medium and low by severity. Sources that cannot be reached are listed in notes; in offline mode none is contacted.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: PrioritizeFindingsArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: args.min_severity,
+            min_qod: args.min_qod,
+        };
+        let result = openvas_prioritize_findings::openvas_prioritize_findings(
+            &args.report_id,
+            &filter,
+            args.enrich,
+            args.max_cves,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
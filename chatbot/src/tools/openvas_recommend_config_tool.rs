@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::RecommendConfigArgs;
+use crate::services::openvas_recommend_config;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Planning tool that recommends how to scan a target with OpenVAS from
+/// the ports nmap finds open.
+pub struct OpenVASRecommendConfigTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASRecommendConfigTool {
+    type Args = RecommendConfigArgs;
+
+    const NAME: &'static str = "openvas_recommend_config";
+    const DESCRIPTION: &'static str = "Recommends the OpenVAS scan config and port range for a target before creating a task: runs an nmap connect scan of its top 1000 TCP ports with service detection (or takes open_ports already known), then returns the config (name and gvmd ID), a port range limited to the open ports, why, and advice such as attaching SSH or SMB credentials for authenticated checks. Pass the result to openvas_full_scan.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::ACTIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: RecommendConfigArgs) -> Result<Value> {
+        let result = openvas_recommend_config::openvas_recommend_config(
+            &args.target,
+            args.open_ports.as_deref(),
+            args.timing,
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
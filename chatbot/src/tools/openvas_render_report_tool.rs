@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::{RenderReportArgs, ReportFilter};
+use crate::services::openvas_render_report;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that renders an OpenVAS/GVM report, with earlier nmap results, as
+/// a Markdown or HTML document for people rather than pipelines.
+pub struct OpenVASRenderReportTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASRenderReportTool {
+    type Args = RenderReportArgs;
+
+    const NAME: &'static str = "openvas_render_report";
+    const DESCRIPTION: &'static str = "Renders an OpenVAS/GVM report as a Markdown (default) or HTML document: a summary table of findings per threat level and per host, a section per host with its findings most severe first, and remediation notes grouped by fix. Pass nmap_targets to add the open ports and services from their latest nmap scans. The document is saved on the server and returned as an openvas://report/{report_id}/{format} resource URI.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: RenderReportArgs) -> Result<Value> {
+        let filter = ReportFilter {
+            min_severity: args.min_severity,
+            min_qod: args.min_qod,
+        };
+        let result =
+            openvas_render_report::openvas_render_report(&args.report_id, args.format, &filter, &args.nmap_targets)
+                .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
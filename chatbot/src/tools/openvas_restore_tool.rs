@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::RestoreArgs;
+use crate::services::openvas_restore;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that moves an object out of the OpenVAS/GVM trashcan via the Go
+/// backend.
+pub struct OpenVASRestoreTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASRestoreTool {
+    type Args = RestoreArgs;
+
+    const NAME: &'static str = "openvas_restore";
+    const DESCRIPTION: &'static str = "Restores a task, target, scan config, schedule, alert, credential or port list from the OpenVAS/GVM trashcan by ID (see openvas_list_trash) via the Go backend. Restore what an object depends on first, e.g. a task's target before the task.";
+    const CATEGORY: ToolCategory = ToolCategory::Admin;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: RestoreArgs) -> Result<Value> {
+        let result = openvas_restore::openvas_restore(&args.id).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
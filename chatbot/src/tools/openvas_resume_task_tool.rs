@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::ResumeTaskArgs;
+use crate::services::openvas_resume_task;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that resumes a stopped or interrupted OpenVAS/GVM task via the Go
+/// backend, so a long scan cut short by a scanner restart continues instead
+/// of being recreated from scratch.
+pub struct OpenVASResumeTaskTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASResumeTaskTool {
+    type Args = ResumeTaskArgs;
+
+    const NAME: &'static str = "openvas_resume_task";
+    const DESCRIPTION: &'static str = "Resumes a Stopped or Interrupted OpenVAS/GVM task by ID via the Go backend, continuing the scan where it left off instead of starting over, and returns the raw XML response.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: ResumeTaskArgs) -> Result<Value> {
+        let result = openvas_resume_task::openvas_resume_task(&args.task_id).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
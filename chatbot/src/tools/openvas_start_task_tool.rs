@@ -1,45 +1,39 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::StartTaskArgs;
 use crate::services::openvas_start_task;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that starts an existing OpenVAS/GVM task via the Go backend
 /// and returns the raw start_task_response XML.
 pub struct OpenVASStartTaskTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASStartTaskTool {
-    fn name(&self) -> &'static str {
-        "openvas_start_task"
-    }
+impl TypedTool for OpenVASStartTaskTool {
+    type Args = StartTaskArgs;
+
+    const NAME: &'static str = "openvas_start_task";
+    const DESCRIPTION: &'static str = "Starts an existing OpenVAS/GVM task by ID via the Go backend and returns the raw XML response.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
 
-    fn description(&self) -> &'static str {
-        "Starts an existing OpenVAS/GVM task by ID via the Go backend and returns the raw XML response."
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::INTRUSIVE_SCAN
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "task_id": {
-                    "type": "string",
-                    "description": "OpenVAS task ID to start."
-                }
-            },
-            "required": ["task_id"],
-            "additionalProperties": false
-        })
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let task_id = input
-            .get("task_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `task_id`"))?;
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        let result = openvas_start_task::openvas_start_task(task_id).await?;
-        Ok(result)
+    async fn run(&self, args: StartTaskArgs) -> Result<Value> {
+        let result = openvas_start_task::openvas_start_task(&args.task_id).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::StopTaskArgs;
+use crate::services::openvas_stop_task;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that stops a running OpenVAS/GVM task via the Go backend, e.g. a
+/// runaway scan, and returns the raw stop_task_response XML.
+pub struct OpenVASStopTaskTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASStopTaskTool {
+    type Args = StopTaskArgs;
+
+    const NAME: &'static str = "openvas_stop_task";
+    const DESCRIPTION: &'static str = "Stops a running OpenVAS/GVM task by ID via the Go backend and returns the raw XML response. The partial report is kept and the task can be resumed later.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: StopTaskArgs) -> Result<Value> {
+        let result = openvas_stop_task::openvas_stop_task(&args.task_id).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::SyncFeedArgs;
+use crate::services::openvas_sync_feed;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that syncs a GVM feed from Greenbone's feed servers via the Go
+/// backend.
+pub struct OpenVASSyncFeedTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASSyncFeedTool {
+    type Args = SyncFeedArgs;
+
+    const NAME: &'static str = "openvas_sync_feed";
+    const DESCRIPTION: &'static str = "Syncs a GVM feed (nvt, scap, cert, gvmd_data, or all by default) from Greenbone's feed servers by running greenbone-feed-sync in the gvmd container via the Go backend, and returns its output. A full sync can take half an hour; gvmd then loads the new data in the background, so check openvas_feed_status before scanning. Refused in offline mode and over --gmp.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::BACKEND_WRITE
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: SyncFeedArgs) -> Result<Value> {
+        let result = openvas_sync_feed::openvas_sync_feed(args.feed).await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
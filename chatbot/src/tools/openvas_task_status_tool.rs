@@ -1,45 +1,39 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
+use super::typed::TypedTool;
+use crate::models::openvas::TaskStatusArgs;
 use crate::services::openvas_task_status;
-use crate::Tool;
+use crate::{ToolAnnotations, ToolCategory};
 
 /// Tool that fetches the current status/details for an existing OpenVAS/GVM
 /// task via the Go backend and returns the raw get_tasks_response XML.
 pub struct OpenVASTaskStatusTool;
 
 #[async_trait::async_trait]
-impl Tool for OpenVASTaskStatusTool {
-    fn name(&self) -> &'static str {
-        "openvas_task_status"
-    }
+impl TypedTool for OpenVASTaskStatusTool {
+    type Args = TaskStatusArgs;
+
+    const NAME: &'static str = "openvas_task_status";
+    const DESCRIPTION: &'static str = "Fetches the current status/details for an existing OpenVAS/GVM task by ID via the Go backend: status, progress percent, name, the running and last report IDs and when the scan started and ended, alongside gvmd's raw response.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
 
-    fn description(&self) -> &'static str {
-        "Fetches the current status/details for an existing OpenVAS/GVM task by ID via the Go backend."
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
     }
 
-    fn input_schema(&self) -> serde_json::Value {
-        serde_json::json!({
-            "type": "object",
-            "properties": {
-                "task_id": {
-                    "type": "string",
-                    "description": "OpenVAS task ID whose status should be fetched."
-                }
-            },
-            "required": ["task_id"],
-            "additionalProperties": false
-        })
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(2 * 60))
     }
 
-    async fn execute(&self, input: Value) -> Result<Value> {
-        let task_id = input
-            .get("task_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("missing required field `task_id`"))?;
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
 
-        let result = openvas_task_status::openvas_task_status(task_id).await?;
-        Ok(result)
+    async fn run(&self, args: TaskStatusArgs) -> Result<Value> {
+        let result = openvas_task_status::openvas_task_status(&args.task_id).await?;
+        Ok(serde_json::to_value(result)?)
     }
 }
-
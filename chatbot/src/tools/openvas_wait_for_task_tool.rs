@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::typed::TypedTool;
+use crate::models::openvas::WaitForTaskArgs;
+use crate::services::openvas_wait_for_task;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Longest `max_wait_secs` accepted; the tool's timeout leaves room above it.
+const MAX_WAIT_SECS: u64 = 24 * 60 * 60;
+
+/// Tool that waits for an OpenVAS/GVM task to finish, sending MCP progress
+/// notifications while it polls, so agents need not poll themselves.
+pub struct OpenVASWaitForTaskTool;
+
+#[async_trait::async_trait]
+impl TypedTool for OpenVASWaitForTaskTool {
+    type Args = WaitForTaskArgs;
+
+    const NAME: &'static str = "openvas_wait_for_task";
+    const DESCRIPTION: &'static str = "Waits until an OpenVAS/GVM task is Done, Stopped or Interrupted (at most max_wait_secs, one hour by default), polling its status and sending progress notifications with gvmd's percentage, then returns the final status and report ID for openvas_get_report.";
+    const CATEGORY: ToolCategory = ToolCategory::VulnScan;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(MAX_WAIT_SECS + 10 * 60))
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        true
+    }
+
+    async fn run(&self, args: WaitForTaskArgs) -> Result<Value> {
+        if args.max_wait_secs > MAX_WAIT_SECS {
+            anyhow::bail!("max_wait_secs cannot exceed {MAX_WAIT_SECS}");
+        }
+        if args.poll_interval_secs == 0 {
+            anyhow::bail!("poll_interval_secs must be at least 1");
+        }
+        let result = openvas_wait_for_task::openvas_wait_for_task(
+            &args.task_id,
+            Duration::from_secs(args.max_wait_secs),
+            Duration::from_secs(args.poll_interval_secs),
+        )
+        .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+}
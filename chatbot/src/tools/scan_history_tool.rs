@@ -0,0 +1,51 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::typed::TypedTool;
+use crate::scan_history;
+use crate::tool_error::ToolError;
+use crate::{ToolAnnotations, ToolCategory};
+
+/// Tool that lists the recorded nmap scans of a host (see `--scan-history`),
+/// so changes over time can be seen without scanning again.
+pub struct ScanHistoryTool;
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ScanHistoryArgs {
+    /// Host (as nmap reported it) or target as scanned, e.g. '10.0.0.5', 'web.example.com' or '10.0.0.0/24'.
+    target: String,
+    /// Most entries to return, newest first (1-500). A scan of a subnet has one entry per host. Default: 20
+    #[serde(default = "default_limit")]
+    limit: usize,
+    /// Include each scan's full output (raw and parsed). Default: false
+    #[serde(default)]
+    include_output: bool,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+#[async_trait::async_trait]
+impl TypedTool for ScanHistoryTool {
+    type Args = ScanHistoryArgs;
+
+    const NAME: &'static str = "scan_history";
+    const DESCRIPTION: &'static str = "Lists earlier nmap scans of a host or target, newest first: when, with which tool, the open ports and services found, and the ports opened and closed since the scan before. Needs --scan-history.";
+    const CATEGORY: ToolCategory = ToolCategory::Recon;
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    async fn run(&self, args: ScanHistoryArgs) -> Result<Value> {
+        if !(1..=500).contains(&args.limit) {
+            return Err(ToolError::invalid_input("limit must be between 1 and 500").into());
+        }
+        let scans = scan_history::list(&args.target, args.limit, args.include_output)?;
+        Ok(json!({ "target": args.target.trim(), "count": scans.len(), "scans": scans }))
+    }
+}
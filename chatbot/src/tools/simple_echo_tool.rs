@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use serde_json::Value;
 
-use crate::Tool;
+use crate::{Tool, ToolAnnotations, ToolCategory};
 
 /// Simple echo tool used mainly for testing the MCP plumbing.
 pub struct EchoTool;
@@ -16,6 +18,10 @@ impl Tool for EchoTool {
         "Echoes back the given JSON input."
     }
 
+    fn category(&self) -> ToolCategory {
+        ToolCategory::Admin
+    }
+
     fn input_schema(&self) -> serde_json::Value {
         serde_json::json!({
             "type": "object",
@@ -23,6 +29,14 @@ impl Tool for EchoTool {
         })
     }
 
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::READ_ONLY
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_secs(30))
+    }
+
     async fn execute(&self, input: Value) -> Result<Value> {
         Ok(serde_json::json!({ "echo": input }))
     }
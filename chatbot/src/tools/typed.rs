@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::api::gvm_backends;
+use crate::middleware::RetryPolicy;
+use crate::{Tool, ToolAnnotations, ToolCategory};
+
+/// Declarative alternative to implementing `Tool` by hand: the tool's input
+/// is a typed struct, and the MCP `inputSchema` is derived from it (doc
+/// comments become descriptions, `#[serde(default)]` values become
+/// defaults, `deny_unknown_fields` becomes `additionalProperties: false`).
+/// Every `TypedTool` is a `Tool` through the blanket impl below, so it is
+/// registered like any other tool.
+#[async_trait::async_trait]
+pub trait TypedTool: Send + Sync + 'static {
+    type Args: DeserializeOwned + JsonSchema + Send;
+
+    const NAME: &'static str;
+    const DESCRIPTION: &'static str;
+    const CATEGORY: ToolCategory;
+
+    fn version(&self) -> &'static str {
+        crate::DEFAULT_TOOL_VERSION
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        None
+    }
+
+    fn max_concurrency(&self) -> Option<usize> {
+        None
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        if TypedTool::annotations(self).read_only_hint {
+            RetryPolicy::READ_ONLY
+        } else {
+            RetryPolicy::NONE
+        }
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        false
+    }
+
+    /// The OpenVAS tools, named `openvas_*`, talk to gvmd.
+    fn selects_gvm_backend(&self) -> bool {
+        Self::NAME.starts_with("openvas_")
+    }
+
+    async fn run(&self, args: Self::Args) -> Result<Value>;
+}
+
+#[async_trait::async_trait]
+impl<T: TypedTool> Tool for T {
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        T::DESCRIPTION
+    }
+
+    fn category(&self) -> ToolCategory {
+        T::CATEGORY
+    }
+
+    fn version(&self) -> &'static str {
+        TypedTool::version(self)
+    }
+
+    fn input_schema(&self) -> Value {
+        let mut schema = schema_for::<T::Args>();
+        if TypedTool::supports_dry_run(self)
+            && let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut)
+        {
+            props.insert(
+                "dry_run".into(),
+                serde_json::json!({
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Return the backend request (and nmap command line) this call would send, without sending it"
+                }),
+            );
+        }
+        let gvm_backends = gvm_backends::names();
+        if TypedTool::selects_gvm_backend(self)
+            && !gvm_backends.is_empty()
+            && let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut)
+        {
+            props.insert(
+                "backend".into(),
+                serde_json::json!({
+                    "type": "string",
+                    "enum": gvm_backends,
+                    "description": "GVM backend (account) to send this call to. Default: the workspace's backend"
+                }),
+            );
+        }
+        schema
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        TypedTool::annotations(self)
+    }
+
+    fn default_timeout(&self) -> Option<Duration> {
+        TypedTool::default_timeout(self)
+    }
+
+    fn cache_ttl(&self) -> Option<Duration> {
+        TypedTool::cache_ttl(self)
+    }
+
+    fn max_concurrency(&self) -> Option<usize> {
+        TypedTool::max_concurrency(self)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        TypedTool::retry_policy(self)
+    }
+
+    fn supports_dry_run(&self) -> bool {
+        TypedTool::supports_dry_run(self)
+    }
+
+    fn selects_gvm_backend(&self) -> bool {
+        TypedTool::selects_gvm_backend(self)
+    }
+
+    async fn execute(&self, input: Value) -> Result<Value> {
+        // Clients may omit `input` entirely for tools without required fields.
+        let input = if input.is_null() { Value::Object(Default::default()) } else { input };
+        let args: T::Args = serde_json::from_value(input)
+            .map_err(|err| anyhow::anyhow!("invalid input for {}: {err}", T::NAME))?;
+        self.run(args).await
+    }
+}
+
+/// Self-contained JSON Schema for `A`, shaped like hand-written schemas: no
+/// `$schema`/`title`/`description`, sub-schemas inlined, no `null` for
+/// optional fields.
+fn schema_for<A: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|s| s.inline_subschemas = true)
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<A>().to_value();
+    // The tool's own description covers what the arguments struct is for.
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("$schema");
+        obj.remove("title");
+        obj.remove("description");
+    }
+    // Optional fields are simply not required, as in hand-written schemas.
+    if let Some(props) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        props.values_mut().for_each(strip_null);
+    }
+    schema
+}
+
+/// Turn `Option<T>`'s `T | null` back into plain `T`, here and in nested
+/// object properties.
+fn strip_null(prop: &mut Value) {
+    let Some(obj) = prop.as_object_mut() else {
+        return;
+    };
+    if let Some(Value::Array(types)) = obj.get_mut("type") {
+        types.retain(|t| t != "null");
+        if types.len() == 1 {
+            let only = types.remove(0);
+            obj.insert("type".into(), only);
+        }
+    }
+    if let Some(Value::Array(values)) = obj.get_mut("enum") {
+        values.retain(|v| !v.is_null());
+    }
+    if let Some(Value::Array(variants)) = obj.get("anyOf") {
+        let non_null: Vec<&Value> = variants
+            .iter()
+            .filter(|v| v.get("type") != Some(&"null".into()))
+            .collect();
+        if let [inner] = non_null[..]
+            && let Some(inner) = inner.as_object().cloned()
+        {
+            obj.remove("anyOf");
+            for (k, v) in inner {
+                obj.entry(k).or_insert(v);
+            }
+        }
+    }
+    // Nested objects (e.g. `stealth_options`) have optional fields too.
+    if let Some(props) = obj.get_mut("properties").and_then(Value::as_object_mut) {
+        props.values_mut().for_each(strip_null);
+    }
+}
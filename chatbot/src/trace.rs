@@ -0,0 +1,23 @@
+use std::future::Future;
+
+/// Header carrying the trace id on every backend call.
+pub const HEADER: &str = "X-Trace-Id";
+
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+/// Fresh trace id for one JSON-RPC request.
+pub fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Run `fut` with `id` as the current trace id.
+pub async fn scope<F: Future>(id: String, fut: F) -> F::Output {
+    CURRENT.scope(id, fut).await
+}
+
+/// Trace id of the request being handled, if any.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(|id| id.clone()).ok()
+}
@@ -0,0 +1,270 @@
+pub mod stdio;
+pub mod tcp;
+#[cfg(unix)]
+pub mod unix;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc;
+
+use crate::session::{self, Session};
+use crate::{handle_request, RpcRequest, ToolRegistry};
+
+/// Largest message accepted from a client, in bytes. A bigger
+/// `Content-Length` (or a longer line) ends the session instead of making
+/// the server allocate whatever the peer asks for.
+pub const MAX_FRAME_BYTES: usize = 4 * 1024 * 1024;
+
+/// How JSON-RPC messages are delimited on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Framing {
+    /// One JSON message per line (MCP stdio default).
+    #[default]
+    Lines,
+    /// LSP-style `Content-Length: N` header block followed by N bytes of JSON.
+    Headers,
+}
+
+impl Framing {
+    pub fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "lines" => Ok(Framing::Lines),
+            "headers" => Ok(Framing::Headers),
+            other => anyhow::bail!("unknown framing `{other}` (expected lines or headers)"),
+        }
+    }
+}
+
+/// Run one JSON-RPC session over any reader/writer pair.
+/// Every transport (stdio, TCP, ...) funnels into this loop, so all of them
+/// share the same dispatch logic and the same `ToolRegistry`.
+///
+/// Each request is dispatched on its own task so a long scan never blocks
+/// `tools/list` or other calls; responses are funnelled through a channel to
+/// a single writer task, which keeps output lines from interleaving.
+pub async fn serve_session<R, W>(
+    reader: R,
+    writer: W,
+    registry: Arc<ToolRegistry>,
+    transport: String,
+    framing: Framing,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let mut reader = reader;
+    let (out_tx, out_rx) = mpsc::unbounded_channel::<String>();
+    let writer_task = tokio::spawn(write_responses(writer, out_rx, framing));
+    let session = Session::new(transport, out_tx.clone());
+
+    while let Some(text) = read_message(&mut reader, framing).await? {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let Ok(message) = serde_json::from_str::<Value>(text) else {
+            continue;
+        };
+
+        // Messages without a method are responses to requests the server
+        // sent (e.g. elicitation); hand them to whoever is waiting.
+        if message.get("method").is_none() {
+            session.complete_request(message);
+            continue;
+        }
+
+        // Try to parse a request.
+        let req: RpcRequest = match serde_json::from_value(message) {
+            Ok(r) => r,
+            Err(_err) => {
+                // If we can't parse the incoming JSON at all, just ignore it.
+                // MCP clients can send various notifications; we don't want to
+                // emit malformed error responses that confuse the client.
+                continue;
+            }
+        };
+
+        // Notifications in MCP/JSON-RPC do not include an `id` and must not
+        // receive a response. Only handle messages with an ID as requests.
+        let Some(id) = req.id.clone() else {
+            continue;
+        };
+
+        // Handle the request concurrently and queue its response.
+        let registry = registry.clone();
+        let out_tx = out_tx.clone();
+        let session = session.clone();
+        tokio::spawn(async move {
+            let resp = session::scope(session, handle_request(registry, id, req)).await;
+            match serde_json::to_string(&resp) {
+                Ok(text) => {
+                    // The receiver only goes away if the writer failed, in
+                    // which case the session is over anyway.
+                    let _ = out_tx.send(text);
+                }
+                Err(err) => eprintln!("failed to serialize response: {err}"),
+            }
+        });
+    }
+
+    // Input is closed: let in-flight requests finish, then drain the writer.
+    session.close();
+    drop(out_tx);
+    writer_task.await?
+}
+
+/// Read the next message body, or `None` at end of input. Malformed or
+/// oversized frames are errors: the stream can't be resynchronised after
+/// them, so the session ends.
+async fn read_message<R>(reader: &mut R, framing: Framing) -> Result<Option<String>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = String::new();
+    match framing {
+        Framing::Lines => {
+            if read_bounded_line(reader, &mut line).await? == 0 {
+                return Ok(None);
+            }
+            Ok(Some(line))
+        }
+        Framing::Headers => {
+            let mut content_length = None;
+            let mut in_headers = false;
+            loop {
+                line.clear();
+                if read_bounded_line(reader, &mut line).await? == 0 {
+                    if in_headers {
+                        anyhow::bail!("input ended inside a message header block");
+                    }
+                    return Ok(None);
+                }
+                let header = line.trim_end_matches(['\r', '\n']);
+                if header.is_empty() {
+                    // Tolerate stray blank lines between messages.
+                    if !in_headers {
+                        continue;
+                    }
+                    break;
+                }
+                in_headers = true;
+                if let Some((name, value)) = header.split_once(':')
+                    && name.trim().eq_ignore_ascii_case("content-length")
+                {
+                    content_length = Some(value.trim().parse::<usize>().map_err(|_| {
+                        anyhow::anyhow!("invalid Content-Length header `{header}`")
+                    })?);
+                }
+            }
+
+            let Some(length) = content_length else {
+                anyhow::bail!("message header block has no Content-Length");
+            };
+            if length > MAX_FRAME_BYTES {
+                anyhow::bail!("message of {length} bytes exceeds the {MAX_FRAME_BYTES}-byte limit");
+            }
+            let mut body = vec![0u8; length];
+            reader.read_exact(&mut body).await?;
+            Ok(Some(String::from_utf8_lossy(&body).into_owned()))
+        }
+    }
+}
+
+/// `read_line` that refuses lines longer than `MAX_FRAME_BYTES`.
+async fn read_bounded_line<R>(reader: &mut R, line: &mut String) -> Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let n = (&mut *reader).take(MAX_FRAME_BYTES as u64 + 1).read_line(line).await?;
+    if n > MAX_FRAME_BYTES {
+        anyhow::bail!("line exceeds the {MAX_FRAME_BYTES}-byte message limit");
+    }
+    Ok(n)
+}
+
+/// Write queued responses to the client until every sender is dropped.
+async fn write_responses<W>(
+    writer: W,
+    mut out_rx: mpsc::UnboundedReceiver<String>,
+    framing: Framing,
+) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut writer = BufWriter::new(writer);
+    while let Some(text) = out_rx.recv().await {
+        match framing {
+            Framing::Lines => {
+                writer.write_all(text.as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+            }
+            Framing::Headers => {
+                writer
+                    .write_all(format!("Content-Length: {}\r\n\r\n", text.len()).as_bytes())
+                    .await?;
+                writer.write_all(text.as_bytes()).await?;
+            }
+        }
+        writer.flush().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read_all(mut input: &[u8], framing: Framing) -> Result<Vec<String>> {
+        let mut messages = Vec::new();
+        while let Some(message) = read_message(&mut input, framing).await? {
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    #[tokio::test]
+    async fn reads_header_framed_messages() {
+        let input = b"Content-Length: 2\r\n\r\n{}\r\ncontent-type: application/json\r\nCONTENT-LENGTH:  5 \r\n\r\n[1,2]";
+        assert_eq!(read_all(input, Framing::Headers).await.unwrap(), ["{}", "[1,2]"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_header() {
+        let err = read_all(b"Content-Type: application/json\r\n\r\n{}", Framing::Headers)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no Content-Length"));
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_header() {
+        for header in ["Content-Length: ten", "Content-Length: -1", "Content-Length:"] {
+            let input = format!("{header}\r\n\r\n{{}}");
+            let err = read_all(input.as_bytes(), Framing::Headers).await.unwrap_err();
+            assert!(err.to_string().contains("invalid Content-Length"), "{header}");
+        }
+        let err = read_all(b"Content-Length: 2\r\n", Framing::Headers).await.unwrap_err();
+        assert!(err.to_string().contains("header block"));
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_frames() {
+        let input = format!("Content-Length: {}\r\n\r\n", MAX_FRAME_BYTES + 1);
+        let err = read_all(input.as_bytes(), Framing::Headers).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+
+        let line = vec![b'x'; MAX_FRAME_BYTES + 1];
+        let err = read_all(&line, Framing::Lines).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+    }
+
+    #[tokio::test]
+    async fn reads_lines() {
+        assert_eq!(read_all(b"{}\n[]", Framing::Lines).await.unwrap(), ["{}\n", "[]"]);
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::io::{self, BufReader};
+
+use super::Framing;
+use crate::ToolRegistry;
+
+/// Serve a single JSON-RPC session over stdin/stdout (the default MCP mode),
+/// newline-delimited or with LSP-style `Content-Length` headers.
+pub async fn serve(registry: Arc<ToolRegistry>, framing: Framing) -> Result<()> {
+    super::serve_session(
+        BufReader::new(io::stdin()),
+        io::stdout(),
+        registry,
+        "stdio".to_string(),
+        framing,
+    )
+    .await
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+
+use super::Framing;
+use crate::ToolRegistry;
+
+/// Accept TCP connections on `addr` and run an independent JSON-RPC session
+/// for each one. All sessions share the same `ToolRegistry`, so several
+/// analysts can drive one hacker_agent instance concurrently.
+///
+/// Connections are only authenticated when `--auth-config` is set, and even
+/// then the tokens cross the wire in the clear, so only loopback addresses
+/// are accepted unless `--allow-remote` says otherwise.
+pub async fn serve(addr: &str, allow_remote: bool, registry: Arc<ToolRegistry>) -> Result<()> {
+    let addrs: Vec<_> = tokio::net::lookup_host(addr)
+        .await
+        .with_context(|| format!("cannot resolve --listen address `{addr}`"))?
+        .collect();
+    if !allow_remote && let Some(remote) = addrs.iter().find(|a| !a.ip().is_loopback()) {
+        anyhow::bail!(
+            "refusing to listen on non-loopback address {remote}: without --auth-config anyone who can \
+             reach it could run scans, and with it tokens are sent unencrypted; bind 127.0.0.1 or \
+             pass --allow-remote"
+        );
+    }
+    let listener = TcpListener::bind(&addrs[..]).await?;
+    // stdout is not the protocol channel in this mode, but keep diagnostics on
+    // stderr anyway so the output is consistent with stdio mode.
+    eprintln!("hacker_agent listening on {}", listener.local_addr()?);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let registry = registry.clone();
+
+        tokio::spawn(async move {
+            eprintln!("client connected: {peer}");
+            let (read_half, write_half) = stream.into_split();
+            let transport = format!("tcp:{peer}");
+            if let Err(err) = super::serve_session(
+                BufReader::new(read_half),
+                write_half,
+                registry,
+                transport,
+                Framing::Lines,
+            )
+            .await
+            {
+                eprintln!("session with {peer} ended with error: {err}");
+            }
+            eprintln!("client disconnected: {peer}");
+        });
+    }
+}
@@ -0,0 +1,45 @@
+use std::os::unix::fs::FileTypeExt;
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::io::BufReader;
+use tokio::net::UnixListener;
+
+use super::Framing;
+use crate::ToolRegistry;
+
+/// Serve JSON-RPC over a unix domain socket at `path`. Each connection gets
+/// its own session against the shared `ToolRegistry`, like the TCP transport.
+/// A stale socket file left behind by a previous run is removed first;
+/// anything else at `path` is left alone and reported as an error.
+pub async fn serve(path: &str, registry: Arc<ToolRegistry>) -> Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)?,
+        Ok(_) => anyhow::bail!("--unix-socket {path} exists and is not a socket; refusing to replace it"),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let listener = UnixListener::bind(path)?;
+    eprintln!("hacker_agent listening on unix socket {path}");
+
+    loop {
+        let (stream, _addr) = listener.accept().await?;
+        let registry = registry.clone();
+
+        tokio::spawn(async move {
+            let (read_half, write_half) = stream.into_split();
+            if let Err(err) = super::serve_session(
+                BufReader::new(read_half),
+                write_half,
+                registry,
+                "unix".to_string(),
+                Framing::Lines,
+            )
+            .await
+            {
+                eprintln!("unix socket session ended with error: {err}");
+            }
+        });
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// One way a tool input fails its `inputSchema`.
+#[derive(Debug, Serialize)]
+pub struct FieldError {
+    /// JSON pointer to the offending value (`/ports`, `/options/0`); empty
+    /// for the input object itself, e.g. a missing required field.
+    pub field: String,
+    pub message: String,
+}
+
+/// Compile a tool's input schema. A schema that does not compile is a bug in
+/// the tool; it is reported and the tool's input goes unchecked rather than
+/// making the tool unusable.
+pub fn compile(tool: &str, schema: &Value) -> Option<jsonschema::Validator> {
+    match jsonschema::validator_for(schema) {
+        Ok(validator) => Some(validator),
+        Err(err) => {
+            eprintln!("input schema of {tool} is invalid, its input will not be validated: {err}");
+            None
+        }
+    }
+}
+
+/// Every schema violation in `input`, in document order.
+pub fn check(validator: &jsonschema::Validator, input: &Value) -> Result<(), Vec<FieldError>> {
+    let errors: Vec<FieldError> = validator
+        .iter_errors(input)
+        .map(|err| FieldError {
+            field: err.instance_path().to_string(),
+            message: err.to_string(),
+        })
+        .collect();
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// One-line summary for the JSON-RPC error message; the full list goes in
+/// `error.data.errors`.
+pub fn summarize(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| {
+            if e.field.is_empty() {
+                e.message.clone()
+            } else {
+                format!("{}: {}", e.field, e.message)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::api::backend;
+use crate::{api, config, idle, middleware, session};
+
+/// gvmd statuses of a task that was started and has not ended yet.
+const ACTIVE_STATUSES: [&str; 4] = ["Requested", "Queued", "Running", "Resume Requested"];
+
+/// Resumes the watchdog tries per task before leaving it interrupted; a task
+/// that keeps getting interrupted needs someone to look at the scanner.
+const MAX_RESUMES: u32 = 3;
+
+/// What the watchdog did about an interrupted task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Resumed,
+    /// gvmd refused the resume; it is tried again next round.
+    Failed,
+    /// `MAX_RESUMES` were used up; the task is left interrupted.
+    GaveUp,
+    /// The task's target is denied or outside `--scope`; it is not resumed.
+    OutOfScope,
+}
+
+/// One recovery attempt, as notified.
+#[derive(Debug, Clone, Serialize)]
+pub struct Recovery {
+    pub task_id: String,
+    pub task_name: String,
+    pub outcome: Outcome,
+    /// Resumes tried for the task so far, this one included.
+    pub attempt: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// A started task being watched.
+#[derive(Debug, Default)]
+struct Watched {
+    resumes: u32,
+    gave_up: bool,
+}
+
+/// Tasks seen running, by task ID. Only tasks the watchdog saw under way are
+/// resumed: one already interrupted when it first looks is left alone, as
+/// nothing says it should still run.
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    watched: BTreeMap<String, Watched>,
+}
+
+impl Watchdog {
+    /// List gvmd's tasks once: start watching the ones under way, resume the
+    /// watched ones gvmd reports `Interrupted`, and forget the ones that
+    /// finished, were stopped or deleted.
+    pub async fn round(&mut self) -> Result<Vec<Recovery>> {
+        let tasks = backend::current().list_tasks().await?.tasks;
+        self.watched.retain(|id, _| tasks.iter().any(|t| t.id == *id));
+
+        let mut recoveries = Vec::new();
+        for task in tasks {
+            if ACTIVE_STATUSES.contains(&task.status.as_str()) {
+                self.watched.entry(task.id).or_default();
+                continue;
+            }
+            if task.status != "Interrupted" {
+                self.watched.remove(&task.id);
+                continue;
+            }
+            let Some(watched) = self.watched.get_mut(&task.id) else {
+                continue;
+            };
+            if watched.resumes >= MAX_RESUMES {
+                if !watched.gave_up {
+                    watched.gave_up = true;
+                    recoveries.push(Recovery {
+                        task_id: task.id,
+                        task_name: task.name,
+                        outcome: Outcome::GaveUp,
+                        attempt: watched.resumes,
+                        error: None,
+                        at: Utc::now(),
+                    });
+                }
+                continue;
+            }
+            if watched.gave_up {
+                continue;
+            }
+            if let Some((target, reason)) = middleware::refused_task(&task.id).await? {
+                watched.gave_up = true;
+                recoveries.push(Recovery {
+                    task_id: task.id,
+                    task_name: task.name,
+                    outcome: Outcome::OutOfScope,
+                    attempt: watched.resumes,
+                    error: Some(format!("{target} is {}", reason.replace('_', " "))),
+                    at: Utc::now(),
+                });
+                continue;
+            }
+            watched.resumes += 1;
+            let result = backend::current().resume_task(&task.id).await;
+            recoveries.push(Recovery {
+                outcome: if result.is_ok() { Outcome::Resumed } else { Outcome::Failed },
+                error: result.err().map(|err| format!("{err:#}")),
+                task_id: task.id,
+                task_name: task.name,
+                attempt: watched.resumes,
+                at: Utc::now(),
+            });
+        }
+        Ok(recoveries)
+    }
+}
+
+/// Check gvmd's tasks every `interval` and resume those interrupted while
+/// running, typically by a scanner restart; zero leaves the watchdog off.
+/// Each recovery is logged to stderr, sent to clients as a
+/// `notifications/message` and POSTed to `--monitor-webhook`. Rounds are
+/// skipped while the server is idle-suspended.
+pub fn spawn(interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut watchdog = Watchdog::default();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if idle::is_suspended() {
+                continue;
+            }
+            match watchdog.round().await {
+                Ok(recoveries) => {
+                    for recovery in recoveries {
+                        notify(&recovery).await;
+                    }
+                }
+                Err(err) => eprintln!("task watchdog could not list tasks: {err:#}"),
+            }
+        }
+    });
+}
+
+async fn notify(recovery: &Recovery) {
+    let text = match recovery.outcome {
+        Outcome::Resumed => format!(
+            "hacker_agent: resumed interrupted OpenVAS task {} ({}), attempt {} of {MAX_RESUMES}",
+            recovery.task_name, recovery.task_id, recovery.attempt
+        ),
+        Outcome::Failed => format!(
+            "hacker_agent: could not resume interrupted OpenVAS task {} ({}): {}",
+            recovery.task_name,
+            recovery.task_id,
+            recovery.error.as_deref().unwrap_or_default()
+        ),
+        Outcome::GaveUp => format!(
+            "hacker_agent: OpenVAS task {} ({}) was interrupted again after {MAX_RESUMES} resumes; leaving it \
+             interrupted, check the scanner",
+            recovery.task_name, recovery.task_id
+        ),
+        Outcome::OutOfScope => format!(
+            "hacker_agent: not resuming interrupted OpenVAS task {} ({}): {}",
+            recovery.task_name,
+            recovery.task_id,
+            recovery.error.as_deref().unwrap_or_default()
+        ),
+    };
+    eprintln!("{text}");
+    let level = if recovery.outcome == Outcome::Resumed { "notice" } else { "warning" };
+    session::log_all(level, json!({ "message": text, "watchdog": recovery }));
+
+    if let Err(err) = post(&text, recovery).await {
+        eprintln!("failed to deliver watchdog notification for task {}: {err}", recovery.task_id);
+    }
+}
+
+/// POST the recovery to `--monitor-webhook`, in the same Slack-compatible
+/// shape as monitor change notifications.
+async fn post(text: &str, recovery: &Recovery) -> Result<()> {
+    let Some(url) = config::current().monitor_webhook.clone() else {
+        return Ok(());
+    };
+    api::ensure_allowed(&url)?;
+    api::http_client()
+        .post(&url)
+        .json(&json!({ "text": text, "watchdog": recovery }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::api::backend::{with_backend, OpenvasBackend};
+    use crate::api::errors::BackendError;
+    use crate::api::mock::MockBackend;
+
+    #[tokio::test]
+    async fn interrupted_tasks_are_resumed_until_the_watchdog_gives_up() {
+        let mock = Arc::new(MockBackend::default().with_running_tasks());
+        let target = mock.create_target("web", "10.0.0.5", None, None).await.unwrap();
+        let watched = mock.create_task("long scan", "config", &target.id, None, None).await.unwrap();
+        mock.start_task(&watched.id).await.unwrap();
+
+        let rounds = with_backend(mock.clone(), async {
+            let mut watchdog = Watchdog::default();
+            let mut rounds = vec![watchdog.round().await.unwrap()];
+            // Interrupted before the watchdog ever saw it running: left alone.
+            let unseen = mock.create_task("unseen", "config", &target.id, None, None).await.unwrap();
+            mock.start_task(&unseen.id).await.unwrap();
+            mock.interrupt_running_tasks();
+            mock.fail_next(BackendError::from_response(503, "connection refused"));
+            assert!(watchdog.round().await.is_err());
+            for _ in 0..MAX_RESUMES + 1 {
+                mock.interrupt_running_tasks();
+                rounds.push(watchdog.round().await.unwrap());
+            }
+            rounds
+        })
+        .await;
+
+        assert!(rounds[0].is_empty());
+        let outcomes: Vec<(Outcome, u32)> = rounds[1..].iter().flatten().map(|r| (r.outcome, r.attempt)).collect();
+        assert_eq!(
+            outcomes,
+            [(Outcome::Resumed, 1), (Outcome::Resumed, 2), (Outcome::Resumed, 3), (Outcome::GaveUp, 3)]
+        );
+        assert!(rounds[1..].iter().flatten().all(|r| r.task_id == watched.id));
+        let status = mock.get_task_status(&watched.id).await.unwrap();
+        assert_eq!(status.status.as_deref(), Some("Interrupted"));
+    }
+}
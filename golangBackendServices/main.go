@@ -1,9 +1,11 @@
 package main
 
 import (
+	"crypto/subtle"
 	"encoding/json"
 	"log"
 	"net/http"
+	"os"
 	"os/exec"
 	"strings"
 
@@ -30,6 +32,60 @@ type scanRequest struct {
 	StealthOptions map[string]interface{} `json:"stealth_options,omitempty"`
 }
 
+// traceHeader carries the MCP server's per-request trace id so backend logs
+// can be correlated with the JSON-RPC call that caused them.
+const traceHeader = "X-Trace-Id"
+
+// withTraceID logs every request with its trace id and echoes the id back.
+func withTraceID(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		traceID := r.Header.Get(traceHeader)
+		if traceID != "" {
+			w.Header().Set(traceHeader, traceID)
+		}
+		log.Printf("[trace %s] %s %s", traceID, r.Method, r.URL.Path)
+		next.ServeHTTP(w, r)
+	})
+}
+
+// withAuth refuses requests without the bearer token in BACKEND_TOKEN or
+// the "user:password" basic auth credentials in BACKEND_BASIC_AUTH, when
+// either is set. With neither, anyone who can reach LISTEN_ADDR can run
+// scans, which is only acceptable on loopback.
+func withAuth(next http.Handler) http.Handler {
+	token := strings.TrimSpace(os.Getenv("BACKEND_TOKEN"))
+	basic := strings.TrimSpace(os.Getenv("BACKEND_BASIC_AUTH"))
+	if token == "" && basic == "" {
+		log.Printf("BACKEND_TOKEN and BACKEND_BASIC_AUTH are not set: requests are not authenticated")
+		return next
+	}
+	equal := func(got, want string) bool {
+		return subtle.ConstantTimeCompare([]byte(got), []byte(want)) == 1
+	}
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if token != "" {
+			got, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+			if ok && equal(strings.TrimSpace(got), token) {
+				next.ServeHTTP(w, r)
+				return
+			}
+		}
+		if basic != "" {
+			if user, password, ok := r.BasicAuth(); ok && equal(user+":"+password, basic) {
+				next.ServeHTTP(w, r)
+				return
+			}
+		}
+		log.Printf("[trace %s] rejected %s %s: missing or wrong backend credentials", r.Header.Get(traceHeader), r.Method, r.URL.Path)
+		if token != "" {
+			w.Header().Set("WWW-Authenticate", `Bearer realm="hacker_agent"`)
+		} else {
+			w.Header().Set("WWW-Authenticate", `Basic realm="hacker_agent"`)
+		}
+		http.Error(w, "missing or wrong backend credentials", http.StatusUnauthorized)
+	})
+}
+
 type scanResponse struct {
 	Target    string `json:"target"`
 	RawOutput string `json:"raw_output"`
@@ -114,6 +170,11 @@ func scanOpenPortsHandler(w http.ResponseWriter, r *http.Request) {
 		}
 		if format, exists := validFormats[req.OutputFormat]; exists {
 			cmdArgs = append(cmdArgs, format)
+			// Write XML and greppable output to stdout, so it is returned
+			// in raw_output rather than to a file named after the target.
+			if format == "-oX" || format == "-oG" {
+				cmdArgs = append(cmdArgs, "-")
+			}
 		}
 	}
 
@@ -152,7 +213,7 @@ func scanOpenPortsHandler(w http.ResponseWriter, r *http.Request) {
 	out, err := cmd.CombinedOutput()
 	if err != nil {
 		// Still return whatever output we got, plus the error text.
-		log.Printf("nmap error for target %s: %v", req.Target, err)
+		log.Printf("[trace %s] nmap error for target %s: %v", r.Header.Get(traceHeader), req.Target, err)
 	}
 
 	resp := scanResponse{
@@ -166,27 +227,80 @@ func scanOpenPortsHandler(w http.ResponseWriter, r *http.Request) {
 	}
 }
 
+// healthHandler answers the agent's periodic health probe. It only shows
+// that the backend is up; it does not check gvmd or docker, whose failures
+// are reported by the calls that need them.
+func healthHandler(w http.ResponseWriter, r *http.Request) {
+	if r.Method != http.MethodGet {
+		http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+		return
+	}
+	w.Header().Set("Content-Type", "application/json")
+	if err := json.NewEncoder(w).Encode(map[string]string{"status": "ok"}); err != nil {
+		log.Printf("[trace %s] failed to encode health response: %v", r.Header.Get(traceHeader), err)
+	}
+}
+
 func main() {
 	// Load environment variables from .env so OpenVAS auth/config
 	// is available without manually exporting each time.
 	_ = godotenv.Load(".env")
 
 	mux := http.NewServeMux()
+	mux.HandleFunc("/health", healthHandler)
 	mux.HandleFunc("/scan-open-ports", scanOpenPortsHandler)
 
 	// Modular OpenVAS APIs.
 	openVASService := NewOpenVASServiceFromEnv()
 	mux.Handle("/openvas/version", openVASVersionHandler(openVASService))
 	mux.Handle("/openvas/configs", openVASConfigsHandler(openVASService))
+	mux.Handle("/openvas/configs/clone", openVASCloneConfigHandler(openVASService))
+	mux.Handle("/openvas/configs/modify", openVASModifyConfigHandler(openVASService))
+	mux.Handle("/openvas/nvts", openVASGetNVTHandler(openVASService))
+	mux.Handle("/openvas/feeds", openVASListFeedsHandler(openVASService))
+	mux.Handle("/openvas/feeds/sync", openVASSyncFeedHandler(openVASService))
+	mux.Handle("/openvas/assets/hosts", openVASListHostAssetsHandler(openVASService))
+	mux.Handle("/openvas/tickets", openVASCreateTicketHandler(openVASService))
+	mux.Handle("/openvas/tickets/list", openVASListTicketsHandler(openVASService))
+	mux.Handle("/openvas/tickets/modify", openVASModifyTicketHandler(openVASService))
+	mux.Handle("/openvas/scanners", openVASListScannersHandler(openVASService))
 	mux.Handle("/openvas/targets", openVASCreateTargetHandler(openVASService))
+	mux.Handle("/openvas/targets/list", openVASListTargetsHandler(openVASService))
+	mux.Handle("/openvas/targets/delete", openVASDeleteTargetHandler(openVASService))
+	mux.Handle("/openvas/port-lists", openVASCreatePortListHandler(openVASService))
+	mux.Handle("/openvas/port-lists/list", openVASListPortListsHandler(openVASService))
+	mux.Handle("/openvas/credentials", openVASCreateCredentialHandler(openVASService))
+	mux.Handle("/openvas/credentials/list", openVASListCredentialsHandler(openVASService))
+	mux.Handle("/openvas/targets/credential", openVASAttachCredentialHandler(openVASService))
+	mux.Handle("/openvas/alerts", openVASCreateAlertHandler(openVASService))
 	mux.Handle("/openvas/tasks", openVASCreateTaskHandler(openVASService))
+	mux.Handle("/openvas/tasks/container", openVASCreateContainerTaskHandler(openVASService))
+	mux.Handle("/openvas/tasks/list", openVASListTasksHandler(openVASService))
+	mux.Handle("/openvas/tasks/delete", openVASDeleteTaskHandler(openVASService))
+	mux.Handle("/openvas/tasks/modify", openVASModifyTaskHandler(openVASService))
 	mux.Handle("/openvas/tasks/start", openVASStartTaskHandler(openVASService))
+	mux.Handle("/openvas/tasks/stop", openVASStopTaskHandler(openVASService))
+	mux.Handle("/openvas/tasks/resume", openVASResumeTaskHandler(openVASService))
 	mux.Handle("/openvas/tasks/status", openVASTaskStatusHandler(openVASService))
 	mux.Handle("/openvas/reports", openVASGetReportHandler(openVASService))
+	mux.Handle("/openvas/reports/list", openVASListReportsHandler(openVASService))
+	mux.Handle("/openvas/reports/delete", openVASDeleteReportHandler(openVASService))
+	mux.Handle("/openvas/reports/import", openVASImportReportHandler(openVASService))
+	mux.Handle("/openvas/results", openVASGetResultsHandler(openVASService))
+	mux.Handle("/openvas/schedules", openVASCreateScheduleHandler(openVASService))
+	mux.Handle("/openvas/schedules/list", openVASListSchedulesHandler(openVASService))
+	mux.Handle("/openvas/schedules/delete", openVASDeleteScheduleHandler(openVASService))
+	mux.Handle("/openvas/cleanup", openVASCleanupHandler(openVASService))
+	mux.Handle("/openvas/trash", openVASListTrashHandler(openVASService))
+	mux.Handle("/openvas/trash/empty", openVASEmptyTrashcanHandler(openVASService))
+	mux.Handle("/openvas/trash/restore", openVASRestoreHandler(openVASService))
 
-	addr := ":8080"
+	addr := os.Getenv("LISTEN_ADDR")
+	if addr == "" {
+		addr = ":8080"
+	}
 	log.Printf("Go backend listening on %s", addr)
-	if err := http.ListenAndServe(addr, mux); err != nil {
+	if err := http.ListenAndServe(addr, withTraceID(withAuth(mux))); err != nil {
 		log.Fatalf("server failed: %v", err)
 	}
 }
@@ -1,11 +1,14 @@
 package main
 
 import (
+	"encoding/base64"
 	"encoding/json"
 	"encoding/xml"
+	"errors"
 	"log"
 	"net/http"
 	"strings"
+	"time"
 )
 
 type openVASVersionResponse struct {
@@ -37,9 +40,10 @@ type openVASConfigXML struct {
 
 // openVASCreateTargetRequest is the JSON input for creating a new target.
 type openVASCreateTargetRequest struct {
-	Name      string `json:"name"`
-	Hosts     string `json:"hosts"`
-	PortRange string `json:"port_range,omitempty"`
+	Name       string `json:"name"`
+	Hosts      string `json:"hosts"`
+	PortRange  string `json:"port_range,omitempty"`
+	PortListID string `json:"port_list_id,omitempty"`
 }
 
 // openVASCreateTargetResponse is the JSON response returned when a target is
@@ -49,11 +53,66 @@ type openVASCreateTargetResponse struct {
 	Existed bool   `json:"existed,omitempty"`
 }
 
+// openVASListTargetsResponse wraps all existing targets in a stable JSON
+// shape.
+type openVASListTargetsResponse struct {
+	Targets []TargetSummary `json:"targets"`
+}
+
+// openVASDeleteRequest is the JSON input for deleting a target, task or
+// schedule. Without ultimate the object goes to the trashcan.
+type openVASDeleteRequest struct {
+	TargetID   string `json:"target_id,omitempty"`
+	TaskID     string `json:"task_id,omitempty"`
+	ScheduleID string `json:"schedule_id,omitempty"`
+	Ultimate   bool   `json:"ultimate"`
+}
+
+// openVASDeleteResponse wraps the raw XML response from gvmd when deleting an
+// object.
+type openVASDeleteResponse struct {
+	ID          string `json:"id"`
+	Ultimate    bool   `json:"ultimate"`
+	ResponseRaw string `json:"response_raw"`
+}
+
+// openVASDeleteReportRequest is the JSON input for deleting a report.
+type openVASDeleteReportRequest struct {
+	ReportID string `json:"report_id"`
+}
+
+// openVASRestoreRequest is the JSON input for restoring an object from the
+// trashcan.
+type openVASRestoreRequest struct {
+	ID string `json:"id"`
+}
+
+// openVASRestoreResponse wraps the raw XML response from gvmd when restoring
+// an object.
+type openVASRestoreResponse struct {
+	ID          string `json:"id"`
+	ResponseRaw string `json:"response_raw"`
+}
+
+// openVASTrashResponse wraps the objects in the trashcan in a stable JSON
+// shape.
+type openVASTrashResponse struct {
+	Objects []TrashObject `json:"objects"`
+}
+
+// openVASEmptyTrashcanResponse wraps the raw XML response from gvmd when
+// emptying the trashcan.
+type openVASEmptyTrashcanResponse struct {
+	ResponseRaw string `json:"response_raw"`
+}
+
 // openVASCreateTaskRequest is the JSON input for creating a new task.
 type openVASCreateTaskRequest struct {
-	Name     string `json:"name"`
-	ConfigID string `json:"config_id"`
-	TargetID string `json:"target_id"`
+	Name       string `json:"name"`
+	ConfigID   string `json:"config_id"`
+	TargetID   string `json:"target_id"`
+	ScheduleID string `json:"schedule_id,omitempty"`
+	ScannerID  string `json:"scanner_id,omitempty"`
 }
 
 // openVASCreateTaskResponse is the JSON response returned when a task is
@@ -63,13 +122,195 @@ type openVASCreateTaskResponse struct {
 	Existed bool   `json:"existed,omitempty"`
 }
 
-// openVASStartTaskRequest is the JSON input for starting an existing task.
+// openVASCreateContainerTaskRequest is the JSON input for creating a
+// container task.
+type openVASCreateContainerTaskRequest struct {
+	Name string `json:"name"`
+}
+
+// openVASImportReportRequest is the JSON input for importing a report into
+// a container task.
+type openVASImportReportRequest struct {
+	TaskID    string `json:"task_id"`
+	ReportXML string `json:"report_xml"`
+}
+
+// openVASImportReportResponse wraps the new report's ID and the raw XML
+// response from gvmd.
+type openVASImportReportResponse struct {
+	ReportID    string `json:"report_id"`
+	ResponseRaw string `json:"response_raw"`
+}
+
+// openVASCreateScheduleResponse is the JSON response returned when a
+// schedule is created.
+type openVASCreateScheduleResponse struct {
+	ID string `json:"id"`
+}
+
+// openVASCreateAlertResponse is the JSON response returned when an alert
+// is created.
+type openVASCreateAlertResponse struct {
+	ID string `json:"id"`
+}
+
+// openVASListSchedulesResponse wraps all existing schedules in a stable
+// JSON shape.
+type openVASListSchedulesResponse struct {
+	Schedules []ScheduleSummary `json:"schedules"`
+}
+
+// openVASCreateCredentialResponse is the JSON response returned when a
+// credential is created; it never echoes the secrets.
+type openVASCreateCredentialResponse struct {
+	ID string `json:"id"`
+}
+
+// openVASListCredentialsResponse wraps all existing credentials in a stable
+// JSON shape.
+type openVASListCredentialsResponse struct {
+	Credentials []CredentialSummary `json:"credentials"`
+}
+
+// openVASAttachCredentialRequest is the JSON input for attaching a
+// credential to a target. Kind is "ssh" or "smb".
+type openVASAttachCredentialRequest struct {
+	TargetID     string `json:"target_id"`
+	CredentialID string `json:"credential_id"`
+	Kind         string `json:"kind"`
+	Port         int    `json:"port,omitempty"`
+}
+
+// openVASAttachCredentialResponse wraps the raw XML response from gvmd when
+// attaching a credential.
+type openVASAttachCredentialResponse struct {
+	TargetID     string `json:"target_id"`
+	CredentialID string `json:"credential_id"`
+	Kind         string `json:"kind"`
+	ResponseRaw  string `json:"response_raw"`
+}
+
+// openVASCloneConfigRequest is the JSON input for copying a scan config.
+type openVASCloneConfigRequest struct {
+	ConfigID string `json:"config_id"`
+	Name     string `json:"name"`
+	Comment  string `json:"comment,omitempty"`
+}
+
+// openVASModifyConfigRequest is the JSON input for changing the NVT
+// selection of a scan config.
+type openVASModifyConfigRequest struct {
+	ConfigID string `json:"config_id"`
+	ConfigChanges
+}
+
+// openVASModifyConfigResponse lists the families selected in a scan config
+// after a change.
+type openVASModifyConfigResponse struct {
+	ConfigID string         `json:"config_id"`
+	Families []ConfigFamily `json:"families"`
+}
+
+// openVASGetNVTRequest is the JSON input for looking up an NVT.
+type openVASGetNVTRequest struct {
+	OID string `json:"oid"`
+}
+
+// openVASListFeedsResponse wraps the state of every feed in a stable JSON
+// shape.
+type openVASListFeedsResponse struct {
+	Feeds []FeedSummary `json:"feeds"`
+}
+
+// openVASSyncFeedRequest is the JSON input for syncing a feed.
+type openVASSyncFeedRequest struct {
+	Feed string `json:"feed"`
+}
+
+// openVASSyncFeedResponse carries the output of greenbone-feed-sync.
+type openVASSyncFeedResponse struct {
+	Feed   string `json:"feed"`
+	Output string `json:"output"`
+}
+
+// openVASListHostAssetsResponse wraps every host asset in a stable JSON
+// shape.
+type openVASListHostAssetsResponse struct {
+	Hosts []HostAsset `json:"hosts"`
+}
+
+// openVASCreateTicketRequest is the JSON input for opening a remediation
+// ticket for a result.
+type openVASCreateTicketRequest struct {
+	ResultID string `json:"result_id"`
+	Assignee string `json:"assignee"`
+	Note     string `json:"note"`
+}
+
+// openVASListTicketsResponse wraps every ticket in a stable JSON shape.
+type openVASListTicketsResponse struct {
+	Tickets []Ticket `json:"tickets"`
+}
+
+// openVASModifyTicketRequest is the JSON input for changing a ticket.
+type openVASModifyTicketRequest struct {
+	TicketID string `json:"ticket_id"`
+	TicketChanges
+}
+
+// openVASGetResultsRequest is the JSON input for fetching one page of
+// results.
+type openVASGetResultsRequest struct {
+	Filter string `json:"filter"`
+	First  int    `json:"first"`
+	Rows   int    `json:"rows"`
+}
+
+// openVASCreatePortListRequest is the JSON input for creating a port list.
+type openVASCreatePortListRequest struct {
+	Name      string `json:"name"`
+	PortRange string `json:"port_range"`
+	Comment   string `json:"comment,omitempty"`
+}
+
+// openVASCreatePortListResponse is the JSON response returned when a port
+// list is created.
+type openVASCreatePortListResponse struct {
+	ID string `json:"id"`
+}
+
+// openVASListScannersResponse wraps all existing scanners in a stable JSON
+// shape.
+type openVASListScannersResponse struct {
+	Scanners []ScannerSummary `json:"scanners"`
+}
+
+// openVASListPortListsResponse wraps all existing port lists in a stable
+// JSON shape.
+type openVASListPortListsResponse struct {
+	PortLists []PortListSummary `json:"port_lists"`
+}
+
+// openVASListTasksResponse wraps all existing tasks in a stable JSON shape.
+type openVASListTasksResponse struct {
+	Tasks []TaskSummary `json:"tasks"`
+}
+
+// openVASModifyTaskRequest is the JSON input for changing an existing task.
+type openVASModifyTaskRequest struct {
+	TaskID string `json:"task_id"`
+	TaskChanges
+}
+
+// openVASStartTaskRequest is the JSON input for starting an existing task;
+// stopping and resuming take the same input.
 type openVASStartTaskRequest struct {
 	TaskID string `json:"task_id"`
 }
 
 // openVASStartTaskResponse wraps the raw XML response from gvmd when starting
-// a task so that callers can inspect status details if needed.
+// (or stopping, resuming or changing) a task so that callers can inspect status
+// details if needed.
 type openVASStartTaskResponse struct {
 	TaskID      string `json:"task_id"`
 	ResponseRaw string `json:"response_raw"`
@@ -82,22 +323,64 @@ type openVASTaskStatusRequest struct {
 }
 
 // openVASTaskStatusResponse wraps the raw XML response from gvmd when querying
-// task status so that callers can inspect status details if needed.
+// task status so that callers can inspect status details if needed, along
+// with the parsed status, progress and last report ID for polling.
 type openVASTaskStatusResponse struct {
 	TaskID      string `json:"task_id"`
 	ResponseRaw string `json:"response_raw"`
+	*TaskState
 }
 
-// openVASGetReportRequest is the JSON input for fetching a final report by ID.
+// openVASGetReportRequest is the JSON input for fetching a final report by
+// ID. FormatID is a gvmd report format ID; empty means XML. MinSeverity
+// (CVSS) and MinQoD (0-100) drop results below them; zero keeps them.
 type openVASGetReportRequest struct {
-	ReportID string `json:"report_id"`
+	ReportID    string  `json:"report_id"`
+	FormatID    string  `json:"format_id,omitempty"`
+	MinSeverity float64 `json:"min_severity,omitempty"`
+	MinQoD      int     `json:"min_qod,omitempty"`
 }
 
 // openVASGetReportResponse wraps the raw XML response from gvmd when fetching
-// a report so that callers can inspect full vulnerability details.
+// a report so that callers can inspect full vulnerability details. Results
+// holds the parsed findings. For formats other than XML, ResponseRaw is
+// empty and the output is in Content when it is text, or in ContentBase64.
 type openVASGetReportResponse struct {
-	ReportID    string `json:"report_id"`
-	ResponseRaw string `json:"response_raw"`
+	ReportID      string         `json:"report_id"`
+	ResponseRaw   string         `json:"response_raw"`
+	Results       []ReportResult `json:"results,omitempty"`
+	ContentType   string         `json:"content_type,omitempty"`
+	Extension     string         `json:"extension,omitempty"`
+	Content       string         `json:"content,omitempty"`
+	ContentBase64 string         `json:"content_base64,omitempty"`
+}
+
+// openVASListReportsRequest is the JSON input for listing reports; an empty
+// TaskID lists the reports of every task.
+type openVASListReportsRequest struct {
+	TaskID string `json:"task_id,omitempty"`
+}
+
+// openVASListReportsResponse wraps report summaries, newest first.
+type openVASListReportsResponse struct {
+	Reports []ReportSummary `json:"reports"`
+}
+
+// openVASCleanupRequest is the JSON input for trashcan and housekeeping.
+type openVASCleanupRequest struct {
+	NamePattern   string `json:"name_pattern"`
+	RetentionDays int    `json:"retention_days"`
+	EmptyTrashcan bool   `json:"empty_trashcan"`
+	Apply         bool   `json:"apply"`
+}
+
+// serviceError logs a failed service call and answers 500 with msg followed
+// by the underlying error, so clients see gvm-cli's and gvmd's own text
+// (e.g. "Authentication failed") and can tell configuration problems from
+// transient ones.
+func serviceError(w http.ResponseWriter, r *http.Request, msg string, err error) {
+	log.Printf("[trace %s] %s: %v", r.Header.Get(traceHeader), msg, err)
+	http.Error(w, msg+": "+err.Error(), http.StatusInternalServerError)
 }
 
 // openVASVersionHandler is a modular HTTP handler that uses OpenVASService
@@ -111,8 +394,7 @@ func openVASVersionHandler(svc *OpenVASService) http.Handler {
 
 		versionXML, err := svc.GetVersion(r.Context())
 		if err != nil {
-			log.Printf("failed to get OpenVAS version: %v", err)
-			http.Error(w, "failed to get OpenVAS version", http.StatusInternalServerError)
+			serviceError(w, r, "failed to get OpenVAS version", err)
 			return
 		}
 
@@ -120,7 +402,7 @@ func openVASVersionHandler(svc *OpenVASService) http.Handler {
 		if err := json.NewEncoder(w).Encode(openVASVersionResponse{
 			VersionRaw: versionXML,
 		}); err != nil {
-			log.Printf("failed to encode OpenVAS version response: %v", err)
+			log.Printf("[trace %s] failed to encode OpenVAS version response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
@@ -136,15 +418,13 @@ func openVASConfigsHandler(svc *OpenVASService) http.Handler {
 
 		configsXML, err := svc.GetConfigs(r.Context())
 		if err != nil {
-			log.Printf("failed to get OpenVAS configs: %v", err)
-			http.Error(w, "failed to get OpenVAS configs", http.StatusInternalServerError)
+			serviceError(w, r, "failed to get OpenVAS configs", err)
 			return
 		}
 
 		var parsed openVASGetConfigsXML
 		if err := xml.Unmarshal([]byte(configsXML), &parsed); err != nil {
-			log.Printf("failed to parse OpenVAS configs XML: %v", err)
-			http.Error(w, "failed to parse OpenVAS configs", http.StatusInternalServerError)
+			serviceError(w, r, "failed to parse OpenVAS configs", err)
 			return
 		}
 
@@ -162,203 +442,1372 @@ func openVASConfigsHandler(svc *OpenVASService) http.Handler {
 
 		w.Header().Set("Content-Type", "application/json")
 		if err := json.NewEncoder(w).Encode(resp); err != nil {
-			log.Printf("failed to encode OpenVAS configs response: %v", err)
+			log.Printf("[trace %s] failed to encode OpenVAS configs response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
 
-// openVASCreateTargetHandler creates a new OpenVAS/GVM target in an
-// idempotent way. If a target with the same name and hosts already exists,
-// it returns that existing target ID instead of failing.
-func openVASCreateTargetHandler(svc *OpenVASService) http.Handler {
+// openVASCloneConfigHandler copies an existing OpenVAS/GVM scan config under
+// a new name, so it can be tailored with /openvas/configs/modify.
+func openVASCloneConfigHandler(svc *OpenVASService) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodPost {
 			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
 			return
 		}
 
-		var req openVASCreateTargetRequest
+		var req openVASCloneConfigRequest
 		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
 			http.Error(w, "invalid JSON body", http.StatusBadRequest)
 			return
 		}
 
+		req.ConfigID = strings.TrimSpace(req.ConfigID)
 		req.Name = strings.TrimSpace(req.Name)
-		req.Hosts = strings.TrimSpace(req.Hosts)
-		req.PortRange = strings.TrimSpace(req.PortRange)
+		if req.ConfigID == "" || req.Name == "" {
+			http.Error(w, "config_id and name are required", http.StatusBadRequest)
+			return
+		}
 
-		if req.Name == "" || req.Hosts == "" {
-			http.Error(w, "name and hosts are required", http.StatusBadRequest)
+		id, err := svc.CloneConfig(r.Context(), req.ConfigID, req.Name, req.Comment)
+		if err != nil {
+			serviceError(w, r, "failed to clone OpenVAS config", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreatePortListResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS clone config response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASModifyConfigHandler enables or disables NVT families and single
+// NVTs in an existing OpenVAS/GVM scan config.
+func openVASModifyConfigHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASModifyConfigRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.ConfigID = strings.TrimSpace(req.ConfigID)
+		if req.ConfigID == "" {
+			http.Error(w, "config_id is required", http.StatusBadRequest)
 			return
 		}
 
-		id, existed, err := svc.CreateTarget(r.Context(), req.Name, req.Hosts, req.PortRange)
+		families, err := svc.ModifyConfig(r.Context(), req.ConfigID, req.ConfigChanges)
 		if err != nil {
-			log.Printf("failed to create OpenVAS target: %v", err)
-			http.Error(w, "failed to create OpenVAS target", http.StatusInternalServerError)
+			serviceError(w, r, "failed to modify OpenVAS config", err)
 			return
 		}
 
 		w.Header().Set("Content-Type", "application/json")
-		if err := json.NewEncoder(w).Encode(openVASCreateTargetResponse{
-			ID:      id,
-			Existed: existed,
+		if err := json.NewEncoder(w).Encode(openVASModifyConfigResponse{
+			ConfigID: req.ConfigID,
+			Families: families,
 		}); err != nil {
-			log.Printf("failed to encode OpenVAS create target response: %v", err)
+			log.Printf("[trace %s] failed to encode OpenVAS modify config response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
 
-// openVASCreateTaskHandler creates a new OpenVAS/GVM task in an idempotent
-// way. If a task with the same name, config ID and target ID already exists,
-// it returns that existing task ID instead of failing.
-func openVASCreateTaskHandler(svc *OpenVASService) http.Handler {
+// openVASGetNVTHandler returns the description, solution, CVSS and
+// references of one NVT by OID.
+func openVASGetNVTHandler(svc *OpenVASService) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodPost {
 			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
 			return
 		}
 
-		var req openVASCreateTaskRequest
+		var req openVASGetNVTRequest
 		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
 			http.Error(w, "invalid JSON body", http.StatusBadRequest)
 			return
 		}
 
-		req.Name = strings.TrimSpace(req.Name)
-		req.ConfigID = strings.TrimSpace(req.ConfigID)
-		req.TargetID = strings.TrimSpace(req.TargetID)
+		req.OID = strings.TrimSpace(req.OID)
+		if req.OID == "" {
+			http.Error(w, "oid is required", http.StatusBadRequest)
+			return
+		}
 
-		if req.Name == "" || req.ConfigID == "" || req.TargetID == "" {
-			http.Error(w, "name, config_id and target_id are required", http.StatusBadRequest)
+		details, err := svc.GetNVT(r.Context(), req.OID)
+		if err != nil {
+			serviceError(w, r, "failed to get OpenVAS NVT", err)
 			return
 		}
 
-		id, existed, err := svc.CreateTask(r.Context(), req.Name, req.ConfigID, req.TargetID)
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(details); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS NVT response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListFeedsHandler reports the version and sync state of every
+// OpenVAS/GVM feed.
+func openVASListFeedsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		feeds, err := svc.GetFeeds(r.Context())
 		if err != nil {
-			log.Printf("failed to create OpenVAS task: %v", err)
-			http.Error(w, "failed to create OpenVAS task", http.StatusInternalServerError)
+			serviceError(w, r, "failed to get OpenVAS feeds", err)
 			return
 		}
 
 		w.Header().Set("Content-Type", "application/json")
-		if err := json.NewEncoder(w).Encode(openVASCreateTaskResponse{
-			ID:      id,
-			Existed: existed,
-		}); err != nil {
-			log.Printf("failed to encode OpenVAS create task response: %v", err)
+		if err := json.NewEncoder(w).Encode(openVASListFeedsResponse{Feeds: feeds}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS feeds response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
 
-// openVASStartTaskHandler starts an existing OpenVAS/GVM task by ID.
-func openVASStartTaskHandler(svc *OpenVASService) http.Handler {
+// openVASSyncFeedHandler syncs one OpenVAS/GVM feed, or all of them, with
+// greenbone-feed-sync.
+func openVASSyncFeedHandler(svc *OpenVASService) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodPost {
 			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
 			return
 		}
 
-		var req openVASStartTaskRequest
+		var req openVASSyncFeedRequest
 		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
 			http.Error(w, "invalid JSON body", http.StatusBadRequest)
 			return
 		}
 
-		req.TaskID = strings.TrimSpace(req.TaskID)
-		if req.TaskID == "" {
-			http.Error(w, "task_id is required", http.StatusBadRequest)
+		req.Feed = strings.TrimSpace(req.Feed)
+		if req.Feed == "" {
+			req.Feed = "all"
+		}
+		if _, ok := feedSyncTypes[req.Feed]; !ok {
+			http.Error(w, "feed must be one of nvt, scap, cert, gvmd_data or all", http.StatusBadRequest)
 			return
 		}
 
-		raw, err := svc.StartTask(r.Context(), req.TaskID)
+		out, err := svc.SyncFeed(r.Context(), req.Feed)
 		if err != nil {
-			log.Printf("failed to start OpenVAS task: %v", err)
-			http.Error(w, "failed to start OpenVAS task", http.StatusInternalServerError)
+			serviceError(w, r, "failed to sync OpenVAS feed", err)
 			return
 		}
 
 		w.Header().Set("Content-Type", "application/json")
-		if err := json.NewEncoder(w).Encode(openVASStartTaskResponse{
-			TaskID:      req.TaskID,
-			ResponseRaw: raw,
-		}); err != nil {
-			log.Printf("failed to encode OpenVAS start task response: %v", err)
+		if err := json.NewEncoder(w).Encode(openVASSyncFeedResponse{Feed: req.Feed, Output: out}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS sync feed response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
 
-// openVASTaskStatusHandler fetches the current status/details for an existing
-// OpenVAS/GVM task by ID.
-func openVASTaskStatusHandler(svc *OpenVASService) http.Handler {
+// openVASListHostAssetsHandler lists the hosts in OpenVAS/GVM's asset
+// database.
+func openVASListHostAssetsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		hosts, err := svc.ListHostAssets(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS host assets", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListHostAssetsResponse{Hosts: hosts}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS host assets response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateTicketHandler opens an OpenVAS/GVM remediation ticket for a
+// result, assigned to a gvmd user.
+func openVASCreateTicketHandler(svc *OpenVASService) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodPost {
 			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
 			return
 		}
 
-		var req openVASTaskStatusRequest
+		var req openVASCreateTicketRequest
 		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
 			http.Error(w, "invalid JSON body", http.StatusBadRequest)
 			return
 		}
 
-		req.TaskID = strings.TrimSpace(req.TaskID)
-		if req.TaskID == "" {
-			http.Error(w, "task_id is required", http.StatusBadRequest)
+		req.ResultID = strings.TrimSpace(req.ResultID)
+		req.Assignee = strings.TrimSpace(req.Assignee)
+		req.Note = strings.TrimSpace(req.Note)
+		if req.ResultID == "" || req.Assignee == "" || req.Note == "" {
+			http.Error(w, "result_id, assignee and note are required", http.StatusBadRequest)
 			return
 		}
 
-		raw, err := svc.GetTaskStatus(r.Context(), req.TaskID)
+		id, err := svc.CreateTicket(r.Context(), req.ResultID, req.Assignee, req.Note)
 		if err != nil {
-			log.Printf("failed to get OpenVAS task status: %v", err)
-			http.Error(w, "failed to get OpenVAS task status", http.StatusInternalServerError)
+			serviceError(w, r, "failed to create OpenVAS ticket", err)
 			return
 		}
 
 		w.Header().Set("Content-Type", "application/json")
-		if err := json.NewEncoder(w).Encode(openVASTaskStatusResponse{
-			TaskID:      req.TaskID,
-			ResponseRaw: raw,
-		}); err != nil {
-			log.Printf("failed to encode OpenVAS task status response: %v", err)
+		if err := json.NewEncoder(w).Encode(openVASCreatePortListResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create ticket response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
 
-// openVASGetReportHandler fetches the final report for a given report ID.
-func openVASGetReportHandler(svc *OpenVASService) http.Handler {
+// openVASListTicketsHandler lists every OpenVAS/GVM remediation ticket.
+func openVASListTicketsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		tickets, err := svc.ListTickets(r.Context(), "")
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS tickets", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListTicketsResponse{Tickets: tickets}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list tickets response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASModifyTicketHandler changes the status, with a note, or the
+// assignee of an OpenVAS/GVM remediation ticket.
+func openVASModifyTicketHandler(svc *OpenVASService) http.Handler {
 	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		if r.Method != http.MethodPost {
 			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
 			return
 		}
 
-		var req openVASGetReportRequest
+		var req openVASModifyTicketRequest
 		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
 			http.Error(w, "invalid JSON body", http.StatusBadRequest)
 			return
 		}
 
-		req.ReportID = strings.TrimSpace(req.ReportID)
-		if req.ReportID == "" {
-			http.Error(w, "report_id is required", http.StatusBadRequest)
+		req.TicketID = strings.TrimSpace(req.TicketID)
+		req.Status = strings.TrimSpace(req.Status)
+		req.Note = strings.TrimSpace(req.Note)
+		if req.TicketID == "" {
+			http.Error(w, "ticket_id is required", http.StatusBadRequest)
+			return
+		}
+		if _, ok := ticketStatuses[req.Status]; req.Status != "" && !ok {
+			http.Error(w, "status must be Open, Fixed or Closed", http.StatusBadRequest)
+			return
+		}
+		if req.Status != "" && req.Note == "" {
+			http.Error(w, "note is required when changing status", http.StatusBadRequest)
+			return
+		}
+
+		ticket, err := svc.ModifyTicket(r.Context(), req.TicketID, req.TicketChanges)
+		if err != nil {
+			serviceError(w, r, "failed to modify OpenVAS ticket", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(ticket); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS modify ticket response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASGetResultsHandler returns one page of OpenVAS/GVM results across
+// reports, selected by a gvmd filter string.
+func openVASGetResultsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASGetResultsRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Filter = strings.TrimSpace(req.Filter)
+		if req.First < 1 || req.Rows < 1 {
+			http.Error(w, "first and rows must be at least 1", http.StatusBadRequest)
+			return
+		}
+
+		page, err := svc.GetResults(r.Context(), req.Filter, req.First, req.Rows)
+		if err != nil {
+			serviceError(w, r, "failed to get OpenVAS results", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(page); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS get results response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateTargetHandler creates a new OpenVAS/GVM target in an
+// idempotent way. If a target with the same name, hosts and ports already
+// exists, it returns that existing target ID instead of failing; one with
+// the same name but other hosts or ports is a 409.
+func openVASCreateTargetHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASCreateTargetRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.Hosts = strings.TrimSpace(req.Hosts)
+		req.PortRange = strings.TrimSpace(req.PortRange)
+		req.PortListID = strings.TrimSpace(req.PortListID)
+
+		if req.Name == "" || req.Hosts == "" {
+			http.Error(w, "name and hosts are required", http.StatusBadRequest)
+			return
+		}
+		if req.PortRange != "" && req.PortListID != "" {
+			http.Error(w, "port_range and port_list_id are exclusive", http.StatusBadRequest)
+			return
+		}
+
+		id, existed, err := svc.CreateTarget(r.Context(), req.Name, req.Hosts, req.PortRange, req.PortListID)
+		if errors.Is(err, ErrTargetConflict) {
+			http.Error(w, err.Error(), http.StatusConflict)
+			return
+		}
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS target", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateTargetResponse{
+			ID:      id,
+			Existed: existed,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create target response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListTargetsHandler returns every existing OpenVAS/GVM target, so
+// callers can reuse one instead of creating a duplicate.
+func openVASListTargetsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		targets, err := svc.ListTargets(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS targets", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListTargetsResponse{
+			Targets: targets,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list targets response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASDeleteTargetHandler deletes (or trashes) an OpenVAS/GVM target by
+// ID.
+func openVASDeleteTargetHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASDeleteRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TargetID = strings.TrimSpace(req.TargetID)
+		if req.TargetID == "" {
+			http.Error(w, "target_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.DeleteTarget(r.Context(), req.TargetID, req.Ultimate)
+		if err != nil {
+			serviceError(w, r, "failed to delete OpenVAS target", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASDeleteResponse{
+			ID:          req.TargetID,
+			Ultimate:    req.Ultimate,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS delete target response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateScheduleHandler creates an OpenVAS/GVM schedule from an
+// iCalendar event.
+func openVASCreateScheduleHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req ScheduleSpec
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.ICalendar = strings.TrimSpace(req.ICalendar)
+		if req.Name == "" || req.ICalendar == "" {
+			http.Error(w, "name and icalendar are required", http.StatusBadRequest)
+			return
+		}
+
+		id, err := svc.CreateSchedule(r.Context(), req)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS schedule", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateScheduleResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create schedule response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateAlertHandler creates an OpenVAS/GVM alert.
+func openVASCreateAlertHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req AlertSpec
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.Status = strings.TrimSpace(req.Status)
+		if req.Name == "" || req.Status == "" {
+			http.Error(w, "name and status are required", http.StatusBadRequest)
+			return
+		}
+		if !alertMethods[req.Method] {
+			http.Error(w, "method must be \"Email\" or \"HTTP Get\"", http.StatusBadRequest)
+			return
+		}
+
+		id, err := svc.CreateAlert(r.Context(), req)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS alert", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateAlertResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create alert response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListSchedulesHandler lists every existing OpenVAS/GVM schedule.
+func openVASListSchedulesHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		schedules, err := svc.ListSchedules(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS schedules", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListSchedulesResponse{
+			Schedules: schedules,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list schedules response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASDeleteScheduleHandler deletes (or trashes) an OpenVAS/GVM schedule
+// by ID.
+func openVASDeleteScheduleHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASDeleteRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.ScheduleID = strings.TrimSpace(req.ScheduleID)
+		if req.ScheduleID == "" {
+			http.Error(w, "schedule_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.DeleteSchedule(r.Context(), req.ScheduleID, req.Ultimate)
+		if err != nil {
+			serviceError(w, r, "failed to delete OpenVAS schedule", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASDeleteResponse{
+			ID:          req.ScheduleID,
+			Ultimate:    req.Ultimate,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS delete schedule response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreatePortListHandler creates an OpenVAS/GVM port list.
+func openVASCreatePortListHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASCreatePortListRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.PortRange = strings.TrimSpace(req.PortRange)
+		if req.Name == "" || req.PortRange == "" {
+			http.Error(w, "name and port_range are required", http.StatusBadRequest)
+			return
+		}
+
+		id, err := svc.CreatePortList(r.Context(), req.Name, req.PortRange, req.Comment)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS port list", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreatePortListResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create port list response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListPortListsHandler lists every existing OpenVAS/GVM port list.
+func openVASListPortListsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		portLists, err := svc.ListPortLists(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS port lists", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListPortListsResponse{
+			PortLists: portLists,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list port lists response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListScannersHandler lists every existing OpenVAS/GVM scanner.
+func openVASListScannersHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		scanners, err := svc.ListScanners(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS scanners", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListScannersResponse{
+			Scanners: scanners,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list scanners response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateCredentialHandler creates an OpenVAS/GVM credential for
+// authenticated scans.
+func openVASCreateCredentialHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req CredentialSpec
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.Login = strings.TrimSpace(req.Login)
+		req.Type = strings.TrimSpace(req.Type)
+		if req.Name == "" || req.Login == "" || req.Type == "" {
+			http.Error(w, "name, type and login are required", http.StatusBadRequest)
+			return
+		}
+
+		id, err := svc.CreateCredential(r.Context(), req)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS credential", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateCredentialResponse{ID: id}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create credential response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListCredentialsHandler lists every existing OpenVAS/GVM
+// credential, without secrets.
+func openVASListCredentialsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		credentials, err := svc.ListCredentials(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS credentials", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListCredentialsResponse{
+			Credentials: credentials,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list credentials response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASAttachCredentialHandler sets the SSH or SMB credential of an
+// OpenVAS/GVM target.
+func openVASAttachCredentialHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASAttachCredentialRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TargetID = strings.TrimSpace(req.TargetID)
+		req.CredentialID = strings.TrimSpace(req.CredentialID)
+		req.Kind = strings.TrimSpace(req.Kind)
+		if req.TargetID == "" || req.CredentialID == "" || req.Kind == "" {
+			http.Error(w, "target_id, credential_id and kind are required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.AttachCredential(r.Context(), req.TargetID, req.CredentialID, req.Kind, req.Port)
+		if err != nil {
+			serviceError(w, r, "failed to attach OpenVAS credential", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASAttachCredentialResponse{
+			TargetID:     req.TargetID,
+			CredentialID: req.CredentialID,
+			Kind:         req.Kind,
+			ResponseRaw:  raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS attach credential response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateTaskHandler creates a new OpenVAS/GVM task in an idempotent
+// way. If a task with the same name, config ID and target ID already exists,
+// it returns that existing task ID instead of failing.
+func openVASCreateTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASCreateTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		req.ConfigID = strings.TrimSpace(req.ConfigID)
+		req.TargetID = strings.TrimSpace(req.TargetID)
+
+		if req.Name == "" || req.ConfigID == "" || req.TargetID == "" {
+			http.Error(w, "name, config_id and target_id are required", http.StatusBadRequest)
+			return
+		}
+
+		id, existed, err := svc.CreateTask(r.Context(), req.Name, req.ConfigID, req.TargetID, req.ScheduleID, req.ScannerID)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateTaskResponse{
+			ID:      id,
+			Existed: existed,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCreateContainerTaskHandler creates an OpenVAS/GVM container task
+// for imported reports, reusing an existing one with the same name.
+func openVASCreateContainerTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASCreateContainerTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.Name = strings.TrimSpace(req.Name)
+		if req.Name == "" {
+			http.Error(w, "name is required", http.StatusBadRequest)
+			return
+		}
+
+		id, existed, err := svc.CreateContainerTask(r.Context(), req.Name)
+		if err != nil {
+			serviceError(w, r, "failed to create OpenVAS container task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASCreateTaskResponse{
+			ID:      id,
+			Existed: existed,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS create container task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListTasksHandler returns every existing OpenVAS/GVM task with its
+// status, so callers can pick up scans started earlier.
+func openVASListTasksHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		tasks, err := svc.ListTasks(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS tasks", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListTasksResponse{
+			Tasks: tasks,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list tasks response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASDeleteTaskHandler deletes (or trashes) an OpenVAS/GVM task by ID.
+func openVASDeleteTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASDeleteRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.DeleteTask(r.Context(), req.TaskID, req.Ultimate)
+		if err != nil {
+			serviceError(w, r, "failed to delete OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASDeleteResponse{
+			ID:          req.TaskID,
+			Ultimate:    req.Ultimate,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS delete task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASModifyTaskHandler changes the name, scan config, target, schedule or
+// scanner preferences of an existing OpenVAS/GVM task.
+func openVASModifyTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASModifyTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.ModifyTask(r.Context(), req.TaskID, req.TaskChanges)
+		if err != nil {
+			serviceError(w, r, "failed to modify OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASStartTaskResponse{
+			TaskID:      req.TaskID,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS modify task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASStartTaskHandler starts an existing OpenVAS/GVM task by ID.
+func openVASStartTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASStartTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.StartTask(r.Context(), req.TaskID)
+		if err != nil {
+			serviceError(w, r, "failed to start OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASStartTaskResponse{
+			TaskID:      req.TaskID,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS start task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASStopTaskHandler stops a running OpenVAS/GVM task by ID.
+func openVASStopTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASStartTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.StopTask(r.Context(), req.TaskID)
+		if err != nil {
+			serviceError(w, r, "failed to stop OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASStartTaskResponse{
+			TaskID:      req.TaskID,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS stop task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASResumeTaskHandler resumes a stopped or interrupted OpenVAS/GVM task
+// by ID.
+func openVASResumeTaskHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASStartTaskRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.ResumeTask(r.Context(), req.TaskID)
+		if err != nil {
+			serviceError(w, r, "failed to resume OpenVAS task", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASStartTaskResponse{
+			TaskID:      req.TaskID,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS resume task response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASTaskStatusHandler fetches the current status/details for an existing
+// OpenVAS/GVM task by ID.
+func openVASTaskStatusHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASTaskStatusRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" {
+			http.Error(w, "task_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.GetTaskStatus(r.Context(), req.TaskID)
+		if err != nil {
+			serviceError(w, r, "failed to get OpenVAS task status", err)
+			return
+		}
+
+		// Parsed fields are a convenience; the raw XML is still returned when
+		// gvmd's response can't be parsed.
+		state, err := ParseTaskState(raw)
+		if err != nil {
+			log.Printf("[trace %s] %v", r.Header.Get(traceHeader), err)
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASTaskStatusResponse{
+			TaskID:      req.TaskID,
+			ResponseRaw: raw,
+			TaskState:   state,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS task status response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASGetReportHandler fetches the final report for a given report ID.
+func openVASGetReportHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASGetReportRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.ReportID = strings.TrimSpace(req.ReportID)
+		if req.ReportID == "" {
+			http.Error(w, "report_id is required", http.StatusBadRequest)
+			return
+		}
+
+		filter := ReportFilter{MinSeverity: req.MinSeverity, MinQoD: req.MinQoD}
+		raw, err := svc.GetReport(r.Context(), req.ReportID, req.FormatID, filter)
+		if err != nil {
+			serviceError(w, r, "failed to get OpenVAS report", err)
+			return
+		}
+
+		resp := openVASGetReportResponse{ReportID: req.ReportID}
+		content, err := ParseReportContent(raw)
+		if err != nil {
+			log.Printf("[trace %s] %v", r.Header.Get(traceHeader), err)
+		}
+		resp.ContentType = content.ContentType
+		resp.Extension = content.Extension
+		switch {
+		case content.IsXML():
+			resp.ResponseRaw = raw
+			if resp.Results, err = ParseReportResults(raw); err != nil {
+				log.Printf("[trace %s] %v", r.Header.Get(traceHeader), err)
+			}
+		case content.IsText():
+			decoded, err := base64.StdEncoding.DecodeString(content.Base64)
+			if err != nil {
+				serviceError(w, r, "failed to decode OpenVAS report", err)
+				return
+			}
+			resp.Content = string(decoded)
+		default:
+			resp.ContentBase64 = content.Base64
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(resp); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS get report response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListReportsHandler lists reports with their timestamps and
+// severity counts, optionally only those of one task.
+func openVASListReportsHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASListReportsRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		reports, err := svc.ListReports(r.Context(), req.TaskID)
+		if err != nil {
+			serviceError(w, r, "failed to list OpenVAS reports", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASListReportsResponse{
+			Reports: reports,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS list reports response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASCleanupHandler deletes agent-created tasks and targets older than a
+// retention window and optionally empties the trashcan.
+func openVASCleanupHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASCleanupRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.NamePattern = strings.TrimSpace(req.NamePattern)
+		if req.NamePattern == "" {
+			http.Error(w, "name_pattern is required", http.StatusBadRequest)
+			return
+		}
+		if req.RetentionDays < 0 {
+			http.Error(w, "retention_days must not be negative", http.StatusBadRequest)
+			return
+		}
+
+		retention := time.Duration(req.RetentionDays) * 24 * time.Hour
+		result, err := svc.Cleanup(r.Context(), req.NamePattern, retention, req.EmptyTrashcan, req.Apply)
+		if err != nil {
+			serviceError(w, r, "failed to clean up OpenVAS objects", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(result); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS cleanup response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASDeleteReportHandler deletes an OpenVAS/GVM report by ID. Reports
+// are deleted outright; there is no trashcan for them.
+func openVASDeleteReportHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASDeleteReportRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.ReportID = strings.TrimSpace(req.ReportID)
+		if req.ReportID == "" {
+			http.Error(w, "report_id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.DeleteReport(r.Context(), req.ReportID)
+		if err != nil {
+			serviceError(w, r, "failed to delete OpenVAS report", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASDeleteResponse{
+			ID:          req.ReportID,
+			Ultimate:    true,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS delete report response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASListTrashHandler lists the objects in the OpenVAS/GVM trashcan.
+func openVASListTrashHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodGet {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		objects, err := svc.ListTrash(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to list the OpenVAS trashcan", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASTrashResponse{
+			Objects: objects,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS trash response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASEmptyTrashcanHandler deletes everything in the OpenVAS/GVM
+// trashcan for good.
+func openVASEmptyTrashcanHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		raw, err := svc.EmptyTrashcan(r.Context())
+		if err != nil {
+			serviceError(w, r, "failed to empty the OpenVAS trashcan", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASEmptyTrashcanResponse{
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS empty trashcan response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASRestoreHandler moves an object out of the OpenVAS/GVM trashcan.
+func openVASRestoreHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASRestoreRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.ID = strings.TrimSpace(req.ID)
+		if req.ID == "" {
+			http.Error(w, "id is required", http.StatusBadRequest)
+			return
+		}
+
+		raw, err := svc.RestoreObject(r.Context(), req.ID)
+		if err != nil {
+			serviceError(w, r, "failed to restore OpenVAS object", err)
+			return
+		}
+
+		w.Header().Set("Content-Type", "application/json")
+		if err := json.NewEncoder(w).Encode(openVASRestoreResponse{
+			ID:          req.ID,
+			ResponseRaw: raw,
+		}); err != nil {
+			log.Printf("[trace %s] failed to encode OpenVAS restore response: %v", r.Header.Get(traceHeader), err)
+		}
+	})
+}
+
+// openVASImportReportHandler imports a GVM-format report into an
+// OpenVAS/GVM container task.
+func openVASImportReportHandler(svc *OpenVASService) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Method != http.MethodPost {
+			http.Error(w, "method not allowed", http.StatusMethodNotAllowed)
+			return
+		}
+
+		var req openVASImportReportRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			http.Error(w, "invalid JSON body", http.StatusBadRequest)
+			return
+		}
+
+		req.TaskID = strings.TrimSpace(req.TaskID)
+		if req.TaskID == "" || strings.TrimSpace(req.ReportXML) == "" {
+			http.Error(w, "task_id and report_xml are required", http.StatusBadRequest)
 			return
 		}
 
-		raw, err := svc.GetReport(r.Context(), req.ReportID)
+		reportID, raw, err := svc.ImportReport(r.Context(), req.TaskID, req.ReportXML)
 		if err != nil {
-			log.Printf("failed to get OpenVAS report: %v", err)
-			http.Error(w, "failed to get OpenVAS report", http.StatusInternalServerError)
+			serviceError(w, r, "failed to import OpenVAS report", err)
 			return
 		}
 
 		w.Header().Set("Content-Type", "application/json")
-		if err := json.NewEncoder(w).Encode(openVASGetReportResponse{
-			ReportID:    req.ReportID,
+		if err := json.NewEncoder(w).Encode(openVASImportReportResponse{
+			ReportID:    reportID,
 			ResponseRaw: raw,
 		}); err != nil {
-			log.Printf("failed to encode OpenVAS get report response: %v", err)
+			log.Printf("[trace %s] failed to encode OpenVAS import report response: %v", r.Header.Get(traceHeader), err)
 		}
 	})
 }
@@ -3,10 +3,15 @@ package main
 import (
 	"context"
 	"encoding/xml"
+	"errors"
 	"fmt"
 	"os"
 	"os/exec"
+	"path"
+	"sort"
+	"strconv"
 	"strings"
+	"time"
 )
 
 // OpenVASService encapsulates calls to gvm-cli (OpenVAS/GVM).
@@ -119,6 +124,823 @@ func (s *OpenVASService) GetConfigs(ctx context.Context) (string, error) {
 	return string(out), nil
 }
 
+// CloneConfig copies an existing scan config via <create_config><copy> and
+// returns the new config's ID. Predefined configs cannot be changed, so
+// tailored configs start as a clone of one.
+func (s *OpenVASService) CloneConfig(ctx context.Context, configID, name, comment string) (string, error) {
+	configID = strings.TrimSpace(configID)
+	name = strings.TrimSpace(name)
+	if configID == "" || name == "" {
+		return "", fmt.Errorf("configID and name are required")
+	}
+
+	type createConfigXML struct {
+		XMLName xml.Name `xml:"create_config"`
+		Copy    string   `xml:"copy"`
+		Name    string   `xml:"name"`
+		Comment string   `xml:"comment,omitempty"`
+	}
+	xmlBody, err := xml.Marshal(&createConfigXML{
+		Copy:    configID,
+		Name:    name,
+		Comment: strings.TrimSpace(comment),
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_config XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+
+	type createConfigResponseXML struct {
+		XMLName xml.Name `xml:"create_config_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createConfigResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_config_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty config id in create_config_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// ConfigChanges lists what ModifyConfig changes in a scan config. Families
+// are gvmd's NVT family names, e.g. "Denial of Service"; NVTs are OIDs.
+// Enabled families take every NVT of the family, including ones added by
+// later feed updates.
+type ConfigChanges struct {
+	EnableFamilies  []string `json:"enable_families,omitempty"`
+	DisableFamilies []string `json:"disable_families,omitempty"`
+	EnableNVTs      []string `json:"enable_nvts,omitempty"`
+	DisableNVTs     []string `json:"disable_nvts,omitempty"`
+}
+
+// ConfigFamily is one NVT family selected in a scan config.
+type ConfigFamily struct {
+	Name        string `json:"name"`
+	NVTCount    int    `json:"nvt_count"`
+	MaxNVTCount int    `json:"max_nvt_count"`
+	Growing     bool   `json:"growing"`
+}
+
+// internal XML structs for the family selection of a scan config.
+type configFamiliesXML struct {
+	Config struct {
+		FamilyCount struct {
+			Growing int `xml:"growing"`
+		} `xml:"family_count"`
+		Families []struct {
+			Name        string `xml:"name"`
+			NVTCount    int    `xml:"nvt_count"`
+			MaxNVTCount int    `xml:"max_nvt_count"`
+			Growing     int    `xml:"growing"`
+		} `xml:"families>family"`
+	} `xml:"config"`
+}
+
+type nvtsXML struct {
+	NVTs []struct {
+		OID    string `xml:"oid,attr"`
+		Family string `xml:"family"`
+	} `xml:"nvt"`
+}
+
+// configFamilies returns the families selected in a scan config, and
+// whether families added by feed updates join it.
+func (s *OpenVASService) configFamilies(ctx context.Context, configID string) (bool, []ConfigFamily, error) {
+	type getConfigsXML struct {
+		XMLName  xml.Name `xml:"get_configs"`
+		ConfigID string   `xml:"config_id,attr"`
+		Families int      `xml:"families,attr"`
+	}
+	xmlBody, err := xml.Marshal(&getConfigsXML{ConfigID: configID, Families: 1})
+	if err != nil {
+		return false, nil, fmt.Errorf("failed to marshal get_configs XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return false, nil, err
+	}
+	var parsed configFamiliesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return false, nil, fmt.Errorf("failed to parse get_configs response XML: %w", err)
+	}
+	families := make([]ConfigFamily, 0, len(parsed.Config.Families))
+	for _, f := range parsed.Config.Families {
+		families = append(families, ConfigFamily{
+			Name:        strings.TrimSpace(f.Name),
+			NVTCount:    f.NVTCount,
+			MaxNVTCount: f.MaxNVTCount,
+			Growing:     f.Growing != 0,
+		})
+	}
+	return parsed.Config.FamilyCount.Growing != 0, families, nil
+}
+
+// getNVTs runs <get_nvts> with the given attributes.
+func (s *OpenVASService) getNVTs(ctx context.Context, attrs []xml.Attr) (nvtsXML, error) {
+	var parsed nvtsXML
+	xmlBody, err := xml.Marshal(&struct {
+		XMLName xml.Name   `xml:"get_nvts"`
+		Attrs   []xml.Attr `xml:",any,attr"`
+	}{Attrs: attrs})
+	if err != nil {
+		return parsed, fmt.Errorf("failed to marshal get_nvts XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return parsed, err
+	}
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return parsed, fmt.Errorf("failed to parse get_nvts response XML: %w", err)
+	}
+	return parsed, nil
+}
+
+// ModifyConfig enables and disables NVT families and single NVTs in a scan
+// config and returns the families selected afterwards. gvmd replaces the
+// whole family selection, and the whole NVT selection of a family, on each
+// <modify_config>, so the current selection is read first and sent back
+// with the changes applied. Predefined configs are refused by gvmd.
+func (s *OpenVASService) ModifyConfig(ctx context.Context, configID string, changes ConfigChanges) ([]ConfigFamily, error) {
+	configID = strings.TrimSpace(configID)
+	if configID == "" {
+		return nil, fmt.Errorf("configID is required")
+	}
+
+	type familyXML struct {
+		Name    string `xml:"name"`
+		All     int    `xml:"all"`
+		Growing int    `xml:"growing"`
+	}
+	type nvtXML struct {
+		OID string `xml:"oid,attr"`
+	}
+	type familySelectionXML struct {
+		Growing  int         `xml:"growing"`
+		Families []familyXML `xml:"family"`
+	}
+	type nvtSelectionXML struct {
+		Family string   `xml:"family"`
+		NVTs   []nvtXML `xml:"nvt"`
+	}
+	type modifyConfigXML struct {
+		XMLName         xml.Name            `xml:"modify_config"`
+		ConfigID        string              `xml:"config_id,attr"`
+		FamilySelection *familySelectionXML `xml:"family_selection"`
+		NVTSelection    *nvtSelectionXML    `xml:"nvt_selection"`
+	}
+	modify := func(payload modifyConfigXML) error {
+		payload.ConfigID = configID
+		xmlBody, err := xml.Marshal(&payload)
+		if err != nil {
+			return fmt.Errorf("failed to marshal modify_config XML: %w", err)
+		}
+		_, err = s.runGMP(ctx, string(xmlBody))
+		return err
+	}
+	boolInt := func(b bool) int {
+		if b {
+			return 1
+		}
+		return 0
+	}
+
+	if len(changes.EnableFamilies) > 0 || len(changes.DisableFamilies) > 0 {
+		growing, current, err := s.configFamilies(ctx, configID)
+		if err != nil {
+			return nil, err
+		}
+		disabled := map[string]bool{}
+		for _, name := range changes.DisableFamilies {
+			disabled[strings.TrimSpace(name)] = true
+		}
+		selected := map[string]familyXML{}
+		for _, f := range current {
+			if f.NVTCount > 0 && !disabled[f.Name] {
+				selected[f.Name] = familyXML{Name: f.Name, All: boolInt(f.NVTCount == f.MaxNVTCount), Growing: boolInt(f.Growing)}
+			}
+		}
+		for _, name := range changes.EnableFamilies {
+			name = strings.TrimSpace(name)
+			selected[name] = familyXML{Name: name, All: 1, Growing: 1}
+		}
+		var payload modifyConfigXML
+		payload.FamilySelection = &familySelectionXML{Growing: boolInt(growing)}
+		for _, f := range selected {
+			payload.FamilySelection.Families = append(payload.FamilySelection.Families, f)
+		}
+		sort.Slice(payload.FamilySelection.Families, func(i, j int) bool {
+			return payload.FamilySelection.Families[i].Name < payload.FamilySelection.Families[j].Name
+		})
+		if err := modify(payload); err != nil {
+			return nil, err
+		}
+	}
+
+	// NVT selections are per family: group the OIDs by their family.
+	type nvtChange struct {
+		oid    string
+		enable bool
+	}
+	byFamily := map[string][]nvtChange{}
+	for _, list := range []struct {
+		oids   []string
+		enable bool
+	}{{changes.EnableNVTs, true}, {changes.DisableNVTs, false}} {
+		for _, oid := range list.oids {
+			oid = strings.TrimSpace(oid)
+			found, err := s.getNVTs(ctx, []xml.Attr{{Name: xml.Name{Local: "nvt_oid"}, Value: oid}})
+			if err != nil {
+				return nil, err
+			}
+			if len(found.NVTs) == 0 {
+				return nil, fmt.Errorf("failed to find NVT '%s'", oid)
+			}
+			family := strings.TrimSpace(found.NVTs[0].Family)
+			byFamily[family] = append(byFamily[family], nvtChange{oid: oid, enable: list.enable})
+		}
+	}
+	families := make([]string, 0, len(byFamily))
+	for family := range byFamily {
+		families = append(families, family)
+	}
+	sort.Strings(families)
+	for _, family := range families {
+		current, err := s.getNVTs(ctx, []xml.Attr{
+			{Name: xml.Name{Local: "config_id"}, Value: configID},
+			{Name: xml.Name{Local: "family"}, Value: family},
+		})
+		if err != nil {
+			return nil, err
+		}
+		selected := map[string]bool{}
+		for _, n := range current.NVTs {
+			selected[n.OID] = true
+		}
+		for _, c := range byFamily[family] {
+			selected[c.oid] = c.enable
+		}
+		var payload modifyConfigXML
+		payload.NVTSelection = &nvtSelectionXML{Family: family}
+		for oid, on := range selected {
+			if on {
+				payload.NVTSelection.NVTs = append(payload.NVTSelection.NVTs, nvtXML{OID: oid})
+			}
+		}
+		sort.Slice(payload.NVTSelection.NVTs, func(i, j int) bool {
+			return payload.NVTSelection.NVTs[i].OID < payload.NVTSelection.NVTs[j].OID
+		})
+		if err := modify(payload); err != nil {
+			return nil, err
+		}
+	}
+
+	_, result, err := s.configFamilies(ctx, configID)
+	return result, err
+}
+
+// NVTReference is one reference of an NVT: a CVE, CERT advisory or URL.
+type NVTReference struct {
+	Type string `json:"type"`
+	ID   string `json:"id"`
+}
+
+// NVTDetails is what gvmd knows about one NVT. The description fields come
+// from the NVT's tags; SolutionType is VendorFix, Workaround, Mitigation,
+// NoneAvailable or WillNotFix.
+type NVTDetails struct {
+	OID          string         `json:"oid"`
+	Name         string         `json:"name"`
+	Family       string         `json:"family,omitempty"`
+	CVSSBase     *float64       `json:"cvss_base,omitempty"`
+	CVSSVector   string         `json:"cvss_vector,omitempty"`
+	Summary      string         `json:"summary,omitempty"`
+	Insight      string         `json:"insight,omitempty"`
+	Affected     string         `json:"affected,omitempty"`
+	Impact       string         `json:"impact,omitempty"`
+	Detection    string         `json:"detection,omitempty"`
+	Solution     string         `json:"solution,omitempty"`
+	SolutionType string         `json:"solution_type,omitempty"`
+	References   []NVTReference `json:"references"`
+}
+
+// internal XML structs for the fields of <get_nvts_response details='1'>
+// that GetNVT reports.
+type nvtDetailsXML struct {
+	NVTs []struct {
+		OID        string `xml:"oid,attr"`
+		Name       string `xml:"name"`
+		Family     string `xml:"family"`
+		CVSSBase   string `xml:"cvss_base"`
+		Tags       string `xml:"tags"`
+		Severities struct {
+			Severity []struct {
+				Type  string `xml:"type,attr"`
+				Value string `xml:"value"`
+				Score string `xml:"score"`
+			} `xml:"severity"`
+		} `xml:"severities"`
+		Solution struct {
+			Type string `xml:"type,attr"`
+			Text string `xml:",chardata"`
+		} `xml:"solution"`
+		Refs []struct {
+			Type string `xml:"type,attr"`
+			ID   string `xml:"id,attr"`
+		} `xml:"refs>ref"`
+	} `xml:"nvt"`
+}
+
+// GetNVT returns the details of the NVT with the given OID, e.g. the
+// nvt_oid of a report finding.
+func (s *OpenVASService) GetNVT(ctx context.Context, oid string) (NVTDetails, error) {
+	oid = strings.TrimSpace(oid)
+	if oid == "" {
+		return NVTDetails{}, fmt.Errorf("oid is required")
+	}
+	xmlBody, err := xml.Marshal(&struct {
+		XMLName xml.Name `xml:"get_nvts"`
+		OID     string   `xml:"nvt_oid,attr"`
+		Details int      `xml:"details,attr"`
+	}{OID: oid, Details: 1})
+	if err != nil {
+		return NVTDetails{}, fmt.Errorf("failed to marshal get_nvts XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return NVTDetails{}, err
+	}
+	var parsed nvtDetailsXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return NVTDetails{}, fmt.Errorf("failed to parse get_nvts response XML: %w", err)
+	}
+	if len(parsed.NVTs) == 0 {
+		return NVTDetails{}, fmt.Errorf("failed to find NVT '%s'", oid)
+	}
+	n := parsed.NVTs[0]
+
+	// Tags are "key=value" pairs separated by "|".
+	tags := map[string]string{}
+	for _, tag := range strings.Split(n.Tags, "|") {
+		if key, value, ok := strings.Cut(tag, "="); ok {
+			tags[strings.TrimSpace(key)] = strings.TrimSpace(value)
+		}
+	}
+	details := NVTDetails{
+		OID:          n.OID,
+		Name:         strings.TrimSpace(n.Name),
+		Family:       strings.TrimSpace(n.Family),
+		Summary:      tags["summary"],
+		Insight:      tags["insight"],
+		Affected:     tags["affected"],
+		Impact:       tags["impact"],
+		Detection:    tags["vuldetect"],
+		Solution:     strings.TrimSpace(n.Solution.Text),
+		SolutionType: strings.TrimSpace(n.Solution.Type),
+		References:   make([]NVTReference, 0, len(n.Refs)),
+	}
+	if details.Solution == "" {
+		details.Solution = tags["solution"]
+	}
+	if details.SolutionType == "" {
+		details.SolutionType = tags["solution_type"]
+	}
+	// Prefer the newest CVSS version gvmd has a vector for.
+	score := strings.TrimSpace(n.CVSSBase)
+	for _, sev := range n.Severities.Severity {
+		if strings.HasPrefix(sev.Type, "cvss_base") {
+			details.CVSSVector = strings.TrimSpace(sev.Value)
+			if sevScore := strings.TrimSpace(sev.Score); sevScore != "" {
+				score = sevScore
+			}
+		}
+	}
+	if details.CVSSVector == "" {
+		details.CVSSVector = tags["cvss_base_vector"]
+	}
+	if v, err := strconv.ParseFloat(score, 64); err == nil {
+		details.CVSSBase = &v
+	}
+	for _, ref := range n.Refs {
+		details.References = append(details.References, NVTReference{
+			Type: strings.ToLower(strings.TrimSpace(ref.Type)),
+			ID:   strings.TrimSpace(ref.ID),
+		})
+	}
+	return details, nil
+}
+
+// FeedSummary is the state of one GVM feed. Version is the feed's
+// timestamp, e.g. "202401250530" (UTC, minute precision).
+type FeedSummary struct {
+	Type             string `json:"type"`
+	Name             string `json:"name"`
+	Version          string `json:"version"`
+	Description      string `json:"description,omitempty"`
+	CurrentlySyncing bool   `json:"currently_syncing"`
+}
+
+// internal XML structs for the fields of <get_feeds_response> that
+// GetFeeds reports.
+type feedsXML struct {
+	Feeds []struct {
+		Type             string    `xml:"type"`
+		Name             string    `xml:"name"`
+		Version          string    `xml:"version"`
+		Description      string    `xml:"description"`
+		CurrentlySyncing *struct{} `xml:"currently_syncing"`
+	} `xml:"feed"`
+}
+
+// GetFeeds returns the NVT, SCAP, CERT and GVMD_DATA feeds gvmd has loaded.
+func (s *OpenVASService) GetFeeds(ctx context.Context) ([]FeedSummary, error) {
+	out, err := s.runGMP(ctx, "<get_feeds/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed feedsXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_feeds response XML: %w", err)
+	}
+	feeds := make([]FeedSummary, 0, len(parsed.Feeds))
+	for _, f := range parsed.Feeds {
+		feeds = append(feeds, FeedSummary{
+			Type:             strings.TrimSpace(f.Type),
+			Name:             strings.TrimSpace(f.Name),
+			Version:          strings.TrimSpace(f.Version),
+			Description:      strings.TrimSpace(f.Description),
+			CurrentlySyncing: f.CurrentlySyncing != nil,
+		})
+	}
+	return feeds, nil
+}
+
+// feedSyncTypes maps the feeds SyncFeed accepts to greenbone-feed-sync's
+// --type values.
+var feedSyncTypes = map[string]string{
+	"nvt":       "nvt",
+	"scap":      "scap",
+	"cert":      "cert",
+	"gvmd_data": "gvmd-data",
+	"all":       "all",
+}
+
+// SyncFeed runs greenbone-feed-sync in the gvmd container for one feed, or
+// all of them, and returns its output. gvmd picks the new data up on its
+// own once the files are in place; GMP itself has no sync command.
+func (s *OpenVASService) SyncFeed(ctx context.Context, feed string) (string, error) {
+	syncType, ok := feedSyncTypes[strings.TrimSpace(feed)]
+	if !ok {
+		return "", fmt.Errorf("unsupported feed %q", feed)
+	}
+	cmd := exec.CommandContext(ctx, "docker", "exec", s.ContainerName, "greenbone-feed-sync", "--type", syncType)
+	out, err := cmd.CombinedOutput()
+	if err != nil {
+		return "", fmt.Errorf("greenbone-feed-sync --type %s failed: %w; output: %s", syncType, err, string(out))
+	}
+	return string(out), nil
+}
+
+// HostAsset is one host in gvmd's asset database, built from the reports
+// that saw it. Severity is the highest of its latest report; ReportIDs are
+// the reports it appears in, oldest first as gvmd lists them.
+type HostAsset struct {
+	ID        string            `json:"id"`
+	IP        string            `json:"ip"`
+	Hostnames []string          `json:"hostnames,omitempty"`
+	OS        string            `json:"os,omitempty"`
+	OSCPE     string            `json:"os_cpe,omitempty"`
+	Severity  *float64          `json:"severity,omitempty"`
+	FirstSeen string            `json:"first_seen,omitempty"`
+	LastSeen  string            `json:"last_seen,omitempty"`
+	ReportIDs []string          `json:"report_ids,omitempty"`
+	Details   map[string]string `json:"details,omitempty"`
+}
+
+// internal XML structs for the fields of <get_assets_response> that
+// ListHostAssets reports.
+type hostAssetsXML struct {
+	Assets []struct {
+		ID               string `xml:"id,attr"`
+		Name             string `xml:"name"`
+		CreationTime     string `xml:"creation_time"`
+		ModificationTime string `xml:"modification_time"`
+		Identifiers      []struct {
+			Name   string `xml:"name"`
+			Value  string `xml:"value"`
+			Source struct {
+				ID   string `xml:"id,attr"`
+				Type string `xml:"type"`
+			} `xml:"source"`
+		} `xml:"identifiers>identifier"`
+		Host struct {
+			Severity string `xml:"severity>value"`
+			Details  []struct {
+				Name  string `xml:"name"`
+				Value string `xml:"value"`
+			} `xml:"detail"`
+		} `xml:"host"`
+	} `xml:"asset"`
+}
+
+// ListHostAssets returns every host asset gvmd knows, with its details.
+func (s *OpenVASService) ListHostAssets(ctx context.Context) ([]HostAsset, error) {
+	out, err := s.runGMP(ctx, "<get_assets type='host' details='1' filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed hostAssetsXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_assets response XML: %w", err)
+	}
+	hosts := make([]HostAsset, 0, len(parsed.Assets))
+	for _, a := range parsed.Assets {
+		host := HostAsset{
+			ID:        a.ID,
+			IP:        strings.TrimSpace(a.Name),
+			FirstSeen: strings.TrimSpace(a.CreationTime),
+			LastSeen:  strings.TrimSpace(a.ModificationTime),
+			Details:   map[string]string{},
+		}
+		if v, err := strconv.ParseFloat(strings.TrimSpace(a.Host.Severity), 64); err == nil {
+			host.Severity = &v
+		}
+		for _, d := range a.Host.Details {
+			host.Details[strings.TrimSpace(d.Name)] = strings.TrimSpace(d.Value)
+		}
+		host.OS = host.Details["best_os_txt"]
+		host.OSCPE = host.Details["best_os_cpe"]
+		seen := map[string]bool{}
+		for _, ident := range a.Identifiers {
+			value := strings.TrimSpace(ident.Value)
+			switch strings.TrimSpace(ident.Name) {
+			case "hostname", "DNS-via-TargetDefinition":
+				if value != "" && !seen["host:"+value] {
+					seen["host:"+value] = true
+					host.Hostnames = append(host.Hostnames, value)
+				}
+			}
+			id := strings.TrimSpace(ident.Source.ID)
+			if strings.HasPrefix(ident.Source.Type, "Report") && id != "" && !seen["report:"+id] {
+				seen["report:"+id] = true
+				host.ReportIDs = append(host.ReportIDs, id)
+			}
+		}
+		hosts = append(hosts, host)
+	}
+	return hosts, nil
+}
+
+// Ticket is one GVM remediation ticket: a result assigned to a user, with
+// its status (Open, Fixed, Fix Verified or Closed) and the note left at
+// each status change. gvmd sets Fix Verified itself once a later report of
+// the task no longer has the result.
+type Ticket struct {
+	ID              string   `json:"id"`
+	Name            string   `json:"name"`
+	Status          string   `json:"status"`
+	Assignee        string   `json:"assignee,omitempty"`
+	Host            string   `json:"host,omitempty"`
+	Location        string   `json:"location,omitempty"`
+	Severity        *float64 `json:"severity,omitempty"`
+	SolutionType    string   `json:"solution_type,omitempty"`
+	ResultID        string   `json:"result_id,omitempty"`
+	TaskID          string   `json:"task_id,omitempty"`
+	ReportID        string   `json:"report_id,omitempty"`
+	OpenTime        string   `json:"open_time,omitempty"`
+	FixedTime       string   `json:"fixed_time,omitempty"`
+	FixVerifiedTime string   `json:"fix_verified_time,omitempty"`
+	ClosedTime      string   `json:"closed_time,omitempty"`
+	OpenNote        string   `json:"open_note,omitempty"`
+	FixedNote       string   `json:"fixed_note,omitempty"`
+	ClosedNote      string   `json:"closed_note,omitempty"`
+}
+
+// TicketChanges lists what ModifyTicket changes; empty fields are left
+// alone. gvmd wants a note with every status change, stored as the note
+// of the new status.
+type TicketChanges struct {
+	Status   string `json:"status,omitempty"`
+	Note     string `json:"note,omitempty"`
+	Assignee string `json:"assignee,omitempty"`
+}
+
+// ticketStatuses maps the statuses a ticket can be set to onto the element
+// holding the note of each.
+var ticketStatuses = map[string]string{
+	"Open":   "open_note",
+	"Fixed":  "fixed_note",
+	"Closed": "closed_note",
+}
+
+// userID returns the ID of the gvmd user called name.
+func (s *OpenVASService) userID(ctx context.Context, name string) (string, error) {
+	type getUsersXML struct {
+		XMLName xml.Name `xml:"get_users"`
+		Filter  string   `xml:"filter,attr"`
+	}
+	xmlBody, err := xml.Marshal(&getUsersXML{Filter: fmt.Sprintf("name=\"%s\" rows=-1", name)})
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal get_users XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+	var parsed struct {
+		Users []struct {
+			ID   string `xml:"id,attr"`
+			Name string `xml:"name"`
+		} `xml:"user"`
+	}
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return "", fmt.Errorf("failed to parse get_users response XML: %w", err)
+	}
+	for _, u := range parsed.Users {
+		if strings.TrimSpace(u.Name) == name {
+			return u.ID, nil
+		}
+	}
+	return "", fmt.Errorf("failed to find user '%s'", name)
+}
+
+// CreateTicket opens a ticket for a result via <create_ticket>, assigned
+// to the user called assignee, and returns its ID.
+func (s *OpenVASService) CreateTicket(ctx context.Context, resultID, assignee, note string) (string, error) {
+	resultID = strings.TrimSpace(resultID)
+	assignee = strings.TrimSpace(assignee)
+	note = strings.TrimSpace(note)
+	if resultID == "" || assignee == "" || note == "" {
+		return "", fmt.Errorf("resultID, assignee and note are required")
+	}
+	userID, err := s.userID(ctx, assignee)
+	if err != nil {
+		return "", err
+	}
+
+	type idXML struct {
+		ID string `xml:"id,attr"`
+	}
+	type createTicketXML struct {
+		XMLName    xml.Name `xml:"create_ticket"`
+		Result     idXML    `xml:"result"`
+		AssignedTo idXML    `xml:"assigned_to>user"`
+		OpenNote   string   `xml:"open_note"`
+	}
+	xmlBody, err := xml.Marshal(&createTicketXML{
+		Result:     idXML{ID: resultID},
+		AssignedTo: idXML{ID: userID},
+		OpenNote:   note,
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_ticket XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+
+	var resp struct {
+		ID string `xml:"id,attr"`
+	}
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_ticket_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty ticket id in create_ticket_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// internal XML structs for the fields of <get_tickets_response> that
+// ListTickets reports.
+type ticketsXML struct {
+	Tickets []struct {
+		ID         string `xml:"id,attr"`
+		Name       string `xml:"name"`
+		Status     string `xml:"status"`
+		AssignedTo string `xml:"assigned_to>user>name"`
+		Host       string `xml:"host"`
+		Location   string `xml:"location"`
+		Severity   string `xml:"severity"`
+		Solution   string `xml:"solution_type"`
+		Result     struct {
+			ID string `xml:"id,attr"`
+		} `xml:"result"`
+		Task struct {
+			ID string `xml:"id,attr"`
+		} `xml:"task"`
+		Report struct {
+			ID string `xml:"id,attr"`
+		} `xml:"report"`
+		OpenTime        string `xml:"open_time"`
+		FixedTime       string `xml:"fixed_time"`
+		FixVerifiedTime string `xml:"fix_verified_time"`
+		ClosedTime      string `xml:"closed_time"`
+		OpenNote        string `xml:"open_note"`
+		FixedNote       string `xml:"fixed_note"`
+		ClosedNote      string `xml:"closed_note"`
+	} `xml:"ticket"`
+}
+
+// ListTickets returns every ticket, or only ticketID when it is set.
+func (s *OpenVASService) ListTickets(ctx context.Context, ticketID string) ([]Ticket, error) {
+	command := "<get_tickets filter='rows=-1'/>"
+	if ticketID = strings.TrimSpace(ticketID); ticketID != "" {
+		command = fmt.Sprintf("<get_tickets ticket_id='%s'/>", ticketID)
+	}
+	out, err := s.runGMP(ctx, command)
+	if err != nil {
+		return nil, err
+	}
+	var parsed ticketsXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_tickets response XML: %w", err)
+	}
+	tickets := make([]Ticket, 0, len(parsed.Tickets))
+	for _, t := range parsed.Tickets {
+		ticket := Ticket{
+			ID:              t.ID,
+			Name:            strings.TrimSpace(t.Name),
+			Status:          strings.TrimSpace(t.Status),
+			Assignee:        strings.TrimSpace(t.AssignedTo),
+			Host:            strings.TrimSpace(t.Host),
+			Location:        strings.TrimSpace(t.Location),
+			SolutionType:    strings.TrimSpace(t.Solution),
+			ResultID:        strings.TrimSpace(t.Result.ID),
+			TaskID:          strings.TrimSpace(t.Task.ID),
+			ReportID:        strings.TrimSpace(t.Report.ID),
+			OpenTime:        strings.TrimSpace(t.OpenTime),
+			FixedTime:       strings.TrimSpace(t.FixedTime),
+			FixVerifiedTime: strings.TrimSpace(t.FixVerifiedTime),
+			ClosedTime:      strings.TrimSpace(t.ClosedTime),
+			OpenNote:        strings.TrimSpace(t.OpenNote),
+			FixedNote:       strings.TrimSpace(t.FixedNote),
+			ClosedNote:      strings.TrimSpace(t.ClosedNote),
+		}
+		if v, err := strconv.ParseFloat(strings.TrimSpace(t.Severity), 64); err == nil {
+			ticket.Severity = &v
+		}
+		tickets = append(tickets, ticket)
+	}
+	return tickets, nil
+}
+
+// ModifyTicket changes a ticket's status, with its note, and assignee via
+// <modify_ticket>, and returns the ticket as gvmd has it afterwards.
+func (s *OpenVASService) ModifyTicket(ctx context.Context, ticketID string, changes TicketChanges) (Ticket, error) {
+	ticketID = strings.TrimSpace(ticketID)
+	if ticketID == "" {
+		return Ticket{}, fmt.Errorf("ticketID is required")
+	}
+	changes.Status = strings.TrimSpace(changes.Status)
+	changes.Note = strings.TrimSpace(changes.Note)
+	changes.Assignee = strings.TrimSpace(changes.Assignee)
+
+	var inner strings.Builder
+	if changes.Status != "" {
+		noteElement, ok := ticketStatuses[changes.Status]
+		if !ok {
+			return Ticket{}, fmt.Errorf("unknown ticket status %q (Open, Fixed or Closed)", changes.Status)
+		}
+		if changes.Note == "" {
+			return Ticket{}, fmt.Errorf("a note is required to set a ticket %s", changes.Status)
+		}
+		inner.WriteString("<status>" + changes.Status + "</status>")
+		inner.WriteString("<" + noteElement + ">")
+		if err := xml.EscapeText(&inner, []byte(changes.Note)); err != nil {
+			return Ticket{}, err
+		}
+		inner.WriteString("</" + noteElement + ">")
+	}
+	if changes.Assignee != "" {
+		userID, err := s.userID(ctx, changes.Assignee)
+		if err != nil {
+			return Ticket{}, err
+		}
+		inner.WriteString(fmt.Sprintf("<assigned_to><user id='%s'/></assigned_to>", userID))
+	}
+	if inner.Len() > 0 {
+		command := fmt.Sprintf("<modify_ticket ticket_id='%s'>%s</modify_ticket>", ticketID, inner.String())
+		if _, err := s.runGMP(ctx, command); err != nil {
+			return Ticket{}, err
+		}
+	}
+
+	tickets, err := s.ListTickets(ctx, ticketID)
+	if err != nil {
+		return Ticket{}, err
+	}
+	if len(tickets) == 0 {
+		return Ticket{}, fmt.Errorf("failed to find ticket '%s'", ticketID)
+	}
+	return tickets[0], nil
+}
+
 // internal XML structs for working with targets.
 type openVASTargetsXML struct {
 	Targets []openVASTargetXML `xml:"target"`
@@ -128,14 +950,25 @@ type openVASTargetXML struct {
 	ID       string `xml:"id,attr"`
 	Name     string `xml:"name"`
 	HostsRaw string `xml:"hosts"`
+	PortList struct {
+		ID string `xml:"id,attr"`
+	} `xml:"port_list"`
 }
 
+// ErrTargetConflict is returned by CreateTarget when a target with the
+// requested name exists but scans other hosts or ports.
+var ErrTargetConflict = errors.New("target exists with different hosts or ports")
+
 // CreateTarget ensures idempotent target creation:
-//   - If a target with the same name and hosts already exists, it returns
-//     the existing target ID and existed=true.
+//   - If a target with the same name, the same hosts and the requested
+//     ports already exists, it returns the existing target ID and
+//     existed=true. Without portRange or portListID any port list matches.
+//   - If a target with that name exists but differs, it returns an error
+//     wrapping ErrTargetConflict rather than hand back the wrong target.
 //   - Otherwise it creates a new target via <create_target> and returns
-//     the new target ID and existed=false.
-func (s *OpenVASService) CreateTarget(ctx context.Context, name, hosts, portRange string) (id string, existed bool, err error) {
+//     the new target ID and existed=false. Its ports are portRange, or the
+//     port list portListID; gvmd's default port list when both are empty.
+func (s *OpenVASService) CreateTarget(ctx context.Context, name, hosts, portRange, portListID string) (id string, existed bool, err error) {
 	if s.Password == "" {
 		return "", false, fmt.Errorf("GVM_PASSWORD is not set")
 	}
@@ -143,55 +976,62 @@ func (s *OpenVASService) CreateTarget(ctx context.Context, name, hosts, portRang
 	name = strings.TrimSpace(name)
 	hosts = strings.TrimSpace(hosts)
 	portRange = strings.TrimSpace(portRange)
+	portListID = strings.TrimSpace(portListID)
 
 	if name == "" || hosts == "" {
 		return "", false, fmt.Errorf("name and hosts are required")
 	}
-
-	// First: check for an existing target with the same name and hosts.
-	getTargetsArgs := []string{
-		"exec",
-		"-u", "gvm",
-		s.ContainerName,
-		"gvm-cli",
-		"--gmp-username", s.Username,
-		"--gmp-password", s.Password,
-		"tls",
-		"--hostname", s.Host,
-		"--port", s.Port,
-		"--xml", "<get_targets/>",
+	if portRange != "" && portListID != "" {
+		return "", false, fmt.Errorf("portRange and portListID are exclusive")
+	}
+	var wantPorts string
+	if portRange != "" {
+		if wantPorts, err = normalizePortRange(portRange); err != nil {
+			return "", false, err
+		}
 	}
 
-	getTargetsCmd := exec.CommandContext(ctx, "docker", getTargetsArgs...)
-	targetsOut, getTargetsErr := getTargetsCmd.CombinedOutput()
+	// First: check for an existing target with the same name.
+	targetsOut, getTargetsErr := s.runGMP(ctx, "<get_targets filter='rows=-1'/>")
 	if getTargetsErr == nil {
 		var parsed openVASTargetsXML
 		if err := xml.Unmarshal(targetsOut, &parsed); err == nil {
-			wantName := strings.TrimSpace(name)
-			wantHosts := strings.TrimSpace(hosts)
-
 			for _, t := range parsed.Targets {
-				if strings.TrimSpace(t.Name) != wantName {
+				if strings.TrimSpace(t.Name) != name {
 					continue
 				}
-
-				// Hosts can be represented in different ways in XML; perform
-				// a relaxed match against the raw hosts XML/text.
-				hostsLower := strings.ToLower(wantHosts)
-				rawLower := strings.ToLower(t.HostsRaw)
-				if hostsLower != "" && strings.Contains(rawLower, hostsLower) {
-					return t.ID, true, nil
+				// gvmd may list hosts in another order or case than they
+				// were given, so compare them as sets.
+				same := normalizeHosts(t.HostsRaw) == normalizeHosts(hosts)
+				if same && portListID != "" {
+					same = strings.TrimSpace(t.PortList.ID) == portListID
 				}
+				if same && portRange != "" {
+					havePorts, err := s.portListRanges(ctx, t.PortList.ID)
+					if err != nil {
+						return "", false, err
+					}
+					same = havePorts == wantPorts
+				}
+				if !same {
+					return "", false, fmt.Errorf("%w: target %q (%s) has hosts %q; choose another name or delete it",
+						ErrTargetConflict, name, t.ID, strings.TrimSpace(t.HostsRaw))
+				}
+				return t.ID, true, nil
 			}
 		}
 	}
 
 	// If we didn't find an existing target (or get_targets failed), create one.
+	type portListRefXML struct {
+		ID string `xml:"id,attr"`
+	}
 	type createTargetXML struct {
-		XMLName   xml.Name `xml:"create_target"`
-		Name      string   `xml:"name"`
-		Hosts     string   `xml:"hosts"`
-		PortRange string   `xml:"port_range,omitempty"`
+		XMLName   xml.Name        `xml:"create_target"`
+		Name      string          `xml:"name"`
+		Hosts     string          `xml:"hosts"`
+		PortRange string          `xml:"port_range,omitempty"`
+		PortList  *portListRefXML `xml:"port_list"`
 	}
 
 	payload := createTargetXML{
@@ -201,6 +1041,9 @@ func (s *OpenVASService) CreateTarget(ctx context.Context, name, hosts, portRang
 	if portRange != "" {
 		payload.PortRange = portRange
 	}
+	if portListID != "" {
+		payload.PortList = &portListRefXML{ID: portListID}
+	}
 
 	xmlBody, err := xml.Marshal(&payload)
 	if err != nil {
@@ -242,45 +1085,775 @@ func (s *OpenVASService) CreateTarget(ctx context.Context, name, hosts, portRang
 	return strings.TrimSpace(resp.ID), false, nil
 }
 
-// internal XML structs for working with tasks.
-type openVASTasksXML struct {
-	Tasks []openVASTaskXML `xml:"task"`
+// normalizeHosts turns a gvmd hosts string into a canonical form so that
+// "10.0.0.2, 10.0.0.1" and "10.0.0.1,10.0.0.2" compare equal while
+// "10.0.0.1" and "10.0.0.10" do not.
+func normalizeHosts(hosts string) string {
+	fields := strings.FieldsFunc(strings.ToLower(hosts), func(r rune) bool {
+		return r == ',' || r == ' ' || r == '\t' || r == '\n' || r == '\r'
+	})
+	sort.Strings(fields)
+	unique := fields[:0]
+	for i, f := range fields {
+		if i == 0 || f != fields[i-1] {
+			unique = append(unique, f)
+		}
+	}
+	return strings.Join(unique, ",")
 }
 
-type openVASTaskXML struct {
-	ID     string               `xml:"id,attr"`
-	Name   string               `xml:"name"`
-	Config openVASTaskConfigXML `xml:"config"`
-	Target openVASTaskTargetXML `xml:"target"`
+// normalizePortRange parses gvmd's port range syntax ("T:1-1024,U:53", where
+// a T:/U: prefix switches the protocol for the ranges after it and TCP is
+// the default) into a sorted canonical form, so that two spellings of the
+// same ports compare equal. Port numbers are zero-padded to sort correctly.
+func normalizePortRange(portRange string) (string, error) {
+	protocol := "tcp"
+	var ranges []string
+	for _, item := range strings.Split(portRange, ",") {
+		item = strings.TrimSpace(item)
+		switch {
+		case strings.HasPrefix(strings.ToUpper(item), "T:"):
+			protocol, item = "tcp", strings.TrimSpace(item[2:])
+		case strings.HasPrefix(strings.ToUpper(item), "U:"):
+			protocol, item = "udp", strings.TrimSpace(item[2:])
+		}
+		if item == "" {
+			continue
+		}
+		startRaw, endRaw, isRange := strings.Cut(item, "-")
+		if !isRange {
+			endRaw = startRaw
+		}
+		start, errStart := strconv.Atoi(strings.TrimSpace(startRaw))
+		end, errEnd := strconv.Atoi(strings.TrimSpace(endRaw))
+		if errStart != nil || errEnd != nil || start < 1 || end > 65535 || start > end {
+			return "", fmt.Errorf("invalid port range %q", item)
+		}
+		ranges = append(ranges, formatPortRange(protocol, start, end))
+	}
+	sort.Strings(ranges)
+	return strings.Join(ranges, ","), nil
 }
 
-type openVASTaskConfigXML struct {
-	ID string `xml:"id,attr"`
+func formatPortRange(protocol string, start, end int) string {
+	return fmt.Sprintf("%s:%05d-%05d", protocol, start, end)
 }
 
-type openVASTaskTargetXML struct {
-	ID string `xml:"id,attr"`
+// internal XML structs for the ranges of one port list.
+type portListRangesXML struct {
+	Ranges []struct {
+		Start int    `xml:"start"`
+		End   int    `xml:"end"`
+		Type  string `xml:"type"`
+	} `xml:"port_list>port_ranges>port_range"`
 }
 
-// CreateTask ensures idempotent task creation:
-//   - If a task with the same name, config ID, and target ID already exists,
-//     it returns the existing task ID and existed=true.
-//   - Otherwise it creates a new task via <create_task> and returns the new
-//     task ID and existed=false.
-func (s *OpenVASService) CreateTask(ctx context.Context, name, configID, targetID string) (id string, existed bool, err error) {
-	if s.Password == "" {
-		return "", false, fmt.Errorf("GVM_PASSWORD is not set")
+// portListRanges returns the ranges of port list portListID in the form
+// normalizePortRange produces.
+func (s *OpenVASService) portListRanges(ctx context.Context, portListID string) (string, error) {
+	out, err := s.runGMP(ctx, fmt.Sprintf("<get_port_lists port_list_id='%s' details='1'/>", portListID))
+	if err != nil {
+		return "", err
+	}
+	var parsed portListRangesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return "", fmt.Errorf("failed to parse get_port_lists response XML: %w", err)
+	}
+	ranges := make([]string, 0, len(parsed.Ranges))
+	for _, r := range parsed.Ranges {
+		ranges = append(ranges, formatPortRange(strings.ToLower(strings.TrimSpace(r.Type)), r.Start, r.End))
 	}
+	sort.Strings(ranges)
+	return strings.Join(ranges, ","), nil
+}
 
+// CreatePortList creates a port list via <create_port_list> and returns its
+// ID. portRange uses gvmd's syntax, e.g. "T:1-1024,U:53,161".
+func (s *OpenVASService) CreatePortList(ctx context.Context, name, portRange, comment string) (string, error) {
 	name = strings.TrimSpace(name)
-	configID = strings.TrimSpace(configID)
-	targetID = strings.TrimSpace(targetID)
-
-	if name == "" || configID == "" || targetID == "" {
-		return "", false, fmt.Errorf("name, configID, and targetID are required")
+	portRange = strings.TrimSpace(portRange)
+	if name == "" || portRange == "" {
+		return "", fmt.Errorf("name and portRange are required")
 	}
 
-	// First: check for an existing task with the same name, config, and target.
+	type createPortListXML struct {
+		XMLName   xml.Name `xml:"create_port_list"`
+		Name      string   `xml:"name"`
+		Comment   string   `xml:"comment,omitempty"`
+		PortRange string   `xml:"port_range"`
+	}
+	xmlBody, err := xml.Marshal(&createPortListXML{
+		Name:      name,
+		Comment:   strings.TrimSpace(comment),
+		PortRange: portRange,
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_port_list XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+
+	type createPortListResponseXML struct {
+		XMLName xml.Name `xml:"create_port_list_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createPortListResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_port_list_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty port list id in create_port_list_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// internal XML structs for the fields of <get_port_lists_response> that
+// ListPortLists reports.
+type portListSummaryXML struct {
+	ID         string `xml:"id,attr"`
+	Name       string `xml:"name"`
+	Comment    string `xml:"comment"`
+	InUse      int    `xml:"in_use"`
+	Predefined int    `xml:"predefined"`
+	PortCount  struct {
+		All int `xml:"all"`
+		TCP int `xml:"tcp"`
+		UDP int `xml:"udp"`
+	} `xml:"port_count"`
+}
+
+type portListSummariesXML struct {
+	PortLists []portListSummaryXML `xml:"port_list"`
+}
+
+// PortCount counts the ports of a port list by protocol.
+type PortCount struct {
+	All int `json:"all"`
+	TCP int `json:"tcp"`
+	UDP int `json:"udp"`
+}
+
+// PortListSummary is one existing port list.
+type PortListSummary struct {
+	ID         string    `json:"id"`
+	Name       string    `json:"name"`
+	Comment    string    `json:"comment,omitempty"`
+	PortCount  PortCount `json:"port_count"`
+	InUse      bool      `json:"in_use"`
+	Predefined bool      `json:"predefined"`
+}
+
+// ListPortLists returns every port list gvmd knows, predefined ones
+// included, outside the trashcan.
+func (s *OpenVASService) ListPortLists(ctx context.Context) ([]PortListSummary, error) {
+	out, err := s.runGMP(ctx, "<get_port_lists filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed portListSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_port_lists response XML: %w", err)
+	}
+	portLists := make([]PortListSummary, 0, len(parsed.PortLists))
+	for _, p := range parsed.PortLists {
+		portLists = append(portLists, PortListSummary{
+			ID:         p.ID,
+			Name:       strings.TrimSpace(p.Name),
+			Comment:    strings.TrimSpace(p.Comment),
+			PortCount:  PortCount{All: p.PortCount.All, TCP: p.PortCount.TCP, UDP: p.PortCount.UDP},
+			InUse:      p.InUse != 0,
+			Predefined: p.Predefined != 0,
+		})
+	}
+	return portLists, nil
+}
+
+// internal XML structs for the fields of <get_targets_response> that
+// ListTargets reports.
+type targetSummaryXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	Hosts        string `xml:"hosts"`
+	InUse        int    `xml:"in_use"`
+	CreationTime string `xml:"creation_time"`
+	PortList     struct {
+		ID   string `xml:"id,attr"`
+		Name string `xml:"name"`
+	} `xml:"port_list"`
+}
+
+type targetSummariesXML struct {
+	Targets []targetSummaryXML `xml:"target"`
+}
+
+// TargetSummary is one existing target.
+type TargetSummary struct {
+	ID       string `json:"id"`
+	Name     string `json:"name"`
+	Hosts    string `json:"hosts"`
+	PortList string `json:"port_list,omitempty"`
+	InUse    bool   `json:"in_use"`
+	Created  string `json:"created,omitempty"`
+}
+
+// ListTargets returns every target gvmd knows, outside the trashcan.
+func (s *OpenVASService) ListTargets(ctx context.Context) ([]TargetSummary, error) {
+	out, err := s.runGMP(ctx, "<get_targets filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed targetSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_targets response XML: %w", err)
+	}
+	targets := make([]TargetSummary, 0, len(parsed.Targets))
+	for _, t := range parsed.Targets {
+		targets = append(targets, TargetSummary{
+			ID:       t.ID,
+			Name:     strings.TrimSpace(t.Name),
+			Hosts:    strings.TrimSpace(t.Hosts),
+			PortList: strings.TrimSpace(t.PortList.Name),
+			InUse:    t.InUse != 0,
+			Created:  strings.TrimSpace(t.CreationTime),
+		})
+	}
+	return targets, nil
+}
+
+// DeleteTarget deletes a target by ID and returns the raw XML response from
+// gvmd. Without ultimate the target goes to the trashcan, where it can be
+// restored; with it the target is removed for good. gvmd refuses to delete
+// a target that a task still uses.
+func (s *OpenVASService) DeleteTarget(ctx context.Context, targetID string, ultimate bool) (string, error) {
+	targetID = strings.TrimSpace(targetID)
+	if targetID == "" {
+		return "", fmt.Errorf("targetID is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<delete_target target_id='%s' ultimate='%d'/>", targetID, boolFlag(ultimate)))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// boolFlag renders b as GMP's 0/1 attribute value.
+func boolFlag(b bool) int {
+	if b {
+		return 1
+	}
+	return 0
+}
+
+// ScheduleSpec is what CreateSchedule needs. ICalendar holds one VEVENT
+// whose DTSTART is the first run and whose RRULE, if any, the recurrence.
+type ScheduleSpec struct {
+	Name      string `json:"name"`
+	ICalendar string `json:"icalendar"`
+	Timezone  string `json:"timezone,omitempty"`
+	Comment   string `json:"comment,omitempty"`
+}
+
+// CreateSchedule creates a schedule via <create_schedule> and returns its
+// ID. The timezone defaults to UTC.
+func (s *OpenVASService) CreateSchedule(ctx context.Context, spec ScheduleSpec) (string, error) {
+	spec.Name = strings.TrimSpace(spec.Name)
+	spec.ICalendar = strings.TrimSpace(spec.ICalendar)
+	if spec.Name == "" || spec.ICalendar == "" {
+		return "", fmt.Errorf("name and icalendar are required")
+	}
+	if spec.Timezone = strings.TrimSpace(spec.Timezone); spec.Timezone == "" {
+		spec.Timezone = "UTC"
+	}
+
+	type createScheduleXML struct {
+		XMLName   xml.Name `xml:"create_schedule"`
+		Name      string   `xml:"name"`
+		Comment   string   `xml:"comment,omitempty"`
+		ICalendar string   `xml:"icalendar"`
+		Timezone  string   `xml:"timezone"`
+	}
+	xmlBody, err := xml.Marshal(&createScheduleXML{
+		Name:      spec.Name,
+		Comment:   strings.TrimSpace(spec.Comment),
+		ICalendar: spec.ICalendar,
+		Timezone:  spec.Timezone,
+	})
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_schedule XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+
+	type createScheduleResponseXML struct {
+		XMLName xml.Name `xml:"create_schedule_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createScheduleResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_schedule_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty schedule id in create_schedule_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// alertMethods are the gvmd alert methods CreateAlert accepts.
+var alertMethods = map[string]bool{
+	"Email":    true,
+	"HTTP Get": true,
+}
+
+// AlertSpec is what CreateAlert needs: an alert set off when a task's run
+// status changes to Status, notifying by Method. MethodData holds the
+// method's settings by gvmd's names, e.g. "to_address" or "URL".
+type AlertSpec struct {
+	Name       string            `json:"name"`
+	Method     string            `json:"method"`
+	Status     string            `json:"status"`
+	MethodData map[string]string `json:"method_data"`
+	Comment    string            `json:"comment,omitempty"`
+}
+
+// CreateAlert creates an alert via <create_alert> and returns its ID. The
+// alert has no condition beyond its event; attach it to tasks with
+// ModifyTask.
+func (s *OpenVASService) CreateAlert(ctx context.Context, spec AlertSpec) (string, error) {
+	spec.Name = strings.TrimSpace(spec.Name)
+	spec.Status = strings.TrimSpace(spec.Status)
+	if spec.Name == "" || spec.Status == "" {
+		return "", fmt.Errorf("name and status are required")
+	}
+	if !alertMethods[spec.Method] {
+		return "", fmt.Errorf("unsupported alert method %q", spec.Method)
+	}
+
+	type dataXML struct {
+		Value string `xml:",chardata"`
+		Name  string `xml:"name"`
+	}
+	type eventXML struct {
+		Type string  `xml:",chardata"`
+		Data dataXML `xml:"data"`
+	}
+	type methodXML struct {
+		Type string    `xml:",chardata"`
+		Data []dataXML `xml:"data"`
+	}
+	type createAlertXML struct {
+		XMLName   xml.Name  `xml:"create_alert"`
+		Name      string    `xml:"name"`
+		Comment   string    `xml:"comment,omitempty"`
+		Condition string    `xml:"condition"`
+		Event     eventXML  `xml:"event"`
+		Method    methodXML `xml:"method"`
+	}
+
+	payload := createAlertXML{
+		Name:      spec.Name,
+		Comment:   strings.TrimSpace(spec.Comment),
+		Condition: "Always",
+		Event:     eventXML{Type: "Task run status changed", Data: dataXML{Name: "status", Value: spec.Status}},
+		Method:    methodXML{Type: spec.Method},
+	}
+	names := make([]string, 0, len(spec.MethodData))
+	for name := range spec.MethodData {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		payload.Method.Data = append(payload.Method.Data, dataXML{Name: name, Value: spec.MethodData[name]})
+	}
+
+	xmlBody, err := xml.Marshal(&payload)
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_alert XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+
+	type createAlertResponseXML struct {
+		XMLName xml.Name `xml:"create_alert_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createAlertResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_alert_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty alert id in create_alert_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// internal XML structs for the fields of <get_schedules_response> that
+// ListSchedules reports.
+type scheduleSummaryXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	ICalendar    string `xml:"icalendar"`
+	Timezone     string `xml:"timezone"`
+	InUse        int    `xml:"in_use"`
+	CreationTime string `xml:"creation_time"`
+	Tasks        []struct {
+		ID string `xml:"id,attr"`
+	} `xml:"tasks>task"`
+}
+
+type scheduleSummariesXML struct {
+	Schedules []scheduleSummaryXML `xml:"schedule"`
+}
+
+// ScheduleSummary is one existing schedule and the tasks that use it.
+type ScheduleSummary struct {
+	ID        string   `json:"id"`
+	Name      string   `json:"name"`
+	ICalendar string   `json:"icalendar"`
+	Timezone  string   `json:"timezone"`
+	InUse     bool     `json:"in_use"`
+	TaskIDs   []string `json:"task_ids,omitempty"`
+	Created   string   `json:"created,omitempty"`
+}
+
+// ListSchedules returns every schedule gvmd knows, outside the trashcan.
+func (s *OpenVASService) ListSchedules(ctx context.Context) ([]ScheduleSummary, error) {
+	out, err := s.runGMP(ctx, "<get_schedules filter='rows=-1' tasks='1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed scheduleSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_schedules response XML: %w", err)
+	}
+	schedules := make([]ScheduleSummary, 0, len(parsed.Schedules))
+	for _, sc := range parsed.Schedules {
+		summary := ScheduleSummary{
+			ID:        sc.ID,
+			Name:      strings.TrimSpace(sc.Name),
+			ICalendar: strings.TrimSpace(sc.ICalendar),
+			Timezone:  strings.TrimSpace(sc.Timezone),
+			InUse:     sc.InUse != 0,
+			Created:   strings.TrimSpace(sc.CreationTime),
+		}
+		for _, t := range sc.Tasks {
+			summary.TaskIDs = append(summary.TaskIDs, t.ID)
+		}
+		schedules = append(schedules, summary)
+	}
+	return schedules, nil
+}
+
+// DeleteSchedule deletes a schedule by ID and returns the raw XML response
+// from gvmd. Without ultimate the schedule goes to the trashcan. gvmd
+// refuses to delete a schedule that a task still uses.
+func (s *OpenVASService) DeleteSchedule(ctx context.Context, scheduleID string, ultimate bool) (string, error) {
+	scheduleID = strings.TrimSpace(scheduleID)
+	if scheduleID == "" {
+		return "", fmt.Errorf("scheduleID is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<delete_schedule schedule_id='%s' ultimate='%d'/>", scheduleID, boolFlag(ultimate)))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// CredentialSpec is what CreateCredential needs. Type is gvmd's credential
+// type: "up" (username and password) or "usk" (username and SSH key, with
+// an optional passphrase in Password).
+type CredentialSpec struct {
+	Name       string `json:"name"`
+	Type       string `json:"type"`
+	Login      string `json:"login"`
+	Password   string `json:"password,omitempty"`
+	PrivateKey string `json:"private_key,omitempty"`
+	Comment    string `json:"comment,omitempty"`
+}
+
+// CreateCredential creates a credential via <create_credential> and returns
+// its ID. The secrets never appear in errors.
+func (s *OpenVASService) CreateCredential(ctx context.Context, spec CredentialSpec) (string, error) {
+	spec.Name = strings.TrimSpace(spec.Name)
+	spec.Login = strings.TrimSpace(spec.Login)
+	if spec.Name == "" || spec.Login == "" {
+		return "", fmt.Errorf("name and login are required")
+	}
+
+	type keyXML struct {
+		Private string `xml:"private"`
+		Phrase  string `xml:"phrase,omitempty"`
+	}
+	type createCredentialXML struct {
+		XMLName  xml.Name `xml:"create_credential"`
+		Name     string   `xml:"name"`
+		Comment  string   `xml:"comment,omitempty"`
+		Type     string   `xml:"type"`
+		Login    string   `xml:"login"`
+		Password string   `xml:"password,omitempty"`
+		Key      *keyXML  `xml:"key"`
+	}
+	payload := createCredentialXML{
+		Name:    spec.Name,
+		Comment: strings.TrimSpace(spec.Comment),
+		Type:    spec.Type,
+		Login:   spec.Login,
+	}
+	switch spec.Type {
+	case "up":
+		if spec.Password == "" {
+			return "", fmt.Errorf("password is required for type up")
+		}
+		payload.Password = spec.Password
+	case "usk":
+		if strings.TrimSpace(spec.PrivateKey) == "" {
+			return "", fmt.Errorf("private_key is required for type usk")
+		}
+		payload.Key = &keyXML{Private: spec.PrivateKey, Phrase: spec.Password}
+	default:
+		return "", fmt.Errorf("unsupported credential type %q (expected up or usk)", spec.Type)
+	}
+
+	xmlBody, err := xml.Marshal(&payload)
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal create_credential XML: %w", err)
+	}
+	out, err := s.runGMPAs(ctx, string(xmlBody), "create_credential "+spec.Name)
+	if err != nil {
+		return "", err
+	}
+
+	type createCredentialResponseXML struct {
+		XMLName xml.Name `xml:"create_credential_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createCredentialResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", fmt.Errorf("failed to parse create_credential_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", fmt.Errorf("empty credential id in create_credential_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), nil
+}
+
+// internal XML structs for the fields of <get_credentials_response> that
+// ListCredentials reports.
+type credentialSummaryXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	Type         string `xml:"type"`
+	Login        string `xml:"login"`
+	InUse        int    `xml:"in_use"`
+	CreationTime string `xml:"creation_time"`
+}
+
+type credentialSummariesXML struct {
+	Credentials []credentialSummaryXML `xml:"credential"`
+}
+
+// CredentialSummary is one existing credential, without its secrets.
+type CredentialSummary struct {
+	ID      string `json:"id"`
+	Name    string `json:"name"`
+	Type    string `json:"type"`
+	Login   string `json:"login"`
+	InUse   bool   `json:"in_use"`
+	Created string `json:"created,omitempty"`
+}
+
+// ListCredentials returns every credential gvmd knows, outside the
+// trashcan. gvmd never returns the secrets themselves.
+func (s *OpenVASService) ListCredentials(ctx context.Context) ([]CredentialSummary, error) {
+	out, err := s.runGMP(ctx, "<get_credentials filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed credentialSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_credentials response XML: %w", err)
+	}
+	credentials := make([]CredentialSummary, 0, len(parsed.Credentials))
+	for _, c := range parsed.Credentials {
+		credentials = append(credentials, CredentialSummary{
+			ID:      c.ID,
+			Name:    strings.TrimSpace(c.Name),
+			Type:    strings.TrimSpace(c.Type),
+			Login:   strings.TrimSpace(c.Login),
+			InUse:   c.InUse != 0,
+			Created: strings.TrimSpace(c.CreationTime),
+		})
+	}
+	return credentials, nil
+}
+
+// internal XML structs for the fields of <get_scanners_response> that
+// ListScanners reports.
+type scannerSummaryXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	Type         int    `xml:"type"`
+	Host         string `xml:"host"`
+	Port         string `xml:"port"`
+	InUse        int    `xml:"in_use"`
+	CreationTime string `xml:"creation_time"`
+}
+
+type scannerSummariesXML struct {
+	Scanners []scannerSummaryXML `xml:"scanner"`
+}
+
+// scannerTypeNames maps gvmd's numeric scanner types to their names.
+var scannerTypeNames = map[int]string{
+	1: "osp",
+	2: "openvas",
+	3: "cve",
+	5: "osp_sensor",
+}
+
+// ScannerSummary is one scanner tasks can run on.
+type ScannerSummary struct {
+	ID      string `json:"id"`
+	Name    string `json:"name"`
+	Type    string `json:"type"`
+	Host    string `json:"host,omitempty"`
+	Port    string `json:"port,omitempty"`
+	InUse   bool   `json:"in_use"`
+	Created string `json:"created,omitempty"`
+}
+
+// ListScanners returns every scanner gvmd knows, e.g. the default OpenVAS
+// scanner, the CVE scanner and any remote sensors, outside the trashcan.
+func (s *OpenVASService) ListScanners(ctx context.Context) ([]ScannerSummary, error) {
+	out, err := s.runGMP(ctx, "<get_scanners filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed scannerSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_scanners response XML: %w", err)
+	}
+	scanners := make([]ScannerSummary, 0, len(parsed.Scanners))
+	for _, sc := range parsed.Scanners {
+		scannerType, ok := scannerTypeNames[sc.Type]
+		if !ok {
+			scannerType = strconv.Itoa(sc.Type)
+		}
+		scanners = append(scanners, ScannerSummary{
+			ID:      sc.ID,
+			Name:    strings.TrimSpace(sc.Name),
+			Type:    scannerType,
+			Host:    strings.TrimSpace(sc.Host),
+			Port:    strings.TrimSpace(sc.Port),
+			InUse:   sc.InUse != 0,
+			Created: strings.TrimSpace(sc.CreationTime),
+		})
+	}
+	return scanners, nil
+}
+
+// AttachCredential sets the credential a target's scans log in with, via
+// <modify_target>, and returns the raw XML response from gvmd. Kind is
+// "ssh" or "smb"; port is the SSH port (0 keeps gvmd's default). gvmd only
+// changes targets that no task uses.
+func (s *OpenVASService) AttachCredential(ctx context.Context, targetID, credentialID, kind string, port int) (string, error) {
+	targetID = strings.TrimSpace(targetID)
+	credentialID = strings.TrimSpace(credentialID)
+	if targetID == "" || credentialID == "" {
+		return "", fmt.Errorf("targetID and credentialID are required")
+	}
+
+	type credentialRefXML struct {
+		ID   string `xml:"id,attr"`
+		Port int    `xml:"port,omitempty"`
+	}
+	type modifyTargetXML struct {
+		XMLName       xml.Name          `xml:"modify_target"`
+		TargetID      string            `xml:"target_id,attr"`
+		SSHCredential *credentialRefXML `xml:"ssh_credential"`
+		SMBCredential *credentialRefXML `xml:"smb_credential"`
+	}
+	payload := modifyTargetXML{TargetID: targetID}
+	switch kind {
+	case "ssh":
+		payload.SSHCredential = &credentialRefXML{ID: credentialID, Port: port}
+	case "smb":
+		payload.SMBCredential = &credentialRefXML{ID: credentialID}
+	default:
+		return "", fmt.Errorf("unsupported credential kind %q (expected ssh or smb)", kind)
+	}
+
+	xmlBody, err := xml.Marshal(&payload)
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal modify_target XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// internal XML structs for working with tasks.
+type openVASTasksXML struct {
+	Tasks []openVASTaskXML `xml:"task"`
+}
+
+type openVASTaskXML struct {
+	ID       string                 `xml:"id,attr"`
+	Name     string                 `xml:"name"`
+	Config   openVASTaskConfigXML   `xml:"config"`
+	Target   openVASTaskTargetXML   `xml:"target"`
+	Schedule openVASTaskScheduleXML `xml:"schedule"`
+	Scanner  openVASTaskScannerXML  `xml:"scanner"`
+}
+
+type openVASTaskConfigXML struct {
+	ID string `xml:"id,attr"`
+}
+
+type openVASTaskTargetXML struct {
+	ID string `xml:"id,attr"`
+}
+
+type openVASTaskScheduleXML struct {
+	ID string `xml:"id,attr"`
+}
+
+type openVASTaskScannerXML struct {
+	ID string `xml:"id,attr"`
+}
+
+// CreateTask ensures idempotent task creation:
+//   - If a task with the same name, config ID, and target ID (and schedule
+//     and scanner IDs, when given) already exists, it returns the existing
+//     task ID and existed=true.
+//   - Otherwise it creates a new task via <create_task> and returns the new
+//     task ID and existed=false. A non-empty scheduleID attaches the
+//     schedule, so gvmd starts the task at the scheduled times; a non-empty
+//     scannerID runs the task on that scanner instead of gvmd's default
+//     OpenVAS scanner.
+func (s *OpenVASService) CreateTask(ctx context.Context, name, configID, targetID, scheduleID, scannerID string) (id string, existed bool, err error) {
+	if s.Password == "" {
+		return "", false, fmt.Errorf("GVM_PASSWORD is not set")
+	}
+
+	name = strings.TrimSpace(name)
+	configID = strings.TrimSpace(configID)
+	targetID = strings.TrimSpace(targetID)
+	scheduleID = strings.TrimSpace(scheduleID)
+	scannerID = strings.TrimSpace(scannerID)
+
+	if name == "" || configID == "" || targetID == "" {
+		return "", false, fmt.Errorf("name, configID, and targetID are required")
+	}
+
+	// First: check for an existing task with the same name, config, and target.
 	getTasksArgs := []string{
 		"exec",
 		"-u", "gvm",
@@ -313,67 +1886,335 @@ func (s *OpenVASService) CreateTask(ctx context.Context, name, configID, targetI
 				if strings.TrimSpace(t.Target.ID) != wantTarget {
 					continue
 				}
+				if scheduleID != "" && strings.TrimSpace(t.Schedule.ID) != scheduleID {
+					continue
+				}
+				if scannerID != "" && strings.TrimSpace(t.Scanner.ID) != scannerID {
+					continue
+				}
 				return t.ID, true, nil
 			}
 		}
 	}
-
-	// If we didn't find an existing task (or get_tasks failed), create one.
-	type createTaskXML struct {
-		XMLName xml.Name             `xml:"create_task"`
-		Name    string               `xml:"name"`
-		Config  openVASTaskConfigXML `xml:"config"`
-		Target  openVASTaskTargetXML `xml:"target"`
+
+	// If we didn't find an existing task (or get_tasks failed), create one.
+	type createTaskXML struct {
+		XMLName  xml.Name                `xml:"create_task"`
+		Name     string                  `xml:"name"`
+		Config   openVASTaskConfigXML    `xml:"config"`
+		Target   openVASTaskTargetXML    `xml:"target"`
+		Schedule *openVASTaskScheduleXML `xml:"schedule"`
+		Scanner  *openVASTaskScannerXML  `xml:"scanner"`
+	}
+
+	payload := createTaskXML{
+		Name: name,
+		Config: openVASTaskConfigXML{
+			ID: configID,
+		},
+		Target: openVASTaskTargetXML{
+			ID: targetID,
+		},
+	}
+	if scheduleID != "" {
+		payload.Schedule = &openVASTaskScheduleXML{ID: scheduleID}
+	}
+	if scannerID != "" {
+		payload.Scanner = &openVASTaskScannerXML{ID: scannerID}
+	}
+
+	xmlBody, err := xml.Marshal(&payload)
+	if err != nil {
+		return "", false, fmt.Errorf("failed to marshal create_task XML: %w", err)
+	}
+
+	createArgs := []string{
+		"exec",
+		"-u", "gvm",
+		s.ContainerName,
+		"gvm-cli",
+		"--gmp-username", s.Username,
+		"--gmp-password", s.Password,
+		"tls",
+		"--hostname", s.Host,
+		"--port", s.Port,
+		"--xml", string(xmlBody),
+	}
+
+	createCmd := exec.CommandContext(ctx, "docker", createArgs...)
+	createOut, createErr := createCmd.CombinedOutput()
+	if createErr != nil {
+		return "", false, fmt.Errorf("gvm-cli create_task failed: %w; output: %s", createErr, string(createOut))
+	}
+
+	type createTaskResponseXML struct {
+		XMLName xml.Name `xml:"create_task_response"`
+		ID      string   `xml:"id,attr"`
+	}
+
+	var resp createTaskResponseXML
+	if err := xml.Unmarshal(createOut, &resp); err != nil {
+		return "", false, fmt.Errorf("failed to parse create_task_response XML: %w; output: %s", err, string(createOut))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", false, fmt.Errorf("empty task id in create_task_response; output: %s", string(createOut))
+	}
+
+	return strings.TrimSpace(resp.ID), false, nil
+}
+
+// CreateContainerTask creates a container task, a task without a target
+// that only holds imported reports, and returns its ID. Like CreateTask it
+// is idempotent: an existing container task with the same name is reused
+// and returned with existed=true.
+func (s *OpenVASService) CreateContainerTask(ctx context.Context, name string) (id string, existed bool, err error) {
+	name = strings.TrimSpace(name)
+	if name == "" {
+		return "", false, fmt.Errorf("name is required")
+	}
+
+	tasks, err := s.ListTasks(ctx)
+	if err != nil {
+		return "", false, err
+	}
+	for _, t := range tasks {
+		if strings.TrimSpace(t.Name) == name && t.TargetID == "" {
+			return t.ID, true, nil
+		}
+	}
+
+	// gvmd makes a container task out of a task whose target ID is 0.
+	type createContainerTaskXML struct {
+		XMLName xml.Name             `xml:"create_task"`
+		Name    string               `xml:"name"`
+		Target  openVASTaskTargetXML `xml:"target"`
+	}
+	xmlBody, err := xml.Marshal(&createContainerTaskXML{
+		Name:   name,
+		Target: openVASTaskTargetXML{ID: "0"},
+	})
+	if err != nil {
+		return "", false, fmt.Errorf("failed to marshal create_task XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", false, err
+	}
+
+	type createTaskResponseXML struct {
+		XMLName xml.Name `xml:"create_task_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createTaskResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", false, fmt.Errorf("failed to parse create_task_response XML: %w; output: %s", err, string(out))
+	}
+	if strings.TrimSpace(resp.ID) == "" {
+		return "", false, fmt.Errorf("empty task id in create_task_response; output: %s", string(out))
+	}
+	return strings.TrimSpace(resp.ID), false, nil
+}
+
+// ImportReport imports reportXML, a GVM report (a <report> element as
+// returned by <get_reports> or built by a converter), into the container
+// task taskID via <create_report>, and returns the new report's ID along
+// with the raw XML response from gvmd.
+func (s *OpenVASService) ImportReport(ctx context.Context, taskID, reportXML string) (reportID, raw string, err error) {
+	taskID = strings.TrimSpace(taskID)
+	reportXML = strings.TrimSpace(reportXML)
+	if taskID == "" || reportXML == "" {
+		return "", "", fmt.Errorf("taskID and reportXML are required")
+	}
+	var root struct {
+		XMLName xml.Name
+	}
+	if err := xml.Unmarshal([]byte(reportXML), &root); err != nil {
+		return "", "", fmt.Errorf("report is not well-formed XML: %w", err)
+	}
+	if root.XMLName.Local != "report" {
+		return "", "", fmt.Errorf("report must be a <report> element, not <%s>", root.XMLName.Local)
+	}
+
+	var id strings.Builder
+	if err := xml.EscapeText(&id, []byte(taskID)); err != nil {
+		return "", "", fmt.Errorf("failed to escape task id: %w", err)
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<create_report><task id='%s'/>%s</create_report>", id.String(), reportXML))
+	if err != nil {
+		return "", "", err
+	}
+
+	type createReportResponseXML struct {
+		XMLName xml.Name `xml:"create_report_response"`
+		ID      string   `xml:"id,attr"`
+	}
+	var resp createReportResponseXML
+	if err := xml.Unmarshal(out, &resp); err != nil {
+		return "", "", fmt.Errorf("failed to parse create_report_response XML: %w; output: %s", err, string(out))
+	}
+	return strings.TrimSpace(resp.ID), string(out), nil
+}
+
+// internal XML structs for the fields of <get_tasks_response> that ListTasks
+// reports.
+type taskSummaryXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	Status       string `xml:"status"`
+	Progress     int    `xml:"progress"`
+	CreationTime string `xml:"creation_time"`
+	Config       struct {
+		ID   string `xml:"id,attr"`
+		Name string `xml:"name"`
+	} `xml:"config"`
+	Target struct {
+		ID   string `xml:"id,attr"`
+		Name string `xml:"name"`
+	} `xml:"target"`
+	LastReport struct {
+		ID   string `xml:"id,attr"`
+		Time string `xml:"timestamp"`
+	} `xml:"last_report>report"`
+}
+
+type taskSummariesXML struct {
+	Tasks []taskSummaryXML `xml:"task"`
+}
+
+// TaskSummary is one existing task and where it stands.
+type TaskSummary struct {
+	ID             string `json:"id"`
+	Name           string `json:"name"`
+	Status         string `json:"status"`
+	Progress       int    `json:"progress"`
+	ConfigID       string `json:"config_id,omitempty"`
+	ConfigName     string `json:"config_name,omitempty"`
+	TargetID       string `json:"target_id,omitempty"`
+	TargetName     string `json:"target_name,omitempty"`
+	LastReportID   string `json:"last_report_id,omitempty"`
+	LastReportTime string `json:"last_report_time,omitempty"`
+	Created        string `json:"created,omitempty"`
+}
+
+// ListTasks returns every task gvmd knows, outside the trashcan, with its
+// status, progress, target and last report.
+func (s *OpenVASService) ListTasks(ctx context.Context) ([]TaskSummary, error) {
+	out, err := s.runGMP(ctx, "<get_tasks filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	var parsed taskSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_tasks response XML: %w", err)
+	}
+	tasks := make([]TaskSummary, 0, len(parsed.Tasks))
+	for _, t := range parsed.Tasks {
+		tasks = append(tasks, TaskSummary{
+			ID:             t.ID,
+			Name:           strings.TrimSpace(t.Name),
+			Status:         strings.TrimSpace(t.Status),
+			Progress:       t.Progress,
+			ConfigID:       t.Config.ID,
+			ConfigName:     strings.TrimSpace(t.Config.Name),
+			TargetID:       t.Target.ID,
+			TargetName:     strings.TrimSpace(t.Target.Name),
+			LastReportID:   strings.TrimSpace(t.LastReport.ID),
+			LastReportTime: strings.TrimSpace(t.LastReport.Time),
+			Created:        strings.TrimSpace(t.CreationTime),
+		})
 	}
+	return tasks, nil
+}
 
-	payload := createTaskXML{
-		Name: name,
-		Config: openVASTaskConfigXML{
-			ID: configID,
-		},
-		Target: openVASTaskTargetXML{
-			ID: targetID,
-		},
+// DeleteTask deletes a task by ID, along with its reports, and returns the
+// raw XML response from gvmd. Without ultimate the task goes to the
+// trashcan. A running task is stopped by gvmd before it is removed; callers
+// decide whether that is acceptable.
+func (s *OpenVASService) DeleteTask(ctx context.Context, taskID string, ultimate bool) (string, error) {
+	taskID = strings.TrimSpace(taskID)
+	if taskID == "" {
+		return "", fmt.Errorf("taskID is required")
 	}
-
-	xmlBody, err := xml.Marshal(&payload)
+	out, err := s.runGMP(ctx, fmt.Sprintf("<delete_task task_id='%s' ultimate='%d'/>", taskID, boolFlag(ultimate)))
 	if err != nil {
-		return "", false, fmt.Errorf("failed to marshal create_task XML: %w", err)
+		return "", err
 	}
+	return string(out), nil
+}
 
-	createArgs := []string{
-		"exec",
-		"-u", "gvm",
-		s.ContainerName,
-		"gvm-cli",
-		"--gmp-username", s.Username,
-		"--gmp-password", s.Password,
-		"tls",
-		"--hostname", s.Host,
-		"--port", s.Port,
-		"--xml", string(xmlBody),
-	}
+// TaskChanges lists what ModifyTask changes; empty fields are left alone.
+// AlertIDs replaces the task's alerts when set; an empty list removes them
+// all. Preferences are scanner preferences by name, e.g. "max_checks".
+type TaskChanges struct {
+	Name        string            `json:"name,omitempty"`
+	ConfigID    string            `json:"config_id,omitempty"`
+	TargetID    string            `json:"target_id,omitempty"`
+	ScheduleID  string            `json:"schedule_id,omitempty"`
+	AlertIDs    *[]string         `json:"alert_ids,omitempty"`
+	Preferences map[string]string `json:"preferences,omitempty"`
+}
 
-	createCmd := exec.CommandContext(ctx, "docker", createArgs...)
-	createOut, createErr := createCmd.CombinedOutput()
-	if createErr != nil {
-		return "", false, fmt.Errorf("gvm-cli create_task failed: %w; output: %s", createErr, string(createOut))
+// ModifyTask changes an existing task via <modify_task> and returns the raw
+// XML response from gvmd. gvmd only changes the scan config or target of a
+// task that has never run.
+func (s *OpenVASService) ModifyTask(ctx context.Context, taskID string, changes TaskChanges) (string, error) {
+	taskID = strings.TrimSpace(taskID)
+	if taskID == "" {
+		return "", fmt.Errorf("taskID is required")
 	}
 
-	type createTaskResponseXML struct {
-		XMLName xml.Name `xml:"create_task_response"`
-		ID      string   `xml:"id,attr"`
+	type idXML struct {
+		ID string `xml:"id,attr"`
+	}
+	type preferenceXML struct {
+		Name  string `xml:"scanner_name"`
+		Value string `xml:"value"`
+	}
+	type modifyTaskXML struct {
+		XMLName     xml.Name        `xml:"modify_task"`
+		TaskID      string          `xml:"task_id,attr"`
+		Name        string          `xml:"name,omitempty"`
+		Config      *idXML          `xml:"config"`
+		Target      *idXML          `xml:"target"`
+		Schedule    *idXML          `xml:"schedule"`
+		Alerts      []idXML         `xml:"alert"`
+		Preferences []preferenceXML `xml:"preferences>preference"`
 	}
 
-	var resp createTaskResponseXML
-	if err := xml.Unmarshal(createOut, &resp); err != nil {
-		return "", false, fmt.Errorf("failed to parse create_task_response XML: %w; output: %s", err, string(createOut))
+	payload := modifyTaskXML{TaskID: taskID, Name: strings.TrimSpace(changes.Name)}
+	if id := strings.TrimSpace(changes.ConfigID); id != "" {
+		payload.Config = &idXML{ID: id}
 	}
-	if strings.TrimSpace(resp.ID) == "" {
-		return "", false, fmt.Errorf("empty task id in create_task_response; output: %s", string(createOut))
+	if id := strings.TrimSpace(changes.TargetID); id != "" {
+		payload.Target = &idXML{ID: id}
+	}
+	if id := strings.TrimSpace(changes.ScheduleID); id != "" {
+		payload.Schedule = &idXML{ID: id}
+	}
+	if changes.AlertIDs != nil {
+		for _, id := range *changes.AlertIDs {
+			if id = strings.TrimSpace(id); id != "" {
+				payload.Alerts = append(payload.Alerts, idXML{ID: id})
+			}
+		}
+		if len(payload.Alerts) == 0 {
+			// gvmd's way of saying "no alerts".
+			payload.Alerts = []idXML{{ID: "0"}}
+		}
+	}
+	for name, value := range changes.Preferences {
+		payload.Preferences = append(payload.Preferences, preferenceXML{Name: name, Value: value})
 	}
 
-	return strings.TrimSpace(resp.ID), false, nil
+	xmlBody, err := xml.Marshal(&payload)
+	if err != nil {
+		return "", fmt.Errorf("failed to marshal modify_task XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
 }
 
 // StartTask starts an existing OpenVAS/GVM task by ID and returns the raw XML
@@ -412,6 +2253,36 @@ func (s *OpenVASService) StartTask(ctx context.Context, taskID string) (string,
 	return string(out), nil
 }
 
+// StopTask stops a running OpenVAS/GVM task by ID and returns the raw XML
+// response from gvmd. The task keeps the partial report of the stopped scan
+// and can be resumed later.
+func (s *OpenVASService) StopTask(ctx context.Context, taskID string) (string, error) {
+	taskID = strings.TrimSpace(taskID)
+	if taskID == "" {
+		return "", fmt.Errorf("taskID is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<stop_task task_id='%s'/>", taskID))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// ResumeTask resumes a stopped or interrupted OpenVAS/GVM task by ID and
+// returns the raw XML response from gvmd. The scan continues where it left
+// off, in the task's last report.
+func (s *OpenVASService) ResumeTask(ctx context.Context, taskID string) (string, error) {
+	taskID = strings.TrimSpace(taskID)
+	if taskID == "" {
+		return "", fmt.Errorf("taskID is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<resume_task task_id='%s'/>", taskID))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
 // GetTaskStatus fetches the current status/details for an existing OpenVAS/GVM
 // task by ID using <get_tasks task_id='...' details='1'/> and returns the raw
 // XML response from gvmd.
@@ -449,10 +2320,36 @@ func (s *OpenVASService) GetTaskStatus(ctx context.Context, taskID string) (stri
 	return string(out), nil
 }
 
+// ReportFilter narrows the results of a report; zero values keep every
+// result (subject to gvmd's default QoD filter).
+type ReportFilter struct {
+	MinSeverity float64
+	MinQoD      int
+}
+
+// gvmFilter renders f as a gvmd filter string, or "" when f keeps
+// everything.
+func (f ReportFilter) gvmFilter() string {
+	var terms []string
+	if f.MinSeverity > 0 {
+		// gvmd filters have no >=; CVSS scores have one decimal.
+		terms = append(terms, fmt.Sprintf("severity>%.2f", f.MinSeverity-0.05))
+	}
+	if f.MinQoD > 0 {
+		terms = append(terms, fmt.Sprintf("min_qod=%d", f.MinQoD))
+	}
+	if len(terms) == 0 {
+		return ""
+	}
+	return strings.Join(append([]string{"apply_overrides=0", "rows=-1"}, terms...), " ")
+}
+
 // GetReport fetches the final report for a given report ID using
 // <get_reports report_id='...' details='1'/> and returns the raw XML
-// response from gvmd.
-func (s *OpenVASService) GetReport(ctx context.Context, reportID string) (string, error) {
+// response from gvmd. A non-empty formatID selects a report format other
+// than gvmd's default XML; see ParseReportContent for reading its output.
+// filter drops results from the report in any format.
+func (s *OpenVASService) GetReport(ctx context.Context, reportID, formatID string, filter ReportFilter) (string, error) {
 	if s.Password == "" {
 		return "", fmt.Errorf("GVM_PASSWORD is not set")
 	}
@@ -462,7 +2359,14 @@ func (s *OpenVASService) GetReport(ctx context.Context, reportID string) (string
 		return "", fmt.Errorf("reportID is required")
 	}
 
-	xmlBody := fmt.Sprintf("<get_reports report_id='%s' details='1'/>", reportID)
+	attrs := fmt.Sprintf("report_id='%s' details='1'", reportID)
+	if formatID = strings.TrimSpace(formatID); formatID != "" {
+		attrs += fmt.Sprintf(" format_id='%s' ignore_pagination='1'", formatID)
+	}
+	if f := filter.gvmFilter(); f != "" {
+		attrs += fmt.Sprintf(" filter='%s'", f)
+	}
+	xmlBody := "<get_reports " + attrs + "/>"
 
 	args := []string{
 		"exec",
@@ -485,3 +2389,562 @@ func (s *OpenVASService) GetReport(ctx context.Context, reportID string) (string
 
 	return string(out), nil
 }
+
+// internal XML structs for the fields of <get_reports_response> that
+// ListReports reports; the report element is nested inside the report
+// wrapper.
+type reportSummaryXML struct {
+	ID   string `xml:"id,attr"`
+	Task struct {
+		ID   string `xml:"id,attr"`
+		Name string `xml:"name"`
+	} `xml:"task"`
+	Report struct {
+		ScanRunStatus string `xml:"scan_run_status"`
+		ScanStart     string `xml:"scan_start"`
+		ScanEnd       string `xml:"scan_end"`
+		Severity      string `xml:"severity>full"`
+		ResultCount   struct {
+			High          int `xml:"high>full"`
+			Medium        int `xml:"medium>full"`
+			Low           int `xml:"low>full"`
+			Log           int `xml:"log>full"`
+			FalsePositive int `xml:"false_positive>full"`
+		} `xml:"result_count"`
+	} `xml:"report"`
+}
+
+type reportSummariesXML struct {
+	Reports []reportSummaryXML `xml:"report"`
+}
+
+// SeverityCounts counts a report's results by threat level.
+type SeverityCounts struct {
+	High          int `json:"high"`
+	Medium        int `json:"medium"`
+	Low           int `json:"low"`
+	Log           int `json:"log"`
+	FalsePositive int `json:"false_positive"`
+}
+
+// ReportSummary is one report, without its results.
+type ReportSummary struct {
+	ID       string         `json:"id"`
+	TaskID   string         `json:"task_id"`
+	TaskName string         `json:"task_name,omitempty"`
+	Status   string         `json:"status"`
+	Started  string         `json:"started,omitempty"`
+	Finished string         `json:"finished,omitempty"`
+	Severity float64        `json:"severity"`
+	Counts   SeverityCounts `json:"counts"`
+}
+
+// ListReports returns the reports of one task, or of every task when taskID
+// is empty, newest first.
+func (s *OpenVASService) ListReports(ctx context.Context, taskID string) ([]ReportSummary, error) {
+	filter := "rows=-1 sort-reverse=date"
+	if taskID = strings.TrimSpace(taskID); taskID != "" {
+		filter += " task_id=" + taskID
+	}
+	type getReportsXML struct {
+		XMLName          xml.Name `xml:"get_reports"`
+		Filter           string   `xml:"filter,attr"`
+		IgnorePagination int      `xml:"ignore_pagination,attr"`
+		Details          int      `xml:"details,attr"`
+	}
+	xmlBody, err := xml.Marshal(&getReportsXML{Filter: filter, IgnorePagination: 1})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal get_reports XML: %w", err)
+	}
+	out, err := s.runGMP(ctx, string(xmlBody))
+	if err != nil {
+		return nil, err
+	}
+	var parsed reportSummariesXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_reports response XML: %w", err)
+	}
+	reports := make([]ReportSummary, 0, len(parsed.Reports))
+	for _, r := range parsed.Reports {
+		var severity float64
+		fmt.Sscanf(strings.TrimSpace(r.Report.Severity), "%g", &severity)
+		counts := r.Report.ResultCount
+		reports = append(reports, ReportSummary{
+			ID:       r.ID,
+			TaskID:   r.Task.ID,
+			TaskName: strings.TrimSpace(r.Task.Name),
+			Status:   strings.TrimSpace(r.Report.ScanRunStatus),
+			Started:  strings.TrimSpace(r.Report.ScanStart),
+			Finished: strings.TrimSpace(r.Report.ScanEnd),
+			Severity: severity,
+			Counts: SeverityCounts{
+				High:          counts.High,
+				Medium:        counts.Medium,
+				Low:           counts.Low,
+				Log:           counts.Log,
+				FalsePositive: counts.FalsePositive,
+			},
+		})
+	}
+	return reports, nil
+}
+
+// DeleteReport deletes a report by ID and returns the raw XML response from
+// gvmd. Reports have no trashcan, so this cannot be undone; gvmd refuses to
+// delete the report of a task that is still running.
+func (s *OpenVASService) DeleteReport(ctx context.Context, reportID string) (string, error) {
+	reportID = strings.TrimSpace(reportID)
+	if reportID == "" {
+		return "", fmt.Errorf("reportID is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<delete_report report_id='%s'/>", reportID))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// internal XML structs for the fields of <get_tasks_response> that callers
+// poll on.
+type taskStateXML struct {
+	Task struct {
+		Status       string `xml:"status"`
+		Progress     int    `xml:"progress"`
+		LastReportID string `xml:"last_report>report>id,attr"`
+	} `xml:"task"`
+}
+
+// TaskState is the parsed status of one task.
+type TaskState struct {
+	Status       string `json:"status"`
+	Progress     int    `json:"progress"`
+	LastReportID string `json:"last_report_id,omitempty"`
+}
+
+// ParseTaskState extracts status, progress and last report ID from a raw
+// <get_tasks_response>.
+func ParseTaskState(raw string) (*TaskState, error) {
+	var parsed taskStateXML
+	if err := xml.Unmarshal([]byte(raw), &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_tasks response XML: %w", err)
+	}
+	return &TaskState{
+		Status:       strings.TrimSpace(parsed.Task.Status),
+		Progress:     parsed.Task.Progress,
+		LastReportID: strings.TrimSpace(parsed.Task.LastReportID),
+	}, nil
+}
+
+// internal XML structs for the results of a <get_reports_response>; the
+// report element is nested inside the report wrapper.
+type reportResultXML struct {
+	ID   string `xml:"id,attr"`
+	Name string `xml:"name"`
+	Host string `xml:"host"`
+	Port string `xml:"port"`
+	NVT  struct {
+		OID  string `xml:"oid,attr"`
+		Refs []struct {
+			Type string `xml:"type,attr"`
+			ID   string `xml:"id,attr"`
+		} `xml:"refs>ref"`
+		Solution string `xml:"solution"`
+	} `xml:"nvt"`
+	Severity string `xml:"severity"`
+	Threat   string `xml:"threat"`
+	QoD      string `xml:"qod>value"`
+}
+
+type reportResultsXML struct {
+	Results []reportResultXML `xml:"report>report>results>result"`
+}
+
+// ReportResult is one finding of a report.
+type ReportResult struct {
+	ID       string   `json:"id"`
+	Name     string   `json:"name"`
+	Host     string   `json:"host"`
+	Port     string   `json:"port"`
+	NVTOID   string   `json:"nvt_oid"`
+	Severity float64  `json:"severity"`
+	Threat   string   `json:"threat"`
+	QoD      int      `json:"qod"`
+	CVEs     []string `json:"cves,omitempty"`
+	Solution string   `json:"solution,omitempty"`
+}
+
+// result converts one parsed <result> element.
+func (r reportResultXML) result() ReportResult {
+	var severity float64
+	fmt.Sscanf(strings.TrimSpace(r.Severity), "%g", &severity)
+	var qod int
+	fmt.Sscanf(strings.TrimSpace(r.QoD), "%d", &qod)
+	var cves []string
+	for _, ref := range r.NVT.Refs {
+		if ref.Type == "cve" {
+			cves = append(cves, ref.ID)
+		}
+	}
+	return ReportResult{
+		ID:       r.ID,
+		Name:     strings.TrimSpace(r.Name),
+		Host:     strings.TrimSpace(r.Host),
+		Port:     strings.TrimSpace(r.Port),
+		NVTOID:   r.NVT.OID,
+		Severity: severity,
+		Threat:   strings.TrimSpace(r.Threat),
+		QoD:      qod,
+		CVEs:     cves,
+		Solution: strings.TrimSpace(r.NVT.Solution),
+	}
+}
+
+// ParseReportResults extracts the findings from a raw <get_reports_response>.
+func ParseReportResults(raw string) ([]ReportResult, error) {
+	var parsed reportResultsXML
+	if err := xml.Unmarshal([]byte(raw), &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse get_reports response XML: %w", err)
+	}
+	results := make([]ReportResult, 0, len(parsed.Results))
+	for _, r := range parsed.Results {
+		results = append(results, r.result())
+	}
+	return results, nil
+}
+
+// ResultPage is one page of <get_results>: the results on it and how many
+// match the filter across all pages.
+type ResultPage struct {
+	Results  []ReportResult `json:"results"`
+	First    int            `json:"first"`
+	Rows     int            `json:"rows"`
+	Filtered int            `json:"filtered"`
+}
+
+// internal XML struct for a <get_results_response>; results sit directly
+// under the root, with the same shape as in a report.
+type resultPageXML struct {
+	Results  []reportResultXML `xml:"result"`
+	Filtered string            `xml:"result_count>filtered"`
+}
+
+// GetResults fetches one page of results across every report via
+// <get_results>. filter is a gvmd filter string such as
+// "severity>6.9 and host=10.0.0.5"; first is the 1-based index of the
+// first result and rows how many to return, so large result sets can be
+// read incrementally instead of as one report.
+func (s *OpenVASService) GetResults(ctx context.Context, filter string, first, rows int) (ResultPage, error) {
+	var attr strings.Builder
+	full := fmt.Sprintf("%s apply_overrides=0 first=%d rows=%d", strings.TrimSpace(filter), first, rows)
+	if err := xml.EscapeText(&attr, []byte(strings.TrimSpace(full))); err != nil {
+		return ResultPage{}, err
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<get_results details='1' filter='%s'/>", attr.String()))
+	if err != nil {
+		return ResultPage{}, err
+	}
+	var parsed resultPageXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return ResultPage{}, fmt.Errorf("failed to parse get_results response XML: %w", err)
+	}
+	page := ResultPage{Results: make([]ReportResult, 0, len(parsed.Results)), First: first, Rows: rows}
+	for _, r := range parsed.Results {
+		page.Results = append(page.Results, r.result())
+	}
+	page.Filtered, _ = strconv.Atoi(strings.TrimSpace(parsed.Filtered))
+	return page, nil
+}
+
+// internal XML struct for the outer report element of a
+// <get_reports_response>: XML formats nest the report inside it, every
+// other format puts its output there base64-encoded.
+type reportContentXML struct {
+	Report struct {
+		ContentType string `xml:"content_type,attr"`
+		Extension   string `xml:"extension,attr"`
+		Data        string `xml:",chardata"`
+	} `xml:"report"`
+}
+
+// ReportContent is a report in a format other than XML.
+type ReportContent struct {
+	ContentType string
+	Extension   string
+	// Base64 is the output as gvmd sent it, base64-encoded.
+	Base64 string
+}
+
+// IsXML reports whether the format is one of gvmd's XML formats, whose
+// output is the raw response itself.
+func (c ReportContent) IsXML() bool {
+	return c.ContentType == "" || strings.HasSuffix(c.ContentType, "/xml")
+}
+
+// IsText reports whether the output is text (CSV, TXT, ...) that can be
+// returned decoded.
+func (c ReportContent) IsText() bool {
+	return strings.HasPrefix(c.ContentType, "text/")
+}
+
+// ParseReportContent reads the content type, extension and base64 output
+// of the report in a raw <get_reports_response>.
+func ParseReportContent(raw string) (ReportContent, error) {
+	var parsed reportContentXML
+	if err := xml.Unmarshal([]byte(raw), &parsed); err != nil {
+		return ReportContent{}, fmt.Errorf("failed to parse get_reports response XML: %w", err)
+	}
+	return ReportContent{
+		ContentType: strings.TrimSpace(parsed.Report.ContentType),
+		Extension:   strings.TrimSpace(parsed.Report.Extension),
+		Base64:      strings.Join(strings.Fields(parsed.Report.Data), ""),
+	}, nil
+}
+
+// runGMP sends one GMP command through gvm-cli and returns gvmd's raw XML
+// response.
+func (s *OpenVASService) runGMP(ctx context.Context, xmlBody string) ([]byte, error) {
+	return s.runGMPAs(ctx, xmlBody, xmlBody)
+}
+
+// runGMPAs is runGMP for commands that carry secrets: errors name the
+// command as label instead of quoting xmlBody.
+func (s *OpenVASService) runGMPAs(ctx context.Context, xmlBody, label string) ([]byte, error) {
+	if s.Password == "" {
+		return nil, fmt.Errorf("GVM_PASSWORD is not set")
+	}
+
+	args := []string{
+		"exec",
+		"-u", "gvm",
+		s.ContainerName,
+		"gvm-cli",
+		"--gmp-username", s.Username,
+		"--gmp-password", s.Password,
+		"tls",
+		"--hostname", s.Host,
+		"--port", s.Port,
+		"--xml", xmlBody,
+	}
+
+	cmd := exec.CommandContext(ctx, "docker", args...)
+	out, err := cmd.CombinedOutput()
+	if err != nil {
+		return nil, fmt.Errorf("gvm-cli %s failed: %w; output: %s", label, err, string(out))
+	}
+	return out, nil
+}
+
+// internal XML structs for housekeeping; tasks and targets share the fields
+// cleanup needs.
+type gmpObjectXML struct {
+	ID           string `xml:"id,attr"`
+	Name         string `xml:"name"`
+	CreationTime string `xml:"creation_time"`
+	Status       string `xml:"status"`
+}
+
+type gmpObjectsXML struct {
+	Tasks   []gmpObjectXML `xml:"task"`
+	Targets []gmpObjectXML `xml:"target"`
+}
+
+// CleanupObject is one task or target selected for deletion.
+type CleanupObject struct {
+	Type    string `json:"type"`
+	ID      string `json:"id"`
+	Name    string `json:"name"`
+	Created string `json:"created"`
+	Deleted bool   `json:"deleted"`
+	Error   string `json:"error,omitempty"`
+}
+
+// CleanupResult reports what Cleanup removed, or would remove when not
+// applied.
+type CleanupResult struct {
+	Applied         bool            `json:"applied"`
+	Objects         []CleanupObject `json:"objects"`
+	DeletedTasks    int             `json:"deleted_tasks"`
+	DeletedTargets  int             `json:"deleted_targets"`
+	TrashTasks      int             `json:"trash_tasks"`
+	TrashTargets    int             `json:"trash_targets"`
+	TrashcanEmptied bool            `json:"trashcan_emptied"`
+}
+
+// busyTaskStatuses are task states that must not be deleted from under gvmd.
+var busyTaskStatuses = map[string]bool{
+	"Running":          true,
+	"Requested":        true,
+	"Queued":           true,
+	"Stop Requested":   true,
+	"Delete Requested": true,
+}
+
+// Cleanup deletes tasks and then targets whose names match namePattern (a
+// path.Match glob such as "hacker_agent-*") and that were created more than
+// retention ago, skipping tasks that are still running. With emptyTrashcan
+// the objects are deleted outright and the trashcan is emptied afterwards.
+// Unless apply is set nothing is changed and the candidates are only listed.
+func (s *OpenVASService) Cleanup(ctx context.Context, namePattern string, retention time.Duration, emptyTrashcan, apply bool) (*CleanupResult, error) {
+	namePattern = strings.TrimSpace(namePattern)
+	if namePattern == "" {
+		return nil, fmt.Errorf("namePattern is required")
+	}
+	if _, err := path.Match(namePattern, ""); err != nil {
+		return nil, fmt.Errorf("invalid name pattern %q: %w", namePattern, err)
+	}
+
+	cutoff := time.Now().Add(-retention)
+	expired := func(o gmpObjectXML) bool {
+		if ok, _ := path.Match(namePattern, strings.TrimSpace(o.Name)); !ok {
+			return false
+		}
+		created, err := time.Parse(time.RFC3339, strings.TrimSpace(o.CreationTime))
+		return err == nil && created.Before(cutoff)
+	}
+	ultimate := boolFlag(emptyTrashcan)
+
+	result := &CleanupResult{Applied: apply, Objects: []CleanupObject{}}
+
+	// Tasks first: targets referenced by a task cannot be deleted.
+	tasks, err := s.listObjects(ctx, "<get_tasks filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	for _, t := range tasks.Tasks {
+		if !expired(t) || busyTaskStatuses[strings.TrimSpace(t.Status)] {
+			continue
+		}
+		obj := CleanupObject{Type: "task", ID: t.ID, Name: t.Name, Created: t.CreationTime}
+		if apply {
+			if _, err := s.runGMP(ctx, fmt.Sprintf("<delete_task task_id='%s' ultimate='%d'/>", t.ID, ultimate)); err != nil {
+				obj.Error = err.Error()
+			} else {
+				obj.Deleted = true
+				result.DeletedTasks++
+			}
+		}
+		result.Objects = append(result.Objects, obj)
+	}
+
+	targets, err := s.listObjects(ctx, "<get_targets filter='rows=-1'/>")
+	if err != nil {
+		return nil, err
+	}
+	for _, t := range targets.Targets {
+		if !expired(t) {
+			continue
+		}
+		obj := CleanupObject{Type: "target", ID: t.ID, Name: t.Name, Created: t.CreationTime}
+		if apply {
+			if _, err := s.runGMP(ctx, fmt.Sprintf("<delete_target target_id='%s' ultimate='%d'/>", t.ID, ultimate)); err != nil {
+				obj.Error = err.Error()
+			} else {
+				obj.Deleted = true
+				result.DeletedTargets++
+			}
+		}
+		result.Objects = append(result.Objects, obj)
+	}
+
+	if !emptyTrashcan {
+		return result, nil
+	}
+
+	// Count what the trashcan holds before emptying it so the caller sees
+	// how much was reclaimed.
+	if trashTasks, err := s.listObjects(ctx, "<get_tasks trash='1' filter='rows=-1'/>"); err == nil {
+		result.TrashTasks = len(trashTasks.Tasks)
+	}
+	if trashTargets, err := s.listObjects(ctx, "<get_targets trash='1' filter='rows=-1'/>"); err == nil {
+		result.TrashTargets = len(trashTargets.Targets)
+	}
+	if apply {
+		if _, err := s.runGMP(ctx, "<empty_trashcan/>"); err != nil {
+			return nil, err
+		}
+		result.TrashcanEmptied = true
+	}
+
+	return result, nil
+}
+
+func (s *OpenVASService) listObjects(ctx context.Context, xmlBody string) (*gmpObjectsXML, error) {
+	out, err := s.runGMP(ctx, xmlBody)
+	if err != nil {
+		return nil, err
+	}
+	var parsed gmpObjectsXML
+	if err := xml.Unmarshal(out, &parsed); err != nil {
+		return nil, fmt.Errorf("failed to parse %s response XML: %w", xmlBody, err)
+	}
+	return &parsed, nil
+}
+
+// trashTypes are the gvmd object types the agent creates, and so the ones
+// that end up in the trashcan when deleted without ultimate.
+var trashTypes = []string{"task", "target", "config", "schedule", "alert", "credential", "port_list"}
+
+// internal XML struct for a <get_*s trash='1'> response; besides the objects
+// the root holds filter and count elements, told apart by XMLName.
+type trashXML struct {
+	Objects []struct {
+		XMLName xml.Name
+		ID      string `xml:"id,attr"`
+		Name    string `xml:"name"`
+	} `xml:",any"`
+}
+
+// TrashObject is one object in the trashcan.
+type TrashObject struct {
+	Type string `json:"type"`
+	ID   string `json:"id"`
+	Name string `json:"name"`
+}
+
+// ListTrash lists the objects of every type in trashTypes that sit in the
+// trashcan, tasks first.
+func (s *OpenVASService) ListTrash(ctx context.Context) ([]TrashObject, error) {
+	objects := []TrashObject{}
+	for _, typ := range trashTypes {
+		out, err := s.runGMP(ctx, fmt.Sprintf("<get_%ss trash='1' filter='rows=-1'/>", typ))
+		if err != nil {
+			return nil, err
+		}
+		var parsed trashXML
+		if err := xml.Unmarshal(out, &parsed); err != nil {
+			return nil, fmt.Errorf("failed to parse get_%ss response XML: %w", typ, err)
+		}
+		for _, o := range parsed.Objects {
+			if o.XMLName.Local != typ || o.ID == "" {
+				continue
+			}
+			objects = append(objects, TrashObject{Type: typ, ID: o.ID, Name: strings.TrimSpace(o.Name)})
+		}
+	}
+	return objects, nil
+}
+
+// EmptyTrashcan deletes everything in the trashcan for good and returns the
+// raw XML response from gvmd.
+func (s *OpenVASService) EmptyTrashcan(ctx context.Context) (string, error) {
+	out, err := s.runGMP(ctx, "<empty_trashcan/>")
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}
+
+// RestoreObject moves an object of any type out of the trashcan and returns
+// the raw XML response from gvmd. gvmd refuses to restore an object whose
+// dependencies, such as a task's target, are still in the trashcan.
+func (s *OpenVASService) RestoreObject(ctx context.Context, id string) (string, error) {
+	id = strings.TrimSpace(id)
+	if id == "" {
+		return "", fmt.Errorf("id is required")
+	}
+	out, err := s.runGMP(ctx, fmt.Sprintf("<restore id='%s'/>", id))
+	if err != nil {
+		return "", err
+	}
+	return string(out), nil
+}